@@ -8,6 +8,7 @@ use lazytail::filter::string_filter::StringFilter;
 use lazytail::filter::Filter;
 use lazytail::index::builder::IndexBuilder;
 use lazytail::index::reader::IndexReader;
+use lazytail::reader::encoding::Encoding;
 use std::io::Write;
 use std::path::Path;
 use std::sync::Arc;
@@ -218,8 +219,13 @@ fn make_cases(test_file: &Path) -> Vec<FilterBenchCase> {
         name: "string/case_insensitive",
         run: Box::new(move |_| {
             let filter: Arc<dyn Filter> = Arc::new(StringFilter::new("connection refused", false));
-            let rx = streaming_filter::run_streaming_filter(p.clone(), filter, CancelToken::new())
-                .unwrap();
+            let rx = streaming_filter::run_streaming_filter(
+                p.clone(),
+                filter,
+                Encoding::Utf8,
+                CancelToken::new(),
+            )
+            .unwrap();
             collect_matches(rx)
         }),
     };
@@ -229,8 +235,13 @@ fn make_cases(test_file: &Path) -> Vec<FilterBenchCase> {
         name: "string/case_sensitive",
         run: Box::new(move |_| {
             let filter: Arc<dyn Filter> = Arc::new(StringFilter::new("connection refused", true));
-            let rx = streaming_filter::run_streaming_filter(p.clone(), filter, CancelToken::new())
-                .unwrap();
+            let rx = streaming_filter::run_streaming_filter(
+                p.clone(),
+                filter,
+                Encoding::Utf8,
+                CancelToken::new(),
+            )
+            .unwrap();
             collect_matches(rx)
         }),
     };
@@ -255,8 +266,13 @@ fn make_cases(test_file: &Path) -> Vec<FilterBenchCase> {
         name: "string/rare_pattern",
         run: Box::new(move |_| {
             let filter: Arc<dyn Filter> = Arc::new(StringFilter::new("TLS handshake", false));
-            let rx = streaming_filter::run_streaming_filter(p.clone(), filter, CancelToken::new())
-                .unwrap();
+            let rx = streaming_filter::run_streaming_filter(
+                p.clone(),
+                filter,
+                Encoding::Utf8,
+                CancelToken::new(),
+            )
+            .unwrap();
             collect_matches(rx)
         }),
     };
@@ -266,8 +282,13 @@ fn make_cases(test_file: &Path) -> Vec<FilterBenchCase> {
         name: "regex/simple",
         run: Box::new(move |_| {
             let filter: Arc<dyn Filter> = Arc::new(RegexFilter::new(r"error|warn", false).unwrap());
-            let rx = streaming_filter::run_streaming_filter(p.clone(), filter, CancelToken::new())
-                .unwrap();
+            let rx = streaming_filter::run_streaming_filter(
+                p.clone(),
+                filter,
+                Encoding::Utf8,
+                CancelToken::new(),
+            )
+            .unwrap();
             collect_matches(rx)
         }),
     };
@@ -283,8 +304,13 @@ fn make_cases(test_file: &Path) -> Vec<FilterBenchCase> {
                 )
                 .unwrap(),
             );
-            let rx = streaming_filter::run_streaming_filter(p.clone(), filter, CancelToken::new())
-                .unwrap();
+            let rx = streaming_filter::run_streaming_filter(
+                p.clone(),
+                filter,
+                Encoding::Utf8,
+                CancelToken::new(),
+            )
+            .unwrap();
             collect_matches(rx)
         }),
     };
@@ -295,8 +321,13 @@ fn make_cases(test_file: &Path) -> Vec<FilterBenchCase> {
         run: Box::new(move |_| {
             let fq = query::parse_query(r#"json | level == "error""#).unwrap();
             let filter: Arc<dyn Filter> = Arc::new(QueryFilter::new(fq).unwrap());
-            let rx = streaming_filter::run_streaming_filter(p.clone(), filter, CancelToken::new())
-                .unwrap();
+            let rx = streaming_filter::run_streaming_filter(
+                p.clone(),
+                filter,
+                Encoding::Utf8,
+                CancelToken::new(),
+            )
+            .unwrap();
             collect_matches(rx)
         }),
     };
@@ -307,8 +338,13 @@ fn make_cases(test_file: &Path) -> Vec<FilterBenchCase> {
         run: Box::new(move |_| {
             let fq = query::parse_query("logfmt | level == error").unwrap();
             let filter: Arc<dyn Filter> = Arc::new(QueryFilter::new(fq).unwrap());
-            let rx = streaming_filter::run_streaming_filter(p.clone(), filter, CancelToken::new())
-                .unwrap();
+            let rx = streaming_filter::run_streaming_filter(
+                p.clone(),
+                filter,
+                Encoding::Utf8,
+                CancelToken::new(),
+            )
+            .unwrap();
             collect_matches(rx)
         }),
     };
@@ -319,8 +355,13 @@ fn make_cases(test_file: &Path) -> Vec<FilterBenchCase> {
         run: Box::new(move |_| {
             let fq = query::parse_query(r#"json | level == "error" | service == "api""#).unwrap();
             let filter: Arc<dyn Filter> = Arc::new(QueryFilter::new(fq).unwrap());
-            let rx = streaming_filter::run_streaming_filter(p.clone(), filter, CancelToken::new())
-                .unwrap();
+            let rx = streaming_filter::run_streaming_filter(
+                p.clone(),
+                filter,
+                Encoding::Utf8,
+                CancelToken::new(),
+            )
+            .unwrap();
             collect_matches(rx)
         }),
     };
@@ -338,6 +379,7 @@ fn make_cases(test_file: &Path) -> Vec<FilterBenchCase> {
             let rx = streaming_filter::run_streaming_filter_indexed(
                 p.clone(),
                 filter,
+                Encoding::Utf8,
                 bitmap,
                 CancelToken::new(),
             )
@@ -358,6 +400,7 @@ fn make_cases(test_file: &Path) -> Vec<FilterBenchCase> {
             let rx = streaming_filter::run_streaming_filter_indexed(
                 p.clone(),
                 filter,
+                Encoding::Utf8,
                 bitmap,
                 CancelToken::new(),
             )
@@ -378,6 +421,7 @@ fn make_cases(test_file: &Path) -> Vec<FilterBenchCase> {
             let rx = streaming_filter::run_streaming_filter_indexed(
                 p.clone(),
                 filter,
+                Encoding::Utf8,
                 bitmap,
                 CancelToken::new(),
             )
@@ -392,8 +436,17 @@ fn make_cases(test_file: &Path) -> Vec<FilterBenchCase> {
         name: "engine/string_dispatch",
         run: Box::new(move |_| {
             let filter: Arc<dyn Filter> = Arc::new(StringFilter::new("connection refused", false));
-            let rx = SearchEngine::search_file(&p, filter, None, None, None, CancelToken::new())
-                .unwrap();
+            let rx = SearchEngine::search_file(
+                &p,
+                filter,
+                Encoding::Utf8,
+                None,
+                None,
+                None,
+                None,
+                CancelToken::new(),
+            )
+            .unwrap();
             collect_matches(rx)
         }),
     };
@@ -408,9 +461,11 @@ fn make_cases(test_file: &Path) -> Vec<FilterBenchCase> {
             let rx = SearchEngine::search_file(
                 &p,
                 filter,
+                Encoding::Utf8,
                 Some(&fq),
                 reader.as_ref(),
                 None,
+                None,
                 CancelToken::new(),
             )
             .unwrap();
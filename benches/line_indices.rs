@@ -0,0 +1,203 @@
+//! Memory held by a tab's line indices for a huge file.
+//!
+//! Compares `LineIndices` with the `Vec<usize>` it replaced. The indices
+//! only depend on the line count, so lines are counted synthetically rather
+//! than read from a generated file.
+//!
+//! Usage: cargo bench --bench line_indices -- [--lines=N] [--json]
+
+#[allow(dead_code, unused_imports)]
+#[path = "../src/app/line_indices.rs"]
+mod line_indices;
+
+use line_indices::LineIndices;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+// ---------------------------------------------------------------------------
+// Config
+// ---------------------------------------------------------------------------
+
+const DEFAULT_LINES: usize = 100_000_000;
+/// Lines appended per batch when following a growing file
+const FOLLOW_BATCH: usize = 1_000;
+/// One line in this many matches the filtered case
+const FILTER_EVERY: usize = 100;
+
+// ---------------------------------------------------------------------------
+// Allocation tracking
+// ---------------------------------------------------------------------------
+
+/// System allocator that tracks live and peak heap bytes.
+struct Counting;
+
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let live = LIVE.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(live, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Heap use of one case: what it keeps and the most it held at once.
+struct Usage {
+    retained: usize,
+    peak: usize,
+    elapsed: Duration,
+}
+
+/// Run `build`, keeping its result alive while measuring.
+fn measure<T>(build: impl FnOnce() -> T) -> Usage {
+    let base = LIVE.load(Ordering::Relaxed);
+    PEAK.store(base, Ordering::Relaxed);
+    let start = Instant::now();
+    let value = build();
+    let elapsed = start.elapsed();
+    let usage = Usage {
+        retained: LIVE.load(Ordering::Relaxed) - base,
+        peak: PEAK.load(Ordering::Relaxed) - base,
+        elapsed,
+    };
+    drop(value);
+    usage
+}
+
+// ---------------------------------------------------------------------------
+// Formatting
+// ---------------------------------------------------------------------------
+
+fn fmt_dur(d: Duration) -> String {
+    let ms = d.as_secs_f64() * 1000.0;
+    if ms < 1.0 {
+        format!("{:.1} us", ms * 1000.0)
+    } else if ms < 1000.0 {
+        format!("{:.2} ms", ms)
+    } else {
+        format!("{:.2} s", ms / 1000.0)
+    }
+}
+
+fn fmt_size(bytes: usize) -> String {
+    const MB: usize = 1024 * 1024;
+    const KB: usize = 1024;
+    if bytes >= MB {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.1} KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Main
+// ---------------------------------------------------------------------------
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+
+    let lines = args
+        .iter()
+        .find(|a| a.starts_with("--lines="))
+        .and_then(|a| a.strip_prefix("--lines=")?.parse().ok())
+        .unwrap_or(DEFAULT_LINES);
+
+    let json_output = args.iter().any(|a| a == "--json");
+
+    type Case = (&'static str, Box<dyn Fn(usize) -> Usage>);
+    let cases: Vec<Case> = vec![
+        (
+            "all_lines/vec",
+            Box::new(|n| measure(|| (0..n).collect::<Vec<usize>>())),
+        ),
+        (
+            "all_lines/line_indices",
+            Box::new(|n| measure(|| LineIndices::all(n))),
+        ),
+        (
+            "follow/vec",
+            Box::new(|n| {
+                measure(|| {
+                    let mut indices = Vec::new();
+                    for start in (0..n).step_by(FOLLOW_BATCH) {
+                        indices.extend(start..(start + FOLLOW_BATCH).min(n));
+                    }
+                    indices
+                })
+            }),
+        ),
+        (
+            "follow/line_indices",
+            Box::new(|n| {
+                measure(|| {
+                    let mut indices = LineIndices::default();
+                    for start in (0..n).step_by(FOLLOW_BATCH) {
+                        indices.extend_range(start..(start + FOLLOW_BATCH).min(n));
+                    }
+                    indices
+                })
+            }),
+        ),
+        (
+            "filtered/line_indices",
+            Box::new(|n| {
+                measure(|| LineIndices::from((0..n).step_by(FILTER_EVERY).collect::<Vec<_>>()))
+            }),
+        ),
+    ];
+
+    if !json_output {
+        println!();
+        println!("Line Indices Memory Benchmark");
+        println!("=============================");
+        println!("Lines:       {}", lines);
+        println!("Filtered:    1 in {}", FILTER_EVERY);
+        println!();
+    }
+
+    let mut json_results = Vec::new();
+
+    for (name, run) in &cases {
+        eprintln!("  Running {}...", name);
+        let usage = run(lines);
+
+        if json_output {
+            json_results.push(serde_json::json!({
+                "name": name,
+                "lines": lines,
+                "retained_bytes": usage.retained,
+                "peak_bytes": usage.peak,
+                "build_ms": usage.elapsed.as_secs_f64() * 1000.0,
+            }));
+        } else {
+            println!(
+                "  {:<24}  retained: {:>10}  peak: {:>10}  build: {:>10}",
+                name,
+                fmt_size(usage.retained),
+                fmt_size(usage.peak),
+                fmt_dur(usage.elapsed),
+            );
+        }
+    }
+
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&json_results).unwrap());
+    } else {
+        println!();
+    }
+}
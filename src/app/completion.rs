@@ -0,0 +1,189 @@
+//! Tab completion of field names in query input (`json | serv` → `service`).
+//!
+//! Names come from a sample of the source's newest lines, parsed the way the
+//! quick filter parses them, so nested JSON keys complete in dot notation.
+
+use super::quick_filter;
+use crate::ansi::strip_ansi;
+use crate::reader::LogReader;
+
+/// Newest lines read for field names on each completion
+const SAMPLE_LINES: usize = 200;
+
+/// Parser names offered where a stage may declare one
+const PARSERS: &[&str] = &["json", "logfmt"];
+
+/// A partly typed name under the cursor that can be completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slot {
+    /// Byte offset where the name starts
+    pub start: usize,
+    /// A parser name fits here (no stage has declared one yet)
+    pub parsers: bool,
+    /// A field name fits here (a parser was declared earlier)
+    pub fields: bool,
+}
+
+/// Find the name being typed at `cursor` in query `input`, if it sits where
+/// the query expects a parser or field name: at the start of a stage,
+/// after `has`/`!has`, or in a `count by` list.
+pub fn slot(input: &str, cursor: usize) -> Option<Slot> {
+    let (before, after) = input.split_at(cursor);
+    if after.starts_with(is_name_char) {
+        return None;
+    }
+    let start = before
+        .char_indices()
+        .rev()
+        .take_while(|&(_, c)| is_name_char(c))
+        .last()
+        .map(|(i, _)| i)?;
+
+    let preceding = &before[..start];
+    let (earlier, stage) = match preceding.rfind('|') {
+        Some(pipe) => (&preceding[..pipe], preceding[pipe + 1..].trim()),
+        None => ("", preceding.trim()),
+    };
+    let has_parser = earlier
+        .split('|')
+        .any(|s| PARSERS.contains(&s.trim().trim_start_matches('!')));
+    let first_stage = !preceding.contains('|');
+
+    let stage_start = matches!(stage, "" | "!" | "has" | "!has");
+    let count_by = stage.starts_with("count")
+        && (stage.ends_with("by") || stage.ends_with('(') || stage.ends_with(','));
+    let parsers = !has_parser && matches!(stage, "" | "!");
+    let fields = has_parser && !first_stage && (stage_start || count_by);
+
+    (parsers || fields).then_some(Slot {
+        start,
+        parsers,
+        fields,
+    })
+}
+
+/// Distinct field names in the newest lines of `reader`, sorted.
+pub fn sample_field_names(reader: &mut dyn LogReader) -> Vec<String> {
+    let total = reader.total_lines();
+    let mut names: Vec<String> = (total.saturating_sub(SAMPLE_LINES)..total)
+        .filter_map(|line| reader.get_line(line).ok().flatten())
+        .flat_map(|line| quick_filter::candidates(&strip_ansi(&line)))
+        .filter_map(|candidate| candidate.field)
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+/// Names a completion at `slot` may offer, given the source's field names.
+pub fn names_for(slot: Slot, fields: Vec<String>) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    if slot.parsers {
+        names.extend(PARSERS.iter().map(|p| p.to_string()));
+    }
+    if slot.fields {
+        names.extend(fields);
+    }
+    names
+}
+
+/// The names starting with `prefix` and the text all of them share beyond
+/// it (what Tab inserts).
+pub fn complete(names: &[String], prefix: &str) -> (Vec<String>, String) {
+    let matches: Vec<String> = names
+        .iter()
+        .filter(|name| name.starts_with(prefix) && name.len() > prefix.len())
+        .cloned()
+        .collect();
+    let mut common = matches
+        .first()
+        .map_or("", |first| &first[prefix.len()..])
+        .to_string();
+    for name in matches.iter().skip(1) {
+        let shared: usize = common
+            .chars()
+            .zip(name[prefix.len()..].chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a.len_utf8())
+            .sum();
+        common.truncate(shared);
+    }
+    (matches, common)
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.' || c == '@'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::stream_reader::StreamReader;
+    use crate::reader::StreamableReader;
+
+    fn slot_at_end(input: &str) -> Option<Slot> {
+        slot(input, input.len())
+    }
+
+    #[test]
+    fn test_slot_finds_parser_and_field_positions() {
+        let parser = slot_at_end("js").unwrap();
+        assert!(parser.parsers && !parser.fields);
+        assert_eq!(parser.start, 0);
+
+        let field = slot_at_end("json | serv").unwrap();
+        assert!(field.fields && !field.parsers);
+        assert_eq!(field.start, 7);
+
+        assert!(slot_at_end("json | has req").unwrap().fields);
+        assert!(
+            slot_at_end("json | level == \"x\" | !has tr")
+                .unwrap()
+                .fields
+        );
+        assert!(slot_at_end("json | count by (service, lev").unwrap().fields);
+        assert!(slot_at_end("json | count by ser").unwrap().fields);
+        assert!(slot_at_end("@ts >= now-5m | lo").unwrap().parsers);
+
+        // Fields need a parser declared first
+        assert!(!slot_at_end("serv").unwrap().fields);
+
+        // Operators, values, empty prefixes and mid-word cursors aren't
+        assert_eq!(slot_at_end("json | level == err"), None);
+        assert_eq!(slot_at_end("json | "), None);
+        assert_eq!(slot("json | service", 9), None);
+    }
+
+    #[test]
+    fn test_sample_collects_json_and_logfmt_fields() {
+        let mut reader = StreamReader::new_incremental();
+        reader.append_lines(vec![
+            r#"{"level":"info","http":{"status":200}}"#.to_string(),
+            "plain text line".to_string(),
+            "level=warn service=api".to_string(),
+        ]);
+        assert_eq!(
+            sample_field_names(&mut reader),
+            vec!["http.status", "level", "service"]
+        );
+    }
+
+    #[test]
+    fn test_complete_extends_to_the_common_prefix() {
+        let names: Vec<String> = ["service", "server_id", "status", "level"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let (matches, insert) = complete(&names, "se");
+        assert_eq!(matches, vec!["service", "server_id"]);
+        assert_eq!(insert, "rv");
+
+        let (matches, insert) = complete(&names, "st");
+        assert_eq!(matches, vec!["status"]);
+        assert_eq!(insert, "atus");
+
+        let (matches, insert) = complete(&names, "x");
+        assert!(matches.is_empty());
+        assert_eq!(insert, "");
+    }
+}
@@ -0,0 +1,141 @@
+//! Right-click context menu for the log view.
+
+use super::LayoutRect;
+
+/// Actions offered by the context menu, in display order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextMenuAction {
+    Copy,
+    Expand,
+    MarkFromHere,
+    FilterToLine,
+}
+
+impl ContextMenuAction {
+    pub const ALL: [ContextMenuAction; 4] = [
+        ContextMenuAction::Copy,
+        ContextMenuAction::Expand,
+        ContextMenuAction::MarkFromHere,
+        ContextMenuAction::FilterToLine,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ContextMenuAction::Copy => "Copy line",
+            ContextMenuAction::Expand => "Expand / collapse",
+            ContextMenuAction::MarkFromHere => "Show from here",
+            ContextMenuAction::FilterToLine => "Filter to this line",
+        }
+    }
+}
+
+/// An open context menu anchored at the clicked cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContextMenu {
+    pub column: u16,
+    pub row: u16,
+    pub selected: usize,
+}
+
+impl ContextMenu {
+    pub fn new(column: u16, row: u16) -> Self {
+        Self {
+            column,
+            row,
+            selected: 0,
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1) % ContextMenuAction::ALL.len();
+    }
+
+    pub fn select_prev(&mut self) {
+        let len = ContextMenuAction::ALL.len();
+        self.selected = (self.selected + len - 1) % len;
+    }
+
+    pub fn selected_action(&self) -> ContextMenuAction {
+        ContextMenuAction::ALL[self.selected]
+    }
+
+    /// Popup area: just below-right of the click, shifted to stay inside `bounds`.
+    pub fn area(&self, bounds: LayoutRect) -> LayoutRect {
+        let label_width = ContextMenuAction::ALL
+            .iter()
+            .map(|a| a.label().len())
+            .max()
+            .unwrap_or(0) as u16;
+        // Borders plus one column of padding on each side
+        let width = (label_width + 4).min(bounds.width);
+        let height = (ContextMenuAction::ALL.len() as u16 + 2).min(bounds.height);
+
+        let max_x = bounds.x + bounds.width.saturating_sub(width);
+        let max_y = bounds.y + bounds.height.saturating_sub(height);
+        LayoutRect {
+            x: self.column.clamp(bounds.x, max_x),
+            y: (self.row + 1).clamp(bounds.y, max_y),
+            width,
+            height,
+        }
+    }
+
+    /// Action under a click, if it landed on a menu item.
+    pub fn action_at(
+        &self,
+        bounds: LayoutRect,
+        column: u16,
+        row: u16,
+    ) -> Option<ContextMenuAction> {
+        let area = self.area(bounds);
+        if !area.contains_inner(column, row) {
+            return None;
+        }
+        ContextMenuAction::ALL.get(area.inner_row(row)).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOUNDS: LayoutRect = LayoutRect {
+        x: 10,
+        y: 0,
+        width: 80,
+        height: 24,
+    };
+
+    #[test]
+    fn test_selection_wraps() {
+        let mut menu = ContextMenu::new(20, 5);
+        menu.select_prev();
+        assert_eq!(menu.selected_action(), ContextMenuAction::FilterToLine);
+        menu.select_next();
+        assert_eq!(menu.selected_action(), ContextMenuAction::Copy);
+    }
+
+    #[test]
+    fn test_area_stays_inside_bounds() {
+        let menu = ContextMenu::new(88, 23);
+        let area = menu.area(BOUNDS);
+        assert!(area.x + area.width <= BOUNDS.x + BOUNDS.width);
+        assert!(area.y + area.height <= BOUNDS.y + BOUNDS.height);
+    }
+
+    #[test]
+    fn test_action_at_maps_rows_to_items() {
+        let menu = ContextMenu::new(20, 5);
+        let area = menu.area(BOUNDS);
+        // First item sits just inside the top border
+        assert_eq!(
+            menu.action_at(BOUNDS, area.x + 1, area.y + 1),
+            Some(ContextMenuAction::Copy)
+        );
+        assert_eq!(
+            menu.action_at(BOUNDS, area.x + 1, area.y + 2),
+            Some(ContextMenuAction::Expand)
+        );
+        assert_eq!(menu.action_at(BOUNDS, 0, 0), None);
+    }
+}
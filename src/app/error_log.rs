@@ -0,0 +1,93 @@
+//! Errors collected during a session (config, reload, watcher, filter),
+//! shown in the `!` overlay instead of being lost on stderr behind the TUI.
+
+use std::collections::VecDeque;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Oldest entries are dropped beyond this
+const MAX_ENTRIES: usize = 500;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoggedError {
+    /// When the error last occurred, epoch milliseconds
+    pub at_ms: u64,
+    pub message: String,
+    /// Consecutive occurrences folded into this entry
+    pub count: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct ErrorLog {
+    entries: VecDeque<LoggedError>,
+}
+
+impl ErrorLog {
+    /// Record an error now. A repeat of the previous message bumps its count
+    /// instead of adding an entry, so a failing watcher can't flood the log.
+    pub fn push(&mut self, message: impl Into<String>) {
+        let at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+        self.push_at(at_ms, message.into());
+    }
+
+    fn push_at(&mut self, at_ms: u64, message: String) {
+        if let Some(last) = self.entries.back_mut() {
+            if last.message == message {
+                last.count += 1;
+                last.at_ms = at_ms;
+                return;
+            }
+        }
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(LoggedError {
+            at_ms,
+            message,
+            count: 1,
+        });
+    }
+
+    /// Entries, oldest first
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &LoggedError> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeats_are_folded() {
+        let mut log = ErrorLog::default();
+        log.push_at(1, "watch failed".to_string());
+        log.push_at(2, "watch failed".to_string());
+        log.push_at(3, "bad regex".to_string());
+
+        let entries: Vec<_> = log.entries().collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].count, 2);
+        assert_eq!(entries[0].at_ms, 2);
+        assert_eq!(entries[1].message, "bad regex");
+    }
+
+    #[test]
+    fn test_oldest_entries_are_dropped() {
+        let mut log = ErrorLog::default();
+        for i in 0..MAX_ENTRIES + 1 {
+            log.push_at(i as u64, format!("error {}", i));
+        }
+        assert_eq!(log.len(), MAX_ENTRIES);
+        assert_eq!(log.entries().next().unwrap().message, "error 1");
+    }
+}
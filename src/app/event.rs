@@ -1,3 +1,5 @@
+use crate::index::flags::Severity;
+
 /// Events that can occur in the application
 /// Handlers return these events instead of mutating app state directly
 #[derive(Debug, Clone, PartialEq)]
@@ -13,25 +15,49 @@ pub enum AppEvent {
     MouseScrollUp(usize),   // scroll amount (lines)
     ViewportDown,           // Ctrl+E - scroll viewport down, keep selection
     ViewportUp,             // Ctrl+Y - scroll viewport up, keep selection
+    /// Select the first visible line at or above a severity
+    JumpToFirstSeverity(Severity),
+    /// Select the last visible line at or above a severity
+    JumpToLastSeverity(Severity),
+    /// Go back to the position before the last jump (Ctrl+O)
+    JumpBack,
+    /// Undo a `JumpBack` (Ctrl+N)
+    JumpForward,
+    /// Cycle the severity floor combined with the filter (off → info → warn → error)
+    CycleMinSeverity,
+    /// `H` pressed, waiting for the level to hide or show
+    EnterHideLevelMode,
+    /// Cancel `H` mode
+    ExitHideLevelMode,
+    /// Hide lines of a severity, or show them again if hidden
+    ToggleHiddenSeverity(Severity),
+    /// Show every severity again
+    ShowAllSeverities,
+    /// Open the aggregation view with the visible lines counted by severity
+    ShowSeverityBreakdown,
+    /// Release or re-grab the mouse (native terminal selection while released)
+    ToggleMouseCapture,
 
     // Filter events
     StartFilterInput,
     FilterInputChar(char),
     FilterInputBackspace,
     FilterInputSubmit,
+    /// Apply the filter being typed but keep the prompt open (Ctrl+Enter)
+    FilterInputApplyKeepOpen,
     FilterInputCancel,
+    /// Stop the running filter scan, keeping matches found so far
+    StopFilterScan,
     ClearFilter,
-    ToggleFilterMode,      // Tab in filter input - switch Plain/Regex
+    ToggleFilterMode, // Tab in filter input - switch Plain/Regex
+    /// Complete the parser or field name typed in query input (Tab)
+    CompleteQueryField,
     ToggleCaseSensitivity, // Alt+C in filter input
+    ToggleNegate,          // Alt+N in filter input - show non-matching lines
     CursorLeft,            // Move cursor left in input
     CursorRight,           // Move cursor right in input
     CursorHome,            // Move cursor to start of input
     CursorEnd,             // Move cursor to end of input
-    StartFilter {
-        pattern: String,
-        incremental: bool,
-        range: Option<(usize, usize)>,
-    },
     FilterProgress(usize),
     /// Partial filter results (for immediate display while filtering continues)
     FilterPartialResults {
@@ -70,6 +96,8 @@ pub enum AppEvent {
     ConfirmCloseTab,
     /// User cancelled the pending close action
     CancelCloseTab,
+    /// Reopen the most recently closed tab
+    ReopenLastClosed,
 
     // Source panel events
     FocusSourcePanel,
@@ -80,19 +108,47 @@ pub enum AppEvent {
     SelectSource,
     CopySourcePath,
     CopySelectedLine,
+    CopyFilter,
+    PasteFilter,
 
     // Mode toggles
     ToggleFollowMode,
     DisableFollowMode,
     ToggleRawMode,
+    CycleRenderer,
     ToggleLineWrap,
     ToggleTimestamps,
+    ToggleReverseOrder,
+    ToggleSinceMarker,
+    /// Highlight occurrences of the word under the cursor, or stop (`*`)
+    ToggleWordHighlight,
+    /// Move the word cursor to the previous/next word of the selected line
+    WordCursorLeft,
+    WordCursorRight,
+
+    // Index
+    BuildIndex,
 
     // Help mode
     ShowHelp,
+    /// Open help showing only the `;` command list
+    ShowCommandHelp,
     HideHelp,
     ScrollHelpDown,
     ScrollHelpUp,
+    StartHelpSearch,
+    HelpSearchChar(char),
+    HelpSearchBackspace,
+    /// Stop typing the help search, keeping the filter
+    FinishHelpSearch,
+    /// Drop the help search and show everything again
+    ClearHelpSearch,
+
+    // Errors overlay
+    ShowErrors,
+    HideErrors,
+    ScrollErrorsDown,
+    ScrollErrorsUp,
 
     // Line jump events
     StartLineJumpInput,
@@ -100,6 +156,21 @@ pub enum AppEvent {
     LineJumpInputBackspace,
     LineJumpInputSubmit,
     LineJumpInputCancel,
+    /// Jump to a 1-based line number
+    JumpToLine(usize),
+
+    // `;` command line
+    StartCommandInput,
+    CommandInputChar(char),
+    CommandInputBackspace,
+    /// Close the command line (the command's own events follow on Enter)
+    CommandInputCancel,
+    /// The command didn't parse; shown in the status bar
+    CommandFailed(String),
+    /// Apply a filter pattern in the current filter mode (`;filter`)
+    ApplyFilter(String),
+    /// Set the severity floor directly (`;level`)
+    SetMinSeverity(Option<Severity>),
 
     // Filter history navigation
     HistoryUp,
@@ -115,6 +186,7 @@ pub enum AppEvent {
     // Line expansion events
     ToggleLineExpansion, // Toggle expansion of currently selected line
     CollapseAll,         // Collapse all expanded lines
+    ExpandAllVisible,    // Expand the lines on screen (plus a buffer)
 
     // Aggregation events
     AggregationDown,
@@ -132,6 +204,36 @@ pub enum AppEvent {
         column: u16,
         row: u16,
     },
+    MouseDoubleClick {
+        column: u16,
+        row: u16,
+    },
+    MouseRightClick {
+        column: u16,
+        row: u16,
+    },
+
+    // Context menu
+    ContextMenuUp,
+    ContextMenuDown,
+    ContextMenuSelect,
+    ContextMenuClose,
+
+    // Quick filter from the selected line
+    QuickFilter,
+    /// Copy a field value of the selected line (the word under the cursor
+    /// for plain-text lines)
+    CopyField,
+    FieldPickerUp,
+    FieldPickerDown,
+    FieldPickerSelect,
+    FieldPickerClose,
+    /// Open a merged view of every source filtered to the line's trace id
+    TraceSelectedLine,
+    /// Switch from a combined view to the selected line's own source tab
+    JumpToSourceTab,
+    /// Switch to the tab and line of the latest background tab alert
+    JumpToTabAlert,
 
     // Warning popup
     DismissWarning,
@@ -6,8 +6,11 @@ use std::time::{Duration, Instant};
 /// Debounce delay for live filter preview (milliseconds)
 const FILTER_DEBOUNCE_MS: u64 = 500;
 
-/// Maximum number of filter history entries to keep
-const MAX_HISTORY_ENTRIES: usize = 50;
+/// Default number of filter history entries to keep
+const DEFAULT_MAX_HISTORY_ENTRIES: usize = 50;
+
+/// Upper bound for the configurable history size (keeps the file sane)
+const MAX_HISTORY_LIMIT: usize = 10_000;
 
 /// Manages filter validation, debouncing, and history navigation.
 #[derive(Debug)]
@@ -15,6 +18,10 @@ pub struct FilterController {
     /// Current filter mode for input (Plain, Regex, or Query, with case sensitivity)
     pub current_mode: FilterMode,
 
+    /// Smart case: case-insensitive input becomes case-sensitive when the
+    /// pattern contains an uppercase letter
+    pub smart_case: bool,
+
     /// Regex validation error (None = valid or plain mode)
     pub regex_error: Option<String>,
 
@@ -24,9 +31,12 @@ pub struct FilterController {
     /// Time when pending filter should be triggered (for debouncing)
     pub pending_at: Option<Instant>,
 
-    /// Filter history (up to MAX_HISTORY_ENTRIES)
+    /// Filter history (up to max_history entries)
     history: Vec<FilterHistoryEntry>,
 
+    /// Maximum number of history entries kept
+    max_history: usize,
+
     /// Current position in filter history (None = not navigating)
     history_index: Option<usize>,
 }
@@ -35,14 +45,45 @@ impl FilterController {
     pub fn new() -> Self {
         Self {
             current_mode: FilterMode::default(),
+            smart_case: false,
             regex_error: None,
             query_error: None,
             pending_at: None,
             history: history::load_history(),
+            max_history: DEFAULT_MAX_HISTORY_ENTRIES,
             history_index: None,
         }
     }
 
+    /// Set the history size limit (clamped to 1..=MAX_HISTORY_LIMIT).
+    ///
+    /// Excess entries loaded from disk are dropped from memory here; the
+    /// history file is trimmed on the next save.
+    pub fn set_max_history(&mut self, max: usize) {
+        self.max_history = max.clamp(1, MAX_HISTORY_LIMIT);
+        self.trim_history();
+    }
+
+    /// Drop the oldest entries beyond the history limit
+    fn trim_history(&mut self) {
+        if self.history.len() > self.max_history {
+            let excess = self.history.len() - self.max_history;
+            self.history.drain(..excess);
+            self.history_index = None;
+        }
+    }
+
+    /// Mode the filter actually runs with for `pattern` (smart case resolved).
+    ///
+    /// History keeps `current_mode` so recalled entries stay smart.
+    pub fn effective_mode(&self, pattern: &str) -> FilterMode {
+        if self.smart_case {
+            self.current_mode.with_smart_case(pattern)
+        } else {
+            self.current_mode
+        }
+    }
+
     /// Validate the current input as a regex (if in regex mode)
     pub fn validate_regex(&mut self, buffer: &str) {
         self.validate_query(buffer);
@@ -111,10 +152,7 @@ impl FilterController {
         }
 
         self.history.push(entry);
-
-        if self.history.len() > MAX_HISTORY_ENTRIES {
-            self.history.remove(0);
-        }
+        self.trim_history();
 
         self.history_index = None;
         history::save_history(&self.history);
@@ -4,12 +4,20 @@ pub enum InputMode {
     Normal,
     EnteringFilter,
     EnteringLineJump,
+    /// Typing a `;` command
+    EnteringCommand,
     /// Waiting for second key after 'z' (for zz, zt, zb commands)
     ZPending,
+    /// Waiting for the severity to hide or show after 'H'
+    HideLevelPending,
     /// Source panel is focused for tree navigation
     SourcePanel,
     /// Waiting for user to confirm tab close
     ConfirmClose,
+    /// Right-click context menu is open on a log line
+    ContextMenu,
+    /// Quick filter field picker is open
+    FieldPicker,
 }
 
 /// Manages text input state: buffer, cursor position, and input mode.
@@ -115,6 +123,11 @@ impl InputController {
         self.mode == InputMode::EnteringLineJump
     }
 
+    /// Check if currently entering a command
+    pub fn is_entering_command(&self) -> bool {
+        self.mode == InputMode::EnteringCommand
+    }
+
     /// Set buffer content and move cursor to end (used by history navigation)
     pub fn set_content(&mut self, content: String) {
         self.buffer = content;
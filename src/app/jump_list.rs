@@ -0,0 +1,188 @@
+//! Per-tab jump list (`Ctrl+O` back, `Ctrl+N` forward), like vim's.
+//!
+//! A tab records its position before a jump moves it somewhere else:
+//! `g`/`G`, `:123`, the severity jumps (`[ ] { }`), applying or clearing a
+//! filter (`/`, `v`, `P`, `Esc`), drilling into an aggregation group, and
+//! landing in the tab from a combined view or a background alert. Line by
+//! line movement, paging and scrolling don't record anything.
+
+use super::ViewMode;
+use crate::filter::FilterMode;
+
+/// Entries kept per tab; the oldest is dropped beyond this
+const MAX_JUMPS: usize = 100;
+
+/// A position to jump back to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JumpEntry {
+    /// Selected file line
+    pub line: usize,
+    /// Rows between the top of the viewport and the selection
+    pub screen_offset: usize,
+    /// View the tab was in
+    pub view: ViewMode,
+    /// Filter pattern and mode active at the time (`None` for the
+    /// unfiltered view)
+    pub filter: Option<(String, FilterMode)>,
+}
+
+#[derive(Debug, Default)]
+pub struct JumpList {
+    entries: Vec<JumpEntry>,
+    /// Entry being viewed; `entries.len()` when not navigating the list
+    cursor: usize,
+    /// Position before filter input started, recorded if the filter is applied
+    pending: Option<JumpEntry>,
+}
+
+impl JumpList {
+    /// Record the position a jump leaves. Entries ahead of the cursor
+    /// (reached with `back`) are dropped, as in a browser's history.
+    pub fn push(&mut self, entry: JumpEntry) {
+        self.entries.truncate(self.cursor);
+        if self
+            .entries
+            .last()
+            .is_some_and(|last| same_place(last, &entry))
+        {
+            self.entries.pop();
+        }
+        self.entries.push(entry);
+        if self.entries.len() > MAX_JUMPS {
+            self.entries.remove(0);
+        }
+        self.cursor = self.entries.len();
+    }
+
+    /// Step back from `current`. The position being left is kept, so
+    /// `forward` returns to it; an entry for the place it already is at is
+    /// skipped.
+    pub fn back(&mut self, current: JumpEntry) -> Option<JumpEntry> {
+        if self.cursor == self.entries.len() {
+            self.push(current);
+            self.cursor = self.entries.len() - 1;
+        } else {
+            self.entries[self.cursor] = current;
+        }
+        if self.cursor == 0 {
+            return None;
+        }
+        self.cursor -= 1;
+        Some(self.entries[self.cursor].clone())
+    }
+
+    /// Step forward again after `back`.
+    pub fn forward(&mut self, current: JumpEntry) -> Option<JumpEntry> {
+        if self.cursor + 1 >= self.entries.len() {
+            return None;
+        }
+        self.entries[self.cursor] = current;
+        self.cursor += 1;
+        Some(self.entries[self.cursor].clone())
+    }
+
+    /// Hold `entry` until the filter being typed is applied or cancelled.
+    pub fn set_pending(&mut self, entry: JumpEntry) {
+        self.pending = Some(entry);
+    }
+
+    /// Record the held position, if any (the filter was applied).
+    pub fn push_pending(&mut self) {
+        if let Some(entry) = self.pending.take() {
+            self.push(entry);
+        }
+    }
+
+    /// Forget the held position (the filter input was cancelled).
+    pub fn clear_pending(&mut self) {
+        self.pending = None;
+    }
+}
+
+/// Whether two entries are the same line of the same filter; the newer one
+/// replaces the older rather than stacking up.
+fn same_place(a: &JumpEntry, b: &JumpEntry) -> bool {
+    a.line == b.line && a.filter == b.filter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(line: usize) -> JumpEntry {
+        JumpEntry {
+            line,
+            screen_offset: 0,
+            view: ViewMode::Normal,
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn test_back_and_forward_return_to_the_present() {
+        let mut jumps = JumpList::default();
+        jumps.push(at(1));
+        jumps.push(at(2));
+
+        assert_eq!(jumps.back(at(3)), Some(at(2)));
+        assert_eq!(jumps.back(at(2)), Some(at(1)));
+        assert_eq!(jumps.back(at(1)), None);
+        assert_eq!(jumps.forward(at(1)), Some(at(2)));
+        assert_eq!(jumps.forward(at(2)), Some(at(3)));
+        assert_eq!(jumps.forward(at(3)), None);
+    }
+
+    #[test]
+    fn test_back_skips_an_entry_for_the_current_place() {
+        let mut jumps = JumpList::default();
+        jumps.push(at(1));
+        jumps.push(at(2));
+        assert_eq!(jumps.back(at(2)), Some(at(1)));
+        assert_eq!(jumps.forward(at(1)), Some(at(2)));
+        assert_eq!(jumps.forward(at(2)), None);
+    }
+
+    #[test]
+    fn test_push_after_back_drops_forward_entries() {
+        let mut jumps = JumpList::default();
+        jumps.push(at(1));
+        jumps.push(at(2));
+        assert_eq!(jumps.back(at(3)), Some(at(2)));
+
+        jumps.push(at(20));
+        assert_eq!(jumps.forward(at(30)), None);
+        assert_eq!(jumps.back(at(30)), Some(at(20)));
+        assert_eq!(jumps.back(at(20)), Some(at(1)));
+    }
+
+    #[test]
+    fn test_repeated_place_is_recorded_once_and_list_is_bounded() {
+        let mut jumps = JumpList::default();
+        jumps.push(at(5));
+        jumps.push(JumpEntry {
+            screen_offset: 4,
+            ..at(5)
+        });
+        assert_eq!(jumps.entries.len(), 1);
+        assert_eq!(jumps.entries[0].screen_offset, 4);
+
+        for line in 0..MAX_JUMPS + 10 {
+            jumps.push(at(line));
+        }
+        assert_eq!(jumps.entries.len(), MAX_JUMPS);
+        assert_eq!(jumps.entries[0], at(10));
+    }
+
+    #[test]
+    fn test_pending_entry_is_pushed_only_when_committed() {
+        let mut jumps = JumpList::default();
+        jumps.set_pending(at(7));
+        jumps.clear_pending();
+        jumps.push_pending();
+        assert!(jumps.entries.is_empty());
+
+        jumps.set_pending(at(7));
+        jumps.push_pending();
+        assert_eq!(jumps.back(at(9)), Some(at(7)));
+    }
+}
@@ -0,0 +1,230 @@
+//! Compact storage for the file lines a tab displays.
+//!
+//! The unfiltered view shows a contiguous run of lines, so it is kept as a
+//! range (two integers) instead of one `usize` per line; a 100M-line file
+//! would otherwise need 800 MB just for its indices. Filtered views hold an
+//! explicit list. Both behave like a sorted `[usize]`.
+
+use std::ops::Range;
+
+/// Sorted file line numbers shown by a tab.
+///
+/// Equality compares the lines, not the representation.
+#[derive(Debug, Clone)]
+pub enum LineIndices {
+    /// Every line in the range
+    Range(Range<usize>),
+    /// Explicit, ascending line numbers (filter results)
+    List(Vec<usize>),
+}
+
+impl Default for LineIndices {
+    fn default() -> Self {
+        LineIndices::Range(0..0)
+    }
+}
+
+impl LineIndices {
+    /// All lines of a file with `total` lines.
+    pub fn all(total: usize) -> Self {
+        LineIndices::Range(0..total)
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            LineIndices::Range(range) => range.len(),
+            LineIndices::List(list) => list.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Line number at position `index`.
+    pub fn get(&self, index: usize) -> Option<usize> {
+        match self {
+            LineIndices::Range(range) => (index < range.len()).then(|| range.start + index),
+            LineIndices::List(list) => list.get(index).copied(),
+        }
+    }
+
+    /// Line number at position `index`; panics when out of bounds, like
+    /// slice indexing.
+    pub fn at(&self, index: usize) -> usize {
+        match self.get(index) {
+            Some(line) => line,
+            None => panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len(),
+                index
+            ),
+        }
+    }
+
+    pub fn first(&self) -> Option<usize> {
+        self.get(0)
+    }
+
+    pub fn last(&self) -> Option<usize> {
+        self.len().checked_sub(1).and_then(|i| self.get(i))
+    }
+
+    /// Same contract as `slice::binary_search`.
+    pub fn binary_search(&self, line: &usize) -> Result<usize, usize> {
+        match self {
+            LineIndices::Range(range) => {
+                if *line < range.start {
+                    Err(0)
+                } else if *line >= range.end {
+                    Err(range.len())
+                } else {
+                    Ok(line - range.start)
+                }
+            }
+            LineIndices::List(list) => list.binary_search(line),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        let (range, list) = match self {
+            LineIndices::Range(range) => (range.clone(), &[][..]),
+            LineIndices::List(list) => (0..0, list.as_slice()),
+        };
+        range.chain(list.iter().copied())
+    }
+
+    pub fn to_vec(&self) -> Vec<usize> {
+        self.iter().collect()
+    }
+
+    /// Append the lines `lines`, which must all be past the current last line.
+    ///
+    /// Stays a range when `lines` continues it (the common "new lines
+    /// arrived" case).
+    pub fn extend_range(&mut self, lines: Range<usize>) {
+        if lines.is_empty() {
+            return;
+        }
+        match self {
+            LineIndices::Range(range) if range.start >= range.end => *range = lines,
+            LineIndices::Range(range) if range.end == lines.start => range.end = lines.end,
+            _ => self.make_list().extend(lines),
+        }
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        match self {
+            LineIndices::Range(range) => range.end = range.end.min(range.start + len),
+            LineIndices::List(list) => list.truncate(len),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = LineIndices::default();
+    }
+
+    /// Materialize into an explicit list (no-op if already one).
+    fn make_list(&mut self) -> &mut Vec<usize> {
+        if let LineIndices::Range(range) = self {
+            *self = LineIndices::List(range.clone().collect());
+        }
+        match self {
+            LineIndices::List(list) => list,
+            LineIndices::Range(_) => unreachable!(),
+        }
+    }
+}
+
+impl From<Vec<usize>> for LineIndices {
+    fn from(list: Vec<usize>) -> Self {
+        LineIndices::List(list)
+    }
+}
+
+impl From<Range<usize>> for LineIndices {
+    fn from(range: Range<usize>) -> Self {
+        LineIndices::Range(range)
+    }
+}
+
+impl FromIterator<usize> for LineIndices {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        LineIndices::List(iter.into_iter().collect())
+    }
+}
+
+impl Extend<usize> for LineIndices {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        self.make_list().extend(iter);
+    }
+}
+
+impl PartialEq for LineIndices {
+    fn eq(&self, other: &LineIndices) -> bool {
+        match (self, other) {
+            (LineIndices::Range(a), LineIndices::Range(b)) => {
+                a.len() == b.len() && (a.is_empty() || a.start == b.start)
+            }
+            _ => self.len() == other.len() && self.iter().eq(other.iter()),
+        }
+    }
+}
+
+impl Eq for LineIndices {}
+
+impl PartialEq<Vec<usize>> for LineIndices {
+    fn eq(&self, other: &Vec<usize>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_behaves_like_slice() {
+        let range = LineIndices::Range(3..7);
+        let list = LineIndices::from(vec![3, 4, 5, 6]);
+
+        for probe in [0, 3, 5, 6, 7, 100] {
+            assert_eq!(range.binary_search(&probe), list.binary_search(&probe));
+        }
+        assert_eq!(range.get(1), Some(4));
+        assert_eq!(range.get(4), None);
+        assert_eq!(range.first(), Some(3));
+        assert_eq!(range.last(), Some(6));
+        assert_eq!(range, vec![3, 4, 5, 6]);
+        assert_eq!(range, list);
+        assert_eq!(LineIndices::default().last(), None);
+    }
+
+    #[test]
+    fn test_all_lines_stay_compact_as_file_grows() {
+        let mut indices = LineIndices::all(100_000_000);
+        indices.extend_range(100_000_000..100_000_500);
+        assert!(matches!(indices, LineIndices::Range(ref r) if *r == (0..100_000_500)));
+        assert_eq!(indices.at(100_000_499), 100_000_499);
+
+        indices.truncate(10);
+        assert!(matches!(indices, LineIndices::Range(ref r) if *r == (0..10)));
+    }
+
+    #[test]
+    fn test_gapped_extend_materializes() {
+        let mut indices = LineIndices::Range(0..3);
+        indices.extend([5, 8]);
+        assert!(matches!(indices, LineIndices::List(_)));
+        assert_eq!(indices, vec![0, 1, 2, 5, 8]);
+
+        indices.extend_range(9..11);
+        assert_eq!(indices, vec![0, 1, 2, 5, 8, 9, 10]);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_at_out_of_bounds_panics() {
+        LineIndices::Range(0..2).at(2);
+    }
+}
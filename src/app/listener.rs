@@ -0,0 +1,229 @@
+//! TCP line listener for `--listen <ADDR>`.
+//!
+//! Accepts line-oriented TCP connections (syslog-style senders, `nc`,
+//! scripts) and streams their lines into one tab. Marker lines in the
+//! stream show each connect and disconnect. Input is plaintext and
+//! unauthenticated, so the listener is meant for trusted networks.
+
+use super::tab::StreamMessage;
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, ErrorKind, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Connections served at once; further ones are turned away
+pub const MAX_CONNECTIONS: usize = 64;
+
+/// How often the accept loop checks for shutdown
+const ACCEPT_POLL: Duration = Duration::from_millis(100);
+
+/// Lines sent per message while a sender keeps the socket busy
+const MAX_BATCH_LINES: usize = 1_000;
+
+type Connections = Arc<Mutex<HashMap<u64, TcpStream>>>;
+
+/// Accepts connections in a background thread. Dropping it stops
+/// accepting and closes every open connection.
+pub struct Listener {
+    local_addr: SocketAddr,
+    stop: Arc<AtomicBool>,
+    connections: Connections,
+}
+
+impl Listener {
+    /// Bind `addr` (e.g. `0.0.0.0:5140`) and start sending received lines
+    /// to `tx`.
+    pub fn bind(addr: &str, tx: Sender<StreamMessage>) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        let local_addr = listener.local_addr()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let connections = Connections::default();
+
+        let acceptor = Acceptor {
+            listener,
+            tx,
+            stop: stop.clone(),
+            connections: connections.clone(),
+        };
+        thread::spawn(move || acceptor.run());
+        Ok(Self {
+            local_addr,
+            stop,
+            connections,
+        })
+    }
+
+    /// The address actually bound (resolves port 0).
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        for stream in lock(&self.connections).values() {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+struct Acceptor {
+    listener: TcpListener,
+    tx: Sender<StreamMessage>,
+    stop: Arc<AtomicBool>,
+    connections: Connections,
+}
+
+impl Acceptor {
+    fn run(self) {
+        let mut next_id = 0u64;
+        while !self.stop.load(Ordering::SeqCst) {
+            let (mut stream, peer) = match self.listener.accept() {
+                Ok(accepted) => accepted,
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                    thread::sleep(ACCEPT_POLL);
+                    continue;
+                }
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    let _ = self.marker(&format!("[lazytail] listener stopped: {}", e));
+                    return;
+                }
+            };
+
+            if lock(&self.connections).len() >= MAX_CONNECTIONS {
+                let _ = writeln!(stream, "lazytail: too many connections");
+                let message = format!(
+                    "[lazytail] refused {}: {} connections already open",
+                    peer, MAX_CONNECTIONS
+                );
+                if !self.marker(&message) {
+                    return;
+                }
+                continue;
+            }
+
+            let registered = stream
+                .set_nonblocking(false)
+                .and_then(|_| stream.try_clone());
+            let clone = match registered {
+                Ok(clone) => clone,
+                Err(e) => {
+                    let _ = self.marker(&format!("[lazytail] dropped {}: {}", peer, e));
+                    continue;
+                }
+            };
+            let id = next_id;
+            next_id += 1;
+            lock(&self.connections).insert(id, clone);
+
+            if !self.marker(&format!("[lazytail] {} connected", peer)) {
+                return;
+            }
+            let tx = self.tx.clone();
+            let connections = self.connections.clone();
+            thread::spawn(move || {
+                let reason = match forward_lines(stream, &tx) {
+                    Ok(true) => "disconnected".to_string(),
+                    Ok(false) => return,
+                    Err(e) => format!("disconnected ({})", e),
+                };
+                lock(&connections).remove(&id);
+                let _ = tx.send(StreamMessage::Lines(vec![format!(
+                    "[lazytail] {} {}",
+                    peer, reason
+                )]));
+            });
+        }
+    }
+
+    /// Append a marker line to the stream. Returns `false` if the receiver
+    /// is gone.
+    fn marker(&self, text: &str) -> bool {
+        self.tx
+            .send(StreamMessage::Lines(vec![text.to_string()]))
+            .is_ok()
+    }
+}
+
+/// Send the connection's lines to `tx` until it closes. Lines go out as
+/// soon as the socket has no more buffered data, so slow senders show up
+/// live. Invalid UTF-8 is replaced rather than dropping the connection.
+///
+/// Returns `Ok(false)` if the receiver was dropped.
+fn forward_lines(stream: TcpStream, tx: &Sender<StreamMessage>) -> io::Result<bool> {
+    let mut reader = BufReader::new(stream);
+    let mut batch = Vec::new();
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+        let line = buf.strip_suffix(b"\n").unwrap_or(&buf);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        batch.push(String::from_utf8_lossy(line).into_owned());
+
+        if (reader.buffer().is_empty() || batch.len() >= MAX_BATCH_LINES)
+            && tx
+                .send(StreamMessage::Lines(std::mem::take(&mut batch)))
+                .is_err()
+        {
+            return Ok(false);
+        }
+    }
+    if !batch.is_empty() && tx.send(StreamMessage::Lines(batch)).is_err() {
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+fn lock(connections: &Connections) -> std::sync::MutexGuard<'_, HashMap<u64, TcpStream>> {
+    match connections.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    fn collect_lines(rx: &mpsc::Receiver<StreamMessage>, count: usize) -> Vec<String> {
+        let mut lines = Vec::new();
+        while lines.len() < count {
+            match rx.recv_timeout(Duration::from_secs(10)).unwrap() {
+                StreamMessage::Lines(batch) => lines.extend(batch),
+                other => panic!("unexpected message: {:?}", other),
+            }
+        }
+        lines
+    }
+
+    #[test]
+    fn test_forwards_lines_with_connect_markers() {
+        let (tx, rx) = mpsc::channel();
+        let listener = Listener::bind("127.0.0.1:0", tx).unwrap();
+
+        let mut client = TcpStream::connect(listener.local_addr()).unwrap();
+        let peer = client.local_addr().unwrap();
+        client.write_all(b"first\r\nsecond\n").unwrap();
+        let lines = collect_lines(&rx, 3);
+        assert_eq!(lines[0], format!("[lazytail] {} connected", peer));
+        assert_eq!(&lines[1..], ["first", "second"]);
+
+        // A trailing partial line is delivered on close
+        client.write_all(b"last").unwrap();
+        drop(client);
+        let lines = collect_lines(&rx, 2);
+        assert_eq!(lines[0], "last");
+        assert_eq!(lines[1], format!("[lazytail] {} disconnected", peer));
+    }
+}
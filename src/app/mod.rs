@@ -1,24 +1,54 @@
+pub mod completion;
+pub mod context_menu;
+pub mod error_log;
 pub mod event;
 pub mod filter_controller;
 pub mod input_controller;
+pub mod jump_list;
+pub mod line_indices;
+pub mod listener;
+pub mod quick_filter;
+pub mod respawn;
 pub mod source_panel;
+pub mod syslog;
 pub mod tab;
+pub mod tab_bar;
 pub mod tab_manager;
+pub mod tee;
 pub mod viewport;
 
 pub use event::AppEvent;
 pub use filter_controller::FilterController;
 pub use input_controller::{InputController, InputMode};
+pub use line_indices::LineIndices;
 pub use source_panel::SourcePanelController;
 pub use tab::{StreamMessage, TabState};
 pub use tab_manager::TabManager;
 
+use crate::config::types::{
+    BackgroundAlert, ConfirmClose, DefaultFilterConfig, DoubleClickAction, FilterEsc,
+    FormatRenderers, LinePrefixConfig, NavLayout,
+};
+use crate::filter::FilterMode;
 use crate::filter_orchestrator::FilterOrchestrator;
+use crate::handlers::keymap::Keymap;
+use crate::index::flags::Severity;
+use crate::reader::combined_reader::CombinedReader;
+use crate::reader::encoding::Encoding;
+use crate::reader::json_record_reader::JsonRecordReader;
+use crate::reader::rotated_reader::RotatedReader;
+use crate::reader::LogReader;
+use crate::renderer::highlight::{word_ranges, HighlightSet};
+use crate::renderer::transform::DisplayTransform;
 use crate::renderer::PresetRegistry;
+use crate::source::SourceStatus;
+use context_menu::{ContextMenu, ContextMenuAction};
+use error_log::ErrorLog;
+use jump_list::JumpEntry;
+use quick_filter::{FieldPicker, PickerAction, QuickFilter};
 use std::collections::HashMap;
-#[cfg(test)]
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 /// Lightweight rectangle for storing layout areas (avoids ratatui dependency in app module)
@@ -53,6 +83,8 @@ pub struct LayoutAreas {
     pub side_panel_sources: LayoutRect,
     /// The main log content area
     pub log_view: LayoutRect,
+    /// The tab bar row (zero-sized unless `nav: tabbar`)
+    pub tab_bar: LayoutRect,
 }
 
 /// Represents the current view mode
@@ -63,6 +95,52 @@ pub enum ViewMode {
     Aggregation,
 }
 
+/// Raw display state, cycled by the raw mode key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RawMode {
+    /// Presets, display transforms, highlights and tab expansion applied
+    #[default]
+    Off,
+    /// Bytes as written, but ANSI colors are still interpreted
+    Ansi,
+    /// Bytes as written, escape sequences shown verbatim
+    Plain,
+}
+
+impl RawMode {
+    /// Next state in the Off → Ansi → Plain → Off cycle.
+    pub fn next(self) -> Self {
+        match self {
+            RawMode::Off => RawMode::Ansi,
+            RawMode::Ansi => RawMode::Plain,
+            RawMode::Plain => RawMode::Off,
+        }
+    }
+
+    pub fn is_raw(self) -> bool {
+        self != RawMode::Off
+    }
+}
+
+/// Renderer picked at runtime with the renderer key, replacing the
+/// source's configured presets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RendererOverride {
+    /// Render with this preset only
+    Preset(String),
+    /// Render without any preset
+    Raw,
+}
+
+impl RendererOverride {
+    pub fn label(&self) -> &str {
+        match self {
+            RendererOverride::Preset(name) => name,
+            RendererOverride::Raw => "raw",
+        }
+    }
+}
+
 /// Source type for categorizing tabs in the tree view
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(usize)]
@@ -79,6 +157,112 @@ pub enum SourceType {
     Pipe,
 }
 
+/// How many closed tabs are remembered for reopening
+const MAX_CLOSED_TABS: usize = 10;
+
+/// Default live-preview scan budget (`confirm_scan_mb`)
+const DEFAULT_CONFIRM_SCAN_MB: u64 = 1024;
+
+/// Lines scrolled per mouse wheel notch unless configured
+const DEFAULT_MOUSE_SCROLL_LINES: usize = 3;
+
+/// Blank rows kept below the newest line in follow mode unless configured
+pub const DEFAULT_FOLLOW_MARGIN: usize = 2;
+
+/// Query completions listed in the status bar before the rest are elided
+const MAX_COMPLETIONS_SHOWN: usize = 8;
+
+/// Identifying info of a closed file-backed tab, enough to reopen it
+#[derive(Debug, Clone)]
+struct ClosedTab {
+    name: String,
+    path: PathBuf,
+    kind: ClosedTabKind,
+    config_source_type: Option<SourceType>,
+    source_status: Option<SourceStatus>,
+    watch: bool,
+    renderer_names: Vec<String>,
+    display_transform: Option<DisplayTransform>,
+    default_filter: Option<DefaultFilterConfig>,
+}
+
+/// Which reader a closed tab was opened with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ClosedTabKind {
+    File,
+    /// A live file with its rotated siblings (`--rotated`)
+    Rotated,
+    /// Folded multi-line JSON (`multiline_json` sources)
+    JsonRecords,
+}
+
+impl ClosedTab {
+    /// `None` for tabs without a file (pipes, combined views)
+    fn from_tab(tab: &TabState) -> Option<Self> {
+        let (path, kind) = match &tab.source.source_path {
+            Some(path) => (path.clone(), ClosedTabKind::File),
+            None => {
+                let reader = match tab.source.reader.lock() {
+                    Ok(guard) => guard,
+                    Err(poisoned) => poisoned.into_inner(),
+                };
+                let reader = reader.as_any();
+                if let Some(rotated) = reader.downcast_ref::<RotatedReader>() {
+                    (rotated.live_path().to_path_buf(), ClosedTabKind::Rotated)
+                } else {
+                    let records = reader.downcast_ref::<JsonRecordReader>()?;
+                    (records.path().to_path_buf(), ClosedTabKind::JsonRecords)
+                }
+            }
+        };
+        Some(Self {
+            name: tab.source.name.clone(),
+            path,
+            kind,
+            config_source_type: tab.config_source_type,
+            source_status: tab.source.source_status,
+            watch: tab.watcher.is_some(),
+            renderer_names: tab.source.renderer_names.clone(),
+            display_transform: tab.source.display_transform.clone(),
+            default_filter: tab.source.default_filter.clone(),
+        })
+    }
+
+    /// Open the same kind of tab again, with its config settings.
+    fn reopen(self) -> anyhow::Result<TabState> {
+        let mut tab = match self.kind {
+            // It was open before, so don't refuse it as binary now
+            ClosedTabKind::File => TabState::open(self.path, self.watch, true)?,
+            ClosedTabKind::Rotated => TabState::from_rotated(self.path, self.watch)?,
+            ClosedTabKind::JsonRecords => {
+                let source = crate::config::Source {
+                    name: self.name.clone(),
+                    path: Some(self.path),
+                    exists: true,
+                    renderer_names: Vec::new(),
+                    display_transform: None,
+                    color: None,
+                    default_filter: None,
+                    multiline_json: true,
+                };
+                let source_type = self.config_source_type.unwrap_or(SourceType::File);
+                TabState::from_config_source(&source, source_type, self.watch)?
+                    .ok_or_else(|| anyhow::anyhow!("source has no file"))?
+            }
+        };
+        tab.source.name = self.name;
+        tab.config_source_type = self.config_source_type;
+        tab.source.renderer_names = self.renderer_names;
+        tab.source.display_transform = self.display_transform;
+        tab.source.default_filter = self.default_filter;
+        if self.source_status.is_some() {
+            tab.source.source_status = self.source_status;
+            tab.refresh_source_status();
+        }
+        Ok(tab)
+    }
+}
+
 impl SourceType {
     /// Convert array index back to SourceType.
     pub fn from_index(idx: usize) -> Self {
@@ -104,6 +288,36 @@ pub enum TreeSelection {
     Item(SourceType, usize),
 }
 
+/// Search within the help overlay (`/` while help is open)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HelpSearch {
+    /// Case-insensitive text the shown shortcuts must contain
+    pub query: String,
+    /// Keys go to the query; Enter stops typing and keeps the filter
+    pub editing: bool,
+}
+
+/// A line at or above the alert threshold that arrived in a followed
+/// background tab
+#[derive(Clone)]
+pub struct TabAlert {
+    /// Name of the tab's source
+    pub source: String,
+    /// The tab's reader, which identifies it even when names repeat
+    pub reader: Arc<Mutex<dyn LogReader + Send>>,
+    /// File line to select when jumping there
+    pub line: usize,
+    pub severity: Severity,
+    pub at: Instant,
+}
+
+impl TabAlert {
+    /// Whether the alert was raised by this tab.
+    pub fn is_from(&self, tab: &TabState) -> bool {
+        !tab.is_combined && Arc::ptr_eq(&tab.source.reader, &self.reader)
+    }
+}
+
 /// Filter state tracking
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum FilterState {
@@ -137,25 +351,45 @@ pub struct App {
     /// Help overlay scroll offset (None = hidden, Some(n) = visible at offset n)
     pub help_scroll_offset: Option<usize>,
 
+    /// Search narrowing the help overlay (None = show everything)
+    pub help_search: Option<HelpSearch>,
+
+    /// Keybindings for the log view, source panel and aggregation view
+    pub keymap: Keymap,
+
+    /// Lowest severity in a followed background tab that raises an alert
+    pub background_alert: BackgroundAlert,
+
+    /// Most recent background tab alert, until jumped to
+    pub tab_alert: Option<TabAlert>,
+
+    /// Errors collected during the session (config, reload, watcher, filter)
+    pub error_log: ErrorLog,
+
+    /// Errors overlay scroll offset (None = hidden, Some(n) = visible at offset n)
+    pub errors_scroll_offset: Option<usize>,
+
     /// Tab pending close confirmation: (index, name) for identity verification
     pub pending_close_tab: Option<(usize, String)>,
 
     /// Input mode to restore when cancelling close confirmation
     confirm_return_mode: InputMode,
 
+    /// Recently closed tabs, most recent last
+    closed_tabs: Vec<ClosedTab>,
+
     /// Temporary status message shown in the status bar
     pub status_message: Option<(String, Instant)>,
 
-    /// Transient flag: set per event batch when a StartFilter event is present.
-    /// Suppresses follow-mode jump on FileModified (the filter restart handles positioning).
-    pub has_start_filter_in_batch: bool,
-
     /// Startup timestamp for measuring time-to-first-render
     pub startup_time: Option<Instant>,
 
     /// Elapsed time to first render (printed after terminal restore)
     pub first_render_elapsed: Option<Duration>,
 
+    /// Frames rendered so far (drives the filter prompt spinner)
+    pub frame: usize,
+
     /// Verbose mode (-v flag)
     pub verbose: bool,
 
@@ -168,12 +402,107 @@ pub struct App {
     /// Color theme for UI rendering
     pub theme: crate::theme::Theme,
 
+    /// Always-on highlight rules applied to every rendered line
+    pub highlights: Arc<HighlightSet>,
+
     /// Map from source name to renderer preset names (from config).
     /// Used to assign renderers to dynamically discovered sources.
     pub source_renderer_map: HashMap<String, Vec<String>>,
 
+    /// Map from source name to display transform (from config).
+    /// Used like `source_renderer_map` for dynamically discovered sources.
+    pub source_transform_map: HashMap<String, DisplayTransform>,
+
+    /// Map from source name to default filter (from config).
+    /// Used like `source_renderer_map` for dynamically discovered sources.
+    pub source_default_filter_map: HashMap<String, DefaultFilterConfig>,
+
+    /// Map from source name to configured accent color (from config).
+    pub source_color_map: HashMap<String, ratatui::style::Color>,
+
+    /// Renderer presets by detected line format (from config).
+    /// Applied to dynamically discovered sources without renderers.
+    pub format_renderers: Option<FormatRenderers>,
+
+    /// What double-clicking a log line does (from config)
+    pub double_click_action: DoubleClickAction,
+
+    /// Open right-click menu (set while in InputMode::ContextMenu)
+    pub context_menu: Option<ContextMenu>,
+
+    /// Fields the quick filter applies directly when present (from config)
+    pub quick_filter_fields: Vec<String>,
+
+    /// Open quick filter or copy picker (set while in InputMode::FieldPicker)
+    pub field_picker: Option<FieldPicker>,
+
+    /// Fields `C` copies directly when present (from config)
+    pub copy_fields: Vec<String>,
+
+    /// Fields checked for a trace/correlation id, in priority order
+    pub correlation_fields: Vec<String>,
+
+    /// Draw a marker on wrapped continuation rows (from config)
+    pub wrap_markers: bool,
+
+    /// Show large line and match counts compactly, like `1.2M` (from config)
+    pub compact_numbers: bool,
+
+    /// Line number prefix format (from config)
+    pub line_prefix: LinePrefixConfig,
+
+    /// Show the follow-paused banner with a new-line counter (from config)
+    pub follow_pause_banner: bool,
+
+    /// Blank rows kept below the newest line in follow mode (from config)
+    pub follow_margin: usize,
+
+    /// Widest the log view is drawn, in columns (from config; None = full width)
+    pub max_content_width: Option<usize>,
+
+    /// Center the log view when `max_content_width` caps it (from config)
+    pub center_content: bool,
+
+    /// Offer to build an index when filtering a large unindexed file (from config)
+    pub suggest_index: bool,
+
+    /// When closing a tab asks for confirmation (from config)
+    pub confirm_close: ConfirmClose,
+
+    /// Whether `Esc` in the filter prompt clears or keeps the previewed
+    /// filter (from config)
+    pub filter_esc: FilterEsc,
+
+    /// Side panel tree or top tab bar (from config)
+    pub nav: NavLayout,
+
+    /// Low-resource mode: startup indexing skipped, slower polling (from
+    /// config or `--low-resource`)
+    pub low_resource: bool,
+
+    /// Filters that would scan more than this many bytes without index
+    /// acceleration don't live-preview; Enter runs them (None = no guard)
+    pub confirm_scan_bytes: Option<u64>,
+
+    /// Lines scrolled per mouse wheel notch (from config)
+    pub mouse_scroll_lines: usize,
+
+    /// Whether the mouse is captured; the main loop applies changes to the
+    /// terminal (from config or `--no-mouse`, toggled with M)
+    pub mouse_capture: bool,
+
+    /// strftime-style timestamp column format; None = adaptive default
+    /// (from config)
+    pub timestamp_format: Option<String>,
+
     /// Warning popup — shown as overlay, dismissed on any key
     pub warning_popup: Option<String>,
+
+    /// Quit after this long without input (`--idle-exit`)
+    pub idle_exit: Option<Duration>,
+
+    /// Text encoding applied to every file-backed tab (including tabs added later)
+    encoding: Encoding,
 }
 
 impl App {
@@ -203,18 +532,57 @@ impl App {
             panel: SourcePanelController::new(),
             should_quit: false,
             help_scroll_offset: None,
+            help_search: None,
+            keymap: Keymap::default(),
+            background_alert: BackgroundAlert::default(),
+            tab_alert: None,
+            error_log: ErrorLog::default(),
+            errors_scroll_offset: None,
             pending_close_tab: None,
+            closed_tabs: Vec::new(),
             confirm_return_mode: InputMode::Normal,
             status_message: None,
-            has_start_filter_in_batch: false,
             startup_time: None,
             first_render_elapsed: None,
+            frame: 0,
             verbose: false,
             layout: LayoutAreas::default(),
             preset_registry,
             theme: crate::theme::Theme::dark(),
+            highlights: Arc::new(HighlightSet::default()),
             source_renderer_map: HashMap::new(),
+            source_transform_map: HashMap::new(),
+            source_default_filter_map: HashMap::new(),
+            source_color_map: HashMap::new(),
+            format_renderers: None,
+            double_click_action: DoubleClickAction::default(),
+            context_menu: None,
+            quick_filter_fields: Vec::new(),
+            field_picker: None,
+            copy_fields: Vec::new(),
+            correlation_fields: quick_filter::DEFAULT_CORRELATION_FIELDS
+                .iter()
+                .map(|f| f.to_string())
+                .collect(),
+            wrap_markers: true,
+            compact_numbers: true,
+            line_prefix: LinePrefixConfig::default(),
+            follow_pause_banner: true,
+            follow_margin: DEFAULT_FOLLOW_MARGIN,
+            max_content_width: None,
+            center_content: true,
+            suggest_index: true,
+            confirm_close: ConfirmClose::default(),
+            filter_esc: FilterEsc::default(),
+            low_resource: false,
+            nav: NavLayout::default(),
+            confirm_scan_bytes: Some(DEFAULT_CONFIRM_SCAN_MB * 1024 * 1024),
+            mouse_scroll_lines: DEFAULT_MOUSE_SCROLL_LINES,
+            mouse_capture: true,
+            timestamp_format: None,
             warning_popup: None,
+            idle_exit: None,
+            encoding: Encoding::default(),
         }
     }
 
@@ -257,14 +625,57 @@ impl App {
         self.tab_mgr.tab_count()
     }
 
+    /// Text encoding applied to file-backed tabs
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Set the text encoding for all current and future tabs
+    pub fn set_encoding(&mut self, encoding: Encoding) {
+        self.encoding = encoding;
+        for tab in &mut self.tab_mgr.tabs {
+            tab.set_encoding(encoding);
+        }
+    }
+
     /// Add a new tab
-    pub fn add_tab(&mut self, tab: TabState) {
+    pub fn add_tab(&mut self, mut tab: TabState) {
+        if self.encoding != Encoding::default() {
+            tab.set_encoding(self.encoding);
+        }
+        if let Err(e) = tab.apply_default_filter() {
+            self.status_message = Some((
+                format!("{}: default filter: {}", tab.source.name, e),
+                Instant::now(),
+            ));
+        }
         self.tab_mgr.add_tab(tab);
         self.check_index_warning();
     }
 
-    /// Close a tab by index
+    /// Start the configured default filter on every tab that has one.
+    pub fn apply_default_filters(&mut self) {
+        let mut failed = Vec::new();
+        for tab in &mut self.tab_mgr.tabs {
+            if let Err(e) = tab.apply_default_filter() {
+                failed.push(format!("{}: default filter: {}", tab.source.name, e));
+            }
+        }
+        if !failed.is_empty() {
+            self.status_message = Some((failed.join("; "), Instant::now()));
+        }
+    }
+
+    /// Close a tab by index, remembering it for `reopen_last_closed`
     pub fn close_tab(&mut self, index: usize) {
+        if self.tab_mgr.tabs.len() > 1 {
+            if let Some(closed) = self.tab_mgr.tabs.get(index).and_then(ClosedTab::from_tab) {
+                if self.closed_tabs.len() >= MAX_CLOSED_TABS {
+                    self.closed_tabs.remove(0);
+                }
+                self.closed_tabs.push(closed);
+            }
+        }
         let should_quit = self.tab_mgr.close_tab(index);
         if should_quit {
             self.should_quit = true;
@@ -296,6 +707,24 @@ impl App {
         self.input.is_entering_line_jump()
     }
 
+    /// Check if currently typing a `;` command
+    pub fn is_entering_command(&self) -> bool {
+        self.input.is_entering_command()
+    }
+
+    /// Whether `--idle-exit` should quit after `idle` without input. Never
+    /// fires while a prompt, dialog or overlay is open.
+    pub fn idle_exit_due(&self, idle: Duration) -> bool {
+        let Some(limit) = self.idle_exit else {
+            return false;
+        };
+        let modal_open = !matches!(self.input.mode, InputMode::Normal | InputMode::SourcePanel)
+            || self.help_scroll_offset.is_some()
+            || self.errors_scroll_offset.is_some()
+            || self.warning_popup.is_some();
+        idle >= limit && !modal_open
+    }
+
     // === Source Panel Methods ===
 
     /// Focus the source panel for tree navigation
@@ -367,57 +796,167 @@ impl App {
             None
         };
 
-        if let Some(tab_idx) = tab_idx {
-            if let Some(path) = &self.tab_mgr.tabs[tab_idx].source.source_path {
-                let path_str = path.display().to_string();
-                let encoded = base64_encode(path_str.as_bytes());
-                print!("\x1b]52;c;{}\x07", encoded);
-                self.status_message = Some((format!("Copied: {}", path_str), Instant::now()));
-            }
+        let path = tab_idx
+            .and_then(|idx| self.tab_mgr.tabs[idx].source.source_path.as_ref())
+            .map(|path| path.display().to_string());
+        if let Some(path) = path {
+            self.copy_to_clipboard("Copied", &path, "path");
         }
     }
 
-    /// Copy the selected line's content (ANSI-stripped) to clipboard via OSC 52
-    fn copy_selected_line(&mut self) {
+    /// Content of the selected line with ANSI codes stripped
+    fn selected_line_text(&mut self) -> Option<String> {
+        let tab = self.active_tab_mut();
+        let file_line_number = tab.source.line_indices.get(tab.selected_line)?;
+
+        let mut reader = match tab.source.reader.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let raw = reader.get_line(file_line_number).ok().flatten()?;
+        Some(crate::ansi::strip_ansi(&raw))
+    }
+
+    /// The selected line as the view draws it: ANSI-stripped, with the
+    /// source's display transform applied. The word cursor walks this text.
+    fn selected_display_text(&mut self) -> Option<String> {
+        let text = self.selected_line_text()?;
+        let transform = self.active_tab().source.display_transform.as_ref();
+        Some(
+            transform
+                .and_then(|transform| transform.apply(&text))
+                .map_or(text, |shown| crate::ansi::strip_ansi(&shown)),
+        )
+    }
+
+    /// Turn highlighting of the word under the cursor on (starting at the
+    /// selected line's first word) or off.
+    fn toggle_word_highlight(&mut self) {
+        let tab = self.active_tab_mut();
+        tab.word_cursor = match tab.word_cursor {
+            Some(_) => None,
+            None => Some(0),
+        };
+    }
+
+    /// Move the word cursor to the next (`forward`) or previous word of the
+    /// selected line, wrapping around at either end.
+    fn move_word_cursor(&mut self, forward: bool) {
+        let count = self
+            .selected_display_text()
+            .map_or(0, |text| word_ranges(&text).len());
         let tab = self.active_tab_mut();
-        if tab.source.line_indices.is_empty() {
+        let Some(cursor) = tab.word_cursor.filter(|_| count > 0) else {
             return;
+        };
+        let cursor = cursor.min(count - 1);
+        tab.word_cursor = Some(if forward {
+            (cursor + 1) % count
+        } else {
+            (cursor + count - 1) % count
+        });
+    }
+
+    /// Copy the selected line's content (ANSI-stripped) to clipboard via OSC 52
+    fn copy_selected_line(&mut self) {
+        if let Some(clean) = self.selected_line_text() {
+            self.copy_to_clipboard("Copied", &clean, "line");
         }
+    }
+
+    /// Copy `text` to clipboard via OSC 52 and show it after `label` in the
+    /// status bar, shortened if long (`what` names an empty `text`, as in
+    /// "(empty line)")
+    fn copy_to_clipboard(&mut self, label: &str, text: &str, what: &str) {
+        let encoded = base64_encode(text.as_bytes());
+        print!("\x1b]52;c;{}\x07", encoded);
+
+        let display = if text.is_empty() {
+            format!("{}: (empty {})", label, what)
+        } else if text.len() > 60 {
+            format!("{}: {}...", label, &text[..text.floor_char_boundary(57)])
+        } else {
+            format!("{}: {}", label, text)
+        };
+        self.status_message = Some((display, Instant::now()));
+    }
 
-        let file_line_number = match tab.source.line_indices.get(tab.selected_line) {
-            Some(&n) => n,
-            None => return,
+    /// Copy the active filter to clipboard via OSC 52, as arguments for the
+    /// MCP `search` tool so an interactive query can be reused in scripts
+    fn copy_filter(&mut self) {
+        if self.active_tab().source.filter.mode.is_negated() {
+            self.status_message = Some((
+                "Inverted filters have no search equivalent".to_string(),
+                Instant::now(),
+            ));
+            return;
+        }
+        let Some(args) = filter_search_args(&self.active_tab().source) else {
+            self.status_message = Some(("No active filter to copy".to_string(), Instant::now()));
+            return;
         };
+        self.copy_to_clipboard("Copied filter", &args, "filter");
+    }
 
-        let content = {
-            let mut reader = match tab.source.reader.lock() {
-                Ok(guard) => guard,
-                Err(poisoned) => poisoned.into_inner(),
-            };
-            reader.get_line(file_line_number).ok().flatten()
+    /// Apply the clipboard contents as a filter on the active tab: either
+    /// `search` arguments copied with `Y`, or a pattern run in the current
+    /// filter mode
+    fn paste_filter(&mut self) {
+        let result = crate::clipboard::read().and_then(|text| {
+            let fallback = self.filter.effective_mode(text.trim());
+            let (pattern, mode) = pasted_filter(&text, fallback)?;
+            self.start_filter_from(pattern.clone(), mode)
+                .map_err(|e| format!("Pasted filter is invalid: {}", e))?;
+            Ok(pattern)
+        });
+        let message = match result {
+            Ok(pattern) => format!("Applied filter: {}", pattern),
+            Err(e) => e,
         };
+        self.status_message = Some((message, Instant::now()));
+    }
 
-        if let Some(raw) = content {
-            let clean = crate::ansi::strip_ansi(&raw);
-            let encoded = base64_encode(clean.as_bytes());
-            print!("\x1b]52;c;{}\x07", encoded);
+    /// Apply a filter typed on the command line, in the prompt's current
+    /// mode (query syntax is detected as in the prompt).
+    fn apply_command_filter(&mut self, pattern: String) {
+        let mode = self.filter.effective_mode(&pattern);
+        let message = match self.start_filter_from(pattern.clone(), mode) {
+            Ok(()) => format!("Applied filter: {}", pattern),
+            Err(e) => format!("Invalid filter: {}", e),
+        };
+        self.status_message = Some((message, Instant::now()));
+    }
 
-            let display = if clean.is_empty() {
-                "Copied: (empty line)".to_string()
-            } else if clean.len() > 60 {
-                format!("Copied: {}...", &clean[..clean.floor_char_boundary(57)])
-            } else {
-                format!("Copied: {}", clean)
-            };
-            self.status_message = Some((display, Instant::now()));
-        }
+    /// Run a filter that didn't come from the filter prompt, recording the
+    /// jump and the history entry the prompt would.
+    fn start_filter_from(&mut self, pattern: String, mode: FilterMode) -> Result<(), String> {
+        let tab = self.active_tab_mut();
+        let from = tab.jump_position();
+        tab.start_filter(pattern.clone(), mode)?;
+        tab.jumps.push(from);
+        self.filter.add_to_history(pattern, mode);
+        Ok(())
     }
 
     // === Close Confirmation Methods ===
 
-    /// Request closing a tab with confirmation dialog
+    /// Request closing a tab, asking for confirmation if the
+    /// `confirm_close` policy requires it
     fn request_close_tab(&mut self, tab_index: usize) {
         if tab_index < self.tab_mgr.tabs.len() {
+            let confirm = match self.confirm_close {
+                ConfirmClose::Always => true,
+                ConfirmClose::Destructive => self.tab_mgr.tabs[tab_index].close_deletes_source(),
+                ConfirmClose::Never => false,
+            };
+            if !confirm {
+                self.close_tab(tab_index);
+                if self.input.mode == InputMode::SourcePanel {
+                    self.fix_source_panel_selection();
+                }
+                return;
+            }
+
             let tab_name = self.tab_mgr.tabs[tab_index].source.name.clone();
             self.pending_close_tab = Some((tab_index, tab_name));
             self.confirm_return_mode = self.input.mode;
@@ -443,6 +982,38 @@ impl App {
         }
     }
 
+    /// Reopen the most recently closed tab and switch to it
+    fn reopen_last_closed(&mut self) {
+        let Some(closed) = self.closed_tabs.pop() else {
+            self.status_message = Some(("No closed tab to reopen".to_string(), Instant::now()));
+            return;
+        };
+        if !closed.path.exists() {
+            self.status_message = Some((
+                format!(
+                    "Cannot reopen {}: {} was deleted",
+                    closed.name,
+                    closed.path.display()
+                ),
+                Instant::now(),
+            ));
+            return;
+        }
+
+        let name = closed.name.clone();
+        match closed.reopen() {
+            Ok(tab) => {
+                self.add_tab(tab);
+                self.select_tab(self.tab_mgr.tabs.len() - 1);
+                self.status_message = Some((format!("Reopened {}", name), Instant::now()));
+            }
+            Err(e) => {
+                self.status_message =
+                    Some((format!("Cannot reopen {}: {:#}", name, e), Instant::now()));
+            }
+        }
+    }
+
     /// Cancel the pending tab close and return to previous mode
     fn cancel_pending_close(&mut self) {
         self.pending_close_tab = None;
@@ -496,9 +1067,9 @@ impl App {
             .apply_filter(matching_indices, pattern);
     }
 
-    pub fn append_filter_results(&mut self, new_matching_indices: Vec<usize>) {
+    pub fn append_filter_results(&mut self, new_matching_indices: Vec<usize>) -> Vec<usize> {
         self.active_tab_mut()
-            .append_filter_results(new_matching_indices);
+            .append_filter_results(new_matching_indices)
     }
 
     pub fn merge_partial_filter_results(
@@ -515,16 +1086,17 @@ impl App {
         let selected = tab.aggregation_view.selected_row;
 
         if let Some(result) = tab.source.aggregation_result.take() {
-            if let Some(group) = result.groups.get(selected) {
-                let drill_pattern = group
-                    .key
-                    .iter()
-                    .map(|(name, value)| format!("{} == \"{}\"", name, value))
-                    .collect::<Vec<_>>()
-                    .join(" & ");
+            if let Some(row) = result.rows().get(selected) {
+                let drill_pattern = row.drill_down_pattern();
+                tab.push_jump();
 
                 tab.source.filter.drill_down_pattern = tab.source.filter.pattern.clone();
-                tab.source.line_indices = group.line_indices.clone();
+                let mut indices = match result.row_severity(row) {
+                    Some(severity) if result.lines_deferred => tab.lines_with_severity(severity),
+                    _ => result.row_line_indices(row),
+                };
+                tab.source.project_since_marker(&mut indices);
+                tab.source.line_indices = indices.into();
                 tab.source.mode = ViewMode::Filtered;
                 tab.source.filter.pattern = Some(drill_pattern);
                 tab.source.filter.state = FilterState::Complete {
@@ -551,40 +1123,172 @@ impl App {
         }
     }
 
-    fn maybe_compute_aggregation(&mut self) {
+    /// Fold newly merged filter matches into the running aggregation, so
+    /// each batch only reads its own lines. `fresh` means the batch
+    /// replaced the previous results: counting restarts from `line_indices`.
+    fn update_aggregation(&mut self, mut batch: Vec<usize>, fresh: bool) {
+        use crate::filter::aggregation::AggregationAccumulator;
+
         let tab = self.active_tab_mut();
-        if let Some((ref agg, ref parser)) = tab.source.filter.pending_aggregation {
-            let agg = agg.clone();
-            let parser = parser.clone();
-            let indices = tab.source.line_indices.clone();
-            let mut reader = match tab.source.reader.lock() {
-                Ok(guard) => guard,
-                Err(poisoned) => poisoned.into_inner(),
-            };
-            let result = crate::filter::aggregation::AggregationResult::compute(
-                &mut *reader,
-                &indices,
-                &agg,
-                &parser,
-            );
-            drop(reader);
-            let tab = self.active_tab_mut();
-            tab.source.aggregation_result = Some(result);
-            tab.source.mode = ViewMode::Aggregation;
-            tab.aggregation_view = tab::AggregationViewState::default();
+        let Some((agg, parser)) = tab.source.filter.pending_aggregation.clone() else {
+            tab.source.filter.aggregation_progress = None;
+            return;
+        };
+        let accumulator = match tab.source.filter.aggregation_progress.take() {
+            Some(accumulator) if !fresh => {
+                tab.source.project_since_marker(&mut batch);
+                Some(accumulator)
+            }
+            _ => None,
+        };
+        let (mut accumulator, batch) = match accumulator {
+            Some(accumulator) => (accumulator, batch),
+            None => (
+                AggregationAccumulator::new(agg, parser),
+                tab.source.line_indices.to_vec(),
+            ),
+        };
+        // The old snapshot shares the group lines; dropping it first lets
+        // the batch extend them in place instead of copying every group
+        tab.source.aggregation_result = None;
+        let mut reader = match tab.source.reader.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        accumulator.add(&mut *reader, &batch);
+        drop(reader);
+        tab.source.aggregation_result = Some(accumulator.snapshot());
+        tab.source.filter.aggregation_progress = Some(accumulator);
+        tab.source.mode = ViewMode::Aggregation;
+        tab.aggregation_view = tab::AggregationViewState::default();
+    }
+
+    /// Switch the active source to the next renderer preset (or raw) and
+    /// report which one is in use.
+    fn cycle_renderer(&mut self) {
+        if self.active_tab().is_combined {
+            self.status_message = Some((
+                "Renderers are per source: cycle them in the source's own tab".to_string(),
+                Instant::now(),
+            ));
+            return;
         }
+        let registry = Arc::clone(&self.preset_registry);
+        let source = &mut self.active_tab_mut().source;
+        source.cycle_renderer(&registry.all_preset_names());
+        let label = match &source.renderer_override {
+            Some(renderer) => renderer.label().to_string(),
+            None if source.renderer_names.is_empty() => "auto-detect".to_string(),
+            None => format!("configured ({})", source.renderer_names.join(", ")),
+        };
+        self.status_message = Some((format!("Renderer: {}", label), Instant::now()));
     }
 
     pub fn clear_filter(&mut self) {
         self.active_tab_mut().clear_filter();
     }
 
+    /// Stop the active tab's filter scan and show all lines again.
+    fn clear_active_filter(&mut self) {
+        self.filter.pending_at = None;
+        FilterOrchestrator::cancel(&mut self.active_tab_mut().source);
+        self.active_tab_mut().source.filter.receiver = None;
+        self.clear_filter();
+    }
+
+    /// Return to the position before the active tab's last jump. Going back
+    /// past a filter's application shows all lines again.
+    fn jump_back(&mut self) {
+        let tab = self.active_tab_mut();
+        let current = tab.jump_position();
+        match tab.jumps.back(current) {
+            Some(entry) => self.restore_jump(entry),
+            None => self.status_message = Some(("No earlier jumps".to_string(), Instant::now())),
+        }
+    }
+
+    /// Step forward through positions left with `jump_back`.
+    fn jump_forward(&mut self) {
+        let tab = self.active_tab_mut();
+        let current = tab.jump_position();
+        match tab.jumps.forward(current) {
+            Some(entry) => self.restore_jump(entry),
+            None => self.status_message = Some(("No newer jumps".to_string(), Instant::now())),
+        }
+    }
+
+    /// Go to a jump list position, first returning to the view it was
+    /// recorded in: back to the groups of a drilled-into aggregation, or the
+    /// entry's filter re-run (instant when its results are cached). The
+    /// entry's line is selected again once the filter's results arrive.
+    fn restore_jump(&mut self, entry: JumpEntry) {
+        let tab = self.active_tab();
+        let current = tab.jump_position();
+        if current.view != entry.view || current.filter != entry.filter {
+            let back_to_groups = entry.view == ViewMode::Aggregation
+                && tab.source.filter.drill_down_aggregation.is_some()
+                && tab.source.filter.drill_down_pattern.as_deref()
+                    == entry.filter.as_ref().map(|(pattern, _)| pattern.as_str());
+            match entry.filter.clone() {
+                _ if back_to_groups => self.aggregation_back(),
+                None => self.clear_active_filter(),
+                Some((pattern, mode)) => {
+                    self.filter.pending_at = None;
+                    let tab = self.active_tab_mut();
+                    FilterOrchestrator::cancel(&mut tab.source);
+                    match tab.start_filter(pattern, mode) {
+                        Ok(()) => tab.source.filter.origin_line = Some(entry.line),
+                        Err(e) => self.status_message = Some((e, Instant::now())),
+                    }
+                }
+            }
+        }
+        self.active_tab_mut().restore_jump(&entry);
+    }
+
+    /// Turn on low-resource mode, saying what it defers.
+    pub fn enable_low_resource(&mut self) {
+        self.low_resource = true;
+        self.status_message = Some((
+            "Low-resource mode: indexes are built only on request (I), files are polled less often"
+                .to_string(),
+            Instant::now(),
+        ));
+    }
+
+    /// Set the live-preview scan budget from `confirm_scan_mb` (0 disables
+    /// the guard).
+    pub fn set_confirm_scan_mb(&mut self, mb: Option<u64>) {
+        let mb = mb.unwrap_or(DEFAULT_CONFIRM_SCAN_MB);
+        self.confirm_scan_bytes = (mb > 0).then(|| mb.saturating_mul(1024 * 1024));
+    }
+
     /// Trigger live filter preview based on current input.
+    ///
+    /// A filter that would scan more than the `confirm_scan_bytes` budget
+    /// without index acceleration is not previewed; pressing Enter runs it.
     pub fn trigger_filter_preview(&mut self) {
         let pattern = self.get_input().to_string();
-        let mode = self.filter.current_mode;
+        let mode = self.filter.effective_mode(&pattern);
 
         if !pattern.is_empty() && self.is_regex_valid() {
+            let scan = FilterOrchestrator::unaccelerated_scan_bytes(
+                &self.active_tab().source,
+                &pattern,
+                mode,
+            );
+            if let (Some(bytes), Some(budget)) = (scan, self.confirm_scan_bytes) {
+                if bytes > budget {
+                    self.status_message = Some((
+                        format!(
+                            "Preview paused: would scan {} without an index — Enter to run",
+                            crate::source::format_bytes(bytes)
+                        ),
+                        Instant::now(),
+                    ));
+                    return;
+                }
+            }
             let tab = self.active_tab_mut();
             tab.source.filter.pattern = Some(pattern.clone());
             tab.source.filter.mode = mode;
@@ -592,12 +1296,102 @@ impl App {
                 self.status_message = Some((e, Instant::now()));
                 self.active_tab_mut().source.filter.state = FilterState::Inactive;
             }
+        } else if self.active_tab().source.filter.min_severity.is_some()
+            || !self.active_tab().source.filter.hidden_severities.is_empty()
+        {
+            // Text cleared: keep filtering by severity alone
+            let tab = self.active_tab_mut();
+            tab.source.filter.pattern = None;
+            let min = tab.source.filter.min_severity;
+            if let Err(e) = tab.set_min_severity(min) {
+                self.status_message = Some((e, Instant::now()));
+            }
         } else {
             self.clear_filter();
             self.active_tab_mut().source.filter.receiver = None;
         }
     }
 
+    /// Offer to build an index the first time a large unindexed file is
+    /// filtered.
+    fn suggest_index_build(&mut self) {
+        if !self.suggest_index || !self.active_tab().should_suggest_index() {
+            return;
+        }
+        self.active_tab_mut().source.index_suggested = true;
+        self.status_message = Some((
+            "Large file without an index — press I to build one for faster filtering".to_string(),
+            Instant::now(),
+        ));
+    }
+
+    /// Start a background index build for the active tab.
+    fn build_index(&mut self) {
+        let tab = self.active_tab_mut();
+        let message = match tab.start_index_build() {
+            Ok(()) => format!("Building index for {}...", tab.source.name),
+            Err(e) => e,
+        };
+        self.status_message = Some((message, Instant::now()));
+    }
+
+    /// Attach finished background index builds and re-run the tab's filter so
+    /// it can use the new index.
+    pub fn poll_index_builds(&mut self) {
+        for tab in &mut self.tab_mgr.tabs {
+            let Some(result) = tab.poll_index_build() else {
+                continue;
+            };
+            let message = match result {
+                Ok(()) => {
+                    if let Some((pattern, mode)) = tab.source.filter.scan() {
+                        if let Err(e) =
+                            FilterOrchestrator::trigger(&mut tab.source, pattern, mode, None)
+                        {
+                            tab.source.filter.state = FilterState::Inactive;
+                            self.status_message = Some((e, Instant::now()));
+                            continue;
+                        }
+                    }
+                    format!("Index built for {}", tab.source.name)
+                }
+                Err(e) => match tab.source.index_disabled {
+                    Some(reason) => index_disabled_message(&[tab.source.name.as_str()], reason),
+                    None => format!("Index build failed for {}: {}", tab.source.name, e),
+                },
+            };
+            self.status_message = Some((message, Instant::now()));
+        }
+    }
+
+    /// Attach `--wait` tabs whose file has appeared. `force_check` looks for
+    /// the files even without a directory event.
+    pub fn attach_waiting_tabs(&mut self, force_check: bool) {
+        let encoding = self.encoding;
+        let mut attached = false;
+        for tab in &mut self.tab_mgr.tabs {
+            if !tab.is_waiting() {
+                continue;
+            }
+            let name = tab.source.name.clone();
+            let message = match tab.attach_if_created(force_check) {
+                Ok(false) => continue,
+                Ok(true) => {
+                    attached = true;
+                    if encoding != Encoding::default() {
+                        tab.set_encoding(encoding);
+                    }
+                    format!("{} appeared, following it", name)
+                }
+                Err(e) => format!("{}: {:#}", name, e),
+            };
+            self.status_message = Some((message, Instant::now()));
+        }
+        if attached {
+            self.tab_mgr.ensure_combined_tabs();
+        }
+    }
+
     /// Enter filter input mode
     pub fn start_filter_input(&mut self) {
         self.input.mode = InputMode::EnteringFilter;
@@ -606,19 +1400,113 @@ impl App {
         let tab = self.active_tab_mut();
         let current_line = tab.viewport.selected_line();
         tab.source.filter.origin_line = Some(current_line);
+        let from = tab.jump_position();
+        tab.jumps.set_pending(from);
     }
 
-    /// Cancel filter input and return to normal mode
-    pub fn cancel_filter_input(&mut self) {
-        self.input.mode = InputMode::Normal;
-        self.input.clear();
-        self.filter.reset_history_index();
+    /// Whether the active tab's filter is still scanning
+    pub fn is_filter_processing(&self) -> bool {
+        matches!(
+            self.active_tab().source.filter.state,
+            FilterState::Processing { .. }
+        )
     }
 
-    /// Enter line jump input mode
-    pub fn start_line_jump_input(&mut self) {
-        self.input.mode = InputMode::EnteringLineJump;
-        self.input.clear();
+    /// Run the filter being typed right away, skipping the debounce and the
+    /// preview scan budget. Returns the pattern; history and the jump list
+    /// are left to the caller, so applying while the prompt stays open
+    /// records nothing.
+    fn apply_filter_input(&mut self) -> String {
+        self.filter.pending_at = None;
+        let pattern = self.input.buffer.clone();
+        let mode = self.filter.effective_mode(&pattern);
+        if !pattern.is_empty() && self.is_regex_valid() {
+            let tab = self.active_tab_mut();
+            tab.source.filter.pattern = Some(pattern.clone());
+            tab.source.filter.mode = mode;
+            if let Err(e) =
+                FilterOrchestrator::trigger(&mut tab.source, pattern.clone(), mode, None)
+            {
+                self.status_message = Some((e, Instant::now()));
+                self.active_tab_mut().source.filter.state = FilterState::Inactive;
+            } else {
+                self.suggest_index_build();
+            }
+        }
+        pattern
+    }
+
+    /// Whether Tab in the filter prompt may complete a name rather than
+    /// cycling the mode: in query mode, with a parser or field name partly
+    /// typed. Names are only looked up when completing.
+    pub fn can_complete_query(&self) -> bool {
+        self.filter.current_mode.is_query()
+            && completion::slot(&self.input.buffer, self.input.cursor).is_some()
+    }
+
+    /// The names completing the parser or field name partly typed under the
+    /// cursor (from the parser names and the fields seen in the active
+    /// source's newest lines), and the text they share beyond it.
+    fn query_completions(&self) -> Option<(Vec<String>, String)> {
+        let slot = completion::slot(&self.input.buffer, self.input.cursor)?;
+        let fields = if slot.fields {
+            let reader = self.active_tab().source.reader.clone();
+            let mut reader = match reader.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            completion::sample_field_names(&mut *reader)
+        } else {
+            Vec::new()
+        };
+        let prefix = &self.input.buffer[slot.start..self.input.cursor];
+        let names = completion::names_for(slot, fields);
+        Some(completion::complete(&names, prefix))
+    }
+
+    /// Complete the name under the cursor. Several matches are extended to
+    /// their shared prefix and listed in the status bar; a name nothing
+    /// completes leaves Tab cycling the mode.
+    fn complete_query_field(&mut self) {
+        let Some((matches, insert)) = self.query_completions() else {
+            return;
+        };
+        if matches.is_empty() {
+            self.handle_filter_input_event(event::AppEvent::ToggleFilterMode);
+            return;
+        }
+
+        for c in insert.chars() {
+            self.input.input_char(c);
+        }
+        if !insert.is_empty() {
+            self.filter.validate_regex(&self.input.buffer);
+            FilterOrchestrator::cancel(&mut self.active_tab_mut().source);
+            self.filter.schedule_debounce();
+        }
+        let message = match matches.len() {
+            1 => return,
+            n if n > MAX_COMPLETIONS_SHOWN => format!(
+                "{} … ({} matches)",
+                matches[..MAX_COMPLETIONS_SHOWN].join("  "),
+                n
+            ),
+            _ => matches.join("  "),
+        };
+        self.status_message = Some((message, Instant::now()));
+    }
+
+    /// Cancel filter input and return to normal mode
+    pub fn cancel_filter_input(&mut self) {
+        self.input.mode = InputMode::Normal;
+        self.input.clear();
+        self.filter.reset_history_index();
+    }
+
+    /// Enter line jump input mode
+    pub fn start_line_jump_input(&mut self) {
+        self.input.mode = InputMode::EnteringLineJump;
+        self.input.clear();
     }
 
     /// Cancel line jump input and return to normal mode
@@ -643,6 +1531,104 @@ impl App {
         self.active_tab_mut().jump_to_start();
     }
 
+    /// Select the first (or last) visible line at or above `min`, telling
+    /// the user when there is none.
+    fn jump_to_severity(&mut self, min: Severity, last: bool) {
+        let tab = self.active_tab_mut();
+        let from = tab.jump_position();
+        if tab.jump_to_severity(min, last) {
+            tab.jumps.push(from);
+        } else {
+            let label = min.label().unwrap_or("matching");
+            self.status_message = Some((format!("No {} lines", label), Instant::now()));
+        }
+    }
+
+    /// Step the active tab's severity floor: off → info → warn → error → off.
+    /// The floor is combined with any text filter in one pass.
+    fn cycle_min_severity(&mut self) {
+        let next = match self.active_tab().source.filter.min_severity {
+            None => Some(Severity::Info),
+            Some(Severity::Info) => Some(Severity::Warn),
+            Some(Severity::Warn) => Some(Severity::Error),
+            Some(_) => None,
+        };
+        self.set_min_severity(next);
+    }
+
+    /// Set the active tab's severity floor (`None` turns it off).
+    fn set_min_severity(&mut self, min: Option<Severity>) {
+        let tab = self.active_tab_mut();
+        if tab.source.mode == ViewMode::Aggregation {
+            return;
+        }
+        let message = match tab.set_min_severity(min) {
+            Err(e) => e,
+            Ok(()) => match min.and_then(|s| s.label()) {
+                Some(level) => format!("Showing {} and above", level),
+                None => "Severity filter off".to_string(),
+            },
+        };
+        self.status_message = Some((message, Instant::now()));
+    }
+
+    /// Hide lines of `level` in the active tab, or show them again if
+    /// hidden; `None` shows every level. Combines with the text filter and
+    /// severity floor.
+    fn toggle_hidden_severity(&mut self, level: Option<Severity>) {
+        let tab = self.active_tab_mut();
+        if tab.source.mode == ViewMode::Aggregation {
+            return;
+        }
+        let result = match level {
+            Some(level) => tab.toggle_hidden_severity(level),
+            None => tab.set_hidden_severities(Vec::new()).map(|()| false),
+        };
+        let label = level.and_then(|s| s.label());
+        let message = match (result, label) {
+            (Err(e), _) => e,
+            (Ok(true), Some(label)) => format!("Hiding {} lines", label),
+            (Ok(false), Some(label)) => format!("Showing {} lines", label),
+            (Ok(_), None) => "Showing all levels".to_string(),
+        };
+        self.status_message = Some((message, Instant::now()));
+    }
+
+    fn show_severity_breakdown(&mut self) {
+        let tab = self.active_tab_mut();
+        if tab.source.mode == ViewMode::Aggregation {
+            return;
+        }
+        if let Some(counted) = tab.show_severity_breakdown() {
+            self.status_message = Some((
+                format!("Severity breakdown of {} lines", counted),
+                Instant::now(),
+            ));
+        }
+    }
+
+    /// Show severity breakdowns that finished counting in the background.
+    pub fn poll_severity_breakdowns(&mut self) {
+        for tab in &mut self.tab_mgr.tabs {
+            if let Some(counted) = tab.poll_severity_breakdown() {
+                self.status_message = Some((
+                    format!("Severity breakdown of {} lines", counted),
+                    Instant::now(),
+                ));
+            }
+        }
+    }
+
+    fn toggle_mouse_capture(&mut self) {
+        self.mouse_capture = !self.mouse_capture;
+        let message = if self.mouse_capture {
+            "Mouse capture on"
+        } else {
+            "Mouse released: select text with the terminal, M to re-capture"
+        };
+        self.status_message = Some((message.to_string(), Instant::now()));
+    }
+
     /// Apply an event to the application state.
     ///
     /// Central event dispatcher — delegates to concern-focused handler methods.
@@ -673,7 +1659,8 @@ impl App {
             | AppEvent::CloseCurrentTab
             | AppEvent::CloseSelectedTab
             | AppEvent::ConfirmCloseTab
-            | AppEvent::CancelCloseTab => self.handle_tab_event(event),
+            | AppEvent::CancelCloseTab
+            | AppEvent::ReopenLastClosed => self.handle_tab_event(event),
 
             // Source panel
             AppEvent::FocusSourcePanel
@@ -684,21 +1671,26 @@ impl App {
             | AppEvent::SelectSource
             | AppEvent::CopySourcePath
             | AppEvent::CopySelectedLine => self.handle_source_panel_event(event),
+            AppEvent::CopyFilter => self.copy_filter(),
+            AppEvent::PasteFilter => self.paste_filter(),
 
             // Filter input
             AppEvent::StartFilterInput
             | AppEvent::FilterInputChar(_)
             | AppEvent::FilterInputBackspace
             | AppEvent::FilterInputSubmit
+            | AppEvent::FilterInputApplyKeepOpen
             | AppEvent::FilterInputCancel
+            | AppEvent::StopFilterScan
             | AppEvent::ClearFilter
             | AppEvent::ToggleFilterMode
+            | AppEvent::CompleteQueryField
             | AppEvent::ToggleCaseSensitivity
+            | AppEvent::ToggleNegate
             | AppEvent::CursorLeft
             | AppEvent::CursorRight
             | AppEvent::CursorHome
-            | AppEvent::CursorEnd
-            | AppEvent::StartFilter { .. } => self.handle_filter_input_event(event),
+            | AppEvent::CursorEnd => self.handle_filter_input_event(event),
 
             // Filter progress
             AppEvent::FilterProgress(_)
@@ -713,16 +1705,44 @@ impl App {
 
             // Help overlay
             AppEvent::ShowHelp
+            | AppEvent::ShowCommandHelp
             | AppEvent::HideHelp
             | AppEvent::ScrollHelpDown
-            | AppEvent::ScrollHelpUp => self.handle_help_event(event),
+            | AppEvent::ScrollHelpUp
+            | AppEvent::StartHelpSearch
+            | AppEvent::HelpSearchChar(_)
+            | AppEvent::HelpSearchBackspace
+            | AppEvent::FinishHelpSearch
+            | AppEvent::ClearHelpSearch => self.handle_help_event(event),
+            AppEvent::ShowErrors
+            | AppEvent::HideErrors
+            | AppEvent::ScrollErrorsDown
+            | AppEvent::ScrollErrorsUp => self.handle_errors_event(event),
 
             // Line jump
             AppEvent::StartLineJumpInput
             | AppEvent::LineJumpInputChar(_)
             | AppEvent::LineJumpInputBackspace
             | AppEvent::LineJumpInputSubmit
-            | AppEvent::LineJumpInputCancel => self.handle_line_jump_event(event),
+            | AppEvent::LineJumpInputCancel
+            | AppEvent::JumpToLine(_) => self.handle_line_jump_event(event),
+
+            // Command line
+            AppEvent::StartCommandInput => {
+                self.input.mode = InputMode::EnteringCommand;
+                self.input.clear();
+            }
+            AppEvent::CommandInputChar(c) => self.input.input_char(c),
+            AppEvent::CommandInputBackspace => self.input.input_backspace(),
+            AppEvent::CommandInputCancel => {
+                self.input.mode = InputMode::Normal;
+                self.input.clear();
+            }
+            AppEvent::CommandFailed(message) => {
+                self.status_message = Some((message, Instant::now()));
+            }
+            AppEvent::ApplyFilter(pattern) => self.apply_command_filter(pattern),
+            AppEvent::SetMinSeverity(min) => self.set_min_severity(min),
 
             // Filter history
             AppEvent::HistoryUp | AppEvent::HistoryDown => self.handle_history_event(event),
@@ -736,13 +1756,12 @@ impl App {
 
             // Mode toggles
             AppEvent::ToggleFollowMode => self.toggle_follow_mode(),
-            AppEvent::DisableFollowMode => {
-                self.active_tab_mut().source.follow_mode = false;
-            }
+            AppEvent::DisableFollowMode => self.active_tab_mut().pause_follow(),
             AppEvent::ToggleRawMode => {
                 let tab = self.active_tab_mut();
-                tab.source.raw_mode = !tab.source.raw_mode;
+                tab.source.raw_mode = tab.source.raw_mode.next();
             }
+            AppEvent::CycleRenderer => self.cycle_renderer(),
             AppEvent::ToggleLineWrap => {
                 let tab = self.active_tab_mut();
                 tab.source.line_wrap = !tab.source.line_wrap;
@@ -751,10 +1770,16 @@ impl App {
                 let tab = self.active_tab_mut();
                 tab.source.show_timestamps = !tab.source.show_timestamps;
             }
+            AppEvent::ToggleReverseOrder => self.active_tab_mut().toggle_reverse_order(),
+            AppEvent::ToggleSinceMarker => self.active_tab_mut().toggle_since_marker(),
+            AppEvent::ToggleWordHighlight => self.toggle_word_highlight(),
+            AppEvent::WordCursorLeft => self.move_word_cursor(false),
+            AppEvent::WordCursorRight => self.move_word_cursor(true),
 
             // Line expansion
             AppEvent::ToggleLineExpansion => self.active_tab_mut().toggle_expansion(),
             AppEvent::CollapseAll => self.active_tab_mut().collapse_all(),
+            AppEvent::ExpandAllVisible => self.active_tab_mut().expand_all_visible(),
 
             // Aggregation
             AppEvent::AggregationDown
@@ -779,8 +1804,78 @@ impl App {
                 }
             }
 
+            // Index
+            AppEvent::BuildIndex => self.build_index(),
+
             // Mouse
             AppEvent::MouseClick { column, row } => self.handle_mouse_click(column, row),
+            AppEvent::MouseDoubleClick { column, row } => {
+                self.handle_mouse_double_click(column, row)
+            }
+            AppEvent::MouseRightClick { column, row } => self.open_context_menu(column, row),
+            AppEvent::ContextMenuUp => {
+                if let Some(menu) = self.context_menu.as_mut() {
+                    menu.select_prev();
+                }
+            }
+            AppEvent::ContextMenuDown => {
+                if let Some(menu) = self.context_menu.as_mut() {
+                    menu.select_next();
+                }
+            }
+            AppEvent::ContextMenuSelect => {
+                if let Some(menu) = self.context_menu {
+                    self.run_context_menu_action(menu.selected_action());
+                }
+            }
+            AppEvent::ContextMenuClose => self.close_context_menu(),
+
+            // Quick filter
+            AppEvent::QuickFilter => self.start_quick_filter(),
+            AppEvent::CopyField => self.start_copy_field(),
+            AppEvent::FieldPickerUp => {
+                if let Some(picker) = self.field_picker.as_mut() {
+                    picker.select_prev();
+                }
+            }
+            AppEvent::FieldPickerDown => {
+                if let Some(picker) = self.field_picker.as_mut() {
+                    picker.select_next();
+                }
+            }
+            AppEvent::FieldPickerSelect => {
+                let picked = self
+                    .field_picker
+                    .as_ref()
+                    .and_then(|p| Some((p.action, p.selected_item()?.clone())));
+                self.close_field_picker();
+                match picked {
+                    Some((PickerAction::Filter, item)) => self.apply_quick_filter(item),
+                    Some((PickerAction::Copy, item)) => self.copy_field_value(&item),
+                    None => {}
+                }
+            }
+            AppEvent::FieldPickerClose => self.close_field_picker(),
+            AppEvent::TraceSelectedLine => self.open_trace_view(),
+            AppEvent::JumpToSourceTab => self.jump_to_source_tab(),
+            AppEvent::JumpToTabAlert => self.jump_to_tab_alert(),
+            AppEvent::JumpToFirstSeverity(min) => self.jump_to_severity(min, false),
+            AppEvent::JumpToLastSeverity(min) => self.jump_to_severity(min, true),
+            AppEvent::JumpBack => self.jump_back(),
+            AppEvent::JumpForward => self.jump_forward(),
+            AppEvent::CycleMinSeverity => self.cycle_min_severity(),
+            AppEvent::EnterHideLevelMode => {
+                self.input.mode = InputMode::HideLevelPending;
+                self.status_message = Some((
+                    "Hide/show level: t d i w e f (H shows all)".to_string(),
+                    Instant::now(),
+                ));
+            }
+            AppEvent::ExitHideLevelMode => self.input.mode = InputMode::Normal,
+            AppEvent::ToggleHiddenSeverity(level) => self.toggle_hidden_severity(Some(level)),
+            AppEvent::ShowAllSeverities => self.toggle_hidden_severity(None),
+            AppEvent::ShowSeverityBreakdown => self.show_severity_breakdown(),
+            AppEvent::ToggleMouseCapture => self.toggle_mouse_capture(),
 
             // System
             AppEvent::DismissWarning => self.warning_popup = None,
@@ -800,8 +1895,14 @@ impl App {
             AppEvent::ScrollUp => self.scroll_up(),
             AppEvent::PageDown(size) => self.page_down(size),
             AppEvent::PageUp(size) => self.page_up(size),
-            AppEvent::JumpToStart => self.jump_to_start(),
-            AppEvent::JumpToEnd => self.jump_to_end(),
+            AppEvent::JumpToStart => {
+                self.active_tab_mut().push_jump();
+                self.jump_to_start();
+            }
+            AppEvent::JumpToEnd => {
+                self.active_tab_mut().push_jump();
+                self.jump_to_end();
+            }
             AppEvent::MouseScrollDown(lines) => self.mouse_scroll_down(lines),
             AppEvent::MouseScrollUp(lines) => self.mouse_scroll_up(lines),
             AppEvent::ViewportDown => self.viewport_down(),
@@ -818,6 +1919,7 @@ impl App {
                     self.select_tab(tab_idx);
                 }
             }
+            #[allow(clippy::collapsible_match)]
             AppEvent::CloseCurrentTab => {
                 if self.tab_mgr.active_combined.is_none() {
                     let idx = self.tab_mgr.active;
@@ -835,6 +1937,7 @@ impl App {
             },
             AppEvent::ConfirmCloseTab => self.confirm_pending_close(),
             AppEvent::CancelCloseTab => self.cancel_pending_close(),
+            AppEvent::ReopenLastClosed => self.reopen_last_closed(),
             _ => {}
         }
     }
@@ -877,34 +1980,57 @@ impl App {
                 self.filter.schedule_debounce();
             }
             AppEvent::FilterInputSubmit => {
-                self.filter.pending_at = None;
-                let pattern = self.input.buffer.clone();
-                let mode = self.filter.current_mode;
-                if !pattern.is_empty() && self.is_regex_valid() {
-                    let tab = self.active_tab_mut();
-                    tab.source.filter.pattern = Some(pattern.clone());
-                    tab.source.filter.mode = mode;
-                    if let Err(e) =
-                        FilterOrchestrator::trigger(&mut tab.source, pattern.clone(), mode, None)
-                    {
-                        self.status_message = Some((e, Instant::now()));
-                        self.active_tab_mut().source.filter.state = FilterState::Inactive;
-                    }
+                let pattern = self.apply_filter_input();
+                let tab = self.active_tab_mut();
+                if pattern.is_empty() {
+                    tab.jumps.clear_pending();
+                } else {
+                    tab.jumps.push_pending();
                 }
-                self.filter.add_to_history(pattern, mode);
+                self.filter
+                    .add_to_history(pattern, self.filter.current_mode);
                 self.active_tab_mut().source.filter.origin_line = None;
                 self.cancel_filter_input();
             }
+            AppEvent::FilterInputApplyKeepOpen => {
+                self.apply_filter_input();
+            }
             AppEvent::FilterInputCancel => {
-                self.filter.pending_at = None;
-                FilterOrchestrator::cancel(&mut self.active_tab_mut().source);
+                match self.filter_esc {
+                    FilterEsc::Clear => {
+                        self.filter.pending_at = None;
+                        self.active_tab_mut().jumps.clear_pending();
+                        FilterOrchestrator::cancel(&mut self.active_tab_mut().source);
+                    }
+                    FilterEsc::Keep => {
+                        // Preview what was typed last rather than dropping it
+                        if self.filter.pending_at.take().is_some() {
+                            self.trigger_filter_preview();
+                        }
+                        let tab = self.active_tab_mut();
+                        if tab.source.filter.pattern.is_some() {
+                            tab.jumps.push_pending();
+                        } else {
+                            tab.jumps.clear_pending();
+                        }
+                        tab.source.filter.origin_line = None;
+                    }
+                }
                 self.cancel_filter_input();
             }
-            AppEvent::ClearFilter => {
+            AppEvent::StopFilterScan => {
                 self.filter.pending_at = None;
-                FilterOrchestrator::cancel(&mut self.active_tab_mut().source);
-                self.active_tab_mut().source.filter.receiver = None;
-                self.clear_filter();
+                let matches = self.active_tab_mut().stop_filter_scan();
+                self.status_message = Some((
+                    format!("Scan stopped: {} matches so far", matches),
+                    Instant::now(),
+                ));
+            }
+            AppEvent::ClearFilter => {
+                if self.active_tab().source.filter.pattern.is_some() {
+                    self.active_tab_mut().push_jump();
+                }
+                self.clear_active_filter();
             }
             AppEvent::ToggleFilterMode => {
                 self.filter.current_mode.cycle_mode();
@@ -912,25 +2038,21 @@ impl App {
                 FilterOrchestrator::cancel(&mut self.active_tab_mut().source);
                 self.filter.schedule_debounce();
             }
+            AppEvent::CompleteQueryField => self.complete_query_field(),
             AppEvent::ToggleCaseSensitivity => {
                 self.filter.current_mode.toggle_case_sensitivity();
                 FilterOrchestrator::cancel(&mut self.active_tab_mut().source);
                 self.filter.schedule_debounce();
             }
+            AppEvent::ToggleNegate => {
+                self.filter.current_mode.toggle_negate();
+                FilterOrchestrator::cancel(&mut self.active_tab_mut().source);
+                self.filter.schedule_debounce();
+            }
             AppEvent::CursorLeft => self.input.cursor_left(),
             AppEvent::CursorRight => self.input.cursor_right(),
             AppEvent::CursorHome => self.input.cursor_home(),
             AppEvent::CursorEnd => self.input.cursor_end(),
-            AppEvent::StartFilter { pattern, range, .. } => {
-                let mode = self.filter.current_mode;
-                let tab = self.active_tab_mut();
-                tab.source.filter.pattern = Some(pattern.clone());
-                tab.source.filter.mode = mode;
-                if let Err(e) = FilterOrchestrator::trigger(&mut tab.source, pattern, mode, range) {
-                    self.status_message = Some((e, Instant::now()));
-                    self.active_tab_mut().source.filter.state = FilterState::Inactive;
-                }
-            }
             _ => {}
         }
     }
@@ -946,26 +2068,28 @@ impl App {
                 matches,
                 lines_processed,
             } => {
-                self.merge_partial_filter_results(matches, lines_processed);
-                self.maybe_compute_aggregation();
+                let source = &self.active_tab().source;
+                let fresh = source.filter.needs_clear || source.mode == ViewMode::Normal;
+                self.merge_partial_filter_results(matches.clone(), lines_processed);
+                self.update_aggregation(matches, fresh);
             }
             AppEvent::FilterComplete {
                 indices,
                 incremental,
             } => {
-                if incremental {
-                    self.append_filter_results(indices);
+                // Aggregate only lines new to the view: a catch-up run can
+                // repeat matches the full scan already counted
+                let (added, fresh) = if incremental {
+                    (self.append_filter_results(indices), false)
                 } else {
-                    let pattern = self
-                        .active_tab()
-                        .source
-                        .filter
-                        .pattern
-                        .clone()
-                        .unwrap_or_default();
-                    self.apply_filter(indices, pattern);
-                }
-                self.maybe_compute_aggregation();
+                    let filter = &self.active_tab().source.filter;
+                    let fresh = filter.needs_clear
+                        || !matches!(filter.state, FilterState::Processing { .. });
+                    let pattern = filter.pattern.clone().unwrap_or_default();
+                    self.apply_filter(indices.clone(), pattern);
+                    (indices, fresh)
+                };
+                self.update_aggregation(added, fresh);
                 if self.active_tab().source.follow_mode
                     && self.active_tab().source.mode != ViewMode::Aggregation
                 {
@@ -974,6 +2098,9 @@ impl App {
             }
             AppEvent::FilterError(ref err) => {
                 self.status_message = Some((format!("Filter error: {}", err), Instant::now()));
+                let name = self.active_tab().source.name.clone();
+                self.error_log
+                    .push(format!("Filter error in {}: {}", name, err));
                 self.active_tab_mut().source.filter.state = FilterState::Inactive;
             }
             _ => {}
@@ -984,23 +2111,9 @@ impl App {
         use event::AppEvent;
         match event {
             AppEvent::FileModified { new_total, .. } => {
-                let tab = self.active_tab_mut();
-                tab.source.total_lines = new_total;
-                tab.source.rate_tracker.record(new_total);
-                if tab.source.mode == ViewMode::Normal {
-                    let old = tab.source.line_indices.len();
-                    if new_total > old {
-                        tab.source.line_indices.extend(old..new_total);
-                    }
-                }
-                if let (Some(ref mut ir), Some(ref path)) =
-                    (&mut tab.source.index_reader, &tab.source.source_path)
-                {
-                    ir.refresh(path);
-                }
+                self.active_tab_mut().apply_file_modification(new_total);
                 let should_jump = self.active_tab().source.follow_mode
-                    && self.active_tab().source.mode == ViewMode::Normal
-                    && !self.has_start_filter_in_batch;
+                    && self.active_tab().source.mode == ViewMode::Normal;
                 if should_jump {
                     self.jump_to_end();
                 }
@@ -1020,8 +2133,48 @@ impl App {
     fn handle_help_event(&mut self, event: event::AppEvent) {
         use event::AppEvent;
         match event {
-            AppEvent::ShowHelp => self.help_scroll_offset = Some(0),
-            AppEvent::HideHelp => self.help_scroll_offset = None,
+            AppEvent::ShowHelp => {
+                self.help_scroll_offset = Some(0);
+                self.help_search = None;
+            }
+            AppEvent::ShowCommandHelp => {
+                self.help_scroll_offset = Some(0);
+                self.help_search = Some(HelpSearch {
+                    query: crate::handlers::command::COMMANDS_HEADER.to_string(),
+                    editing: false,
+                });
+            }
+            AppEvent::HideHelp => {
+                self.help_scroll_offset = None;
+                self.help_search = None;
+            }
+            AppEvent::StartHelpSearch => {
+                self.help_search = Some(HelpSearch {
+                    editing: true,
+                    ..self.help_search.take().unwrap_or_default()
+                });
+            }
+            AppEvent::HelpSearchChar(c) => {
+                if let Some(search) = &mut self.help_search {
+                    search.query.push(c);
+                    self.help_scroll_offset = Some(0);
+                }
+            }
+            AppEvent::HelpSearchBackspace => {
+                if let Some(search) = &mut self.help_search {
+                    search.query.pop();
+                    self.help_scroll_offset = Some(0);
+                }
+            }
+            AppEvent::FinishHelpSearch => {
+                if let Some(search) = &mut self.help_search {
+                    search.editing = false;
+                }
+            }
+            AppEvent::ClearHelpSearch => {
+                self.help_search = None;
+                self.help_scroll_offset = Some(0);
+            }
             AppEvent::ScrollHelpDown => {
                 if let Some(offset) = &mut self.help_scroll_offset {
                     *offset = offset.saturating_add(1);
@@ -1036,10 +2189,73 @@ impl App {
         }
     }
 
+    fn handle_errors_event(&mut self, event: event::AppEvent) {
+        use event::AppEvent;
+        match event {
+            AppEvent::ShowErrors => {
+                if self.error_log.is_empty() {
+                    self.status_message = Some(("No errors".to_string(), Instant::now()));
+                } else {
+                    self.errors_scroll_offset = Some(0);
+                }
+            }
+            AppEvent::HideErrors => self.errors_scroll_offset = None,
+            AppEvent::ScrollErrorsDown => {
+                if let Some(offset) = &mut self.errors_scroll_offset {
+                    *offset = offset.saturating_add(1);
+                }
+            }
+            AppEvent::ScrollErrorsUp => {
+                if let Some(offset) = &mut self.errors_scroll_offset {
+                    *offset = offset.saturating_sub(1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Explain, once, which sources had indexing disabled at startup because
+    /// their index location isn't writable.
+    ///
+    /// Also logged to the `!` overlay: later startup notices replace the
+    /// status message.
+    pub fn report_disabled_indexes(&mut self) {
+        let disabled: Vec<(&str, &'static str)> = self
+            .tab_mgr
+            .tabs
+            .iter()
+            .filter_map(|tab| Some((tab.source.name.as_str(), tab.source.index_disabled?)))
+            .collect();
+        let Some(&(_, reason)) = disabled.first() else {
+            return;
+        };
+        let names: Vec<&str> = disabled.iter().map(|(name, _)| *name).collect();
+        let message = index_disabled_message(&names, reason);
+        self.error_log.push(message.clone());
+        self.status_message = Some((message, Instant::now()));
+    }
+
+    /// Keep errors found before the TUI started (config, sources) and point
+    /// at the `!` overlay.
+    pub fn report_startup_errors(&mut self, errors: Vec<String>) {
+        if errors.is_empty() {
+            return;
+        }
+        let noun = if errors.len() == 1 { "error" } else { "errors" };
+        self.status_message = Some((
+            format!("{} config {} — press ! to view", errors.len(), noun),
+            Instant::now(),
+        ));
+        for error in errors {
+            self.error_log.push(error);
+        }
+    }
+
     fn handle_line_jump_event(&mut self, event: event::AppEvent) {
         use event::AppEvent;
         match event {
             AppEvent::StartLineJumpInput => self.start_line_jump_input(),
+            #[allow(clippy::collapsible_match)]
             AppEvent::LineJumpInputChar(c) => {
                 if c.is_ascii_digit() {
                     self.input.input_char(c);
@@ -1048,11 +2264,15 @@ impl App {
             AppEvent::LineJumpInputBackspace => self.input.input_backspace(),
             AppEvent::LineJumpInputSubmit => {
                 if let Ok(line_num) = self.input.buffer.parse::<usize>() {
-                    self.jump_to_line(line_num);
-                    self.active_tab_mut().source.follow_mode = false;
+                    self.handle_line_jump_event(AppEvent::JumpToLine(line_num));
                 }
                 self.cancel_line_jump_input();
             }
+            AppEvent::JumpToLine(line_num) => {
+                self.active_tab_mut().push_jump();
+                self.jump_to_line(line_num);
+                self.active_tab_mut().pause_follow();
+            }
             AppEvent::LineJumpInputCancel => self.cancel_line_jump_input(),
             _ => {}
         }
@@ -1101,7 +2321,7 @@ impl App {
             AppEvent::AggregationDown => {
                 let tab = self.active_tab_mut();
                 if let Some(ref result) = tab.source.aggregation_result {
-                    let max = result.groups.len().saturating_sub(1);
+                    let max = result.rows().len().saturating_sub(1);
                     if tab.aggregation_view.selected_row < max {
                         tab.aggregation_view.selected_row += 1;
                     }
@@ -1122,7 +2342,7 @@ impl App {
             AppEvent::AggregationJumpToEnd => {
                 let tab = self.active_tab_mut();
                 if let Some(ref result) = tab.source.aggregation_result {
-                    tab.aggregation_view.selected_row = result.groups.len().saturating_sub(1);
+                    tab.aggregation_view.selected_row = result.rows().len().saturating_sub(1);
                 }
                 self.active_tab_mut().aggregation_view.ensure_visible();
             }
@@ -1136,6 +2356,25 @@ impl App {
     fn handle_mouse_click(&mut self, column: u16, row: u16) {
         if self.help_scroll_offset.is_some() {
             self.help_scroll_offset = None;
+            self.help_search = None;
+            return;
+        }
+        if self.errors_scroll_offset.is_some() {
+            self.errors_scroll_offset = None;
+            return;
+        }
+
+        if let Some(menu) = self.context_menu {
+            // Click on an item runs it; click-away just closes the menu
+            match menu.action_at(self.layout.log_view, column, row) {
+                Some(action) => self.run_context_menu_action(action),
+                None => self.close_context_menu(),
+            }
+            return;
+        }
+
+        if self.field_picker.is_some() {
+            self.close_field_picker();
             return;
         }
 
@@ -1143,12 +2382,26 @@ impl App {
             InputMode::ConfirmClose
             | InputMode::EnteringFilter
             | InputMode::EnteringLineJump
-            | InputMode::ZPending => return,
+            | InputMode::EnteringCommand
+            | InputMode::ZPending
+            | InputMode::HideLevelPending => return,
             _ => {}
         }
 
         let sp = self.layout.side_panel_sources;
         let lv = self.layout.log_view;
+        let tb = self.layout.tab_bar;
+
+        if row == tb.y && column >= tb.x && column < tb.x + tb.width {
+            let items = tab_bar::layout(&self.tab_mgr, tb.width);
+            match tab_bar::item_at(&items, column - tb.x).map(|item| item.target) {
+                Some(tab_bar::TabBarTarget::Combined(cat)) => self.tab_mgr.select_combined_tab(cat),
+                Some(tab_bar::TabBarTarget::Tab(idx)) => self.select_tab(idx),
+                None => {}
+            }
+            self.input.mode = InputMode::Normal;
+            return;
+        }
 
         if sp.contains_inner(column, row) {
             let inner_row = sp.inner_row(row);
@@ -1182,10 +2435,9 @@ impl App {
             let scroll_pos = tab.viewport.scroll_position();
             let target_index = scroll_pos + inner_row;
 
-            if target_index < tab.source.line_indices.len() {
-                let file_line = tab.source.line_indices[target_index];
+            if let Some(file_line) = tab.viewport.line_at(&tab.source.line_indices, target_index) {
                 tab.select_line(file_line);
-                tab.source.follow_mode = false;
+                tab.pause_follow();
             }
 
             if self.input.mode == InputMode::SourcePanel {
@@ -1193,70 +2445,530 @@ impl App {
             }
         }
     }
-}
 
-/// Minimal base64 encoder for OSC 52 clipboard
-fn base64_encode(data: &[u8]) -> String {
-    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
-    let mut result = String::with_capacity(data.len().div_ceil(3) * 4);
-    for chunk in data.chunks(3) {
-        let b0 = chunk[0] as u32;
-        let b1 = chunk.get(1).copied().unwrap_or(0) as u32;
-        let b2 = chunk.get(2).copied().unwrap_or(0) as u32;
-        let n = (b0 << 16) | (b1 << 8) | b2;
-        result.push(ALPHABET[(n >> 18 & 0x3F) as usize] as char);
-        result.push(ALPHABET[(n >> 12 & 0x3F) as usize] as char);
-        if chunk.len() > 1 {
-            result.push(ALPHABET[(n >> 6 & 0x3F) as usize] as char);
-        } else {
-            result.push('=');
+    /// Select the right-clicked log line and open the context menu on it.
+    fn open_context_menu(&mut self, column: u16, row: u16) {
+        if self.context_menu.is_some() {
+            self.close_context_menu();
+            return;
         }
-        if chunk.len() > 2 {
-            result.push(ALPHABET[(n & 0x3F) as usize] as char);
-        } else {
-            result.push('=');
+        if !matches!(self.input.mode, InputMode::Normal | InputMode::SourcePanel)
+            || self.help_scroll_offset.is_some()
+            || self.errors_scroll_offset.is_some()
+            || !self.layout.log_view.contains_inner(column, row)
+            || self.active_tab().source.mode == ViewMode::Aggregation
+        {
+            return;
+        }
+
+        self.handle_mouse_click(column, row);
+        if self.active_tab().source.line_indices.is_empty() {
+            return;
         }
+        self.context_menu = Some(ContextMenu::new(column, row));
+        self.input.mode = InputMode::ContextMenu;
     }
-    result
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::filter::FilterMode;
-    use event::AppEvent;
-    use std::io::Write;
-    use tempfile::NamedTempFile;
+    fn close_context_menu(&mut self) {
+        self.context_menu = None;
+        if self.input.mode == InputMode::ContextMenu {
+            self.input.mode = InputMode::Normal;
+        }
+    }
 
-    fn create_temp_log_file(lines: &[&str]) -> NamedTempFile {
-        let mut file = NamedTempFile::new().unwrap();
-        for line in lines {
-            writeln!(file, "{}", line).unwrap();
+    /// Close the menu and apply `action` to the selected line.
+    fn run_context_menu_action(&mut self, action: ContextMenuAction) {
+        self.close_context_menu();
+        match action {
+            ContextMenuAction::Copy => self.copy_selected_line(),
+            ContextMenuAction::Expand => self.active_tab_mut().toggle_expansion(),
+            ContextMenuAction::MarkFromHere => self.active_tab_mut().mark_from_selected_line(),
+            ContextMenuAction::FilterToLine => self.filter_to_selected_line(),
         }
-        file.flush().unwrap();
-        file
     }
 
-    #[test]
-    fn test_app_initialization() {
-        let temp_file = create_temp_log_file(&["line1", "line2", "line3"]);
-        let app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+    /// Open the filter prompt prefilled with the selected line's text.
+    fn filter_to_selected_line(&mut self) {
+        let Some(text) = self.selected_line_text() else {
+            return;
+        };
+        let pattern = match self.filter.current_mode {
+            FilterMode::Regex { .. } => regex::escape(&text),
+            FilterMode::Query {} => {
+                self.filter.current_mode = FilterMode::plain();
+                text
+            }
+            FilterMode::Plain { .. } => text,
+        };
+        self.start_filter_input();
+        self.input.set_content(pattern);
+        self.filter.validate_regex(&self.input.buffer);
+        self.filter.schedule_debounce();
+    }
 
-        assert_eq!(app.tab_mgr.tabs.len(), 1);
-        assert_eq!(app.tab_mgr.active, 0);
-        assert_eq!(app.active_tab().source.total_lines, 3);
-        assert!(!app.should_quit);
-        assert!(app.help_scroll_offset.is_none());
+    /// Filter to the selected line's value for a configured default field,
+    /// or let the user pick a field (or word) when none is present.
+    fn start_quick_filter(&mut self) {
+        if self.active_tab().source.mode == ViewMode::Aggregation {
+            return;
+        }
+        let Some(text) = self.selected_line_text() else {
+            return;
+        };
+        let items = quick_filter::candidates(&text);
+        if let Some(item) = quick_filter::default_candidate(&items, &self.quick_filter_fields) {
+            let item = item.clone();
+            self.apply_quick_filter(item);
+            return;
+        }
+        match items.len() {
+            0 => {
+                self.status_message = Some((
+                    "Nothing to filter by on this line".to_string(),
+                    Instant::now(),
+                ));
+            }
+            1 => {
+                let item = items.into_iter().next().unwrap();
+                self.apply_quick_filter(item);
+            }
+            _ => {
+                self.field_picker = Some(FieldPicker::new(items, PickerAction::Filter));
+                self.input.mode = InputMode::FieldPicker;
+            }
+        }
     }
 
-    #[test]
-    fn test_multiple_tabs() {
-        let file1 = create_temp_log_file(&["line1", "line2"]);
-        let file2 = create_temp_log_file(&["a", "b", "c"]);
-        let file3 = create_temp_log_file(&["x"]);
+    /// Copy the selected line's value for a configured copy field, or let
+    /// the user pick one of its fields. Plain-text lines copy the word
+    /// under the cursor instead, turning the word cursor on first if needed.
+    fn start_copy_field(&mut self) {
+        if self.active_tab().source.mode == ViewMode::Aggregation {
+            return;
+        }
+        let Some(text) = self.selected_line_text() else {
+            return;
+        };
+        let items: Vec<QuickFilter> = quick_filter::candidates(&text)
+            .into_iter()
+            .filter(|item| item.field.is_some())
+            .collect();
+        if let Some(item) = quick_filter::default_candidate(&items, &self.copy_fields) {
+            let item = item.clone();
+            self.copy_field_value(&item);
+            return;
+        }
+        match items.len() {
+            0 => {
+                // The highlighted word is found in the line as drawn
+                if let Some(shown) = self.selected_display_text() {
+                    self.copy_word_under_cursor(&shown);
+                }
+            }
+            1 => self.copy_field_value(&items[0]),
+            _ => {
+                self.field_picker = Some(FieldPicker::new(items, PickerAction::Copy));
+                self.input.mode = InputMode::FieldPicker;
+            }
+        }
+    }
 
-        let app = App::new(
-            vec![
+    fn copy_field_value(&mut self, item: &QuickFilter) {
+        let label = format!("Copied {}", item.field.as_deref().unwrap_or("word"));
+        self.copy_to_clipboard(&label, &item.value, "value");
+    }
+
+    /// Copy the highlighted word of an unstructured line, or start
+    /// highlighting so one can be picked.
+    fn copy_word_under_cursor(&mut self, text: &str) {
+        let words = word_ranges(text);
+        let Some(last) = words.len().checked_sub(1) else {
+            self.status_message =
+                Some(("Nothing to copy on this line".to_string(), Instant::now()));
+            return;
+        };
+        match self.active_tab().word_cursor {
+            Some(cursor) => {
+                let word = &text[words[cursor.min(last)].clone()];
+                self.copy_to_clipboard("Copied", word, "word");
+            }
+            None => {
+                self.active_tab_mut().word_cursor = Some(0);
+                self.status_message = Some((
+                    "No fields on this line: pick a word with ←/→, then press C".to_string(),
+                    Instant::now(),
+                ));
+            }
+        }
+    }
+
+    fn close_field_picker(&mut self) {
+        self.field_picker = None;
+        if self.input.mode == InputMode::FieldPicker {
+            self.input.mode = InputMode::Normal;
+        }
+    }
+
+    /// Apply a quick filter to the active tab and remember it in history.
+    fn apply_quick_filter(&mut self, item: QuickFilter) {
+        let mode = item.mode();
+        let pattern = item.pattern.clone();
+        FilterOrchestrator::cancel(&mut self.active_tab_mut().source);
+        let tab = self.active_tab_mut();
+        tab.push_jump();
+        tab.source.filter.pattern = Some(pattern.clone());
+        tab.source.filter.mode = mode;
+        if let Err(e) = FilterOrchestrator::trigger(&mut tab.source, pattern.clone(), mode, None) {
+            self.status_message = Some((e, Instant::now()));
+            self.active_tab_mut().source.filter.state = FilterState::Inactive;
+            return;
+        }
+        self.filter.add_to_history(pattern, mode);
+        self.status_message = Some((format!("Filter: {}", item.label()), Instant::now()));
+    }
+
+    /// Follow the selected line's trace id across every open source.
+    ///
+    /// Opens (or reuses) a merged, timestamp-ordered tab of all sources and
+    /// filters it to the id. The id is matched as case-sensitive plain text,
+    /// so sources in different formats (JSON, logfmt, plain) all match.
+    fn open_trace_view(&mut self) {
+        if self.active_tab().source.mode == ViewMode::Aggregation {
+            return;
+        }
+        let Some(text) = self.selected_line_text() else {
+            return;
+        };
+        let items = quick_filter::candidates(&text);
+        let Some(item) = quick_filter::default_candidate(&items, &self.correlation_fields) else {
+            self.status_message = Some((
+                format!(
+                    "No trace id on this line ({})",
+                    self.correlation_fields.join(", ")
+                ),
+                Instant::now(),
+            ));
+            return;
+        };
+        let value = item.value.clone();
+
+        // Listed alongside the tab the trace started from
+        let source_type = self.active_tab().source_type();
+        let Some(idx) = self
+            .tab_mgr
+            .open_merged_tab(format!("trace {}", value), source_type)
+        else {
+            return;
+        };
+        self.tab_mgr.active = idx;
+        self.tab_mgr.active_combined = None;
+
+        let mode = FilterMode::Plain {
+            case_sensitive: true,
+            negate: false,
+        };
+        let tab = self.active_tab_mut();
+        tab.source.filter.pattern = Some(value.clone());
+        tab.source.filter.mode = mode;
+        if let Err(e) = FilterOrchestrator::trigger(&mut tab.source, value, mode, None) {
+            self.status_message = Some((e, Instant::now()));
+            self.active_tab_mut().source.filter.state = FilterState::Inactive;
+        }
+    }
+
+    /// From a combined view, switch to the selected line's source tab and
+    /// select the same line there.
+    fn jump_to_source_tab(&mut self) {
+        let tab = self.active_tab();
+        if !tab.is_combined {
+            return;
+        }
+        let Some(virtual_line) = tab.source.line_indices.get(tab.selected_line) else {
+            return;
+        };
+        let origin = {
+            let reader = match tab.source.reader.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            reader
+                .as_any()
+                .downcast_ref::<CombinedReader>()
+                .and_then(|combined| combined.source_line(virtual_line))
+                .map(|(source, line)| (source.name.clone(), source.reader.clone(), line))
+        };
+        let Some((name, source_reader, file_line)) = origin else {
+            return;
+        };
+
+        // Names can repeat across project and global sources; the merged
+        // view shares each source tab's reader, so match on that instead
+        let target = self
+            .tab_mgr
+            .tabs
+            .iter()
+            .position(|t| !t.is_combined && Arc::ptr_eq(&t.source.reader, &source_reader));
+        let Some(idx) = target else {
+            self.status_message = Some((
+                format!("Source tab {} is no longer open", name),
+                Instant::now(),
+            ));
+            return;
+        };
+        self.select_tab(idx);
+        let tab = self.active_tab_mut();
+        tab.push_jump();
+        tab.select_line(file_line);
+    }
+
+    /// Remember an alert from a background tab, replacing any older one.
+    pub fn raise_tab_alert(
+        &mut self,
+        source: String,
+        reader: Arc<Mutex<dyn LogReader + Send>>,
+        line: usize,
+        severity: Severity,
+    ) {
+        self.tab_alert = Some(TabAlert {
+            source,
+            reader,
+            line,
+            severity,
+            at: Instant::now(),
+        });
+    }
+
+    /// Switch to the tab of the latest background alert and select its line.
+    fn jump_to_tab_alert(&mut self) {
+        let Some(alert) = self.tab_alert.take() else {
+            self.status_message = Some(("No background alerts".to_string(), Instant::now()));
+            return;
+        };
+        let target = self.tab_mgr.tabs.iter().position(|t| alert.is_from(t));
+        let Some(idx) = target else {
+            self.status_message = Some((
+                format!("Source tab {} is no longer open", alert.source),
+                Instant::now(),
+            ));
+            return;
+        };
+        self.select_tab(idx);
+        let tab = self.active_tab_mut();
+        tab.pause_follow();
+        tab.push_jump();
+        tab.select_line(alert.line);
+    }
+
+    /// Run the configured double-click action on the clicked log line.
+    ///
+    /// The preceding `MouseClick` has already selected the line.
+    fn handle_mouse_double_click(&mut self, column: u16, row: u16) {
+        if self.input.mode != InputMode::Normal
+            || self.help_scroll_offset.is_some()
+            || self.errors_scroll_offset.is_some()
+            || !self.layout.log_view.contains_inner(column, row)
+        {
+            return;
+        }
+
+        match self.double_click_action {
+            DoubleClickAction::Expand => self.active_tab_mut().toggle_expansion(),
+            DoubleClickAction::Copy => self.copy_selected_line(),
+            DoubleClickAction::Disabled => {}
+        }
+    }
+}
+
+/// Minimal base64 encoder for OSC 52 clipboard
+fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut result = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = chunk.get(1).copied().unwrap_or(0) as u32;
+        let b2 = chunk.get(2).copied().unwrap_or(0) as u32;
+        let n = (b0 << 16) | (b1 << 8) | b2;
+        result.push(ALPHABET[(n >> 18 & 0x3F) as usize] as char);
+        result.push(ALPHABET[(n >> 12 & 0x3F) as usize] as char);
+        if chunk.len() > 1 {
+            result.push(ALPHABET[(n >> 6 & 0x3F) as usize] as char);
+        } else {
+            result.push('=');
+        }
+        if chunk.len() > 2 {
+            result.push(ALPHABET[(n & 0x3F) as usize] as char);
+        } else {
+            result.push('=');
+        }
+    }
+    result
+}
+
+/// Pattern and mode from pasted clipboard text. `search` arguments (as
+/// copied by [`filter_search_args`]) carry their own mode, a structured
+/// `query` becoming query text; any other text is a single-line pattern run
+/// with `fallback`.
+fn pasted_filter(text: &str, fallback: FilterMode) -> Result<(String, FilterMode), String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Clipboard is empty".to_string());
+    }
+
+    if let Ok(serde_json::Value::Object(args)) = serde_json::from_str(text) {
+        if let Some(query) = args.get("query") {
+            let mut query: crate::filter::query::FilterQuery =
+                serde_json::from_value(query.clone())
+                    .map_err(|e| format!("Clipboard query is invalid: {}", e))?;
+            query.partition_ts_filters();
+            let text = crate::filter::query::query_text(&query)
+                .map_err(|e| format!("Clipboard query can't be applied: {}", e))?;
+            return Ok((text, FilterMode::query()));
+        }
+        let Some(pattern) = args.get("pattern").and_then(|p| p.as_str()) else {
+            return Err("Clipboard JSON has no \"pattern\"".to_string());
+        };
+        let case_sensitive = args
+            .get("case_sensitive")
+            .and_then(|c| c.as_bool())
+            .unwrap_or(false);
+        let mode = match args.get("mode").and_then(|m| m.as_str()) {
+            Some("regex") => FilterMode::Regex {
+                case_sensitive,
+                negate: false,
+            },
+            _ => FilterMode::Plain {
+                case_sensitive,
+                negate: false,
+            },
+        };
+        return Ok((pattern.to_string(), mode));
+    }
+
+    if text.contains('\n') {
+        return Err("Clipboard holds more than one line".to_string());
+    }
+    Ok((text.to_string(), fallback))
+}
+
+/// The source's active filter as MCP `search` arguments (JSON), or `None`
+/// without a filter. Query-mode filters are sent as the structured `query`.
+fn filter_search_args(source: &crate::log_source::LogSource) -> Option<String> {
+    let pattern = source.filter.pattern.as_ref()?;
+    let mode = source.filter.mode;
+    let args = if mode.is_query() {
+        let query = crate::filter::query::parse_query(pattern).ok()?;
+        serde_json::json!({ "source": source.name, "query": query })
+    } else {
+        serde_json::json!({
+            "source": source.name,
+            "pattern": pattern,
+            "mode": if mode.is_regex() { "regex" } else { "plain" },
+            "case_sensitive": mode.is_case_sensitive(),
+        })
+    };
+    Some(args.to_string())
+}
+
+/// Status message for sources whose index can't be written.
+fn index_disabled_message(names: &[&str], reason: &str) -> String {
+    match names {
+        [name] => format!(
+            "Indexing disabled for {}: {} — filters scan the file instead",
+            name, reason
+        ),
+        _ => format!(
+            "Indexing disabled for {} sources: {} — filters scan the files instead",
+            names.len(),
+            reason
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::FilterMode;
+    use event::AppEvent;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn create_temp_log_file(lines: &[&str]) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        for line in lines {
+            writeln!(file, "{}", line).unwrap();
+        }
+        file.flush().unwrap();
+        file
+    }
+
+    #[test]
+    fn test_app_initialization() {
+        let temp_file = create_temp_log_file(&["line1", "line2", "line3"]);
+        let app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        assert_eq!(app.tab_mgr.tabs.len(), 1);
+        assert_eq!(app.tab_mgr.active, 0);
+        assert_eq!(app.active_tab().source.total_lines, 3);
+        assert!(!app.should_quit);
+        assert!(app.help_scroll_offset.is_none());
+    }
+
+    #[test]
+    fn test_streamed_aggregation_matches_full_compute() {
+        use crate::filter::aggregation::AggregationResult;
+
+        let lines: Vec<String> = (0..300)
+            .map(|i| format!(r#"{{"service":"svc{}"}}"#, i % 7))
+            .collect();
+        let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let temp_file = create_temp_log_file(&refs);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        let mut query = crate::filter::query::parse_query("json | count by (service)").unwrap();
+        let agg = query.aggregate.take().unwrap();
+        let source = &mut app.active_tab_mut().source;
+        source.filter.pending_aggregation = Some((agg.clone(), query.parser.clone()));
+        source.filter.needs_clear = true;
+        source.filter.state = FilterState::Processing { lines_processed: 0 };
+
+        // Partials arrive newest batch first, then an empty Complete
+        let matches: Vec<usize> = (0..300).filter(|i| i % 3 != 0).collect();
+        for chunk in matches.rchunks(50) {
+            app.apply_event(AppEvent::FilterPartialResults {
+                matches: chunk.to_vec(),
+                lines_processed: 300 - chunk[0],
+            });
+        }
+        app.apply_event(AppEvent::FilterComplete {
+            indices: vec![],
+            incremental: false,
+        });
+
+        let tab = app.active_tab();
+        assert_eq!(tab.source.mode, ViewMode::Aggregation);
+        let streamed = tab.source.aggregation_result.as_ref().unwrap();
+        let expected = {
+            let mut reader = tab.source.reader.lock().unwrap();
+            AggregationResult::compute(&mut *reader, &matches, &agg, &query.parser)
+        };
+        assert_eq!(streamed.total_matches, expected.total_matches);
+        let summary = |r: &AggregationResult| {
+            r.groups
+                .iter()
+                .map(|g| (g.key.clone(), g.line_indices.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(summary(streamed), summary(&expected));
+    }
+
+    #[test]
+    fn test_multiple_tabs() {
+        let file1 = create_temp_log_file(&["line1", "line2"]);
+        let file2 = create_temp_log_file(&["a", "b", "c"]);
+        let file3 = create_temp_log_file(&["x"]);
+
+        let app = App::new(
+            vec![
                 file1.path().to_path_buf(),
                 file2.path().to_path_buf(),
                 file3.path().to_path_buf(),
@@ -1363,6 +3075,62 @@ mod tests {
         assert_eq!(app.active_tab().selected_line, 5);
     }
 
+    #[test]
+    fn test_jump_back_and_forward_across_line_jump_and_filter() {
+        let temp_file = create_temp_log_file(&["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+        let selected = |app: &App| app.active_tab().viewport.selected_line();
+        assert_eq!(selected(&app), 9);
+
+        app.apply_event(AppEvent::StartLineJumpInput);
+        app.apply_event(AppEvent::LineJumpInputChar('3'));
+        app.apply_event(AppEvent::LineJumpInputSubmit);
+        assert_eq!(selected(&app), 2);
+
+        // Moving line by line records nothing
+        app.apply_event(AppEvent::ScrollDown);
+        assert_eq!(selected(&app), 3);
+
+        app.apply_event(AppEvent::StartFilterInput);
+        app.apply_event(AppEvent::FilterInputChar('7'));
+        app.apply_event(AppEvent::FilterInputSubmit);
+        app.apply_filter(vec![7], "7".to_string());
+        app.active_tab_mut().select_line(7);
+
+        app.apply_event(AppEvent::JumpBack);
+        assert_eq!(app.active_tab().source.mode, ViewMode::Normal);
+        assert_eq!(app.active_tab().source.filter.pattern, None);
+        assert_eq!(selected(&app), 3);
+
+        app.apply_event(AppEvent::JumpBack);
+        assert_eq!(selected(&app), 9);
+        app.apply_event(AppEvent::JumpBack);
+        assert_eq!(selected(&app), 9);
+
+        app.apply_event(AppEvent::JumpForward);
+        assert_eq!(selected(&app), 3);
+        // The filter is re-run, and its line selected again once it finishes
+        app.apply_event(AppEvent::JumpForward);
+        let filter = &app.active_tab().source.filter;
+        assert_eq!(filter.pattern.as_deref(), Some("7"));
+        assert_eq!(filter.origin_line, Some(7));
+        app.apply_filter(vec![7], "7".to_string());
+        assert_eq!(app.active_tab().source.mode, ViewMode::Filtered);
+        assert_eq!(selected(&app), 7);
+    }
+
+    #[test]
+    fn test_cancelled_filter_input_records_no_jump() {
+        let temp_file = create_temp_log_file(&["0", "1", "2"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+        app.apply_event(AppEvent::StartFilterInput);
+        app.apply_event(AppEvent::FilterInputCancel);
+
+        app.apply_event(AppEvent::JumpBack);
+        assert_eq!(app.active_tab().viewport.selected_line(), 2);
+        assert!(app.status_message.is_some());
+    }
+
     #[test]
     fn test_filter_application() {
         let temp_file = create_temp_log_file(&["error", "info", "error", "debug"]);
@@ -1405,6 +3173,177 @@ mod tests {
         assert!(app.active_tab().source.follow_mode);
     }
 
+    #[test]
+    fn test_filter_search_args() {
+        let temp_file = create_temp_log_file(&["1"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+        assert!(filter_search_args(&app.active_tab().source).is_none());
+
+        let source = &mut app.active_tab_mut().source;
+        source.name = "api".to_string();
+        source.filter.pattern = Some("time(out|d)".to_string());
+        source.filter.mode = FilterMode::Regex {
+            case_sensitive: true,
+            negate: false,
+        };
+        assert_eq!(
+            filter_search_args(source).unwrap(),
+            r#"{"source":"api","pattern":"time(out|d)","mode":"regex","case_sensitive":true}"#
+        );
+
+        source.filter.pattern = Some("json | level == \"error\"".to_string());
+        source.filter.mode = FilterMode::query();
+        assert_eq!(
+            filter_search_args(source).unwrap(),
+            r#"{"source":"api","query":{"parser":"json","filters":[{"field":"level","op":"eq","value":"error"}]}}"#
+        );
+    }
+
+    #[test]
+    fn test_pasted_filter() {
+        let plain = FilterMode::plain();
+        assert_eq!(
+            pasted_filter("  timeout\n", plain),
+            Ok(("timeout".to_string(), plain))
+        );
+        assert_eq!(
+            pasted_filter(
+                r#"{"source":"api","pattern":"time(out|d)","mode":"regex","case_sensitive":true}"#,
+                plain
+            ),
+            Ok((
+                "time(out|d)".to_string(),
+                FilterMode::Regex {
+                    case_sensitive: true,
+                    negate: false,
+                }
+            ))
+        );
+        assert!(pasted_filter(r#"{"source":"api","query":{}}"#, plain).is_err());
+        assert!(pasted_filter(r#"{"source":"api","query":{"parser":3}}"#, plain).is_err());
+        assert!(pasted_filter("a\nb", plain).is_err());
+        assert!(pasted_filter("  ", plain).is_err());
+    }
+
+    #[test]
+    fn test_pasted_filter_reads_back_a_copied_query() {
+        let temp_file = create_temp_log_file(&["1"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+        let source = &mut app.active_tab_mut().source;
+        for query in [
+            r#"json | level == "error" | msg =~ "time(out|d)""#,
+            r#"@ts >= "now-5m" | logfmt | !has trace_id | count by service, level | top 5"#,
+            r#"!json | @len > 2000"#,
+        ] {
+            source.filter.pattern = Some(query.to_string());
+            source.filter.mode = FilterMode::query();
+            let copied = filter_search_args(source).unwrap();
+            let (pattern, mode) = pasted_filter(&copied, FilterMode::plain()).unwrap();
+            assert_eq!(mode, FilterMode::query());
+            assert_eq!(
+                serde_json::to_value(crate::filter::query::parse_query(&pattern).unwrap()).unwrap(),
+                serde_json::to_value(crate::filter::query::parse_query(query).unwrap()).unwrap(),
+                "{} pasted back as {}",
+                query,
+                pattern
+            );
+        }
+    }
+
+    #[test]
+    fn test_raw_mode_cycles_through_ansi_and_plain() {
+        let temp_file = create_temp_log_file(&["1"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        assert_eq!(app.active_tab().source.raw_mode, RawMode::Off);
+        app.apply_event(AppEvent::ToggleRawMode);
+        assert_eq!(app.active_tab().source.raw_mode, RawMode::Ansi);
+        app.apply_event(AppEvent::ToggleRawMode);
+        assert_eq!(app.active_tab().source.raw_mode, RawMode::Plain);
+        app.apply_event(AppEvent::ToggleRawMode);
+        assert_eq!(app.active_tab().source.raw_mode, RawMode::Off);
+    }
+
+    #[test]
+    fn test_cycle_renderer_steps_through_presets_and_raw() {
+        let temp_file = create_temp_log_file(&["1"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+        let presets: Vec<String> = app
+            .preset_registry
+            .all_preset_names()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert!(!presets.is_empty());
+
+        for name in &presets {
+            app.apply_event(AppEvent::CycleRenderer);
+            assert_eq!(
+                app.active_tab().source.renderer_override,
+                Some(RendererOverride::Preset(name.clone()))
+            );
+        }
+        app.apply_event(AppEvent::CycleRenderer);
+        assert_eq!(
+            app.active_tab().source.renderer_override,
+            Some(RendererOverride::Raw)
+        );
+        assert!(app.status_message.as_ref().unwrap().0.contains("raw"));
+
+        app.apply_event(AppEvent::CycleRenderer);
+        assert_eq!(app.active_tab().source.renderer_override, None);
+        assert!(app
+            .status_message
+            .as_ref()
+            .unwrap()
+            .0
+            .contains("auto-detect"));
+    }
+
+    #[test]
+    fn test_paused_follow_counts_new_lines() {
+        let temp_file = create_temp_log_file(&["1", "2", "3"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        app.apply_event(AppEvent::DisableFollowMode);
+        assert!(!app.active_tab().source.follow_mode);
+        assert_eq!(app.active_tab().source.new_lines_while_paused(), 0);
+
+        app.apply_event(AppEvent::FileModified {
+            new_total: 5,
+            old_total: 3,
+        });
+        assert_eq!(app.active_tab().source.new_lines_while_paused(), 2);
+
+        // Jumping to the end catches up but stays paused
+        app.apply_event(AppEvent::JumpToEnd);
+        assert_eq!(app.active_tab().source.new_lines_while_paused(), 0);
+        app.apply_event(AppEvent::FileModified {
+            new_total: 6,
+            old_total: 5,
+        });
+        assert_eq!(app.active_tab().source.new_lines_while_paused(), 1);
+
+        // Resuming follow clears the counter
+        app.toggle_follow_mode();
+        assert!(app.active_tab().source.follow_mode);
+        assert_eq!(app.active_tab().source.follow_paused_at, None);
+    }
+
+    #[test]
+    fn test_manual_follow_toggle_does_not_count() {
+        let temp_file = create_temp_log_file(&["1", "2", "3"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        app.toggle_follow_mode();
+        app.apply_event(AppEvent::DisableFollowMode);
+        app.apply_event(AppEvent::FileModified {
+            new_total: 5,
+            old_total: 3,
+        });
+        assert_eq!(app.active_tab().source.new_lines_while_paused(), 0);
+    }
+
     #[test]
     fn test_filter_input_mode() {
         let temp_file = create_temp_log_file(&["line"]);
@@ -1478,6 +3417,28 @@ mod tests {
         assert_eq!(app.get_input(), "TEST_HIST_ERROR_12345");
     }
 
+    #[test]
+    fn test_max_history_trims_oldest_entries() {
+        let temp_file = create_temp_log_file(&["line"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        app.filter.set_max_history(2);
+        app.filter
+            .add_to_history("HIST_LIMIT_A".to_string(), FilterMode::plain());
+        app.filter
+            .add_to_history("HIST_LIMIT_B".to_string(), FilterMode::plain());
+        app.filter
+            .add_to_history("HIST_LIMIT_C".to_string(), FilterMode::plain());
+
+        app.start_filter_input();
+        app.apply_event(AppEvent::HistoryUp);
+        assert_eq!(app.get_input(), "HIST_LIMIT_C");
+        app.apply_event(AppEvent::HistoryUp);
+        assert_eq!(app.get_input(), "HIST_LIMIT_B");
+        app.apply_event(AppEvent::HistoryUp);
+        assert_eq!(app.get_input(), "HIST_LIMIT_B"); // oldest entry was dropped
+    }
+
     #[test]
     fn test_add_to_history_skips_duplicates() {
         let temp_file = create_temp_log_file(&["line"]);
@@ -1555,12 +3516,14 @@ mod tests {
             "error".to_string(),
             FilterMode::Plain {
                 case_sensitive: false,
+                negate: false,
             },
         );
         app.filter.add_to_history(
             "Error".to_string(),
             FilterMode::Plain {
                 case_sensitive: true,
+                negate: false,
             },
         );
 
@@ -1576,14 +3539,32 @@ mod tests {
     }
 
     #[test]
-    fn test_toggle_filter_mode() {
-        let temp_file = create_temp_log_file(&["line"]);
+    fn test_smart_case_resolves_mode_on_submit() {
+        let temp_file = create_temp_log_file(&["Error one", "error two"]);
         let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+        app.filter.smart_case = true;
 
-        assert!(!app.filter.current_mode.is_regex());
-        assert!(!app.filter.current_mode.is_query());
+        app.start_filter_input();
+        for c in "SMART_CASE_Error".chars() {
+            app.apply_event(AppEvent::FilterInputChar(c));
+        }
+        app.apply_event(AppEvent::FilterInputSubmit);
 
-        app.apply_event(AppEvent::ToggleFilterMode);
+        assert!(app.active_tab().source.filter.mode.is_case_sensitive());
+        // Input mode stays insensitive so the next pattern is judged afresh
+        assert!(!app.filter.current_mode.is_case_sensitive());
+        assert_eq!(app.filter.effective_mode("lower"), FilterMode::plain());
+    }
+
+    #[test]
+    fn test_toggle_filter_mode() {
+        let temp_file = create_temp_log_file(&["line"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        assert!(!app.filter.current_mode.is_regex());
+        assert!(!app.filter.current_mode.is_query());
+
+        app.apply_event(AppEvent::ToggleFilterMode);
         assert!(app.filter.current_mode.is_regex());
 
         app.apply_event(AppEvent::ToggleFilterMode);
@@ -1608,6 +3589,24 @@ mod tests {
         assert!(!app.filter.current_mode.is_case_sensitive());
     }
 
+    #[test]
+    fn test_toggle_negate() {
+        let temp_file = create_temp_log_file(&["line"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        app.apply_event(AppEvent::ToggleNegate);
+        assert!(app.filter.current_mode.is_negated());
+        assert_eq!(app.filter.current_mode.prompt_label(), "Filter !");
+
+        // Kept when switching to regex, dropped for query
+        app.apply_event(AppEvent::ToggleFilterMode);
+        assert!(app.filter.current_mode.is_negated());
+        app.apply_event(AppEvent::ToggleFilterMode);
+        assert!(!app.filter.current_mode.is_negated());
+        app.apply_event(AppEvent::ToggleNegate);
+        assert!(!app.filter.current_mode.is_negated());
+    }
+
     #[test]
     fn test_cycle_mode_case_sensitivity_behavior() {
         let temp_file = create_temp_log_file(&["line"]);
@@ -1737,6 +3736,36 @@ mod tests {
         assert_eq!(app.tab_mgr.active, 1);
     }
 
+    #[test]
+    fn test_jump_to_tab_alert_selects_alerted_line() {
+        let file1 = create_temp_log_file(&["a"]);
+        let file2 = create_temp_log_file(&["INFO b", "ERROR c", "INFO d"]);
+        let mut app = App::new(
+            vec![file1.path().to_path_buf(), file2.path().to_path_buf()],
+            false,
+        )
+        .unwrap();
+        // A project and a global source can share a name
+        app.tab_mgr.tabs[0].source.name = app.tab_mgr.tabs[1].source.name.clone();
+        let source = app.tab_mgr.tabs[1].source.name.clone();
+        let reader = app.tab_mgr.tabs[1].source.reader.clone();
+
+        app.raise_tab_alert(source, reader, 1, Severity::Error);
+        app.apply_event(AppEvent::JumpToTabAlert);
+        assert_eq!(app.tab_mgr.active, 1);
+        assert_eq!(app.active_tab().selected_line, 1);
+        assert!(!app.active_tab().source.follow_mode);
+        assert!(app.tab_alert.is_none());
+
+        app.apply_event(AppEvent::JumpToTabAlert);
+        assert!(app
+            .status_message
+            .as_ref()
+            .unwrap()
+            .0
+            .contains("No background"));
+    }
+
     #[test]
     fn test_cursor_starts_at_zero() {
         let temp_file = create_temp_log_file(&["line"]);
@@ -1893,6 +3922,114 @@ mod tests {
         assert_eq!(app.get_input(), "日語本");
     }
 
+    #[test]
+    fn test_apply_keep_open_filters_without_closing_or_recording_history() {
+        let temp_file = create_temp_log_file(&["KEEP_OPEN_1", "KEEP_OPEN_2", "other"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        app.start_filter_input();
+        for c in "KEEP_OPEN_".chars() {
+            app.apply_event(AppEvent::FilterInputChar(c));
+        }
+        app.apply_event(AppEvent::FilterInputApplyKeepOpen);
+        finish_filter(&mut app);
+        assert!(app.is_entering_filter());
+        assert_eq!(app.get_input(), "KEEP_OPEN_");
+        assert_eq!(app.active_tab().source.line_indices.len(), 2);
+
+        app.apply_event(AppEvent::FilterInputChar('2'));
+        app.apply_event(AppEvent::FilterInputSubmit);
+        finish_filter(&mut app);
+        assert!(!app.is_entering_filter());
+        assert_eq!(app.active_tab().source.line_indices.len(), 1);
+
+        // Only the submitted pattern made it into history
+        app.start_filter_input();
+        app.apply_event(AppEvent::HistoryUp);
+        assert_eq!(app.get_input(), "KEEP_OPEN_2");
+        app.apply_event(AppEvent::HistoryUp);
+        assert_ne!(app.get_input(), "KEEP_OPEN_");
+    }
+
+    #[test]
+    fn test_filter_esc_keep_leaves_previewed_filter_applied() {
+        let temp_file = create_temp_log_file(&["ESC_KEEP", "other", "ESC_KEEP again"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+        app.filter_esc = FilterEsc::Keep;
+
+        app.start_filter_input();
+        for c in "ESC_KEEP".chars() {
+            app.apply_event(AppEvent::FilterInputChar(c));
+        }
+        // Debounce still pending: Esc runs the preview instead of dropping it
+        app.apply_event(AppEvent::FilterInputCancel);
+        finish_filter(&mut app);
+
+        assert!(!app.is_entering_filter());
+        let tab = app.active_tab();
+        assert_eq!(tab.source.filter.pattern.as_deref(), Some("ESC_KEEP"));
+        assert_eq!(tab.source.line_indices.len(), 2);
+        assert_eq!(tab.source.filter.origin_line, None);
+    }
+
+    #[test]
+    fn test_command_filter_and_goto_record_jumps() {
+        let temp_file = create_temp_log_file(&["a", "CMD_FILTER b", "c", "CMD_FILTER d"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        app.apply_event(AppEvent::StartCommandInput);
+        assert!(app.is_entering_command());
+        app.apply_event(AppEvent::CommandInputCancel);
+        assert!(!app.is_entering_command());
+
+        app.apply_event(AppEvent::JumpToLine(2));
+        assert_eq!(app.active_tab().viewport.selected_line(), 1);
+        assert!(!app.active_tab().source.follow_mode);
+
+        app.apply_event(AppEvent::ApplyFilter("CMD_FILTER".to_string()));
+        finish_filter(&mut app);
+        assert_eq!(app.active_tab().source.line_indices.len(), 2);
+
+        app.apply_event(AppEvent::JumpBack);
+        assert_eq!(app.active_tab().source.filter.pattern, None);
+        assert_eq!(app.active_tab().viewport.selected_line(), 1);
+    }
+
+    #[test]
+    fn test_tab_completes_query_fields_from_the_source() {
+        let temp_file = create_temp_log_file(&[
+            r#"{"service":"api","server_id":7,"status":200}"#,
+            r#"{"service":"db","level":"info"}"#,
+        ]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+        app.start_filter_input();
+        app.filter.current_mode = FilterMode::query();
+        for c in "json | st".chars() {
+            app.apply_event(AppEvent::FilterInputChar(c));
+        }
+        assert!(app.can_complete_query());
+        app.apply_event(AppEvent::CompleteQueryField);
+        assert_eq!(app.get_input(), "json | status");
+
+        for _ in 0.."status".len() - 1 {
+            app.apply_event(AppEvent::FilterInputBackspace);
+        }
+        app.apply_event(AppEvent::FilterInputChar('e'));
+        app.apply_event(AppEvent::CompleteQueryField);
+        assert_eq!(app.get_input(), "json | serv");
+        let (message, _) = app.status_message.clone().unwrap();
+        assert_eq!(message, "server_id  service");
+
+        // A name nothing completes leaves Tab for cycling the mode
+        app.apply_event(AppEvent::FilterInputChar('x'));
+        app.apply_event(AppEvent::CompleteQueryField);
+        assert_eq!(app.get_input(), "json | servx");
+        assert!(!app.filter.current_mode.is_query());
+
+        // Other modes keep Tab for cycling the mode
+        assert!(!app.can_complete_query());
+    }
+
     #[test]
     fn test_history_sets_cursor_to_end() {
         let temp_file = create_temp_log_file(&["line"]);
@@ -1909,6 +4046,27 @@ mod tests {
         assert_eq!(app.get_cursor_position(), 5);
     }
 
+    #[test]
+    fn test_toggle_reverse_order_event() {
+        let temp_file = create_temp_log_file(&["line1", "line2", "line3"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        app.apply_event(AppEvent::JumpToStart);
+        app.apply_event(AppEvent::ToggleReverseOrder);
+        assert!(app.active_tab().viewport.is_reversed());
+        // Selection stays on the same line, reported as a data index
+        assert_eq!(app.active_tab().selected_line, 0);
+
+        // Moving down the screen walks towards older lines
+        app.apply_event(AppEvent::JumpToEnd);
+        app.apply_event(AppEvent::ScrollDown);
+        assert_eq!(app.active_tab().selected_line, 1);
+
+        app.apply_event(AppEvent::ToggleReverseOrder);
+        assert!(!app.active_tab().viewport.is_reversed());
+        assert_eq!(app.active_tab().selected_line, 1);
+    }
+
     #[test]
     fn test_toggle_line_expansion_event() {
         let temp_file = create_temp_log_file(&["line1", "line2", "line3"]);
@@ -1942,6 +4100,353 @@ mod tests {
         assert!(app.active_tab().expansion.expanded_lines.is_empty());
     }
 
+    #[test]
+    fn test_report_disabled_indexes() {
+        let a = create_temp_log_file(&["line1"]);
+        let b = create_temp_log_file(&["line1"]);
+        let mut app =
+            App::new(vec![a.path().to_path_buf(), b.path().to_path_buf()], false).unwrap();
+
+        app.report_disabled_indexes();
+        assert!(app.status_message.is_none());
+
+        app.tab_mgr.tabs[0].source.index_disabled = Some("index directory is not writable");
+        app.report_disabled_indexes();
+        let (message, _) = app.status_message.take().unwrap();
+        assert!(message.starts_with("Indexing disabled for "));
+        assert!(message.contains("index directory is not writable"));
+
+        app.tab_mgr.tabs[1].source.index_disabled = Some("index directory is not writable");
+        app.report_disabled_indexes();
+        let (message, _) = app.status_message.take().unwrap();
+        assert!(message.starts_with("Indexing disabled for 2 sources"));
+
+        // Survives later startup notices replacing the status message
+        app.report_startup_errors(vec!["Unknown field 'colour'".to_string()]);
+        let logged: Vec<_> = app.error_log.entries().map(|e| e.message.clone()).collect();
+        assert_eq!(logged.len(), 3);
+        assert_eq!(logged[1], message);
+    }
+
+    #[test]
+    fn test_jump_to_severity_without_index() {
+        let temp_file = create_temp_log_file(&[
+            "INFO start",
+            "ERROR first failure",
+            "WARN slow",
+            "FATAL crash",
+            "INFO done",
+        ]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        app.apply_event(AppEvent::JumpToFirstSeverity(Severity::Error));
+        assert_eq!(app.active_tab().selected_line, 1);
+
+        app.apply_event(AppEvent::JumpToLastSeverity(Severity::Error));
+        assert_eq!(app.active_tab().selected_line, 3);
+
+        app.apply_event(AppEvent::JumpToLastSeverity(Severity::Warn));
+        assert_eq!(app.active_tab().selected_line, 3);
+        app.apply_event(AppEvent::JumpToFirstSeverity(Severity::Warn));
+        assert_eq!(app.active_tab().selected_line, 1);
+        assert!(app.status_message.is_none());
+    }
+
+    #[test]
+    fn test_jump_to_severity_reports_no_match() {
+        let temp_file = create_temp_log_file(&["INFO a", "INFO b"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        app.apply_event(AppEvent::JumpToFirstSeverity(Severity::Error));
+        assert_eq!(app.active_tab().selected_line, 1);
+        assert!(app.status_message.as_ref().unwrap().0.contains("No error"));
+    }
+
+    #[test]
+    fn test_severity_breakdown_drills_down_to_level() {
+        let temp_file = create_temp_log_file(&["ERROR a", "INFO b", "plain", "ERROR c", "WARN d"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        app.apply_event(AppEvent::ShowSeverityBreakdown);
+        assert_eq!(app.active_tab().source.mode, ViewMode::Aggregation);
+        // An unindexed file is counted on a worker thread
+        while app.active_tab().source.aggregation_result.is_none() {
+            std::thread::sleep(Duration::from_millis(5));
+            app.poll_severity_breakdowns();
+        }
+        assert!(app
+            .status_message
+            .as_ref()
+            .unwrap()
+            .0
+            .contains("of 5 lines"));
+        let tab = app.active_tab();
+        let result = tab.source.aggregation_result.as_ref().unwrap();
+        let groups: Vec<_> = result
+            .groups
+            .iter()
+            .map(|g| (g.key[0].1.as_str(), g.count))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![("error", 2), ("warn", 1), ("info", 1), ("unknown", 1)]
+        );
+
+        app.apply_event(AppEvent::AggregationDrillDown);
+        let tab = app.active_tab();
+        assert_eq!(tab.source.mode, ViewMode::Filtered);
+        assert_eq!(tab.source.line_indices.to_vec(), vec![0, 3]);
+
+        app.apply_event(AppEvent::AggregationBack);
+        assert_eq!(app.active_tab().source.mode, ViewMode::Aggregation);
+    }
+
+    #[test]
+    fn test_stop_filter_scan_keeps_partial_matches() {
+        let temp_file = create_temp_log_file(&["a error", "b", "c error", "d error"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+        app.active_tab_mut()
+            .start_filter("error".to_string(), FilterMode::plain())
+            .unwrap();
+        app.merge_partial_filter_results(vec![0, 2], 3);
+        assert!(app.is_filter_processing());
+
+        app.apply_event(AppEvent::StopFilterScan);
+        let tab = app.active_tab();
+        assert!(!app.is_filter_processing());
+        assert!(tab.source.filter.receiver.is_none());
+        assert_eq!(tab.source.line_indices.len(), 2);
+        assert_eq!(
+            tab.source.filter.state,
+            FilterState::Complete { matches: 2 }
+        );
+        assert!(app.status_message.as_ref().unwrap().0.contains("2 matches"));
+    }
+
+    #[test]
+    fn test_toggle_mouse_capture() {
+        let temp_file = create_temp_log_file(&["line"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+        assert!(app.mouse_capture);
+
+        app.apply_event(AppEvent::ToggleMouseCapture);
+        assert!(!app.mouse_capture);
+        assert!(app
+            .status_message
+            .as_ref()
+            .unwrap()
+            .0
+            .contains("Mouse released"));
+
+        app.apply_event(AppEvent::ToggleMouseCapture);
+        assert!(app.mouse_capture);
+    }
+
+    #[test]
+    fn test_errors_overlay_collects_startup_and_filter_errors() {
+        let temp_file = create_temp_log_file(&["line"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        // Nothing to show yet
+        app.apply_event(AppEvent::ShowErrors);
+        assert!(app.errors_scroll_offset.is_none());
+
+        app.report_startup_errors(vec!["Unknown field 'colour'".to_string()]);
+        assert!(app.status_message.as_ref().unwrap().0.contains("press !"));
+        app.apply_event(AppEvent::FilterError("bad regex".to_string()));
+        let messages: Vec<_> = app.error_log.entries().map(|e| e.message.clone()).collect();
+        assert_eq!(messages[0], "Unknown field 'colour'");
+        assert!(messages[1].ends_with(": bad regex"));
+
+        app.apply_event(AppEvent::ShowErrors);
+        app.apply_event(AppEvent::ScrollErrorsDown);
+        assert_eq!(app.errors_scroll_offset, Some(1));
+        app.apply_event(AppEvent::HideErrors);
+        assert!(app.errors_scroll_offset.is_none());
+    }
+
+    #[test]
+    fn test_cycle_min_severity_keeps_text_filter() {
+        let temp_file = create_temp_log_file(&["ERROR timeout", "INFO timeout", "WARN x"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+        app.active_tab_mut()
+            .start_filter("timeout".to_string(), FilterMode::plain())
+            .unwrap();
+
+        app.apply_event(AppEvent::CycleMinSeverity);
+        let filter = &app.active_tab().source.filter;
+        assert_eq!(filter.min_severity, Some(Severity::Info));
+        assert_eq!(filter.pattern.as_deref(), Some("timeout"));
+        assert_eq!(
+            app.status_message.as_ref().unwrap().0,
+            "Showing info and above"
+        );
+
+        app.apply_event(AppEvent::CycleMinSeverity);
+        app.apply_event(AppEvent::CycleMinSeverity);
+        assert_eq!(
+            app.active_tab().source.filter.min_severity,
+            Some(Severity::Error)
+        );
+        app.apply_event(AppEvent::CycleMinSeverity);
+        assert_eq!(app.active_tab().source.filter.min_severity, None);
+        assert_eq!(
+            app.active_tab().source.filter.pattern.as_deref(),
+            Some("timeout")
+        );
+
+        // Esc clears both constraints
+        app.apply_event(AppEvent::CycleMinSeverity);
+        app.apply_event(AppEvent::ClearFilter);
+        assert_eq!(app.active_tab().source.filter.min_severity, None);
+        assert_eq!(app.active_tab().source.mode, ViewMode::Normal);
+    }
+
+    /// Run the active tab's filter scan to completion, as the main loop does.
+    fn finish_filter(app: &mut App) {
+        use crate::filter::engine::FilterProgress;
+
+        let receiver = app.active_tab_mut().source.filter.receiver.take();
+        let receiver = receiver.expect("no filter running");
+        while let Ok(progress) = receiver.recv() {
+            let done = matches!(
+                progress,
+                FilterProgress::Complete { .. } | FilterProgress::Error(_)
+            );
+            for event in crate::handlers::filter::handle_filter_progress(progress, false) {
+                app.apply_event(event);
+            }
+            if done {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn test_word_cursor_moves_along_selected_line_and_wraps() {
+        let temp_file = create_temp_log_file(&["GET /users 200", "one"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+        app.active_tab_mut().selected_line = 0;
+
+        app.apply_event(AppEvent::WordCursorRight);
+        assert_eq!(app.active_tab().word_cursor, None);
+        app.apply_event(AppEvent::ToggleWordHighlight);
+        assert_eq!(app.active_tab().word_cursor, Some(0));
+        app.apply_event(AppEvent::WordCursorLeft);
+        assert_eq!(app.active_tab().word_cursor, Some(2));
+        app.apply_event(AppEvent::WordCursorRight);
+        assert_eq!(app.active_tab().word_cursor, Some(0));
+
+        // A cursor past the end of a shorter line starts from its last word
+        app.active_tab_mut().word_cursor = Some(2);
+        app.active_tab_mut().selected_line = 1;
+        app.apply_event(AppEvent::WordCursorRight);
+        assert_eq!(app.active_tab().word_cursor, Some(0));
+
+        app.apply_event(AppEvent::ToggleWordHighlight);
+        assert_eq!(app.active_tab().word_cursor, None);
+    }
+
+    #[test]
+    fn test_hidden_severities_toggle_and_outlast_clear_filter() {
+        let temp_file =
+            create_temp_log_file(&["ERROR timeout", "DEBUG timeout", "INFO ok", "DEBUG ok"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        app.apply_event(AppEvent::EnterHideLevelMode);
+        assert_eq!(app.input.mode, InputMode::HideLevelPending);
+        app.apply_event(AppEvent::ToggleHiddenSeverity(Severity::Debug));
+        app.apply_event(AppEvent::ExitHideLevelMode);
+        assert_eq!(app.status_message.as_ref().unwrap().0, "Hiding debug lines");
+        finish_filter(&mut app);
+        assert_eq!(app.active_tab().source.line_indices, vec![0, 2]);
+        // Filtering by severity alone sets no text filter
+        assert_eq!(app.active_tab().source.mode, ViewMode::Filtered);
+        assert!(app.active_tab().source.filter.pattern.is_none());
+        assert!(app.active_tab().source.filter.is_severity_only());
+
+        // Composes with a text filter, and stays applied when it's cleared
+        app.active_tab_mut()
+            .start_filter("timeout".to_string(), FilterMode::plain())
+            .unwrap();
+        finish_filter(&mut app);
+        assert_eq!(app.active_tab().source.line_indices, vec![0]);
+        app.apply_event(AppEvent::ClearFilter);
+        finish_filter(&mut app);
+        assert_eq!(app.active_tab().source.line_indices, vec![0, 2]);
+        assert!(app.active_tab().source.filter.pattern.is_none());
+        assert_eq!(
+            app.active_tab().source.filter.hidden_severities,
+            vec![Severity::Debug]
+        );
+
+        app.apply_event(AppEvent::ToggleHiddenSeverity(Severity::Info));
+        finish_filter(&mut app);
+        assert_eq!(app.active_tab().source.line_indices, vec![0]);
+
+        app.apply_event(AppEvent::ShowAllSeverities);
+        assert_eq!(app.status_message.as_ref().unwrap().0, "Showing all levels");
+        assert_eq!(app.active_tab().source.mode, ViewMode::Normal);
+        assert_eq!(app.active_tab().source.line_indices, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_preview_paused_above_scan_budget() {
+        let temp_file = create_temp_log_file(&["error one", "info two"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+        app.set_confirm_scan_mb(None);
+        assert_eq!(app.confirm_scan_bytes, Some(1024 * 1024 * 1024));
+
+        app.confirm_scan_bytes = Some(1);
+        app.apply_event(AppEvent::StartFilterInput);
+        app.apply_event(AppEvent::FilterInputChar('e'));
+        app.trigger_filter_preview();
+        assert!(app.active_tab().source.filter.pattern.is_none());
+        assert!(app
+            .status_message
+            .as_ref()
+            .unwrap()
+            .0
+            .starts_with("Preview paused"));
+
+        // Enter runs it anyway
+        app.apply_event(AppEvent::FilterInputSubmit);
+        assert_eq!(app.active_tab().source.filter.pattern.as_deref(), Some("e"));
+
+        app.set_confirm_scan_mb(Some(0));
+        assert_eq!(app.confirm_scan_bytes, None);
+    }
+
+    #[test]
+    fn test_expand_all_visible_event() {
+        let temp_file = create_temp_log_file(&["line1", "line2", "line3"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        app.apply_event(AppEvent::ExpandAllVisible);
+        assert_eq!(app.active_tab().expansion.expanded_lines.len(), 3);
+
+        app.apply_event(AppEvent::CollapseAll);
+        assert!(app.active_tab().expansion.expanded_lines.is_empty());
+    }
+
+    #[test]
+    fn test_expand_all_visible_is_bounded_to_window() {
+        let lines: Vec<String> = (0..1000).map(|i| format!("line {}", i)).collect();
+        let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let temp_file = create_temp_log_file(&refs);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        app.active_tab_mut().viewport.set_height(10);
+        app.active_tab_mut().scroll_position = 500;
+        app.apply_event(AppEvent::ExpandAllVisible);
+
+        let expanded = &app.active_tab().expansion.expanded_lines;
+        assert_eq!(expanded.len(), 10 + 2 * 200);
+        assert!(expanded.contains(&505));
+        assert!(!expanded.contains(&0));
+        assert!(!expanded.contains(&999));
+    }
+
     #[test]
     fn test_copy_selected_line_sets_status_message() {
         let temp_file = create_temp_log_file(&["hello world", "second line"]);
@@ -1966,6 +4471,17 @@ mod tests {
         assert!(app.status_message.is_none());
     }
 
+    #[test]
+    fn test_copy_selected_line_names_an_empty_line() {
+        let temp_file = create_temp_log_file(&["", "second line"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        app.apply_event(AppEvent::JumpToStart);
+        app.apply_event(AppEvent::CopySelectedLine);
+        let (msg, _) = app.status_message.as_ref().unwrap();
+        assert_eq!(msg, "Copied: (empty line)");
+    }
+
     #[test]
     fn test_copy_selected_line_strips_ansi() {
         let temp_file = create_temp_log_file(&["\x1b[31mred text\x1b[0m"]);
@@ -1987,6 +4503,7 @@ mod tests {
             false,
         )
         .unwrap();
+        app.confirm_close = ConfirmClose::Always;
 
         assert_eq!(app.input.mode, InputMode::Normal);
         app.apply_event(AppEvent::CloseCurrentTab);
@@ -2007,6 +4524,7 @@ mod tests {
             false,
         )
         .unwrap();
+        app.confirm_close = ConfirmClose::Always;
 
         app.apply_event(AppEvent::CloseCurrentTab);
         assert_eq!(app.tab_mgr.tabs.len(), 2);
@@ -2018,6 +4536,168 @@ mod tests {
         assert!(app.pending_close_tab.is_none());
     }
 
+    #[test]
+    fn test_close_plain_tab_skips_confirmation_by_default() {
+        let file1 = create_temp_log_file(&["line1"]);
+        let file2 = create_temp_log_file(&["line2"]);
+        let mut app = App::new(
+            vec![file1.path().to_path_buf(), file2.path().to_path_buf()],
+            false,
+        )
+        .unwrap();
+
+        app.apply_event(AppEvent::CloseCurrentTab);
+        assert_eq!(app.tab_mgr.tabs.len(), 1);
+        assert_eq!(app.input.mode, InputMode::Normal);
+        assert!(app.pending_close_tab.is_none());
+    }
+
+    #[test]
+    fn test_close_ended_source_confirms_unless_never() {
+        let file1 = create_temp_log_file(&["line1"]);
+        let file2 = create_temp_log_file(&["line2"]);
+        let mut app = App::new(
+            vec![file1.path().to_path_buf(), file2.path().to_path_buf()],
+            false,
+        )
+        .unwrap();
+        app.tab_mgr.tabs[0].source.source_status = Some(SourceStatus::Ended);
+
+        app.apply_event(AppEvent::CloseCurrentTab);
+        assert_eq!(app.input.mode, InputMode::ConfirmClose);
+        app.apply_event(AppEvent::CancelCloseTab);
+
+        app.confirm_close = ConfirmClose::Never;
+        app.apply_event(AppEvent::CloseCurrentTab);
+        assert_eq!(app.tab_mgr.tabs.len(), 1);
+        assert_eq!(app.input.mode, InputMode::Normal);
+    }
+
+    #[test]
+    fn test_reopen_last_closed_tab() {
+        let file1 = create_temp_log_file(&["line1"]);
+        let file2 = create_temp_log_file(&["line2"]);
+        let mut app = App::new(
+            vec![file1.path().to_path_buf(), file2.path().to_path_buf()],
+            false,
+        )
+        .unwrap();
+        let name = app.tab_mgr.tabs[0].source.name.clone();
+
+        app.apply_event(AppEvent::CloseCurrentTab);
+        assert_eq!(app.tab_mgr.tabs.len(), 1);
+
+        app.apply_event(AppEvent::ReopenLastClosed);
+        assert_eq!(app.tab_mgr.tabs.len(), 2);
+        assert_eq!(app.active_tab().source.name, name);
+        assert_eq!(app.active_tab().source_type(), SourceType::File);
+
+        app.apply_event(AppEvent::ReopenLastClosed);
+        assert_eq!(app.tab_mgr.tabs.len(), 2);
+        assert_eq!(
+            app.status_message.as_ref().unwrap().0,
+            "No closed tab to reopen"
+        );
+    }
+
+    #[test]
+    fn test_reopen_keeps_reader_kind_and_config_settings() {
+        use crate::config::types::DefaultFilterMode;
+
+        let records =
+            create_temp_log_file(&["{", "  \"level\": \"error\"", "}", "{\"level\":\"info\"}"]);
+        let other = create_temp_log_file(&["line"]);
+        let mut app = App::new(vec![other.path().to_path_buf()], false).unwrap();
+        let source = crate::config::Source {
+            name: "api".to_string(),
+            path: Some(records.path().to_path_buf()),
+            exists: true,
+            renderer_names: vec!["json".to_string()],
+            display_transform: None,
+            color: None,
+            default_filter: Some(DefaultFilterConfig {
+                pattern: "error".to_string(),
+                mode: DefaultFilterMode::Plain,
+            }),
+            multiline_json: true,
+        };
+        let tab = TabState::from_config_source(&source, SourceType::ProjectSource, false)
+            .unwrap()
+            .unwrap();
+        app.add_tab(tab);
+        app.select_tab(1);
+        assert_eq!(app.active_tab().source.total_lines, 2);
+
+        app.apply_event(AppEvent::CloseCurrentTab);
+        app.apply_event(AppEvent::ReopenLastClosed);
+        let tab = app.active_tab();
+        assert_eq!(tab.source.name, "api");
+        assert_eq!(tab.source_type(), SourceType::ProjectSource);
+        assert!(tab.source.multiline_json);
+        assert_eq!(tab.source.total_lines, 2);
+        assert_eq!(tab.source.renderer_names, vec!["json".to_string()]);
+        assert_eq!(tab.source.filter.pattern.as_deref(), Some("error"));
+    }
+
+    #[test]
+    fn test_reopen_deleted_file_shows_message() {
+        let file1 = create_temp_log_file(&["line1"]);
+        let file2 = create_temp_log_file(&["line2"]);
+        let mut app = App::new(
+            vec![file1.path().to_path_buf(), file2.path().to_path_buf()],
+            false,
+        )
+        .unwrap();
+
+        app.apply_event(AppEvent::CloseCurrentTab);
+        drop(file1);
+        app.apply_event(AppEvent::ReopenLastClosed);
+        assert_eq!(app.tab_mgr.tabs.len(), 1);
+        assert!(app
+            .status_message
+            .as_ref()
+            .unwrap()
+            .0
+            .contains("was deleted"));
+    }
+
+    #[test]
+    fn test_closed_tab_stack_is_capped() {
+        let files: Vec<_> = (0..MAX_CLOSED_TABS + 3)
+            .map(|_| create_temp_log_file(&["line"]))
+            .collect();
+        let mut app = App::new(
+            files.iter().map(|f| f.path().to_path_buf()).collect(),
+            false,
+        )
+        .unwrap();
+
+        for _ in 0..MAX_CLOSED_TABS + 2 {
+            app.close_tab(0);
+        }
+        assert_eq!(app.closed_tabs.len(), MAX_CLOSED_TABS);
+        assert_eq!(
+            app.closed_tabs.last().unwrap().path,
+            files[MAX_CLOSED_TABS + 1].path()
+        );
+    }
+
+    #[test]
+    fn test_idle_exit_waits_for_modals() {
+        let file = create_temp_log_file(&["line1"]);
+        let mut app = App::new(vec![file.path().to_path_buf()], false).unwrap();
+        assert!(!app.idle_exit_due(Duration::from_secs(3600)));
+
+        app.idle_exit = Some(Duration::from_secs(5));
+        assert!(!app.idle_exit_due(Duration::from_secs(4)));
+        assert!(app.idle_exit_due(Duration::from_secs(5)));
+
+        app.apply_event(AppEvent::StartFilterInput);
+        assert!(!app.idle_exit_due(Duration::from_secs(60)));
+        app.apply_event(AppEvent::FilterInputCancel);
+        assert!(app.idle_exit_due(Duration::from_secs(60)));
+    }
+
     #[test]
     fn test_cancel_close_tab_restores_mode_without_closing() {
         let file1 = create_temp_log_file(&["line1"]);
@@ -2027,6 +4707,7 @@ mod tests {
             false,
         )
         .unwrap();
+        app.confirm_close = ConfirmClose::Always;
 
         app.apply_event(AppEvent::CloseCurrentTab);
         assert_eq!(app.input.mode, InputMode::ConfirmClose);
@@ -2141,6 +4822,330 @@ mod tests {
         assert!(!app.active_tab().source.follow_mode);
     }
 
+    fn app_with_log_view(lines: &[&str]) -> (App, NamedTempFile) {
+        let temp_file = create_temp_log_file(lines);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+        app.apply_event(AppEvent::JumpToStart);
+        app.layout.log_view = LayoutRect {
+            x: 32,
+            y: 0,
+            width: 80,
+            height: 20,
+        };
+        (app, temp_file)
+    }
+
+    #[test]
+    fn test_right_click_opens_menu_on_clicked_line() {
+        let (mut app, _file) = app_with_log_view(&["line1", "line2", "line3"]);
+
+        app.apply_event(AppEvent::MouseRightClick { column: 40, row: 2 });
+
+        assert_eq!(app.input.mode, InputMode::ContextMenu);
+        assert!(app.context_menu.is_some());
+        assert_eq!(app.active_tab().selected_line, 1);
+
+        app.apply_event(AppEvent::ContextMenuClose);
+        assert_eq!(app.input.mode, InputMode::Normal);
+        assert!(app.context_menu.is_none());
+    }
+
+    #[test]
+    fn test_context_menu_keyboard_select_expands_line() {
+        let (mut app, _file) = app_with_log_view(&["line1", "line2", "line3"]);
+
+        app.apply_event(AppEvent::MouseRightClick { column: 40, row: 3 });
+        app.apply_event(AppEvent::ContextMenuDown);
+        app.apply_event(AppEvent::ContextMenuSelect);
+
+        assert!(app.active_tab().is_line_expanded(2));
+        assert_eq!(app.input.mode, InputMode::Normal);
+    }
+
+    #[test]
+    fn test_context_menu_click_away_closes() {
+        let (mut app, _file) = app_with_log_view(&["line1", "line2", "line3"]);
+
+        app.apply_event(AppEvent::MouseRightClick { column: 40, row: 1 });
+        app.apply_event(AppEvent::MouseClick {
+            column: 100,
+            row: 15,
+        });
+
+        assert!(app.context_menu.is_none());
+        assert_eq!(app.input.mode, InputMode::Normal);
+        // Click-away doesn't also move the selection
+        assert_eq!(app.active_tab().selected_line, 0);
+    }
+
+    #[test]
+    fn test_context_menu_mark_and_filter_actions() {
+        let (mut app, _file) = app_with_log_view(&["a.b", "second", "third"]);
+
+        app.apply_event(AppEvent::MouseRightClick { column: 40, row: 2 });
+        let menu = app.context_menu.unwrap();
+        let area = menu.area(app.layout.log_view);
+        // Third item: "Show from here"
+        app.apply_event(AppEvent::MouseClick {
+            column: area.x + 2,
+            row: area.y + 3,
+        });
+        assert_eq!(app.active_tab().source.since_line, Some(1));
+        assert_eq!(app.active_tab().source.line_indices, vec![1, 2]);
+
+        app.apply_event(AppEvent::ToggleSinceMarker); // same key resets
+        app.filter.current_mode = FilterMode::regex();
+        app.apply_event(AppEvent::JumpToStart);
+        app.apply_event(AppEvent::MouseRightClick { column: 40, row: 1 });
+        app.apply_event(AppEvent::ContextMenuUp); // wraps to "Filter to this line"
+        app.apply_event(AppEvent::ContextMenuSelect);
+
+        assert_eq!(app.input.mode, InputMode::EnteringFilter);
+        assert_eq!(app.get_input(), r"a\.b");
+    }
+
+    #[test]
+    fn test_quick_filter_uses_configured_field() {
+        let (mut app, _file) = app_with_log_view(&["level=info trace_id=t1 request_id=r1"]);
+        app.quick_filter_fields = vec!["request_id".to_string()];
+
+        app.apply_event(AppEvent::QuickFilter);
+
+        assert_eq!(app.input.mode, InputMode::Normal);
+        let filter = &app.active_tab().source.filter;
+        assert_eq!(
+            filter.pattern.as_deref(),
+            Some(r#"logfmt | request_id == "r1""#)
+        );
+        assert!(filter.mode.is_query());
+    }
+
+    #[test]
+    fn test_quick_filter_picker_for_unstructured_line() {
+        let (mut app, _file) = app_with_log_view(&["disk full on node7", "ok"]);
+
+        app.apply_event(AppEvent::QuickFilter);
+        assert_eq!(app.input.mode, InputMode::FieldPicker);
+
+        app.apply_event(AppEvent::FieldPickerDown);
+        app.apply_event(AppEvent::FieldPickerSelect);
+
+        assert_eq!(app.input.mode, InputMode::Normal);
+        assert!(app.field_picker.is_none());
+        let filter = &app.active_tab().source.filter;
+        assert_eq!(filter.pattern.as_deref(), Some("full"));
+        assert!(!filter.mode.is_query());
+    }
+
+    #[test]
+    fn test_copy_field_uses_configured_field_or_picker() {
+        let (mut app, _file) = app_with_log_view(&[r#"{"msg":"disk full","trace_id":"t1","n":3}"#]);
+        app.copy_fields = vec!["trace_id".to_string()];
+        app.apply_event(AppEvent::CopyField);
+        assert_eq!(app.input.mode, InputMode::Normal);
+        assert_eq!(
+            app.status_message.as_ref().unwrap().0,
+            "Copied trace_id: t1"
+        );
+
+        app.copy_fields.clear();
+        app.apply_event(AppEvent::CopyField);
+        assert_eq!(app.input.mode, InputMode::FieldPicker);
+        assert_eq!(
+            app.field_picker.as_ref().unwrap().action,
+            PickerAction::Copy
+        );
+        app.apply_event(AppEvent::FieldPickerSelect);
+        assert_eq!(app.input.mode, InputMode::Normal);
+        assert_eq!(
+            app.status_message.as_ref().unwrap().0,
+            "Copied msg: disk full"
+        );
+        // Copying leaves the filter alone
+        assert!(app.active_tab().source.filter.pattern.is_none());
+    }
+
+    #[test]
+    fn test_copy_field_falls_back_to_word_under_cursor() {
+        let (mut app, _file) = app_with_log_view(&["disk full on node7"]);
+
+        app.apply_event(AppEvent::CopyField);
+        assert_eq!(app.active_tab().word_cursor, Some(0));
+        assert!(app
+            .status_message
+            .as_ref()
+            .unwrap()
+            .0
+            .contains("pick a word"));
+
+        app.apply_event(AppEvent::WordCursorRight);
+        app.apply_event(AppEvent::CopyField);
+        assert_eq!(app.status_message.as_ref().unwrap().0, "Copied: full");
+    }
+
+    #[test]
+    fn test_copy_word_uses_the_transformed_line() {
+        use crate::config::types::RawDisplayTransform;
+        use crate::renderer::transform::DisplayTransform;
+
+        let (mut app, _file) = app_with_log_view(&["disk full on node7"]);
+        let transform = DisplayTransform::compile(&RawDisplayTransform {
+            pattern: r"^(\w+) (\w+) on (\w+)$".to_string(),
+            template: "$3: $1 $2".to_string(),
+        })
+        .unwrap();
+        app.active_tab_mut().source.display_transform = Some(transform);
+
+        app.apply_event(AppEvent::ToggleWordHighlight);
+        app.apply_event(AppEvent::WordCursorLeft);
+        app.apply_event(AppEvent::CopyField);
+        assert_eq!(app.status_message.as_ref().unwrap().0, "Copied: full");
+    }
+
+    #[test]
+    fn test_trace_view_merges_all_sources() {
+        let api = create_temp_log_file(&["level=info trace_id=abc msg=start", "other"]);
+        let worker = create_temp_log_file(&["{\"traceId\":\"zzz\"}", "job trace_id=abc"]);
+        let mut app = App::new(
+            vec![api.path().to_path_buf(), worker.path().to_path_buf()],
+            false,
+        )
+        .unwrap();
+        app.apply_event(AppEvent::JumpToStart);
+
+        app.apply_event(AppEvent::TraceSelectedLine);
+
+        assert_eq!(app.tab_mgr.tab_count(), 3);
+        let tab = app.active_tab();
+        assert!(tab.is_combined);
+        assert_eq!(tab.source.name, "trace abc");
+        assert_eq!(tab.source_type(), SourceType::File);
+        assert_eq!(tab.source.total_lines, 4);
+        assert_eq!(tab.source.filter.pattern.as_deref(), Some("abc"));
+        assert!(tab.source.filter.mode.is_case_sensitive());
+
+        // The trace tab is not itself a source of the category's $all view
+        app.tab_mgr.ensure_combined_tabs();
+        let all = app.tab_mgr.combined[SourceType::File as usize]
+            .as_ref()
+            .unwrap();
+        assert_eq!(all.source.name, "$all (2 sources)");
+
+        // Tracing the same id again reuses the tab
+        app.tab_mgr.select_tab(0);
+        app.apply_event(AppEvent::TraceSelectedLine);
+        assert_eq!(app.tab_mgr.tab_count(), 3);
+        assert_eq!(app.tab_mgr.active, 2);
+    }
+
+    #[test]
+    fn test_combined_min_sources_controls_all_tab() {
+        let api = create_temp_log_file(&["a1"]);
+        let mut app = App::new(vec![api.path().to_path_buf()], false).unwrap();
+        let file_all = |app: &App| app.tab_mgr.combined[SourceType::File as usize].is_some();
+
+        app.tab_mgr.ensure_combined_tabs();
+        assert!(!file_all(&app));
+
+        app.tab_mgr.combined_min_sources = Some(1);
+        app.tab_mgr.ensure_combined_tabs();
+        assert!(file_all(&app));
+        // Empty categories never get one
+        assert!(app.tab_mgr.combined[SourceType::Pipe as usize].is_none());
+
+        app.tab_mgr.active_combined = Some(SourceType::File);
+        app.tab_mgr.combined_min_sources = None;
+        app.tab_mgr.ensure_combined_tabs();
+        assert!(!file_all(&app));
+        assert!(app.tab_mgr.active_combined.is_none());
+    }
+
+    #[test]
+    fn test_jump_from_combined_view_to_source_tab() {
+        let api = create_temp_log_file(&["a1", "a2", "a3"]);
+        let worker = create_temp_log_file(&["w1", "w2"]);
+        let mut app = App::new(
+            vec![api.path().to_path_buf(), worker.path().to_path_buf()],
+            false,
+        )
+        .unwrap();
+        // A project and a global source can share a name
+        app.tab_mgr.tabs[1].source.name = app.tab_mgr.tabs[0].source.name.clone();
+        app.tab_mgr.ensure_combined_tabs();
+        app.tab_mgr.active_combined = Some(SourceType::File);
+
+        // Untimestamped sources are merged in order: a1 a2 a3 w1 w2
+        app.active_tab_mut().select_line(4);
+        app.apply_event(AppEvent::JumpToSourceTab);
+
+        assert!(app.tab_mgr.active_combined.is_none());
+        assert_eq!(app.tab_mgr.active, 1);
+        assert_eq!(app.active_tab().selected_line, 1);
+    }
+
+    #[test]
+    fn test_jump_to_closed_source_tab_shows_message() {
+        let api = create_temp_log_file(&["a1"]);
+        let worker = create_temp_log_file(&["w1"]);
+        let mut app = App::new(
+            vec![api.path().to_path_buf(), worker.path().to_path_buf()],
+            false,
+        )
+        .unwrap();
+        app.tab_mgr.ensure_combined_tabs();
+        app.tab_mgr.active_combined = Some(SourceType::File);
+        app.active_tab_mut().select_line(1);
+
+        // Swap the source tab's reader so the combined snapshot no longer
+        // matches it
+        let reopened = crate::reader::file_reader::FileReader::new(worker.path()).unwrap();
+        app.tab_mgr.tabs[1].source.reader = Arc::new(Mutex::new(reopened));
+        app.apply_event(AppEvent::JumpToSourceTab);
+
+        assert_eq!(app.tab_mgr.active_combined, Some(SourceType::File));
+        assert!(app
+            .status_message
+            .as_ref()
+            .unwrap()
+            .0
+            .contains("no longer open"));
+    }
+
+    #[test]
+    fn test_trace_view_needs_correlation_field() {
+        let (mut app, _file) = app_with_log_view(&["no ids here"]);
+
+        app.apply_event(AppEvent::TraceSelectedLine);
+
+        assert_eq!(app.tab_mgr.tab_count(), 1);
+        assert!(app.status_message.is_some());
+    }
+
+    #[test]
+    fn test_mouse_double_click_toggles_expansion() {
+        let temp_file = create_temp_log_file(&["line1", "line2", "line3", "line4", "line5"]);
+        let mut app = App::new(vec![temp_file.path().to_path_buf()], false).unwrap();
+
+        app.apply_event(AppEvent::JumpToStart);
+        app.layout.log_view = LayoutRect {
+            x: 32,
+            y: 0,
+            width: 80,
+            height: 20,
+        };
+
+        app.apply_event(AppEvent::MouseClick { column: 40, row: 3 });
+        app.apply_event(AppEvent::MouseDoubleClick { column: 40, row: 3 });
+        assert!(app.active_tab().is_line_expanded(2));
+
+        app.apply_event(AppEvent::MouseDoubleClick { column: 40, row: 3 });
+        assert!(!app.active_tab().is_line_expanded(2));
+
+        app.double_click_action = DoubleClickAction::Disabled;
+        app.apply_event(AppEvent::MouseDoubleClick { column: 40, row: 3 });
+        assert!(app.active_tab().expansion.expanded_lines.is_empty());
+    }
+
     #[test]
     fn test_mouse_click_dismisses_help() {
         let temp_file = create_temp_log_file(&["line"]);
@@ -2175,6 +5180,38 @@ mod tests {
         assert_eq!(app.input.mode, InputMode::EnteringFilter);
     }
 
+    #[test]
+    fn test_mouse_click_on_tab_bar_switches_tab() {
+        let file1 = create_temp_log_file(&["line1"]);
+        let file2 = create_temp_log_file(&["line2"]);
+        let mut app = App::new(
+            vec![file1.path().to_path_buf(), file2.path().to_path_buf()],
+            false,
+        )
+        .unwrap();
+        app.tab_mgr.ensure_combined_tabs();
+        app.layout.tab_bar = LayoutRect {
+            x: 0,
+            y: 0,
+            width: 80,
+            height: 1,
+        };
+
+        let second = tab_bar::layout(&app.tab_mgr, 80)
+            .into_iter()
+            .find(|item| item.target == tab_bar::TabBarTarget::Tab(1))
+            .unwrap();
+        app.apply_event(AppEvent::MouseClick {
+            column: second.x + 1,
+            row: 0,
+        });
+        assert_eq!(app.tab_mgr.active, 1);
+        assert!(app.tab_mgr.active_combined.is_none());
+
+        app.apply_event(AppEvent::MouseClick { column: 0, row: 0 });
+        assert_eq!(app.tab_mgr.active_combined, Some(SourceType::File));
+    }
+
     #[test]
     fn test_mouse_click_ignored_during_confirm_close() {
         let file1 = create_temp_log_file(&["line1"]);
@@ -2184,6 +5221,7 @@ mod tests {
             false,
         )
         .unwrap();
+        app.confirm_close = ConfirmClose::Always;
 
         app.layout.side_panel_sources = LayoutRect {
             x: 0,
@@ -2215,6 +5253,7 @@ mod tests {
             false,
         )
         .unwrap();
+        app.confirm_close = ConfirmClose::Always;
 
         app.tab_mgr.active = 1;
         let original_name = app.tab_mgr.tabs[1].source.name.clone();
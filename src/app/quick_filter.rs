@@ -0,0 +1,306 @@
+//! Quick filter from the selected line: pick one of its field values (or a
+//! word, for unstructured lines) and filter to every line sharing it. The
+//! same picker chooses a field value to copy (`C`).
+
+use super::LayoutRect;
+use crate::filter::FilterMode;
+use crate::parsing::parse_logfmt;
+
+/// Words shorter than this are too generic to be worth offering
+const MIN_WORD_CHARS: usize = 3;
+
+/// Cap on offered words so long free-text lines keep a usable picker
+const MAX_WORDS: usize = 30;
+
+/// Trace/correlation id fields tried when none are configured
+pub const DEFAULT_CORRELATION_FIELDS: &[&str] =
+    &["trace_id", "traceId", "request_id", "correlation_id"];
+
+/// One filter candidate taken from a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuickFilter {
+    /// Field name for structured lines, `None` for a plain word
+    pub field: Option<String>,
+    pub value: String,
+    /// Pattern handed to the filter (a query for fields, the word otherwise)
+    pub pattern: String,
+}
+
+impl QuickFilter {
+    fn field(parser: &str, field: String, value: String) -> Self {
+        let pattern = format!("{} | {} == \"{}\"", parser, field, escape_query(&value));
+        Self {
+            field: Some(field),
+            value,
+            pattern,
+        }
+    }
+
+    fn word(word: &str) -> Self {
+        Self {
+            field: None,
+            value: word.to_string(),
+            pattern: word.to_string(),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match &self.field {
+            Some(field) => format!("{} = {}", field, self.value),
+            None => self.value.clone(),
+        }
+    }
+
+    pub fn mode(&self) -> FilterMode {
+        if self.field.is_some() {
+            FilterMode::query()
+        } else {
+            FilterMode::plain()
+        }
+    }
+}
+
+/// Collect filter candidates from an ANSI-stripped line.
+///
+/// JSON objects yield their scalar leaves (nested keys in dot notation),
+/// logfmt lines their key/value pairs in line order, and anything else its
+/// distinct words.
+pub fn candidates(line: &str) -> Vec<QuickFilter> {
+    let trimmed = line.trim();
+    if trimmed.starts_with('{') {
+        if let Ok(serde_json::Value::Object(map)) = serde_json::from_str(trimmed) {
+            let mut out = Vec::new();
+            for (key, value) in &map {
+                flatten_json(key.clone(), value, &mut out);
+            }
+            if !out.is_empty() {
+                return out;
+            }
+        }
+    }
+
+    let fields = parse_logfmt(line);
+    if !fields.is_empty() {
+        let mut pairs: Vec<(String, String)> = fields
+            .into_iter()
+            .filter(|(key, _)| is_query_field(key))
+            .collect();
+        pairs.sort_by_key(|(key, _)| line.find(&format!("{}=", key)).unwrap_or(usize::MAX));
+        if !pairs.is_empty() {
+            return pairs
+                .into_iter()
+                .map(|(key, value)| QuickFilter::field("logfmt", key, value))
+                .collect();
+        }
+    }
+
+    let mut words: Vec<QuickFilter> = Vec::new();
+    for word in line
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()))
+    {
+        if word.chars().count() >= MIN_WORD_CHARS && !words.iter().any(|w| w.value == word) {
+            words.push(QuickFilter::word(word));
+            if words.len() == MAX_WORDS {
+                break;
+            }
+        }
+    }
+    words
+}
+
+/// First candidate whose field is listed in `fields` (earlier fields win).
+pub fn default_candidate<'a>(
+    candidates: &'a [QuickFilter],
+    fields: &[String],
+) -> Option<&'a QuickFilter> {
+    fields.iter().find_map(|wanted| {
+        candidates
+            .iter()
+            .find(|c| c.field.as_deref() == Some(wanted.as_str()))
+    })
+}
+
+fn flatten_json(path: String, value: &serde_json::Value, out: &mut Vec<QuickFilter>) {
+    let scalar = match value {
+        serde_json::Value::Object(map) => {
+            for (key, nested) in map {
+                flatten_json(format!("{}.{}", path, key), nested, out);
+            }
+            return;
+        }
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Number(n) => n.to_string(),
+        serde_json::Value::Bool(b) => b.to_string(),
+        // Arrays and nulls make poor equality filters
+        serde_json::Value::Array(_) | serde_json::Value::Null => return,
+    };
+    if is_query_field(&path) {
+        out.push(QuickFilter::field("json", path, scalar));
+    }
+}
+
+/// Whether the query parser accepts `name` as a bare field name.
+fn is_query_field(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+}
+
+/// Escape a value for a double-quoted query string.
+fn escape_query(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// What choosing an item in the [`FieldPicker`] does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickerAction {
+    /// Filter to lines sharing the value
+    Filter,
+    /// Copy the value to the clipboard
+    Copy,
+}
+
+impl PickerAction {
+    pub fn title(self) -> &'static str {
+        match self {
+            PickerAction::Filter => " Filter by ",
+            PickerAction::Copy => " Copy value of ",
+        }
+    }
+}
+
+/// Popup listing the candidates of the selected line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldPicker {
+    pub items: Vec<QuickFilter>,
+    pub selected: usize,
+    pub action: PickerAction,
+}
+
+impl FieldPicker {
+    pub fn new(items: Vec<QuickFilter>, action: PickerAction) -> Self {
+        Self {
+            items,
+            selected: 0,
+            action,
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if !self.items.is_empty() {
+            self.selected = (self.selected + 1) % self.items.len();
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        let len = self.items.len();
+        if len > 0 {
+            self.selected = (self.selected + len - 1) % len;
+        }
+    }
+
+    pub fn selected_item(&self) -> Option<&QuickFilter> {
+        self.items.get(self.selected)
+    }
+
+    /// Popup area, centered in `bounds`.
+    pub fn area(&self, bounds: LayoutRect) -> LayoutRect {
+        let label_width = self
+            .items
+            .iter()
+            .map(|item| item.label().chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(u16::MAX as usize) as u16;
+        // Borders plus one column of padding on each side
+        let width = label_width.saturating_add(4).min(bounds.width);
+        let height = (self.items.len().min(u16::MAX as usize) as u16)
+            .saturating_add(2)
+            .min(bounds.height);
+        LayoutRect {
+            x: bounds.x + (bounds.width - width) / 2,
+            y: bounds.y + (bounds.height - height) / 2,
+            width,
+            height,
+        }
+    }
+
+    /// First item index to draw so the selection stays inside `rows` rows.
+    pub fn scroll_offset(&self, rows: usize) -> usize {
+        (self.selected + 1).saturating_sub(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_candidates_flatten_nested_fields() {
+        let items = candidates(r#"{"level":"error","req":{"id":"a\"b"},"tags":[1],"n":5}"#);
+        let labels: Vec<String> = items.iter().map(|i| i.label()).collect();
+        assert!(labels.contains(&"level = error".to_string()));
+        assert!(labels.contains(&"n = 5".to_string()));
+        assert!(!labels.iter().any(|l| l.starts_with("tags")));
+
+        let id = items
+            .iter()
+            .find(|i| i.field.as_deref() == Some("req.id"))
+            .unwrap();
+        assert_eq!(id.pattern, r#"json | req.id == "a\"b""#);
+        assert!(id.mode().is_query());
+    }
+
+    #[test]
+    fn test_logfmt_candidates_keep_line_order() {
+        let items = candidates("ts=1 level=info msg=\"hello world\" trace_id=abc");
+        let fields: Vec<&str> = items.iter().filter_map(|i| i.field.as_deref()).collect();
+        assert_eq!(fields, vec!["ts", "level", "msg", "trace_id"]);
+        assert_eq!(items[2].pattern, r#"logfmt | msg == "hello world""#);
+    }
+
+    #[test]
+    fn test_unstructured_line_offers_distinct_words() {
+        let items = candidates("Connection to db-1 failed: connection refused (db-1)");
+        let words: Vec<&str> = items.iter().map(|i| i.value.as_str()).collect();
+        assert_eq!(
+            words,
+            vec!["Connection", "db-1", "failed", "connection", "refused"]
+        );
+        assert!(items.iter().all(|i| i.field.is_none()));
+        assert!(!items[0].mode().is_query());
+    }
+
+    #[test]
+    fn test_default_candidate_prefers_earlier_fields() {
+        let items = candidates("trace_id=t1 request_id=r1");
+        let fields = vec!["request_id".to_string(), "trace_id".to_string()];
+        let chosen = default_candidate(&items, &fields).unwrap();
+        assert_eq!(chosen.value, "r1");
+        assert!(default_candidate(&items, &["user".to_string()]).is_none());
+    }
+
+    #[test]
+    fn test_picker_selection_and_scroll() {
+        let mut picker = FieldPicker::new(candidates("one two three four"), PickerAction::Filter);
+        picker.select_prev();
+        assert_eq!(picker.selected_item().unwrap().value, "four");
+        assert_eq!(picker.scroll_offset(2), 2);
+        picker.select_next();
+        assert_eq!(picker.selected_item().unwrap().value, "one");
+        assert_eq!(picker.scroll_offset(2), 0);
+    }
+}
@@ -0,0 +1,244 @@
+//! Respawning command source for `--respawn -- <cmd...>`.
+//!
+//! Runs a long-lived command (`kubectl logs -f`, `ssh host tail -F`), streams
+//! its output into a tab, and restarts it with backoff whenever it exits. A
+//! marker line in the stream shows each exit and reconnect.
+
+use super::tab::{send_line_batches, StreamMessage};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Delay before the first restart
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Upper bound for the doubling restart delay
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// A run at least this long resets the backoff (the command was healthy)
+const HEALTHY_RUN: Duration = Duration::from_secs(10);
+
+/// How often the backoff sleep checks for shutdown
+const STOP_POLL: Duration = Duration::from_millis(100);
+
+/// Supervises the command in a background thread. Dropping it stops the
+/// supervisor and kills the running command.
+pub struct Respawner {
+    stop: Arc<AtomicBool>,
+    child: Arc<Mutex<Option<Child>>>,
+}
+
+impl Respawner {
+    /// Start supervising `command` (program followed by its arguments),
+    /// sending its stdout and stderr lines to `tx`.
+    pub fn spawn(command: Vec<String>, tx: Sender<StreamMessage>) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let child = Arc::new(Mutex::new(None));
+        let supervisor = Supervisor {
+            command,
+            tx,
+            stop: stop.clone(),
+            child: child.clone(),
+        };
+        thread::spawn(move || supervisor.run());
+        Self { stop, child }
+    }
+}
+
+impl Drop for Respawner {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        let mut child = match self.child.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if let Some(child) = child.as_mut() {
+            let _ = child.kill();
+        }
+    }
+}
+
+struct Supervisor {
+    command: Vec<String>,
+    tx: Sender<StreamMessage>,
+    stop: Arc<AtomicBool>,
+    child: Arc<Mutex<Option<Child>>>,
+}
+
+impl Supervisor {
+    fn run(self) {
+        let display = self.command.join(" ");
+        let mut backoff = INITIAL_BACKOFF;
+        let mut restarts = 0usize;
+
+        loop {
+            let started = Instant::now();
+            let outcome = self.run_once(restarts);
+            if self.stopped() {
+                return;
+            }
+            let reason = match outcome {
+                Some(reason) => reason,
+                // Receiver dropped: the tab is gone
+                None => return,
+            };
+
+            // Quick failures back off exponentially; a healthy run starts over
+            if started.elapsed() >= HEALTHY_RUN {
+                backoff = INITIAL_BACKOFF;
+            }
+            if !self.marker(&format!(
+                "[lazytail] `{}` {}; restarting in {}s",
+                display,
+                reason,
+                backoff.as_secs()
+            )) {
+                return;
+            }
+            if !self.sleep(backoff) {
+                return;
+            }
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+            restarts += 1;
+        }
+    }
+
+    /// Run the command until it exits. Returns why it ended, or `None` if
+    /// the receiver went away.
+    fn run_once(&self, restarts: usize) -> Option<String> {
+        let spawned = Command::new(&self.command[0])
+            .args(&self.command[1..])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn();
+        let mut child = match spawned {
+            Ok(child) => child,
+            Err(e) => return Some(format!("failed to start: {}", e)),
+        };
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+        *self.lock_child() = Some(child);
+
+        if restarts > 0 && !self.marker(&format!("[lazytail] reconnected (restart #{})", restarts))
+        {
+            self.kill();
+            return None;
+        }
+
+        // stderr goes to the same stream so connection errors are visible
+        let stderr_thread = stderr.map(|stderr| {
+            let tx = self.tx.clone();
+            thread::spawn(move || send_line_batches(stderr, &tx))
+        });
+        let forwarded = stdout.map_or(Ok(true), |stdout| send_line_batches(stdout, &self.tx));
+        if matches!(forwarded, Ok(false)) {
+            self.kill();
+            return None;
+        }
+        if forwarded.is_err() {
+            // The output pipe failed: restart rather than spin
+            self.kill();
+        }
+        if let Some(handle) = stderr_thread {
+            let _ = handle.join();
+        }
+
+        let child = self.lock_child().take();
+        let reason = match child.map(|mut c| c.wait()) {
+            Some(Ok(status)) => format!("exited ({})", status),
+            Some(Err(e)) => format!("exited ({})", e),
+            None => "exited".to_string(),
+        };
+        Some(match forwarded {
+            Err(e) => format!("{}: read error: {}", reason, e),
+            Ok(_) => reason,
+        })
+    }
+
+    /// Append a marker line to the stream. Returns `false` if the receiver
+    /// is gone.
+    fn marker(&self, text: &str) -> bool {
+        self.tx
+            .send(StreamMessage::Lines(vec![text.to_string()]))
+            .is_ok()
+    }
+
+    /// Sleep for `duration`, waking early on shutdown. Returns `false` if
+    /// stopped.
+    fn sleep(&self, duration: Duration) -> bool {
+        let deadline = Instant::now() + duration;
+        while Instant::now() < deadline {
+            if self.stopped() {
+                return false;
+            }
+            thread::sleep(STOP_POLL.min(deadline - Instant::now()));
+        }
+        !self.stopped()
+    }
+
+    fn stopped(&self) -> bool {
+        self.stop.load(Ordering::SeqCst)
+    }
+
+    fn kill(&self) {
+        if let Some(mut child) = self.lock_child().take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+
+    fn lock_child(&self) -> std::sync::MutexGuard<'_, Option<Child>> {
+        match self.child.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    fn collect_lines(rx: &mpsc::Receiver<StreamMessage>, count: usize) -> Vec<String> {
+        let mut lines = Vec::new();
+        while lines.len() < count {
+            match rx.recv_timeout(Duration::from_secs(10)).unwrap() {
+                StreamMessage::Lines(batch) => lines.extend(batch),
+                other => panic!("unexpected message: {:?}", other),
+            }
+        }
+        lines
+    }
+
+    #[test]
+    #[ignore] // Slow: spawns processes and waits for the restart backoff
+    fn test_restarts_command_with_marker() {
+        let (tx, rx) = mpsc::channel();
+        let respawner = Respawner::spawn(
+            vec!["sh".to_string(), "-c".to_string(), "echo hello".to_string()],
+            tx,
+        );
+
+        let lines = collect_lines(&rx, 4);
+        assert_eq!(lines[0], "hello");
+        assert!(lines[1].starts_with("[lazytail] `sh -c echo hello` exited"));
+        assert!(lines[1].ends_with("restarting in 1s"));
+        assert_eq!(lines[2], "[lazytail] reconnected (restart #1)");
+        assert_eq!(lines[3], "hello");
+        drop(respawner);
+    }
+
+    #[test]
+    fn test_missing_command_reports_failure() {
+        let (tx, rx) = mpsc::channel();
+        let _respawner = Respawner::spawn(vec!["lazytail-no-such-command".to_string()], tx);
+
+        let lines = collect_lines(&rx, 1);
+        assert!(lines[0].contains("failed to start"), "{}", lines[0]);
+    }
+}
@@ -0,0 +1,431 @@
+//! UDP syslog input for `--syslog-udp <ADDR>`.
+//!
+//! Each datagram is parsed as RFC 5424 or RFC 3164 syslog and shown as one
+//! logfmt line (`level=error facility=auth host=... msg="..."`), so
+//! severity coloring, the severity floor and `logfmt | ...` queries work
+//! on it. Datagrams that aren't syslog are shown as received.
+
+use super::tab::StreamMessage;
+use std::io::{self, ErrorKind};
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// How often the receive loop checks for shutdown
+const RECV_POLL: Duration = Duration::from_millis(100);
+
+/// Largest possible UDP payload
+const MAX_DATAGRAM: usize = 65_535;
+
+const FACILITIES: [&str; 24] = [
+    "kern",
+    "user",
+    "mail",
+    "daemon",
+    "auth",
+    "syslog",
+    "lpr",
+    "news",
+    "uucp",
+    "cron",
+    "authpriv",
+    "ftp",
+    "ntp",
+    "security",
+    "console",
+    "solaris-cron",
+    "local0",
+    "local1",
+    "local2",
+    "local3",
+    "local4",
+    "local5",
+    "local6",
+    "local7",
+];
+
+const SEVERITIES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// A parsed syslog message. Absent (or RFC 5424 nil `-`) fields are `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyslogMessage {
+    pub facility: u8,
+    /// Syslog severity, 0 (emerg) to 7 (debug)
+    pub severity: u8,
+    pub timestamp: Option<String>,
+    pub host: Option<String>,
+    pub app: Option<String>,
+    pub pid: Option<String>,
+    pub msg: String,
+}
+
+impl SyslogMessage {
+    /// Parse an RFC 5424 or RFC 3164 message. `None` if it doesn't start
+    /// with a valid `<PRI>`.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (pri, rest) = parse_pri(text)?;
+        let mut message = match rest.strip_prefix("1 ") {
+            Some(rest) => parse_rfc5424(rest)?,
+            None => parse_rfc3164(rest),
+        };
+        message.facility = pri / 8;
+        message.severity = pri % 8;
+        Some(message)
+    }
+
+    /// lazytail level word for the syslog severity: emerg/alert/crit are
+    /// fatal, notice counts as info.
+    pub fn level(&self) -> &'static str {
+        match self.severity {
+            0..=2 => "fatal",
+            3 => "error",
+            4 => "warn",
+            5 | 6 => "info",
+            _ => "debug",
+        }
+    }
+
+    /// One logfmt line; `level` comes first so severity detection sees it
+    /// before any word in the message.
+    pub fn to_logfmt(&self) -> String {
+        let mut out = format!("level={}", self.level());
+        let fields = [
+            ("severity", Some(SEVERITIES[self.severity as usize])),
+            ("facility", FACILITIES.get(self.facility as usize).copied()),
+            ("ts", self.timestamp.as_deref()),
+            ("host", self.host.as_deref()),
+            ("app", self.app.as_deref()),
+            ("pid", self.pid.as_deref()),
+            ("msg", Some(self.msg.as_str())),
+        ];
+        for (key, value) in fields {
+            if let Some(value) = value {
+                out.push(' ');
+                out.push_str(key);
+                out.push('=');
+                push_logfmt_value(&mut out, value);
+            }
+        }
+        out
+    }
+}
+
+/// Display line for a datagram: the parsed message as logfmt, or the raw
+/// text (one line) if it isn't syslog.
+pub fn datagram_line(data: &[u8]) -> String {
+    let text = String::from_utf8_lossy(data);
+    let text = text.trim_end_matches(['\r', '\n', '\0']);
+    match SyslogMessage::parse(text) {
+        Some(message) => message.to_logfmt(),
+        None => text.replace(['\r', '\n'], " "),
+    }
+}
+
+fn parse_pri(text: &str) -> Option<(u8, &str)> {
+    let rest = text.strip_prefix('<')?;
+    let end = rest.find('>')?;
+    let digits = &rest[..end];
+    if digits.is_empty() || digits.len() > 3 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let pri: u8 = digits.parse().ok().filter(|&p| p <= 191)?;
+    Some((pri, &rest[end + 1..]))
+}
+
+/// `TIMESTAMP HOSTNAME APP-NAME PROCID MSGID [SD] MSG` after the version.
+fn parse_rfc5424(rest: &str) -> Option<SyslogMessage> {
+    let mut parts = rest.splitn(6, ' ');
+    let mut field = || {
+        parts
+            .next()
+            .map(|f| (f != "-").then(|| f.to_string()))
+            .ok_or(())
+    };
+    let timestamp = field().ok()?;
+    let host = field().ok()?;
+    let app = field().ok()?;
+    let pid = field().ok()?;
+    let _msgid = field().ok()?;
+    let rest = parts.next().unwrap_or("");
+    let msg = skip_structured_data(rest)?;
+    let msg = msg.strip_prefix(' ').unwrap_or(msg);
+    Some(SyslogMessage {
+        facility: 0,
+        severity: 0,
+        timestamp,
+        host,
+        app,
+        pid,
+        msg: msg.trim_start_matches('\u{feff}').to_string(),
+    })
+}
+
+/// Skip the STRUCTURED-DATA field (`-` or `[id k="v"]...`), returning what
+/// follows. `None` if an element is unterminated.
+fn skip_structured_data(text: &str) -> Option<&str> {
+    if let Some(rest) = text.strip_prefix('-') {
+        return Some(rest);
+    }
+    let mut rest = text;
+    while rest.starts_with('[') {
+        let mut in_quotes = false;
+        let mut escaped = false;
+        let mut end = None;
+        for (i, c) in rest.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_quotes = !in_quotes,
+                ']' if !in_quotes => {
+                    end = Some(i);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        rest = &rest[end? + 1..];
+    }
+    Some(rest)
+}
+
+/// `Mmm dd hh:mm:ss HOSTNAME TAG[PID]: MSG`. Without a valid timestamp the
+/// whole text is the message.
+fn parse_rfc3164(rest: &str) -> SyslogMessage {
+    let mut message = SyslogMessage {
+        facility: 0,
+        severity: 0,
+        timestamp: None,
+        host: None,
+        app: None,
+        pid: None,
+        msg: rest.to_string(),
+    };
+    let Some(timestamp) = rest.get(..15).filter(|ts| is_bsd_timestamp(ts)) else {
+        return message;
+    };
+    message.timestamp = Some(timestamp.to_string());
+    let rest = rest[15..].trim_start();
+
+    let (host, rest) = rest.split_once(' ').unwrap_or((rest, ""));
+    message.host = Some(host.to_string());
+
+    // TAG: up to 32 characters ending at '[' or ':'
+    let tag_end = rest
+        .find(|c: char| c == '[' || c == ':' || c.is_whitespace())
+        .filter(|&end| end > 0 && end <= 32);
+    let mut msg = rest;
+    if let Some(end) = tag_end {
+        let after_tag = &rest[end..];
+        let (pid, after) = match after_tag.strip_prefix('[').and_then(|s| s.split_once(']')) {
+            Some((pid, after)) => (Some(pid.to_string()), after),
+            None => (None, after_tag),
+        };
+        if let Some(after) = after.strip_prefix(':') {
+            message.app = Some(rest[..end].to_string());
+            message.pid = pid;
+            msg = after.strip_prefix(' ').unwrap_or(after);
+        }
+    }
+    message.msg = msg.to_string();
+    message
+}
+
+/// `Mmm dd hh:mm:ss` with a space-padded day.
+fn is_bsd_timestamp(ts: &str) -> bool {
+    let b = ts.as_bytes();
+    ts.get(..3).is_some_and(|month| MONTHS.contains(&month))
+        && b[3] == b' '
+        && (b[4] == b' ' || b[4].is_ascii_digit())
+        && b[5].is_ascii_digit()
+        && b[6] == b' '
+        && b[9] == b':'
+        && b[12] == b':'
+        && [7, 8, 10, 11, 13, 14]
+            .iter()
+            .all(|&i| b[i].is_ascii_digit())
+}
+
+fn push_logfmt_value(out: &mut String, value: &str) {
+    let needs_quotes = value.is_empty()
+        || value
+            .chars()
+            .any(|c| c.is_whitespace() || c == '"' || c == '=' || c == '\\');
+    if !needs_quotes {
+        out.push_str(value);
+        return;
+    }
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Receives datagrams in a background thread. Dropping it stops receiving.
+pub struct SyslogReceiver {
+    local_addr: SocketAddr,
+    stop: Arc<AtomicBool>,
+}
+
+impl SyslogReceiver {
+    /// Bind UDP `addr` (e.g. `0.0.0.0:514`) and send each datagram's line
+    /// to `tx`.
+    pub fn bind(addr: &str, tx: Sender<StreamMessage>) -> io::Result<Self> {
+        let socket = UdpSocket::bind(addr)?;
+        socket.set_read_timeout(Some(RECV_POLL))?;
+        let local_addr = socket.local_addr()?;
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let thread_stop = stop.clone();
+        thread::spawn(move || {
+            let mut buf = vec![0u8; MAX_DATAGRAM];
+            while !thread_stop.load(Ordering::SeqCst) {
+                let len = match socket.recv(&mut buf) {
+                    Ok(len) => len,
+                    Err(e)
+                        if matches!(
+                            e.kind(),
+                            ErrorKind::WouldBlock | ErrorKind::TimedOut | ErrorKind::Interrupted
+                        ) =>
+                    {
+                        continue
+                    }
+                    Err(e) => {
+                        let _ = tx.send(StreamMessage::Lines(vec![format!(
+                            "[lazytail] syslog receiver stopped: {}",
+                            e
+                        )]));
+                        return;
+                    }
+                };
+                if tx
+                    .send(StreamMessage::Lines(vec![datagram_line(&buf[..len])]))
+                    .is_err()
+                {
+                    return;
+                }
+            }
+        });
+        Ok(Self { local_addr, stop })
+    }
+
+    /// The address actually bound (resolves port 0).
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+impl Drop for SyslogReceiver {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::flags::{detect_flags, Severity};
+    use crate::parsing::parse_logfmt;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_parse_rfc5424() {
+        let message = SyslogMessage::parse(
+            r#"<165>1 2003-10-11T22:14:15.003Z mymachine.example.com evntslog - ID47 [exampleSDID@32473 iut="3" eventID="1011"] BOMAn application event"#,
+        )
+        .unwrap();
+        assert_eq!(message.facility, 20);
+        assert_eq!(message.severity, 5);
+        assert_eq!(
+            message.timestamp.as_deref(),
+            Some("2003-10-11T22:14:15.003Z")
+        );
+        assert_eq!(message.host.as_deref(), Some("mymachine.example.com"));
+        assert_eq!(message.app.as_deref(), Some("evntslog"));
+        assert_eq!(message.pid, None);
+        assert_eq!(message.msg, "BOMAn application event");
+
+        let nil = SyslogMessage::parse("<11>1 - - - - - -").unwrap();
+        assert_eq!(nil.host, None);
+        assert_eq!(nil.msg, "");
+    }
+
+    #[test]
+    fn test_parse_rfc3164() {
+        let message =
+            SyslogMessage::parse("<34>Oct 11 22:14:15 mymachine su[230]: 'su root' failed")
+                .unwrap();
+        assert_eq!(message.facility, 4);
+        assert_eq!(message.severity, 2);
+        assert_eq!(message.timestamp.as_deref(), Some("Oct 11 22:14:15"));
+        assert_eq!(message.host.as_deref(), Some("mymachine"));
+        assert_eq!(message.app.as_deref(), Some("su"));
+        assert_eq!(message.pid.as_deref(), Some("230"));
+        assert_eq!(message.msg, "'su root' failed");
+
+        // No timestamp: everything after PRI is the message
+        let bare = SyslogMessage::parse("<13>just text").unwrap();
+        assert_eq!(bare.timestamp, None);
+        assert_eq!(bare.msg, "just text");
+        let multibyte = SyslogMessage::parse("<13>éé Oct 11 22:14:15").unwrap();
+        assert_eq!(multibyte.timestamp, None);
+    }
+
+    #[test]
+    fn test_logfmt_line_carries_severity() {
+        let line = datagram_line(b"<27>Oct  1 08:00:00 db1 pg[7]: info: \"disk\" nearly full\n");
+        assert_eq!(
+            line,
+            r#"level=error severity=err facility=daemon ts="Oct  1 08:00:00" host=db1 app=pg pid=7 msg="info: \"disk\" nearly full""#
+        );
+        assert_eq!(Severity::from_flags(detect_flags(&line)), Severity::Error);
+        assert_eq!(
+            parse_logfmt(&line).get("msg").map(String::as_str),
+            Some(r#"info: "disk" nearly full"#)
+        );
+    }
+
+    #[test]
+    fn test_malformed_datagram_is_shown_raw() {
+        assert_eq!(datagram_line(b"<999>bad pri\r\n"), "<999>bad pri");
+        assert_eq!(datagram_line(b"no pri\nat all"), "no pri at all");
+        assert_eq!(
+            datagram_line(b"<13>1 ts host app - - [unterminated"),
+            "<13>1 ts host app - - [unterminated"
+        );
+    }
+
+    #[test]
+    fn test_receives_datagrams() {
+        let (tx, rx) = mpsc::channel();
+        let receiver = SyslogReceiver::bind("127.0.0.1:0", tx).unwrap();
+        let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+        client
+            .send_to(b"<14>1 - host app - - - hello", receiver.local_addr())
+            .unwrap();
+
+        match rx.recv_timeout(Duration::from_secs(10)).unwrap() {
+            StreamMessage::Lines(lines) => assert_eq!(
+                lines,
+                vec!["level=info severity=info facility=user host=host app=app msg=hello"]
+            ),
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+}
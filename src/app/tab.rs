@@ -1,24 +1,42 @@
+use super::jump_list::{JumpEntry, JumpList};
+use super::listener::Listener;
+use super::respawn::Respawner;
+use super::syslog::SyslogReceiver;
+use super::tee::StreamTee;
 use super::viewport::Viewport;
 use crate::app::{FilterState, SourceType, ViewMode};
 use crate::config;
+use crate::filter::aggregation::AggregationResult;
+use crate::filter::result_cache::ResultCache;
+use crate::filter::FilterMode;
+use crate::index::builder::{unwritable_reason, IndexBuilder};
+use crate::index::flags::{detect_flags, Severity, SEVERITY_MASK};
+use crate::index::meta::IndexMeta;
 use crate::index::reader::IndexReader;
+use crate::line_diff::LineDiff;
 use crate::log_source::calculate_index_size;
 use crate::reader::{
-    file_reader::FileReader, stream_reader::StreamReader, LogReader, StreamableReader,
+    encoding::Encoding, file_identity, file_reader::FileReader,
+    json_record_reader::JsonRecordReader, looks_binary, rotated_reader::RotatedReader,
+    stream_reader::StreamReader, LogReader, StreamableReader,
 };
+use crate::renderer::detect::dominant_format;
+use crate::renderer::preset::PresetParser;
+use crate::renderer::transform::DisplayTransform;
 use crate::source::{
-    check_source_status, check_source_status_in_dir, index_dir_for_log, DiscoveredSource,
-    SourceLocation,
+    check_source_status, check_source_status_in_dir, index_builds_deferred,
+    index_checkpoint_interval, index_dir_for_log, DiscoveredSource, SourceLocation, SourceStatus,
 };
-use crate::watcher::FileWatcher;
+use crate::watcher::{DirEvent, DirectoryWatcher, FileWatcher};
 use anyhow::{Context, Result};
 use std::collections::HashSet;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::PathBuf;
-use std::sync::mpsc::{self, Receiver, Sender};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 // Re-export LogSource for convenience
 pub use crate::log_source::LogSource;
@@ -26,6 +44,18 @@ pub use crate::log_source::LogSource;
 /// Batch size for sending lines from background reader
 const STREAM_BATCH_SIZE: usize = 10_000;
 
+/// Bytes requested per read of a stream
+const STREAM_READ_SIZE: usize = 64 * 1024;
+
+/// Lines past each edge of the screen that "expand all" also expands
+const EXPAND_ALL_BUFFER: usize = 200;
+
+/// Unindexed files at least this large get an index suggestion on first filter
+const SUGGEST_INDEX_MIN_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Newest appended lines checked for a background tab alert
+pub const ALERT_SCAN_LINES: usize = 10_000;
+
 /// Messages sent from the background stream reader thread
 #[derive(Debug)]
 pub enum StreamMessage {
@@ -35,6 +65,9 @@ pub enum StreamMessage {
     Complete,
     /// An error occurred while reading
     Error(String),
+    /// The unterminated last line read so far. The next `Lines` batch
+    /// starts with it completed; if reading ends first, it is the final line.
+    Partial(String),
 }
 
 /// Mode for expanding log entries
@@ -77,6 +110,15 @@ impl AggregationViewState {
     }
 }
 
+/// How to open a `--wait` tab's file once it appears.
+struct WaitingFile {
+    /// Reports the file's creation; `None` if the directory can't be
+    /// watched (then only the periodic check finds it)
+    watcher: Option<DirectoryWatcher>,
+    watch: bool,
+    force_text: bool,
+}
+
 /// Per-tab state for viewing a single log source.
 ///
 /// Contains a `LogSource` (domain core) plus TUI-specific state
@@ -94,6 +136,8 @@ pub struct TabState {
     pub viewport: Viewport,
     /// Line expansion state
     pub expansion: ExpansionState,
+    /// Positions to return to with Ctrl+O / Ctrl+N
+    pub jumps: JumpList,
     /// Whether this tab is a combined (merged) view of multiple sources.
     pub is_combined: bool,
     /// Stream writer handle for stream-specific operations (append, mark_complete).
@@ -101,10 +145,28 @@ pub struct TabState {
     stream_writer: Option<Arc<Mutex<dyn StreamableReader>>>,
     /// Receiver for background stream loading (pipes/stdin)
     pub stream_receiver: Option<Receiver<StreamMessage>>,
+    /// The stream's unterminated last line, held until it completes
+    partial_line: Option<String>,
+    /// Show the unterminated last line while it is being written instead of
+    /// waiting for its newline (`--no-line-buffer`)
+    pub show_partial_lines: bool,
+    /// Copy of streamed lines written to disk (stdin tabs with --save)
+    pub tee: Option<StreamTee>,
+    /// Supervisor restarting the command behind a `--respawn` tab
+    _respawner: Option<Respawner>,
+    /// TCP listener feeding a `--listen` tab
+    _listener: Option<Listener>,
+    /// UDP receiver feeding a `--syslog-udp` tab
+    _syslog: Option<SyslogReceiver>,
+    /// Set while a `--wait` tab's file does not exist yet
+    waiting: Option<WaitingFile>,
     /// Source type from config (ProjectSource or GlobalSource)
     pub config_source_type: Option<SourceType>,
     /// Aggregation table navigation state
     pub aggregation_view: AggregationViewState,
+    /// Index of the word on the selected line whose occurrences are
+    /// highlighted (`*`); `None` when word highlighting is off
+    pub word_cursor: Option<usize>,
 }
 
 impl TabState {
@@ -134,8 +196,15 @@ impl TabState {
         }
     }
 
-    /// Create a new tab from a file path
+    /// Create a new tab from a file path, refusing binary files (test helper)
+    #[cfg(test)]
     pub fn new(path: PathBuf, watch: bool) -> Result<Self> {
+        Self::open(path, watch, false)
+    }
+
+    /// Create a new tab from a file path. Regular files that look binary
+    /// (see [`looks_binary`]) are refused unless `force_text` is set.
+    pub fn open(path: PathBuf, watch: bool, force_text: bool) -> Result<Self> {
         // Check file type to determine if it's a regular file or pipe/FIFO
         let metadata = std::fs::metadata(&path)
             .with_context(|| format!("Failed to get metadata: {}", path.display()))?;
@@ -151,6 +220,14 @@ impl TabState {
             .unwrap_or_else(|| path.to_string_lossy().to_string());
 
         if is_regular_file {
+            if !force_text && looks_binary(&path)? {
+                anyhow::bail!(
+                    "{} looks like a binary file (it contains NUL bytes); \
+                     use --force-text to open it anyway",
+                    path.display()
+                );
+            }
+
             // Regular file - open reader + index directly
             drop(file);
             let file_reader = FileReader::new(&path)?;
@@ -159,11 +236,14 @@ impl TabState {
             let index_size = index_reader
                 .as_ref()
                 .and_then(|_| calculate_index_size(&path));
-            let index_warning = if index_reader.is_none() && index_dir_for_log(&path).exists() {
-                Some("Index is corrupt — restart capture to fix".to_string())
-            } else {
-                None
-            };
+            // An index built from the file alone is rebuilt rather than reported
+            let rebuild_index = index_reader.is_none() && index_is_rebuildable(&path);
+            let index_warning =
+                if index_reader.is_none() && !rebuild_index && index_dir_for_log(&path).exists() {
+                    Some("Index is corrupt — restart capture to fix".to_string())
+                } else {
+                    None
+                };
 
             let watcher = if watch {
                 FileWatcher::new(&path).ok()
@@ -174,7 +254,7 @@ impl TabState {
             let total_lines = file_reader.total_lines();
             let selected_line = total_lines.saturating_sub(1);
 
-            Ok(Self {
+            let mut tab = Self {
                 source: LogSource::new(name, Arc::new(Mutex::new(file_reader)))
                     .with_path(path)
                     .with_lines(total_lines)
@@ -186,12 +266,25 @@ impl TabState {
                 watcher,
                 viewport: Viewport::new(selected_line),
                 expansion: ExpansionState::default(),
+                jumps: JumpList::default(),
                 is_combined: false,
                 stream_writer: None,
                 stream_receiver: None,
+                partial_line: None,
+                show_partial_lines: false,
+                word_cursor: None,
+                tee: None,
+                _respawner: None,
+                _listener: None,
+                _syslog: None,
+                waiting: None,
                 config_source_type: None,
                 aggregation_view: AggregationViewState::default(),
-            })
+            };
+            if rebuild_index && !index_builds_deferred() {
+                let _ = tab.start_index_build();
+            }
+            Ok(tab)
         } else {
             // Pipe/FIFO - use background loading for immediate UI
             let stream_reader = Arc::new(Mutex::new(StreamReader::new_incremental()));
@@ -209,15 +302,81 @@ impl TabState {
                 watcher: None,
                 viewport: Viewport::new(0),
                 expansion: ExpansionState::default(),
+                jumps: JumpList::default(),
                 is_combined: false,
                 stream_writer: Some(stream_writer),
                 stream_receiver: Some(rx),
+                partial_line: None,
+                show_partial_lines: false,
+                word_cursor: None,
+                tee: None,
+                _respawner: None,
+                _listener: None,
+                _syslog: None,
+                waiting: None,
                 config_source_type: None,
                 aggregation_view: AggregationViewState::default(),
             })
         }
     }
 
+    /// Create a placeholder tab for a file that doesn't exist yet. It shows
+    /// as waiting until the file appears, then attaches like `tail -F` (see
+    /// [`attach_if_created`](Self::attach_if_created)).
+    pub fn waiting_for(path: PathBuf, watch: bool, force_text: bool) -> Result<Self> {
+        let name = path
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string());
+        let watcher = DirectoryWatcher::for_file(&path).ok();
+        let mut tab = Self::disabled_source(name, path, SourceType::File)?;
+        tab.waiting = Some(WaitingFile {
+            watcher,
+            watch,
+            force_text,
+        });
+        Ok(tab)
+    }
+
+    /// Whether this is a `--wait` tab whose file has not appeared yet.
+    pub fn is_waiting(&self) -> bool {
+        self.waiting.is_some()
+    }
+
+    /// Open a waiting tab's file once it exists, start indexing it and
+    /// follow it from then on. `force_check` looks for the file even
+    /// without a directory event, as a safety net for missed events.
+    ///
+    /// Returns `Ok(true)` when the tab attached. On error the tab stops
+    /// waiting and stays disabled.
+    pub fn attach_if_created(&mut self, force_check: bool) -> Result<bool> {
+        let Some(waiting) = self.waiting.take() else {
+            return Ok(false);
+        };
+        let mut created = force_check;
+        if let Some(ref watcher) = waiting.watcher {
+            while let Some(event) = watcher.try_recv() {
+                if matches!(event, DirEvent::NewFile(_)) {
+                    created = true;
+                }
+            }
+        }
+        let path = match self.source.source_path.clone() {
+            Some(path) if created && path.is_file() => path,
+            _ => {
+                self.waiting = Some(waiting);
+                return Ok(false);
+            }
+        };
+
+        let mut tab = Self::open(path, waiting.watch, waiting.force_text)?;
+        if tab.source.index_reader.is_none() && !index_builds_deferred() {
+            let _ = tab.start_index_build();
+        }
+        *self = tab;
+        Ok(true)
+    }
+
     /// Create a new tab from stdin (with background loading)
     pub fn from_stdin() -> Result<Self> {
         let stream_reader = Arc::new(Mutex::new(StreamReader::new_incremental()));
@@ -235,19 +394,192 @@ impl TabState {
             watcher: None,
             viewport: Viewport::new(0),
             expansion: ExpansionState::default(),
+            jumps: JumpList::default(),
+            is_combined: false,
+            stream_writer: Some(stream_writer),
+            stream_receiver: Some(rx),
+            partial_line: None,
+            show_partial_lines: false,
+            word_cursor: None,
+            tee: None,
+            _respawner: None,
+            _listener: None,
+            _syslog: None,
+            waiting: None,
+            config_source_type: None,
+            aggregation_view: AggregationViewState::default(),
+        })
+    }
+
+    /// Create a tab streaming the output of `command`, restarting it with
+    /// backoff whenever it exits.
+    pub fn from_command(command: Vec<String>) -> Result<Self> {
+        if command.is_empty() {
+            anyhow::bail!("--respawn requires a command after --");
+        }
+        let stream_reader = Arc::new(Mutex::new(StreamReader::new_incremental()));
+        let reader: Arc<Mutex<dyn LogReader + Send>> = stream_reader.clone();
+        let stream_writer: Arc<Mutex<dyn StreamableReader>> = stream_reader;
+
+        let name = command.join(" ");
+        let (tx, rx) = mpsc::channel();
+        let respawner = Respawner::spawn(command, tx);
+
+        Ok(Self {
+            source: LogSource::new(name, reader),
+            scroll_position: 0,
+            selected_line: 0,
+            watcher: None,
+            viewport: Viewport::new(0),
+            expansion: ExpansionState::default(),
+            jumps: JumpList::default(),
+            is_combined: false,
+            stream_writer: Some(stream_writer),
+            stream_receiver: Some(rx),
+            partial_line: None,
+            show_partial_lines: false,
+            word_cursor: None,
+            tee: None,
+            _respawner: Some(respawner),
+            _listener: None,
+            _syslog: None,
+            waiting: None,
+            config_source_type: None,
+            aggregation_view: AggregationViewState::default(),
+        })
+    }
+
+    /// Create a tab streaming lines received on TCP `addr` from any number
+    /// of connections (up to [`MAX_CONNECTIONS`](super::listener::MAX_CONNECTIONS)).
+    pub fn from_listener(addr: &str) -> Result<Self> {
+        let stream_reader = Arc::new(Mutex::new(StreamReader::new_incremental()));
+        let reader: Arc<Mutex<dyn LogReader + Send>> = stream_reader.clone();
+        let stream_writer: Arc<Mutex<dyn StreamableReader>> = stream_reader;
+
+        let (tx, rx) = mpsc::channel();
+        let listener =
+            Listener::bind(addr, tx).with_context(|| format!("Failed to listen on {}", addr))?;
+        let name = format!("tcp:{}", listener.local_addr());
+
+        Ok(Self {
+            source: LogSource::new(name, reader),
+            scroll_position: 0,
+            selected_line: 0,
+            watcher: None,
+            viewport: Viewport::new(0),
+            expansion: ExpansionState::default(),
+            jumps: JumpList::default(),
+            is_combined: false,
+            stream_writer: Some(stream_writer),
+            stream_receiver: Some(rx),
+            partial_line: None,
+            show_partial_lines: false,
+            word_cursor: None,
+            tee: None,
+            _respawner: None,
+            _listener: Some(listener),
+            _syslog: None,
+            waiting: None,
+            config_source_type: None,
+            aggregation_view: AggregationViewState::default(),
+        })
+    }
+
+    /// Create a tab streaming syslog datagrams received on UDP `addr`,
+    /// parsed into logfmt lines.
+    pub fn from_syslog_udp(addr: &str) -> Result<Self> {
+        let stream_reader = Arc::new(Mutex::new(StreamReader::new_incremental()));
+        let reader: Arc<Mutex<dyn LogReader + Send>> = stream_reader.clone();
+        let stream_writer: Arc<Mutex<dyn StreamableReader>> = stream_reader;
+
+        let (tx, rx) = mpsc::channel();
+        let receiver = SyslogReceiver::bind(addr, tx)
+            .with_context(|| format!("Failed to receive syslog on {}", addr))?;
+        let name = format!("syslog:{}", receiver.local_addr());
+
+        Ok(Self {
+            source: LogSource::new(name, reader),
+            scroll_position: 0,
+            selected_line: 0,
+            watcher: None,
+            viewport: Viewport::new(0),
+            expansion: ExpansionState::default(),
+            jumps: JumpList::default(),
             is_combined: false,
             stream_writer: Some(stream_writer),
             stream_receiver: Some(rx),
+            partial_line: None,
+            show_partial_lines: false,
+            word_cursor: None,
+            tee: None,
+            _respawner: None,
+            _listener: None,
+            _syslog: Some(receiver),
+            waiting: None,
             config_source_type: None,
             aggregation_view: AggregationViewState::default(),
         })
     }
 
+    /// Create a tab showing a log file preceded by its rotated siblings
+    /// (`app.log.2.gz`, `app.log.1`, ...), oldest first.
+    ///
+    /// No source path is set: line numbers span several files, so filters
+    /// go through the reader instead of the single-file fast paths.
+    pub fn from_rotated(path: PathBuf, watch: bool) -> Result<Self> {
+        let reader = RotatedReader::open(&path)?;
+        let file_name = path
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string());
+        let name = format!("{} (+{} rotated)", file_name, reader.archive_count());
+
+        // Watch the directory: rotation replaces the live file, and a watcher
+        // on the file itself would stop firing afterwards
+        let watcher = if watch {
+            let dir = match path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir,
+                _ => std::path::Path::new("."),
+            };
+            FileWatcher::new(dir).ok()
+        } else {
+            None
+        };
+
+        let total_lines = reader.total_lines();
+        let selected_line = total_lines.saturating_sub(1);
+
+        Ok(Self {
+            source: LogSource::new(name, Arc::new(Mutex::new(reader))).with_lines(total_lines),
+            scroll_position: 0,
+            selected_line,
+            watcher,
+            viewport: Viewport::new(selected_line),
+            expansion: ExpansionState::default(),
+            jumps: JumpList::default(),
+            is_combined: false,
+            stream_writer: None,
+            stream_receiver: None,
+            partial_line: None,
+            show_partial_lines: false,
+            word_cursor: None,
+            tee: None,
+            _respawner: None,
+            _listener: None,
+            _syslog: None,
+            waiting: None,
+            config_source_type: Some(SourceType::File),
+            aggregation_view: AggregationViewState::default(),
+        })
+    }
+
     /// Create a new tab from a discovered source
     pub fn from_discovered_source(
         source: DiscoveredSource,
         watch: bool,
         renderer_names: Vec<String>,
+        display_transform: Option<DisplayTransform>,
+        default_filter: Option<config::types::DefaultFilterConfig>,
     ) -> Result<Self> {
         let file_reader = FileReader::new(&source.log_path)?;
         let index_reader = IndexReader::open(&source.log_path);
@@ -255,12 +587,14 @@ impl TabState {
         let index_size = index_reader
             .as_ref()
             .and_then(|_| calculate_index_size(&source.log_path));
-        let index_warning =
-            if index_reader.is_none() && index_dir_for_log(&source.log_path).exists() {
-                Some("Index is corrupt — restart capture to fix".to_string())
-            } else {
-                None
-            };
+        let index_dir = index_dir_for_log(&source.log_path);
+        let index_warning = if index_reader.is_some() || !index_dir.exists() {
+            None
+        } else if IndexMeta::is_outdated(index_dir.join("meta")) {
+            Some("Index is from an older version — press I to rebuild".to_string())
+        } else {
+            Some("Index is corrupt — restart capture to fix".to_string())
+        };
 
         let watcher = if watch {
             FileWatcher::new(&source.log_path).ok()
@@ -279,15 +613,26 @@ impl TabState {
                 .with_index(index_reader, index_size)
                 .with_index_warning(index_warning)
                 .with_source_status(source.status)
-                .with_renderer_names(renderer_names),
+                .with_renderer_names(renderer_names)
+                .with_display_transform(display_transform)
+                .with_default_filter(default_filter),
             scroll_position: 0,
             selected_line,
             watcher,
             viewport: Viewport::new(selected_line),
             expansion: ExpansionState::default(),
+            jumps: JumpList::default(),
             is_combined: false,
             stream_writer: None,
             stream_receiver: None,
+            partial_line: None,
+            show_partial_lines: false,
+            word_cursor: None,
+            tee: None,
+            _respawner: None,
+            _listener: None,
+            _syslog: None,
+            waiting: None,
             config_source_type: match source.location {
                 SourceLocation::Project => Some(SourceType::ProjectSource),
                 SourceLocation::Global => None,
@@ -320,6 +665,10 @@ impl TabState {
             )?));
         }
 
+        if source.multiline_json {
+            return Self::from_json_records(source, source_type, path, watch).map(Some);
+        }
+
         // Create normal file tab
         let file_reader = FileReader::new(path)?;
         let index_reader = IndexReader::open(path);
@@ -343,20 +692,82 @@ impl TabState {
                 .with_lines(total_lines)
                 .with_file_size(file_size)
                 .with_index(index_reader, index_size)
-                .with_renderer_names(source.renderer_names.clone()),
+                .with_renderer_names(source.renderer_names.clone())
+                .with_display_transform(source.display_transform.clone())
+                .with_default_filter(source.default_filter.clone()),
             scroll_position: 0,
             selected_line,
             watcher,
             viewport: Viewport::new(selected_line),
             expansion: ExpansionState::default(),
+            jumps: JumpList::default(),
             is_combined: false,
             stream_writer: None,
             stream_receiver: None,
+            partial_line: None,
+            show_partial_lines: false,
+            word_cursor: None,
+            tee: None,
+            _respawner: None,
+            _listener: None,
+            _syslog: None,
+            waiting: None,
             config_source_type: Some(source_type),
             aggregation_view: AggregationViewState::default(),
         }))
     }
 
+    /// Create a tab for a config source that logs pretty-printed JSON, with
+    /// each object folded into one line.
+    ///
+    /// No source path is set: line numbers count objects, not file lines, so
+    /// filters go through the reader instead of the file fast paths and the
+    /// columnar index.
+    fn from_json_records(
+        source: &config::Source,
+        source_type: SourceType,
+        path: &Path,
+        watch: bool,
+    ) -> Result<Self> {
+        let reader = JsonRecordReader::new(path)?;
+        let watcher = if watch {
+            FileWatcher::new(path).ok()
+        } else {
+            None
+        };
+
+        let total_lines = reader.total_lines();
+        let selected_line = total_lines.saturating_sub(1);
+
+        Ok(Self {
+            source: LogSource::new(source.name.clone(), Arc::new(Mutex::new(reader)))
+                .with_lines(total_lines)
+                .with_renderer_names(source.renderer_names.clone())
+                .with_display_transform(source.display_transform.clone())
+                .with_default_filter(source.default_filter.clone())
+                .with_multiline_json(),
+            scroll_position: 0,
+            selected_line,
+            watcher,
+            viewport: Viewport::new(selected_line),
+            expansion: ExpansionState::default(),
+            jumps: JumpList::default(),
+            is_combined: false,
+            stream_writer: None,
+            stream_receiver: None,
+            partial_line: None,
+            show_partial_lines: false,
+            word_cursor: None,
+            tee: None,
+            _respawner: None,
+            _listener: None,
+            _syslog: None,
+            waiting: None,
+            config_source_type: Some(source_type),
+            aggregation_view: AggregationViewState::default(),
+        })
+    }
+
     /// Create a disabled tab for a missing source (shown grayed out in UI).
     fn disabled_source(name: String, path: PathBuf, source_type: SourceType) -> Result<Self> {
         // Use an empty stream reader as a placeholder (no stream_writer needed)
@@ -370,9 +781,18 @@ impl TabState {
             watcher: None,
             viewport: Viewport::new(0),
             expansion: ExpansionState::default(),
+            jumps: JumpList::default(),
             is_combined: false,
             stream_writer: None,
             stream_receiver: None,
+            partial_line: None,
+            show_partial_lines: false,
+            word_cursor: None,
+            tee: None,
+            _respawner: None,
+            _listener: None,
+            _syslog: None,
+            waiting: None,
             config_source_type: Some(source_type),
             aggregation_view: AggregationViewState::default(),
         })
@@ -396,14 +816,57 @@ impl TabState {
             watcher: None,
             viewport: Viewport::new(selected_line),
             expansion: ExpansionState::default(),
+            jumps: JumpList::default(),
             is_combined: true,
             stream_writer: None,
             stream_receiver: None,
+            partial_line: None,
+            show_partial_lines: false,
+            word_cursor: None,
+            tee: None,
+            _respawner: None,
+            _listener: None,
+            _syslog: None,
+            waiting: None,
             config_source_type: None,
             aggregation_view: AggregationViewState::default(),
         }
     }
 
+    /// Merge a stdin tab and file tabs into one combined tab (`--merge-stdin`).
+    ///
+    /// The files are read once, as context, and ordered by their index
+    /// timestamps; stdin lines follow them all, in the order they arrive.
+    /// The merged tab takes over stdin's stream (and `--save` copy), and a
+    /// delivery only appends, so line numbers and filter results stay valid.
+    pub fn merge_stdin(stdin: TabState, files: Vec<TabState>) -> Self {
+        use super::tab_manager::source_entry;
+        use crate::reader::combined_reader::SourceEntry;
+
+        let mut sources = vec![SourceEntry {
+            live: true,
+            ..source_entry(&stdin)
+        }];
+        sources.extend(files.iter().map(source_entry));
+
+        let mut tab = Self::from_combined(sources);
+        tab.source.name = match files.len() {
+            1 => "<stdin> + 1 file".to_string(),
+            n => format!("<stdin> + {} files", n),
+        };
+        tab.stream_writer = stdin.stream_writer;
+        tab.stream_receiver = stdin.stream_receiver;
+        tab.partial_line = stdin.partial_line;
+        tab.show_partial_lines = stdin.show_partial_lines;
+        tab.tee = stdin.tee;
+        tab
+    }
+
+    /// Whether closing this tab deletes its source (an ended discovered source).
+    pub fn close_deletes_source(&self) -> bool {
+        self.source.source_status == Some(SourceStatus::Ended) && self.source.source_path.is_some()
+    }
+
     /// Refresh source status for discovered sources.
     ///
     /// Checks if the source process is still running and updates the status.
@@ -427,17 +890,97 @@ impl TabState {
                 .filter(|sources_dir| sources_dir.exists())
                 .map(|sources_dir| check_source_status_in_dir(&self.source.name, &sources_dir))
                 .unwrap_or_else(|| check_source_status(&self.source.name));
-            self.source.source_status = Some(status);
+            self.set_source_status(status);
+        }
+    }
+
+    /// Record a freshly checked status, noting when the source ends.
+    fn set_source_status(&mut self, status: SourceStatus) {
+        let was_active = self.source.source_status == Some(SourceStatus::Active);
+        self.source.ended_at = match status {
+            SourceStatus::Ended if was_active => Some(Instant::now()),
+            SourceStatus::Ended => self.source.ended_at,
+            SourceStatus::Active => None,
+        };
+        self.source.source_status = Some(status);
+    }
+
+    /// Decode this tab's lines with the given text encoding
+    ///
+    /// Only file-backed readers decode bytes; streams are already text.
+    pub fn set_encoding(&mut self, encoding: Encoding) {
+        self.source.encoding = encoding;
+        let mut reader = match self.source.reader.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let reader = reader.as_any_mut();
+        if let Some(reader) = reader.downcast_mut::<FileReader>() {
+            reader.set_encoding(encoding);
+        } else if let Some(reader) = reader.downcast_mut::<RotatedReader>() {
+            reader.set_encoding(encoding);
+        } else if let Some(reader) = reader.downcast_mut::<JsonRecordReader>() {
+            reader.set_encoding(encoding);
+        }
+        // Lines may decode and match differently now
+        self.source.filter.result_cache.clear();
+    }
+
+    /// Use the preset `format_renderers` names for this source's dominant line
+    /// format, if the source has no renderers of its own. Mixed-format
+    /// sources keep no preset.
+    pub fn apply_format_renderer(&mut self, format_renderers: &config::types::FormatRenderers) {
+        if !self.source.renderer_names.is_empty() {
+            return;
+        }
+        let format = {
+            let mut reader = match self.source.reader.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            dominant_format(&mut *reader)
+        };
+        let preset = match format {
+            Some(PresetParser::Json) => format_renderers.json.as_ref(),
+            Some(PresetParser::Logfmt) => format_renderers.logfmt.as_ref(),
+            _ => None,
+        };
+        if let Some(preset) = preset {
+            self.source.renderer_names = vec![preset.clone()];
         }
     }
 
+    /// Save incoming stream lines to `path` while viewing them
+    pub fn tee_to_file(&mut self, path: &std::path::Path) -> Result<()> {
+        let tee = StreamTee::create(path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        self.tee = Some(tee);
+        Ok(())
+    }
+
     /// Append lines from background stream loading
     pub fn append_stream_lines(&mut self, lines: Vec<String>) {
         let old_total = self.source.total_lines;
         let new_lines_count = lines.len();
+        // A shown partial line is replaced by the first of `lines`
+        let shown_partial =
+            usize::from(self.partial_line.take().is_some() && self.show_partial_lines);
+
+        if let Some(ref mut tee) = self.tee {
+            tee.write_lines(&lines);
+        }
 
         // Add lines via the StreamableReader handle
         if let Some(ref writer) = self.stream_writer {
+            // A merged tab's combined reader stamps arrivals itself
+            if new_lines_count > 0 && !self.is_combined {
+                let epoch_ms = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |d| d.as_millis() as u64);
+                self.source
+                    .arrival_times
+                    .record(old_total - shown_partial, epoch_ms);
+            }
             let mut writer = match writer.lock() {
                 Ok(guard) => guard,
                 Err(poisoned) => poisoned.into_inner(),
@@ -445,16 +988,7 @@ impl TabState {
             writer.append_lines(lines);
         }
 
-        // Update total lines
-        self.source.total_lines = old_total + new_lines_count;
-        self.source.rate_tracker.record(self.source.total_lines);
-
-        // In normal mode, add new line indices
-        if self.source.mode == ViewMode::Normal {
-            self.source
-                .line_indices
-                .extend(old_total..old_total + new_lines_count);
-        }
+        self.sync_stream_total(old_total);
 
         // If in follow mode, jump to end
         if self.source.follow_mode && new_lines_count > 0 {
@@ -462,8 +996,54 @@ impl TabState {
         }
     }
 
-    /// Mark stream loading as complete
+    /// Record the stream's unterminated last line. It stays hidden until its
+    /// newline arrives, unless [`show_partial_lines`](Self::show_partial_lines)
+    /// is set: then it shows as the last line and is replaced as it grows.
+    pub fn set_stream_partial(&mut self, partial: String) {
+        if self.show_partial_lines {
+            if let Some(ref writer) = self.stream_writer {
+                let mut writer = match writer.lock() {
+                    Ok(guard) => guard,
+                    Err(poisoned) => poisoned.into_inner(),
+                };
+                writer.set_partial_line(Some(partial.clone()));
+            }
+            self.sync_stream_total(self.source.total_lines);
+            if self.source.follow_mode {
+                self.jump_to_end();
+            }
+        }
+        self.partial_line = Some(partial);
+    }
+
+    /// Pick up the stream reader's line count after it changed.
+    fn sync_stream_total(&mut self, old_total: usize) {
+        // A merged tab appends the stream's new lines after the files'
+        let new_total = {
+            let mut reader = match self.source.reader.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            if self.is_combined {
+                let _ = reader.reload();
+            }
+            reader.total_lines()
+        };
+        self.source.total_lines = new_total;
+        self.source.rate_tracker.record(new_total);
+
+        // In normal mode, add new line indices
+        if self.source.mode == ViewMode::Normal && new_total > old_total {
+            self.source.line_indices.extend_range(old_total..new_total);
+        }
+    }
+
+    /// Mark stream loading as complete. A held partial line is the stream's
+    /// last line, so it is added first.
     pub fn mark_stream_complete(&mut self) {
+        if let Some(line) = self.partial_line.clone() {
+            self.append_stream_lines(vec![line]);
+        }
         if let Some(ref writer) = self.stream_writer {
             let mut writer = match writer.lock() {
                 Ok(guard) => guard,
@@ -471,6 +1051,9 @@ impl TabState {
             };
             writer.mark_complete();
         }
+        if let Some(ref mut tee) = self.tee {
+            tee.finish();
+        }
         // Clear the receiver since we won't need it anymore
         self.stream_receiver = None;
     }
@@ -511,6 +1094,12 @@ impl TabState {
 
     /// Scroll down by page
     pub fn page_down(&mut self, page_size: usize) {
+        if self.source.line_wrap {
+            // Wrapped lines: page by visual rows (applied at the next render)
+            self.viewport
+                .page_by_rows(page_size.min(isize::MAX as usize) as isize);
+            return;
+        }
         // Clamp to i32::MAX to prevent overflow (page_size > 2^31 is unrealistic anyway)
         let delta = page_size.min(i32::MAX as usize) as i32;
         self.viewport
@@ -520,6 +1109,12 @@ impl TabState {
 
     /// Scroll up by page
     pub fn page_up(&mut self, page_size: usize) {
+        if self.source.line_wrap {
+            // Wrapped lines: page by visual rows (applied at the next render)
+            self.viewport
+                .page_by_rows(-(page_size.min(isize::MAX as usize) as isize));
+            return;
+        }
         // Clamp to i32::MAX to prevent overflow (page_size > 2^31 is unrealistic anyway)
         let delta = page_size.min(i32::MAX as usize) as i32;
         self.viewport
@@ -554,27 +1149,42 @@ impl TabState {
     }
 
     /// Apply filter results (for full filtering)
-    pub fn apply_filter(&mut self, matching_indices: Vec<usize>, pattern: String) {
+    pub fn apply_filter(&mut self, mut matching_indices: Vec<usize>, pattern: String) {
+        self.source.project_since_marker(&mut matching_indices);
+
         // Capture screen offset BEFORE changing line_indices
         let screen_offset = self.viewport.get_screen_offset(&self.source.line_indices);
 
         if self.source.filter.needs_clear {
             // Orchestrator set needs_clear but no partials arrived — Complete has all matches
-            self.source.line_indices = matching_indices;
+            self.source.line_indices = matching_indices.into();
             self.source.filter.needs_clear = false;
         } else if matches!(self.source.filter.state, FilterState::Processing { .. }) {
             // Partials were received (they consumed needs_clear) — extend with final batch
             self.source.line_indices.extend(matching_indices);
         } else {
             // Direct call (no orchestrator, no partials) — replace
-            self.source.line_indices = matching_indices;
+            self.source.line_indices = matching_indices.into();
         }
         self.source.mode = ViewMode::Filtered;
-        self.source.filter.pattern = Some(pattern);
+        // A severity-only scan runs with an empty pattern but sets no text filter
+        if !(pattern.is_empty() && self.source.filter.is_severity_only()) {
+            self.source.filter.pattern = Some(pattern);
+        }
         self.source.filter.state = FilterState::Complete {
             matches: self.source.line_indices.len(),
         };
-        self.source.filter.last_filtered_line = self.source.total_lines;
+        self.source.filter.last_filtered_line = self
+            .source
+            .filter
+            .filtering_to
+            .take()
+            .unwrap_or(self.source.total_lines);
+        self.source.filter.results_key = self
+            .source
+            .filter
+            .scan()
+            .map(|(pattern, mode)| self.source.result_key(&pattern, mode));
 
         // If we have an origin line (from when filtering started), select nearest match
         // while preserving screen position
@@ -594,6 +1204,7 @@ impl TabState {
 
         // Sync old fields from viewport
         self.sync_from_viewport();
+        self.remember_filter_results();
     }
 
     /// Find the line in line_indices nearest to target
@@ -607,13 +1218,14 @@ impl TabState {
             Ok(_) => target, // Exact match
             Err(pos) => {
                 // Find closest between pos-1 and pos
+                let indices = &self.source.line_indices;
                 if pos == 0 {
-                    self.source.line_indices[0]
-                } else if pos >= self.source.line_indices.len() {
-                    self.source.line_indices[self.source.line_indices.len() - 1]
+                    indices.at(0)
+                } else if pos >= indices.len() {
+                    indices.at(indices.len() - 1)
                 } else {
-                    let before = self.source.line_indices[pos - 1];
-                    let after = self.source.line_indices[pos];
+                    let before = indices.at(pos - 1);
+                    let after = indices.at(pos);
                     if target - before <= after - target {
                         before
                     } else {
@@ -624,25 +1236,85 @@ impl TabState {
         }
     }
 
-    /// Append incremental filter results (for new logs only)
-    pub fn append_filter_results(&mut self, new_matching_indices: Vec<usize>) {
-        self.source.line_indices.extend(new_matching_indices);
+    /// Append incremental filter results (for new logs only). Returns the
+    /// lines actually added.
+    pub fn append_filter_results(&mut self, mut new_matching_indices: Vec<usize>) -> Vec<usize> {
+        // A full scan reads to the end of the file, so it may already have
+        // matched lines the catch-up run covers again
+        if let Some(last) = self.source.line_indices.last() {
+            new_matching_indices.retain(|&idx| idx > last);
+        }
+        self.source
+            .line_indices
+            .extend(new_matching_indices.iter().copied());
         self.source.filter.state = FilterState::Complete {
             matches: self.source.line_indices.len(),
         };
+        self.source.filter.last_filtered_line = self
+            .source
+            .filter
+            .filtering_to
+            .take()
+            .unwrap_or(self.source.total_lines);
+        // Don't change selection - let follow mode or user control it.
+        // Catch-up results aren't cached: a followed file would copy its
+        // whole match list on every batch of new lines.
+        new_matching_indices
+    }
+
+    /// Cache the results of a completed full run for instant re-applying.
+    /// Results replayed from the cache are already there.
+    fn remember_filter_results(&mut self) {
+        let Some((pattern, mode)) = self.source.filter.scan() else {
+            return;
+        };
+        if !ResultCache::fits(self.source.line_indices.len()) {
+            return;
+        }
+        let key = self.source.result_key(&pattern, mode);
+        if self.source.filter.result_cache.get(&key).is_some() {
+            return;
+        }
+        let indices = self.source.line_indices.to_vec();
+        self.source.filter.result_cache.insert(key, indices);
+    }
+
+    /// Stop an in-progress filter scan, keeping the matches found so far.
+    /// Returns how many there are.
+    pub fn stop_filter_scan(&mut self) -> usize {
+        use crate::filter_orchestrator::FilterOrchestrator;
+
+        FilterOrchestrator::cancel(&mut self.source);
+        self.source.filter.receiver = None;
+        // Lines the stopped scan didn't reach stay unfiltered; only lines
+        // added from now on are caught up on
+        self.source.filter.filtering_to = None;
         self.source.filter.last_filtered_line = self.source.total_lines;
-        // Don't change selection - let follow mode or user control it
+        if self.source.filter.needs_clear {
+            // No results from this scan yet: don't leave the previous ones up
+            self.source.mode = ViewMode::Filtered;
+            self.source.line_indices.clear();
+            self.source.filter.needs_clear = false;
+            self.viewport.jump_to_end(&self.source.line_indices);
+        }
+        let matches = self.source.line_indices.len();
+        self.source.filter.state = FilterState::Complete { matches };
+        self.sync_from_viewport();
+        matches
     }
 
-    /// Clear filter and return to normal view
+    /// Clear the text filter and severity floor and return to normal view.
+    /// Hidden severities stay applied.
     pub fn clear_filter(&mut self) {
-        self.source.line_indices = (0..self.source.total_lines).collect();
+        self.source.line_indices = self.source.unfiltered_indices();
         self.source.mode = ViewMode::Normal;
         self.source.filter.pattern = None;
+        self.source.filter.min_severity = None;
         self.source.filter.state = FilterState::Inactive;
 
         // Reset aggregation state
         self.source.aggregation_result = None;
+        self.source.severity_breakdown = None;
         self.source.filter.pending_aggregation = None;
         self.source.filter.drill_down_aggregation = None;
         self.source.filter.drill_down_pattern = None;
@@ -657,6 +1329,12 @@ impl TabState {
                 .preserve_screen_offset(&self.source.line_indices);
         }
         self.sync_from_viewport();
+
+        // Hidden severities outlast the filter; if they can't be re-applied
+        // the view falls back to all lines
+        if !self.source.filter.hidden_severities.is_empty() {
+            let _ = self.start_severity_filter();
+        }
     }
 
     /// Jump to a specific line number (1-indexed)
@@ -673,11 +1351,392 @@ impl TabState {
         self.sync_from_viewport();
     }
 
-    /// Toggle follow mode
-    pub fn toggle_follow_mode(&mut self) {
-        self.source.follow_mode = !self.source.follow_mode;
-        if self.source.follow_mode {
-            self.jump_to_end();
+    /// Select the first (or `last`) visible line at or above `min` severity,
+    /// without filtering. Returns `false` if there is none.
+    ///
+    /// Uses the index's flags where it covers the line and detects the
+    /// severity from the text otherwise.
+    pub fn jump_to_severity(&mut self, min: Severity, last: bool) -> bool {
+        let min_bits = min.to_bits();
+        let found = {
+            let mut reader = match self.source.reader.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            let index = self.source.index_reader.as_ref();
+            let mut at_or_above = |line: usize| {
+                let flags = match index.and_then(|ir| ir.flags(line)) {
+                    Some(flags) => flags,
+                    None => match reader.get_line(line) {
+                        Ok(Some(text)) => detect_flags(&text),
+                        _ => return false,
+                    },
+                };
+                Severity::from_flags(flags).to_bits() >= min_bits
+            };
+            let indices = &self.source.line_indices;
+            if last {
+                (0..indices.len())
+                    .rev()
+                    .map(|i| indices.at(i))
+                    .find(|&l| at_or_above(l))
+            } else {
+                indices.iter().find(|&l| at_or_above(l))
+            }
+        };
+        let Some(line) = found else {
+            return false;
+        };
+        self.viewport.jump_to_line(line);
+        self.sync_from_viewport();
+        true
+    }
+
+    /// Open the aggregation view with the visible lines counted by
+    /// severity. Returns how many lines were counted, or `None` while they
+    /// are counted in the background ([`Self::poll_severity_breakdown`]).
+    ///
+    /// An unfiltered view of an indexed file is counted from the index's
+    /// severity counts, reading only the lines past the indexed range.
+    /// Anything else reads the visible lines one by one, on a worker thread.
+    pub fn show_severity_breakdown(&mut self) -> Option<usize> {
+        let indexed = self.source.index_reader.as_ref().map(|ir| ir.len());
+        let result = if self.shows_every_line()
+            && indexed.is_some_and(|indexed| indexed <= self.source.total_lines)
+        {
+            Some(self.file_severity_breakdown())
+        } else {
+            self.start_visible_severity_breakdown();
+            None
+        };
+        let counted = result.as_ref().map(|result| result.total_matches);
+        self.source.filter.pending_aggregation = None;
+        self.source.aggregation_result = result;
+        self.source.mode = ViewMode::Aggregation;
+        self.aggregation_view = AggregationViewState::default();
+        counted
+    }
+
+    /// Check on a background severity breakdown. Once it has finished,
+    /// shows the result and returns how many lines were counted.
+    pub fn poll_severity_breakdown(&mut self) -> Option<usize> {
+        let result = match self.source.severity_breakdown.as_ref()?.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => {
+                self.source.severity_breakdown = None;
+                return None;
+            }
+        };
+        self.source.severity_breakdown = None;
+        let counted = result.total_matches;
+        self.source.aggregation_result = Some(result);
+        Some(counted)
+    }
+
+    /// Whether the view shows every line of the source, unfiltered.
+    fn shows_every_line(&self) -> bool {
+        self.source.mode == ViewMode::Normal
+            && self.source.line_indices.len() == self.source.total_lines
+    }
+
+    /// Breakdown of the whole file: the index's severity counts, plus the
+    /// lines past the indexed range, detected from their text. Group lines
+    /// are found on drill-down ([`Self::lines_with_severity`]).
+    fn file_severity_breakdown(&self) -> AggregationResult {
+        let mut tail = [0usize; 7];
+        let indexed = self.source.index_reader.as_ref().map_or(0, |ir| ir.len());
+        let mut reader = match self.source.reader.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        for line in indexed..self.source.total_lines {
+            if let Ok(Some(text)) = reader.get_line(line) {
+                tail[Severity::from_flags(detect_flags(&text)).to_bits() as usize] += 1;
+            }
+        }
+        let counts = self
+            .source
+            .index_reader
+            .as_ref()
+            .map(|ir| ir.severity_counts())
+            .unwrap_or_default();
+        AggregationResult::from_severity_counts(|severity| {
+            let indexed = match severity {
+                Severity::Unknown => counts.unknown,
+                Severity::Trace => counts.trace,
+                Severity::Debug => counts.debug,
+                Severity::Info => counts.info,
+                Severity::Warn => counts.warn,
+                Severity::Error => counts.error,
+                Severity::Fatal => counts.fatal,
+            };
+            indexed as usize + tail[severity.to_bits() as usize]
+        })
+    }
+
+    /// Lines of `severity`, from the index's flags over the indexed range
+    /// and detected from the text past it.
+    pub fn lines_with_severity(&self, severity: Severity) -> Vec<usize> {
+        let total = self.source.total_lines;
+        let (mut lines, indexed) = match &self.source.index_reader {
+            Some(ir) => (
+                ir.scan_flags(SEVERITY_MASK, severity.to_bits(), total),
+                ir.len().min(total),
+            ),
+            None => (Vec::new(), 0),
+        };
+        let mut reader = match self.source.reader.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        lines.extend((indexed..total).filter(|&line| {
+            reader
+                .get_line(line)
+                .ok()
+                .flatten()
+                .is_some_and(|text| Severity::from_flags(detect_flags(&text)) == severity)
+        }));
+        lines
+    }
+
+    /// Count the visible lines in a background thread, one by one:
+    /// severities come from the index's flags where it covers the line and
+    /// the text otherwise.
+    fn start_visible_severity_breakdown(&mut self) {
+        let reader = self.source.reader.clone();
+        let visible = self.source.line_indices.clone();
+        let index_path = self
+            .source
+            .source_path
+            .clone()
+            .filter(|_| self.source.index_reader.is_some());
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let index = index_path.as_deref().and_then(IndexReader::open);
+            let mut reader = match reader.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            let lines: Vec<(usize, Severity)> = visible
+                .iter()
+                .filter_map(|line| {
+                    let flags = match index.as_ref().and_then(|ir| ir.flags(line)) {
+                        Some(flags) => flags,
+                        None => detect_flags(&reader.get_line(line).ok()??),
+                    };
+                    Some((line, Severity::from_flags(flags)))
+                })
+                .collect();
+            drop(reader);
+            let _ = tx.send(AggregationResult::by_severity(lines));
+        });
+        self.source.severity_breakdown = Some(rx);
+    }
+
+    /// The newest line in `lines` at or above `min` severity, with its
+    /// severity. Only the last [`ALERT_SCAN_LINES`] lines are checked so a
+    /// burst of output can't stall the UI.
+    pub fn newest_line_at_or_above(
+        &self,
+        min: Severity,
+        lines: std::ops::Range<usize>,
+    ) -> Option<(usize, Severity)> {
+        let start = lines.start.max(lines.end.saturating_sub(ALERT_SCAN_LINES));
+        let mut reader = match self.source.reader.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let index = self.source.index_reader.as_ref();
+        (start..lines.end).rev().find_map(|line| {
+            let flags = match index.and_then(|ir| ir.flags(line)) {
+                Some(flags) => flags,
+                None => detect_flags(&reader.get_line(line).ok()??),
+            };
+            let severity = Severity::from_flags(flags);
+            (severity.to_bits() >= min.to_bits()).then_some((line, severity))
+        })
+    }
+
+    /// Toggle follow mode
+    pub fn toggle_follow_mode(&mut self) {
+        self.source.follow_mode = !self.source.follow_mode;
+        self.source.follow_paused_at = None;
+        if self.source.follow_mode {
+            self.jump_to_end();
+        }
+    }
+
+    /// Turn follow mode off because the user navigated away, counting the
+    /// lines that arrive until it is resumed.
+    pub fn pause_follow(&mut self) {
+        if self.source.follow_mode {
+            self.source.follow_mode = false;
+            self.source.follow_paused_at = Some(self.source.total_lines);
+        }
+    }
+
+    /// Toggle between oldest-first and newest-first display order
+    pub fn toggle_reverse_order(&mut self) {
+        let reversed = !self.viewport.is_reversed();
+        self.viewport
+            .set_reversed(reversed, &self.source.line_indices);
+        self.sync_from_viewport();
+    }
+
+    /// Set the since-marker at the current end of the log, hiding everything
+    /// before it, or clear it if already set.
+    ///
+    /// Not available in combined views, where line numbers are re-merged on
+    /// every reload.
+    pub fn toggle_since_marker(&mut self) {
+        if self.is_combined {
+            return;
+        }
+        let since = match self.source.since_line {
+            Some(_) => None,
+            None => Some(self.source.total_lines),
+        };
+        if self.set_since_marker(since) {
+            self.jump_to_end();
+        }
+    }
+
+    /// Hide everything before the selected line (context menu "Show from here").
+    pub fn mark_from_selected_line(&mut self) {
+        if self.is_combined {
+            return;
+        }
+        if let Some(line) = self.source.line_indices.get(self.selected_line) {
+            self.set_since_marker(Some(line));
+        }
+    }
+
+    /// Start the source's configured default filter, if it has one.
+    pub fn apply_default_filter(&mut self) -> std::result::Result<(), String> {
+        let Some(default) = self.source.default_filter.clone() else {
+            return Ok(());
+        };
+        let mode = default.filter_mode();
+        self.start_filter(default.pattern, mode)
+    }
+
+    /// Run `pattern` as this tab's filter.
+    ///
+    /// Returns `Err` with a user-facing message if it could not be started
+    /// (invalid regex, bad query syntax, ...).
+    pub fn start_filter(
+        &mut self,
+        pattern: String,
+        mode: FilterMode,
+    ) -> std::result::Result<(), String> {
+        self.source.filter.pattern = Some(pattern.clone());
+        self.source.filter.mode = mode;
+        crate::filter_orchestrator::FilterOrchestrator::trigger(
+            &mut self.source,
+            pattern,
+            mode,
+            None,
+        )
+        .inspect_err(|_| self.source.filter.state = FilterState::Inactive)
+    }
+
+    /// Set or clear the severity floor and re-run the filter with it.
+    ///
+    /// The floor is ANDed with the text filter in a single pass; without a
+    /// text filter it filters the view on its own.
+    pub fn set_min_severity(&mut self, min: Option<Severity>) -> std::result::Result<(), String> {
+        self.source.filter.min_severity = min;
+        self.apply_severity_filters()
+    }
+
+    /// Hide lines of `level`, or show them again if hidden, and re-run the
+    /// filter. Returns whether the level is now hidden.
+    pub fn toggle_hidden_severity(&mut self, level: Severity) -> std::result::Result<bool, String> {
+        let hidden = &mut self.source.filter.hidden_severities;
+        let now_hidden = match hidden.iter().position(|&s| s == level) {
+            Some(pos) => {
+                hidden.remove(pos);
+                false
+            }
+            None => {
+                hidden.push(level);
+                hidden.sort_by_key(|s| s.to_bits());
+                true
+            }
+        };
+        self.apply_severity_filters().map(|()| now_hidden)
+    }
+
+    /// Replace the hidden severities (e.g. with ones restored from the
+    /// session) and re-run the filter.
+    pub fn set_hidden_severities(
+        &mut self,
+        mut levels: Vec<Severity>,
+    ) -> std::result::Result<(), String> {
+        levels.sort_by_key(|s| s.to_bits());
+        levels.dedup();
+        self.source.filter.hidden_severities = levels;
+        self.apply_severity_filters()
+    }
+
+    /// Re-run the text filter with the current severity floor and hidden
+    /// severities. Either on its own filters the view without a text
+    /// filter; with neither and no text filter, all lines are shown.
+    fn apply_severity_filters(&mut self) -> std::result::Result<(), String> {
+        match self.source.filter.pattern.clone() {
+            Some(pattern) => {
+                let mode = self.source.filter.mode;
+                self.start_filter(pattern, mode)
+            }
+            None if self.source.filter.filters_severity() => self.start_severity_filter(),
+            None => {
+                self.clear_filter();
+                Ok(())
+            }
+        }
+    }
+
+    /// Filter the view by the severity floor and hidden severities alone.
+    /// The text filter stays unset; the scan runs with an empty pattern.
+    fn start_severity_filter(&mut self) -> std::result::Result<(), String> {
+        self.source.filter.pattern = None;
+        crate::filter_orchestrator::FilterOrchestrator::trigger(
+            &mut self.source,
+            String::new(),
+            FilterMode::plain(),
+            None,
+        )
+        .inspect_err(|_| self.source.filter.state = FilterState::Inactive)
+    }
+
+    /// Set or clear the since-marker and re-project the visible lines.
+    ///
+    /// Returns true if the unfiltered view was rebuilt in place; false if
+    /// the active filter was re-run instead.
+    fn set_since_marker(&mut self, since: Option<usize>) -> bool {
+        self.source.since_line = since;
+
+        match self.source.filter.scan() {
+            Some((pattern, mode)) if self.source.mode != ViewMode::Normal => {
+                // Re-run the filter so results are projected against the new marker
+                if let Err(e) = crate::filter_orchestrator::FilterOrchestrator::trigger(
+                    &mut self.source,
+                    pattern,
+                    mode,
+                    None,
+                ) {
+                    eprintln!("Filter error: {}", e);
+                    self.source.filter.state = FilterState::Inactive;
+                }
+                false
+            }
+            _ => {
+                self.source.line_indices = self.source.unfiltered_indices();
+                self.sync_from_viewport();
+                true
+            }
         }
     }
 
@@ -685,6 +1744,10 @@ impl TabState {
     pub fn jump_to_end(&mut self) {
         self.viewport.jump_to_end(&self.source.line_indices);
         self.sync_from_viewport();
+        // Everything that arrived while paused is now on screen
+        if self.source.follow_paused_at.is_some() {
+            self.source.follow_paused_at = Some(self.source.total_lines);
+        }
     }
 
     /// Jump to the beginning of the log
@@ -711,6 +1774,40 @@ impl TabState {
         self.sync_from_viewport();
     }
 
+    /// The current position, with the view and filter it is in, for the
+    /// jump list.
+    pub fn jump_position(&self) -> JumpEntry {
+        JumpEntry {
+            line: self.viewport.selected_line(),
+            screen_offset: self.viewport.get_screen_offset(&self.source.line_indices),
+            view: self.source.mode,
+            filter: self
+                .source
+                .filter
+                .pattern
+                .clone()
+                .map(|pattern| (pattern, self.source.filter.mode)),
+        }
+    }
+
+    /// Record the current position before a jump moves away from it.
+    pub fn push_jump(&mut self) {
+        let entry = self.jump_position();
+        self.jumps.push(entry);
+    }
+
+    /// Go to a jump list position: its line (or the nearest visible one)
+    /// at the same height on screen. Follow mode is paused.
+    pub fn restore_jump(&mut self, entry: &JumpEntry) {
+        self.pause_follow();
+        self.viewport.jump_to_line_at_offset(
+            entry.line,
+            entry.screen_offset,
+            &self.source.line_indices,
+        );
+        self.sync_from_viewport();
+    }
+
     /// Select a specific file line number (for mouse click handling)
     pub fn select_line(&mut self, file_line: usize) {
         self.viewport.jump_to_line(file_line);
@@ -725,7 +1822,7 @@ impl TabState {
 
         // Get the actual file line number (not the index into line_indices)
         let file_line_number = match self.source.line_indices.get(self.selected_line) {
-            Some(&n) => n,
+            Some(n) => n,
             None => return,
         };
 
@@ -747,6 +1844,38 @@ impl TabState {
         self.expansion.expanded_lines.clear();
     }
 
+    /// Expand every line on screen, plus `EXPAND_ALL_BUFFER` lines on
+    /// either side so short scrolls stay expanded.
+    ///
+    /// Bounded to the window instead of all of `line_indices`, which can be
+    /// millions of lines.
+    pub fn expand_all_visible(&mut self) {
+        let total = self.source.line_indices.len();
+        if total == 0 {
+            return;
+        }
+        let height = self.viewport.height().max(1);
+        let start = self.scroll_position.saturating_sub(EXPAND_ALL_BUFFER);
+        let end = (self.scroll_position + height + EXPAND_ALL_BUFFER).min(total);
+        for display_idx in start..end {
+            if let Some(line) = self
+                .viewport
+                .line_at(&self.source.line_indices, display_idx)
+            {
+                self.expansion.expanded_lines.insert(line);
+            }
+        }
+    }
+
+    /// Re-expand lines remembered from a previous session, ignoring lines
+    /// past the end of the file.
+    pub fn restore_expanded_lines(&mut self, lines: &[usize]) {
+        let total = self.source.total_lines;
+        self.expansion
+            .expanded_lines
+            .extend(lines.iter().copied().filter(|&ln| ln < total));
+    }
+
     /// Handle a file modification event (works for both active and inactive tabs).
     ///
     /// Updates total_lines, line_indices, and triggers incremental filtering if needed.
@@ -755,38 +1884,44 @@ impl TabState {
         self.source.rate_tracker.record(new_total);
 
         if self.source.mode == ViewMode::Normal {
-            let old = self.source.line_indices.len();
-            if new_total > old {
-                self.source.line_indices.extend(old..new_total);
-            }
+            self.source.extend_unfiltered_indices(new_total);
         }
 
-        // Refresh index reader to pick up new flags/checkpoints from capture's sync()
-        if let (Some(ref mut ir), Some(ref path)) =
-            (&mut self.source.index_reader, &self.source.source_path)
+        self.refresh_index();
+        self.catch_up_filter();
+    }
+
+    /// Filter the lines added since the completed filter results were
+    /// computed, as an incremental run. Returns whether one was started.
+    ///
+    /// While a run is in flight nothing starts; it covers the lines that
+    /// existed when it began, and the next call after it completes picks up
+    /// the rest, so the results converge however fast the file grows.
+    pub fn catch_up_filter(&mut self) -> bool {
+        if !matches!(self.source.filter.state, FilterState::Complete { .. })
+            || self.source.filter.receiver.is_some()
         {
-            ir.refresh(path);
-        }
-
-        // If tab has a completed filter, trigger incremental filtering for new lines.
-        // Skip if still Processing — the in-flight filter hasn't finished yet.
-        if matches!(self.source.filter.state, FilterState::Complete { .. }) {
-            if let Some(pattern) = self.source.filter.pattern.clone() {
-                if new_total > self.source.filter.last_filtered_line {
-                    let mode = self.source.filter.mode;
-                    let range = Some((self.source.filter.last_filtered_line, new_total));
-                    if let Err(e) = crate::filter_orchestrator::FilterOrchestrator::trigger(
-                        &mut self.source,
-                        pattern,
-                        mode,
-                        range,
-                    ) {
-                        eprintln!("Incremental filter error: {}", e);
-                        self.source.filter.state = FilterState::Inactive;
-                    }
-                }
-            }
+            return false;
         }
+        let Some((pattern, mode)) = self.source.filter.scan() else {
+            return false;
+        };
+        let start = self.source.filter.last_filtered_line;
+        let end = self.source.total_lines;
+        if end <= start {
+            return false;
+        }
+        if let Err(e) = crate::filter_orchestrator::FilterOrchestrator::trigger(
+            &mut self.source,
+            pattern,
+            mode,
+            Some((start, end)),
+        ) {
+            eprintln!("Incremental filter error: {}", e);
+            self.source.filter.state = FilterState::Inactive;
+            return false;
+        }
+        true
     }
 
     /// Apply a filter event directly to this tab (works for both active and inactive tabs).
@@ -817,8 +1952,7 @@ impl TabState {
                 }
                 true
             }
-            AppEvent::FilterError(err) => {
-                eprintln!("Filter error: {}", err);
+            AppEvent::FilterError(_) => {
                 self.source.filter.state = FilterState::Inactive;
                 true
             }
@@ -833,9 +1967,11 @@ impl TabState {
     /// position to keep the view stable when items are prepended.
     pub fn merge_partial_filter_results(
         &mut self,
-        new_indices: Vec<usize>,
+        mut new_indices: Vec<usize>,
         lines_processed: usize,
     ) {
+        self.source.project_since_marker(&mut new_indices);
+
         if self.source.filter.needs_clear {
             self.source.mode = ViewMode::Filtered;
             self.source.line_indices.clear();
@@ -847,39 +1983,247 @@ impl TabState {
 
         let is_first_result = self.source.line_indices.is_empty();
         if is_first_result {
-            self.source.line_indices = new_indices;
+            self.source.line_indices = new_indices.into();
             self.viewport.jump_to_end(&self.source.line_indices);
         } else {
-            let first_existing = self.source.line_indices[0];
-            let prepended_count = new_indices
-                .iter()
-                .filter(|&&idx| idx < first_existing)
-                .count();
+            // "Prepended" is in display order: newest-first shows later lines on top
+            let prepended_count = if self.viewport.is_reversed() {
+                let last_existing = self.source.line_indices.last().unwrap_or(0);
+                new_indices
+                    .iter()
+                    .filter(|&&idx| idx > last_existing)
+                    .count()
+            } else {
+                let first_existing = self.source.line_indices.first().unwrap_or(0);
+                new_indices
+                    .iter()
+                    .filter(|&&idx| idx < first_existing)
+                    .count()
+            };
 
-            let mut merged = Vec::with_capacity(self.source.line_indices.len() + new_indices.len());
-            let mut i = 0;
-            let mut j = 0;
+            let existing = std::mem::take(&mut self.source.line_indices);
+            let mut merged = Vec::with_capacity(existing.len() + new_indices.len());
+            let mut old = existing.iter().peekable();
+            let mut new = new_indices.into_iter().peekable();
 
-            while i < self.source.line_indices.len() && j < new_indices.len() {
-                if self.source.line_indices[i] <= new_indices[j] {
-                    merged.push(self.source.line_indices[i]);
-                    i += 1;
+            while let (Some(&a), Some(&b)) = (old.peek(), new.peek()) {
+                if a <= b {
+                    merged.push(a);
+                    old.next();
                 } else {
-                    merged.push(new_indices[j]);
-                    j += 1;
+                    merged.push(b);
+                    new.next();
                 }
             }
 
-            merged.extend_from_slice(&self.source.line_indices[i..]);
-            merged.extend_from_slice(&new_indices[j..]);
+            merged.extend(old);
+            merged.extend(new);
 
-            self.source.line_indices = merged;
+            self.source.line_indices = merged.into();
             self.viewport.adjust_scroll_for_prepend(prepended_count);
         }
 
         self.source.filter.state = FilterState::Processing { lines_processed };
     }
 
+    /// Whether the file at the source path was replaced (e.g. log rotation).
+    pub fn file_was_replaced(&self) -> bool {
+        match (&self.source.source_path, self.source.file_id) {
+            (Some(path), Some(id)) => file_identity(path).is_some_and(|current| current != id),
+            _ => false,
+        }
+    }
+
+    /// Whether to offer building an index: a large file without one that has
+    /// not been offered one yet.
+    pub fn should_suggest_index(&self) -> bool {
+        let Some(path) = &self.source.source_path else {
+            return false;
+        };
+        !self.source.index_suggested
+            && self.source.index_reader.is_none()
+            && self.source.index_disabled.is_none()
+            && self.source.index_build.is_none()
+            && self
+                .source
+                .file_size
+                .is_some_and(|size| size >= SUGGEST_INDEX_MIN_BYTES)
+            // A broken index is reported separately (index_warning)
+            && !index_dir_for_log(path).exists()
+    }
+
+    /// Build the columnar index for this file in a background thread.
+    ///
+    /// Returns `Err` with a user-facing message if there is nothing to build.
+    pub fn start_index_build(&mut self) -> Result<(), String> {
+        let Some(path) = self.source.source_path.clone() else {
+            return Err("Only file sources can be indexed".to_string());
+        };
+        if self.source.index_build.is_some() {
+            return Err("Index build already in progress".to_string());
+        }
+        if self.source.index_reader.is_some() {
+            return Err("Source is already indexed".to_string());
+        }
+        if let Some(reason) = self.source.index_disabled {
+            return Err(format!("Indexing disabled: {}", reason));
+        }
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let result = IndexBuilder::new()
+                .with_checkpoint_interval(index_checkpoint_interval())
+                .build(&path, &index_dir_for_log(&path))
+                .map(|_| ());
+            let _ = tx.send(result);
+        });
+        self.source.index_build = Some(rx);
+        self.source.index_suggested = true;
+        Ok(())
+    }
+
+    /// Refresh the index reader to pick up new flags/checkpoints from
+    /// capture's `sync()`.
+    ///
+    /// If the file no longer matches the index (replaced or truncated), the
+    /// stale reader is dropped and the index is rebuilt in the background
+    /// (unless builds are deferred to low-resource mode's `I`).
+    pub fn refresh_index(&mut self) {
+        let (Some(ir), Some(path)) = (&mut self.source.index_reader, &self.source.source_path)
+        else {
+            return;
+        };
+        if ir.refresh(path) {
+            return;
+        }
+        self.source.index_reader = None;
+        self.source.index_size = None;
+        if !index_builds_deferred() {
+            let _ = self.start_index_build();
+        }
+    }
+
+    /// Check on a background index build. Once it has finished, attaches the
+    /// new index and returns the outcome.
+    ///
+    /// A build that failed because the index location can't be written
+    /// disables indexing for this source (see [`LogSource::index_disabled`]).
+    pub fn poll_index_build(&mut self) -> Option<Result<(), String>> {
+        let result = match self.source.index_build.as_ref()?.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => Err(anyhow::anyhow!("index build thread exited")),
+        };
+        self.source.index_build = None;
+
+        let result = result.map_err(|e| {
+            self.source.index_disabled = unwritable_reason(&e);
+            e.to_string()
+        });
+        let result = result.and_then(|()| {
+            let path = self
+                .source
+                .source_path
+                .as_ref()
+                .ok_or("source has no path")?;
+            let reader = IndexReader::open(path).ok_or("built index could not be opened")?;
+            self.source.index_reader = Some(reader);
+            self.source.index_size = calculate_index_size(path);
+            self.source.index_warning = None;
+            Ok(())
+        });
+        Some(result)
+    }
+
+    /// Re-attach to a replaced file after the reader has been reloaded.
+    ///
+    /// Refreshes the file identity, columnar index and watcher (the old
+    /// watcher still points at the rotated-away inode), then resets the view
+    /// like a truncation.
+    pub fn reattach_after_rotation(&mut self, new_total: usize) {
+        if let Some(path) = self.source.source_path.clone() {
+            self.source.file_id = file_identity(&path);
+            self.source.file_size = std::fs::metadata(&path).map(|m| m.len()).ok();
+            self.source.index_reader = IndexReader::open(&path);
+            self.source.index_size = self
+                .source
+                .index_reader
+                .as_ref()
+                .and_then(|_| calculate_index_size(&path));
+            if self.watcher.is_some() {
+                self.watcher = FileWatcher::new(&path).ok();
+            }
+        }
+        // Expanded line numbers referred to the old file's content
+        self.collapse_all();
+        self.reset_after_truncation(new_total);
+    }
+
+    /// Switch to diff mode: from now on the unfiltered view shows only the
+    /// lines that changed on the latest reload (initially all of them).
+    pub fn enable_diff(&mut self) -> Result<()> {
+        let Some(path) = self.source.source_path.clone() else {
+            anyhow::bail!("diff mode needs a file source");
+        };
+        let data =
+            std::fs::read(&path).with_context(|| format!("Failed to read: {}", path.display()))?;
+        self.source.diff = Some(LineDiff::new(&data));
+        if self.source.mode == ViewMode::Normal {
+            self.source.line_indices = self.source.unfiltered_indices();
+        }
+        Ok(())
+    }
+
+    /// Reload a diff-mode tab after its file changed.
+    ///
+    /// Rewritten files break the append-only assumptions of
+    /// `FileReader::reload`, so the reader is rebuilt from scratch, in place:
+    /// the `$all` tab shares it. Returns `false` when the content is
+    /// unchanged.
+    pub fn reload_diff(&mut self, encoding: Encoding) -> Result<bool> {
+        let (Some(path), Some(diff)) = (self.source.source_path.clone(), &mut self.source.diff)
+        else {
+            return Ok(false);
+        };
+        let data =
+            std::fs::read(&path).with_context(|| format!("Failed to read: {}", path.display()))?;
+        if !diff.update(&data) {
+            return Ok(false);
+        }
+
+        let mut reader = FileReader::new(&path)?;
+        reader.set_encoding(encoding);
+        let new_total = reader.total_lines();
+        let unshared = {
+            let mut current = match self.source.reader.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            match current.as_any_mut().downcast_mut::<FileReader>() {
+                Some(current) => {
+                    *current = reader;
+                    None
+                }
+                None => Some(reader),
+            }
+        };
+        if let Some(reader) = unshared {
+            self.source.reader = Arc::new(Mutex::new(reader));
+        }
+        self.source.file_size = Some(data.len() as u64);
+        let new_id = file_identity(&path);
+        if new_id != self.source.file_id {
+            // Replaced by rename: the watcher is still on the old inode
+            self.source.file_id = new_id;
+            if self.watcher.is_some() {
+                self.watcher = FileWatcher::new(&path).ok();
+            }
+        }
+        self.collapse_all();
+        self.reset_after_truncation(new_total);
+        Ok(true)
+    }
+
     /// Reset tab state after file truncation.
     ///
     /// Cancels any in-progress filter, resets all filter state,
@@ -890,10 +2234,10 @@ impl TabState {
         FilterOrchestrator::cancel(&mut self.source);
         self.source.filter.receiver = None;
         self.source.filter.is_incremental = false;
+        self.source.filter.result_cache.clear();
 
         self.source.total_lines = new_total;
         self.source.rate_tracker.record(new_total);
-        self.source.line_indices = (0..new_total).collect();
         self.source.mode = ViewMode::Normal;
 
         self.source.filter.pattern = None;
@@ -901,6 +2245,17 @@ impl TabState {
         self.source.filter.last_filtered_line = 0;
         self.source.filter.cancel_token = None;
         self.source.filter.needs_clear = false;
+        // The marker pointed into content that is gone
+        self.source.since_line = None;
+        if self.source.follow_paused_at.is_some() {
+            self.source.follow_paused_at = Some(new_total);
+        }
+        self.source.line_indices = self.source.unfiltered_indices();
+
+        // Line numbers past the new end no longer refer to anything
+        self.expansion
+            .expanded_lines
+            .retain(|&line_number| line_number < new_total);
 
         if new_total > 0 {
             self.jump_to_end();
@@ -910,49 +2265,157 @@ impl TabState {
     }
 }
 
-/// Spawn a background thread to read from a stream and send batches of lines
-fn spawn_stream_reader<R: std::io::Read + Send + 'static>(reader: R, tx: Sender<StreamMessage>) {
-    thread::spawn(move || {
-        let buf_reader = BufReader::new(reader);
-        let mut batch = Vec::with_capacity(STREAM_BATCH_SIZE);
-
-        for line in buf_reader.lines() {
-            match line {
-                Ok(line) => {
-                    batch.push(line);
-                    if batch.len() >= STREAM_BATCH_SIZE {
-                        if tx
-                            .send(StreamMessage::Lines(std::mem::take(&mut batch)))
-                            .is_err()
-                        {
-                            // Receiver dropped, stop reading
-                            return;
-                        }
-                        batch = Vec::with_capacity(STREAM_BATCH_SIZE);
-                    }
-                }
-                Err(e) => {
-                    // Send any remaining lines before error
-                    if !batch.is_empty() {
-                        let _ = tx.send(StreamMessage::Lines(std::mem::take(&mut batch)));
+/// Whether the index next to `path` was written by [`IndexBuilder`], which
+/// records a content fingerprint, so it can be rebuilt from the file alone.
+/// Capture-mode indexes are owned by the capture process and left alone,
+/// unless they were written in an older format nothing can read anymore.
+fn index_is_rebuildable(path: &Path) -> bool {
+    let meta = index_dir_for_log(path).join("meta");
+    IndexMeta::is_outdated(&meta) || IndexMeta::read_from(&meta).is_ok_and(|m| m.sample_hash != 0)
+}
+
+/// Spawn a background thread to read from a stream and send batches of lines.
+/// An unterminated last line is reported as [`StreamMessage::Partial`] and
+/// left for the receiver to flush on `Complete`.
+fn spawn_stream_reader<R: Read + Send + 'static>(reader: R, tx: Sender<StreamMessage>) {
+    thread::spawn(
+        move || match read_line_batches(reader, &tx, true, line_from_bytes) {
+            Ok(true) => {
+                let _ = tx.send(StreamMessage::Complete);
+            }
+            // Receiver dropped, stop reading
+            Ok(false) => {}
+            Err(e) => {
+                let _ = tx.send(StreamMessage::Error(e.to_string()));
+            }
+        },
+    );
+}
+
+/// Read `reader` to the end, sending its lines to `tx` in batches.
+/// Invalid UTF-8 is replaced rather than ending the read, so an error is
+/// always an I/O failure.
+///
+/// Returns `Ok(false)` if the receiver was dropped. Lines read before an
+/// error are sent before the error is returned.
+pub(super) fn send_line_batches<R: Read>(
+    reader: R,
+    tx: &Sender<StreamMessage>,
+) -> io::Result<bool> {
+    read_line_batches(reader, tx, false, lossy_line_from_bytes)
+}
+
+/// Read `reader` to the end, sending complete lines to `tx`.
+///
+/// Lines go out once a read comes back short of a full buffer, i.e. the
+/// writer isn't outpacing us, so slow pipes show up live while fast ones
+/// still arrive in large batches. With `partials`, the unterminated tail is
+/// sent as [`StreamMessage::Partial`] whenever it changes at such a pause,
+/// and is not sent as a line at the end; otherwise it is the final line.
+/// Complete lines are decoded with `decode`.
+fn read_line_batches<R: Read>(
+    mut reader: R,
+    tx: &Sender<StreamMessage>,
+    partials: bool,
+    decode: fn(Vec<u8>) -> io::Result<String>,
+) -> io::Result<bool> {
+    let mut buf = vec![0; STREAM_READ_SIZE];
+    let mut batch = Vec::new();
+    // Bytes of the line being read, and how many of them were last reported
+    let mut pending = Vec::new();
+    let mut reported = 0;
+
+    let result = loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break Ok(()),
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => break Err(e),
+        };
+        let mut line_error = None;
+        for piece in buf[..n].split_inclusive(|&b| b == b'\n') {
+            pending.extend_from_slice(piece);
+            if pending.last() == Some(&b'\n') {
+                match decode(std::mem::take(&mut pending)) {
+                    Ok(line) => batch.push(line),
+                    Err(e) => {
+                        line_error = Some(e);
+                        break;
                     }
-                    let _ = tx.send(StreamMessage::Error(e.to_string()));
-                    return;
                 }
+                reported = 0;
+            }
+        }
+        if let Some(e) = line_error {
+            break Err(e);
+        }
+
+        let paused = n < buf.len();
+        if ((paused && !batch.is_empty()) || batch.len() >= STREAM_BATCH_SIZE)
+            && tx
+                .send(StreamMessage::Lines(std::mem::take(&mut batch)))
+                .is_err()
+        {
+            return Ok(false);
+        }
+        if partials && paused && pending.len() != reported {
+            reported = pending.len();
+            let partial = String::from_utf8_lossy(&pending).into_owned();
+            if tx.send(StreamMessage::Partial(partial)).is_err() {
+                return Ok(false);
+            }
+        }
+    };
+
+    if !batch.is_empty() && tx.send(StreamMessage::Lines(batch)).is_err() {
+        return Ok(false);
+    }
+    if !pending.is_empty() && pending.len() != reported {
+        let message = if partials {
+            StreamMessage::Partial(String::from_utf8_lossy(&pending).into_owned())
+        } else {
+            match decode(pending) {
+                Ok(line) => StreamMessage::Lines(vec![line]),
+                Err(e) => return Err(e),
             }
+        };
+        if tx.send(message).is_err() {
+            return Ok(false);
         }
+    }
+    result.map(|()| true)
+}
 
-        // Send any remaining lines
-        if !batch.is_empty() {
-            let _ = tx.send(StreamMessage::Lines(batch));
+/// Decode a line read from a stream, dropping its `\n` or `\r\n` ending.
+/// Invalid UTF-8 is an error, as with [`std::io::BufRead::lines`].
+fn line_from_bytes(mut bytes: Vec<u8>) -> io::Result<String> {
+    strip_line_ending(&mut bytes);
+    String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Decode a line read from a stream like [`line_from_bytes`], replacing
+/// invalid UTF-8 with U+FFFD.
+fn lossy_line_from_bytes(mut bytes: Vec<u8>) -> io::Result<String> {
+    strip_line_ending(&mut bytes);
+    Ok(match String::from_utf8(bytes) {
+        Ok(line) => line,
+        Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+    })
+}
+
+fn strip_line_ending(bytes: &mut Vec<u8>) {
+    if bytes.last() == Some(&b'\n') {
+        bytes.pop();
+        if bytes.last() == Some(&b'\r') {
+            bytes.pop();
         }
-        let _ = tx.send(StreamMessage::Complete);
-    });
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::filter::engine::FilterProgress;
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -976,6 +2439,453 @@ mod tests {
         assert!(tab.source.follow_mode); // Follow mode enabled by default
     }
 
+    #[test]
+    fn test_format_renderer_follows_dominant_format() {
+        let format_renderers = config::types::FormatRenderers {
+            json: Some("my-json".to_string()),
+            logfmt: None,
+        };
+        let json = create_temp_log_file(&[r#"{"level":"info"}"#, r#"{"level":"warn"}"#]);
+        let mut tab = TabState::new(json.path().to_path_buf(), false).unwrap();
+        tab.apply_format_renderer(&format_renderers);
+        assert_eq!(tab.source.renderer_names, vec!["my-json"]);
+
+        // Explicit renderers win
+        let mut tab = TabState::new(json.path().to_path_buf(), false).unwrap();
+        tab.source.renderer_names = vec!["mine".to_string()];
+        tab.apply_format_renderer(&format_renderers);
+        assert_eq!(tab.source.renderer_names, vec!["mine"]);
+
+        // No preset for logfmt, none for mixed lines
+        let logfmt = create_temp_log_file(&["level=info msg=up"]);
+        let mut tab = TabState::new(logfmt.path().to_path_buf(), false).unwrap();
+        tab.apply_format_renderer(&format_renderers);
+        assert!(tab.source.renderer_names.is_empty());
+
+        let mixed = create_temp_log_file(&[r#"{"level":"info"}"#, "plain text"]);
+        let mut tab = TabState::new(mixed.path().to_path_buf(), false).unwrap();
+        tab.apply_format_renderer(&format_renderers);
+        assert!(tab.source.renderer_names.is_empty());
+    }
+
+    #[test]
+    fn test_ended_at_marks_only_the_active_to_ended_transition() {
+        let temp_file = create_temp_log_file(&["line"]);
+        let mut tab = TabState::new(temp_file.path().to_path_buf(), false).unwrap();
+
+        // Already ended when first seen: no time to report
+        tab.set_source_status(SourceStatus::Ended);
+        assert!(tab.source.ended_at.is_none());
+
+        tab.set_source_status(SourceStatus::Active);
+        tab.set_source_status(SourceStatus::Ended);
+        let ended_at = tab.source.ended_at.expect("transition recorded");
+        tab.set_source_status(SourceStatus::Ended);
+        assert_eq!(tab.source.ended_at, Some(ended_at));
+
+        tab.set_source_status(SourceStatus::Active);
+        assert!(tab.source.ended_at.is_none());
+    }
+
+    #[test]
+    fn test_newest_line_at_or_above_only_checks_given_lines() {
+        let temp_file =
+            create_temp_log_file(&["ERROR old", "INFO a", "WARN b", "ERROR c", "INFO d"]);
+        let tab = TabState::new(temp_file.path().to_path_buf(), false).unwrap();
+
+        assert_eq!(
+            tab.newest_line_at_or_above(Severity::Error, 1..5),
+            Some((3, Severity::Error))
+        );
+        assert_eq!(
+            tab.newest_line_at_or_above(Severity::Warn, 1..3),
+            Some((2, Severity::Warn))
+        );
+        assert_eq!(tab.newest_line_at_or_above(Severity::Error, 1..3), None);
+        assert_eq!(tab.newest_line_at_or_above(Severity::Fatal, 0..5), None);
+    }
+
+    #[test]
+    fn test_reapplied_filter_is_served_from_cache() {
+        let temp_file = create_temp_log_file(&["error a", "ok", "error b"]);
+        let mut tab = TabState::new(temp_file.path().to_path_buf(), false).unwrap();
+        tab.source.filter.mode = FilterMode::plain();
+        tab.apply_filter(vec![0, 2], "error".to_string());
+        tab.clear_filter();
+
+        tab.start_filter("error".to_string(), FilterMode::plain())
+            .unwrap();
+        assert!(tab.source.filter.cancel_token.is_none());
+        let progress = tab.source.filter.receiver.as_ref().unwrap().try_recv();
+        let Ok(FilterProgress::Complete { matches, .. }) = progress else {
+            panic!("expected cached results, got {:?}", progress);
+        };
+        assert_eq!(matches, vec![0, 2]);
+
+        // Truncation invalidates the cache
+        tab.reset_after_truncation(1);
+        assert!(tab
+            .source
+            .filter
+            .result_cache
+            .get(&tab.source.result_key("error", FilterMode::plain()))
+            .is_none());
+    }
+
+    #[test]
+    fn test_catch_up_filter_results_are_not_cached() {
+        let temp_file = create_temp_log_file(&["error a", "ok", "error b", "error c"]);
+        let mut tab = TabState::new(temp_file.path().to_path_buf(), false).unwrap();
+        tab.source.filter.mode = FilterMode::plain();
+        tab.source.total_lines = 3;
+        tab.apply_filter(vec![0, 2], "error".to_string());
+
+        tab.source.total_lines = 4;
+        tab.append_filter_results(vec![3]);
+        assert_eq!(tab.source.line_indices.len(), 3);
+        assert!(tab
+            .source
+            .filter
+            .result_cache
+            .get(&tab.source.result_key("error", FilterMode::plain()))
+            .is_none());
+    }
+
+    #[test]
+    fn test_catch_up_returns_only_appended_lines() {
+        let temp_file = create_temp_log_file(&["error a", "ok", "error b", "error c"]);
+        let mut tab = TabState::new(temp_file.path().to_path_buf(), false).unwrap();
+        tab.apply_filter(vec![0, 2], "error".to_string());
+
+        // The catch-up run repeats line 2, which the full scan already matched
+        let added = tab.append_filter_results(vec![2, 3]);
+        assert_eq!(added, vec![3]);
+        assert_eq!(tab.source.line_indices.to_vec(), vec![0, 2, 3]);
+    }
+
+    #[test]
+    fn test_streamed_lines_record_arrival_time() {
+        let mut tab = TabState::from_listener("127.0.0.1:0").unwrap();
+        let before = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        tab.append_stream_lines(vec!["a".to_string(), "b".to_string()]);
+        tab.append_stream_lines(vec!["c".to_string()]);
+
+        let arrivals = &tab.source.arrival_times;
+        let first = arrivals.get(0).unwrap();
+        assert!(first >= before);
+        assert_eq!(arrivals.get(1), Some(first));
+        assert!(arrivals.get(2).unwrap() >= first);
+
+        // Files have no arrival times
+        let temp_file = create_temp_log_file(&["line1"]);
+        let file_tab = TabState::new(temp_file.path().to_path_buf(), false).unwrap();
+        assert_eq!(file_tab.source.arrival_times.get(0), None);
+    }
+
+    #[test]
+    fn test_merge_stdin_appends_stream_after_file_context() {
+        let mut stream = TabState::from_listener("127.0.0.1:0").unwrap();
+        stream.append_stream_lines(vec!["early".to_string()]);
+        let temp_file = create_temp_log_file(&["ctx1", "ctx2"]);
+        let file_tab = TabState::new(temp_file.path().to_path_buf(), false).unwrap();
+
+        let mut tab = TabState::merge_stdin(stream, vec![file_tab]);
+        assert!(tab.is_combined);
+        assert_eq!(tab.source.name, "<stdin> + 1 file");
+        assert_eq!(tab.source.total_lines, 3);
+
+        tab.append_stream_lines(vec!["live".to_string()]);
+        assert_eq!(tab.source.total_lines, 4);
+        assert_eq!(tab.source.line_indices.len(), 4);
+
+        let mut reader = tab.source.reader.lock().unwrap();
+        let lines: Vec<_> = (0..4)
+            .map(|i| reader.get_line(i).unwrap().unwrap())
+            .collect();
+        assert_eq!(lines, vec!["ctx1", "ctx2", "early", "live"]);
+    }
+
+    /// Reader that returns each chunk from a separate `read` call
+    struct Chunks(std::collections::VecDeque<&'static [u8]>);
+
+    impl Read for Chunks {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let Some(chunk) = self.0.pop_front() else {
+                return Ok(0);
+            };
+            buf[..chunk.len()].copy_from_slice(chunk);
+            Ok(chunk.len())
+        }
+    }
+
+    #[test]
+    fn test_stream_reader_sends_complete_lines_and_reports_partial_tail() {
+        let chunks = Chunks(vec![&b"one\ntw"[..], b"o\r\nthr", b"ee"].into());
+        let (tx, rx) = mpsc::channel();
+        assert!(read_line_batches(chunks, &tx, true, line_from_bytes).unwrap());
+        drop(tx);
+
+        let messages: Vec<_> = rx
+            .iter()
+            .map(|m| match m {
+                StreamMessage::Lines(lines) => format!("lines {:?}", lines),
+                StreamMessage::Partial(partial) => format!("partial {:?}", partial),
+                other => panic!("unexpected message: {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                r#"lines ["one"]"#,
+                r#"partial "tw""#,
+                r#"lines ["two"]"#,
+                r#"partial "thr""#,
+                r#"partial "three""#,
+            ]
+        );
+
+        // Without partials, the tail is the last line
+        let chunks = Chunks(vec![&b"a\nb"[..]].into());
+        let (tx, rx) = mpsc::channel();
+        assert!(send_line_batches(chunks, &tx).unwrap());
+        drop(tx);
+        let lines: Vec<String> = rx
+            .iter()
+            .flat_map(|m| match m {
+                StreamMessage::Lines(lines) => lines,
+                other => panic!("unexpected message: {:?}", other),
+            })
+            .collect();
+        assert_eq!(lines, vec!["a", "b"]);
+
+        // Invalid UTF-8 is replaced instead of ending the read
+        let chunks = Chunks(vec![&b"bad \xff\nok\n"[..]].into());
+        let (tx, rx) = mpsc::channel();
+        assert!(send_line_batches(chunks, &tx).unwrap());
+        drop(tx);
+        let lines: Vec<String> = rx
+            .iter()
+            .flat_map(|m| match m {
+                StreamMessage::Lines(lines) => lines,
+                other => panic!("unexpected message: {:?}", other),
+            })
+            .collect();
+        assert_eq!(lines, vec!["bad \u{fffd}", "ok"]);
+    }
+
+    #[test]
+    fn test_partial_stream_line_is_held_until_complete() {
+        let mut tab = TabState::from_listener("127.0.0.1:0").unwrap();
+        tab.append_stream_lines(vec!["first".to_string()]);
+        tab.set_stream_partial("sec".to_string());
+        assert_eq!(tab.source.total_lines, 1);
+
+        tab.append_stream_lines(vec!["second".to_string()]);
+        tab.set_stream_partial("thi".to_string());
+        tab.mark_stream_complete();
+        assert_eq!(tab.source.total_lines, 3);
+        let mut reader = tab.source.reader.lock().unwrap();
+        assert_eq!(reader.get_line(2).unwrap().as_deref(), Some("thi"));
+    }
+
+    #[test]
+    fn test_partial_stream_line_shows_when_unbuffered() {
+        let mut tab = TabState::from_listener("127.0.0.1:0").unwrap();
+        tab.show_partial_lines = true;
+        tab.append_stream_lines(vec!["first".to_string()]);
+        tab.set_stream_partial("sec".to_string());
+        tab.set_stream_partial("secon".to_string());
+        assert_eq!(tab.source.total_lines, 2);
+        assert_eq!(tab.source.line_indices.len(), 2);
+        assert_eq!(
+            tab.source
+                .reader
+                .lock()
+                .unwrap()
+                .get_line(1)
+                .unwrap()
+                .as_deref(),
+            Some("secon")
+        );
+
+        tab.append_stream_lines(vec!["second".to_string(), "third".to_string()]);
+        assert_eq!(tab.source.total_lines, 3);
+        assert_eq!(tab.source.line_indices.len(), 3);
+        let mut reader = tab.source.reader.lock().unwrap();
+        assert_eq!(reader.get_line(1).unwrap().as_deref(), Some("second"));
+    }
+
+    #[test]
+    fn test_index_suggested_once_for_large_unindexed_file() {
+        let temp_file = create_temp_log_file(&["line1"]);
+        let mut tab = TabState::new(temp_file.path().to_path_buf(), false).unwrap();
+        assert!(!tab.should_suggest_index());
+
+        tab.source.file_size = Some(SUGGEST_INDEX_MIN_BYTES);
+        assert!(tab.should_suggest_index());
+
+        tab.source.index_suggested = true;
+        assert!(!tab.should_suggest_index());
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_background_index_build_attaches_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "INFO start\nERROR boom\n").unwrap();
+        let mut tab = TabState::new(path, false).unwrap();
+        assert!(tab.source.index_reader.is_none());
+
+        tab.start_index_build().unwrap();
+        assert!(tab.start_index_build().is_err());
+        let result = loop {
+            if let Some(result) = tab.poll_index_build() {
+                break result;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        };
+
+        assert_eq!(result, Ok(()));
+        assert!(tab.source.index_reader.is_some());
+        assert!(tab.source.index_build.is_none());
+        assert_eq!(
+            tab.start_index_build(),
+            Err("Source is already indexed".to_string())
+        );
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_unwritable_index_location_disables_indexing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "INFO start\n").unwrap();
+        let mut tab = TabState::new(path, false).unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        tx.send(Err(anyhow::Error::from(std::io::Error::from(
+            std::io::ErrorKind::ReadOnlyFilesystem,
+        ))))
+        .unwrap();
+        tab.source.index_build = Some(rx);
+
+        assert!(tab.poll_index_build().unwrap().is_err());
+        assert_eq!(
+            tab.source.index_disabled,
+            Some("log is on a read-only filesystem")
+        );
+        assert!(!tab.should_suggest_index());
+        assert!(tab.start_index_build().is_err());
+        assert!(tab.source.index_build.is_none());
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_severity_breakdown_counts_from_index_and_tail() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "INFO start\nERROR boom\nplain\n").unwrap();
+        IndexBuilder::new()
+            .build(&path, &index_dir_for_log(&path))
+            .unwrap();
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        file.write_all(b"ERROR again\nWARN late\n").unwrap();
+
+        let mut tab = TabState::new(path, false).unwrap();
+        assert_eq!(tab.source.index_reader.as_ref().unwrap().len(), 3);
+        assert_eq!(tab.show_severity_breakdown(), Some(5));
+        let result = tab.source.aggregation_result.as_ref().unwrap();
+        assert!(result.lines_deferred);
+        let groups: Vec<_> = result
+            .groups
+            .iter()
+            .map(|g| (g.key[0].1.as_str(), g.count))
+            .collect();
+        assert_eq!(
+            groups,
+            vec![("error", 2), ("warn", 1), ("info", 1), ("unknown", 1)]
+        );
+        assert_eq!(tab.lines_with_severity(Severity::Error), vec![1, 3]);
+        assert_eq!(tab.lines_with_severity(Severity::Unknown), vec![2]);
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_stale_index_is_rebuilt_instead_of_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "INFO start\nERROR boom\n").unwrap();
+        IndexBuilder::new()
+            .build(&path, &index_dir_for_log(&path))
+            .unwrap();
+        std::fs::write(&path, "INFO START\nERROR BOOM\n").unwrap();
+
+        let tab = TabState::new(path, false).unwrap();
+        assert!(tab.source.index_reader.is_none());
+        assert!(tab.source.index_warning.is_none());
+        assert!(tab.source.index_build.is_some());
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_old_format_index_is_rebuilt_instead_of_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, "INFO start\nERROR boom\n").unwrap();
+        let idx_dir = index_dir_for_log(&path);
+        std::fs::create_dir_all(&idx_dir).unwrap();
+        let mut old = IndexMeta::new().to_bytes();
+        old[4..6].copy_from_slice(&0u16.to_le_bytes());
+        std::fs::write(idx_dir.join("meta"), old).unwrap();
+
+        let mut tab = TabState::new(path, false).unwrap();
+        assert!(tab.source.index_reader.is_none());
+        assert!(tab.source.index_warning.is_none());
+        let rx = tab.source.index_build.take().unwrap();
+        rx.recv().unwrap().unwrap();
+        let meta = IndexMeta::read_from(idx_dir.join("meta")).unwrap();
+        assert_eq!(meta.version, IndexMeta::new().version);
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_waiting_tab_attaches_once_file_appears() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        let mut tab = TabState::waiting_for(path.clone(), false, false).unwrap();
+        assert!(tab.is_waiting());
+        assert!(tab.source.disabled);
+        assert_eq!(tab.source.name, "app.log");
+        assert!(!tab.attach_if_created(true).unwrap());
+
+        std::fs::write(&path, "first\nsecond\n").unwrap();
+        assert!(tab.attach_if_created(true).unwrap());
+        assert!(!tab.is_waiting());
+        assert!(!tab.source.disabled);
+        assert!(tab.source.follow_mode);
+        assert_eq!(tab.source.total_lines, 2);
+        assert!(tab.source.index_build.is_some());
+    }
+
+    #[test]
+    fn test_binary_file_refused_unless_forced() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"PK\x03\x04\x00\x00\n").unwrap();
+        file.flush().unwrap();
+        let path = file.path().to_path_buf();
+
+        let err = TabState::open(path.clone(), false, false).err().unwrap();
+        assert!(err.to_string().contains("--force-text"));
+        assert!(TabState::open(path, false, true).is_ok());
+    }
+
     #[test]
     fn test_tab_name_extraction() {
         let temp_file = create_temp_log_file(&["line1"]);
@@ -1115,6 +3025,171 @@ mod tests {
         assert_eq!(tab.selected_line, 6); // 8 - 2
     }
 
+    /// Apply a tab's filter progress until its run completes, as the main
+    /// loop does for background tabs.
+    fn finish_filter(tab: &mut TabState) {
+        let receiver = tab
+            .source
+            .filter
+            .receiver
+            .take()
+            .expect("no filter running");
+        while let Ok(progress) = receiver.recv() {
+            let is_incremental = tab.source.filter.is_incremental;
+            let events = crate::handlers::filter::handle_filter_progress(progress, is_incremental);
+            if events.iter().any(|event| tab.apply_filter_event(event)) {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn test_filter_catches_up_on_lines_added_during_a_run() {
+        let mut temp_file = create_temp_log_file(&["error a", "ok", "error b"]);
+        let mut tab = TabState::new(temp_file.path().to_path_buf(), false).unwrap();
+        tab.start_filter("error".to_string(), FilterMode::plain())
+            .unwrap();
+
+        // Lines arrive while the full run is in flight: no second run starts
+        writeln!(temp_file, "error c\nok").unwrap();
+        tab.apply_file_modification(5);
+        assert!(tab.source.filter.receiver.is_some());
+        finish_filter(&mut tab);
+        assert_eq!(tab.source.filter.last_filtered_line, 3);
+        assert_eq!(tab.source.unfiltered_new_lines(), 2);
+
+        // The catch-up run covers them, without repeating lines the full
+        // scan already reached
+        assert!(tab.catch_up_filter());
+        assert!(tab.source.filter.is_incremental);
+        finish_filter(&mut tab);
+        assert_eq!(tab.source.line_indices, vec![0, 2, 3]);
+        assert_eq!(tab.source.unfiltered_new_lines(), 0);
+        assert!(!tab.catch_up_filter());
+    }
+
+    #[test]
+    fn test_expansion_survives_growth_and_prunes_on_truncation() {
+        let temp_file = create_temp_log_file(&["a", "b", "c", "d", "e"]);
+        let mut tab = TabState::new(temp_file.path().to_path_buf(), false).unwrap();
+        tab.expansion.expanded_lines.extend([1, 4]);
+
+        tab.apply_file_modification(8);
+        assert!(tab.is_line_expanded(1));
+        assert!(tab.is_line_expanded(4));
+
+        tab.reset_after_truncation(3);
+        assert!(tab.is_line_expanded(1));
+        assert!(!tab.is_line_expanded(4));
+
+        tab.reset_after_truncation(0);
+        assert!(tab.expansion.expanded_lines.is_empty());
+    }
+
+    #[test]
+    fn test_since_marker_shows_only_new_lines() {
+        let temp_file = create_temp_log_file(&["a", "b", "c"]);
+        let mut tab = TabState::new(temp_file.path().to_path_buf(), false).unwrap();
+
+        tab.toggle_since_marker();
+        assert_eq!(tab.source.since_line, Some(3));
+        assert!(tab.source.line_indices.is_empty());
+
+        tab.apply_file_modification(5);
+        assert_eq!(tab.source.line_indices, vec![3, 4]);
+
+        // Filter results are projected too
+        tab.apply_filter(vec![0, 2, 4], "x".to_string());
+        assert_eq!(tab.source.line_indices, vec![4]);
+
+        tab.clear_filter();
+        assert_eq!(tab.source.line_indices, vec![3, 4]);
+
+        // Same key resets
+        tab.toggle_since_marker();
+        assert_eq!(tab.source.since_line, None);
+        assert_eq!(tab.source.line_indices, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_default_filter_applied_and_cleared() {
+        let temp_file = create_temp_log_file(&["info a", "error b", "info c"]);
+        let mut tab = TabState::new(temp_file.path().to_path_buf(), false).unwrap();
+        tab.source.default_filter = Some(config::types::DefaultFilterConfig {
+            pattern: "error".to_string(),
+            mode: config::types::DefaultFilterMode::Plain,
+        });
+
+        tab.apply_default_filter().unwrap();
+        assert_eq!(tab.source.filter.pattern.as_deref(), Some("error"));
+        assert!(matches!(
+            tab.source.filter.state,
+            FilterState::Processing { .. }
+        ));
+
+        tab.apply_filter(vec![1], "error".to_string());
+        assert!(tab.source.is_default_filter_active());
+
+        tab.clear_filter();
+        assert!(!tab.source.is_default_filter_active());
+    }
+
+    #[test]
+    fn test_invalid_default_filter_reports_error() {
+        let temp_file = create_temp_log_file(&["a"]);
+        let mut tab = TabState::new(temp_file.path().to_path_buf(), false).unwrap();
+        tab.source.default_filter = Some(config::types::DefaultFilterConfig {
+            pattern: "(unclosed".to_string(),
+            mode: config::types::DefaultFilterMode::Regex,
+        });
+
+        assert!(tab.apply_default_filter().is_err());
+        assert_eq!(tab.source.filter.state, FilterState::Inactive);
+    }
+
+    #[test]
+    fn test_since_marker_cleared_on_truncation() {
+        let temp_file = create_temp_log_file(&["a", "b", "c"]);
+        let mut tab = TabState::new(temp_file.path().to_path_buf(), false).unwrap();
+
+        tab.toggle_since_marker();
+        tab.reset_after_truncation(2);
+
+        assert_eq!(tab.source.since_line, None);
+        assert_eq!(tab.source.line_indices, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_diff_mode_shows_only_changed_lines() {
+        let temp_file = create_temp_log_file(&["cpu: 10", "mem: 50", "disk: 70"]);
+        let mut tab = TabState::new(temp_file.path().to_path_buf(), false).unwrap();
+        tab.enable_diff().unwrap();
+        assert_eq!(tab.source.line_indices, vec![0, 1, 2]);
+        // As the `$all` tab holds it
+        let shared = tab.source.reader.clone();
+
+        // Same-size rewrite in place: FileReader alone would not notice
+        std::fs::write(temp_file.path(), "cpu: 12\nmem: 50\ndisk: 70\n").unwrap();
+        assert!(tab.reload_diff(Encoding::default()).unwrap());
+        assert_eq!(tab.source.line_indices, vec![0]);
+        let line = shared.lock().unwrap().get_line(0).unwrap();
+        assert_eq!(line.as_deref(), Some("cpu: 12"));
+
+        assert!(!tab.reload_diff(Encoding::default()).unwrap());
+    }
+
+    #[test]
+    fn test_restore_expanded_lines_ignores_lines_past_end() {
+        let temp_file = create_temp_log_file(&["a", "b", "c"]);
+        let mut tab = TabState::new(temp_file.path().to_path_buf(), false).unwrap();
+
+        tab.restore_expanded_lines(&[0, 2, 3, 100]);
+
+        assert!(tab.is_line_expanded(0));
+        assert!(tab.is_line_expanded(2));
+        assert_eq!(tab.expansion.expanded_lines.len(), 2);
+    }
+
     #[test]
     fn test_toggle_expansion() {
         let temp_file = create_temp_log_file(&["line1", "line2", "line3"]);
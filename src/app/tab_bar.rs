@@ -0,0 +1,184 @@
+//! Layout of the horizontal tab bar (`nav: tabbar`).
+//!
+//! The same layout drives rendering and mouse hit-testing, so both always
+//! agree on where each tab is.
+
+use super::tab_manager::TabManager;
+use super::SourceType;
+use unicode_width::UnicodeWidthStr;
+
+/// Longest source name shown in full; longer names are truncated
+const MAX_NAME_WIDTH: usize = 24;
+
+/// What clicking a tab bar entry switches to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabBarTarget {
+    /// A category's `$all` view
+    Combined(SourceType),
+    /// A regular tab (index into `TabManager::tabs`)
+    Tab(usize),
+}
+
+/// One entry of the tab bar, positioned relative to the bar's left edge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TabBarItem {
+    pub x: u16,
+    pub width: u16,
+    /// Text including its one-column padding on each side
+    pub label: String,
+    pub target: TabBarTarget,
+    pub active: bool,
+    /// First entry of a category (a separator is drawn before it)
+    pub group_start: bool,
+}
+
+/// Lay out the tab bar for a bar `width` columns wide: categories in
+/// side-panel order, each with its `$all` entry first, separated by one
+/// column. Tabs are numbered like the 1-9 shortcuts. When the entries
+/// don't fit, the bar scrolls so the active one is visible.
+pub fn layout(tab_mgr: &TabManager, width: u16) -> Vec<TabBarItem> {
+    let mut items = Vec::new();
+    let mut x = 0usize;
+    let mut number = 0usize;
+
+    for (cat, tab_indices) in tab_mgr.tabs_by_category() {
+        if tab_indices.is_empty() {
+            continue;
+        }
+        let mut group_start = !items.is_empty();
+        if group_start {
+            x += 1;
+        }
+
+        let mut push = |label: String, target: TabBarTarget, active: bool| {
+            let label_width = label.width();
+            items.push(TabBarItem {
+                x: x as u16,
+                width: label_width as u16,
+                label,
+                target,
+                active,
+                group_start,
+            });
+            x += label_width;
+            group_start = false;
+        };
+
+        if tab_mgr.combined[cat as usize].is_some() {
+            push(
+                " $all ".to_string(),
+                TabBarTarget::Combined(cat),
+                tab_mgr.active_combined == Some(cat),
+            );
+        }
+        for tab_idx in tab_indices {
+            number += 1;
+            let name = truncate(&tab_mgr.tabs[tab_idx].source.name);
+            let label = if number <= 9 {
+                format!(" {} {} ", number, name)
+            } else {
+                format!(" {} ", name)
+            };
+            let active = tab_idx == tab_mgr.active && tab_mgr.active_combined.is_none();
+            push(label, TabBarTarget::Tab(tab_idx), active);
+        }
+    }
+
+    // Scroll so the active entry ends at the right edge if it doesn't fit
+    let shift = items
+        .iter()
+        .find(|item| item.active)
+        .map(|item| (item.x + item.width).saturating_sub(width))
+        .unwrap_or(0);
+    if shift > 0 {
+        items.retain(|item| item.x >= shift);
+        for item in &mut items {
+            item.x -= shift;
+        }
+    }
+    items
+}
+
+/// The entry at `column` (relative to the bar's left edge), if any.
+pub fn item_at(items: &[TabBarItem], column: u16) -> Option<&TabBarItem> {
+    items
+        .iter()
+        .find(|item| column >= item.x && column < item.x + item.width)
+}
+
+fn truncate(name: &str) -> String {
+    if name.width() <= MAX_NAME_WIDTH {
+        return name.to_string();
+    }
+    let mut out = String::new();
+    for c in name.chars() {
+        if out.width() + c.to_string().width() > MAX_NAME_WIDTH - 1 {
+            break;
+        }
+        out.push(c);
+    }
+    out.push('…');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::TabState;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn manager_with(names: &[&str]) -> (TabManager, Vec<NamedTempFile>) {
+        let mut files = Vec::new();
+        let mut tabs = Vec::new();
+        for name in names {
+            let mut file = NamedTempFile::new().unwrap();
+            writeln!(file, "line").unwrap();
+            let mut tab = TabState::new(file.path().to_path_buf(), false).unwrap();
+            tab.source.name = name.to_string();
+            tabs.push(tab);
+            files.push(file);
+        }
+        let mut mgr = TabManager::new(tabs);
+        mgr.ensure_combined_tabs();
+        (mgr, files)
+    }
+
+    #[test]
+    fn test_layout_numbers_tabs_after_all_entry() {
+        let (mgr, _files) = manager_with(&["api", "worker"]);
+        let items = layout(&mgr, 80);
+
+        let labels: Vec<_> = items.iter().map(|i| i.label.as_str()).collect();
+        assert_eq!(labels, vec![" $all ", " 1 api ", " 2 worker "]);
+        assert_eq!(items[1].x, 6);
+        assert_eq!(items[2].x, 13);
+        assert!(items[1].active);
+        assert_eq!(items[2].target, TabBarTarget::Tab(1));
+    }
+
+    #[test]
+    fn test_item_at_hits_entry_under_column() {
+        let (mgr, _files) = manager_with(&["api", "worker"]);
+        let items = layout(&mgr, 80);
+
+        assert_eq!(
+            item_at(&items, 0).unwrap().target,
+            TabBarTarget::Combined(SourceType::File)
+        );
+        assert_eq!(item_at(&items, 12).unwrap().target, TabBarTarget::Tab(0));
+        assert_eq!(item_at(&items, 13).unwrap().target, TabBarTarget::Tab(1));
+        assert!(item_at(&items, 40).is_none());
+    }
+
+    #[test]
+    fn test_layout_scrolls_to_active_tab() {
+        let (mut mgr, _files) = manager_with(&["aaaaaaaa", "bbbbbbbb", "cccccccc"]);
+        mgr.active = 2;
+        let items = layout(&mgr, 20);
+
+        let active = items.iter().find(|i| i.active).unwrap();
+        assert_eq!(active.x + active.width, 20);
+        assert!(items.iter().all(|i| i.target != TabBarTarget::Tab(0)));
+    }
+}
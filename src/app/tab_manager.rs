@@ -1,7 +1,11 @@
 use super::tab::TabState;
-use super::{SourceType, ViewMode};
+use super::{LineIndices, SourceType, ViewMode};
+use crate::reader::combined_reader::SourceEntry;
 use crate::source::SourceStatus;
 
+/// Sources a category needs for its combined tab unless configured
+pub const DEFAULT_COMBINED_MIN_SOURCES: usize = 2;
+
 /// Manages the collection of tabs and combined views.
 pub struct TabManager {
     /// All open tabs
@@ -15,6 +19,9 @@ pub struct TabManager {
 
     /// Which category's combined tab is active (None = regular tab active)
     pub active_combined: Option<SourceType>,
+
+    /// Sources a category needs for its combined tab (None = never combine)
+    pub combined_min_sources: Option<usize>,
 }
 
 impl TabManager {
@@ -25,6 +32,7 @@ impl TabManager {
             active: 0,
             combined: [None, None, None, None, None],
             active_combined: None,
+            combined_min_sources: Some(DEFAULT_COMBINED_MIN_SOURCES),
         }
     }
 
@@ -154,10 +162,9 @@ impl TabManager {
             .map(|(i, _)| i)
     }
 
-    /// Create or remove per-category combined ($all) tabs based on source counts.
+    /// Create or remove per-category combined ($all) tabs based on source
+    /// counts and `combined_min_sources`.
     pub fn ensure_combined_tabs(&mut self) {
-        use crate::reader::combined_reader::SourceEntry;
-
         let categories = self.tabs_by_category();
 
         for (cat, tab_indices) in &categories {
@@ -166,22 +173,14 @@ impl TabManager {
             let sources: Vec<SourceEntry> = tab_indices
                 .iter()
                 .map(|&idx| &self.tabs[idx])
-                .filter(|t| !t.source.disabled)
-                .map(|tab| SourceEntry {
-                    name: tab.source.name.clone(),
-                    reader: tab.source.reader.clone(),
-                    index_reader: tab
-                        .source
-                        .source_path
-                        .as_ref()
-                        .and_then(|p| crate::index::reader::IndexReader::open(p)),
-                    source_path: tab.source.source_path.clone(),
-                    total_lines: tab.source.total_lines,
-                    renderer_names: tab.source.renderer_names.clone(),
-                })
+                .filter(|t| !t.source.disabled && !t.is_combined)
+                .map(source_entry)
                 .collect();
 
-            if sources.len() >= 2 {
+            let wanted = self
+                .combined_min_sources
+                .is_some_and(|min| !sources.is_empty() && sources.len() >= min);
+            if wanted {
                 if self.combined[cat_idx].is_none() {
                     self.combined[cat_idx] = Some(TabState::from_combined(sources));
                 }
@@ -196,7 +195,7 @@ impl TabManager {
 
     /// Rebuild a specific category's combined tab reader from current sources.
     pub fn refresh_combined_tab(&mut self, cat: SourceType) {
-        use crate::reader::combined_reader::{CombinedReader, SourceEntry};
+        use crate::reader::combined_reader::CombinedReader;
         use crate::reader::LogReader;
 
         let cat_idx = cat as usize;
@@ -208,19 +207,8 @@ impl TabManager {
         let sources: Vec<SourceEntry> = self
             .tabs
             .iter()
-            .filter(|t| !t.source.disabled && t.source_type() == cat)
-            .map(|tab| SourceEntry {
-                name: tab.source.name.clone(),
-                reader: tab.source.reader.clone(),
-                index_reader: tab
-                    .source
-                    .source_path
-                    .as_ref()
-                    .and_then(|p| crate::index::reader::IndexReader::open(p)),
-                source_path: tab.source.source_path.clone(),
-                total_lines: tab.source.total_lines,
-                renderer_names: tab.source.renderer_names.clone(),
-            })
+            .filter(|t| !t.source.disabled && !t.is_combined && t.source_type() == cat)
+            .map(source_entry)
             .collect();
 
         let source_count = sources.len();
@@ -230,7 +218,7 @@ impl TabManager {
         combined.source.reader = std::sync::Arc::new(std::sync::Mutex::new(new_reader));
         combined.source.total_lines = total_lines;
         if combined.source.mode == ViewMode::Normal {
-            combined.source.line_indices = (0..total_lines).collect();
+            combined.source.line_indices = LineIndices::all(total_lines);
         }
         combined.source.name = format!("$all ({} sources)", source_count);
     }
@@ -243,4 +231,53 @@ impl TabManager {
             self.active_combined = Some(cat);
         }
     }
+
+    /// Open a merged view of every regular tab (all categories) named `name`,
+    /// or return the existing one. Returns its tab index.
+    ///
+    /// The view is a snapshot of the sources at the time it is opened; it
+    /// is not part of any category's `$all` tab. It is listed under
+    /// `source_type` in the side panel.
+    pub fn open_merged_tab(&mut self, name: String, source_type: SourceType) -> Option<usize> {
+        if let Some(idx) = self
+            .tabs
+            .iter()
+            .position(|t| t.is_combined && t.source.name == name)
+        {
+            return Some(idx);
+        }
+
+        let sources: Vec<SourceEntry> = self
+            .tabs
+            .iter()
+            .filter(|t| !t.source.disabled && !t.is_combined)
+            .map(source_entry)
+            .collect();
+        if sources.is_empty() {
+            return None;
+        }
+
+        let mut tab = TabState::from_combined(sources);
+        tab.source.name = name;
+        tab.config_source_type = Some(source_type);
+        self.tabs.push(tab);
+        Some(self.tabs.len() - 1)
+    }
+}
+
+/// Combined-reader entry for one tab's source.
+pub(super) fn source_entry(tab: &TabState) -> SourceEntry {
+    SourceEntry {
+        name: tab.source.name.clone(),
+        reader: tab.source.reader.clone(),
+        index_reader: tab
+            .source
+            .source_path
+            .as_ref()
+            .and_then(|p| crate::index::reader::IndexReader::open(p)),
+        source_path: tab.source.source_path.clone(),
+        total_lines: tab.source.total_lines,
+        renderer_names: tab.source.renderer_names.clone(),
+        live: false,
+    }
 }
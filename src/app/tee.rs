@@ -0,0 +1,100 @@
+//! Tee for stdin tabs: persists streamed lines to a file while viewing.
+//!
+//! Write failures never interrupt the live view; the tee records the error,
+//! stops writing, and the status bar reports it.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Copies incoming stream lines to a file on disk.
+pub struct StreamTee {
+    writer: Option<BufWriter<File>>,
+    error: Option<String>,
+}
+
+impl StreamTee {
+    /// Create (or truncate) the target file.
+    pub fn create(path: &Path) -> std::io::Result<Self> {
+        let file = File::create(path)?;
+        Ok(Self {
+            writer: Some(BufWriter::new(file)),
+            error: None,
+        })
+    }
+
+    /// Write a batch of lines and flush so the file is usable immediately.
+    pub fn write_lines(&mut self, lines: &[String]) {
+        let Some(ref mut writer) = self.writer else {
+            return;
+        };
+        let result = lines
+            .iter()
+            .try_for_each(|line| writeln!(writer, "{}", line))
+            .and_then(|_| writer.flush());
+        if let Err(e) = result {
+            self.error = Some(e.to_string());
+            self.writer = None;
+        }
+    }
+
+    /// Flush and close the file (stream ended).
+    pub fn finish(&mut self) {
+        if let Some(mut writer) = self.writer.take() {
+            if let Err(e) = writer.flush() {
+                self.error = Some(e.to_string());
+            }
+        }
+    }
+
+    /// Whether lines are still being written.
+    pub fn is_active(&self) -> bool {
+        self.writer.is_some()
+    }
+
+    /// Last write error, if saving stopped because of one.
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_write_lines_persists_batches() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("saved.log");
+        let mut tee = StreamTee::create(&path).unwrap();
+
+        tee.write_lines(&["a".to_string(), "b".to_string()]);
+        tee.write_lines(&["c".to_string()]);
+
+        assert!(tee.is_active());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a\nb\nc\n");
+    }
+
+    #[test]
+    fn test_finish_stops_writing() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("saved.log");
+        let mut tee = StreamTee::create(&path).unwrap();
+
+        tee.write_lines(&["a".to_string()]);
+        tee.finish();
+        tee.write_lines(&["b".to_string()]);
+
+        assert!(!tee.is_active());
+        assert!(tee.error().is_none());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a\n");
+    }
+
+    #[test]
+    fn test_create_fails_for_missing_directory() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("missing").join("saved.log");
+        assert!(StreamTee::create(&path).is_err());
+    }
+}
@@ -8,6 +8,13 @@
 //! function. For non-wrap mode the caller passes `|_| 1`; for wrap mode
 //! it returns the actual wrapped height of each line. This keeps viewport
 //! agnostic of content while handling both modes in a single code path.
+//!
+//! Indices handled by the viewport (scroll position, selected index) are
+//! *display* indices. In reversed (newest-first) order display index 0 maps
+//! to the last entry of `line_indices`; `line_indices` itself always stays
+//! sorted ascending.
+
+use super::LineIndices;
 
 /// Default edge padding (vim's scrolloff equivalent)
 const DEFAULT_EDGE_PADDING: usize = 0;
@@ -36,6 +43,16 @@ pub struct Viewport {
     /// Padding to keep at edges (vim's scrolloff)
     edge_padding: usize,
 
+    /// Blank rows to keep below the last line when it is selected
+    tail_gap: usize,
+
+    /// Display newest-first (reverses the line_indices projection)
+    reversed: bool,
+
+    /// Visual rows to page by (negative = up), applied at the next resolve
+    /// once line heights are known
+    pending_page_rows: isize,
+
     /// Cached resolved values (valid after resolve() call)
     cache: Option<ResolvedView>,
 }
@@ -48,6 +65,9 @@ impl Viewport {
             scroll_position: 0,
             height: 0,
             edge_padding: DEFAULT_EDGE_PADDING,
+            tail_gap: 0,
+            reversed: false,
+            pending_page_rows: 0,
             cache: None,
         }
     }
@@ -55,7 +75,7 @@ impl Viewport {
     /// Resolve the viewport against current content.
     /// Each line has visual height 1 (no wrapping).
     #[allow(dead_code)]
-    pub fn resolve(&mut self, line_indices: &[usize], height: usize) -> ResolvedView {
+    pub fn resolve(&mut self, line_indices: &LineIndices, height: usize) -> ResolvedView {
         self.resolve_with_heights(line_indices, height, &mut |_| 1)
     }
 
@@ -68,7 +88,7 @@ impl Viewport {
     /// scroll position keeps selection visible.
     pub fn resolve_with_heights(
         &mut self,
-        line_indices: &[usize],
+        line_indices: &LineIndices,
         height: usize,
         line_height: &mut dyn FnMut(usize) -> usize,
     ) -> ResolvedView {
@@ -84,7 +104,7 @@ impl Viewport {
         }
 
         // Find anchor line in current view
-        let selected_index = match line_indices.binary_search(&self.anchor_line) {
+        let selected_index = match self.search(line_indices, self.anchor_line) {
             Ok(idx) => idx,
             Err(insert_pos) => {
                 let idx = if insert_pos >= line_indices.len() {
@@ -92,15 +112,24 @@ impl Viewport {
                 } else if insert_pos == 0 {
                     0
                 } else {
-                    let before = line_indices[insert_pos - 1];
-                    let after = line_indices[insert_pos];
-                    if self.anchor_line - before <= after - self.anchor_line {
+                    let before = self.at(line_indices, insert_pos - 1);
+                    let after = self.at(line_indices, insert_pos);
+                    if self.anchor_line.abs_diff(before) <= self.anchor_line.abs_diff(after) {
                         insert_pos - 1
                     } else {
                         insert_pos
                     }
                 };
-                self.anchor_line = line_indices[idx];
+                self.anchor_line = self.at(line_indices, idx);
+                idx
+            }
+        };
+
+        let selected_index = match std::mem::take(&mut self.pending_page_rows) {
+            0 => selected_index,
+            rows => {
+                let idx = page_target(selected_index, rows, line_indices.len(), line_height);
+                self.anchor_line = self.at(line_indices, idx);
                 idx
             }
         };
@@ -223,6 +252,18 @@ impl Viewport {
             }
         }
 
+        // Leave blank rows under the selected last line (follow margin)
+        if self.tail_gap > 0 && !self.reversed && selected_index + 1 == total_lines {
+            let gap = self.tail_gap.min(self.height / 2);
+            let mut rows: usize = (self.scroll_position..total_lines)
+                .map(&mut *line_height)
+                .sum();
+            while rows + gap > self.height && self.scroll_position < selected_index {
+                rows -= line_height(self.scroll_position);
+                self.scroll_position += 1;
+            }
+        }
+
         // Clamp scroll_position
         if self.scroll_position >= total_lines {
             self.scroll_position = total_lines.saturating_sub(1);
@@ -231,7 +272,7 @@ impl Viewport {
 
     /// Move selection by delta lines (positive = down, negative = up)
     /// Only updates anchor; scroll adjustment is deferred to resolve.
-    pub fn move_selection(&mut self, delta: i32, line_indices: &[usize]) {
+    pub fn move_selection(&mut self, delta: i32, line_indices: &LineIndices) {
         if line_indices.is_empty() {
             return;
         }
@@ -244,7 +285,16 @@ impl Viewport {
             current_idx.saturating_sub((-delta) as usize)
         };
 
-        self.anchor_line = line_indices[new_idx];
+        self.anchor_line = self.at(line_indices, new_idx);
+        self.cache = None;
+    }
+
+    /// Move selection by a page of `rows` visual rows (negative = up).
+    ///
+    /// With wrapped lines a page covers fewer lines than rows, so the move
+    /// is deferred to the next resolve, which knows each line's height.
+    pub fn page_by_rows(&mut self, rows: isize) {
+        self.pending_page_rows = self.pending_page_rows.saturating_add(rows);
         self.cache = None;
     }
 
@@ -253,7 +303,7 @@ impl Viewport {
     ///
     /// Both viewport and selection move together, so selection stays at
     /// the same position on screen.
-    pub fn move_viewport(&mut self, delta: i32, line_indices: &[usize]) {
+    pub fn move_viewport(&mut self, delta: i32, line_indices: &LineIndices) {
         if line_indices.is_empty() || self.height == 0 {
             return;
         }
@@ -268,11 +318,11 @@ impl Viewport {
             self.scroll_position = new_scroll;
 
             let new_idx = (current_idx + scroll_delta).min(line_indices.len() - 1);
-            self.anchor_line = line_indices[new_idx];
+            self.anchor_line = self.at(line_indices, new_idx);
 
             if scroll_delta == 0 && current_idx < line_indices.len() - 1 {
                 let new_idx = (current_idx + delta_usize).min(line_indices.len() - 1);
-                self.anchor_line = line_indices[new_idx];
+                self.anchor_line = self.at(line_indices, new_idx);
             }
         } else {
             let delta_usize = (-delta) as usize;
@@ -281,11 +331,11 @@ impl Viewport {
             self.scroll_position = new_scroll;
 
             let new_idx = current_idx.saturating_sub(scroll_delta);
-            self.anchor_line = line_indices[new_idx];
+            self.anchor_line = self.at(line_indices, new_idx);
 
             if scroll_delta == 0 && current_idx > 0 {
                 let new_idx = current_idx.saturating_sub(delta_usize);
-                self.anchor_line = line_indices[new_idx];
+                self.anchor_line = self.at(line_indices, new_idx);
             }
         }
 
@@ -293,7 +343,7 @@ impl Viewport {
     }
 
     /// Move both selection and viewport together (for mouse scroll)
-    pub fn scroll_with_selection(&mut self, delta: i32, line_indices: &[usize]) {
+    pub fn scroll_with_selection(&mut self, delta: i32, line_indices: &LineIndices) {
         if line_indices.is_empty() {
             return;
         }
@@ -305,17 +355,24 @@ impl Viewport {
             let actual_delta = delta as usize;
             self.scroll_position = (self.scroll_position + actual_delta).min(max_scroll);
             let new_idx = (current_idx + actual_delta).min(line_indices.len() - 1);
-            self.anchor_line = line_indices[new_idx];
+            self.anchor_line = self.at(line_indices, new_idx);
         } else {
             let actual_delta = (-delta) as usize;
             self.scroll_position = self.scroll_position.saturating_sub(actual_delta);
             let new_idx = current_idx.saturating_sub(actual_delta);
-            self.anchor_line = line_indices[new_idx];
+            self.anchor_line = self.at(line_indices, new_idx);
         }
 
         self.cache = None;
     }
 
+    /// Keep `rows` blank rows below the last line while it is selected
+    /// (at most half the screen). Follow mode sets this so the newest line
+    /// isn't pinned to the bottom border.
+    pub fn set_tail_gap(&mut self, rows: usize) {
+        self.tail_gap = rows;
+    }
+
     /// Jump to a specific file line number
     pub fn jump_to_line(&mut self, line: usize) {
         self.anchor_line = line;
@@ -323,7 +380,7 @@ impl Viewport {
     }
 
     /// Get current screen offset (rows from top of viewport to selection)
-    pub fn get_screen_offset(&self, line_indices: &[usize]) -> usize {
+    pub fn get_screen_offset(&self, line_indices: &LineIndices) -> usize {
         let idx = self.find_index(line_indices);
         idx.saturating_sub(self.scroll_position)
     }
@@ -334,7 +391,7 @@ impl Viewport {
         &mut self,
         line: usize,
         screen_offset: usize,
-        line_indices: &[usize],
+        line_indices: &LineIndices,
     ) {
         if line_indices.is_empty() {
             return;
@@ -353,30 +410,38 @@ impl Viewport {
 
     /// Jump to a specific index in the current view
     #[allow(dead_code)]
-    pub fn jump_to_index(&mut self, index: usize, line_indices: &[usize]) {
+    pub fn jump_to_index(&mut self, index: usize, line_indices: &LineIndices) {
         if line_indices.is_empty() {
             return;
         }
         let index = index.min(line_indices.len() - 1);
-        self.anchor_line = line_indices[index];
+        self.anchor_line = self.at(line_indices, index);
         self.cache = None;
     }
 
-    /// Jump to start (first line)
-    pub fn jump_to_start(&mut self, line_indices: &[usize]) {
+    /// Jump to start (oldest line — the bottom of the screen when reversed)
+    pub fn jump_to_start(&mut self, line_indices: &LineIndices) {
         if !line_indices.is_empty() {
-            self.anchor_line = line_indices[0];
-            self.scroll_position = 0;
+            self.anchor_line = line_indices.at(0);
+            self.scroll_position = if self.reversed {
+                line_indices.len().saturating_sub(self.height)
+            } else {
+                0
+            };
             self.cache = None;
         }
     }
 
-    /// Jump to end (last line)
-    pub fn jump_to_end(&mut self, line_indices: &[usize]) {
+    /// Jump to end (newest line — the top of the screen when reversed)
+    pub fn jump_to_end(&mut self, line_indices: &LineIndices) {
         if !line_indices.is_empty() {
-            self.anchor_line = line_indices[line_indices.len() - 1];
+            self.anchor_line = line_indices.at(line_indices.len() - 1);
             // Approximate: resolve will fix scroll_position precisely
-            self.scroll_position = line_indices.len().saturating_sub(self.height);
+            self.scroll_position = if self.reversed {
+                0
+            } else {
+                line_indices.len().saturating_sub(self.height)
+            };
             self.cache = None;
         }
     }
@@ -388,7 +453,7 @@ impl Viewport {
     }
 
     /// Center the current selection on screen
-    pub fn center(&mut self, line_indices: &[usize]) {
+    pub fn center(&mut self, line_indices: &LineIndices) {
         if line_indices.is_empty() || self.height == 0 {
             return;
         }
@@ -401,31 +466,31 @@ impl Viewport {
     }
 
     /// Move selection to top of viewport (with padding)
-    pub fn anchor_to_top(&mut self, line_indices: &[usize]) {
+    pub fn anchor_to_top(&mut self, line_indices: &LineIndices) {
         if line_indices.is_empty() {
             return;
         }
         let padding = self.edge_padding.min(self.height / 4);
         let target_idx = self.scroll_position + padding;
         let target_idx = target_idx.min(line_indices.len() - 1);
-        self.anchor_line = line_indices[target_idx];
+        self.anchor_line = self.at(line_indices, target_idx);
         self.cache = None;
     }
 
     /// Move selection to bottom of viewport (with padding)
-    pub fn anchor_to_bottom(&mut self, line_indices: &[usize]) {
+    pub fn anchor_to_bottom(&mut self, line_indices: &LineIndices) {
         if line_indices.is_empty() || self.height == 0 {
             return;
         }
         let padding = self.edge_padding.min(self.height / 4);
         let target_idx = (self.scroll_position + self.height).saturating_sub(1 + padding);
         let target_idx = target_idx.min(line_indices.len() - 1);
-        self.anchor_line = line_indices[target_idx];
+        self.anchor_line = self.at(line_indices, target_idx);
         self.cache = None;
     }
 
     /// Preserve screen offset when content changes (e.g., filter cleared)
-    pub fn preserve_screen_offset(&mut self, new_line_indices: &[usize]) {
+    pub fn preserve_screen_offset(&mut self, new_line_indices: &LineIndices) {
         if new_line_indices.is_empty() {
             return;
         }
@@ -446,6 +511,47 @@ impl Viewport {
         self.cache = None;
     }
 
+    /// Whether lines are displayed newest-first
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
+    /// Switch between oldest-first and newest-first display order.
+    /// Keeps the selected line and its distance from the top of the screen.
+    pub fn set_reversed(&mut self, reversed: bool, line_indices: &LineIndices) {
+        if self.reversed == reversed {
+            return;
+        }
+        if line_indices.is_empty() {
+            self.reversed = reversed;
+            self.scroll_position = 0;
+            self.cache = None;
+            return;
+        }
+        let screen_offset = self.get_screen_offset(line_indices);
+        self.reversed = reversed;
+        let new_idx = self.find_index(line_indices);
+        self.scroll_position = new_idx.saturating_sub(screen_offset);
+        self.cache = None;
+    }
+
+    /// Map a display index to an index into line_indices of length `len`.
+    pub fn data_index(&self, display_idx: usize, len: usize) -> usize {
+        if self.reversed {
+            len.saturating_sub(1 + display_idx)
+        } else {
+            display_idx
+        }
+    }
+
+    /// File line number shown at a display index, if any.
+    pub fn line_at(&self, line_indices: &LineIndices, display_idx: usize) -> Option<usize> {
+        if display_idx >= line_indices.len() {
+            return None;
+        }
+        Some(self.at(line_indices, display_idx))
+    }
+
     /// Get the currently selected file line number
     pub fn selected_line(&self) -> usize {
         self.anchor_line
@@ -458,13 +564,11 @@ impl Viewport {
     }
 
     /// Get the cached scroll position (call resolve() first)
-    #[allow(dead_code)]
     pub fn scroll_position(&self) -> usize {
         self.cache.map(|c| c.scroll_position).unwrap_or(0)
     }
 
     /// Get current height
-    #[allow(dead_code)]
     pub fn height(&self) -> usize {
         self.height
     }
@@ -480,21 +584,70 @@ impl Viewport {
 
     // --- Private helpers ---
 
-    /// Find current anchor_line index in line_indices
-    fn find_index(&self, line_indices: &[usize]) -> usize {
-        match line_indices.binary_search(&self.anchor_line) {
+    /// Find current anchor_line display index
+    fn find_index(&self, line_indices: &LineIndices) -> usize {
+        match self.search(line_indices, self.anchor_line) {
             Ok(idx) => idx,
             Err(insert_pos) => insert_pos.min(line_indices.len().saturating_sub(1)),
         }
     }
+
+    /// Binary search for a file line, returning display indices.
+    fn search(&self, line_indices: &LineIndices, line: usize) -> Result<usize, usize> {
+        let len = line_indices.len();
+        match line_indices.binary_search(&line) {
+            Ok(idx) => Ok(self.data_index(idx, len)),
+            Err(pos) if self.reversed => Err(len - pos),
+            Err(pos) => Err(pos),
+        }
+    }
+
+    /// File line at a display index (caller guarantees it is in bounds).
+    fn at(&self, line_indices: &LineIndices, display_idx: usize) -> usize {
+        line_indices.at(self.data_index(display_idx, line_indices.len()))
+    }
+}
+
+/// Display index reached by paging `rows` visual rows from `from`: the
+/// lines passed over fill at most `rows` rows, and at least one line is
+/// passed unless already at the edge.
+fn page_target(
+    from: usize,
+    rows: isize,
+    len: usize,
+    line_height: &mut dyn FnMut(usize) -> usize,
+) -> usize {
+    let page = rows.unsigned_abs();
+    let mut idx = from.min(len - 1);
+    let mut used = 0;
+    if rows > 0 {
+        while idx + 1 < len {
+            let h = line_height(idx).max(1);
+            if used > 0 && used + h > page {
+                break;
+            }
+            used += h;
+            idx += 1;
+        }
+    } else {
+        while idx > 0 {
+            let h = line_height(idx - 1).max(1);
+            if used > 0 && used + h > page {
+                break;
+            }
+            used += h;
+            idx -= 1;
+        }
+    }
+    idx
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    fn make_lines(lines: &[usize]) -> Vec<usize> {
-        lines.to_vec()
+    fn make_lines(lines: &[usize]) -> LineIndices {
+        lines.to_vec().into()
     }
 
     #[test]
@@ -513,6 +666,28 @@ mod tests {
         assert_eq!(view.selected_index, 5);
     }
 
+    #[test]
+    fn test_tail_gap_keeps_blank_rows_below_last_line() {
+        let lines = make_lines(&(0..20).collect::<Vec<_>>());
+        let mut vp = Viewport::new(19);
+        vp.set_tail_gap(2);
+        let view = vp.resolve(&lines, 10);
+        assert_eq!(view.selected_index, 19);
+        assert_eq!(view.scroll_position, 12);
+
+        // Capped at half the screen
+        vp.set_tail_gap(8);
+        assert_eq!(vp.resolve(&lines, 10).scroll_position, 15);
+
+        // Only the last line gets the gap
+        vp.set_tail_gap(2);
+        vp.jump_to_line(15);
+        assert_eq!(vp.resolve(&lines, 10).scroll_position, 15);
+
+        let mut vp = Viewport::new(19);
+        assert_eq!(vp.resolve(&lines, 10).scroll_position, 10);
+    }
+
     #[test]
     fn test_resolve_line_not_found_finds_nearest() {
         let mut vp = Viewport::new(50);
@@ -549,7 +724,7 @@ mod tests {
     #[test]
     fn test_resolve_empty_lines() {
         let mut vp = Viewport::new(5);
-        let lines: Vec<usize> = vec![];
+        let lines: LineIndices = LineIndices::default();
 
         let view = vp.resolve(&lines, 5);
 
@@ -604,7 +779,7 @@ mod tests {
     #[test]
     fn test_vim_like_scrolling_no_scroll_in_middle() {
         let mut vp = Viewport::new(0);
-        let lines: Vec<usize> = (0..50).collect();
+        let lines: LineIndices = (0..50).collect();
         vp.height = 20;
         vp.scroll_position = 0;
 
@@ -618,7 +793,7 @@ mod tests {
     #[test]
     fn test_resolve_scrolls_when_selection_past_bottom() {
         let mut vp = Viewport::new(0);
-        let lines: Vec<usize> = (0..50).collect();
+        let lines: LineIndices = (0..50).collect();
 
         // Selection at 25, viewport height 10: should scroll
         vp.anchor_line = 25;
@@ -634,7 +809,7 @@ mod tests {
     #[test]
     fn test_resolve_scrolls_when_selection_above_top() {
         let mut vp = Viewport::new(0);
-        let lines: Vec<usize> = (0..50).collect();
+        let lines: LineIndices = (0..50).collect();
 
         // scroll_position at 20, selection at 5: should scroll up
         vp.anchor_line = 5;
@@ -648,7 +823,7 @@ mod tests {
     #[test]
     fn test_resolve_with_wrapped_heights() {
         let mut vp = Viewport::new(0);
-        let lines: Vec<usize> = (0..20).collect();
+        let lines: LineIndices = (0..20).collect();
 
         // Each line is 3 visual rows. Height=12 means only 4 lines fit.
         vp.anchor_line = 6;
@@ -665,7 +840,7 @@ mod tests {
     #[test]
     fn test_resolve_wrapped_selection_at_top() {
         let mut vp = Viewport::new(0);
-        let lines: Vec<usize> = (0..20).collect();
+        let lines: LineIndices = (0..20).collect();
 
         // Each line 2 rows, height 10. Selection at 2, scroll at 5.
         // Selection is above viewport — should scroll up.
@@ -677,6 +852,56 @@ mod tests {
         assert_eq!(view.scroll_position, 2);
     }
 
+    #[test]
+    fn test_page_by_rows_over_wrapped_lines() {
+        let mut vp = Viewport::new(0);
+        let lines: LineIndices = (0..10).collect();
+        // Lines 2 and 5 wrap to 4 rows, the rest are single rows
+        let mut heights = |idx: usize| if idx == 2 || idx == 5 { 4 } else { 1 };
+        vp.resolve_with_heights(&lines, 6, &mut heights);
+
+        // 0 (1) + 1 (1) + 2 (4) = 6 rows: one screenful
+        vp.page_by_rows(6);
+        assert_eq!(vp.selected_line(), 0);
+        let view = vp.resolve_with_heights(&lines, 6, &mut heights);
+        assert_eq!(view.selected_index, 3);
+        assert_eq!(vp.selected_line(), 3);
+
+        // 3 (1) + 4 (1) fit in 5 rows, 5 (4) would overshoot
+        vp.page_by_rows(5);
+        let view = vp.resolve_with_heights(&lines, 6, &mut heights);
+        assert_eq!(view.selected_index, 5);
+
+        // A line taller than the page still moves one line
+        vp.page_by_rows(3);
+        let view = vp.resolve_with_heights(&lines, 6, &mut heights);
+        assert_eq!(view.selected_index, 6);
+
+        // Up: 5 (4) + 4 (1) + 3 (1) = 6 rows
+        vp.page_by_rows(-6);
+        let view = vp.resolve_with_heights(&lines, 6, &mut heights);
+        assert_eq!(view.selected_index, 3);
+
+        // Clamped at the start
+        vp.page_by_rows(-100);
+        let view = vp.resolve_with_heights(&lines, 6, &mut heights);
+        assert_eq!(view.selected_index, 0);
+    }
+
+    #[test]
+    fn test_page_by_rows_single_row_lines_moves_by_rows() {
+        let mut vp = Viewport::new(0);
+        let lines: LineIndices = (0..50).collect();
+
+        vp.page_by_rows(10);
+        let view = vp.resolve_with_heights(&lines, 10, &mut |_| 1);
+        assert_eq!(view.selected_index, 10);
+
+        vp.page_by_rows(100);
+        let view = vp.resolve_with_heights(&lines, 10, &mut |_| 1);
+        assert_eq!(view.selected_index, 49);
+    }
+
     #[test]
     fn test_jump_to_start() {
         let mut vp = Viewport::new(50);
@@ -704,7 +929,7 @@ mod tests {
     #[test]
     fn test_center() {
         let mut vp = Viewport::new(25);
-        let lines: Vec<usize> = (0..50).collect();
+        let lines: LineIndices = (0..50).collect();
         vp.height = 10;
         vp.scroll_position = 0;
 
@@ -731,7 +956,7 @@ mod tests {
         let mut vp = Viewport::new(500);
         vp.height = 20;
 
-        let unfiltered: Vec<usize> = (0..1000).collect();
+        let unfiltered: LineIndices = (0..1000).collect();
         let view1 = vp.resolve(&unfiltered, 20);
         assert_eq!(view1.selected_index, 500);
 
@@ -759,7 +984,7 @@ mod tests {
         let mut vp = Viewport::new(50);
         vp.height = 20;
         vp.scroll_position = 45;
-        let lines: Vec<usize> = (0..100).collect();
+        let lines: LineIndices = (0..100).collect();
 
         vp.move_viewport(2, &lines);
 
@@ -772,7 +997,7 @@ mod tests {
         let mut vp = Viewport::new(50);
         vp.height = 20;
         vp.scroll_position = 45;
-        let lines: Vec<usize> = (0..100).collect();
+        let lines: LineIndices = (0..100).collect();
 
         vp.move_viewport(-2, &lines);
 
@@ -785,7 +1010,7 @@ mod tests {
         let mut vp = Viewport::new(10);
         vp.height = 20;
         vp.scroll_position = 10;
-        let lines: Vec<usize> = (0..100).collect();
+        let lines: LineIndices = (0..100).collect();
 
         assert_eq!(vp.selected_line(), 10);
 
@@ -800,7 +1025,7 @@ mod tests {
         let mut vp = Viewport::new(29);
         vp.height = 20;
         vp.scroll_position = 10;
-        let lines: Vec<usize> = (0..100).collect();
+        let lines: LineIndices = (0..100).collect();
 
         assert_eq!(vp.selected_line(), 29);
 
@@ -815,7 +1040,7 @@ mod tests {
         let mut vp = Viewport::new(90);
         vp.height = 20;
         vp.scroll_position = 80;
-        let lines: Vec<usize> = (0..100).collect();
+        let lines: LineIndices = (0..100).collect();
 
         assert_eq!(vp.selected_line(), 90);
 
@@ -832,7 +1057,7 @@ mod tests {
         let mut vp = Viewport::new(10);
         vp.height = 20;
         vp.scroll_position = 0;
-        let lines: Vec<usize> = (0..100).collect();
+        let lines: LineIndices = (0..100).collect();
 
         assert_eq!(vp.selected_line(), 10);
 
@@ -849,7 +1074,7 @@ mod tests {
         let total = 50_000_000;
         let mut vp = Viewport::new(total - 1);
         vp.scroll_position = 0;
-        let lines: Vec<usize> = (0..total).collect();
+        let lines: LineIndices = (0..total).collect();
 
         let view = vp.resolve(&lines, 50);
 
@@ -857,4 +1082,70 @@ mod tests {
         // scroll_position should be near the end
         assert!(view.scroll_position >= total - 50);
     }
+
+    #[test]
+    fn test_reversed_resolve_maps_to_display_index() {
+        let mut vp = Viewport::new(2);
+        vp.set_reversed(true, &LineIndices::default());
+        let lines = make_lines(&[0, 1, 2, 3, 4]);
+
+        let view = vp.resolve(&lines, 5);
+
+        assert_eq!(view.selected_index, 2);
+        assert_eq!(vp.line_at(&lines, 0), Some(4));
+        assert_eq!(vp.data_index(view.selected_index, lines.len()), 2);
+    }
+
+    #[test]
+    fn test_reversed_move_selection_goes_to_older_lines() {
+        let mut vp = Viewport::new(30);
+        vp.set_reversed(true, &LineIndices::default());
+        let lines = make_lines(&[10, 20, 30, 40]);
+
+        vp.move_selection(1, &lines);
+
+        assert_eq!(vp.selected_line(), 20);
+    }
+
+    #[test]
+    fn test_reversed_nearest_line_not_found() {
+        let mut vp = Viewport::new(34);
+        vp.set_reversed(true, &LineIndices::default());
+        let lines = make_lines(&[10, 20, 30, 40, 50]);
+
+        let view = vp.resolve(&lines, 5);
+
+        assert_eq!(vp.selected_line(), 30);
+        assert_eq!(view.selected_index, 2);
+    }
+
+    #[test]
+    fn test_reversed_jump_to_end_selects_newest_at_top() {
+        let mut vp = Viewport::new(0);
+        vp.set_reversed(true, &LineIndices::default());
+        let lines: LineIndices = (0..100).collect();
+
+        vp.jump_to_end(&lines);
+        let view = vp.resolve(&lines, 20);
+
+        assert_eq!(vp.selected_line(), 99);
+        assert_eq!(view.selected_index, 0);
+        assert_eq!(view.scroll_position, 0);
+    }
+
+    #[test]
+    fn test_set_reversed_preserves_selection_and_screen_offset() {
+        let mut vp = Viewport::new(50);
+        let lines: LineIndices = (0..100).collect();
+        vp.scroll_position = 45;
+        vp.height = 20;
+
+        vp.set_reversed(true, &lines);
+        let view = vp.resolve(&lines, 20);
+
+        assert!(vp.is_reversed());
+        assert_eq!(vp.selected_line(), 50);
+        assert_eq!(view.selected_index, 49);
+        assert_eq!(view.scroll_position, 44);
+    }
 }
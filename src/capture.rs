@@ -15,8 +15,9 @@ use crate::renderer::segment::segments_to_ansi;
 use crate::renderer::PresetRegistry;
 use crate::signal::setup_shutdown_handlers;
 use crate::source::{
-    create_marker_for_context, ensure_directories_for_context, index_dir_for_log,
-    remove_marker_for_context, resolve_data_dir, validate_source_name,
+    create_marker_for_context, ensure_directories_for_context, index_checkpoint_interval,
+    index_dir_for_log, record_index_source, remove_marker_for_context, resolve_data_dir,
+    validate_source_name,
 };
 use crate::theme::Palette;
 use anyhow::{Context, Result};
@@ -110,7 +111,8 @@ pub fn run_capture_mode(
         .with_context(|| format!("Failed to resume index at {}", idx_dir.display()))?
     } else {
         let mut indexer = LineIndexer::create(&idx_dir)
-            .with_context(|| format!("Failed to create index at {}", idx_dir.display()))?;
+            .with_context(|| format!("Failed to create index at {}", idx_dir.display()))?
+            .with_checkpoint_interval(index_checkpoint_interval());
         // File is opened with append:true — if it already has content,
         // the indexer must start counting from the current file size.
         let existing_size = std::fs::metadata(&log_path).map(|m| m.len()).unwrap_or(0);
@@ -119,6 +121,8 @@ pub fn run_capture_mode(
         }
         indexer
     };
+    record_index_source(&log_path, &idx_dir)
+        .with_context(|| format!("Failed to record index source in {}", idx_dir.display()))?;
 
     // 9. Tee loop: read stdin, write to file AND stdout
     let stdin = io::stdin();
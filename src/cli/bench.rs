@@ -6,6 +6,7 @@ use crate::filter::regex_filter::RegexFilter;
 use crate::filter::search_engine::SearchEngine;
 use crate::filter::string_filter::StringFilter;
 use crate::filter::Filter;
+use crate::reader::encoding::Encoding;
 use crate::reader::file_reader::FileReader;
 use crate::reader::LogReader;
 use lazytail::index::reader::IndexReader;
@@ -340,9 +341,17 @@ fn run_trials(
     for i in 0..trials {
         let start = Instant::now();
 
-        let rx =
-            SearchEngine::search_file(path, filter.clone(), query, index, None, CancelToken::new())
-                .map_err(|e| format!("Search failed: {}", e))?;
+        let rx = SearchEngine::search_file(
+            path,
+            filter.clone(),
+            Encoding::Utf8,
+            query,
+            index,
+            None,
+            None,
+            CancelToken::new(),
+        )
+        .map_err(|e| format!("Search failed: {}", e))?;
 
         let (matches, lines_searched) = collect_filter_results(rx)?;
         let elapsed = start.elapsed();
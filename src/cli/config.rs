@@ -28,7 +28,8 @@ fn effective_config_path() -> Option<PathBuf> {
 /// - YAML syntax
 /// - Known field names (typo detection)
 /// - Source file existence
-pub fn validate() -> Result<(), i32> {
+/// - `profile`, if given, exists (its sources are checked too)
+pub fn validate(profile: Option<&str>) -> Result<(), i32> {
     // Find config to validate (closest wins)
     let config_path = match effective_config_path() {
         Some(path) => path,
@@ -39,7 +40,7 @@ pub fn validate() -> Result<(), i32> {
     };
 
     // Load ONLY the winning config file
-    match config::load_single_file(&config_path) {
+    match config::load_single_file(&config_path, profile) {
         Ok(cfg) => {
             // Check source file existence
             let mut has_errors = false;
@@ -55,6 +56,13 @@ pub fn validate() -> Result<(), i32> {
                     }
                 }
             }
+            // Shadowed themes are reported but don't fail validation
+            let discovery = config::discover();
+            let themes_dirs =
+                crate::theme::loader::collect_themes_dirs(discovery.project_root.as_deref());
+            for collision in crate::theme::loader::theme_collisions(&themes_dirs) {
+                eprintln!("warning: {}", collision);
+            }
             if has_errors {
                 Err(1)
             } else {
@@ -75,17 +83,18 @@ pub fn validate() -> Result<(), i32> {
 /// Displays:
 /// - Which config file is being used ("Using: path")
 /// - Config name (if set)
+/// - Active profile and the profiles available
 /// - Sources list with paths and existence status
 ///
 /// When no config exists, shows defaults message.
 /// Respects NO_COLOR environment variable via the colored crate.
-pub fn show() -> Result<(), i32> {
+pub fn show(profile: Option<&str>) -> Result<(), i32> {
     let config_path = effective_config_path();
 
     match config_path {
         Some(path) => {
             // Load ONLY the winning config file
-            match config::load_single_file(&path) {
+            match config::load_single_file(&path, profile) {
                 Ok(cfg) => {
                     println!("Using: {}", path.display().to_string().dimmed());
                     println!();
@@ -102,6 +111,16 @@ pub fn show() -> Result<(), i32> {
             println!("{}", "No config found. Using defaults.".dimmed());
             println!();
             // Show empty/default state
+            if let Some(name) = profile {
+                eprintln!(
+                    "{}",
+                    config::error::ConfigError::UnknownProfile {
+                        name: name.to_string(),
+                        available: Vec::new(),
+                    }
+                );
+                return Err(1);
+            }
             println!("{}: {}", "name".cyan(), "(not set)".dimmed());
             println!();
             println!("{}", "(no sources defined)".dimmed());
@@ -119,6 +138,16 @@ fn show_config(cfg: &config::SingleFileConfig) {
         println!("{}: {}", "name".cyan(), "(not set)".dimmed());
     }
 
+    // Profile (sources below include the active profile's)
+    if let Some(profile) = &cfg.profile {
+        println!("{}: {}", "profile".cyan(), profile.green());
+    } else {
+        println!("{}: {}", "profile".cyan(), "(none)".dimmed());
+    }
+    if !cfg.profiles.is_empty() {
+        println!("{}: {}", "profiles".cyan(), cfg.profiles.join(", "));
+    }
+
     // Sources (single list - closest config wins, no merge)
     if !cfg.sources.is_empty() {
         println!();
@@ -0,0 +1,43 @@
+//! Index maintenance commands for lazytail.
+//!
+//! Prunes the central index cache (`index.central_cache`), where indexes are
+//! not removed along with their log files.
+
+use colored::Colorize;
+
+use crate::cli::IndexGcArgs;
+use crate::source::{format_bytes, gc_index_cache, index_cache_dir};
+
+/// Remove orphaned index directories from the central cache.
+pub fn run_gc(args: IndexGcArgs) -> Result<(), i32> {
+    let Some(cache_dir) = index_cache_dir() else {
+        eprintln!("error: Could not determine home directory");
+        return Err(1);
+    };
+
+    let report = match gc_index_cache(&cache_dir, args.dry_run) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("error: {:#}", e);
+            return Err(1);
+        }
+    };
+
+    for dir in &report.orphaned {
+        let name = dir.file_name().unwrap_or_default().to_string_lossy();
+        if args.dry_run {
+            println!("  {} {}", "would remove".yellow(), name);
+        } else {
+            println!("  {} {}", "removed".red(), name);
+        }
+    }
+    println!(
+        "{} orphaned index{} ({}), {} kept in {}",
+        report.orphaned.len(),
+        if report.orphaned.len() == 1 { "" } else { "es" },
+        format_bytes(report.orphaned_bytes),
+        report.kept,
+        cache_dir.display()
+    );
+    Ok(())
+}
@@ -4,7 +4,9 @@
 
 pub mod bench;
 pub mod config;
+pub mod index;
 pub mod init;
+pub mod render;
 pub mod theme;
 #[cfg(feature = "self-update")]
 pub mod update;
@@ -30,6 +32,15 @@ pub enum Commands {
         action: ConfigAction,
     },
 
+    /// Columnar index maintenance
+    Index {
+        #[command(subcommand)]
+        action: IndexAction,
+    },
+
+    /// Print a log through a renderer preset (for authoring presets)
+    Render(RenderArgs),
+
     /// Theme management commands
     Theme {
         #[command(subcommand)]
@@ -107,6 +118,41 @@ pub enum ConfigAction {
     Show,
 }
 
+/// Index subcommand actions.
+#[derive(Subcommand, Debug)]
+pub enum IndexAction {
+    /// Remove cached indexes whose log file no longer exists
+    Gc(IndexGcArgs),
+}
+
+/// Arguments for the index gc subcommand.
+#[derive(Args, Debug)]
+pub struct IndexGcArgs {
+    /// List orphaned indexes without removing them
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Arguments for the render subcommand.
+#[derive(Args, Debug)]
+pub struct RenderArgs {
+    /// Log file, or the name of a configured source
+    #[arg(value_name = "FILE|SOURCE")]
+    pub input: String,
+
+    /// Preset to render with (default: the source's renderers, else auto-detect)
+    #[arg(long, value_name = "NAME")]
+    pub preset: Option<String>,
+
+    /// Preset definition to render with (YAML, as in a renderers directory)
+    #[arg(long, value_name = "FILE", conflicts_with = "preset")]
+    pub preset_file: Option<PathBuf>,
+
+    /// Only render the first N lines
+    #[arg(short = 'n', long, value_name = "N")]
+    pub lines: Option<usize>,
+}
+
 /// Theme subcommand actions.
 #[derive(Subcommand, Debug)]
 pub enum ThemeAction {
@@ -143,10 +189,23 @@ pub struct WebArgs {
     #[arg(short = 'p', long, default_value_t = 8421)]
     pub port: u16,
 
+    /// If the port is in use, try the next ones instead of failing
+    #[arg(long = "port-auto")]
+    pub port_auto: bool,
+
     /// Disable file watching (sources won't auto-reload on changes)
     #[arg(long = "no-watch")]
     pub no_watch: bool,
 
+    /// Open files even if they look binary (contain NUL bytes)
+    #[arg(long = "force-text")]
+    pub force_text: bool,
+
+    /// Minimum milliseconds between change notifications to the browser;
+    /// busy sources' updates in between are batched (0 = every change)
+    #[arg(long = "refresh-ms", value_name = "MS")]
+    pub refresh_ms: Option<u64>,
+
     /// Verbose startup output
     #[arg(short = 'v', long = "verbose")]
     pub verbose: bool,
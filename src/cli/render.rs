@@ -0,0 +1,194 @@
+//! Renderer preview command for lazytail.
+//!
+//! Prints a log through a renderer preset as ANSI text, so a preset can be
+//! tweaked and re-checked without restarting the TUI.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::cli::RenderArgs;
+use crate::config;
+use crate::index::flags::detect_flags;
+use crate::renderer::segment::segments_to_ansi;
+use crate::renderer::{compile_preset_file, PresetRegistry};
+use crate::theme::Palette;
+
+/// Render a file (or configured source) to stdout.
+pub fn run(args: RenderArgs, profile: Option<&str>) -> Result<(), i32> {
+    let discovery = config::discover();
+    let (cfg, mut warnings) = match config::load_with_profile(&discovery, profile) {
+        Ok(cfg) => (cfg, Vec::new()),
+        Err(err @ config::error::ConfigError::UnknownProfile { .. }) => {
+            eprintln!("{}", err);
+            return Err(1);
+        }
+        Err(e) => (config::Config::default(), vec![e.to_string()]),
+    };
+    let (registry, compile_errors) =
+        PresetRegistry::compile_from_config(&cfg.renderers, discovery.project_root.as_deref());
+    warnings.extend(compile_errors);
+    for warning in &warnings {
+        eprintln!("warning: {}", warning);
+    }
+
+    // An existing file wins over a source of the same name
+    let source = cfg
+        .project_sources
+        .iter()
+        .chain(cfg.global_sources.iter())
+        .find(|s| s.name == args.input);
+    let path = match source.and_then(|s| s.path.clone()) {
+        Some(path) if !Path::new(&args.input).exists() => path,
+        _ => PathBuf::from(&args.input),
+    };
+
+    let (registry, names) = if let Some(preset_file) = &args.preset_file {
+        match compile_preset_file(preset_file) {
+            Ok(preset) => {
+                let name = preset.name.clone();
+                (registry.with_preset(preset), vec![name])
+            }
+            Err(e) => {
+                eprintln!("error: {}", e);
+                return Err(1);
+            }
+        }
+    } else if let Some(name) = args.preset {
+        if registry.get_by_name(&name).is_none() {
+            eprintln!(
+                "error: Unknown renderer preset '{}' (available: {})",
+                name,
+                registry.all_preset_names().join(", ")
+            );
+            return Err(1);
+        }
+        (registry, vec![name])
+    } else {
+        let names = source.map(|s| s.renderer_names.clone()).unwrap_or_default();
+        (registry, names)
+    };
+
+    let file = match File::open(&path) {
+        Ok(file) => file,
+        Err(e) => {
+            eprintln!("error: Cannot open {}: {}", path.display(), e);
+            return Err(1);
+        }
+    };
+    let filename = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string());
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let preview = Preview {
+        registry: &registry,
+        names: &names,
+        filename: filename.as_deref(),
+        palette: &cfg.theme.palette,
+    };
+    let (rendered, total) = match preview.render(BufReader::new(file), &mut out, args.lines) {
+        Ok(counts) => counts,
+        // Output closed early (e.g. piped into `head`)
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return Err(1);
+        }
+    };
+    drop(out);
+
+    let by = if names.is_empty() {
+        "auto-detected presets".to_string()
+    } else {
+        names.join(", ")
+    };
+    eprintln!("{} of {} lines rendered by {}", rendered, total, by);
+    Ok(())
+}
+
+/// Renders lines the way the TUI does: each line through `names` in order
+/// (auto-detection when empty), falling back to the raw line.
+struct Preview<'a> {
+    registry: &'a PresetRegistry,
+    names: &'a [String],
+    filename: Option<&'a str>,
+    palette: &'a Palette,
+}
+
+impl Preview<'_> {
+    /// Render up to `limit` lines; returns (rendered, total) line counts.
+    fn render(
+        &self,
+        mut input: impl BufRead,
+        out: &mut impl Write,
+        limit: Option<usize>,
+    ) -> io::Result<(usize, usize)> {
+        let mut buf = Vec::new();
+        let (mut rendered, mut total) = (0, 0);
+        while limit.is_none_or(|limit| total < limit) {
+            buf.clear();
+            if input.read_until(b'\n', &mut buf)? == 0 {
+                break;
+            }
+            let text = String::from_utf8_lossy(&buf);
+            let line = text.trim_end_matches(['\n', '\r']);
+            let flags = Some(detect_flags(line));
+            let segments = if self.names.is_empty() {
+                self.registry.render_line_auto(line, self.filename, flags)
+            } else {
+                self.registry.render_line(line, self.names, flags)
+            };
+            match segments {
+                Some(segments) => {
+                    rendered += 1;
+                    writeln!(out, "{}", segments_to_ansi(&segments, Some(self.palette)))?;
+                }
+                None => writeln!(out, "{}", line)?,
+            }
+            total += 1;
+        }
+        out.flush()?;
+        Ok((rendered, total))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preview(names: &[&str], input: &str, limit: Option<usize>) -> (String, (usize, usize)) {
+        let registry = PresetRegistry::new(Vec::new());
+        let names: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+        let palette = Palette::dark();
+        let preview = Preview {
+            registry: &registry,
+            names: &names,
+            filename: None,
+            palette: &palette,
+        };
+        let mut out = Vec::new();
+        let counts = preview.render(input.as_bytes(), &mut out, limit).unwrap();
+        (String::from_utf8(out).unwrap(), counts)
+    }
+
+    #[test]
+    fn test_render_styles_matching_lines_and_passes_others_through() {
+        let input = "{\"level\":\"error\",\"msg\":\"boom\"}\nplain text\n";
+        let (out, counts) = preview(&["json"], input, None);
+        assert_eq!(counts, (1, 2));
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("boom"));
+        assert!(lines[0].contains('\x1b'));
+        assert_eq!(lines[1], "plain text");
+    }
+
+    #[test]
+    fn test_render_respects_line_limit() {
+        let (out, counts) = preview(&[], "a\nb\nc\n", Some(2));
+        assert_eq!(counts.1, 2);
+        assert_eq!(out, "a\nb\n");
+    }
+}
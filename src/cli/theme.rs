@@ -135,8 +135,8 @@ pub fn run_import(args: ImportArgs) -> Result<(), i32> {
     };
 
     // Ensure global themes dir exists
-    let themes_dir = match crate::source::lazytail_dir() {
-        Some(dir) => dir.join("themes"),
+    let themes_dir = match crate::theme::loader::global_themes_dir() {
+        Some(dir) => dir,
         None => {
             eprintln!("error: cannot determine config directory");
             return Err(1);
@@ -172,22 +172,43 @@ pub fn run_import(args: ImportArgs) -> Result<(), i32> {
 }
 
 pub fn run_list() -> Result<(), i32> {
+    use crate::theme::loader;
+
     let discovery = crate::config::discover();
-    let themes_dirs = crate::theme::loader::collect_themes_dirs(discovery.project_root.as_deref());
+    let themes_dirs = loader::collect_themes_dirs(discovery.project_root.as_deref());
 
     println!("{}:", "Built-in".cyan());
     println!("  dark");
     println!("  light");
 
-    let external = crate::theme::loader::discover_themes(&themes_dirs);
-    if !external.is_empty() {
+    let project_dir = discovery
+        .project_root
+        .as_deref()
+        .map(loader::project_themes_dir);
+    for (label, dir) in [
+        ("Project", project_dir),
+        ("Global", loader::global_themes_dir()),
+    ] {
+        let Some(dir) = dir else { continue };
+        let themes = loader::theme_files(&dir);
+        if themes.is_empty() {
+            continue;
+        }
         println!();
-        println!("{}:", "External".cyan());
-        for name in &external {
+        println!(
+            "{} {}:",
+            label.cyan(),
+            format!("({})", dir.display()).dimmed()
+        );
+        for (name, _) in &themes {
             println!("  {}", name);
         }
     }
 
+    for collision in loader::theme_collisions(&themes_dirs) {
+        eprintln!("{}: {}", "warning".yellow(), collision);
+    }
+
     Ok(())
 }
 
@@ -0,0 +1,36 @@
+//! Clipboard reads through the platform's paste utility.
+//!
+//! Copying uses OSC 52, which terminals treat as write-only, so reading the
+//! clipboard goes through whichever paste command is installed.
+
+use std::process::{Command, Stdio};
+
+/// Paste commands tried in order; the first one that succeeds wins
+const PASTE_COMMANDS: &[&[&str]] = &[
+    &["wl-paste", "--no-newline"],
+    &["xclip", "-selection", "clipboard", "-o"],
+    &["xsel", "--clipboard", "--output"],
+    &["pbpaste"],
+    &["powershell.exe", "-NoProfile", "-Command", "Get-Clipboard"],
+];
+
+/// Read the clipboard as text.
+///
+/// Returns `Err` with a user-facing message if no paste command works.
+pub fn read() -> Result<String, String> {
+    for command in PASTE_COMMANDS {
+        let output = Command::new(command[0])
+            .args(&command[1..])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output();
+        // Not installed, or no display to talk to: try the next one
+        let Ok(output) = output else { continue };
+        if !output.status.success() {
+            continue;
+        }
+        return String::from_utf8(output.stdout)
+            .map_err(|_| "Clipboard does not contain text".to_string());
+    }
+    Err("Cannot read the clipboard: install wl-paste, xclip or xsel".to_string())
+}
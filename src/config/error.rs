@@ -7,10 +7,72 @@ use std::path::PathBuf;
 use strsim::jaro_winkler;
 
 /// Known fields for root config.
-const ROOT_FIELDS: &[&str] = &["name", "sources", "update_check", "renderers", "theme"];
+const ROOT_FIELDS: &[&str] = &[
+    "name",
+    "sources",
+    "profiles",
+    "update_check",
+    "renderers",
+    "theme",
+    "highlights",
+    "max_filter_history",
+    "encoding",
+    "smart_case",
+    "double_click",
+    "quick_filter_fields",
+    "correlation_fields",
+    "copy_fields",
+    "wrap_markers",
+    "compact_numbers",
+    "line_prefix",
+    "follow_pause_banner",
+    "follow_margin",
+    "max_content_width",
+    "center_content",
+    "suggest_index",
+    "confirm_close",
+    "filter_esc",
+    "low_resource",
+    "nav",
+    "confirm_scan_mb",
+    "mouse_scroll_lines",
+    "mouse_capture",
+    "timestamp_format",
+    "background_alert",
+    "combined_min_sources",
+    "combined_views",
+    "index",
+    "web_refresh_ms",
+    "line_buffered",
+    "format_renderers",
+    "dedup_files",
+];
 
 /// Known fields for source entries.
-const SOURCE_FIELDS: &[&str] = &["name", "path", "renderers"];
+const SOURCE_FIELDS: &[&str] = &[
+    "name",
+    "path",
+    "renderers",
+    "display_transform",
+    "color",
+    "default_filter",
+    "multiline_json",
+];
+
+/// Known fields for a source's default filter.
+const DEFAULT_FILTER_FIELDS: &[&str] = &["pattern", "mode"];
+
+/// Known fields for the line prefix entry.
+const LINE_PREFIX_FIELDS: &[&str] = &["show_numbers", "width", "separator"];
+
+/// Known fields for the index entry.
+const INDEX_FIELDS: &[&str] = &["checkpoint_interval", "central_cache"];
+
+/// Known fields for the format_renderers entry.
+const FORMAT_RENDERER_FIELDS: &[&str] = &["json", "logfmt"];
+
+/// Known fields for display transform entries.
+const DISPLAY_TRANSFORM_FIELDS: &[&str] = &["pattern", "template"];
 
 /// Known fields for layout entries.
 const LAYOUT_FIELDS: &[&str] = &[
@@ -33,6 +95,9 @@ const RENDERER_FIELDS: &[&str] = &["name", "detect", "regex", "layout"];
 /// Known fields for detect entries.
 const DETECT_FIELDS: &[&str] = &["parser", "filename"];
 
+/// Known fields for highlight entries.
+const HIGHLIGHT_FIELDS: &[&str] = &["pattern", "style"];
+
 /// Similarity threshold for suggestions (0.0 - 1.0).
 /// 0.8 is a good balance between catching typos and avoiding false positives.
 const SIMILARITY_THRESHOLD: f64 = 0.8;
@@ -58,6 +123,12 @@ pub enum ConfigError {
     /// Validation error (semantic errors after parsing).
     #[allow(dead_code)]
     Validation { path: PathBuf, message: String },
+
+    /// `--profile` named a profile no config file defines.
+    UnknownProfile {
+        name: String,
+        available: Vec<String>,
+    },
 }
 
 impl ConfigError {
@@ -114,6 +185,17 @@ impl ConfigError {
             ConfigError::Validation { path, message } => {
                 format!("error: {}\n  --> {}\n  |\n", message, path.display())
             }
+            ConfigError::UnknownProfile { name, available } => {
+                let help = if available.is_empty() {
+                    "no config file defines `profiles`".to_string()
+                } else {
+                    format!("available profiles: {}", available.join(", "))
+                };
+                format!(
+                    "error: unknown profile `{}`\n  |\n  = help: {}\n",
+                    name, help
+                )
+            }
         }
     }
 }
@@ -190,7 +272,13 @@ fn find_suggestion(unknown_field: &str) -> Option<String> {
         .chain(LAYOUT_FIELDS.iter())
         .chain(RENDERER_FIELDS.iter())
         .chain(DETECT_FIELDS.iter())
-        .chain(STYLE_CONDITION_FIELDS.iter());
+        .chain(STYLE_CONDITION_FIELDS.iter())
+        .chain(HIGHLIGHT_FIELDS.iter())
+        .chain(DISPLAY_TRANSFORM_FIELDS.iter())
+        .chain(LINE_PREFIX_FIELDS.iter())
+        .chain(INDEX_FIELDS.iter())
+        .chain(FORMAT_RENDERER_FIELDS.iter())
+        .chain(DEFAULT_FILTER_FIELDS.iter());
 
     let mut best_match: Option<(&str, f64)> = None;
 
@@ -3,11 +3,19 @@
 //! Loads and validates YAML config files with path expansion.
 
 use std::fs;
+use std::ops::RangeInclusive;
 use std::path::{Path, PathBuf};
 
 use crate::config::discovery::DiscoveryResult;
 use crate::config::error::ConfigError;
-use crate::config::types::{Config, RawConfig, RawSource, Source};
+use crate::config::types::{
+    Config, DefaultFilterConfig, DefaultFilterMode, IndexConfig, RawConfig, RawSource, Source,
+};
+use crate::reader::encoding::Encoding;
+use crate::renderer::transform::DisplayTransform;
+
+/// Accepted values for `index.checkpoint_interval`.
+const CHECKPOINT_INTERVAL_RANGE: RangeInclusive<u16> = 10..=10_000;
 
 /// Config loaded from a single file (for config commands).
 ///
@@ -20,6 +28,10 @@ pub struct SingleFileConfig {
     pub name: Option<String>,
     /// List of log sources from this config file.
     pub sources: Vec<Source>,
+    /// Profile whose sources were added (if one was selected).
+    pub profile: Option<String>,
+    /// Names of the profiles this file defines, sorted.
+    pub profiles: Vec<String>,
 }
 
 /// Expand tilde in path to home directory.
@@ -63,9 +75,24 @@ fn load_file(path: &Path) -> Result<RawConfig, ConfigError> {
 /// Validate and expand paths in raw sources.
 ///
 /// Expands tilde paths and checks file existence.
-fn validate_sources(raw: Vec<RawSource>) -> Vec<Source> {
+fn validate_sources(path: &Path, raw: Vec<RawSource>) -> Result<Vec<Source>, ConfigError> {
     raw.into_iter()
         .map(|raw_source| {
+            let display_transform = raw_source
+                .display_transform
+                .as_ref()
+                .map(DisplayTransform::compile)
+                .transpose()
+                .map_err(|e| ConfigError::Validation {
+                    path: path.to_path_buf(),
+                    message: format!("source '{}': display_transform: {}", raw_source.name, e),
+                })?;
+            if let Some(filter) = &raw_source.default_filter {
+                validate_default_filter(filter).map_err(|e| ConfigError::Validation {
+                    path: path.to_path_buf(),
+                    message: format!("source '{}': default_filter: {}", raw_source.name, e),
+                })?;
+            }
             let (expanded_path, exists) = match raw_source.path {
                 Some(p) => {
                     let expanded = expand_path(&p);
@@ -74,29 +101,149 @@ fn validate_sources(raw: Vec<RawSource>) -> Vec<Source> {
                 }
                 None => (None, false),
             };
-            Source {
+            Ok(Source {
                 name: raw_source.name,
                 path: expanded_path,
                 renderer_names: raw_source.renderers,
+                display_transform,
+                color: raw_source.color.map(|c| c.0),
+                default_filter: raw_source.default_filter,
+                multiline_json: raw_source.multiline_json,
                 exists,
-            }
+            })
         })
         .collect()
 }
 
+/// Reject empty patterns, invalid regexes, and unparsable queries up front.
+fn validate_default_filter(filter: &DefaultFilterConfig) -> Result<(), String> {
+    if filter.pattern.is_empty() {
+        return Err("pattern must not be empty".to_string());
+    }
+    match filter.mode {
+        DefaultFilterMode::Plain => Ok(()),
+        DefaultFilterMode::Regex => regex::Regex::new(&filter.pattern)
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+        DefaultFilterMode::Query => crate::filter::query::parse_query(&filter.pattern)
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+    }
+}
+
+/// Reject a zero filter history limit (upper bound is capped by the consumer).
+fn validate_max_filter_history(
+    path: &Path,
+    value: Option<usize>,
+) -> Result<Option<usize>, ConfigError> {
+    validate_at_least_one(path, "max_filter_history", value)
+}
+
+/// Reject a zero mouse wheel step (scrolling would do nothing).
+fn validate_mouse_scroll_lines(
+    path: &Path,
+    value: Option<usize>,
+) -> Result<Option<usize>, ConfigError> {
+    validate_at_least_one(path, "mouse_scroll_lines", value)
+}
+
+/// Reject a zero combined view threshold (use `combined_views: false` to
+/// turn them off).
+fn validate_combined_min_sources(
+    path: &Path,
+    value: Option<usize>,
+) -> Result<Option<usize>, ConfigError> {
+    validate_at_least_one(path, "combined_min_sources", value)
+}
+
+/// Reject checkpoint intervals outside the supported range: below it the
+/// checkpoints file outgrows the other columns, above it severity summaries
+/// and validation become too coarse to be useful.
+fn validate_index_config(
+    path: &Path,
+    value: Option<IndexConfig>,
+) -> Result<Option<IndexConfig>, ConfigError> {
+    if let Some(index) = &value {
+        if !CHECKPOINT_INTERVAL_RANGE.contains(&index.checkpoint_interval) {
+            return Err(ConfigError::Validation {
+                path: path.to_path_buf(),
+                message: format!(
+                    "index.checkpoint_interval must be between {} and {}",
+                    CHECKPOINT_INTERVAL_RANGE.start(),
+                    CHECKPOINT_INTERVAL_RANGE.end()
+                ),
+            });
+        }
+    }
+    Ok(value)
+}
+
+fn validate_at_least_one(
+    path: &Path,
+    key: &str,
+    value: Option<usize>,
+) -> Result<Option<usize>, ConfigError> {
+    if value == Some(0) {
+        return Err(ConfigError::Validation {
+            path: path.to_path_buf(),
+            message: format!("{} must be at least 1", key),
+        });
+    }
+    Ok(value)
+}
+
+/// Parse the `encoding` key into a supported encoding.
+fn parse_encoding(path: &Path, value: Option<&str>) -> Result<Option<Encoding>, ConfigError> {
+    value
+        .map(|name| {
+            name.parse().map_err(|message| ConfigError::Validation {
+                path: path.to_path_buf(),
+                message,
+            })
+        })
+        .transpose()
+}
+
 /// Load config from a single file (closest-wins semantics for config commands).
 ///
 /// Unlike [`load`] which merges project and global configs for the TUI,
 /// this loads only one config file and returns its contents directly.
 /// Used by `config validate` and `config show` commands.
-pub fn load_single_file(path: &Path) -> Result<SingleFileConfig, ConfigError> {
-    let raw = load_file(path)?;
+///
+/// With `profile`, that profile's sources follow the file's own; the file
+/// must define it.
+pub fn load_single_file(
+    path: &Path,
+    profile: Option<&str>,
+) -> Result<SingleFileConfig, ConfigError> {
+    let mut raw = load_file(path)?;
+    let profiles = profile_names(&raw);
+    let mut sources = validate_sources(path, raw.sources)?;
+    if let Some(name) = profile {
+        let selected = raw
+            .profiles
+            .remove(name)
+            .ok_or_else(|| ConfigError::UnknownProfile {
+                name: name.to_string(),
+                available: profiles.clone(),
+            })?;
+        sources.extend(validate_sources(path, selected.sources)?);
+    }
     Ok(SingleFileConfig {
         name: raw.name,
-        sources: validate_sources(raw.sources),
+        sources,
+        profile: profile.map(str::to_string),
+        profiles,
     })
 }
 
+/// Sorted names of the profiles defined in `raw`.
+fn profile_names(raw: &RawConfig) -> Vec<String> {
+    let mut names: Vec<String> = raw.profiles.keys().cloned().collect();
+    names.sort();
+    names
+}
+
 /// Load config from discovered config files.
 ///
 /// Merges project and global configs:
@@ -105,30 +252,207 @@ pub fn load_single_file(path: &Path) -> Result<SingleFileConfig, ConfigError> {
 ///
 /// Returns an empty Config if no config files exist (graceful degradation).
 pub fn load(discovery: &DiscoveryResult) -> Result<Config, ConfigError> {
+    load_with_profile(discovery, None)
+}
+
+/// Load config like [`load`], adding the named profile on top.
+///
+/// Each file's copy of the profile extends that file's sources, renderers
+/// and highlights; at least one of the files must define it.
+pub fn load_with_profile(
+    discovery: &DiscoveryResult,
+    profile: Option<&str>,
+) -> Result<Config, ConfigError> {
     let mut config = Config::default();
     let mut theme_raw: Option<crate::theme::RawThemeConfig> = None;
+    let mut profile_found = false;
+    let mut available: Vec<String> = Vec::new();
 
     // Load global config if it exists (loaded first so project can override)
     if let Some(global_path) = &discovery.global_config {
-        let raw = load_file(global_path)?;
-        config.global_sources = validate_sources(raw.sources);
+        let mut raw = load_file(global_path)?;
+        available.extend(profile_names(&raw));
+        config.global_sources = validate_sources(global_path, raw.sources)?;
         config.update_check = raw.update_check;
+        config.highlights = raw.highlights;
+        if let Some(selected) = profile.and_then(|name| raw.profiles.remove(name)) {
+            profile_found = true;
+            config
+                .global_sources
+                .extend(validate_sources(global_path, selected.sources)?);
+            config.renderers.extend(selected.renderers);
+            config.highlights.extend(selected.highlights);
+        }
+        config.max_filter_history =
+            validate_max_filter_history(global_path, raw.max_filter_history)?;
+        config.encoding = parse_encoding(global_path, raw.encoding.as_deref())?;
+        config.smart_case = raw.smart_case;
+        config.double_click = raw.double_click;
+        config.quick_filter_fields = raw.quick_filter_fields;
+        config.correlation_fields = raw.correlation_fields;
+        config.copy_fields = raw.copy_fields;
+        config.wrap_markers = raw.wrap_markers;
+        config.compact_numbers = raw.compact_numbers;
+        config.line_prefix = raw.line_prefix;
+        config.follow_pause_banner = raw.follow_pause_banner;
+        config.follow_margin = raw.follow_margin;
+        config.max_content_width =
+            validate_at_least_one(global_path, "max_content_width", raw.max_content_width)?;
+        config.center_content = raw.center_content;
+        config.suggest_index = raw.suggest_index;
+        config.confirm_close = raw.confirm_close;
+        config.filter_esc = raw.filter_esc;
+        config.low_resource = raw.low_resource;
+        config.nav = raw.nav;
+        config.confirm_scan_mb = raw.confirm_scan_mb;
+        config.mouse_scroll_lines =
+            validate_mouse_scroll_lines(global_path, raw.mouse_scroll_lines)?;
+        config.mouse_capture = raw.mouse_capture;
+        config.timestamp_format = raw.timestamp_format;
+        config.background_alert = raw.background_alert;
+        config.combined_min_sources =
+            validate_combined_min_sources(global_path, raw.combined_min_sources)?;
+        config.combined_views = raw.combined_views;
+        config.index = validate_index_config(global_path, raw.index)?;
+        config.web_refresh_ms = raw.web_refresh_ms;
+        config.line_buffered = raw.line_buffered;
+        config.format_renderers = raw.format_renderers;
+        config.dedup_files = raw.dedup_files;
         theme_raw = raw.theme;
         // Note: global name is ignored, project name takes precedence
     }
 
     // Load project config if it exists
     if let Some(project_path) = &discovery.project_config {
-        let raw = load_file(project_path)?;
+        let mut raw = load_file(project_path)?;
+        available.extend(profile_names(&raw));
         config.name = raw.name;
-        config.project_sources = validate_sources(raw.sources);
-        config.renderers = raw.renderers;
+        config.project_sources = validate_sources(project_path, raw.sources)?;
+        config.renderers.extend(raw.renderers);
+        config.highlights.extend(raw.highlights);
+        if let Some(selected) = profile.and_then(|name| raw.profiles.remove(name)) {
+            profile_found = true;
+            config
+                .project_sources
+                .extend(validate_sources(project_path, selected.sources)?);
+            config.renderers.extend(selected.renderers);
+            config.highlights.extend(selected.highlights);
+        }
+        if raw.max_filter_history.is_some() {
+            config.max_filter_history =
+                validate_max_filter_history(project_path, raw.max_filter_history)?;
+        }
+        if raw.encoding.is_some() {
+            config.encoding = parse_encoding(project_path, raw.encoding.as_deref())?;
+        }
+        if raw.smart_case.is_some() {
+            config.smart_case = raw.smart_case;
+        }
+        if raw.double_click.is_some() {
+            config.double_click = raw.double_click;
+        }
+        if raw.quick_filter_fields.is_some() {
+            config.quick_filter_fields = raw.quick_filter_fields;
+        }
+        if raw.correlation_fields.is_some() {
+            config.correlation_fields = raw.correlation_fields;
+        }
+        if raw.copy_fields.is_some() {
+            config.copy_fields = raw.copy_fields;
+        }
+        if raw.wrap_markers.is_some() {
+            config.wrap_markers = raw.wrap_markers;
+        }
+        if raw.compact_numbers.is_some() {
+            config.compact_numbers = raw.compact_numbers;
+        }
+        if raw.line_prefix.is_some() {
+            config.line_prefix = raw.line_prefix;
+        }
+        if raw.follow_pause_banner.is_some() {
+            config.follow_pause_banner = raw.follow_pause_banner;
+        }
+        if raw.follow_margin.is_some() {
+            config.follow_margin = raw.follow_margin;
+        }
+        if raw.max_content_width.is_some() {
+            config.max_content_width =
+                validate_at_least_one(project_path, "max_content_width", raw.max_content_width)?;
+        }
+        if raw.center_content.is_some() {
+            config.center_content = raw.center_content;
+        }
+        if raw.suggest_index.is_some() {
+            config.suggest_index = raw.suggest_index;
+        }
+        if raw.confirm_close.is_some() {
+            config.confirm_close = raw.confirm_close;
+        }
+        if raw.filter_esc.is_some() {
+            config.filter_esc = raw.filter_esc;
+        }
+        if raw.low_resource.is_some() {
+            config.low_resource = raw.low_resource;
+        }
+        if raw.nav.is_some() {
+            config.nav = raw.nav;
+        }
+        if raw.confirm_scan_mb.is_some() {
+            config.confirm_scan_mb = raw.confirm_scan_mb;
+        }
+        if raw.mouse_scroll_lines.is_some() {
+            config.mouse_scroll_lines =
+                validate_mouse_scroll_lines(project_path, raw.mouse_scroll_lines)?;
+        }
+        if raw.mouse_capture.is_some() {
+            config.mouse_capture = raw.mouse_capture;
+        }
+        if raw.timestamp_format.is_some() {
+            config.timestamp_format = raw.timestamp_format;
+        }
+        if raw.background_alert.is_some() {
+            config.background_alert = raw.background_alert;
+        }
+        if raw.combined_min_sources.is_some() {
+            config.combined_min_sources =
+                validate_combined_min_sources(project_path, raw.combined_min_sources)?;
+        }
+        if raw.combined_views.is_some() {
+            config.combined_views = raw.combined_views;
+        }
+        if raw.index.is_some() {
+            config.index = validate_index_config(project_path, raw.index)?;
+        }
+        if raw.web_refresh_ms.is_some() {
+            config.web_refresh_ms = raw.web_refresh_ms;
+        }
+        if raw.line_buffered.is_some() {
+            config.line_buffered = raw.line_buffered;
+        }
+        if raw.format_renderers.is_some() {
+            config.format_renderers = raw.format_renderers;
+        }
+        if raw.dedup_files.is_some() {
+            config.dedup_files = raw.dedup_files;
+        }
         // Project theme overrides global theme (full override, not merge)
         if raw.theme.is_some() {
             theme_raw = raw.theme;
         }
     }
 
+    if let Some(name) = profile {
+        if !profile_found {
+            available.sort();
+            available.dedup();
+            return Err(ConfigError::UnknownProfile {
+                name: name.to_string(),
+                available,
+            });
+        }
+        config.profile = Some(name.to_string());
+    }
+
     // Resolve theme
     let themes_dirs = crate::theme::loader::collect_themes_dirs(discovery.project_root.as_deref());
     config.theme = crate::theme::loader::resolve_theme(&theme_raw, &themes_dirs)?;
@@ -320,6 +644,285 @@ sources: []
         assert!(display.contains("name"));
     }
 
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_load_encoding() {
+        let temp = TempDir::new().unwrap();
+        let config_path = temp.path().join("lazytail.yaml");
+        fs::write(&config_path, "encoding: cp1252\n").unwrap();
+
+        let discovery = DiscoveryResult {
+            project_root: Some(temp.path().to_path_buf()),
+            project_config: Some(config_path.clone()),
+            global_config: None,
+        };
+        let config = load(&discovery).unwrap();
+        assert_eq!(config.encoding, Some(Encoding::Windows1252));
+
+        fs::write(&config_path, "encoding: ebcdic\n").unwrap();
+        let error = load(&discovery).unwrap_err();
+        assert!(error.to_string().contains("unknown encoding"));
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_load_background_alert() {
+        use crate::config::types::BackgroundAlert;
+
+        let temp = TempDir::new().unwrap();
+        let config_path = temp.path().join("lazytail.yaml");
+        fs::write(&config_path, "background_alert: warn\n").unwrap();
+
+        let discovery = DiscoveryResult {
+            project_root: Some(temp.path().to_path_buf()),
+            project_config: Some(config_path.clone()),
+            global_config: None,
+        };
+        let config = load(&discovery).unwrap();
+        assert_eq!(config.background_alert, Some(BackgroundAlert::Warn));
+        assert_eq!(BackgroundAlert::Off.min_severity(), None);
+
+        fs::write(&config_path, "background_alert: loud\n").unwrap();
+        assert!(load(&discovery).is_err());
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_load_combined_view_settings() {
+        let temp = TempDir::new().unwrap();
+        let config_path = temp.path().join("lazytail.yaml");
+        fs::write(
+            &config_path,
+            "combined_min_sources: 1\ncombined_views: false\n",
+        )
+        .unwrap();
+
+        let discovery = DiscoveryResult {
+            project_root: Some(temp.path().to_path_buf()),
+            project_config: Some(config_path.clone()),
+            global_config: None,
+        };
+        let config = load(&discovery).unwrap();
+        assert_eq!(config.combined_min_sources, Some(1));
+        assert_eq!(config.combined_views, Some(false));
+
+        fs::write(&config_path, "combined_min_sources: 0\n").unwrap();
+        let error = load(&discovery).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("combined_min_sources must be at least 1"));
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_load_index_checkpoint_interval() {
+        let temp = TempDir::new().unwrap();
+        let config_path = temp.path().join("lazytail.yaml");
+        let discovery = DiscoveryResult {
+            project_root: Some(temp.path().to_path_buf()),
+            project_config: Some(config_path.clone()),
+            global_config: None,
+        };
+
+        fs::write(&config_path, "name: test\n").unwrap();
+        assert_eq!(load(&discovery).unwrap().index_checkpoint_interval(), 100);
+
+        fs::write(&config_path, "index:\n  checkpoint_interval: 25\n").unwrap();
+        assert_eq!(load(&discovery).unwrap().index_checkpoint_interval(), 25);
+
+        fs::write(&config_path, "index:\n  checkpoint_interval: 5\n").unwrap();
+        let error = load(&discovery).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("index.checkpoint_interval must be between 10 and 10000"));
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_load_line_prefix_fills_defaults() {
+        let temp = TempDir::new().unwrap();
+        let config_path = temp.path().join("lazytail.yaml");
+        fs::write(&config_path, "line_prefix:\n  separator: \": \"\n").unwrap();
+
+        let discovery = DiscoveryResult {
+            project_root: Some(temp.path().to_path_buf()),
+            project_config: Some(config_path),
+            global_config: None,
+        };
+        let prefix = load(&discovery).unwrap().line_prefix.unwrap();
+        assert!(prefix.show_numbers);
+        assert_eq!(prefix.width, 6);
+        assert_eq!(prefix.separator, ": ");
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_load_max_filter_history_zero_rejected() {
+        let temp = TempDir::new().unwrap();
+        let config_path = temp.path().join("lazytail.yaml");
+        fs::write(&config_path, "max_filter_history: 0\n").unwrap();
+
+        let discovery = DiscoveryResult {
+            project_root: Some(temp.path().to_path_buf()),
+            project_config: Some(config_path),
+            global_config: None,
+        };
+
+        let error = load(&discovery).unwrap_err();
+        assert!(error.to_string().contains("max_filter_history"));
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_load_mouse_settings() {
+        let temp = TempDir::new().unwrap();
+        let config_path = temp.path().join("lazytail.yaml");
+        fs::write(
+            &config_path,
+            "mouse_scroll_lines: 5\nmouse_capture: false\n",
+        )
+        .unwrap();
+
+        let discovery = DiscoveryResult {
+            project_root: Some(temp.path().to_path_buf()),
+            project_config: Some(config_path.clone()),
+            global_config: None,
+        };
+        let config = load(&discovery).unwrap();
+        assert_eq!(config.mouse_scroll_lines, Some(5));
+        assert_eq!(config.mouse_capture, Some(false));
+
+        fs::write(&config_path, "mouse_scroll_lines: 0\n").unwrap();
+        let error = load(&discovery).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("mouse_scroll_lines must be at least 1"));
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_load_content_width() {
+        let temp = TempDir::new().unwrap();
+        let config_path = temp.path().join("lazytail.yaml");
+        fs::write(
+            &config_path,
+            "max_content_width: 160\ncenter_content: false\n",
+        )
+        .unwrap();
+
+        let discovery = DiscoveryResult {
+            project_root: Some(temp.path().to_path_buf()),
+            project_config: Some(config_path.clone()),
+            global_config: None,
+        };
+        let config = load(&discovery).unwrap();
+        assert_eq!(config.max_content_width, Some(160));
+        assert_eq!(config.center_content, Some(false));
+
+        fs::write(&config_path, "max_content_width: 0\n").unwrap();
+        let error = load(&discovery).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("max_content_width must be at least 1"));
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_load_display_transform() {
+        let temp = TempDir::new().unwrap();
+        let config_path = temp.path().join("lazytail.yaml");
+        fs::write(
+            &config_path,
+            r#"sources:
+  - name: api
+    path: /tmp/api.log
+    display_transform:
+      pattern: 'msg="([^"]*)"'
+      template: "$1"
+"#,
+        )
+        .unwrap();
+
+        let discovery = DiscoveryResult {
+            project_root: Some(temp.path().to_path_buf()),
+            project_config: Some(config_path.clone()),
+            global_config: None,
+        };
+        let config = load(&discovery).unwrap();
+        let transform = config.project_sources[0]
+            .display_transform
+            .as_ref()
+            .unwrap();
+        assert_eq!(
+            transform.apply(r#"level=info msg="started""#).as_deref(),
+            Some("started")
+        );
+
+        fs::write(
+            &config_path,
+            r#"sources:
+  - name: api
+    path: /tmp/api.log
+    display_transform:
+      pattern: 'msg=(.*)'
+      template: "$2"
+"#,
+        )
+        .unwrap();
+        let error = load(&discovery).unwrap_err().to_string();
+        assert!(error.contains("api"));
+        assert!(error.contains("unknown group"));
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_load_default_filter() {
+        let temp = TempDir::new().unwrap();
+        let config_path = temp.path().join("lazytail.yaml");
+        fs::write(
+            &config_path,
+            r#"sources:
+  - name: api
+    path: /tmp/api.log
+    default_filter:
+      pattern: "error|fatal"
+      mode: regex
+  - name: web
+    path: /tmp/web.log
+    default_filter:
+      pattern: timeout
+"#,
+        )
+        .unwrap();
+
+        let discovery = DiscoveryResult {
+            project_root: Some(temp.path().to_path_buf()),
+            project_config: Some(config_path.clone()),
+            global_config: None,
+        };
+        let config = load(&discovery).unwrap();
+        let api = config.project_sources[0].default_filter.as_ref().unwrap();
+        assert_eq!(api.pattern, "error|fatal");
+        assert_eq!(api.mode, DefaultFilterMode::Regex);
+        let web = config.project_sources[1].default_filter.as_ref().unwrap();
+        assert_eq!(web.mode, DefaultFilterMode::Plain);
+
+        fs::write(
+            &config_path,
+            r#"sources:
+  - name: api
+    path: /tmp/api.log
+    default_filter:
+      pattern: "(unclosed"
+      mode: regex
+"#,
+        )
+        .unwrap();
+        let error = load(&discovery).unwrap_err().to_string();
+        assert!(error.contains("api"));
+        assert!(error.contains("default_filter"));
+    }
+
     #[test]
     #[ignore] // Slow: creates temp directory and files
     fn test_load_both_configs() {
@@ -714,4 +1317,86 @@ renderers:
         assert_eq!(conditions[0].op, "eq");
         assert_eq!(conditions[0].value, "error");
     }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_load_profile_extends_base_config() {
+        let temp = TempDir::new().unwrap();
+        let project_config_path = temp.path().join("lazytail.yaml");
+        let global_config_path = temp.path().join("config.yaml");
+        fs::write(
+            &project_config_path,
+            r#"
+sources:
+  - name: app
+    path: /tmp/app.log
+profiles:
+  prod:
+    sources:
+      - name: prod-app
+        path: /tmp/prod-app.log
+    highlights:
+      - pattern: "5\\d\\d"
+        style: red
+"#,
+        )
+        .unwrap();
+        fs::write(
+            &global_config_path,
+            r#"
+profiles:
+  prod:
+    sources:
+      - name: gateway
+        path: /tmp/gateway.log
+  dev: {}
+"#,
+        )
+        .unwrap();
+        let discovery = DiscoveryResult {
+            project_root: Some(temp.path().to_path_buf()),
+            project_config: Some(project_config_path.clone()),
+            global_config: Some(global_config_path),
+        };
+
+        let base = load(&discovery).unwrap();
+        assert_eq!(base.project_sources.len(), 1);
+        assert!(base.global_sources.is_empty());
+        assert_eq!(base.profile, None);
+
+        let prod = load_with_profile(&discovery, Some("prod")).unwrap();
+        let names: Vec<_> = prod.project_sources.iter().map(|s| &s.name).collect();
+        assert_eq!(names, ["app", "prod-app"]);
+        assert_eq!(prod.global_sources[0].name, "gateway");
+        assert_eq!(prod.highlights.len(), 1);
+        assert_eq!(prod.profile.as_deref(), Some("prod"));
+
+        let single = load_single_file(&project_config_path, Some("prod")).unwrap();
+        assert_eq!(single.sources.len(), 2);
+        assert_eq!(single.profiles, ["prod"]);
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_load_unknown_profile_lists_available() {
+        let temp = TempDir::new().unwrap();
+        let config_path = temp.path().join("lazytail.yaml");
+        fs::write(&config_path, "profiles:\n  prod: {}\n  dev: {}\n").unwrap();
+        let discovery = DiscoveryResult {
+            project_root: Some(temp.path().to_path_buf()),
+            project_config: Some(config_path.clone()),
+            global_config: None,
+        };
+
+        let error = load_with_profile(&discovery, Some("staging"))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("unknown profile `staging`"), "{}", error);
+        assert!(error.contains("available profiles: dev, prod"), "{}", error);
+
+        assert!(matches!(
+            load_single_file(&config_path, Some("staging")),
+            Err(ConfigError::UnknownProfile { .. })
+        ));
+    }
 }
@@ -4,5 +4,5 @@ pub mod loader;
 pub mod types;
 
 pub use discovery::{discover, DiscoveryResult};
-pub use loader::{load, load_single_file, SingleFileConfig};
+pub use loader::{load, load_single_file, load_with_profile, SingleFileConfig};
 pub use types::{Config, Source};
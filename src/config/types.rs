@@ -2,6 +2,7 @@
 //!
 //! Defines structures for parsing and representing configuration files.
 
+use crate::index::flags::Severity;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -27,6 +28,279 @@ pub struct RawConfig {
     /// Theme configuration (name or custom struct).
     #[serde(default)]
     pub theme: Option<crate::theme::RawThemeConfig>,
+    /// Always-on highlight rules for important patterns.
+    #[serde(default)]
+    pub highlights: Vec<RawHighlightDef>,
+    /// Maximum number of filter history entries to keep (default: 50).
+    #[serde(default)]
+    pub max_filter_history: Option<usize>,
+    /// Text encoding of log files (default: utf-8).
+    #[serde(default)]
+    pub encoding: Option<String>,
+    /// Smart-case filtering: uppercase in the pattern implies case-sensitive.
+    #[serde(default)]
+    pub smart_case: Option<bool>,
+    /// Action on double-click in the log view (default: expand).
+    #[serde(default)]
+    pub double_click: Option<DoubleClickAction>,
+    /// Fields the quick filter (`v`) uses without asking, in priority order.
+    #[serde(default)]
+    pub quick_filter_fields: Option<Vec<String>>,
+    /// Fields holding a trace/correlation id, in priority order.
+    #[serde(default)]
+    pub correlation_fields: Option<Vec<String>>,
+    /// Fields copied with `C` without asking, in priority order.
+    #[serde(default)]
+    pub copy_fields: Option<Vec<String>>,
+    /// Mark wrapped continuation rows in the prefix column (default: true).
+    #[serde(default)]
+    pub wrap_markers: Option<bool>,
+    /// Show large line and match counts compactly, like `1.2M` (default: true).
+    #[serde(default)]
+    pub compact_numbers: Option<bool>,
+    /// Line number prefix format in the log view.
+    #[serde(default)]
+    pub line_prefix: Option<LinePrefixConfig>,
+    /// Show the "follow paused" banner with a new-line counter (default: true).
+    #[serde(default)]
+    pub follow_pause_banner: Option<bool>,
+    /// Blank rows kept below the newest line in follow mode (default: 2;
+    /// 0 keeps it on the bottom row).
+    #[serde(default)]
+    pub follow_margin: Option<usize>,
+    /// Widest the log view gets, in columns (default: the full terminal
+    /// width). Wrapping happens at the capped width.
+    #[serde(default)]
+    pub max_content_width: Option<usize>,
+    /// Center the log view when `max_content_width` caps it (default: true).
+    #[serde(default)]
+    pub center_content: Option<bool>,
+    /// Offer to build an index when filtering a large unindexed file (default: true).
+    #[serde(default)]
+    pub suggest_index: Option<bool>,
+    /// When closing a tab asks for confirmation (default: destructive).
+    #[serde(default)]
+    pub confirm_close: Option<ConfirmClose>,
+    /// What `Esc` in the filter prompt does with the previewed filter
+    /// (default: clear).
+    #[serde(default)]
+    pub filter_esc: Option<FilterEsc>,
+    /// Low-resource mode: skip startup index builds and poll less often
+    /// (default: off; auto turns it on when running on battery).
+    #[serde(default)]
+    pub low_resource: Option<LowResource>,
+    /// Source navigation layout (default: sidebar).
+    #[serde(default)]
+    pub nav: Option<NavLayout>,
+    /// Pause live filter preview above this many MB scanned without index
+    /// acceleration (default: 1024; 0 disables).
+    #[serde(default)]
+    pub confirm_scan_mb: Option<u64>,
+    /// Lines scrolled per mouse wheel notch (default: 3).
+    #[serde(default)]
+    pub mouse_scroll_lines: Option<usize>,
+    /// Capture the mouse for scrolling and clicks (default: true). Disable
+    /// to keep the terminal's native text selection.
+    #[serde(default)]
+    pub mouse_capture: Option<bool>,
+    /// strftime-style format of the timestamp column (`%Y %m %d %H %M %S
+    /// %f`). Default: time of day for today's lines, date and time otherwise.
+    #[serde(default)]
+    pub timestamp_format: Option<String>,
+    /// Lowest severity in a followed background tab that raises an alert
+    /// (default: error).
+    #[serde(default)]
+    pub background_alert: Option<BackgroundAlert>,
+    /// Sources a category needs before it gets a combined `$all` view
+    /// (default: 2; 1 creates one even for a single source).
+    #[serde(default)]
+    pub combined_min_sources: Option<usize>,
+    /// Create per-category combined `$all` views (default: true).
+    #[serde(default)]
+    pub combined_views: Option<bool>,
+    /// Columnar index build settings.
+    #[serde(default)]
+    pub index: Option<IndexConfig>,
+    /// Minimum milliseconds between web UI change notifications; changes in
+    /// between are batched (default: 300; 0 notifies on every change).
+    #[serde(default)]
+    pub web_refresh_ms: Option<u64>,
+    /// Hold a pipe's unterminated last line until its newline arrives
+    /// (default: true). Disable to watch partial lines as they are written.
+    #[serde(default)]
+    pub line_buffered: Option<bool>,
+    /// Renderer presets picked by a source's detected line format, for
+    /// sources without their own `renderers`.
+    #[serde(default)]
+    pub format_renderers: Option<FormatRenderers>,
+    /// Skip opening a file that is already open in another tab (default:
+    /// true).
+    #[serde(default)]
+    pub dedup_files: Option<bool>,
+    /// Named profiles selectable with `--profile`.
+    #[serde(default)]
+    pub profiles: HashMap<String, RawProfile>,
+}
+
+/// A named profile: sources, renderers and highlights added to the file's
+/// own when the profile is selected with `--profile`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RawProfile {
+    #[serde(default)]
+    pub sources: Vec<RawSource>,
+    #[serde(default)]
+    pub renderers: Vec<RawRendererDef>,
+    #[serde(default)]
+    pub highlights: Vec<RawHighlightDef>,
+}
+
+/// Renderer presets picked by a source's dominant line format.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FormatRenderers {
+    /// Preset for sources whose lines are mostly JSON.
+    pub json: Option<String>,
+    /// Preset for sources whose lines are mostly logfmt.
+    pub logfmt: Option<String>,
+}
+
+/// Columnar index build settings.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IndexConfig {
+    /// Lines between checkpoints. Smaller intervals give finer-grained
+    /// severity counts at the cost of a larger index.
+    pub checkpoint_interval: u16,
+    /// Store indexes under ~/.cache/lazytail/index/ instead of beside each
+    /// log (for read-only or shared log directories).
+    pub central_cache: bool,
+}
+
+impl Default for IndexConfig {
+    fn default() -> Self {
+        Self {
+            checkpoint_interval: crate::index::builder::DEFAULT_CHECKPOINT_INTERVAL,
+            central_cache: false,
+        }
+    }
+}
+
+/// Line number prefix drawn before each log line.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LinePrefixConfig {
+    /// Show line numbers (the separator is still drawn when false).
+    pub show_numbers: bool,
+    /// Minimum number column width; widened for files with more digits.
+    pub width: usize,
+    /// Text between the number and the line content.
+    pub separator: String,
+}
+
+impl Default for LinePrefixConfig {
+    fn default() -> Self {
+        Self {
+            show_numbers: true,
+            width: 6,
+            separator: " | ".to_string(),
+        }
+    }
+}
+
+/// Action performed when a log line is double-clicked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DoubleClickAction {
+    /// Expand or collapse the line.
+    #[default]
+    Expand,
+    /// Copy the line to the clipboard.
+    Copy,
+    /// Do nothing beyond selecting the line.
+    #[serde(rename = "none")]
+    Disabled,
+}
+
+/// How sources are laid out for navigation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NavLayout {
+    /// Tree of sources in a side panel, with stats below.
+    #[default]
+    Sidebar,
+    /// One-row strip of source tabs above the log view.
+    Tabbar,
+}
+
+/// When closing a tab asks for confirmation first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfirmClose {
+    /// Confirm every close.
+    Always,
+    /// Confirm only closes that delete a file (ended captured sources).
+    #[default]
+    Destructive,
+    /// Never confirm.
+    Never,
+}
+
+/// What `Esc` in the filter prompt does with the filter being previewed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterEsc {
+    /// Clear the filter and return to the line selected before filtering.
+    #[default]
+    Clear,
+    /// Close the prompt, keeping the previewed filter applied.
+    Keep,
+}
+
+/// When low-resource mode is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LowResource {
+    #[default]
+    Off,
+    On,
+    /// On while running on battery, where the OS reports it (Linux).
+    Auto,
+}
+
+/// Lowest severity that raises an alert when it appears in a followed
+/// background tab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackgroundAlert {
+    Warn,
+    #[default]
+    Error,
+    Fatal,
+    /// Never alert.
+    Off,
+}
+
+impl BackgroundAlert {
+    /// The threshold, or `None` when alerts are off.
+    pub fn min_severity(self) -> Option<Severity> {
+        match self {
+            BackgroundAlert::Warn => Some(Severity::Warn),
+            BackgroundAlert::Error => Some(Severity::Error),
+            BackgroundAlert::Fatal => Some(Severity::Fatal),
+            BackgroundAlert::Off => None,
+        }
+    }
+}
+
+/// Raw highlight rule from config file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RawHighlightDef {
+    /// Regex matched against each rendered line.
+    pub pattern: String,
+    /// Style applied to every matching substring.
+    pub style: StyleValue,
 }
 
 /// Raw renderer definition from config file.
@@ -96,6 +370,64 @@ pub struct RawSource {
     /// List of renderer preset names to use for this source.
     #[serde(default)]
     pub renderers: Vec<String>,
+    /// Regex rewrite applied to displayed lines.
+    #[serde(default)]
+    pub display_transform: Option<RawDisplayTransform>,
+    /// Accent color for this source's border and title.
+    #[serde(default)]
+    pub color: Option<crate::theme::ThemeColor>,
+    /// Filter applied automatically when the source's tab opens.
+    #[serde(default)]
+    pub default_filter: Option<DefaultFilterConfig>,
+    /// Fold each pretty-printed multi-line JSON object into one line.
+    #[serde(default)]
+    pub multiline_json: bool,
+}
+
+/// Standing filter for a source, applied when its tab opens.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DefaultFilterConfig {
+    /// Filter pattern, interpreted according to `mode`.
+    pub pattern: String,
+    /// How the pattern is matched (default: plain).
+    #[serde(default)]
+    pub mode: DefaultFilterMode,
+}
+
+/// Match mode for a source's default filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DefaultFilterMode {
+    /// Case-insensitive plain text.
+    #[default]
+    Plain,
+    /// Case-insensitive regular expression.
+    Regex,
+    /// Structured query (`json | level == "error"`).
+    Query,
+}
+
+impl DefaultFilterConfig {
+    /// The filter mode to run the pattern with.
+    pub fn filter_mode(&self) -> crate::filter::FilterMode {
+        use crate::filter::FilterMode;
+        match self.mode {
+            DefaultFilterMode::Plain => FilterMode::plain(),
+            DefaultFilterMode::Regex => FilterMode::regex(),
+            DefaultFilterMode::Query => FilterMode::query(),
+        }
+    }
+}
+
+/// Raw display transform from config file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RawDisplayTransform {
+    /// Regex matched against each line (ANSI codes stripped).
+    pub pattern: String,
+    /// Replacement template with `$1` / `${name}` group references.
+    pub template: String,
 }
 
 /// Validated source with expanded path and existence check.
@@ -111,6 +443,14 @@ pub struct Source {
     pub exists: bool,
     /// Renderer preset names assigned to this source.
     pub renderer_names: Vec<String>,
+    /// Compiled display transform (validated at load time).
+    pub display_transform: Option<crate::renderer::transform::DisplayTransform>,
+    /// Accent color for the source's tab (falls back to the theme).
+    pub color: Option<ratatui::style::Color>,
+    /// Filter applied when the source's tab opens (validated at load time).
+    pub default_filter: Option<DefaultFilterConfig>,
+    /// Fold multi-line JSON objects into one line per object.
+    pub multiline_json: bool,
 }
 
 /// Merged config from global and project files.
@@ -121,6 +461,8 @@ pub struct Source {
 pub struct Config {
     /// Project name from project config (if present).
     pub name: Option<String>,
+    /// Profile selected with `--profile` (if any).
+    pub profile: Option<String>,
     /// Sources defined in the project config.
     pub project_sources: Vec<Source>,
     /// Sources defined in the global config.
@@ -131,9 +473,81 @@ pub struct Config {
     pub renderers: Vec<RawRendererDef>,
     /// Resolved theme.
     pub theme: crate::theme::Theme,
+    /// Raw highlight rules (global first, then project).
+    pub highlights: Vec<RawHighlightDef>,
+    /// Filter history size limit (project overrides global).
+    pub max_filter_history: Option<usize>,
+    /// Log file text encoding (project overrides global).
+    pub encoding: Option<crate::reader::encoding::Encoding>,
+    /// Smart-case filtering default (project overrides global).
+    pub smart_case: Option<bool>,
+    /// Double-click action in the log view (project overrides global).
+    pub double_click: Option<DoubleClickAction>,
+    /// Default quick filter fields (project overrides global).
+    pub quick_filter_fields: Option<Vec<String>>,
+    /// Trace/correlation id fields (project overrides global).
+    pub correlation_fields: Option<Vec<String>>,
+    /// Default copied fields (project overrides global).
+    pub copy_fields: Option<Vec<String>>,
+    /// Wrapped continuation row markers (project overrides global).
+    pub wrap_markers: Option<bool>,
+    /// Compact line and match counts (project overrides global).
+    pub compact_numbers: Option<bool>,
+    /// Line number prefix format (project overrides global).
+    pub line_prefix: Option<LinePrefixConfig>,
+    /// Follow-paused banner (project overrides global).
+    pub follow_pause_banner: Option<bool>,
+    /// Follow mode bottom margin (project overrides global).
+    pub follow_margin: Option<usize>,
+    /// Log view width cap (project overrides global).
+    pub max_content_width: Option<usize>,
+    /// Centering of the capped log view (project overrides global).
+    pub center_content: Option<bool>,
+    /// Index build suggestion (project overrides global).
+    pub suggest_index: Option<bool>,
+    /// Close confirmation policy (project overrides global).
+    pub confirm_close: Option<ConfirmClose>,
+    /// Filter prompt `Esc` behavior (project overrides global).
+    pub filter_esc: Option<FilterEsc>,
+    /// Low-resource mode (project overrides global).
+    pub low_resource: Option<LowResource>,
+    /// Source navigation layout (project overrides global).
+    pub nav: Option<NavLayout>,
+    /// Live-preview scan budget in MB (project overrides global).
+    pub confirm_scan_mb: Option<u64>,
+    /// Mouse wheel scroll step (project overrides global).
+    pub mouse_scroll_lines: Option<usize>,
+    /// Mouse capture toggle (project overrides global).
+    pub mouse_capture: Option<bool>,
+    /// Timestamp column format (project overrides global).
+    pub timestamp_format: Option<String>,
+    /// Background tab alert threshold (project overrides global).
+    pub background_alert: Option<BackgroundAlert>,
+    /// Combined view source threshold (project overrides global).
+    pub combined_min_sources: Option<usize>,
+    /// Combined views toggle (project overrides global).
+    pub combined_views: Option<bool>,
+    /// Index build settings (project overrides global).
+    pub index: Option<IndexConfig>,
+    /// Web UI change notification interval (project overrides global).
+    pub web_refresh_ms: Option<u64>,
+    /// Stream line buffering toggle (project overrides global).
+    pub line_buffered: Option<bool>,
+    /// Renderer presets by detected format (project overrides global).
+    pub format_renderers: Option<FormatRenderers>,
+    /// Duplicate file tab skipping (project overrides global).
+    pub dedup_files: Option<bool>,
 }
 
 impl Config {
+    /// Checkpoint interval for index builds (configured or default).
+    pub fn index_checkpoint_interval(&self) -> u16 {
+        self.index.as_ref().map_or(
+            crate::index::builder::DEFAULT_CHECKPOINT_INTERVAL,
+            |index| index.checkpoint_interval,
+        )
+    }
+
     /// Returns true if any sources are defined (project or global).
     #[cfg(test)]
     pub fn has_sources(&self) -> bool {
@@ -3,9 +3,13 @@
 //! Computes grouped counts from matching log line indices, supporting
 //! `count by (field1, field2, ...)` with optional `top N` limiting.
 
-use crate::filter::query::{extract_json_field, parse_logfmt, Aggregation, Parser};
+use crate::filter::query::{
+    extract_json_field, parse_logfmt, Aggregation, AggregationType, Parser,
+};
+use crate::index::flags::Severity;
 use crate::reader::LogReader;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// A single aggregation group with its key, count, and source line indices.
 #[derive(Debug, Clone)]
@@ -14,15 +18,59 @@ pub struct AggregationGroup {
     pub key: Vec<(String, String)>,
     /// Number of matching lines in this group.
     pub count: usize,
-    /// Original line indices belonging to this group.
-    pub line_indices: Vec<usize>,
+    /// Original line indices belonging to this group, ascending (shared
+    /// with the accumulator that is still adding to them).
+    pub line_indices: Arc<Vec<usize>>,
+}
+
+/// Group value recorded for lines that lack a group-by field.
+pub const MISSING_VALUE: &str = "<missing>";
+
+/// A row of the aggregation table: a group, or (for multi-field keys) a
+/// subtotal over the groups sharing a key prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AggregationRow {
+    /// Key components the row covers (a prefix of the fields for subtotals).
+    pub key: Vec<(String, String)>,
+    /// Number of matching lines under the row.
+    pub count: usize,
+    /// Nesting level, i.e. the field whose value the row introduces.
+    pub depth: usize,
+    /// Indices into [`AggregationResult::groups`] under the row. A subtotal
+    /// may also cover groups past the `top N` limit, numbered after them.
+    pub groups: Vec<usize>,
+}
+
+impl AggregationRow {
+    /// Whether the row sums several groups rather than being one.
+    pub fn is_subtotal(&self, field_count: usize) -> bool {
+        self.depth + 1 < field_count
+    }
+
+    /// Query clause matching every key component, for drilling down.
+    pub fn drill_down_pattern(&self) -> String {
+        self.key
+            .iter()
+            .map(|(name, value)| {
+                if value == MISSING_VALUE {
+                    format!("!has {}", name)
+                } else {
+                    format!("{} == \"{}\"", name, value)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" & ")
+    }
 }
 
 /// Result of an aggregation computation.
 #[derive(Debug, Clone)]
 pub struct AggregationResult {
-    /// Groups sorted by count descending.
+    /// Groups sorted by count descending (severity order for
+    /// [`by_severity`](Self::by_severity)), up to the `top N` limit.
     pub groups: Vec<AggregationGroup>,
+    /// Groups past the limit: not shown, but still part of subtotals.
+    past_limit: Vec<AggregationGroup>,
     /// Total number of matching lines across all groups.
     pub total_matches: usize,
     /// The aggregation clause that produced this result.
@@ -30,9 +78,52 @@ pub struct AggregationResult {
     /// The parser used for field extraction (retained for drill-down context).
     #[allow(dead_code)]
     pub parser: Parser,
+    /// Table rows, built once from the groups (see [`rows`](Self::rows)).
+    rows: Vec<AggregationRow>,
+    /// Groups carry counts only, with empty line lists (see
+    /// [`from_severity_counts`](Self::from_severity_counts)).
+    pub lines_deferred: bool,
 }
 
 impl AggregationResult {
+    fn new(
+        groups: Vec<AggregationGroup>,
+        total_matches: usize,
+        aggregation: Aggregation,
+        parser: Parser,
+    ) -> Self {
+        let mut result = AggregationResult {
+            groups,
+            past_limit: Vec::new(),
+            total_matches,
+            aggregation,
+            parser,
+            rows: Vec::new(),
+            lines_deferred: false,
+        };
+        let all: Vec<usize> = (0..result.groups.len()).collect();
+        let mut rows = Vec::new();
+        result.push_rows(&all, 0, &mut rows);
+
+        // Subtotals are summed over every group; only then are the groups
+        // past the limit hidden, along with subtotals left without any
+        if let Some(limit) = result.aggregation.limit {
+            if result.groups.len() > limit {
+                rows.retain(|row| row.groups.iter().any(|&idx| idx < limit));
+                result.past_limit = result.groups.split_off(limit);
+            }
+        }
+        result.rows = rows;
+        result
+    }
+
+    /// Group `idx` of a row, which may lie past the limit.
+    fn group(&self, idx: usize) -> &AggregationGroup {
+        self.groups
+            .get(idx)
+            .unwrap_or_else(|| &self.past_limit[idx - self.groups.len()])
+    }
+
     /// Compute aggregation from matching line indices.
     ///
     /// Reads each matching line, extracts group-by fields using the specified parser,
@@ -43,28 +134,222 @@ impl AggregationResult {
         aggregation: &Aggregation,
         parser: &Parser,
     ) -> Self {
-        // HashMap: group key (field values) -> (count, line_indices)
-        let mut groups: HashMap<Vec<String>, (usize, Vec<usize>)> = HashMap::new();
+        let mut accumulator = AggregationAccumulator::new(aggregation.clone(), parser.clone());
+        accumulator.add(reader, matching_indices);
+        accumulator.snapshot()
+    }
+
+    /// Table rows, hierarchical for multi-field keys: each value of the
+    /// first field gets a subtotal row followed by its groups (nested the
+    /// same way for further fields). Subtotals sort by count descending.
+    pub fn rows(&self) -> &[AggregationRow] {
+        &self.rows
+    }
+
+    fn push_rows(&self, groups: &[usize], depth: usize, rows: &mut Vec<AggregationRow>) {
+        if depth + 1 >= self.aggregation.fields.len() {
+            // Innermost field: one row per group, in result order
+            rows.extend(groups.iter().map(|&idx| AggregationRow {
+                key: self.groups[idx].key.clone(),
+                count: self.groups[idx].count,
+                depth,
+                groups: vec![idx],
+            }));
+            return;
+        }
+
+        // Bucket by this field's value, keeping result order within buckets
+        let mut buckets: Vec<(&str, Vec<usize>, usize)> = Vec::new();
+        let mut bucket_of: HashMap<&str, usize> = HashMap::new();
+        for &idx in groups {
+            let group = &self.groups[idx];
+            let value = group.key.get(depth).map_or("", |(_, v)| v.as_str());
+            match bucket_of.get(value) {
+                Some(&bucket) => {
+                    let (_, members, count) = &mut buckets[bucket];
+                    members.push(idx);
+                    *count += group.count;
+                }
+                None => {
+                    bucket_of.insert(value, buckets.len());
+                    buckets.push((value, vec![idx], group.count));
+                }
+            }
+        }
+        buckets.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(b.0)));
+
+        for (_, members, count) in buckets {
+            rows.push(AggregationRow {
+                key: self.groups[members[0]].key[..=depth].to_vec(),
+                count,
+                depth,
+                groups: members.clone(),
+            });
+            self.push_rows(&members, depth + 1, rows);
+        }
+    }
+
+    /// Matching lines under a row, ascending.
+    pub fn row_line_indices(&self, row: &AggregationRow) -> Vec<usize> {
+        let mut indices: Vec<usize> = row
+            .groups
+            .iter()
+            .flat_map(|&idx| self.group(idx).line_indices.iter().copied())
+            .collect();
+        if row.groups.len() > 1 {
+            indices.sort_unstable();
+        }
+        indices
+    }
+
+    /// Group lines by severity (`count by (severity)`), most severe first.
+    /// Lines without a detected level form an `unknown` group; empty
+    /// levels are left out.
+    pub fn by_severity(lines: impl IntoIterator<Item = (usize, Severity)>) -> Self {
+        let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); SEVERITY_ORDER.len()];
+        for (line_idx, severity) in lines {
+            let bucket = SEVERITY_ORDER.iter().position(|&s| s == severity);
+            if let Some(bucket) = bucket {
+                buckets[bucket].push(line_idx);
+            }
+        }
+        let groups = SEVERITY_ORDER
+            .iter()
+            .zip(buckets)
+            .map(|(&severity, line_indices)| (severity, line_indices.len(), line_indices))
+            .collect();
+        Self::severity_groups(groups)
+    }
+
+    /// Severity groups from per-severity line counts alone, for breaking
+    /// down a whole file from its index. The groups' line lists are left
+    /// empty ([`lines_deferred`](Self::lines_deferred)) and found when a
+    /// group is drilled into.
+    pub fn from_severity_counts(count: impl Fn(Severity) -> usize) -> Self {
+        let groups = SEVERITY_ORDER
+            .iter()
+            .map(|&severity| (severity, count(severity), Vec::new()))
+            .collect();
+        let mut result = Self::severity_groups(groups);
+        result.lines_deferred = true;
+        result
+    }
 
+    /// `count by (severity)` result from (severity, count, lines) in display
+    /// order; severities without lines are left out.
+    fn severity_groups(groups: Vec<(Severity, usize, Vec<usize>)>) -> Self {
+        let groups: Vec<AggregationGroup> = groups
+            .into_iter()
+            .filter(|&(_, count, _)| count > 0)
+            .map(|(severity, count, line_indices)| AggregationGroup {
+                key: vec![(
+                    "severity".to_string(),
+                    severity.label().unwrap_or("unknown").to_string(),
+                )],
+                count,
+                line_indices: Arc::new(line_indices),
+            })
+            .collect();
+        let total_matches = groups.iter().map(|group| group.count).sum();
+
+        AggregationResult::new(
+            groups,
+            total_matches,
+            Aggregation {
+                agg_type: AggregationType::CountBy,
+                fields: vec!["severity".to_string()],
+                limit: None,
+            },
+            Parser::Raw,
+        )
+    }
+
+    /// The severity a row of a severity breakdown stands for.
+    pub fn row_severity(&self, row: &AggregationRow) -> Option<Severity> {
+        let (_, label) = row.key.first()?;
+        SEVERITY_ORDER
+            .into_iter()
+            .find(|severity| severity.label().unwrap_or("unknown") == label)
+    }
+}
+
+/// Running group counts for an aggregation, fed matching lines batch by
+/// batch as a filter streams them in.
+///
+/// Each batch only reads its own lines; [`snapshot`](Self::snapshot) gives
+/// the same result [`AggregationResult::compute`] would over every line
+/// added so far, in whatever order the batches arrived.
+///
+/// Group lines are shared with the last snapshot rather than copied, so a
+/// batch costs its own size as long as that snapshot is dropped first.
+#[derive(Debug)]
+pub struct AggregationAccumulator {
+    aggregation: Aggregation,
+    parser: Parser,
+    /// Group key (field values) -> line indices
+    groups: HashMap<Vec<String>, GroupLines>,
+    total_matches: usize,
+}
+
+/// One group's lines, sorted lazily: batches may arrive out of order.
+#[derive(Debug, Default)]
+struct GroupLines {
+    lines: Arc<Vec<usize>>,
+    sorted: bool,
+}
+
+impl AggregationAccumulator {
+    pub fn new(aggregation: Aggregation, parser: Parser) -> Self {
+        Self {
+            aggregation,
+            parser,
+            groups: HashMap::new(),
+            total_matches: 0,
+        }
+    }
+
+    /// Count a batch of matching lines not added before.
+    pub fn add(&mut self, reader: &mut dyn LogReader, matching_indices: &[usize]) {
         for &line_idx in matching_indices {
             let line = match reader.get_line(line_idx) {
                 Ok(Some(l)) => l,
                 _ => continue,
             };
 
-            let field_values = extract_fields(&line, &aggregation.fields, parser);
-            let entry = groups
-                .entry(field_values)
-                .or_insert_with(|| (0, Vec::new()));
-            entry.0 += 1;
-            entry.1.push(line_idx);
+            let field_values = extract_fields(&line, &self.aggregation.fields, &self.parser);
+            let group = self.groups.entry(field_values).or_default();
+            let lines = Arc::make_mut(&mut group.lines);
+            group.sorted = match lines.last() {
+                Some(&last) => group.sorted && last < line_idx,
+                None => true,
+            };
+            lines.push(line_idx);
         }
+        self.total_matches += matching_indices.len();
+    }
+
+    /// Current result: groups sorted and limited, line indices ascending.
+    /// Subtotals still count the groups past the limit.
+    pub fn snapshot(&mut self) -> AggregationResult {
+        for group in self.groups.values_mut().filter(|group| !group.sorted) {
+            Arc::make_mut(&mut group.lines).sort_unstable();
+            group.sorted = true;
+        }
+
+        // Sort by count descending, then by key for stability (the field
+        // names are shared, so comparing values orders the keys)
+        let mut ranked: Vec<(&Vec<String>, &Arc<Vec<usize>>)> = self
+            .groups
+            .iter()
+            .map(|(key, group)| (key, &group.lines))
+            .collect();
+        ranked.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(b.0)));
 
-        // Convert to sorted Vec<AggregationGroup>
-        let mut result_groups: Vec<AggregationGroup> = groups
+        let groups = ranked
             .into_iter()
-            .map(|(key_values, (count, line_indices))| {
-                let key = aggregation
+            .map(|(key_values, line_indices)| {
+                let key = self
+                    .aggregation
                     .fields
                     .iter()
                     .zip(key_values.iter())
@@ -72,31 +357,32 @@ impl AggregationResult {
                     .collect();
                 AggregationGroup {
                     key,
-                    count,
-                    line_indices,
+                    count: line_indices.len(),
+                    line_indices: Arc::clone(line_indices),
                 }
             })
             .collect();
 
-        // Sort by count descending, then by key for stability
-        result_groups.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
-
-        // Apply limit
-        if let Some(limit) = aggregation.limit {
-            result_groups.truncate(limit);
-        }
-
-        let total_matches = matching_indices.len();
-
-        AggregationResult {
-            groups: result_groups,
-            total_matches,
-            aggregation: aggregation.clone(),
-            parser: parser.clone(),
-        }
+        AggregationResult::new(
+            groups,
+            self.total_matches,
+            self.aggregation.clone(),
+            self.parser.clone(),
+        )
     }
 }
 
+/// Severity groups in display order, most severe first.
+const SEVERITY_ORDER: [Severity; 7] = [
+    Severity::Fatal,
+    Severity::Error,
+    Severity::Warn,
+    Severity::Info,
+    Severity::Debug,
+    Severity::Trace,
+    Severity::Unknown,
+];
+
 /// Extract field values from a log line using the specified parser.
 fn extract_fields(line: &str, fields: &[String], parser: &Parser) -> Vec<String> {
     match parser {
@@ -107,7 +393,7 @@ fn extract_fields(line: &str, fields: &[String], parser: &Parser) -> Vec<String>
             };
             fields
                 .iter()
-                .map(|f| extract_json_field(&json, f).unwrap_or_else(|| "<missing>".to_string()))
+                .map(|f| extract_json_field(&json, f).unwrap_or_else(|| MISSING_VALUE.to_string()))
                 .collect()
         }
         Parser::Logfmt => {
@@ -117,7 +403,7 @@ fn extract_fields(line: &str, fields: &[String], parser: &Parser) -> Vec<String>
                 .map(|f| {
                     kv.get(f)
                         .cloned()
-                        .unwrap_or_else(|| "<missing>".to_string())
+                        .unwrap_or_else(|| MISSING_VALUE.to_string())
                 })
                 .collect()
         }
@@ -151,6 +437,10 @@ mod tests {
         fn as_any(&self) -> &dyn std::any::Any {
             self
         }
+
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
     }
 
     fn make_aggregation(fields: Vec<&str>, limit: Option<usize>) -> Aggregation {
@@ -270,9 +560,187 @@ mod tests {
         let result = AggregationResult::compute(&mut reader, &indices, &agg, &Parser::Json);
 
         let api_group = &result.groups[0];
-        assert_eq!(api_group.line_indices, vec![0, 2]);
+        assert_eq!(*api_group.line_indices, vec![0, 2]);
         let worker_group = &result.groups[1];
-        assert_eq!(worker_group.line_indices, vec![1]);
+        assert_eq!(*worker_group.line_indices, vec![1]);
+    }
+
+    #[test]
+    fn test_accumulator_matches_batch_compute() {
+        let services = ["api", "worker", "db", "api", "cache", "worker", "api"];
+        let mut reader = MockReader {
+            lines: (0..700)
+                .map(|i| {
+                    format!(
+                        r#"{{"service":"{}","level":"{}"}}"#,
+                        services[i % services.len()],
+                        if i % 3 == 0 { "error" } else { "info" }
+                    )
+                })
+                .collect(),
+        };
+        let indices: Vec<usize> = (0..700).filter(|i| i % 4 != 1).collect();
+
+        for agg in [
+            make_aggregation(vec!["service"], None),
+            make_aggregation(vec!["service", "level"], Some(3)),
+        ] {
+            let batch = AggregationResult::compute(&mut reader, &indices, &agg, &Parser::Json);
+
+            // Streamed newest batch first, like a filter scanning from the end
+            let mut accumulator = AggregationAccumulator::new(agg.clone(), Parser::Json);
+            for chunk in indices.rchunks(64) {
+                accumulator.add(&mut reader, chunk);
+            }
+            let streamed = accumulator.snapshot();
+
+            assert_eq!(streamed.total_matches, batch.total_matches);
+            assert_eq!(streamed.groups.len(), batch.groups.len());
+            for (s, b) in streamed.groups.iter().zip(&batch.groups) {
+                assert_eq!(s.key, b.key);
+                assert_eq!(s.count, b.count);
+                assert_eq!(s.line_indices, b.line_indices);
+            }
+        }
+    }
+
+    /// (depth, key values, count) per row, for compact assertions.
+    fn row_summary(result: &AggregationResult) -> Vec<(usize, Vec<String>, usize)> {
+        result
+            .rows()
+            .iter()
+            .map(|row| {
+                let values = row.key.iter().map(|(_, v)| v.clone()).collect();
+                (row.depth, values, row.count)
+            })
+            .collect()
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_rows_two_fields_with_subtotals() {
+        let mut reader = MockReader {
+            lines: vec![
+                r#"{"service":"api","level":"error"}"#.into(),
+                r#"{"service":"worker","level":"info"}"#.into(),
+                r#"{"service":"api","level":"info"}"#.into(),
+                r#"{"service":"api","level":"error"}"#.into(),
+                r#"{"service":"worker"}"#.into(),
+                r#"{"level":"error"}"#.into(),
+                r#"{"service":"worker","level":"info"}"#.into(),
+            ],
+        };
+        let indices: Vec<usize> = (0..7).collect();
+        let agg = make_aggregation(vec!["service", "level"], None);
+        let result = AggregationResult::compute(&mut reader, &indices, &agg, &Parser::Json);
+
+        assert_eq!(
+            row_summary(&result),
+            vec![
+                (0, strings(&["api"]), 3),
+                (1, strings(&["api", "error"]), 2),
+                (1, strings(&["api", "info"]), 1),
+                (0, strings(&["worker"]), 3),
+                (1, strings(&["worker", "info"]), 2),
+                (1, strings(&["worker", "<missing>"]), 1),
+                (0, strings(&["<missing>"]), 1),
+                (1, strings(&["<missing>", "error"]), 1),
+            ]
+        );
+
+        let rows = result.rows();
+        assert!(rows[0].is_subtotal(2));
+        assert!(!rows[1].is_subtotal(2));
+        assert_eq!(result.row_line_indices(&rows[0]), vec![0, 2, 3]);
+        assert_eq!(result.row_line_indices(&rows[3]), vec![1, 4, 6]);
+        assert_eq!(rows[0].drill_down_pattern(), r#"service == "api""#);
+        assert_eq!(
+            rows[5].drill_down_pattern(),
+            r#"service == "worker" & !has level"#
+        );
+        assert_eq!(rows[6].drill_down_pattern(), "!has service");
+    }
+
+    #[test]
+    fn test_rows_subtotals_count_groups_past_limit() {
+        let mut reader = MockReader {
+            lines: vec![
+                "service=api level=error".into(),
+                "service=api level=error".into(),
+                "service=api level=error".into(),
+                "service=db level=error".into(),
+                "service=db level=error".into(),
+                "service=api level=info".into(),
+                "service=web level=info".into(),
+            ],
+        };
+        let indices: Vec<usize> = (0..7).collect();
+        let agg = make_aggregation(vec!["service", "level"], Some(2));
+        let result = AggregationResult::compute(&mut reader, &indices, &agg, &Parser::Logfmt);
+
+        assert_eq!(result.groups.len(), 2);
+        assert_eq!(
+            row_summary(&result),
+            vec![
+                (0, strings(&["api"]), 4),
+                (1, strings(&["api", "error"]), 3),
+                (0, strings(&["db"]), 2),
+                (1, strings(&["db", "error"]), 2),
+            ]
+        );
+        assert_eq!(result.row_line_indices(&result.rows()[0]), vec![0, 1, 2, 5]);
+    }
+
+    #[test]
+    fn test_rows_three_fields_nest_each_level() {
+        let mut reader = MockReader {
+            lines: vec![
+                "service=api level=error host=a".into(),
+                "service=api level=error host=b".into(),
+                "service=api level=error host=a".into(),
+                "service=api level=warn".into(),
+                "service=db level=error host=a".into(),
+            ],
+        };
+        let indices: Vec<usize> = (0..5).collect();
+        let agg = make_aggregation(vec!["service", "level", "host"], None);
+        let result = AggregationResult::compute(&mut reader, &indices, &agg, &Parser::Logfmt);
+
+        assert_eq!(
+            row_summary(&result),
+            vec![
+                (0, strings(&["api"]), 4),
+                (1, strings(&["api", "error"]), 3),
+                (2, strings(&["api", "error", "a"]), 2),
+                (2, strings(&["api", "error", "b"]), 1),
+                (1, strings(&["api", "warn"]), 1),
+                (2, strings(&["api", "warn", "<missing>"]), 1),
+                (0, strings(&["db"]), 1),
+                (1, strings(&["db", "error"]), 1),
+                (2, strings(&["db", "error", "a"]), 1),
+            ]
+        );
+        let rows = result.rows();
+        assert_eq!(result.row_line_indices(&rows[1]), vec![0, 1, 2]);
+        assert_eq!(
+            rows[2].drill_down_pattern(),
+            r#"service == "api" & level == "error" & host == "a""#
+        );
+    }
+
+    #[test]
+    fn test_rows_single_field_are_the_groups() {
+        let mut reader = MockReader {
+            lines: vec![r#"{"service":"api"}"#.into(), r#"{"other":1}"#.into()],
+        };
+        let agg = make_aggregation(vec!["service"], None);
+        let result = AggregationResult::compute(&mut reader, &[0, 1], &agg, &Parser::Json);
+        let rows = result.rows();
+        assert_eq!(rows.len(), result.groups.len());
+        assert!(rows.iter().all(|row| row.depth == 0 && !row.is_subtotal(1)));
     }
 
     #[test]
@@ -114,6 +114,48 @@ impl FilterEngine {
         rx
     }
 
+    /// Run a filter over a given set of lines only (ascending line numbers).
+    /// Used to narrow a previous result set without rescanning every line.
+    /// Returns a receiver for progress updates.
+    pub fn run_filter_lines<R, F>(
+        reader: Arc<Mutex<R>>,
+        filter: Arc<F>,
+        progress_interval: usize,
+        lines: Vec<usize>,
+        cancel: CancelToken,
+    ) -> Receiver<FilterProgress>
+    where
+        R: LogReader + Send + 'static + ?Sized,
+        F: Filter + 'static + ?Sized,
+    {
+        let (tx, rx) = channel();
+
+        thread::spawn(move || {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                Self::process_lines_shared(
+                    reader,
+                    filter,
+                    tx.clone(),
+                    progress_interval,
+                    &lines,
+                    cancel,
+                )
+            }));
+
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    let _ = tx.send(FilterProgress::Error(e.to_string()));
+                }
+                Err(_) => {
+                    let _ = tx.send(FilterProgress::Error("Filter thread panicked".to_string()));
+                }
+            }
+        });
+
+        rx
+    }
+
     /// Internal filter processing with shared reader (uses locking)
     ///
     /// Processes lines in batches FROM THE END to show recent results first.
@@ -223,6 +265,72 @@ impl FilterEngine {
 
         Ok(())
     }
+
+    /// Like `process_filter_shared`, but only reads the listed lines,
+    /// batching from the end so recent matches arrive first.
+    fn process_lines_shared<R, F>(
+        reader: Arc<Mutex<R>>,
+        filter: Arc<F>,
+        tx: Sender<FilterProgress>,
+        batch_size: usize,
+        lines: &[usize],
+        cancel: CancelToken,
+    ) -> Result<()>
+    where
+        R: LogReader + Send + 'static + ?Sized,
+        F: Filter + 'static + ?Sized,
+    {
+        let batch_size = batch_size.max(100);
+        let end = lines.last().map_or(0, |&last| last + 1);
+
+        for batch in lines.rchunks(batch_size) {
+            if cancel.is_cancelled() {
+                return Ok(());
+            }
+
+            // Read the batch (brief lock)
+            let batch: Vec<(usize, String)> = {
+                let mut reader_guard = match reader.lock() {
+                    Ok(guard) => guard,
+                    Err(poisoned) => poisoned.into_inner(),
+                };
+                batch
+                    .iter()
+                    .filter_map(|&idx| Some((idx, reader_guard.get_line(idx).ok()??)))
+                    .collect()
+            };
+
+            let batch_matches: Vec<usize> = batch
+                .into_iter()
+                .filter(|(_, line)| filter.matches(line))
+                .map(|(idx, _)| idx)
+                .collect();
+
+            if !batch_matches.is_empty() {
+                let lines_processed = end - batch_matches[0];
+                if cancel.is_cancelled() {
+                    return Ok(());
+                }
+                let _ = tx.send(FilterProgress::PartialResults {
+                    matches: batch_matches,
+                    lines_processed,
+                });
+            }
+
+            std::thread::yield_now();
+        }
+
+        if cancel.is_cancelled() {
+            return Ok(());
+        }
+
+        tx.send(FilterProgress::Complete {
+            matches: vec![],
+            lines_processed: end,
+        })?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
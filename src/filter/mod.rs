@@ -3,6 +3,7 @@ pub mod cancel;
 pub mod engine;
 pub mod query;
 pub mod regex_filter;
+pub mod result_cache;
 pub mod search_engine;
 pub mod streaming_filter;
 pub mod string_filter;
@@ -12,38 +13,47 @@ pub trait Filter: Send + Sync {
     fn matches(&self, line: &str) -> bool;
 }
 
+use crate::index::flags::{detect_flags, Severity};
 use serde::{Deserialize, Serialize};
 
 /// Filter mode for switching between plain text, regex, and query filtering
+///
+/// `negate` inverts a plain/regex filter to keep the lines that do NOT match.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FilterMode {
-    Plain { case_sensitive: bool },
-    Regex { case_sensitive: bool },
+    Plain {
+        case_sensitive: bool,
+        #[serde(default)]
+        negate: bool,
+    },
+    Regex {
+        case_sensitive: bool,
+        #[serde(default)]
+        negate: bool,
+    },
     Query {},
 }
 
 impl Default for FilterMode {
     fn default() -> Self {
-        FilterMode::Plain {
-            case_sensitive: false,
-        }
+        FilterMode::plain()
     }
 }
 
 impl FilterMode {
     /// Create a new plain text filter mode (case-insensitive by default)
-    #[allow(dead_code)] // Public API for external use and tests
     pub fn plain() -> Self {
         FilterMode::Plain {
             case_sensitive: false,
+            negate: false,
         }
     }
 
     /// Create a new regex filter mode (case-insensitive by default)
-    #[allow(dead_code)] // Public API for external use and tests
     pub fn regex() -> Self {
         FilterMode::Regex {
             case_sensitive: false,
+            negate: false,
         }
     }
 
@@ -56,24 +66,39 @@ impl FilterMode {
     /// Cycle through filter modes: Plain → Regex → Query → Plain
     pub fn cycle_mode(&mut self) {
         *self = match *self {
-            FilterMode::Plain { case_sensitive } => FilterMode::Regex { case_sensitive },
-            FilterMode::Regex { .. } => FilterMode::Query {},
-            FilterMode::Query {} => FilterMode::Plain {
-                case_sensitive: false,
+            FilterMode::Plain {
+                case_sensitive,
+                negate,
+            } => FilterMode::Regex {
+                case_sensitive,
+                negate,
             },
+            FilterMode::Regex { .. } => FilterMode::Query {},
+            FilterMode::Query {} => FilterMode::plain(),
         };
     }
 
     /// Toggle case sensitivity within the current mode (no-op for Query)
     pub fn toggle_case_sensitivity(&mut self) {
         match self {
-            FilterMode::Plain { case_sensitive } | FilterMode::Regex { case_sensitive } => {
+            FilterMode::Plain { case_sensitive, .. } | FilterMode::Regex { case_sensitive, .. } => {
                 *case_sensitive = !*case_sensitive
             }
             FilterMode::Query {} => {}
         }
     }
 
+    /// Toggle inverse matching within the current mode (no-op for Query,
+    /// which negates with a leading `!` in the query text)
+    pub fn toggle_negate(&mut self) {
+        match self {
+            FilterMode::Plain { negate, .. } | FilterMode::Regex { negate, .. } => {
+                *negate = !*negate
+            }
+            FilterMode::Query {} => {}
+        }
+    }
+
     /// Check if current mode is regex
     pub fn is_regex(&self) -> bool {
         matches!(self, FilterMode::Regex { .. })
@@ -87,31 +112,165 @@ impl FilterMode {
     /// Check if current mode is case sensitive
     pub fn is_case_sensitive(&self) -> bool {
         match self {
-            FilterMode::Plain { case_sensitive } | FilterMode::Regex { case_sensitive } => {
+            FilterMode::Plain { case_sensitive, .. } | FilterMode::Regex { case_sensitive, .. } => {
                 *case_sensitive
             }
             FilterMode::Query {} => false,
         }
     }
 
-    /// Get display label for the filter prompt
-    pub fn prompt_label(&self) -> &'static str {
+    /// Check if current mode keeps the lines that do NOT match
+    pub fn is_negated(&self) -> bool {
+        match self {
+            FilterMode::Plain { negate, .. } | FilterMode::Regex { negate, .. } => *negate,
+            FilterMode::Query {} => false,
+        }
+    }
+
+    /// Resolve smart case against a pattern: a case-insensitive Plain or
+    /// Regex mode becomes case-sensitive when the pattern contains an
+    /// uppercase letter. Explicit case-sensitive modes and Query are unchanged.
+    pub fn with_smart_case(self, pattern: &str) -> Self {
         match self {
             FilterMode::Plain {
                 case_sensitive: false,
+                negate,
+            } if pattern_has_uppercase(pattern, false) => FilterMode::Plain {
+                case_sensitive: true,
+                negate,
+            },
+            FilterMode::Regex {
+                case_sensitive: false,
+                negate,
+            } if pattern_has_uppercase(pattern, true) => FilterMode::Regex {
+                case_sensitive: true,
+                negate,
+            },
+            other => other,
+        }
+    }
+
+    /// Get display label for the filter prompt, marking case-insensitive
+    /// Plain/Regex input as `[smart]` when smart case is enabled
+    pub fn prompt_label_with_smart_case(&self, smart_case: bool) -> String {
+        let base = match self {
+            FilterMode::Plain {
+                case_sensitive: false,
+                ..
+            } if smart_case => "Filter [smart]",
+            FilterMode::Regex {
+                case_sensitive: false,
+                ..
+            } if smart_case => "Regex [smart]",
+            _ => return self.prompt_label(),
+        };
+        self.with_negate_marker(base)
+    }
+
+    /// Get display label for the filter prompt
+    pub fn prompt_label(&self) -> String {
+        let base = match self {
+            FilterMode::Plain {
+                case_sensitive: false,
+                ..
             } => "Filter",
             FilterMode::Plain {
                 case_sensitive: true,
+                ..
             } => "Filter [Aa]",
             FilterMode::Regex {
                 case_sensitive: false,
+                ..
             } => "Regex",
             FilterMode::Regex {
                 case_sensitive: true,
+                ..
             } => "Regex [Aa]",
             FilterMode::Query {} => "Query",
+        };
+        self.with_negate_marker(base)
+    }
+
+    fn with_negate_marker(&self, label: &str) -> String {
+        if self.is_negated() {
+            format!("{} !", label)
+        } else {
+            label.to_string()
+        }
+    }
+}
+
+/// Wraps a filter to keep only matching lines at or above a severity.
+///
+/// Severity is detected from the text; with an index, `SearchEngine` also
+/// narrows the scan to lines whose indexed severity qualifies.
+pub struct MinSeverityFilter {
+    pub inner: std::sync::Arc<dyn Filter>,
+    pub min: Severity,
+}
+
+impl Filter for MinSeverityFilter {
+    fn matches(&self, line: &str) -> bool {
+        Severity::from_flags(detect_flags(line)).to_bits() >= self.min.to_bits()
+            && self.inner.matches(line)
+    }
+}
+
+/// Wraps a filter to drop matching lines of the hidden severities.
+///
+/// Severity is detected from the text, as for `MinSeverityFilter`; with an
+/// index, `SearchEngine` skips indexed lines of hidden severities unread.
+pub struct HiddenSeverityFilter {
+    pub inner: std::sync::Arc<dyn Filter>,
+    pub hidden: Vec<Severity>,
+}
+
+impl Filter for HiddenSeverityFilter {
+    fn matches(&self, line: &str) -> bool {
+        !self
+            .hidden
+            .contains(&Severity::from_flags(detect_flags(line)))
+            && self.inner.matches(line)
+    }
+}
+
+/// Wraps a filter to keep the lines it rejects (inverse filtering).
+pub struct NegatedFilter(pub std::sync::Arc<dyn Filter>);
+
+impl Filter for NegatedFilter {
+    fn matches(&self, line: &str) -> bool {
+        !self.0.matches(line)
+    }
+}
+
+/// Check whether a pattern contains an uppercase literal.
+///
+/// In regex patterns, escape sequences (`\S`, `\W`, `\P{Lu}`, ...) are
+/// syntax rather than literals and are skipped.
+fn pattern_has_uppercase(pattern: &str, is_regex: bool) -> bool {
+    if !is_regex {
+        return pattern.chars().any(char::is_uppercase);
+    }
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            // Unicode class: \p{Name} / \P{Name} / \pL
+            if let Some('p' | 'P') = chars.next() {
+                if chars.clone().next() == Some('{') {
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            break;
+                        }
+                    }
+                } else {
+                    chars.next();
+                }
+            }
+        } else if c.is_uppercase() {
+            return true;
         }
     }
+    false
 }
 
 /// A filter history entry that stores both the pattern and the mode
@@ -155,6 +314,7 @@ mod filter_history_entry_tests {
     fn test_entry_preserves_case_sensitivity() {
         let mode = FilterMode::Regex {
             case_sensitive: true,
+            negate: false,
         };
         let entry = FilterHistoryEntry::new("Error".to_string(), mode);
         assert!(entry.mode.is_case_sensitive());
@@ -187,12 +347,14 @@ mod filter_history_entry_tests {
             "error".to_string(),
             FilterMode::Plain {
                 case_sensitive: false,
+                negate: false,
             },
         );
         let entry2 = FilterHistoryEntry::new(
             "error".to_string(),
             FilterMode::Plain {
                 case_sensitive: true,
+                negate: false,
             },
         );
         assert!(!entry1.matches(&entry2));
@@ -265,6 +427,7 @@ mod filter_mode_tests {
     fn test_cycle_mode_preserves_case_plain_to_regex() {
         let mut mode = FilterMode::Plain {
             case_sensitive: true,
+            negate: false,
         };
         mode.cycle_mode();
         assert!(mode.is_regex());
@@ -275,6 +438,7 @@ mod filter_mode_tests {
     fn test_cycle_mode_regex_to_query_drops_case() {
         let mut mode = FilterMode::Regex {
             case_sensitive: true,
+            negate: false,
         };
         mode.cycle_mode();
         assert!(mode.is_query());
@@ -332,11 +496,13 @@ mod filter_mode_tests {
     fn test_prompt_label_plain() {
         let mode = FilterMode::Plain {
             case_sensitive: false,
+            negate: false,
         };
         assert_eq!(mode.prompt_label(), "Filter");
 
         let mode = FilterMode::Plain {
             case_sensitive: true,
+            negate: false,
         };
         assert_eq!(mode.prompt_label(), "Filter [Aa]");
     }
@@ -345,25 +511,130 @@ mod filter_mode_tests {
     fn test_prompt_label_regex() {
         let mode = FilterMode::Regex {
             case_sensitive: false,
+            negate: false,
         };
         assert_eq!(mode.prompt_label(), "Regex");
 
         let mode = FilterMode::Regex {
             case_sensitive: true,
+            negate: false,
         };
         assert_eq!(mode.prompt_label(), "Regex [Aa]");
     }
 
+    #[test]
+    fn test_prompt_label_negated() {
+        let mut mode = FilterMode::plain();
+        mode.toggle_negate();
+        assert_eq!(mode.prompt_label(), "Filter !");
+        assert_eq!(mode.prompt_label_with_smart_case(true), "Filter [smart] !");
+
+        mode.toggle_case_sensitivity();
+        mode.cycle_mode();
+        assert_eq!(mode.prompt_label(), "Regex [Aa] !");
+    }
+
+    #[test]
+    fn test_negate_persists_and_defaults_for_old_entries() {
+        let mut mode = FilterMode::regex();
+        mode.toggle_negate();
+        let json = serde_json::to_string(&mode).unwrap();
+        assert_eq!(serde_json::from_str::<FilterMode>(&json).unwrap(), mode);
+
+        let old: FilterMode = serde_json::from_str(r#"{"Plain":{"case_sensitive":true}}"#).unwrap();
+        assert!(!old.is_negated());
+    }
+
     #[test]
     fn test_prompt_label_query() {
         let mode = FilterMode::Query {};
         assert_eq!(mode.prompt_label(), "Query");
     }
 
+    #[test]
+    fn test_smart_case_uppercase_makes_sensitive() {
+        assert_eq!(
+            FilterMode::plain().with_smart_case("Error"),
+            FilterMode::Plain {
+                case_sensitive: true,
+                negate: false,
+            }
+        );
+        assert_eq!(
+            FilterMode::plain().with_smart_case("error"),
+            FilterMode::plain()
+        );
+        assert_eq!(
+            FilterMode::regex().with_smart_case("Timeout \\d+"),
+            FilterMode::Regex {
+                case_sensitive: true,
+                negate: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_smart_case_ignores_regex_escapes() {
+        assert_eq!(
+            FilterMode::regex().with_smart_case(r"\S+\W\D"),
+            FilterMode::regex()
+        );
+        assert_eq!(
+            FilterMode::regex().with_smart_case(r"\p{Lu}x\pL"),
+            FilterMode::regex()
+        );
+        // Escapes are literal in plain mode
+        assert_eq!(
+            FilterMode::plain().with_smart_case(r"\S"),
+            FilterMode::Plain {
+                case_sensitive: true,
+                negate: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_smart_case_keeps_explicit_and_query_modes() {
+        let sensitive = FilterMode::Plain {
+            case_sensitive: true,
+            negate: false,
+        };
+        assert_eq!(sensitive.with_smart_case("error"), sensitive);
+        assert_eq!(
+            FilterMode::query().with_smart_case("json | level == \"Error\""),
+            FilterMode::query()
+        );
+    }
+
+    #[test]
+    fn test_prompt_label_with_smart_case() {
+        assert_eq!(
+            FilterMode::plain().prompt_label_with_smart_case(true),
+            "Filter [smart]"
+        );
+        assert_eq!(
+            FilterMode::regex().prompt_label_with_smart_case(true),
+            "Regex [smart]"
+        );
+        assert_eq!(
+            FilterMode::Regex {
+                case_sensitive: true,
+                negate: false,
+            }
+            .prompt_label_with_smart_case(true),
+            "Regex [Aa]"
+        );
+        assert_eq!(
+            FilterMode::plain().prompt_label_with_smart_case(false),
+            "Filter"
+        );
+    }
+
     #[test]
     fn test_filter_mode_clone() {
         let mode1 = FilterMode::Regex {
             case_sensitive: true,
+            negate: false,
         };
         let mode2 = mode1;
         assert_eq!(mode1, mode2);
@@ -386,6 +657,7 @@ mod index_filter_integration_tests {
     use super::Filter;
     use crate::index::builder::IndexBuilder;
     use crate::index::reader::IndexReader;
+    use crate::reader::encoding::Encoding;
     use std::io::Write;
     use std::sync::Arc;
     use tempfile::tempdir;
@@ -459,6 +731,7 @@ mod index_filter_integration_tests {
         let rx_indexed = streaming_filter::run_streaming_filter_indexed(
             log_path.clone(),
             filter.clone(),
+            Encoding::Utf8,
             bitmap,
             CancelToken::new(),
         )
@@ -466,8 +739,13 @@ mod index_filter_integration_tests {
         let indexed_results = collect_matches(rx_indexed);
 
         // Run non-indexed filter
-        let rx_regular =
-            streaming_filter::run_streaming_filter(log_path, filter, CancelToken::new()).unwrap();
+        let rx_regular = streaming_filter::run_streaming_filter(
+            log_path,
+            filter,
+            Encoding::Utf8,
+            CancelToken::new(),
+        )
+        .unwrap();
         let regular_results = collect_matches(rx_regular);
 
         // Results must be identical
@@ -506,14 +784,20 @@ mod index_filter_integration_tests {
         let rx_indexed = streaming_filter::run_streaming_filter_indexed(
             log_path.clone(),
             filter.clone(),
+            Encoding::Utf8,
             bitmap,
             CancelToken::new(),
         )
         .unwrap();
         let indexed_results = collect_matches(rx_indexed);
 
-        let rx_regular =
-            streaming_filter::run_streaming_filter(log_path, filter, CancelToken::new()).unwrap();
+        let rx_regular = streaming_filter::run_streaming_filter(
+            log_path,
+            filter,
+            Encoding::Utf8,
+            CancelToken::new(),
+        )
+        .unwrap();
         let regular_results = collect_matches(rx_regular);
 
         assert_eq!(indexed_results, regular_results);
@@ -549,14 +833,20 @@ mod index_filter_integration_tests {
         let rx_indexed = streaming_filter::run_streaming_filter_indexed(
             log_path.clone(),
             filter.clone(),
+            Encoding::Utf8,
             bitmap,
             CancelToken::new(),
         )
         .unwrap();
         let indexed_results = collect_matches(rx_indexed);
 
-        let rx_regular =
-            streaming_filter::run_streaming_filter(log_path, filter, CancelToken::new()).unwrap();
+        let rx_regular = streaming_filter::run_streaming_filter(
+            log_path,
+            filter,
+            Encoding::Utf8,
+            CancelToken::new(),
+        )
+        .unwrap();
         let regular_results = collect_matches(rx_regular);
 
         assert_eq!(indexed_results, regular_results);
@@ -592,14 +882,20 @@ mod index_filter_integration_tests {
         let rx_indexed = streaming_filter::run_streaming_filter_indexed(
             log_path.clone(),
             filter.clone(),
+            Encoding::Utf8,
             bitmap,
             CancelToken::new(),
         )
         .unwrap();
         let indexed_results = collect_matches(rx_indexed);
 
-        let rx_regular =
-            streaming_filter::run_streaming_filter(log_path, filter, CancelToken::new()).unwrap();
+        let rx_regular = streaming_filter::run_streaming_filter(
+            log_path,
+            filter,
+            Encoding::Utf8,
+            CancelToken::new(),
+        )
+        .unwrap();
         let regular_results = collect_matches(rx_regular);
 
         assert_eq!(indexed_results, regular_results);
@@ -635,14 +931,20 @@ mod index_filter_integration_tests {
         let rx_indexed = streaming_filter::run_streaming_filter_indexed(
             log_path.clone(),
             filter.clone(),
+            Encoding::Utf8,
             bitmap,
             CancelToken::new(),
         )
         .unwrap();
         let indexed_results = collect_matches(rx_indexed);
 
-        let rx_regular =
-            streaming_filter::run_streaming_filter(log_path, filter, CancelToken::new()).unwrap();
+        let rx_regular = streaming_filter::run_streaming_filter(
+            log_path,
+            filter,
+            Encoding::Utf8,
+            CancelToken::new(),
+        )
+        .unwrap();
         let regular_results = collect_matches(rx_regular);
 
         assert_eq!(indexed_results, regular_results);
@@ -673,8 +975,13 @@ mod index_filter_integration_tests {
         let query_filter = QueryFilter::new(filter_query).unwrap();
         let filter: Arc<dyn Filter> = Arc::new(query_filter);
 
-        let rx =
-            streaming_filter::run_streaming_filter(log_path, filter, CancelToken::new()).unwrap();
+        let rx = streaming_filter::run_streaming_filter(
+            log_path,
+            filter,
+            Encoding::Utf8,
+            CancelToken::new(),
+        )
+        .unwrap();
         let results = collect_matches(rx);
         assert_eq!(results, vec![0]);
     }
@@ -713,14 +1020,20 @@ mod index_filter_integration_tests {
         let rx_indexed = streaming_filter::run_streaming_filter_indexed(
             log_path.clone(),
             filter.clone(),
+            Encoding::Utf8,
             bitmap,
             CancelToken::new(),
         )
         .unwrap();
         let indexed_results = collect_matches(rx_indexed);
 
-        let rx_regular =
-            streaming_filter::run_streaming_filter(log_path, filter, CancelToken::new()).unwrap();
+        let rx_regular = streaming_filter::run_streaming_filter(
+            log_path,
+            filter,
+            Encoding::Utf8,
+            CancelToken::new(),
+        )
+        .unwrap();
         let regular_results = collect_matches(rx_regular);
 
         // Indexed results must be a superset of (or equal to) regular results.
@@ -768,14 +1081,20 @@ mod index_filter_integration_tests {
         let rx_indexed = streaming_filter::run_streaming_filter_indexed(
             log_path.clone(),
             filter.clone(),
+            Encoding::Utf8,
             bitmap,
             CancelToken::new(),
         )
         .unwrap();
         let indexed_results = collect_matches(rx_indexed);
 
-        let rx_regular =
-            streaming_filter::run_streaming_filter(log_path, filter, CancelToken::new()).unwrap();
+        let rx_regular = streaming_filter::run_streaming_filter(
+            log_path,
+            filter,
+            Encoding::Utf8,
+            CancelToken::new(),
+        )
+        .unwrap();
         let regular_results = collect_matches(rx_regular);
 
         // Indexed filter falls back to checking lines past bitmap
@@ -811,6 +1130,7 @@ mod index_filter_integration_tests {
         let rx = streaming_filter::run_streaming_filter_indexed(
             log_path,
             filter,
+            Encoding::Utf8,
             bitmap,
             CancelToken::new(),
         )
@@ -820,4 +1140,35 @@ mod index_filter_integration_tests {
 
         let _ = std::fs::remove_dir_all(&idx_dir);
     }
+
+    #[test]
+    fn test_indexed_severity_check_skips_hidden_lines() {
+        use super::search_engine::SearchEngine;
+        use super::string_filter::StringFilter;
+        use crate::index::flags::Severity;
+
+        let dir = tempdir().unwrap();
+        let lines = &["ERROR one", "DEBUG two", "INFO three", "DEBUG four"];
+        let log_path = write_log_file(dir.path(), "test.log", lines);
+        let idx_dir = build_index(&log_path);
+        let reader = IndexReader::open(&log_path).unwrap();
+
+        // The filter matches every line: only the index drops debug lines
+        let filter: Arc<dyn Filter> = Arc::new(StringFilter::new("", false));
+        let keep = |severity| severity != Severity::Debug;
+        let rx = SearchEngine::search_file(
+            &log_path,
+            filter,
+            Encoding::Utf8,
+            None,
+            Some(&reader),
+            Some(&keep),
+            None,
+            CancelToken::new(),
+        )
+        .unwrap();
+        assert_eq!(collect_matches(rx), vec![0, 2]);
+
+        let _ = std::fs::remove_dir_all(&idx_dir);
+    }
 }
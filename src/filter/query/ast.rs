@@ -4,10 +4,10 @@
 //! the JSON/MCP query interface, and the filter implementation.
 
 use schemars::JsonSchema;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Parser for extracting fields from log lines.
-#[derive(Debug, Clone, Default, Deserialize, JsonSchema, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum Parser {
     /// Plain text, no field extraction. Field filters will not match.
@@ -20,7 +20,7 @@ pub enum Parser {
 }
 
 /// Comparison operators for field filtering.
-#[derive(Debug, Clone, Deserialize, JsonSchema, PartialEq, Eq)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum Operator {
     /// Equality (==)
@@ -41,21 +41,29 @@ pub enum Operator {
     Gte,
     /// Less than or equal (<=)
     Lte,
+    /// Field is present (`has field`); the value is ignored
+    Exists,
+    /// Field is absent (`!has field`); the value is ignored
+    NotExists,
 }
 
+/// Virtual field holding the line length in characters (`len` in text queries).
+pub const LEN_FIELD: &str = "@len";
+
 /// A single field filter condition.
-#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct FieldFilter {
     /// Field name to extract from the parsed log line.
     pub field: String,
     /// Comparison operator.
     pub op: Operator,
-    /// Value to compare against.
+    /// Value to compare against (unused by `exists` / `not_exists`).
+    #[serde(default)]
     pub value: String,
 }
 
 /// Exclusion pattern (negative filter).
-#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct ExcludePattern {
     /// Field name to check for exclusion.
     pub field: String,
@@ -64,7 +72,7 @@ pub struct ExcludePattern {
 }
 
 /// Aggregation type for grouped results.
-#[derive(Debug, Clone, Deserialize, JsonSchema, PartialEq, Eq)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum AggregationType {
     /// Count lines grouped by field values.
@@ -72,7 +80,7 @@ pub enum AggregationType {
 }
 
 /// Aggregation clause for grouped query results.
-#[derive(Debug, Clone, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct Aggregation {
     /// Type of aggregation to perform (used by serde for deserialization dispatch).
     #[serde(rename = "type")]
@@ -81,12 +89,12 @@ pub struct Aggregation {
     /// Fields to group by.
     pub fields: Vec<String>,
     /// Optional limit on number of groups returned (top N).
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub limit: Option<usize>,
 }
 
 /// Complete query definition for structured log filtering.
-#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema)]
 pub struct FilterQuery {
     /// Parser to use for extracting fields from log lines.
     #[serde(default)]
@@ -98,16 +106,20 @@ pub struct FilterQuery {
 
     /// Virtual `@ts` filters on the index ingestion timestamp.
     /// Separated from content filters — evaluated as a bitmap at the scan level.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub ts_filters: Vec<FieldFilter>,
 
     /// Exclusion patterns (any match excludes the line).
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub exclude: Vec<ExcludePattern>,
 
     /// Optional aggregation clause for grouped results.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub aggregate: Option<Aggregation>,
+
+    /// Keep the lines the query does NOT match (text query prefix `!`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub negate: bool,
 }
 
 impl FilterQuery {
@@ -138,11 +150,19 @@ impl FilterQuery {
     /// - Format flag (JSON or logfmt) from the parser type
     /// - Empty-line exclusion
     /// - Severity level from `level == "value"` filters (exact match only)
+    ///
+    /// `len` and `has` / `!has` filters are not in the index; they are
+    /// evaluated per line and never narrow the mask.
     pub fn index_mask(&self) -> Option<(u32, u32)> {
         use crate::index::flags::{
             FLAG_FORMAT_JSON, FLAG_FORMAT_LOGFMT, FLAG_IS_EMPTY, SEVERITY_MASK,
         };
 
+        // Non-matching lines can have any flags
+        if self.negate {
+            return None;
+        }
+
         let mut mask = 0u32;
         let mut want = 0u32;
 
@@ -34,6 +34,12 @@ impl QueryFilter {
         let mut resolved_times = Vec::with_capacity(query.filters.len());
 
         for filter in &query.filters {
+            if filter.field == LEN_FIELD
+                && !matches!(filter.op, Operator::Exists | Operator::NotExists)
+                && filter.value.parse::<usize>().is_err()
+            {
+                return Err(format!("len expects a number, got '{}'", filter.value));
+            }
             match filter.op {
                 Operator::Regex => {
                     let regex = Regex::new(&filter.value)
@@ -53,10 +59,13 @@ impl QueryFilter {
                     filter_regexes.push(None);
                     not_regex_patterns.push(None);
                     // Resolve time expressions: relative (e.g., "now-5m") or absolute timestamps
-                    resolved_times.push(
+                    let resolved = if filter.field == LEN_FIELD {
+                        None
+                    } else {
                         time::resolve_relative_time(&filter.value)
-                            .or_else(|| time::parse_timestamp(&filter.value)),
-                    );
+                            .or_else(|| time::parse_timestamp(&filter.value))
+                    };
+                    resolved_times.push(resolved);
                 }
                 _ => {
                     filter_regexes.push(None);
@@ -89,6 +98,9 @@ impl QueryFilter {
             Operator::Contains => field_value.contains(&filter.value),
             Operator::Regex => filter_regex.is_some_and(|r| r.is_match(field_value)),
             Operator::NotRegex => not_regex.is_none_or(|r| !r.is_match(field_value)),
+            // Presence is all that matters; a missing field never gets here
+            Operator::Exists => true,
+            Operator::NotExists => false,
             Operator::Gt | Operator::Lt | Operator::Gte | Operator::Lte => {
                 let ordering = if let Some(threshold) = resolved_time {
                     // Time-aware comparison: parse field value as timestamp
@@ -143,13 +155,54 @@ impl QueryFilter {
     }
 }
 
+impl QueryFilter {
+    /// Check every filter (AND logic) against one line.
+    ///
+    /// `lookup` extracts a structured field; the virtual `@len` field is
+    /// answered from the line itself.
+    fn matches_filters(&self, line: &str, lookup: impl Fn(&str) -> Option<String>) -> bool {
+        for (i, filter) in self.query.filters.iter().enumerate() {
+            let field_value = if filter.field == LEN_FIELD {
+                Some(line.chars().count().to_string())
+            } else {
+                lookup(&filter.field)
+            };
+            let Some(field_value) = field_value else {
+                // Missing field = no match, except for `!has`
+                if filter.op == Operator::NotExists {
+                    continue;
+                }
+                return false;
+            };
+
+            let filter_regex = self.filter_regexes.get(i).and_then(|r| r.as_ref());
+            let not_regex = self.not_regex_patterns.get(i).and_then(|r| r.as_ref());
+            let resolved_time = self.resolved_times.get(i).and_then(|t| *t);
+
+            if !self.matches_filter(&field_value, filter, filter_regex, not_regex, resolved_time) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
 impl Filter for QueryFilter {
     fn matches(&self, line: &str) -> bool {
+        self.matches_query(line) != self.query.negate
+    }
+}
+
+impl QueryFilter {
+    /// Whether the line satisfies the query, ignoring `negate`.
+    fn matches_query(&self, line: &str) -> bool {
         match self.query.parser {
             Parser::Raw => {
-                // Raw parser: no field extraction, filters don't apply
-                // Only return true if there are no filters and no exclusions
-                self.query.filters.is_empty() && self.query.exclude.is_empty()
+                // Raw parser: no field extraction, so only line-level filters
+                // (`len`) can be evaluated
+                self.query.exclude.is_empty()
+                    && self.query.filters.iter().all(|f| f.field == LEN_FIELD)
+                    && self.matches_filters(line, |_| None)
             }
             Parser::Json => {
                 // Parse line as JSON
@@ -163,29 +216,7 @@ impl Filter for QueryFilter {
                     return false;
                 }
 
-                // All filters must match (AND logic)
-                for (i, filter) in self.query.filters.iter().enumerate() {
-                    let field_value = match extract_json_field(&json, &filter.field) {
-                        Some(v) => v,
-                        None => return false, // Missing field = no match
-                    };
-
-                    let filter_regex = self.filter_regexes.get(i).and_then(|r| r.as_ref());
-                    let not_regex = self.not_regex_patterns.get(i).and_then(|r| r.as_ref());
-                    let resolved_time = self.resolved_times.get(i).and_then(|t| *t);
-
-                    if !self.matches_filter(
-                        &field_value,
-                        filter,
-                        filter_regex,
-                        not_regex,
-                        resolved_time,
-                    ) {
-                        return false;
-                    }
-                }
-
-                true
+                self.matches_filters(line, |field| extract_json_field(&json, field))
             }
             Parser::Logfmt => {
                 // Parse line as logfmt
@@ -196,31 +227,9 @@ impl Filter for QueryFilter {
                     return false;
                 }
 
-                // All filters must match (AND logic)
-                for (i, filter) in self.query.filters.iter().enumerate() {
-                    // For logfmt, nested fields use the full field name as key
-                    // (logfmt doesn't have native nesting)
-                    let field_value = match fields.get(&filter.field) {
-                        Some(v) => v.clone(),
-                        None => return false, // Missing field = no match
-                    };
-
-                    let filter_regex = self.filter_regexes.get(i).and_then(|r| r.as_ref());
-                    let not_regex = self.not_regex_patterns.get(i).and_then(|r| r.as_ref());
-                    let resolved_time = self.resolved_times.get(i).and_then(|t| *t);
-
-                    if !self.matches_filter(
-                        &field_value,
-                        filter,
-                        filter_regex,
-                        not_regex,
-                        resolved_time,
-                    ) {
-                        return false;
-                    }
-                }
-
-                true
+                // For logfmt, nested fields use the full field name as key
+                // (logfmt doesn't have native nesting)
+                self.matches_filters(line, |field| fields.get(field).cloned())
             }
         }
     }
@@ -26,14 +26,14 @@ mod parser;
 pub(crate) mod time;
 
 // Re-export public types used outside this module
-pub use ast::{Aggregation, FilterQuery, Parser};
+pub use ast::{Aggregation, AggregationType, FilterQuery, Parser};
 pub use filter::QueryFilter;
-pub use parser::parse_query;
+pub use parser::{parse_query, query_text};
 pub use time::TsBounds;
 
 // Re-export types only used in tests
 #[cfg(test)]
-pub use ast::{AggregationType, ExcludePattern, FieldFilter, Operator};
+pub use ast::{ExcludePattern, FieldFilter, Operator, LEN_FIELD};
 
 // Re-export from shared parsing module
 pub use crate::parsing::{extract_json_field, parse_logfmt};
@@ -148,6 +148,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -169,6 +170,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -190,6 +192,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -211,6 +214,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -232,6 +236,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -252,6 +257,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -273,6 +279,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -295,6 +302,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -316,6 +324,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -344,6 +353,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -369,6 +379,7 @@ mod tests {
                 pattern: "ignore".to_string(),
             }],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -385,6 +396,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -406,6 +418,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -427,6 +440,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -448,6 +462,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let result = QueryFilter::new(query);
@@ -467,6 +482,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -487,6 +503,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -507,6 +524,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -530,6 +548,23 @@ mod tests {
         assert_eq!(query.filters[0].value, "error");
     }
 
+    #[test]
+    fn test_query_text_quotes_values_and_rejects_what_text_cannot_say() {
+        let query = parse_query(r#"json | msg == "say \"hi\"\n" | has user.id"#).unwrap();
+        let text = query_text(&query).unwrap();
+        assert_eq!(text, r#"json | msg == "say \"hi\"\n" | has user.id"#);
+        assert_eq!(parse_query(&text).unwrap().filters[0].value, "say \"hi\"\n");
+
+        let mut contains = query.clone();
+        contains.filters[0].op = Operator::Contains;
+        assert!(query_text(&contains).is_err());
+
+        let mut raw = query;
+        raw.parser = Parser::Raw;
+        assert!(query_text(&raw).is_err());
+        assert!(query_text(&FilterQuery::default()).is_err());
+    }
+
     #[test]
     fn test_parse_json_only() {
         let query = parse_query("json").unwrap();
@@ -723,6 +758,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -744,6 +780,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -782,6 +819,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -824,6 +862,7 @@ mod tests {
                 pattern: "ignore".to_string(),
             }],
             aggregate: None,
+            negate: false,
         };
 
         let filter = QueryFilter::new(query).unwrap();
@@ -840,6 +879,127 @@ mod tests {
     // index_mask() Tests
     // ========================================================================
 
+    #[test]
+    fn test_parse_len_and_presence() {
+        let query = parse_query("json | has trace_id | !has span | @len > 2000").unwrap();
+        assert_eq!(query.parser, Parser::Json);
+        assert_eq!(query.filters.len(), 3);
+        assert_eq!(query.filters[0].field, "trace_id");
+        assert_eq!(query.filters[0].op, Operator::Exists);
+        assert_eq!(query.filters[1].field, "span");
+        assert_eq!(query.filters[1].op, Operator::NotExists);
+        assert_eq!(query.filters[2].field, LEN_FIELD);
+        assert_eq!(query.filters[2].op, Operator::Gt);
+        assert_eq!(query.filters[2].value, "2000");
+
+        // A field literally called "has" still works
+        let query = parse_query("json | has == yes").unwrap();
+        assert_eq!(query.filters[0].field, "has");
+        assert_eq!(query.filters[0].op, Operator::Eq);
+    }
+
+    #[test]
+    fn test_parse_len_without_parser() {
+        let query = parse_query("len >= 10").unwrap();
+        assert_eq!(query.parser, Parser::Raw);
+        assert_eq!(query.filters[0].field, LEN_FIELD);
+
+        let query = parse_query("len>10 | json | level == error").unwrap();
+        assert_eq!(query.parser, Parser::Json);
+        assert_eq!(query.filters.len(), 2);
+    }
+
+    #[test]
+    fn test_query_filter_len() {
+        let filter = QueryFilter::new(parse_query("len > 5").unwrap()).unwrap();
+        assert!(filter.matches("plain text line"));
+        assert!(!filter.matches("short"));
+        // Characters, not bytes
+        assert!(!filter.matches("żółć"));
+
+        let filter = QueryFilter::new(parse_query("json | @len < 20").unwrap()).unwrap();
+        assert!(filter.matches(r#"{"a":1}"#));
+        assert!(!filter.matches(r#"{"message":"a much longer line"}"#));
+        assert!(!filter.matches("not json"));
+
+        assert!(QueryFilter::new(parse_query("len > big").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_len_after_a_parser_is_a_field() {
+        let query = parse_query("json | len == 3").unwrap();
+        assert_eq!(query.filters[0].field, "len");
+
+        let filter = QueryFilter::new(query).unwrap();
+        assert!(filter.matches(r#"{"len":3,"padding":"makes the line long"}"#));
+        assert!(!filter.matches(r#"{"len":4}"#));
+        assert!(!filter.matches(r#"{"size":3}"#));
+    }
+
+    #[test]
+    fn test_query_filter_presence() {
+        let has = QueryFilter::new(parse_query("json | has trace_id").unwrap()).unwrap();
+        let missing = QueryFilter::new(parse_query("json | !has trace_id").unwrap()).unwrap();
+        let with_id = r#"{"msg":"a","trace_id":"abc"}"#;
+        let without_id = r#"{"msg":"b"}"#;
+        assert!(has.matches(with_id));
+        assert!(!has.matches(without_id));
+        assert!(!missing.matches(with_id));
+        assert!(missing.matches(without_id));
+
+        let missing = QueryFilter::new(parse_query("logfmt | !has user").unwrap()).unwrap();
+        assert!(missing.matches("level=info msg=hi"));
+        assert!(!missing.matches("level=info user=bob"));
+
+        // Presence needs a parser
+        let raw = QueryFilter::new(FilterQuery {
+            filters: vec![FieldFilter {
+                field: "trace_id".to_string(),
+                op: Operator::NotExists,
+                value: String::new(),
+            }],
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(!raw.matches("anything"));
+    }
+
+    #[test]
+    fn test_negated_query_inverts_whole_match() {
+        let query = parse_query(r#"! json | level == "error""#).unwrap();
+        assert!(query.negate);
+        assert!(query.index_mask().is_none());
+
+        let filter = QueryFilter::new(query).unwrap();
+        assert!(!filter.matches(r#"{"level": "error"}"#));
+        assert!(filter.matches(r#"{"level": "info"}"#));
+        assert!(filter.matches("not json"));
+
+        assert!(parse_query("!@ts >= now-5m").is_err());
+        assert!(!parse_query("json | !has user").unwrap().negate);
+    }
+
+    #[test]
+    fn test_presence_deserialize_without_value() {
+        let query: FilterQuery = serde_json::from_str(
+            r#"{"parser": "json", "filters": [{"field": "trace_id", "op": "not_exists"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(query.filters[0].op, Operator::NotExists);
+        assert!(query.filters[0].value.is_empty());
+    }
+
+    #[test]
+    fn test_index_mask_ignores_len_and_presence() {
+        use crate::index::flags::*;
+
+        let query = parse_query("json | !has level | @len > 2000").unwrap();
+        let (mask, want) = query.index_mask().unwrap();
+        assert_eq!(mask & SEVERITY_MASK, 0);
+        assert_eq!(want, FLAG_FORMAT_JSON);
+        assert!(parse_query("len > 2000").unwrap().index_mask().is_none());
+    }
+
     #[test]
     fn test_index_mask_json_level_error() {
         use crate::index::flags::*;
@@ -902,6 +1062,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
         assert!(query.index_mask().is_none());
     }
@@ -934,6 +1095,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
         let (_, want) = query.index_mask().unwrap();
         assert_eq!(want & SEVERITY_MASK, SEVERITY_ERROR);
@@ -949,6 +1111,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
         let (_, want) = query.index_mask().unwrap();
         assert_eq!(want & SEVERITY_MASK, SEVERITY_WARN);
@@ -964,6 +1127,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
         let (_, want) = query.index_mask().unwrap();
         assert_eq!(want & SEVERITY_MASK, SEVERITY_FATAL);
@@ -984,6 +1148,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
         let (mask, _want) = query.index_mask().unwrap();
         // No severity constraint since we can't map "notice"
@@ -1015,6 +1180,19 @@ mod tests {
         assert_eq!(agg.fields, vec!["service", "level"]);
     }
 
+    #[test]
+    fn test_parse_count_by_without_parens() {
+        let query = parse_query("json | count by service, level").unwrap();
+        assert_eq!(query.aggregate.unwrap().fields, vec!["service", "level"]);
+
+        let query = parse_query("json | count by service,level , host | top 3").unwrap();
+        let agg = query.aggregate.unwrap();
+        assert_eq!(agg.fields, vec!["service", "level", "host"]);
+        assert_eq!(agg.limit, Some(3));
+
+        assert!(parse_query("json | count by service,").is_err());
+    }
+
     #[test]
     fn test_parse_count_by_with_top() {
         let query = parse_query("json | count by (level) | top 5").unwrap();
@@ -1117,6 +1295,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
         let filter = QueryFilter::new(query).unwrap();
         assert!(filter.matches(&line));
@@ -1132,6 +1311,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
         let filter2 = QueryFilter::new(query2).unwrap();
         assert!(!filter2.matches(&line));
@@ -1153,6 +1333,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
         let filter = QueryFilter::new(query).unwrap();
         assert!(filter.matches(line));
@@ -1175,6 +1356,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
         let filter = QueryFilter::new(query).unwrap();
         assert!(filter.matches(line));
@@ -1222,6 +1404,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
         let filter = QueryFilter::new(query).unwrap();
 
@@ -1249,6 +1432,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
         let filter = QueryFilter::new(query).unwrap();
         assert!(filter.matches(&line));
@@ -1275,6 +1459,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
         let filter = QueryFilter::new(query).unwrap();
         assert!(filter.matches(&line));
@@ -1323,6 +1508,7 @@ mod tests {
             ts_filters: vec![],
             exclude: vec![],
             aggregate: None,
+            negate: false,
         };
         let filter = QueryFilter::new(query).unwrap();
         assert!(!filter.matches(line));
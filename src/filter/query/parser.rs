@@ -1,7 +1,7 @@
 //! Text query parser for LogQL-like query syntax.
 //!
 //! Parses human-readable query strings like `json | level == "error"` into
-//! the `FilterQuery` AST for execution.
+//! the `FilterQuery` AST for execution, and writes an AST back as text.
 
 use super::ast::*;
 
@@ -25,11 +25,95 @@ impl std::fmt::Display for QueryParseError {
 /// parse_query("json | level == \"error\"")
 /// parse_query("json | status >= 400 | service =~ \"api.*\"")
 /// parse_query("logfmt | level == error")
+/// parse_query("json | !has trace_id | @len > 2000")
+/// parse_query("len > 2000 | json | level == error") // `len` before a parser is `@len`
+/// parse_query("!json | level == \"debug\"") // every line except debug ones
 /// ```
 pub fn parse_query(input: &str) -> Result<FilterQuery, QueryParseError> {
     QueryTextParser::new(input).parse()
 }
 
+/// Write `query` in text query syntax, so that [`parse_query`] reads it back.
+///
+/// Fails for queries the text syntax can't express: `contains` filters,
+/// exclusions, field filters without a parser, or nothing to filter on.
+pub fn query_text(query: &FilterQuery) -> Result<String, String> {
+    if !query.exclude.is_empty() {
+        return Err("Exclusions have no text query syntax".to_string());
+    }
+    let line_filter = |f: &FieldFilter| f.field == LEN_FIELD || f.field == "@ts";
+    let (line_filters, field_filters): (Vec<_>, Vec<_>) = query
+        .ts_filters
+        .iter()
+        .chain(query.filters.iter())
+        .partition(|f| line_filter(f));
+
+    let mut stages = line_filters
+        .into_iter()
+        .map(filter_text)
+        .collect::<Result<Vec<_>, _>>()?;
+    match query.parser {
+        Parser::Json => stages.push("json".to_string()),
+        Parser::Logfmt => stages.push("logfmt".to_string()),
+        Parser::Raw if !field_filters.is_empty() || query.aggregate.is_some() => {
+            return Err("Field filters need a json or logfmt parser".to_string());
+        }
+        Parser::Raw => {}
+    }
+    for filter in field_filters {
+        stages.push(filter_text(filter)?);
+    }
+    if let Some(aggregate) = &query.aggregate {
+        stages.push(format!("count by {}", aggregate.fields.join(", ")));
+        if let Some(limit) = aggregate.limit {
+            stages.push(format!("top {}", limit));
+        }
+    }
+    if stages.is_empty() {
+        return Err("Query has nothing to filter on".to_string());
+    }
+
+    let text = stages.join(" | ");
+    Ok(if query.negate {
+        format!("!{}", text)
+    } else {
+        text
+    })
+}
+
+/// One filter stage in text query syntax, its value always quoted.
+fn filter_text(filter: &FieldFilter) -> Result<String, String> {
+    let op = match filter.op {
+        Operator::Exists => return Ok(format!("has {}", filter.field)),
+        Operator::NotExists => return Ok(format!("!has {}", filter.field)),
+        Operator::Contains => {
+            return Err("contains filters have no text query syntax".to_string());
+        }
+        Operator::Eq => "==",
+        Operator::Ne => "!=",
+        Operator::Regex => "=~",
+        Operator::NotRegex => "!~",
+        Operator::Gt => ">",
+        Operator::Lt => "<",
+        Operator::Gte => ">=",
+        Operator::Lte => "<=",
+    };
+    let mut value = String::with_capacity(filter.value.len() + 2);
+    value.push('"');
+    for ch in filter.value.chars() {
+        match ch {
+            '"' => value.push_str("\\\""),
+            '\\' => value.push_str("\\\\"),
+            '\n' => value.push_str("\\n"),
+            '\t' => value.push_str("\\t"),
+            '\r' => value.push_str("\\r"),
+            ch => value.push(ch),
+        }
+    }
+    value.push('"');
+    Ok(format!("{} {} {}", filter.field, op, value))
+}
+
 /// Text parser for LogQL-like query syntax.
 struct QueryTextParser<'a> {
     input: &'a str,
@@ -44,9 +128,14 @@ impl<'a> QueryTextParser<'a> {
     fn parse(&mut self) -> Result<FilterQuery, QueryParseError> {
         self.skip_whitespace();
 
-        // Parser type is optional: queries can start with @ts filters.
-        // e.g., "@ts >= now-5m" or "@ts >= now-5m | json | level == error"
-        let mut parser = if self.peek_char() == Some('@') {
+        // A leading `!` inverts the whole query
+        let negate_pos = self.pos;
+        let negate = self.consume_char('!');
+        self.skip_whitespace();
+
+        // Parser type is optional: queries can start with @ts or len filters.
+        // e.g., "@ts >= now-5m", "len > 2000" or "@ts >= now-5m | json | level == error"
+        let mut parser = if self.peek_line_filter() {
             Parser::Raw
         } else {
             self.parse_parser()?
@@ -57,13 +146,13 @@ impl<'a> QueryTextParser<'a> {
         let mut ts_filters = Vec::new();
         let mut aggregate = None;
 
-        // If parser was omitted (starts with @), parse the first filter directly
-        let started_with_ts = parser == Parser::Raw && self.peek_char() == Some('@');
+        // If parser was omitted (starts with @ts or len), parse the first filter directly
+        let started_without_parser = parser == Parser::Raw && self.peek_line_filter();
 
         self.skip_whitespace();
         while self.pos < self.input.len() {
-            // Skip | separator for the very first @ts filter (no parser prefix)
-            if (!started_with_ts || !filters.is_empty() || !ts_filters.is_empty())
+            // Skip | separator for the very first filter when there is no parser prefix
+            if (!started_without_parser || !filters.is_empty() || !ts_filters.is_empty())
                 && !self.consume_char('|')
             {
                 if self.pos < self.input.len() {
@@ -127,7 +216,7 @@ impl<'a> QueryTextParser<'a> {
 
             // Parse filter expression
             if self.pos < self.input.len() {
-                let filter = self.parse_filter()?;
+                let filter = self.parse_filter(parser == Parser::Raw)?;
                 if filter.field == "@ts" {
                     ts_filters.push(filter);
                 } else {
@@ -138,12 +227,21 @@ impl<'a> QueryTextParser<'a> {
             self.skip_whitespace();
         }
 
+        // @ts filters are applied as an index bitmap, outside the line match
+        if negate && !ts_filters.is_empty() {
+            return Err(QueryParseError {
+                message: "A negated query cannot use @ts filters".to_string(),
+                position: negate_pos,
+            });
+        }
+
         Ok(FilterQuery {
             parser,
             filters,
             ts_filters,
             exclude: vec![],
             aggregate,
+            negate,
         })
     }
 
@@ -160,9 +258,25 @@ impl<'a> QueryTextParser<'a> {
         }
     }
 
-    fn parse_filter(&mut self) -> Result<FieldFilter, QueryParseError> {
+    /// Parse one filter stage. Before a parser is declared (`before_parser`)
+    /// a bare `len` is the line length; after one it is a field like any
+    /// other, and the line length is `@len`.
+    fn parse_filter(&mut self, before_parser: bool) -> Result<FieldFilter, QueryParseError> {
+        // `has field` / `!has field` take no operator or value
+        if let Some(op) = self.parse_presence() {
+            let field = self.parse_field()?;
+            return Ok(FieldFilter {
+                field,
+                op,
+                value: String::new(),
+            });
+        }
+
         // Parse field name (may contain dots for nested access)
-        let field = self.parse_field()?;
+        let field = match self.parse_field()? {
+            field if field == "len" && before_parser => LEN_FIELD.to_string(),
+            field => field,
+        };
 
         self.skip_whitespace();
 
@@ -205,6 +319,45 @@ impl<'a> QueryTextParser<'a> {
         Ok(self.input[start..self.pos].to_string())
     }
 
+    /// Consume `has ` or `!has ` when followed by a field name.
+    ///
+    /// Otherwise nothing is consumed, so `has == 1` still filters a field
+    /// called `has`.
+    fn parse_presence(&mut self) -> Option<Operator> {
+        let saved_pos = self.pos;
+        let op = if self.consume_str("!has") {
+            Operator::NotExists
+        } else if self.consume_str("has") {
+            Operator::Exists
+        } else {
+            return None;
+        };
+        let separated = self.peek_char().is_some_and(char::is_whitespace);
+        self.skip_whitespace();
+        let field_follows = self
+            .peek_char()
+            .is_some_and(|ch| ch.is_alphanumeric() || ch == '_' || ch == '@');
+        if separated && field_follows {
+            Some(op)
+        } else {
+            self.pos = saved_pos;
+            None
+        }
+    }
+
+    /// Whether the next filter is a line-level one (`@ts ...` or `len ...`)
+    /// that needs no parser.
+    fn peek_line_filter(&self) -> bool {
+        let rest = &self.input[self.pos..];
+        rest.starts_with('@')
+            || rest.strip_prefix("len").is_some_and(|after| {
+                after
+                    .chars()
+                    .next()
+                    .is_some_and(|ch| ch.is_whitespace() || "=!<>".contains(ch))
+            })
+    }
+
     fn parse_operator(&mut self) -> Result<Operator, QueryParseError> {
         // Try two-character operators first
         if self.consume_str("==") {
@@ -374,7 +527,7 @@ impl<'a> QueryTextParser<'a> {
         false
     }
 
-    /// Parse `count by (field1, field2, ...)`.
+    /// Parse `count by (field1, field2, ...)`; the parentheses are optional.
     fn parse_count_by(&mut self) -> Result<Vec<String>, QueryParseError> {
         if !self.consume_word("count") {
             return Err(QueryParseError {
@@ -392,7 +545,23 @@ impl<'a> QueryTextParser<'a> {
         }
         self.skip_whitespace();
 
-        self.parse_field_list()
+        if self.peek_char() == Some('(') {
+            return self.parse_field_list();
+        }
+
+        // Bare list: `count by service, level`
+        let mut fields = vec![self.parse_field()?];
+        loop {
+            let saved_pos = self.pos;
+            self.skip_whitespace();
+            if !self.consume_char(',') {
+                self.pos = saved_pos;
+                break;
+            }
+            self.skip_whitespace();
+            fields.push(self.parse_field()?);
+        }
+        Ok(fields)
     }
 
     /// Parse a parenthesized, comma-separated list of field names.
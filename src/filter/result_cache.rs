@@ -0,0 +1,143 @@
+//! Recently computed filter results, so re-applying a filter is instant.
+//!
+//! Toggling a filter off and back on, or stepping through history to a
+//! recent pattern, would otherwise rescan the whole file. Results are only
+//! reused while the source has exactly the line count they were computed
+//! for; anything that rewrites content in place (truncation, rotation,
+//! encoding change) clears the cache.
+
+use super::FilterMode;
+use crate::index::flags::Severity;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// Most result sets kept per source
+const MAX_ENTRIES: usize = 8;
+
+/// Most line numbers kept per source across all entries (32 MB)
+const MAX_TOTAL_INDICES: usize = 4 * 1024 * 1024;
+
+/// Everything a full filter run's result depends on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultKey {
+    pub pattern: String,
+    pub mode: FilterMode,
+    pub min_severity: Option<Severity>,
+    pub hidden_severities: Vec<Severity>,
+    /// Since-marker the results were projected through
+    pub since_line: Option<usize>,
+    pub total_lines: usize,
+}
+
+/// Small LRU of filter results, most recently used first.
+#[derive(Debug, Default)]
+pub struct ResultCache {
+    entries: VecDeque<(ResultKey, Arc<Vec<usize>>)>,
+}
+
+impl ResultCache {
+    /// Matching lines for `key`, if cached. Entries for other line counts
+    /// are stale and dropped.
+    pub fn get(&mut self, key: &ResultKey) -> Option<Arc<Vec<usize>>> {
+        self.entries
+            .retain(|(k, _)| k.total_lines == key.total_lines);
+        let pos = self.entries.iter().position(|(k, _)| k == key)?;
+        let entry = self.entries.remove(pos)?;
+        let indices = entry.1.clone();
+        self.entries.push_front(entry);
+        Some(indices)
+    }
+
+    /// Whether a result set of `len` lines fits the memory budget.
+    pub fn fits(len: usize) -> bool {
+        len <= MAX_TOTAL_INDICES
+    }
+
+    /// Remember the result of a completed full run. Sets that don't
+    /// [`fit`](Self::fits) are not cached.
+    pub fn insert(&mut self, key: ResultKey, indices: Vec<usize>) {
+        if !Self::fits(indices.len()) {
+            return;
+        }
+        self.entries
+            .retain(|(k, _)| k.total_lines == key.total_lines && *k != key);
+        self.entries.push_front((key, Arc::new(indices)));
+
+        let mut total = 0;
+        let keep = self
+            .entries
+            .iter()
+            .take(MAX_ENTRIES)
+            .take_while(|(_, indices)| {
+                total += indices.len();
+                total <= MAX_TOTAL_INDICES
+            })
+            .count();
+        self.entries.truncate(keep);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[cfg(test)]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(pattern: &str, total_lines: usize) -> ResultKey {
+        ResultKey {
+            pattern: pattern.to_string(),
+            mode: FilterMode::plain(),
+            min_severity: None,
+            hidden_severities: Vec::new(),
+            since_line: None,
+            total_lines,
+        }
+    }
+
+    #[test]
+    fn test_hit_requires_same_line_count() {
+        let mut cache = ResultCache::default();
+        cache.insert(key("error", 10), vec![1, 4]);
+
+        assert_eq!(*cache.get(&key("error", 10)).unwrap(), vec![1, 4]);
+        assert!(cache.get(&key("warn", 10)).is_none());
+        assert!(cache.get(&key("error", 11)).is_none());
+        // The grown file made the old entry stale
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut cache = ResultCache::default();
+        for i in 0..MAX_ENTRIES {
+            cache.insert(key(&i.to_string(), 10), vec![i]);
+        }
+        cache.get(&key("0", 10)).unwrap();
+        cache.insert(key("new", 10), vec![9]);
+
+        assert_eq!(cache.len(), MAX_ENTRIES);
+        assert!(cache.get(&key("0", 10)).is_some());
+        assert!(cache.get(&key("1", 10)).is_none());
+    }
+
+    #[test]
+    fn test_mode_is_part_of_the_key() {
+        let mut cache = ResultCache::default();
+        cache.insert(key("error", 10), vec![1]);
+        let mut negated = key("error", 10);
+        negated.mode.toggle_negate();
+        assert!(cache.get(&negated).is_none());
+    }
+}
@@ -8,7 +8,9 @@ use super::cancel::CancelToken;
 use super::engine::{FilterEngine, FilterProgress};
 use super::{streaming_filter, Filter};
 use crate::index::column::ColumnReader;
+use crate::index::flags::Severity;
 use crate::index::reader::IndexReader;
+use crate::reader::encoding::Encoding;
 use crate::reader::LogReader;
 use crate::source::index_dir_for_log;
 use anyhow::Result;
@@ -30,14 +32,22 @@ impl SearchEngine {
     /// filter type, available index, and range.
     ///
     /// - `filter`: Pre-built filter (StringFilter, RegexFilter, or QueryFilter)
+    /// - `encoding`: How the file's lines are decoded before matching
     /// - `query`: Optional FilterQuery AST — needed for index_mask() acceleration
     /// - `index`: Optional IndexReader — needed for bitmap pre-filtering
+    /// - `keep_severity`: Optional severity check (floor, hidden levels) —
+    ///   with an index, only indexed lines whose severity passes it are
+    ///   scanned (the filter must still check lines past the index, e.g. via
+    ///   `MinSeverityFilter`)
     /// - `range`: Optional (start, end) for incremental filtering
+    #[allow(clippy::too_many_arguments)]
     pub fn search_file(
         path: &Path,
         filter: Arc<dyn Filter>,
+        encoding: Encoding,
         query: Option<&FilterQuery>,
         index: Option<&IndexReader>,
+        keep_severity: Option<&dyn Fn(Severity) -> bool>,
         range: Option<(usize, usize)>,
         cancel: CancelToken,
     ) -> Result<Receiver<FilterProgress>> {
@@ -80,6 +90,26 @@ impl SearchEngine {
             }
         }
 
+        // Severity filters: the indexed severity narrows the candidate set
+        if let (Some(keep), Some(reader)) = (keep_severity, index) {
+            let severity_bitmap: Vec<bool> = (0..reader.len())
+                .map(|i| {
+                    reader
+                        .flags(i)
+                        .is_some_and(|flags| keep(Severity::from_flags(flags)))
+                })
+                .collect();
+
+            bitmap = Some(match bitmap {
+                Some(existing) => existing
+                    .iter()
+                    .zip(severity_bitmap.iter())
+                    .map(|(&a, &b)| a && b)
+                    .collect(),
+                None => severity_bitmap,
+            });
+        }
+
         // Guard: if @ts filters were requested but bitmap wasn't created or is
         // empty (index has 0 entries), return an empty result set instead of
         // silently falling through to the unfiltered generic path.
@@ -104,6 +134,7 @@ impl SearchEngine {
             streaming_filter::run_streaming_filter_range(
                 path.to_path_buf(),
                 filter,
+                encoding,
                 start,
                 end,
                 start_byte_offset,
@@ -118,6 +149,7 @@ impl SearchEngine {
             streaming_filter::run_streaming_filter_range(
                 path.to_path_buf(),
                 filter,
+                encoding,
                 0,
                 end,
                 None,
@@ -129,17 +161,20 @@ impl SearchEngine {
             streaming_filter::run_streaming_filter_indexed(
                 path.to_path_buf(),
                 filter,
+                encoding,
                 bitmap,
                 cancel,
             )
         } else {
             // Generic full filter
-            streaming_filter::run_streaming_filter(path.to_path_buf(), filter, cancel)
+            streaming_filter::run_streaming_filter(path.to_path_buf(), filter, encoding, cancel)
         }
     }
 
     /// Fast path for plain text full-file search (SIMD).
     /// Bypasses Filter trait entirely for maximum performance.
+    ///
+    /// Matches raw bytes, so a non-ASCII pattern only finds UTF-8 text.
     pub fn search_file_fast(
         path: &Path,
         pattern: &[u8],
@@ -154,6 +189,26 @@ impl SearchEngine {
         )
     }
 
+    /// File-backed search limited to `lines` (ascending), plus every line
+    /// from `tail` on. Used to narrow a previous result set.
+    pub fn search_file_within(
+        path: &Path,
+        filter: Arc<dyn Filter>,
+        encoding: Encoding,
+        lines: Vec<usize>,
+        tail: usize,
+        cancel: CancelToken,
+    ) -> Result<Receiver<FilterProgress>> {
+        streaming_filter::run_streaming_filter_within(
+            path.to_path_buf(),
+            filter,
+            encoding,
+            lines,
+            tail,
+            cancel,
+        )
+    }
+
     /// Stdin/pipe path: uses FilterEngine with shared reader.
     pub fn search_reader(
         reader: Arc<Mutex<dyn LogReader + Send>>,
@@ -174,4 +229,14 @@ impl SearchEngine {
             FilterEngine::run_filter(reader, filter, FILTER_PROGRESS_INTERVAL, cancel)
         }
     }
+
+    /// Stdin/pipe search limited to the given lines (ascending).
+    pub fn search_reader_within(
+        reader: Arc<Mutex<dyn LogReader + Send>>,
+        filter: Arc<dyn Filter>,
+        lines: Vec<usize>,
+        cancel: CancelToken,
+    ) -> Receiver<FilterProgress> {
+        FilterEngine::run_filter_lines(reader, filter, FILTER_PROGRESS_INTERVAL, lines, cancel)
+    }
 }
@@ -7,6 +7,7 @@
 use super::cancel::CancelToken;
 use super::engine::FilterProgress;
 use super::Filter;
+use crate::reader::encoding::Encoding;
 use anyhow::Result;
 use memchr::memmem;
 use memmap2::Mmap;
@@ -22,6 +23,13 @@ const BATCH_SIZE: usize = 50_000;
 /// How often to check for cancellation (every N lines)
 const CANCEL_CHECK_INTERVAL: usize = 10_000;
 
+/// Whether a line's bytes match `filter` once decoded with `encoding`, the
+/// way the reader shows them (invalid UTF-8 becomes U+FFFD).
+#[inline]
+fn line_matches(filter: &dyn Filter, bytes: &[u8], encoding: Encoding) -> bool {
+    filter.matches(&encoding.decode(bytes))
+}
+
 /// Run a streaming filter on a file (grep-like performance)
 ///
 /// This is MUCH faster than the per-line reader approach because:
@@ -33,6 +41,7 @@ const CANCEL_CHECK_INTERVAL: usize = 10_000;
 pub fn run_streaming_filter<P>(
     path: P,
     filter: Arc<dyn Filter>,
+    encoding: Encoding,
     cancel: CancelToken,
 ) -> Result<Receiver<FilterProgress>>
 where
@@ -43,7 +52,7 @@ where
 
     thread::spawn(move || {
         let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            stream_filter_impl(&path, filter, tx.clone(), cancel)
+            stream_filter_impl(&path, filter, encoding, tx.clone(), cancel)
         }));
 
         match result {
@@ -66,6 +75,7 @@ where
 fn stream_filter_impl(
     path: &Path,
     filter: Arc<dyn Filter>,
+    encoding: Encoding,
     tx: Sender<FilterProgress>,
     cancel: CancelToken,
 ) -> Result<()> {
@@ -108,11 +118,8 @@ fn stream_filter_impl(
             line_end
         };
 
-        // Check if line matches (skip invalid UTF-8)
-        if let Ok(line) = std::str::from_utf8(&data[pos..content_end]) {
-            if filter.matches(line) {
-                batch_matches.push(line_idx);
-            }
+        if line_matches(filter.as_ref(), &data[pos..content_end], encoding) {
+            batch_matches.push(line_idx);
         }
 
         line_idx += 1;
@@ -376,9 +383,11 @@ fn stream_filter_fast_impl(
 ///
 /// If `bitmap` is provided, only lines where `bitmap[line_idx]` is true are checked
 /// with the content filter. Lines past the bitmap length are always checked.
+#[allow(clippy::too_many_arguments)]
 pub fn run_streaming_filter_range<P>(
     path: P,
     filter: Arc<dyn Filter>,
+    encoding: Encoding,
     start_line: usize,
     end_line: usize,
     start_byte_offset: Option<u64>,
@@ -396,6 +405,7 @@ where
             stream_filter_range_impl(
                 &path,
                 filter,
+                encoding,
                 start_line,
                 end_line,
                 start_byte_offset,
@@ -432,6 +442,7 @@ where
 fn stream_filter_range_impl(
     path: &Path,
     filter: Arc<dyn Filter>,
+    encoding: Encoding,
     start_line: usize,
     end_line: usize,
     start_byte_offset: Option<u64>,
@@ -498,10 +509,8 @@ fn stream_filter_range_impl(
                         line_end
                     };
 
-                if let Ok(line) = std::str::from_utf8(&data[pos..content_end]) {
-                    if filter.matches(line) {
-                        batch_matches.push(line_idx);
-                    }
+                if line_matches(filter.as_ref(), &data[pos..content_end], encoding) {
+                    batch_matches.push(line_idx);
                 }
             }
 
@@ -547,9 +556,54 @@ fn stream_filter_range_impl(
 pub fn run_streaming_filter_indexed<P>(
     path: P,
     filter: Arc<dyn Filter>,
+    encoding: Encoding,
     bitmap: Vec<bool>,
     cancel: CancelToken,
 ) -> Result<Receiver<FilterProgress>>
+where
+    P: AsRef<Path> + Send + 'static,
+{
+    let is_candidate = move |line_idx: usize| line_idx >= bitmap.len() || bitmap[line_idx];
+    run_streaming_filter_candidates(path, filter, encoding, is_candidate, cancel)
+}
+
+/// Run a filter over only `lines` (ascending) of a file, plus every line from
+/// `tail` on (lines appended since `lines` were found). Used to narrow a
+/// previous result set; memory is bounded by `lines`, not the file.
+pub fn run_streaming_filter_within<P>(
+    path: P,
+    filter: Arc<dyn Filter>,
+    encoding: Encoding,
+    lines: Vec<usize>,
+    tail: usize,
+    cancel: CancelToken,
+) -> Result<Receiver<FilterProgress>>
+where
+    P: AsRef<Path> + Send + 'static,
+{
+    // The scan visits lines in order, so a cursor into `lines` suffices
+    let mut next = 0;
+    let is_candidate = move |line_idx: usize| {
+        if line_idx >= tail {
+            return true;
+        }
+        while lines.get(next).is_some_and(|&line| line < line_idx) {
+            next += 1;
+        }
+        lines.get(next) == Some(&line_idx)
+    };
+    run_streaming_filter_candidates(path, filter, encoding, is_candidate, cancel)
+}
+
+/// Spawn a scan that only checks lines `is_candidate` accepts, called once
+/// per line in ascending order.
+fn run_streaming_filter_candidates<P>(
+    path: P,
+    filter: Arc<dyn Filter>,
+    encoding: Encoding,
+    is_candidate: impl FnMut(usize) -> bool + Send + 'static,
+    cancel: CancelToken,
+) -> Result<Receiver<FilterProgress>>
 where
     P: AsRef<Path> + Send + 'static,
 {
@@ -558,7 +612,7 @@ where
 
     thread::spawn(move || {
         let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            stream_filter_indexed_impl(&path, filter, &bitmap, tx.clone(), cancel)
+            stream_filter_indexed_impl(&path, filter, encoding, is_candidate, tx.clone(), cancel)
         }));
 
         match result {
@@ -580,12 +634,13 @@ where
 /// Internal implementation for index-accelerated filtering.
 ///
 /// Iterates lines sequentially (mmap + memchr), but only calls `filter.matches()`
-/// on lines where `bitmap[line_idx]` is true. Non-candidate lines are skipped
-/// with just a newline scan (no content parsing).
+/// on lines `is_candidate` accepts. Non-candidate lines are skipped with just
+/// a newline scan (no content parsing).
 fn stream_filter_indexed_impl(
     path: &Path,
     filter: Arc<dyn Filter>,
-    bitmap: &[bool],
+    encoding: Encoding,
+    mut is_candidate: impl FnMut(usize) -> bool,
     tx: Sender<FilterProgress>,
     cancel: CancelToken,
 ) -> Result<()> {
@@ -618,11 +673,7 @@ fn stream_filter_indexed_impl(
             .map(|offset| pos + offset)
             .unwrap_or(data.len());
 
-        // Check bitmap: if line is within bitmap and not a candidate, skip it.
-        // Lines past the bitmap are always checked (index may be shorter than file).
-        let is_candidate = line_idx >= bitmap.len() || bitmap[line_idx];
-
-        if is_candidate {
+        if is_candidate(line_idx) {
             let content_end =
                 if line_end > pos && data.get(line_end.saturating_sub(1)) == Some(&b'\r') {
                     line_end - 1
@@ -630,10 +681,8 @@ fn stream_filter_indexed_impl(
                     line_end
                 };
 
-            if let Ok(line) = std::str::from_utf8(&data[pos..content_end]) {
-                if filter.matches(line) {
-                    batch_matches.push(line_idx);
-                }
+            if line_matches(filter.as_ref(), &data[pos..content_end], encoding) {
+                batch_matches.push(line_idx);
             }
         }
 
@@ -707,7 +756,7 @@ mod tests {
         let path = file.path().to_path_buf();
 
         let filter: Arc<dyn Filter> = Arc::new(StringFilter::new("ERROR", false));
-        let rx = run_streaming_filter(path, filter, CancelToken::new()).unwrap();
+        let rx = run_streaming_filter(path, filter, Encoding::Utf8, CancelToken::new()).unwrap();
         let indices = collect_matches(rx);
         assert_eq!(indices, vec![0, 2, 4]);
     }
@@ -718,7 +767,7 @@ mod tests {
         let path = file.path().to_path_buf();
 
         let filter: Arc<dyn Filter> = Arc::new(StringFilter::new("ERROR", false));
-        let rx = run_streaming_filter(path, filter, CancelToken::new()).unwrap();
+        let rx = run_streaming_filter(path, filter, Encoding::Utf8, CancelToken::new()).unwrap();
         let indices = collect_matches(rx);
         assert!(indices.is_empty());
     }
@@ -729,7 +778,7 @@ mod tests {
         let path = file.path().to_path_buf();
 
         let filter: Arc<dyn Filter> = Arc::new(StringFilter::new("ERROR", false));
-        let rx = run_streaming_filter(path, filter, CancelToken::new()).unwrap();
+        let rx = run_streaming_filter(path, filter, Encoding::Utf8, CancelToken::new()).unwrap();
         let indices = collect_matches(rx);
         assert!(indices.is_empty());
     }
@@ -743,8 +792,17 @@ mod tests {
 
         let filter: Arc<dyn Filter> = Arc::new(StringFilter::new("ERROR", false));
         // Filter only lines 2-5 (no byte offset, no bitmap — scans from start)
-        let rx =
-            run_streaming_filter_range(path, filter, 2, 6, None, None, CancelToken::new()).unwrap();
+        let rx = run_streaming_filter_range(
+            path,
+            filter,
+            Encoding::Utf8,
+            2,
+            6,
+            None,
+            None,
+            CancelToken::new(),
+        )
+        .unwrap();
         let indices = collect_matches(rx);
         assert_eq!(indices, vec![3, 4]); // Lines 3 and 4 match within range 2-6
     }
@@ -767,6 +825,7 @@ mod tests {
         let rx = run_streaming_filter_range(
             path,
             filter,
+            Encoding::Utf8,
             2,
             6,
             start_byte_offset,
@@ -803,6 +862,7 @@ mod tests {
         let rx_scan = run_streaming_filter_range(
             path.clone(),
             filter.clone(),
+            Encoding::Utf8,
             3,
             7,
             None,
@@ -813,9 +873,17 @@ mod tests {
         let scan_result = collect_matches(rx_scan);
 
         // With byte offset (direct seek)
-        let rx_seek =
-            run_streaming_filter_range(path, filter, 3, 7, Some(offset), None, CancelToken::new())
-                .unwrap();
+        let rx_seek = run_streaming_filter_range(
+            path,
+            filter,
+            Encoding::Utf8,
+            3,
+            7,
+            Some(offset),
+            None,
+            CancelToken::new(),
+        )
+        .unwrap();
         let seek_result = collect_matches(rx_seek);
 
         assert_eq!(scan_result, seek_result);
@@ -840,9 +908,17 @@ mod tests {
         // Bitmap: lines 3 and 5 are candidates, line 4 is not
         let bitmap = vec![true, true, true, true, false, true, true];
 
-        let rx =
-            run_streaming_filter_range(path, filter, 2, 6, None, Some(bitmap), CancelToken::new())
-                .unwrap();
+        let rx = run_streaming_filter_range(
+            path,
+            filter,
+            Encoding::Utf8,
+            2,
+            6,
+            None,
+            Some(bitmap),
+            CancelToken::new(),
+        )
+        .unwrap();
         let result = collect_matches(rx);
 
         // Without bitmap: would match lines 3, 4. With bitmap: line 4 is skipped.
@@ -861,9 +937,17 @@ mod tests {
         // Bitmap covers lines 0-3 only; lines 4-5 are past the bitmap → always checked
         let bitmap = vec![true, false, false, false];
 
-        let rx =
-            run_streaming_filter_range(path, filter, 3, 6, None, Some(bitmap), CancelToken::new())
-                .unwrap();
+        let rx = run_streaming_filter_range(
+            path,
+            filter,
+            Encoding::Utf8,
+            3,
+            6,
+            None,
+            Some(bitmap),
+            CancelToken::new(),
+        )
+        .unwrap();
         let result = collect_matches(rx);
 
         // Line 3: bitmap[3]=false → skipped
@@ -877,7 +961,7 @@ mod tests {
         let path = file.path().to_path_buf();
 
         let filter: Arc<dyn Filter> = Arc::new(StringFilter::new("error", false));
-        let rx = run_streaming_filter(path, filter, CancelToken::new()).unwrap();
+        let rx = run_streaming_filter(path, filter, Encoding::Utf8, CancelToken::new()).unwrap();
         let indices = collect_matches(rx);
         assert_eq!(indices, vec![0, 1, 2]); // All match case-insensitively
     }
@@ -932,7 +1016,9 @@ mod tests {
         let bitmap = vec![true, false, false, true, false];
 
         let filter: Arc<dyn Filter> = Arc::new(StringFilter::new("error", false));
-        let rx = run_streaming_filter_indexed(path, filter, bitmap, CancelToken::new()).unwrap();
+        let rx =
+            run_streaming_filter_indexed(path, filter, Encoding::Utf8, bitmap, CancelToken::new())
+                .unwrap();
         let indices = collect_matches(rx);
         // Only line 0 and 3 were checked; line 0 and 3 contain "error"
         assert_eq!(indices, vec![0, 3]);
@@ -947,7 +1033,9 @@ mod tests {
         let bitmap = vec![true, true, true]; // All candidates
 
         let filter: Arc<dyn Filter> = Arc::new(StringFilter::new("ERROR", false));
-        let rx = run_streaming_filter_indexed(path, filter, bitmap, CancelToken::new()).unwrap();
+        let rx =
+            run_streaming_filter_indexed(path, filter, Encoding::Utf8, bitmap, CancelToken::new())
+                .unwrap();
         let indices = collect_matches(rx);
         assert_eq!(indices, vec![0, 2]);
     }
@@ -960,7 +1048,9 @@ mod tests {
         let bitmap = vec![false, false, false]; // No candidates
 
         let filter: Arc<dyn Filter> = Arc::new(StringFilter::new("ERROR", false));
-        let rx = run_streaming_filter_indexed(path, filter, bitmap, CancelToken::new()).unwrap();
+        let rx =
+            run_streaming_filter_indexed(path, filter, Encoding::Utf8, bitmap, CancelToken::new())
+                .unwrap();
         let indices = collect_matches(rx);
         assert!(indices.is_empty());
     }
@@ -974,13 +1064,36 @@ mod tests {
         let bitmap = vec![true, false]; // Only covers lines 0-1
 
         let filter: Arc<dyn Filter> = Arc::new(StringFilter::new("ERROR", false));
-        let rx = run_streaming_filter_indexed(path, filter, bitmap, CancelToken::new()).unwrap();
+        let rx =
+            run_streaming_filter_indexed(path, filter, Encoding::Utf8, bitmap, CancelToken::new())
+                .unwrap();
         let indices = collect_matches(rx);
         // Line 0 is candidate and matches, line 1 skipped,
         // lines 2,3 past bitmap so checked, both match
         assert_eq!(indices, vec![0, 2, 3]);
     }
 
+    #[test]
+    fn test_filter_within_checks_listed_lines_and_the_tail() {
+        let file = create_test_file(&[
+            "ERROR: 0", "ERROR: 1", "INFO: 2", "ERROR: 3", "ERROR: 4", "ERROR: 5",
+        ]);
+        let path = file.path().to_path_buf();
+
+        // Lines 0 and 2-3 were matches; 5 was appended since
+        let filter: Arc<dyn Filter> = Arc::new(StringFilter::new("ERROR", false));
+        let rx = run_streaming_filter_within(
+            path,
+            filter,
+            Encoding::Utf8,
+            vec![0, 2, 3],
+            5,
+            CancelToken::new(),
+        )
+        .unwrap();
+        assert_eq!(collect_matches(rx), vec![0, 3, 5]);
+    }
+
     #[test]
     fn test_indexed_filter_empty_bitmap() {
         // Empty bitmap = all lines are checked (no index available)
@@ -990,7 +1103,9 @@ mod tests {
         let bitmap = vec![]; // Empty
 
         let filter: Arc<dyn Filter> = Arc::new(StringFilter::new("ERROR", false));
-        let rx = run_streaming_filter_indexed(path, filter, bitmap, CancelToken::new()).unwrap();
+        let rx =
+            run_streaming_filter_indexed(path, filter, Encoding::Utf8, bitmap, CancelToken::new())
+                .unwrap();
         let indices = collect_matches(rx);
         assert_eq!(indices, vec![0, 2]);
     }
@@ -1002,7 +1117,9 @@ mod tests {
 
         let bitmap = vec![];
         let filter: Arc<dyn Filter> = Arc::new(StringFilter::new("ERROR", false));
-        let rx = run_streaming_filter_indexed(path, filter, bitmap, CancelToken::new()).unwrap();
+        let rx =
+            run_streaming_filter_indexed(path, filter, Encoding::Utf8, bitmap, CancelToken::new())
+                .unwrap();
         let indices = collect_matches(rx);
         assert!(indices.is_empty());
     }
@@ -1024,14 +1141,20 @@ mod tests {
         let filter: Arc<dyn Filter> = Arc::new(StringFilter::new("error", false));
 
         // Regular filter
-        let rx_regular =
-            run_streaming_filter(path.clone(), filter.clone(), CancelToken::new()).unwrap();
+        let rx_regular = run_streaming_filter(
+            path.clone(),
+            filter.clone(),
+            Encoding::Utf8,
+            CancelToken::new(),
+        )
+        .unwrap();
         let regular_result = collect_matches(rx_regular);
 
         // Indexed filter with all-true bitmap
         let bitmap = vec![true; lines.len()];
         let rx_indexed =
-            run_streaming_filter_indexed(path, filter, bitmap, CancelToken::new()).unwrap();
+            run_streaming_filter_indexed(path, filter, Encoding::Utf8, bitmap, CancelToken::new())
+                .unwrap();
         let indexed_result = collect_matches(rx_indexed);
 
         assert_eq!(regular_result, indexed_result);
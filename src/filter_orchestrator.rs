@@ -1,11 +1,16 @@
-use crate::app::FilterState;
+use crate::app::{FilterState, ViewMode};
 use crate::filter::cancel::CancelToken;
+use crate::filter::engine::FilterProgress;
+use crate::filter::result_cache::ResultKey;
 use crate::filter::search_engine::SearchEngine;
 use crate::filter::{
     query, regex_filter::RegexFilter, string_filter::StringFilter, Filter, FilterMode,
+    HiddenSeverityFilter, MinSeverityFilter, NegatedFilter,
 };
+use crate::index::flags::Severity;
 use crate::log_source::LogSource;
-use std::sync::Arc;
+use crate::reader::encoding::Encoding;
+use std::sync::{mpsc, Arc};
 
 /// Unified filter orchestration — consolidates all filter trigger paths
 /// (full/incremental, file/stdin, plain/regex/query) into one entry point.
@@ -33,6 +38,12 @@ impl FilterOrchestrator {
         if let Some(ref cancel) = source.filter.cancel_token {
             cancel.cancel();
         }
+        source.filter.filtering_to = Some(range.map_or(source.total_lines, |(_, end)| end));
+        // A full run replaces the results the key describes
+        let previous = match range {
+            None => source.filter.results_key.take(),
+            Some(_) => None,
+        };
 
         // Query mode: user explicitly selected via Tab cycling
         if mode.is_query() {
@@ -58,9 +69,13 @@ impl FilterOrchestrator {
                 source.filter.pending_aggregation = None;
             }
 
+            if range.is_none() && Self::replay_cached(source, &pattern, mode) {
+                return Ok(());
+            }
+
             let query_filter = query::QueryFilter::new(filter_query.clone())
                 .map_err(|e| format!("query filter error: {}", e))?;
-            let filter: Arc<dyn Filter> = Arc::new(query_filter);
+            let filter = Self::with_severity_floor(source, Arc::new(query_filter));
 
             Self::execute(source, filter, Some(&filter_query), range)?;
             return Ok(());
@@ -69,11 +84,34 @@ impl FilterOrchestrator {
         // Non-query filters clear any pending aggregation
         source.filter.pending_aggregation = None;
 
+        if range.is_none() && Self::replay_cached(source, &pattern, mode) {
+            return Ok(());
+        }
+
         let case_sensitive = mode.is_case_sensitive();
         let is_regex = mode.is_regex();
 
+        // Pattern extends the last completed one: only its matches can match
+        if let Some(lines) = Self::narrowable_matches(source, previous, &pattern, mode) {
+            let filter = Self::with_severity_floor(
+                source,
+                Arc::new(StringFilter::new(&pattern, case_sensitive)),
+            );
+            Self::execute_within(source, filter, lines)?;
+            return Ok(());
+        }
+
         // For full file + plain text, use the FAST byte-level SIMD path
-        if range.is_none() && !is_regex {
+        // (it only reports matching lines, so negated filters, severity
+        // floors and hidden severities scan line by line; it compares raw
+        // bytes, so non-ASCII patterns in other encodings decode each line)
+        if range.is_none()
+            && !is_regex
+            && !mode.is_negated()
+            && source.filter.min_severity.is_none()
+            && source.filter.hidden_severities.is_empty()
+            && (source.encoding == Encoding::Utf8 || pattern.is_ascii())
+        {
             if let Some(path) = &source.source_path {
                 let cancel = CancelToken::new();
                 source.filter.cancel_token = Some(cancel.clone());
@@ -101,11 +139,54 @@ impl FilterOrchestrator {
         } else {
             Arc::new(StringFilter::new(&pattern, case_sensitive))
         };
+        let filter: Arc<dyn Filter> = if mode.is_negated() {
+            Arc::new(NegatedFilter(filter))
+        } else {
+            filter
+        };
+        let filter = Self::with_severity_floor(source, filter);
 
         Self::execute(source, filter, None, range)?;
         Ok(())
     }
 
+    /// Bytes a full run of this filter would scan without index
+    /// acceleration, or `None` if it is index-accelerated or not file-backed.
+    ///
+    /// Used to hold back expensive live previews before they start.
+    pub fn unaccelerated_scan_bytes(
+        source: &LogSource,
+        pattern: &str,
+        mode: FilterMode,
+    ) -> Option<u64> {
+        source.source_path.as_ref()?;
+        let size = source.file_size?;
+        if source.index_reader.is_some() {
+            let query_mask = mode.is_query()
+                && query::parse_query(pattern).is_ok_and(|q| q.index_mask().is_some());
+            if query_mask || source.filter.min_severity.is_some() {
+                return None;
+            }
+        }
+        Some(size)
+    }
+
+    /// AND the source's severity floor and hidden severities (if any) into
+    /// `filter`, so text and severity are checked in the same pass.
+    fn with_severity_floor(source: &LogSource, filter: Arc<dyn Filter>) -> Arc<dyn Filter> {
+        let filter: Arc<dyn Filter> = match source.filter.min_severity {
+            Some(min) => Arc::new(MinSeverityFilter { inner: filter, min }),
+            None => filter,
+        };
+        if source.filter.hidden_severities.is_empty() {
+            return filter;
+        }
+        Arc::new(HiddenSeverityFilter {
+            inner: filter,
+            hidden: source.filter.hidden_severities.clone(),
+        })
+    }
+
     /// Set LogSource flags and delegate to the appropriate SearchEngine method.
     fn execute(
         source: &mut LogSource,
@@ -126,11 +207,16 @@ impl FilterOrchestrator {
         }
 
         let receiver = if let Some(path) = &source.source_path {
+            let keep = |severity| source.filter.keeps_severity(severity);
+            let keep_severity: Option<&dyn Fn(Severity) -> bool> =
+                source.filter.filters_severity().then_some(&keep);
             SearchEngine::search_file(
                 path,
                 filter,
+                source.encoding,
                 query,
                 source.index_reader.as_ref(),
+                keep_severity,
                 range,
                 cancel,
             )
@@ -143,6 +229,87 @@ impl FilterOrchestrator {
         Ok(())
     }
 
+    /// The source's current matches, if a full run of `pattern` can be
+    /// narrowed from them instead of rescanning every line.
+    ///
+    /// Holds when the results come from a completed plain run (same mode,
+    /// severity floor, since-marker and line count) whose pattern `pattern`
+    /// contains: every line containing `pattern` contains it too. Regex,
+    /// query and negated filters, and shortened patterns, always rescan.
+    fn narrowable_matches(
+        source: &LogSource,
+        previous: Option<ResultKey>,
+        pattern: &str,
+        mode: FilterMode,
+    ) -> Option<Vec<usize>> {
+        let previous = previous?;
+        if mode.is_regex() || mode.is_query() || mode.is_negated() || previous.mode != mode {
+            return None;
+        }
+        let extends = if mode.is_case_sensitive() {
+            pattern.contains(&previous.pattern)
+        } else {
+            pattern
+                .to_lowercase()
+                .contains(&previous.pattern.to_lowercase())
+        };
+        let unchanged = source.result_key(&previous.pattern, mode) == previous
+            && source.mode == ViewMode::Filtered
+            && matches!(source.filter.state, FilterState::Complete { .. });
+        (extends && unchanged).then(|| source.line_indices.to_vec())
+    }
+
+    /// Full run that only checks `lines` (sorted), for narrowing a previous
+    /// result set. Lines appended to a file since are checked as well.
+    fn execute_within(
+        source: &mut LogSource,
+        filter: Arc<dyn Filter>,
+        lines: Vec<usize>,
+    ) -> Result<(), String> {
+        let cancel = CancelToken::new();
+        source.filter.cancel_token = Some(cancel.clone());
+        source.filter.needs_clear = true;
+        source.filter.state = FilterState::Processing { lines_processed: 0 };
+        source.filter.is_incremental = false;
+
+        let receiver = if let Some(path) = &source.source_path {
+            SearchEngine::search_file_within(
+                path,
+                filter,
+                source.encoding,
+                lines,
+                source.total_lines,
+                cancel,
+            )
+            .map_err(|e| format!("filter I/O error: {}", e))?
+        } else {
+            SearchEngine::search_reader_within(source.reader.clone(), filter, lines, cancel)
+        };
+
+        source.filter.receiver = Some(receiver);
+        Ok(())
+    }
+
+    /// Serve a full run from the source's result cache: the cached matches
+    /// arrive as a single `Complete` message, like a very fast scan.
+    fn replay_cached(source: &mut LogSource, pattern: &str, mode: FilterMode) -> bool {
+        let key = source.result_key(pattern, mode);
+        let Some(matches) = source.filter.result_cache.get(&key) else {
+            return false;
+        };
+        let (tx, rx) = mpsc::channel();
+        let _ = tx.send(FilterProgress::Complete {
+            matches: matches.as_ref().clone(),
+            lines_processed: source.total_lines,
+        });
+        source.filter.cancel_token = None;
+        source.filter.needs_clear = true;
+        source.filter.state = FilterState::Processing { lines_processed: 0 };
+        source.filter.is_incremental = false;
+        source.filter.receiver = Some(rx);
+        true
+    }
+
     /// Cancel any in-progress filter on a source.
     pub fn cancel(source: &mut LogSource) {
         if let Some(ref cancel) = source.filter.cancel_token {
@@ -155,6 +322,8 @@ impl FilterOrchestrator {
 mod tests {
     use super::*;
     use crate::filter::engine::FilterProgress;
+    use crate::index::flags::{detect_flags, Severity};
+    use crate::index::reader::IndexReader;
     use crate::log_source::LogSource;
     use crate::test_utils::MockLogReader;
     use std::sync::Mutex;
@@ -190,6 +359,7 @@ mod tests {
         let mut source = make_source(vec!["ERROR: fail", "INFO: ok", "ERROR: boom"]);
         let mode = FilterMode::Plain {
             case_sensitive: false,
+            negate: false,
         };
 
         FilterOrchestrator::trigger(&mut source, "error".into(), mode, None).unwrap();
@@ -204,11 +374,39 @@ mod tests {
         assert_eq!(matches, vec![0, 2]);
     }
 
+    #[test]
+    fn latin1_file_matches_decoded_lines() {
+        use std::io::Write;
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"caf\xe9 open\nclos\xe9\nplain\n").unwrap();
+        let reader = Arc::new(Mutex::new(MockLogReader::new(vec![])));
+        let mut source = LogSource::new("latin1".into(), reader)
+            .with_path(file.path().to_path_buf())
+            .with_lines(3);
+        source.encoding = Encoding::Latin1;
+
+        let plain = FilterMode::Plain {
+            case_sensitive: false,
+            negate: false,
+        };
+        FilterOrchestrator::trigger(&mut source, "CAFÉ".into(), plain, None).unwrap();
+        assert_eq!(collect_matches(&mut source), vec![0]);
+
+        let regex = FilterMode::Regex {
+            case_sensitive: true,
+            negate: false,
+        };
+        FilterOrchestrator::trigger(&mut source, "é$".into(), regex, None).unwrap();
+        assert_eq!(collect_matches(&mut source), vec![1]);
+    }
+
     #[test]
     fn regex_filter_finds_matches() {
         let mut source = make_source(vec!["line 42", "line 7", "line 100"]);
         let mode = FilterMode::Regex {
             case_sensitive: false,
+            negate: false,
         };
 
         FilterOrchestrator::trigger(&mut source, r"line \d{2,}".into(), mode, None).unwrap();
@@ -217,11 +415,243 @@ mod tests {
         assert_eq!(matches, vec![0, 2]);
     }
 
+    #[test]
+    fn negated_filter_keeps_non_matching_lines() {
+        let mut source = make_source(vec![
+            "ERROR: fail",
+            "INFO: ok",
+            "healthcheck",
+            "ERROR: boom",
+        ]);
+        let mode = FilterMode::Plain {
+            case_sensitive: false,
+            negate: true,
+        };
+        FilterOrchestrator::trigger(&mut source, "error".into(), mode, None).unwrap();
+        assert_eq!(collect_matches(&mut source), vec![1, 2]);
+
+        let mode = FilterMode::Regex {
+            case_sensitive: false,
+            negate: true,
+        };
+        FilterOrchestrator::trigger(&mut source, "^(error|info)".into(), mode, None).unwrap();
+        assert_eq!(collect_matches(&mut source), vec![2]);
+    }
+
+    #[test]
+    fn negated_query_keeps_non_matching_lines() {
+        let mut source = make_source(vec![
+            r#"{"level":"error"}"#,
+            r#"{"level":"info"}"#,
+            "plain text",
+        ]);
+
+        FilterOrchestrator::trigger(
+            &mut source,
+            r#"!json | level == "error""#.into(),
+            FilterMode::Query {},
+            None,
+        )
+        .unwrap();
+        assert_eq!(collect_matches(&mut source), vec![1, 2]);
+    }
+
+    /// Naive two-step reference: severity first, then text.
+    fn naive_intersection(lines: &[&str], min: Severity, text: &str) -> Vec<usize> {
+        let text_filter = StringFilter::new(text, false);
+        lines
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| Severity::from_flags(detect_flags(l)).to_bits() >= min.to_bits())
+            .filter(|(_, l)| text_filter.matches(l))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    const LEVELED: [&str; 6] = [
+        "ERROR request timeout",
+        "INFO request timeout",
+        "WARN slow, timeout soon",
+        "ERROR disk full",
+        "DEBUG timeout config",
+        "FATAL timeout, giving up",
+    ];
+
+    #[test]
+    fn severity_floor_combines_with_text_filter() {
+        let mut source = make_source(LEVELED.to_vec());
+        source.filter.min_severity = Some(Severity::Error);
+
+        FilterOrchestrator::trigger(&mut source, "timeout".into(), FilterMode::plain(), None)
+            .unwrap();
+        let matches = collect_matches(&mut source);
+        assert_eq!(matches, vec![0, 5]);
+        assert_eq!(
+            matches,
+            naive_intersection(&LEVELED, Severity::Error, "timeout")
+        );
+    }
+
+    #[test]
+    fn hidden_severities_combine_with_floor_and_text_filter() {
+        let mut source = make_source(LEVELED.to_vec());
+        source.filter.hidden_severities = vec![Severity::Debug, Severity::Error];
+
+        FilterOrchestrator::trigger(&mut source, "timeout".into(), FilterMode::plain(), None)
+            .unwrap();
+        assert_eq!(collect_matches(&mut source), vec![1, 2, 5]);
+
+        source.filter.min_severity = Some(Severity::Warn);
+        FilterOrchestrator::trigger(&mut source, "timeout".into(), FilterMode::plain(), None)
+            .unwrap();
+        assert_eq!(collect_matches(&mut source), vec![2, 5]);
+    }
+
+    #[test]
+    fn severity_floor_uses_index_and_checks_lines_past_it() {
+        use crate::index::builder::IndexBuilder;
+        use crate::source::index_dir_for_log;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, LEVELED.join("\n") + "\n").unwrap();
+        IndexBuilder::new()
+            .build(&path, &index_dir_for_log(&path))
+            .unwrap();
+        // Lines appended after the index was built
+        let extra = ["WARN timeout again", "INFO timeout again"];
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        writeln!(file, "{}", extra.join("\n")).unwrap();
+        let all: Vec<&str> = LEVELED.iter().chain(extra.iter()).copied().collect();
+
+        for min in [Severity::Info, Severity::Warn, Severity::Error] {
+            let mut source = make_source(all.clone());
+            source.source_path = Some(path.clone());
+            source.index_reader = IndexReader::open(&path);
+            assert!(source.index_reader.is_some());
+            source.filter.min_severity = Some(min);
+
+            FilterOrchestrator::trigger(&mut source, "timeout".into(), FilterMode::plain(), None)
+                .unwrap();
+            assert_eq!(
+                collect_matches(&mut source),
+                naive_intersection(&all, min, "timeout"),
+                "min severity {:?}",
+                min
+            );
+        }
+    }
+
+    /// Run a full filter to completion and record it the way
+    /// `TabState::apply_filter` does.
+    fn complete_filter(source: &mut LogSource, pattern: &str, mode: FilterMode) {
+        source.filter.mode = mode;
+        FilterOrchestrator::trigger(source, pattern.into(), mode, None).unwrap();
+        let matches = collect_matches(source);
+        source.line_indices = matches.into();
+        source.mode = ViewMode::Filtered;
+        source.filter.state = FilterState::Complete {
+            matches: source.line_indices.len(),
+        };
+        source.filter.results_key = Some(source.result_key(pattern, mode));
+    }
+
+    const REQUESTS: [&str; 5] = [
+        "ERROR GET /api/users",
+        "INFO GET /api/users",
+        "ERROR POST /api/orders",
+        "error get /api/USERS/42",
+        "WARN GET /health",
+    ];
+
+    #[test]
+    fn extended_plain_pattern_narrows_previous_matches() {
+        let mode = FilterMode::plain();
+        let mut source = make_source(REQUESTS.to_vec());
+        complete_filter(&mut source, "error", mode);
+        assert_eq!(source.line_indices.to_vec(), vec![0, 2, 3]);
+
+        // Only the previous matches are checked: hide one to prove it
+        source.line_indices = vec![0, 2].into();
+        FilterOrchestrator::trigger(&mut source, "error get".into(), mode, None).unwrap();
+        assert!(source.filter.needs_clear);
+        assert_eq!(collect_matches(&mut source), vec![0]);
+    }
+
+    #[test]
+    fn narrowing_matches_a_full_scan() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        std::fs::write(&path, REQUESTS.join("\n") + "\n").unwrap();
+
+        let case_sensitive = FilterMode::Plain {
+            case_sensitive: true,
+            negate: false,
+        };
+        for mode in [FilterMode::plain(), case_sensitive] {
+            let mut source = make_source(REQUESTS.to_vec());
+            source.source_path = Some(path.clone());
+            complete_filter(&mut source, "GET", mode);
+            FilterOrchestrator::trigger(&mut source, "GET /api/users".into(), mode, None).unwrap();
+            let narrowed = collect_matches(&mut source);
+
+            let mut fresh = make_source(REQUESTS.to_vec());
+            fresh.source_path = Some(path.clone());
+            FilterOrchestrator::trigger(&mut fresh, "GET /api/users".into(), mode, None).unwrap();
+            assert_eq!(narrowed, collect_matches(&mut fresh), "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn shortened_or_regex_pattern_rescans() {
+        let mut source = make_source(REQUESTS.to_vec());
+        complete_filter(&mut source, "error get", FilterMode::plain());
+        FilterOrchestrator::trigger(&mut source, "error".into(), FilterMode::plain(), None)
+            .unwrap();
+        assert_eq!(collect_matches(&mut source), vec![0, 2, 3]);
+
+        complete_filter(&mut source, "error get", FilterMode::plain());
+        FilterOrchestrator::trigger(
+            &mut source,
+            "error get|info".into(),
+            FilterMode::regex(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(collect_matches(&mut source), vec![0, 1, 3]);
+    }
+
+    #[test]
+    fn unaccelerated_scan_bytes_only_for_unindexed_file_scans() {
+        let mut source = make_source(vec!["a"]);
+        assert_eq!(
+            FilterOrchestrator::unaccelerated_scan_bytes(&source, "a", FilterMode::plain()),
+            None
+        );
+
+        source.source_path = Some("/tmp/app.log".into());
+        source.file_size = Some(4096);
+        let query = r#"json | level == "error""#;
+        assert_eq!(
+            FilterOrchestrator::unaccelerated_scan_bytes(&source, "a", FilterMode::plain()),
+            Some(4096)
+        );
+        assert_eq!(
+            FilterOrchestrator::unaccelerated_scan_bytes(&source, query, FilterMode::query()),
+            Some(4096)
+        );
+    }
+
     #[test]
     fn invalid_regex_returns_error() {
         let mut source = make_source(vec!["test"]);
         let mode = FilterMode::Regex {
             case_sensitive: false,
+            negate: false,
         };
 
         let result = FilterOrchestrator::trigger(&mut source, "[invalid".into(), mode, None);
@@ -247,6 +677,7 @@ mod tests {
         let mut source = make_source(vec!["a", "b", "c"]);
         let mode = FilterMode::Plain {
             case_sensitive: false,
+            negate: false,
         };
 
         FilterOrchestrator::trigger(&mut source, "a".into(), mode, None).unwrap();
@@ -261,6 +692,7 @@ mod tests {
         let mut source = make_source(vec!["a", "b", "c", "d", "e"]);
         let mode = FilterMode::Plain {
             case_sensitive: false,
+            negate: false,
         };
 
         FilterOrchestrator::trigger(&mut source, "a".into(), mode, Some((3, 5))).unwrap();
@@ -277,6 +709,7 @@ mod tests {
         let mut source = make_source(vec!["a", "b"]);
         let mode = FilterMode::Plain {
             case_sensitive: false,
+            negate: false,
         };
 
         FilterOrchestrator::trigger(&mut source, "a".into(), mode, None).unwrap();
@@ -290,6 +723,7 @@ mod tests {
         let mut source = make_source(vec!["test"]);
         let mode = FilterMode::Plain {
             case_sensitive: false,
+            negate: false,
         };
 
         FilterOrchestrator::trigger(&mut source, "test".into(), mode, None).unwrap();
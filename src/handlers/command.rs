@@ -0,0 +1,156 @@
+//! `;` command line: named commands for features otherwise bound to keys,
+//! like `filter error`, `follow off` or `level warn`.
+//!
+//! A command parses into the same events its key would send, so it behaves
+//! exactly like pressing the key.
+
+use crate::app::{App, AppEvent};
+use crate::index::flags::Severity;
+
+/// Help overlay section listing [`COMMANDS`]
+pub const COMMANDS_HEADER: &str = "Commands (;)";
+
+/// Commands and their descriptions, for the help overlay
+pub const COMMANDS: &[(&str, &str)] = &[
+    ("filter <text>", "Apply a filter (filter clear removes it)"),
+    ("follow [on|off]", "Toggle or set follow mode"),
+    (
+        "level <lvl|off>",
+        "Severity floor: info / warn / error / off",
+    ),
+    ("goto <n>", "Jump to line n (or just <n>)"),
+    ("tab <n>", "Switch to tab n"),
+    ("close / reopen", "Close the tab / reopen the last closed"),
+    ("wrap / raw", "Toggle line wrap / raw mode"),
+    ("timestamps", "Toggle timestamps"),
+    ("reverse", "Toggle newest-first order"),
+    ("index", "Build index for the current file"),
+    ("help / errors", "Show help / the error log"),
+    ("?", "List these commands"),
+    ("quit", "Quit"),
+];
+
+/// Parse a command line into the events that carry it out.
+pub fn parse(input: &str, app: &App) -> Result<Vec<AppEvent>, String> {
+    let input = input.trim();
+    let (name, arg) = match input.split_once(char::is_whitespace) {
+        Some((name, arg)) => (name, arg.trim()),
+        None => (input, ""),
+    };
+
+    let events = match (name, arg) {
+        ("", _) => vec![],
+        ("filter" | "f", "clear") => vec![AppEvent::ClearFilter],
+        ("filter" | "f", "") => return Err("Usage: filter <text> | filter clear".to_string()),
+        ("filter" | "f", pattern) => vec![AppEvent::ApplyFilter(pattern.to_string())],
+        ("follow", "") => vec![AppEvent::ToggleFollowMode],
+        ("follow", "on") if app.active_tab().source.follow_mode => vec![],
+        ("follow", "on") => vec![AppEvent::ToggleFollowMode],
+        ("follow", "off") => vec![AppEvent::DisableFollowMode],
+        ("level", "off") => vec![AppEvent::SetMinSeverity(None)],
+        ("level", level) => match Severity::from_label(level) {
+            Some(level) => vec![AppEvent::SetMinSeverity(Some(level))],
+            None => return Err(format!("Unknown level: {}", level)),
+        },
+        ("goto", line) => vec![AppEvent::JumpToLine(parse_number(line)?)],
+        (line, "") if line.starts_with(|c: char| c.is_ascii_digit()) => {
+            vec![AppEvent::JumpToLine(parse_number(line)?)]
+        }
+        ("tab", n) => match parse_number(n)? {
+            0 => return Err("Tabs are numbered from 1".to_string()),
+            n => vec![AppEvent::SelectTab(n - 1)],
+        },
+        ("close", "") => vec![AppEvent::CloseCurrentTab],
+        ("reopen", "") => vec![AppEvent::ReopenLastClosed],
+        ("wrap", "") => vec![AppEvent::ToggleLineWrap],
+        ("raw", "") => vec![AppEvent::ToggleRawMode],
+        ("timestamps", "") => vec![AppEvent::ToggleTimestamps],
+        ("reverse", "") => vec![AppEvent::ToggleReverseOrder],
+        ("index", "") => vec![AppEvent::BuildIndex],
+        ("help", "") => vec![AppEvent::ShowHelp],
+        ("?", "") => vec![AppEvent::ShowCommandHelp],
+        ("errors", "") => vec![AppEvent::ShowErrors],
+        ("quit" | "q", "") => vec![AppEvent::Quit],
+        (name, _) if is_command(name) => {
+            return Err(format!("Bad arguments for {}: {}", name, input));
+        }
+        (name, _) => return Err(format!("Unknown command: {}", name)),
+    };
+    Ok(events)
+}
+
+/// Whether `name` is one of the commands listed in [`COMMANDS`]
+fn is_command(name: &str) -> bool {
+    COMMANDS
+        .iter()
+        .any(|(usage, _)| usage.split([' ', '/']).any(|word| word == name))
+}
+
+fn parse_number(text: &str) -> Result<usize, String> {
+    text.parse()
+        .map_err(|_| format!("Expected a number, got '{}'", text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn create_test_app() -> (App, NamedTempFile) {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "line").unwrap();
+        file.flush().unwrap();
+        let app = App::new(vec![file.path().to_path_buf()], false).unwrap();
+        (app, file)
+    }
+
+    #[test]
+    fn test_commands_map_to_events() {
+        let (app, _file) = create_test_app();
+        let parse = |input| parse(input, &app).unwrap();
+
+        assert_eq!(
+            parse("filter  request failed "),
+            vec![AppEvent::ApplyFilter("request failed".to_string())]
+        );
+        assert_eq!(parse("filter clear"), vec![AppEvent::ClearFilter]);
+        assert_eq!(parse("follow off"), vec![AppEvent::DisableFollowMode]);
+        assert_eq!(
+            parse("level warn"),
+            vec![AppEvent::SetMinSeverity(Some(Severity::Warn))]
+        );
+        assert_eq!(parse("level off"), vec![AppEvent::SetMinSeverity(None)]);
+        assert_eq!(parse("goto 42"), vec![AppEvent::JumpToLine(42)]);
+        assert_eq!(parse("42"), vec![AppEvent::JumpToLine(42)]);
+        assert_eq!(parse("tab 2"), vec![AppEvent::SelectTab(1)]);
+        assert_eq!(parse("?"), vec![AppEvent::ShowCommandHelp]);
+        assert_eq!(parse("q"), vec![AppEvent::Quit]);
+        assert_eq!(parse("  "), vec![]);
+    }
+
+    #[test]
+    fn test_follow_on_only_toggles_when_off() {
+        let (mut app, _file) = create_test_app();
+        app.active_tab_mut().source.follow_mode = false;
+        assert_eq!(
+            parse("follow on", &app).unwrap(),
+            vec![AppEvent::ToggleFollowMode]
+        );
+        app.active_tab_mut().source.follow_mode = true;
+        assert_eq!(parse("follow on", &app).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_invalid_commands_are_reported() {
+        let (app, _file) = create_test_app();
+        let error = |input| parse(input, &app).unwrap_err();
+
+        assert_eq!(error("theme dark"), "Unknown command: theme");
+        assert_eq!(error("level loud"), "Unknown level: loud");
+        assert_eq!(error("goto x"), "Expected a number, got 'x'");
+        assert_eq!(error("tab 0"), "Tabs are numbered from 1");
+        assert!(error("follow maybe").starts_with("Bad arguments for follow"));
+        assert!(error("filter").starts_with("Usage"));
+    }
+}
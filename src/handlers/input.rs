@@ -1,5 +1,9 @@
+use super::command;
+use super::keymap::KeyContext;
 use crate::app::AppEvent;
 use crate::app::{App, InputMode};
+use crate::config::types::FilterEsc;
+use crate::index::flags::Severity;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 /// Handle keyboard input and return corresponding events
@@ -7,7 +11,11 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 pub fn handle_input_event(key: KeyEvent, app: &App) -> Vec<AppEvent> {
     // If help is showing, handle help navigation or hide
     if app.help_scroll_offset.is_some() {
-        return handle_help_mode(key);
+        return handle_help_mode(key, app);
+    }
+
+    if app.errors_scroll_offset.is_some() {
+        return handle_errors_mode(key);
     }
 
     // Dismiss warning popup on any key
@@ -16,18 +24,40 @@ pub fn handle_input_event(key: KeyEvent, app: &App) -> Vec<AppEvent> {
     }
 
     match app.input.mode {
-        InputMode::EnteringFilter => handle_filter_input_mode(key),
+        InputMode::EnteringFilter => handle_filter_input_mode(key, app),
         InputMode::EnteringLineJump => handle_line_jump_input_mode(key),
+        InputMode::EnteringCommand => handle_command_input_mode(key, app),
         InputMode::ZPending => handle_z_pending_mode(key),
-        InputMode::SourcePanel => handle_source_panel_mode(key),
+        InputMode::HideLevelPending => handle_hide_level_pending_mode(key),
+        InputMode::SourcePanel => handle_source_panel_mode(key, app),
         InputMode::ConfirmClose => handle_confirm_close_mode(key),
+        InputMode::ContextMenu => handle_context_menu_mode(key),
+        InputMode::FieldPicker => handle_field_picker_mode(key),
         InputMode::Normal => handle_normal_mode(key, app),
     }
 }
 
 /// Handle keyboard input when help overlay is showing
-fn handle_help_mode(key: KeyEvent) -> Vec<AppEvent> {
+fn handle_help_mode(key: KeyEvent, app: &App) -> Vec<AppEvent> {
+    let search = app.help_search.as_ref();
+    if search.is_some_and(|s| s.editing) {
+        return match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                vec![AppEvent::Quit]
+            }
+            KeyCode::Char(c) => vec![AppEvent::HelpSearchChar(c)],
+            KeyCode::Backspace => vec![AppEvent::HelpSearchBackspace],
+            KeyCode::Enter => vec![AppEvent::FinishHelpSearch],
+            KeyCode::Esc => vec![AppEvent::ClearHelpSearch],
+            KeyCode::Down => vec![AppEvent::ScrollHelpDown],
+            KeyCode::Up => vec![AppEvent::ScrollHelpUp],
+            _ => vec![],
+        };
+    }
     match key.code {
+        KeyCode::Char('/') => vec![AppEvent::StartHelpSearch],
+        // Esc first drops a kept search, then closes
+        KeyCode::Esc if search.is_some() => vec![AppEvent::ClearHelpSearch],
         KeyCode::Char('q') => vec![AppEvent::Quit],
         KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             vec![AppEvent::Quit]
@@ -39,13 +69,30 @@ fn handle_help_mode(key: KeyEvent) -> Vec<AppEvent> {
     }
 }
 
+/// Handle keyboard input when the errors overlay is showing
+fn handle_errors_mode(key: KeyEvent) -> Vec<AppEvent> {
+    match key.code {
+        KeyCode::Char('q') => vec![AppEvent::Quit],
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            vec![AppEvent::Quit]
+        }
+        KeyCode::Char('j') | KeyCode::Down => vec![AppEvent::ScrollErrorsDown],
+        KeyCode::Char('k') | KeyCode::Up => vec![AppEvent::ScrollErrorsUp],
+        _ => vec![AppEvent::HideErrors],
+    }
+}
+
 /// Handle keyboard input in filter input mode
-fn handle_filter_input_mode(key: KeyEvent) -> Vec<AppEvent> {
+fn handle_filter_input_mode(key: KeyEvent, app: &App) -> Vec<AppEvent> {
     match key.code {
         // Alt+C toggles case sensitivity (Ctrl+I doesn't work - same as Tab in terminals)
         KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::ALT) => {
             vec![AppEvent::ToggleCaseSensitivity]
         }
+        // Alt+N inverts the filter (show lines that do NOT match)
+        KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::ALT) => {
+            vec![AppEvent::ToggleNegate]
+        }
         // Ctrl+A goes to start of line
         KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             vec![AppEvent::CursorHome]
@@ -56,11 +103,25 @@ fn handle_filter_input_mode(key: KeyEvent) -> Vec<AppEvent> {
         }
         KeyCode::Char(c) => vec![AppEvent::FilterInputChar(c)],
         KeyCode::Backspace => vec![AppEvent::FilterInputBackspace],
+        // Ctrl+Enter applies but keeps the prompt open; Alt+Enter for
+        // terminals that don't report Ctrl on Enter
+        KeyCode::Enter
+            if key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+        {
+            vec![AppEvent::FilterInputApplyKeepOpen]
+        }
         KeyCode::Enter => vec![AppEvent::FilterInputSubmit],
+        // Esc first stops a running scan; the next one closes the prompt,
+        // clearing the filter unless `filter_esc: keep`
+        KeyCode::Esc if app.is_filter_processing() => vec![AppEvent::StopFilterScan],
+        KeyCode::Esc if app.filter_esc == FilterEsc::Keep => vec![AppEvent::FilterInputCancel],
         KeyCode::Esc => vec![AppEvent::FilterInputCancel, AppEvent::ClearFilter],
         KeyCode::Up => vec![AppEvent::HistoryUp],
         KeyCode::Down => vec![AppEvent::HistoryDown],
-        // Tab toggles between Plain and Regex mode
+        // Tab completes a partly typed query name, otherwise cycles the mode
+        KeyCode::Tab if app.can_complete_query() => vec![AppEvent::CompleteQueryField],
         KeyCode::Tab => vec![AppEvent::ToggleFilterMode],
         // Cursor navigation
         KeyCode::Left => vec![AppEvent::CursorLeft],
@@ -71,6 +132,35 @@ fn handle_filter_input_mode(key: KeyEvent) -> Vec<AppEvent> {
     }
 }
 
+/// Handle keyboard input while typing a `;` command. Enter closes the
+/// prompt and sends the command's events.
+fn handle_command_input_mode(key: KeyEvent, app: &App) -> Vec<AppEvent> {
+    match key.code {
+        KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            vec![AppEvent::CursorHome]
+        }
+        KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            vec![AppEvent::CursorEnd]
+        }
+        KeyCode::Char(c) => vec![AppEvent::CommandInputChar(c)],
+        KeyCode::Backspace => vec![AppEvent::CommandInputBackspace],
+        KeyCode::Enter => {
+            let mut events = vec![AppEvent::CommandInputCancel];
+            match command::parse(app.get_input(), app) {
+                Ok(command_events) => events.extend(command_events),
+                Err(message) => events.push(AppEvent::CommandFailed(message)),
+            }
+            events
+        }
+        KeyCode::Esc => vec![AppEvent::CommandInputCancel],
+        KeyCode::Left => vec![AppEvent::CursorLeft],
+        KeyCode::Right => vec![AppEvent::CursorRight],
+        KeyCode::Home => vec![AppEvent::CursorHome],
+        KeyCode::End => vec![AppEvent::CursorEnd],
+        _ => vec![],
+    }
+}
+
 /// Handle keyboard input in line jump input mode
 fn handle_line_jump_input_mode(key: KeyEvent) -> Vec<AppEvent> {
     match key.code {
@@ -107,26 +197,31 @@ fn handle_z_pending_mode(key: KeyEvent) -> Vec<AppEvent> {
     }
 }
 
-/// Handle keyboard input in source panel focus mode
-fn handle_source_panel_mode(key: KeyEvent) -> Vec<AppEvent> {
-    match key.code {
-        KeyCode::Esc | KeyCode::Tab => vec![AppEvent::UnfocusSourcePanel],
-        KeyCode::Up | KeyCode::Char('k') => vec![AppEvent::SourcePanelUp],
-        KeyCode::Down | KeyCode::Char('j') => vec![AppEvent::SourcePanelDown],
-        KeyCode::Char(' ') => vec![AppEvent::ToggleCategoryExpand],
-        KeyCode::Enter => vec![AppEvent::SelectSource],
-        KeyCode::Char('x') => vec![AppEvent::CloseSelectedTab],
-        KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            vec![AppEvent::CloseSelectedTab]
-        }
-        KeyCode::Char('q') => vec![AppEvent::Quit],
-        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            vec![AppEvent::Quit]
+/// Handle keyboard input in hide-level pending mode (waiting for the level
+/// after 'H': Ht, Hd, Hi, Hw, He, Hf; HH shows all levels)
+fn handle_hide_level_pending_mode(key: KeyEvent) -> Vec<AppEvent> {
+    let level = match key.code {
+        KeyCode::Char('t') => Severity::Trace,
+        KeyCode::Char('d') => Severity::Debug,
+        KeyCode::Char('i') => Severity::Info,
+        KeyCode::Char('w') => Severity::Warn,
+        KeyCode::Char('e') => Severity::Error,
+        KeyCode::Char('f') => Severity::Fatal,
+        KeyCode::Char('H') => {
+            return vec![AppEvent::ShowAllSeverities, AppEvent::ExitHideLevelMode];
         }
-        KeyCode::Char('y') => vec![AppEvent::CopySourcePath],
-        KeyCode::Char('?') => vec![AppEvent::ShowHelp],
-        _ => vec![],
-    }
+        // Any other key cancels the mode
+        _ => return vec![AppEvent::ExitHideLevelMode],
+    };
+    vec![
+        AppEvent::ToggleHiddenSeverity(level),
+        AppEvent::ExitHideLevelMode,
+    ]
+}
+
+/// Handle keyboard input in source panel focus mode
+fn handle_source_panel_mode(key: KeyEvent, app: &App) -> Vec<AppEvent> {
+    app.keymap.events(KeyContext::SourcePanel, key, app)
 }
 
 /// Handle keyboard input in close confirmation mode
@@ -142,98 +237,48 @@ fn handle_confirm_close_mode(key: KeyEvent) -> Vec<AppEvent> {
     }
 }
 
-/// Handle keyboard input in aggregation view mode
-fn handle_aggregation_mode(key: KeyEvent) -> Vec<AppEvent> {
+/// Handle keyboard input while the line context menu is open
+fn handle_context_menu_mode(key: KeyEvent) -> Vec<AppEvent> {
     match key.code {
-        KeyCode::Char('j') | KeyCode::Down => vec![AppEvent::AggregationDown],
-        KeyCode::Char('k') | KeyCode::Up => vec![AppEvent::AggregationUp],
-        KeyCode::Enter => vec![AppEvent::AggregationDrillDown],
-        KeyCode::Esc => vec![AppEvent::AggregationBack],
-        KeyCode::Char('g') => vec![AppEvent::AggregationJumpToStart],
-        KeyCode::Char('G') => vec![AppEvent::AggregationJumpToEnd],
-        KeyCode::Char('/') => vec![AppEvent::StartFilterInput],
-        KeyCode::Char('q') => vec![AppEvent::Quit],
+        KeyCode::Char('k') | KeyCode::Up => vec![AppEvent::ContextMenuUp],
+        KeyCode::Char('j') | KeyCode::Down => vec![AppEvent::ContextMenuDown],
+        KeyCode::Enter => vec![AppEvent::ContextMenuSelect],
+        KeyCode::Esc => vec![AppEvent::ContextMenuClose],
         KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             vec![AppEvent::Quit]
         }
-        KeyCode::Char('?') => vec![AppEvent::ShowHelp],
-        KeyCode::Tab => vec![AppEvent::FocusSourcePanel],
-        KeyCode::Char(c @ '1'..='9') => {
-            let index = (c as usize) - ('1' as usize);
-            vec![AppEvent::SelectTab(index)]
-        }
         _ => vec![],
     }
 }
 
-fn handle_normal_mode(key: KeyEvent, app: &App) -> Vec<AppEvent> {
-    // In aggregation mode, use aggregation-specific keybindings
-    if app.active_tab().source.mode == crate::app::ViewMode::Aggregation {
-        return handle_aggregation_mode(key);
-    }
-
+/// Handle keyboard input while the quick filter picker is open
+fn handle_field_picker_mode(key: KeyEvent) -> Vec<AppEvent> {
     match key.code {
-        KeyCode::Char('q') => vec![AppEvent::Quit],
+        KeyCode::Char('k') | KeyCode::Up => vec![AppEvent::FieldPickerUp],
+        KeyCode::Char('j') | KeyCode::Down => vec![AppEvent::FieldPickerDown],
+        KeyCode::Enter => vec![AppEvent::FieldPickerSelect],
+        KeyCode::Esc => vec![AppEvent::FieldPickerClose],
         KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             vec![AppEvent::Quit]
         }
-        // Ctrl+E - scroll viewport down (vim style)
-        KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            vec![AppEvent::ViewportDown, AppEvent::DisableFollowMode]
-        }
-        // Ctrl+Y - scroll viewport up (vim style)
-        KeyCode::Char('y') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            vec![AppEvent::ViewportUp, AppEvent::DisableFollowMode]
-        }
-        KeyCode::Down | KeyCode::Char('j') => {
-            vec![AppEvent::ScrollDown, AppEvent::DisableFollowMode]
-        }
-        KeyCode::Up | KeyCode::Char('k') => vec![AppEvent::ScrollUp, AppEvent::DisableFollowMode],
-        KeyCode::PageDown => {
-            // Page size will be set by caller based on terminal size
-            vec![AppEvent::DisableFollowMode]
-        }
-        KeyCode::PageUp => {
-            // Page size will be set by caller based on terminal size
-            vec![AppEvent::DisableFollowMode]
-        }
-        KeyCode::Char('g') => vec![AppEvent::JumpToStart, AppEvent::DisableFollowMode],
-        KeyCode::Char('G') => vec![AppEvent::JumpToEnd, AppEvent::DisableFollowMode],
-        KeyCode::Char('f') => vec![AppEvent::ToggleFollowMode],
-        KeyCode::Char('/') => vec![AppEvent::StartFilterInput],
-        KeyCode::Char(':') => vec![AppEvent::StartLineJumpInput],
-        KeyCode::Char('?') => vec![AppEvent::ShowHelp],
-        KeyCode::Char('z') => vec![AppEvent::EnterZMode],
-        KeyCode::Char(' ') => vec![AppEvent::ToggleLineExpansion],
-        KeyCode::Char('c') => vec![AppEvent::CollapseAll],
-        KeyCode::Char('r') => vec![AppEvent::ToggleRawMode],
-        KeyCode::Char('t') => vec![AppEvent::ToggleTimestamps],
-        KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            vec![AppEvent::CloseCurrentTab]
-        }
-        KeyCode::Char('w') => vec![AppEvent::ToggleLineWrap],
-        KeyCode::Char('y') => vec![AppEvent::CopySelectedLine],
-        KeyCode::Char('R') if app.active_tab().is_combined => {
-            vec![AppEvent::RefreshCombinedView]
-        }
-        KeyCode::Esc => vec![AppEvent::ClearFilter],
-        // Tab toggles source panel focus
-        KeyCode::Tab => vec![AppEvent::FocusSourcePanel],
-        // Direct tab selection (1-9)
-        KeyCode::Char(c @ '1'..='9') => {
-            let index = (c as usize) - ('1' as usize);
-            vec![AppEvent::SelectTab(index)]
-        }
-        // Close current tab
-        KeyCode::Char('x') => vec![AppEvent::CloseCurrentTab],
         _ => vec![],
     }
 }
 
+fn handle_normal_mode(key: KeyEvent, app: &App) -> Vec<AppEvent> {
+    // In aggregation mode, use aggregation-specific keybindings
+    let context = if app.active_tab().source.mode == crate::app::ViewMode::Aggregation {
+        KeyContext::Aggregation
+    } else {
+        KeyContext::Normal
+    };
+    app.keymap.events(context, key, app)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::app::App;
+    use crate::app::{App, FilterState};
     use std::io::Write;
     use tempfile::NamedTempFile;
 
@@ -307,6 +352,104 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_v_starts_quick_filter() {
+        let (app, _file) = create_test_app();
+        let key = KeyEvent::new(KeyCode::Char('v'), KeyModifiers::NONE);
+        let events = handle_input_event(key, &app);
+        assert_eq!(events, vec![AppEvent::QuickFilter]);
+    }
+
+    #[test]
+    fn test_shift_t_traces_selected_line() {
+        let (app, _file) = create_test_app();
+        let key = KeyEvent::new(KeyCode::Char('T'), KeyModifiers::SHIFT);
+        let events = handle_input_event(key, &app);
+        assert_eq!(events, vec![AppEvent::TraceSelectedLine]);
+    }
+
+    #[test]
+    fn test_arrows_move_word_cursor_only_while_highlighting() {
+        let (mut app, _file) = create_test_app();
+        let right = KeyEvent::new(KeyCode::Right, KeyModifiers::NONE);
+        assert!(handle_input_event(right, &app).is_empty());
+
+        let star = KeyEvent::new(KeyCode::Char('*'), KeyModifiers::SHIFT);
+        assert_eq!(
+            handle_input_event(star, &app),
+            vec![AppEvent::ToggleWordHighlight]
+        );
+        app.active_tab_mut().word_cursor = Some(0);
+        assert_eq!(
+            handle_input_event(right, &app),
+            vec![AppEvent::WordCursorRight]
+        );
+        let left = KeyEvent::new(KeyCode::Left, KeyModifiers::NONE);
+        assert_eq!(
+            handle_input_event(left, &app),
+            vec![AppEvent::WordCursorLeft]
+        );
+    }
+
+    #[test]
+    fn test_shift_h_then_level_toggles_hidden_severity() {
+        let (mut app, _file) = create_test_app();
+        let key = KeyEvent::new(KeyCode::Char('H'), KeyModifiers::SHIFT);
+        assert_eq!(
+            handle_input_event(key, &app),
+            vec![AppEvent::EnterHideLevelMode]
+        );
+
+        app.input.mode = InputMode::HideLevelPending;
+        let key = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE);
+        assert_eq!(
+            handle_input_event(key, &app),
+            vec![
+                AppEvent::ToggleHiddenSeverity(Severity::Debug),
+                AppEvent::ExitHideLevelMode
+            ]
+        );
+        let key = KeyEvent::new(KeyCode::Char('H'), KeyModifiers::SHIFT);
+        assert_eq!(
+            handle_input_event(key, &app),
+            vec![AppEvent::ShowAllSeverities, AppEvent::ExitHideLevelMode]
+        );
+        let key = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(
+            handle_input_event(key, &app),
+            vec![AppEvent::ExitHideLevelMode]
+        );
+    }
+
+    #[test]
+    fn test_brackets_jump_to_errors() {
+        let (app, _file) = create_test_app();
+        let key = KeyEvent::new(KeyCode::Char('['), KeyModifiers::NONE);
+        assert_eq!(
+            handle_input_event(key, &app),
+            vec![
+                AppEvent::JumpToFirstSeverity(Severity::Error),
+                AppEvent::DisableFollowMode
+            ]
+        );
+        let key = KeyEvent::new(KeyCode::Char('}'), KeyModifiers::SHIFT);
+        assert_eq!(
+            handle_input_event(key, &app),
+            vec![
+                AppEvent::JumpToLastSeverity(Severity::Warn),
+                AppEvent::DisableFollowMode
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shift_e_expands_all_visible() {
+        let (app, _file) = create_test_app();
+        let key = KeyEvent::new(KeyCode::Char('E'), KeyModifiers::SHIFT);
+        let events = handle_input_event(key, &app);
+        assert_eq!(events, vec![AppEvent::ExpandAllVisible]);
+    }
+
     #[test]
     fn test_toggle_follow_mode() {
         let (app, _file) = create_test_app();
@@ -350,6 +493,17 @@ mod tests {
         assert_eq!(events, vec![AppEvent::FilterInputSubmit]);
     }
 
+    #[test]
+    fn test_filter_input_ctrl_enter_applies_and_keeps_open() {
+        let (mut app, _file) = create_test_app();
+        app.start_filter_input();
+        for modifiers in [KeyModifiers::CONTROL, KeyModifiers::ALT] {
+            let key = KeyEvent::new(KeyCode::Enter, modifiers);
+            let events = handle_input_event(key, &app);
+            assert_eq!(events, vec![AppEvent::FilterInputApplyKeepOpen]);
+        }
+    }
+
     #[test]
     fn test_filter_input_cancel() {
         let (mut app, _file) = create_test_app();
@@ -362,6 +516,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_filter_input_esc_keeps_filter_when_configured() {
+        let (mut app, _file) = create_test_app();
+        app.filter_esc = FilterEsc::Keep;
+        app.start_filter_input();
+        let key = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        let events = handle_input_event(key, &app);
+        assert_eq!(events, vec![AppEvent::FilterInputCancel]);
+    }
+
+    #[test]
+    fn test_command_input_enter_closes_prompt_and_runs_command() {
+        let (mut app, _file) = create_test_app();
+        app.apply_event(AppEvent::StartCommandInput);
+        for c in "level error".chars() {
+            app.apply_event(AppEvent::CommandInputChar(c));
+        }
+        let key = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(
+            handle_input_event(key, &app),
+            vec![
+                AppEvent::CommandInputCancel,
+                AppEvent::SetMinSeverity(Some(Severity::Error))
+            ]
+        );
+
+        app.input.set_content("frobnicate".to_string());
+        assert_eq!(
+            handle_input_event(key, &app),
+            vec![
+                AppEvent::CommandInputCancel,
+                AppEvent::CommandFailed("Unknown command: frobnicate".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filter_input_esc_stops_running_scan() {
+        let (mut app, _file) = create_test_app();
+        app.start_filter_input();
+        app.active_tab_mut().source.filter.state = FilterState::Processing { lines_processed: 1 };
+        let key = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(
+            handle_input_event(key, &app),
+            vec![AppEvent::StopFilterScan]
+        );
+    }
+
     #[test]
     fn test_jump_to_start() {
         let (app, _file) = create_test_app();
@@ -417,6 +619,39 @@ mod tests {
         assert_eq!(events, vec![AppEvent::ScrollHelpUp]);
     }
 
+    #[test]
+    fn test_help_search_keys() {
+        let (mut app, _file) = create_test_app();
+        app.help_scroll_offset = Some(0);
+        let key = KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE);
+        assert_eq!(
+            handle_input_event(key, &app),
+            vec![AppEvent::StartHelpSearch]
+        );
+
+        app.apply_event(AppEvent::StartHelpSearch);
+        // While typing, letters (even j/q) go to the query
+        let key = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
+        assert_eq!(
+            handle_input_event(key, &app),
+            vec![AppEvent::HelpSearchChar('q')]
+        );
+        let key = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(
+            handle_input_event(key, &app),
+            vec![AppEvent::FinishHelpSearch]
+        );
+
+        app.apply_event(AppEvent::FinishHelpSearch);
+        let key = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(
+            handle_input_event(key, &app),
+            vec![AppEvent::ClearHelpSearch]
+        );
+        let key = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(handle_input_event(key, &app), vec![AppEvent::HideHelp]);
+    }
+
     #[test]
     fn test_quit_from_help_mode() {
         let (mut app, _file) = create_test_app();
@@ -427,6 +662,22 @@ mod tests {
         assert_eq!(events, vec![AppEvent::Quit]);
     }
 
+    #[test]
+    fn test_errors_overlay_keys() {
+        let (mut app, _file) = create_test_app();
+        let key = KeyEvent::new(KeyCode::Char('!'), KeyModifiers::SHIFT);
+        assert_eq!(handle_input_event(key, &app), vec![AppEvent::ShowErrors]);
+
+        app.errors_scroll_offset = Some(0);
+        let key = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert_eq!(
+            handle_input_event(key, &app),
+            vec![AppEvent::ScrollErrorsDown]
+        );
+        let key = KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE);
+        assert_eq!(handle_input_event(key, &app), vec![AppEvent::HideErrors]);
+    }
+
     #[test]
     fn test_start_line_jump_input() {
         let (app, _file) = create_test_app();
@@ -637,6 +888,22 @@ mod tests {
         assert_eq!(events, vec![AppEvent::ToggleRawMode]);
     }
 
+    #[test]
+    fn test_cycle_renderer_key() {
+        let (app, _file) = create_test_app();
+        let key = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE);
+        let events = handle_input_event(key, &app);
+        assert_eq!(events, vec![AppEvent::CycleRenderer]);
+    }
+
+    #[test]
+    fn test_build_index_key() {
+        let (app, _file) = create_test_app();
+        let key = KeyEvent::new(KeyCode::Char('I'), KeyModifiers::NONE);
+        let events = handle_input_event(key, &app);
+        assert_eq!(events, vec![AppEvent::BuildIndex]);
+    }
+
     #[test]
     fn test_toggle_line_wrap() {
         let (app, _file) = create_test_app();
@@ -645,6 +912,22 @@ mod tests {
         assert_eq!(events, vec![AppEvent::ToggleLineWrap]);
     }
 
+    #[test]
+    fn test_toggle_reverse_order() {
+        let (app, _file) = create_test_app();
+        let key = KeyEvent::new(KeyCode::Char('o'), KeyModifiers::NONE);
+        let events = handle_input_event(key, &app);
+        assert_eq!(events, vec![AppEvent::ToggleReverseOrder]);
+    }
+
+    #[test]
+    fn test_toggle_since_marker() {
+        let (app, _file) = create_test_app();
+        let key = KeyEvent::new(KeyCode::Char('m'), KeyModifiers::NONE);
+        let events = handle_input_event(key, &app);
+        assert_eq!(events, vec![AppEvent::ToggleSinceMarker]);
+    }
+
     #[test]
     fn test_ctrl_w_closes_tab_not_wrap() {
         let (app, _file) = create_test_app();
@@ -661,6 +944,52 @@ mod tests {
         assert_eq!(events, vec![AppEvent::CopySelectedLine]);
     }
 
+    #[test]
+    fn test_shift_y_copies_filter() {
+        let (app, _file) = create_test_app();
+        let key = KeyEvent::new(KeyCode::Char('Y'), KeyModifiers::SHIFT);
+        let events = handle_input_event(key, &app);
+        assert_eq!(events, vec![AppEvent::CopyFilter]);
+    }
+
+    #[test]
+    fn test_shift_c_copies_field() {
+        let (app, _file) = create_test_app();
+        let key = KeyEvent::new(KeyCode::Char('C'), KeyModifiers::SHIFT);
+        let events = handle_input_event(key, &app);
+        assert_eq!(events, vec![AppEvent::CopyField]);
+    }
+
+    #[test]
+    fn test_shift_p_pastes_filter() {
+        let (app, _file) = create_test_app();
+        let key = KeyEvent::new(KeyCode::Char('P'), KeyModifiers::SHIFT);
+        let events = handle_input_event(key, &app);
+        assert_eq!(events, vec![AppEvent::PasteFilter]);
+    }
+
+    #[test]
+    fn test_u_reopens_last_closed_tab() {
+        let (app, _file) = create_test_app();
+        let key = KeyEvent::new(KeyCode::Char('u'), KeyModifiers::NONE);
+        let events = handle_input_event(key, &app);
+        assert_eq!(events, vec![AppEvent::ReopenLastClosed]);
+    }
+
+    #[test]
+    fn test_enter_jumps_to_source_only_in_combined_view() {
+        let (mut app, _file) = create_test_app();
+        let key = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        assert!(handle_input_event(key, &app).is_empty());
+
+        app.active_tab_mut().is_combined = true;
+        let events = handle_input_event(key, &app);
+        assert_eq!(
+            events,
+            vec![AppEvent::JumpToSourceTab, AppEvent::DisableFollowMode]
+        );
+    }
+
     #[test]
     fn test_ctrl_y_viewport_up_not_copy() {
         let (app, _file) = create_test_app();
@@ -0,0 +1,743 @@
+//! Keybinding registry for the log view, source panel and aggregation view.
+//!
+//! Input handling looks keys up here and the help overlay is generated from
+//! the same table, so the help can't drift from what the keys actually do.
+//! Prompt-style modes (filter input, line jump, menus) keep their own
+//! handlers since every printable key is text there.
+
+use crate::app::{App, AppEvent};
+use crate::index::flags::Severity;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Input mode a binding is active in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyContext {
+    Normal,
+    SourcePanel,
+    Aggregation,
+}
+
+/// Help section a binding is listed under
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Navigation,
+    Filtering,
+    Tabs,
+    SourcePanel,
+    View,
+    Aggregation,
+    Mouse,
+    General,
+}
+
+impl Category {
+    pub fn title(self) -> &'static str {
+        match self {
+            Category::Navigation => "Navigation",
+            Category::Filtering => "Filtering",
+            Category::Tabs => "Tabs",
+            Category::SourcePanel => "Source Panel",
+            Category::View => "View",
+            Category::Aggregation => "Aggregation View",
+            Category::Mouse => "Mouse",
+            Category::General => "General",
+        }
+    }
+}
+
+/// A key as matched against bindings. Shift is part of the character
+/// (`G` vs `g`) and Alt is ignored, so only Ctrl is tracked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    pub code: KeyCode,
+    pub ctrl: bool,
+}
+
+impl Key {
+    pub const fn plain(code: KeyCode) -> Self {
+        Self { code, ctrl: false }
+    }
+
+    pub const fn char(c: char) -> Self {
+        Self::plain(KeyCode::Char(c))
+    }
+
+    pub const fn ctrl(c: char) -> Self {
+        Self {
+            code: KeyCode::Char(c),
+            ctrl: true,
+        }
+    }
+
+    /// How the key is written in the help overlay
+    pub fn label(&self) -> String {
+        let base = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) if self.ctrl => c.to_ascii_uppercase().to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::PageUp => "PageUp".to_string(),
+            KeyCode::PageDown => "PageDown".to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            other => format!("{:?}", other),
+        };
+        if self.ctrl {
+            format!("Ctrl+{}", base)
+        } else {
+            base
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Binding {
+    pub keys: Vec<Key>,
+    pub contexts: &'static [KeyContext],
+    pub category: Category,
+    pub description: &'static str,
+    /// Shown in help instead of the key list, for keys that read better
+    /// as a group (`1-9`, `zz/zt/zb`)
+    label: Option<&'static str>,
+    /// The binding only applies while this holds
+    when: Option<fn(&App) -> bool>,
+    events: fn(KeyCode) -> Vec<AppEvent>,
+}
+
+impl Binding {
+    fn new(
+        category: Category,
+        contexts: &'static [KeyContext],
+        keys: &[Key],
+        description: &'static str,
+        events: fn(KeyCode) -> Vec<AppEvent>,
+    ) -> Self {
+        Self {
+            keys: keys.to_vec(),
+            contexts,
+            category,
+            description,
+            label: None,
+            when: None,
+            events,
+        }
+    }
+
+    fn label(mut self, label: &'static str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    fn when(mut self, condition: fn(&App) -> bool) -> Self {
+        self.when = Some(condition);
+        self
+    }
+
+    /// Keys as written in the help overlay
+    pub fn keys_label(&self) -> String {
+        match self.label {
+            Some(label) => label.to_string(),
+            None => self
+                .keys
+                .iter()
+                .map(Key::label)
+                .collect::<Vec<_>>()
+                .join(", "),
+        }
+    }
+
+    fn applies(&self, context: KeyContext, key: Key, app: &App) -> bool {
+        self.contexts.contains(&context)
+            && self.keys.contains(&key)
+            && self.when.is_none_or(|condition| condition(app))
+    }
+}
+
+pub struct Keymap {
+    bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            bindings: default_bindings(),
+        }
+    }
+}
+
+impl Keymap {
+    /// Events for `key` in `context`. A Ctrl chord without its own binding
+    /// falls back to the plain key.
+    pub fn events(&self, context: KeyContext, key: KeyEvent, app: &App) -> Vec<AppEvent> {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let lookup = |key: Key| {
+            self.bindings
+                .iter()
+                .find(|binding| binding.applies(context, key, app))
+        };
+        let binding = lookup(Key {
+            code: key.code,
+            ctrl,
+        })
+        .or_else(|| ctrl.then(|| lookup(Key::plain(key.code))).flatten());
+        binding.map_or_else(Vec::new, |binding| (binding.events)(key.code))
+    }
+
+    /// Bindings listed under `category`, in table order
+    pub fn in_category(&self, category: Category) -> impl Iterator<Item = &Binding> {
+        self.bindings
+            .iter()
+            .filter(move |binding| binding.category == category)
+    }
+}
+
+const NORMAL: &[KeyContext] = &[KeyContext::Normal];
+const PANEL: &[KeyContext] = &[KeyContext::SourcePanel];
+const AGGREGATION: &[KeyContext] = &[KeyContext::Aggregation];
+const NORMAL_AND_AGGREGATION: &[KeyContext] = &[KeyContext::Normal, KeyContext::Aggregation];
+const EVERYWHERE: &[KeyContext] = &[
+    KeyContext::Normal,
+    KeyContext::SourcePanel,
+    KeyContext::Aggregation,
+];
+
+fn is_combined(app: &App) -> bool {
+    app.active_tab().is_combined
+}
+
+fn is_word_highlighted(app: &App) -> bool {
+    app.active_tab().word_cursor.is_some()
+}
+
+fn default_bindings() -> Vec<Binding> {
+    use Category::*;
+    use KeyCode::{Down, Enter, Esc, Left, PageDown, PageUp, Right, Tab, Up};
+    vec![
+        // Navigation
+        Binding::new(
+            Navigation,
+            NORMAL,
+            &[Key::char('j'), Key::plain(Down)],
+            "Move selection down",
+            |_| vec![AppEvent::ScrollDown, AppEvent::DisableFollowMode],
+        ),
+        Binding::new(
+            Navigation,
+            NORMAL,
+            &[Key::char('k'), Key::plain(Up)],
+            "Move selection up",
+            |_| vec![AppEvent::ScrollUp, AppEvent::DisableFollowMode],
+        ),
+        Binding::new(
+            Navigation,
+            NORMAL,
+            &[Key::char('g')],
+            "Jump to start",
+            |_| vec![AppEvent::JumpToStart, AppEvent::DisableFollowMode],
+        ),
+        Binding::new(Navigation, NORMAL, &[Key::char('G')], "Jump to end", |_| {
+            vec![AppEvent::JumpToEnd, AppEvent::DisableFollowMode]
+        }),
+        // The page size is added by the caller, which knows the terminal size
+        Binding::new(
+            Navigation,
+            NORMAL,
+            &[Key::plain(PageUp), Key::plain(PageDown)],
+            "Scroll by page",
+            |_| vec![AppEvent::DisableFollowMode],
+        ),
+        Binding::new(
+            Navigation,
+            NORMAL,
+            &[Key::ctrl('e')],
+            "Scroll viewport down (vim-style)",
+            |_| vec![AppEvent::ViewportDown, AppEvent::DisableFollowMode],
+        ),
+        Binding::new(
+            Navigation,
+            NORMAL,
+            &[Key::ctrl('y')],
+            "Scroll viewport up (vim-style)",
+            |_| vec![AppEvent::ViewportUp, AppEvent::DisableFollowMode],
+        ),
+        Binding::new(
+            Navigation,
+            NORMAL,
+            &[Key::char(':')],
+            "Jump to line number",
+            |_| vec![AppEvent::StartLineJumpInput],
+        )
+        .label(":123"),
+        Binding::new(Navigation, NORMAL, &[Key::char('[')], "First error", |_| {
+            vec![
+                AppEvent::JumpToFirstSeverity(Severity::Error),
+                AppEvent::DisableFollowMode,
+            ]
+        }),
+        Binding::new(Navigation, NORMAL, &[Key::char(']')], "Last error", |_| {
+            vec![
+                AppEvent::JumpToLastSeverity(Severity::Error),
+                AppEvent::DisableFollowMode,
+            ]
+        }),
+        Binding::new(
+            Navigation,
+            NORMAL,
+            &[Key::char('{')],
+            "First warning",
+            |_| {
+                vec![
+                    AppEvent::JumpToFirstSeverity(Severity::Warn),
+                    AppEvent::DisableFollowMode,
+                ]
+            },
+        ),
+        Binding::new(
+            Navigation,
+            NORMAL,
+            &[Key::char('}')],
+            "Last warning",
+            |_| {
+                vec![
+                    AppEvent::JumpToLastSeverity(Severity::Warn),
+                    AppEvent::DisableFollowMode,
+                ]
+            },
+        ),
+        // Ctrl+I (vim's forward) arrives as Tab, which focuses the panel
+        Binding::new(
+            Navigation,
+            NORMAL,
+            &[Key::ctrl('o')],
+            "Back to the position before the last jump",
+            |_| vec![AppEvent::JumpBack, AppEvent::DisableFollowMode],
+        ),
+        Binding::new(
+            Navigation,
+            NORMAL,
+            &[Key::ctrl('n')],
+            "Forward again after Ctrl+O",
+            |_| vec![AppEvent::JumpForward, AppEvent::DisableFollowMode],
+        ),
+        Binding::new(
+            Navigation,
+            NORMAL,
+            &[Key::char('z')],
+            "Center/top/bottom view",
+            |_| vec![AppEvent::EnterZMode],
+        )
+        .label("zz/zt/zb"),
+        // Filtering
+        Binding::new(
+            Filtering,
+            NORMAL_AND_AGGREGATION,
+            &[Key::char('/')],
+            "Start filter (live preview)",
+            |_| vec![AppEvent::StartFilterInput],
+        ),
+        Binding::new(
+            Filtering,
+            NORMAL,
+            &[Key::char('L')],
+            "Severity floor: info+ / warn+ / error+ / off",
+            |_| vec![AppEvent::CycleMinSeverity],
+        ),
+        Binding::new(
+            Filtering,
+            NORMAL,
+            &[Key::char('H')],
+            "Hide/show a level: t/d/i/w/e/f (HH shows all)",
+            |_| vec![AppEvent::EnterHideLevelMode],
+        )
+        .label("H+level"),
+        Binding::new(
+            Filtering,
+            NORMAL,
+            &[Key::char('S')],
+            "Count lines by severity (Enter drills down)",
+            |_| vec![AppEvent::ShowSeverityBreakdown],
+        ),
+        Binding::new(
+            Filtering,
+            NORMAL,
+            &[Key::char('v')],
+            "Filter by a field/word of the line",
+            |_| vec![AppEvent::QuickFilter],
+        ),
+        Binding::new(
+            Filtering,
+            NORMAL,
+            &[Key::char('Y')],
+            "Copy filter as search arguments",
+            |_| vec![AppEvent::CopyFilter],
+        ),
+        Binding::new(
+            Filtering,
+            NORMAL,
+            &[Key::char('P')],
+            "Apply filter from clipboard",
+            |_| vec![AppEvent::PasteFilter],
+        ),
+        Binding::new(
+            Filtering,
+            NORMAL,
+            &[Key::plain(Esc)],
+            "Clear active filter",
+            |_| vec![AppEvent::ClearFilter],
+        ),
+        // Tabs
+        Binding::new(
+            Tabs,
+            NORMAL_AND_AGGREGATION,
+            &[
+                Key::char('1'),
+                Key::char('2'),
+                Key::char('3'),
+                Key::char('4'),
+                Key::char('5'),
+                Key::char('6'),
+                Key::char('7'),
+                Key::char('8'),
+                Key::char('9'),
+            ],
+            "Jump to tab",
+            |code| match code {
+                KeyCode::Char(c @ '1'..='9') => {
+                    vec![AppEvent::SelectTab((c as usize) - ('1' as usize))]
+                }
+                _ => vec![],
+            },
+        )
+        .label("1-9"),
+        Binding::new(
+            Tabs,
+            NORMAL,
+            &[Key::char('x'), Key::ctrl('w')],
+            "Close tab",
+            |_| vec![AppEvent::CloseCurrentTab],
+        ),
+        Binding::new(
+            Tabs,
+            NORMAL,
+            &[Key::char('u')],
+            "Reopen last closed tab",
+            |_| vec![AppEvent::ReopenLastClosed],
+        ),
+        Binding::new(
+            Tabs,
+            NORMAL,
+            &[Key::char('N')],
+            "Jump to the latest error in a background tab",
+            |_| vec![AppEvent::JumpToTabAlert],
+        ),
+        Binding::new(
+            Tabs,
+            NORMAL,
+            &[Key::char('R')],
+            "Refresh combined view",
+            |_| vec![AppEvent::RefreshCombinedView],
+        )
+        .when(is_combined),
+        Binding::new(
+            Tabs,
+            NORMAL,
+            &[Key::plain(Enter)],
+            "Open combined line in its source tab",
+            |_| vec![AppEvent::JumpToSourceTab, AppEvent::DisableFollowMode],
+        )
+        .when(is_combined),
+        // Source panel
+        Binding::new(
+            SourcePanel,
+            NORMAL_AND_AGGREGATION,
+            &[Key::plain(Tab)],
+            "Focus the source panel",
+            |_| vec![AppEvent::FocusSourcePanel],
+        ),
+        Binding::new(
+            SourcePanel,
+            PANEL,
+            &[Key::char('j'), Key::plain(Down)],
+            "Next item in the tree",
+            |_| vec![AppEvent::SourcePanelDown],
+        ),
+        Binding::new(
+            SourcePanel,
+            PANEL,
+            &[Key::char('k'), Key::plain(Up)],
+            "Previous item in the tree",
+            |_| vec![AppEvent::SourcePanelUp],
+        ),
+        Binding::new(
+            SourcePanel,
+            PANEL,
+            &[Key::char(' ')],
+            "Expand/collapse category",
+            |_| vec![AppEvent::ToggleCategoryExpand],
+        ),
+        Binding::new(
+            SourcePanel,
+            PANEL,
+            &[Key::plain(Enter)],
+            "Select source",
+            |_| vec![AppEvent::SelectSource],
+        ),
+        Binding::new(
+            SourcePanel,
+            PANEL,
+            &[Key::char('x'), Key::ctrl('w')],
+            "Close selected source",
+            |_| vec![AppEvent::CloseSelectedTab],
+        ),
+        Binding::new(
+            SourcePanel,
+            PANEL,
+            &[Key::char('y')],
+            "Copy source path",
+            |_| vec![AppEvent::CopySourcePath],
+        ),
+        Binding::new(
+            SourcePanel,
+            PANEL,
+            &[Key::plain(Esc), Key::plain(Tab)],
+            "Return to log view",
+            |_| vec![AppEvent::UnfocusSourcePanel],
+        ),
+        // View
+        Binding::new(
+            View,
+            NORMAL,
+            &[Key::char(' ')],
+            "Expand/collapse line",
+            |_| vec![AppEvent::ToggleLineExpansion],
+        ),
+        Binding::new(View, NORMAL, &[Key::char('c')], "Collapse all", |_| {
+            vec![AppEvent::CollapseAll]
+        }),
+        Binding::new(
+            View,
+            NORMAL,
+            &[Key::char('E')],
+            "Expand all visible",
+            |_| vec![AppEvent::ExpandAllVisible],
+        ),
+        Binding::new(
+            View,
+            NORMAL,
+            &[Key::char('f')],
+            "Toggle follow mode",
+            |_| vec![AppEvent::ToggleFollowMode],
+        ),
+        Binding::new(
+            View,
+            NORMAL,
+            &[Key::char('r')],
+            "Cycle raw mode (raw, raw without ANSI, off)",
+            |_| vec![AppEvent::ToggleRawMode],
+        ),
+        Binding::new(
+            View,
+            NORMAL,
+            &[Key::char('p')],
+            "Cycle renderer preset (configured, each preset, raw)",
+            |_| vec![AppEvent::CycleRenderer],
+        ),
+        Binding::new(View, NORMAL, &[Key::char('w')], "Toggle line wrap", |_| {
+            vec![AppEvent::ToggleLineWrap]
+        }),
+        Binding::new(
+            View,
+            NORMAL,
+            &[Key::char('t')],
+            "Toggle timestamps (arrival time for live sources)",
+            |_| vec![AppEvent::ToggleTimestamps],
+        ),
+        Binding::new(
+            View,
+            NORMAL,
+            &[Key::char('o')],
+            "Toggle newest-first order",
+            |_| vec![AppEvent::ToggleReverseOrder],
+        ),
+        Binding::new(
+            View,
+            NORMAL,
+            &[Key::char('m')],
+            "Only show new lines (mark now / reset)",
+            |_| vec![AppEvent::ToggleSinceMarker],
+        ),
+        Binding::new(
+            View,
+            NORMAL,
+            &[Key::char('*')],
+            "Highlight the word under the cursor in view",
+            |_| vec![AppEvent::ToggleWordHighlight],
+        ),
+        Binding::new(
+            View,
+            NORMAL,
+            &[Key::plain(Left), Key::plain(Right)],
+            "Move the highlighted word along the line",
+            |key| match key {
+                Left => vec![AppEvent::WordCursorLeft],
+                _ => vec![AppEvent::WordCursorRight],
+            },
+        )
+        .when(is_word_highlighted),
+        Binding::new(
+            View,
+            NORMAL,
+            &[Key::char('y')],
+            "Copy line to clipboard",
+            |_| vec![AppEvent::CopySelectedLine],
+        ),
+        Binding::new(
+            View,
+            NORMAL,
+            &[Key::char('C')],
+            "Copy a field's value (or the highlighted word)",
+            |_| vec![AppEvent::CopyField],
+        ),
+        Binding::new(
+            View,
+            NORMAL,
+            &[Key::char('T')],
+            "Follow line's trace id across sources",
+            |_| vec![AppEvent::TraceSelectedLine],
+        ),
+        Binding::new(
+            View,
+            NORMAL,
+            &[Key::char('I')],
+            "Build index for the current file",
+            |_| vec![AppEvent::BuildIndex],
+        ),
+        // Aggregation view
+        Binding::new(
+            Aggregation,
+            AGGREGATION,
+            &[Key::char('j'), Key::plain(Down)],
+            "Next group",
+            |_| vec![AppEvent::AggregationDown],
+        ),
+        Binding::new(
+            Aggregation,
+            AGGREGATION,
+            &[Key::char('k'), Key::plain(Up)],
+            "Previous group",
+            |_| vec![AppEvent::AggregationUp],
+        ),
+        Binding::new(
+            Aggregation,
+            AGGREGATION,
+            &[Key::plain(Enter)],
+            "Drill down into group",
+            |_| vec![AppEvent::AggregationDrillDown],
+        ),
+        Binding::new(
+            Aggregation,
+            AGGREGATION,
+            &[Key::plain(Esc)],
+            "Back to log view",
+            |_| vec![AppEvent::AggregationBack],
+        ),
+        Binding::new(
+            Aggregation,
+            AGGREGATION,
+            &[Key::char('g')],
+            "Jump to first group",
+            |_| vec![AppEvent::AggregationJumpToStart],
+        ),
+        Binding::new(
+            Aggregation,
+            AGGREGATION,
+            &[Key::char('G')],
+            "Jump to last group",
+            |_| vec![AppEvent::AggregationJumpToEnd],
+        ),
+        // Mouse
+        Binding::new(
+            Mouse,
+            NORMAL,
+            &[Key::char('M')],
+            "Release mouse for terminal selection / re-capture",
+            |_| vec![AppEvent::ToggleMouseCapture],
+        ),
+        // General
+        Binding::new(
+            General,
+            EVERYWHERE,
+            &[Key::char('?')],
+            "Show this help (/ to search it)",
+            |_| vec![AppEvent::ShowHelp],
+        ),
+        Binding::new(
+            General,
+            NORMAL,
+            &[Key::char(';')],
+            "Command line (filter, follow, level, goto...)",
+            |_| vec![AppEvent::StartCommandInput],
+        )
+        .label(";cmd"),
+        Binding::new(
+            General,
+            EVERYWHERE,
+            &[Key::char('!')],
+            "Show config, reload and filter errors",
+            |_| vec![AppEvent::ShowErrors],
+        ),
+        Binding::new(
+            General,
+            EVERYWHERE,
+            &[Key::char('q'), Key::ctrl('c')],
+            "Quit",
+            |_| vec![AppEvent::Quit],
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_key_is_bound_twice_in_a_context() {
+        let keymap = Keymap::default();
+        let unconditional: Vec<_> = keymap
+            .bindings
+            .iter()
+            .filter(|binding| binding.when.is_none())
+            .collect();
+        for (i, a) in unconditional.iter().enumerate() {
+            for b in &unconditional[i + 1..] {
+                let shared_context = a.contexts.iter().any(|c| b.contexts.contains(c));
+                let shared_key = a.keys.iter().any(|k| b.keys.contains(k));
+                assert!(
+                    !(shared_context && shared_key),
+                    "\"{}\" and \"{}\" share a key",
+                    a.description,
+                    b.description
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_key_labels() {
+        assert_eq!(Key::ctrl('w').label(), "Ctrl+W");
+        assert_eq!(Key::char(' ').label(), "Space");
+        assert_eq!(Key::plain(KeyCode::Down).label(), "↓");
+        let binding = Binding::new(
+            Category::Tabs,
+            NORMAL,
+            &[Key::char('x'), Key::ctrl('w')],
+            "Close tab",
+            |_| vec![],
+        );
+        assert_eq!(binding.keys_label(), "x, Ctrl+W");
+    }
+}
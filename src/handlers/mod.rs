@@ -1,3 +1,6 @@
+pub mod command;
 pub mod file_events;
 pub mod filter;
 pub mod input;
+pub mod keymap;
+pub mod mouse;
@@ -0,0 +1,72 @@
+use std::time::{Duration, Instant};
+
+/// Maximum delay between two clicks to count as a double-click
+const DOUBLE_CLICK_MS: u64 = 400;
+
+/// Detects double-clicks from the separate button-down events crossterm
+/// delivers.
+///
+/// Two clicks only pair up when they land on the same row within
+/// `DOUBLE_CLICK_MS`; a second click on a different row starts a new
+/// sequence, so it is treated as another single click.
+#[derive(Debug, Default)]
+pub struct DoubleClickDetector {
+    last_click: Option<(Instant, u16)>,
+}
+
+impl DoubleClickDetector {
+    /// Register a left-button press; returns true if it completes a double-click.
+    pub fn register(&mut self, row: u16, now: Instant) -> bool {
+        let is_double = self.last_click.is_some_and(|(at, last_row)| {
+            last_row == row && now.duration_since(at) <= Duration::from_millis(DOUBLE_CLICK_MS)
+        });
+
+        // A completed double-click resets, so a third click starts over
+        self.last_click = if is_double { None } else { Some((now, row)) };
+        is_double
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_quick_clicks_on_same_row() {
+        let mut detector = DoubleClickDetector::default();
+        let t0 = Instant::now();
+
+        assert!(!detector.register(5, t0));
+        assert!(detector.register(5, t0 + Duration::from_millis(150)));
+    }
+
+    #[test]
+    fn test_second_click_on_other_row_is_single() {
+        let mut detector = DoubleClickDetector::default();
+        let t0 = Instant::now();
+
+        assert!(!detector.register(5, t0));
+        assert!(!detector.register(6, t0 + Duration::from_millis(100)));
+        // The click on row 6 starts a new sequence
+        assert!(detector.register(6, t0 + Duration::from_millis(200)));
+    }
+
+    #[test]
+    fn test_slow_clicks_are_single() {
+        let mut detector = DoubleClickDetector::default();
+        let t0 = Instant::now();
+
+        assert!(!detector.register(5, t0));
+        assert!(!detector.register(5, t0 + Duration::from_millis(DOUBLE_CLICK_MS + 1)));
+    }
+
+    #[test]
+    fn test_triple_click_is_one_double_then_single() {
+        let mut detector = DoubleClickDetector::default();
+        let t0 = Instant::now();
+
+        assert!(!detector.register(5, t0));
+        assert!(detector.register(5, t0 + Duration::from_millis(100)));
+        assert!(!detector.register(5, t0 + Duration::from_millis(200)));
+    }
+}
@@ -107,6 +107,7 @@ mod tests {
                 "warn.*".to_string(),
                 FilterMode::Regex {
                     case_sensitive: true,
+                    negate: false,
                 },
             ),
             FilterHistoryEntry::new(
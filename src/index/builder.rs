@@ -14,9 +14,13 @@ use super::flags::{
 };
 use super::lock::IndexWriteLock;
 use super::meta::{ColumnBit, IndexMeta};
+use super::validate::{mtime_millis, sample_hash};
 
 const BATCH: usize = 1024;
 
+/// Lines between checkpoints unless configured otherwise.
+pub const DEFAULT_CHECKPOINT_INTERVAL: u16 = 100;
+
 pub fn now_millis() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -52,23 +56,46 @@ pub struct IndexBuilder {
 impl IndexBuilder {
     pub fn new() -> Self {
         Self {
-            checkpoint_interval: 100,
+            checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
         }
     }
 
-    #[allow(dead_code)]
     pub fn with_checkpoint_interval(mut self, interval: u16) -> Self {
         self.checkpoint_interval = interval;
         self
     }
 
+    /// Build the index for `log_path` into `index_dir`.
+    ///
+    /// A failed build removes whatever it wrote, so readers never see a
+    /// partial index. Use [`unwritable_reason`] to tell an index location
+    /// that can't be written apart from other failures.
     pub fn build(self, log_path: &Path, index_dir: &Path) -> Result<IndexMeta> {
-        let Some(_lock) = IndexWriteLock::try_acquire(index_dir)? else {
-            bail!("index is being written by another process, skipping");
+        let existed = index_dir.exists();
+        let lock = match IndexWriteLock::try_acquire(index_dir) {
+            Ok(Some(lock)) => lock,
+            Ok(None) => bail!("index is being written by another process, skipping"),
+            Err(e) => {
+                if !existed {
+                    let _ = std::fs::remove_dir(index_dir);
+                }
+                return Err(e);
+            }
         };
 
+        let result = self.build_locked(log_path, index_dir);
+        if result.is_err() {
+            let _ = std::fs::remove_dir_all(index_dir);
+        }
+        drop(lock);
+        result
+    }
+
+    fn build_locked(&self, log_path: &Path, index_dir: &Path) -> Result<IndexMeta> {
         std::fs::create_dir_all(index_dir)
             .with_context(|| format!("creating index dir: {}", index_dir.display()))?;
+        crate::source::record_index_source(log_path, index_dir)
+            .with_context(|| format!("recording index source: {}", index_dir.display()))?;
 
         let file = File::open(log_path)
             .with_context(|| format!("opening log file: {}", log_path.display()))?;
@@ -209,6 +236,8 @@ impl IndexBuilder {
         meta.checkpoint_interval = self.checkpoint_interval;
         meta.entry_count = line_count;
         meta.log_file_size = file_size;
+        meta.source_mtime_ms = mtime_millis(&file_meta);
+        meta.sample_hash = sample_hash(log_path, file_size).unwrap_or(0);
         self.set_columns_present(&mut meta);
         meta.write_to(index_dir.join("meta"))?;
 
@@ -230,6 +259,19 @@ impl Default for IndexBuilder {
     }
 }
 
+/// Why writing an index failed, when the cause is the index location itself
+/// (permissions or a read-only mount) rather than the log file or a bug.
+/// Retrying such a build is pointless until the location changes.
+pub fn unwritable_reason(err: &anyhow::Error) -> Option<&'static str> {
+    err.chain()
+        .filter_map(|cause| cause.downcast_ref::<std::io::Error>())
+        .find_map(|io| match io.kind() {
+            std::io::ErrorKind::PermissionDenied => Some("index directory is not writable"),
+            std::io::ErrorKind::ReadOnlyFilesystem => Some("log is on a read-only filesystem"),
+            _ => None,
+        })
+}
+
 /// Capture-time incremental indexer: accepts lines one at a time.
 ///
 /// `push_line` expects raw bytes including the trailing delimiter (`\n` or `\r\n`).
@@ -275,7 +317,7 @@ impl LineIndexer {
             flags_writer: ColumnWriter::create(index_dir.join("flags"))?,
             time_writer: ColumnWriter::create(index_dir.join("time"))?,
             checkpoint_writer: CheckpointWriter::create(index_dir.join("checkpoints"))?,
-            checkpoint_interval: 100,
+            checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
             line_count: 0,
             current_offset: 0,
             severity_counts: SeverityCounts::default(),
@@ -284,6 +326,13 @@ impl LineIndexer {
         })
     }
 
+    /// Checkpoint every `interval` lines instead of the default. Only for a
+    /// new index: a resumed one keeps the interval it was written with.
+    pub fn with_checkpoint_interval(mut self, interval: u16) -> Self {
+        self.checkpoint_interval = interval;
+        self
+    }
+
     pub fn resume(index_dir: &Path) -> Result<Self> {
         let meta = IndexMeta::read_from(index_dir.join("meta"))?;
         Self::resume_at(
@@ -478,6 +527,34 @@ mod tests {
         assert!(meta.has_column(ColumnBit::Flags));
     }
 
+    #[test]
+    fn failed_build_leaves_no_index_behind() {
+        let dir = tempdir().unwrap();
+        // A directory opens like a file but can't be mapped
+        let log = dir.path().join("not-a-file.log");
+        std::fs::create_dir(&log).unwrap();
+        let idx_dir = dir.path().join("idx");
+
+        let err = IndexBuilder::new().build(&log, &idx_dir).unwrap_err();
+        assert_eq!(unwritable_reason(&err), None);
+        assert!(!idx_dir.exists(), "partial index must be removed");
+    }
+
+    #[test]
+    fn unwritable_reason_classifies_io_errors() {
+        use std::io::{Error, ErrorKind};
+        let err = |kind| anyhow::Error::from(Error::from(kind)).context("creating index dir");
+        assert_eq!(
+            unwritable_reason(&err(ErrorKind::PermissionDenied)),
+            Some("index directory is not writable")
+        );
+        assert_eq!(
+            unwritable_reason(&err(ErrorKind::ReadOnlyFilesystem)),
+            Some("log is on a read-only filesystem")
+        );
+        assert_eq!(unwritable_reason(&err(ErrorKind::NotFound)), None);
+    }
+
     #[test]
     fn build_single_line() {
         let dir = tempdir().unwrap();
@@ -759,6 +836,26 @@ just a plain text line
         assert_eq!(flags.get(4).unwrap() & SEVERITY_MASK, SEVERITY_DEBUG);
     }
 
+    #[test]
+    fn indexer_checkpoint_interval() {
+        let dir = tempdir().unwrap();
+        let idx_dir = dir.path().join("idx");
+        let now = now_millis();
+
+        let mut indexer = LineIndexer::create(&idx_dir)
+            .unwrap()
+            .with_checkpoint_interval(10);
+        for _ in 0..25 {
+            indexer.push_line(b"line\n", now).unwrap();
+        }
+        let meta = indexer.finish(&idx_dir).unwrap();
+        assert_eq!(meta.checkpoint_interval, 10);
+
+        let ckpts = CheckpointReader::open(idx_dir.join("checkpoints")).unwrap();
+        assert_eq!(ckpts.get(0).unwrap().line_number, 10);
+        assert_eq!(ckpts.get(1).unwrap().line_number, 20);
+    }
+
     #[test]
     fn indexer_resume() {
         let dir = tempdir().unwrap();
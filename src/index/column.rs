@@ -1,5 +1,5 @@
 use std::fs::{File, OpenOptions};
-use std::io::{BufWriter, Write};
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
 use std::marker::PhantomData;
 use std::path::Path;
 
@@ -188,6 +188,17 @@ impl<T: ColumnElement> ColumnReader<T> {
     }
 }
 
+/// Read one entry straight from a column file without mapping it, so a
+/// concurrent writer truncating the file can't fault the caller. Returns
+/// `None` if the file is missing or shorter than the entry.
+pub fn read_entry<T: ColumnElement>(path: impl AsRef<Path>, index: usize) -> Option<T> {
+    let mut file = File::open(path).ok()?;
+    file.seek(SeekFrom::Start((index * T::SIZE) as u64)).ok()?;
+    let mut buf = [0u8; 8];
+    file.read_exact(&mut buf[..T::SIZE]).ok()?;
+    Some(T::read_le(&buf))
+}
+
 pub struct ColumnIter<'a, T: ColumnElement> {
     reader: &'a ColumnReader<T>,
     index: usize,
@@ -215,6 +226,21 @@ mod tests {
     use super::*;
     use tempfile::tempdir;
 
+    // --- read_entry ---
+
+    #[test]
+    fn read_entry_reads_without_mapping() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("col");
+        let mut writer = ColumnWriter::<u64>::create(&path).unwrap();
+        writer.push_batch(&[7, 42]).unwrap();
+        writer.flush().unwrap();
+
+        assert_eq!(read_entry::<u64>(&path, 1), Some(42));
+        assert_eq!(read_entry::<u64>(&path, 2), None);
+        assert_eq!(read_entry::<u64>(dir.path().join("missing"), 0), None);
+    }
+
     // --- ColumnElement trait ---
 
     #[test]
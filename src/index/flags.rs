@@ -71,6 +71,20 @@ impl Severity {
             Severity::Unknown => None,
         }
     }
+
+    /// Parse a label produced by [`Severity::label`].
+    pub fn from_label(label: &str) -> Option<Self> {
+        [
+            Severity::Trace,
+            Severity::Debug,
+            Severity::Info,
+            Severity::Warn,
+            Severity::Error,
+            Severity::Fatal,
+        ]
+        .into_iter()
+        .find(|s| s.label() == Some(label))
+    }
 }
 
 /// Extract the template ID (bits 16-31) from a flags value.
@@ -140,6 +154,8 @@ pub fn detect_flags(line: &str) -> u32 {
 /// on the first keyword match — no multiple passes, no SIMD setup overhead.
 /// Cache-friendly sequential access; most lines match within the first 30-60 bytes.
 #[inline]
+// Arms mirror the `w` arm, which checks two keywords
+#[allow(clippy::collapsible_match)]
 fn detect_severity_single_pass(bytes: &[u8]) -> u32 {
     let len = bytes.len();
     let mut i = 0;
@@ -201,6 +217,8 @@ fn detect_severity_single_pass(bytes: &[u8]) -> u32 {
 /// Fallback for lines containing ANSI escapes — `memchr` can't distinguish
 /// keyword bytes inside vs outside escape sequences, so this scans byte-by-byte
 /// with inline ANSI skipping. Scans the full line.
+// Arms mirror the `w` arm, which checks two keywords
+#[allow(clippy::collapsible_match)]
 fn detect_severity_scalar(bytes: &[u8]) -> u32 {
     let len = bytes.len();
     let mut i = 0;
@@ -29,6 +29,12 @@ pub struct IndexMeta {
     pub log_file_size: u64,
     pub columns_present: u64,
     pub flags_schema: u16,
+    /// Modification time of the log file (ms since epoch) when the index was
+    /// built. 0 if not recorded.
+    pub source_mtime_ms: u64,
+    /// Hash of sampled content from the indexed range of the log file, used
+    /// to detect a file replaced by different content. 0 if not recorded.
+    pub sample_hash: u64,
 }
 
 impl IndexMeta {
@@ -40,6 +46,8 @@ impl IndexMeta {
             log_file_size: 0,
             columns_present: 0,
             flags_schema: 1,
+            source_mtime_ms: 0,
+            sample_hash: 0,
         }
     }
 
@@ -64,7 +72,9 @@ impl IndexMeta {
         buf[16..24].copy_from_slice(&self.log_file_size.to_le_bytes());
         buf[24..32].copy_from_slice(&self.columns_present.to_le_bytes());
         buf[32..34].copy_from_slice(&self.flags_schema.to_le_bytes());
-        // bytes 34..64 are reserved (zeros)
+        buf[34..42].copy_from_slice(&self.source_mtime_ms.to_le_bytes());
+        buf[42..50].copy_from_slice(&self.sample_hash.to_le_bytes());
+        // bytes 50..64 are reserved (zeros)
         buf
     }
 
@@ -83,6 +93,8 @@ impl IndexMeta {
             log_file_size: u64::from_le_bytes(buf[16..24].try_into().unwrap()),
             columns_present: u64::from_le_bytes(buf[24..32].try_into().unwrap()),
             flags_schema: u16::from_le_bytes([buf[32], buf[33]]),
+            source_mtime_ms: u64::from_le_bytes(buf[34..42].try_into().unwrap()),
+            sample_hash: u64::from_le_bytes(buf[42..50].try_into().unwrap()),
         })
     }
 
@@ -109,6 +121,17 @@ impl IndexMeta {
             .map_err(|_| anyhow::anyhow!("meta slice conversion failed"))?;
         Self::from_bytes(&buf)
     }
+
+    /// Whether the meta file at `path` was written by an older index format.
+    ///
+    /// Such an index can't be read and should be rebuilt. A newer version is
+    /// not outdated: it belongs to a newer lazytail and is left alone.
+    pub fn is_outdated(path: impl AsRef<Path>) -> bool {
+        let Ok(data) = std::fs::read(path.as_ref()) else {
+            return false;
+        };
+        data.len() >= 6 && &data[0..4] == MAGIC && u16::from_le_bytes([data[4], data[5]]) < VERSION
+    }
 }
 
 impl Default for IndexMeta {
@@ -130,6 +153,8 @@ mod tests {
         meta.checkpoint_interval = 100;
         meta.set_column(ColumnBit::Offsets);
         meta.set_column(ColumnBit::Flags);
+        meta.source_mtime_ms = 1_700_000_000_000;
+        meta.sample_hash = 0xdead_beef_cafe_f00d;
 
         let bytes = meta.to_bytes();
         let restored = IndexMeta::from_bytes(&bytes).unwrap();
@@ -153,6 +178,25 @@ mod tests {
         assert!(err.to_string().contains("unsupported index version"));
     }
 
+    #[test]
+    fn outdated_version_is_detected() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("meta");
+        assert!(!IndexMeta::is_outdated(&path));
+
+        IndexMeta::new().write_to(&path).unwrap();
+        assert!(!IndexMeta::is_outdated(&path));
+
+        let mut buf = IndexMeta::new().to_bytes();
+        buf[4..6].copy_from_slice(&0u16.to_le_bytes());
+        std::fs::write(&path, buf).unwrap();
+        assert!(IndexMeta::is_outdated(&path));
+
+        buf[4..6].copy_from_slice(&(VERSION + 1).to_le_bytes());
+        std::fs::write(&path, buf).unwrap();
+        assert!(!IndexMeta::is_outdated(&path));
+    }
+
     #[test]
     fn column_bit_operations() {
         let mut meta = IndexMeta::new();
@@ -201,6 +245,8 @@ mod tests {
             log_file_size: 0,
             columns_present: 0,
             flags_schema: 1,
+            source_mtime_ms: 0,
+            sample_hash: 0x1112131415161718,
         };
         let bytes = meta.to_bytes();
 
@@ -210,6 +256,9 @@ mod tests {
         assert_eq!(&bytes[4..6], &1u16.to_le_bytes());
         // entry_count at offset 8 (LE)
         assert_eq!(&bytes[8..16], &0x0102030405060708u64.to_le_bytes());
+        // sample_hash at offset 42 (LE), followed by reserved zeros
+        assert_eq!(&bytes[42..50], &0x1112131415161718u64.to_le_bytes());
+        assert!(bytes[50..].iter().all(|&b| b == 0));
     }
 
     #[test]
@@ -1,9 +1,9 @@
 use crate::index::checkpoint::{Checkpoint, CheckpointReader, SeverityCounts};
-use crate::index::column::ColumnReader;
+use crate::index::column::{read_entry, ColumnReader};
 use crate::index::flags::Severity;
 use crate::index::meta::{ColumnBit, IndexMeta};
 use crate::source::index_dir_for_log;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Aggregated index statistics for a log file.
 pub struct IndexStats {
@@ -29,6 +29,9 @@ pub struct IndexReader {
     timestamps: Vec<u64>,
     /// Cached severity counts, maintained incrementally on refresh.
     cached_severity_counts: SeverityCounts,
+    /// Index directory, when it has the offsets and lengths columns that
+    /// [`byte_range`](Self::byte_range) reads on demand.
+    range_columns_dir: Option<PathBuf>,
 }
 
 impl IndexReader {
@@ -72,12 +75,17 @@ impl IndexReader {
             Vec::new()
         };
 
+        let range_columns_dir = (meta.has_column(ColumnBit::Offsets)
+            && meta.has_column(ColumnBit::Lengths))
+        .then_some(idx_dir);
+
         let cached_severity_counts = Self::count_severity(&flags);
         Some(Self {
             flags,
             checkpoints,
             timestamps,
             cached_severity_counts,
+            range_columns_dir,
         })
     }
 
@@ -104,16 +112,30 @@ impl IndexReader {
     ///
     /// Incrementally extends existing Vecs with only the new entries, avoiding
     /// re-reading the entire column on each refresh.
-    pub fn refresh(&mut self, log_path: &Path) {
+    ///
+    /// Returns `false` when the log file no longer matches the index (it was
+    /// truncated below the indexed range or replaced by different content).
+    /// The reader is then stale and should be dropped and the index rebuilt.
+    pub fn refresh(&mut self, log_path: &Path) -> bool {
         let idx_dir = index_dir_for_log(log_path);
         let meta = match IndexMeta::read_from(idx_dir.join("meta")).ok() {
             Some(m) => m,
-            None => return,
+            None => return true,
         };
 
+        if let Ok(file_meta) = std::fs::metadata(log_path) {
+            if file_meta.len() < meta.log_file_size
+                || !crate::index::validate::fingerprint_matches(
+                    log_path, &idx_dir, &file_meta, &meta,
+                )
+            {
+                return false;
+            }
+        }
+
         let new_count = meta.entry_count as usize;
         if new_count <= self.flags.len() {
-            return; // No new data
+            return true; // No new data
         }
 
         let old_count = self.flags.len();
@@ -143,6 +165,7 @@ impl IndexReader {
                     .extend((old_count..new_count).filter_map(|i| col.get(i)));
             }
         }
+        true
     }
 
     /// Get the severity level for a specific line.
@@ -164,6 +187,32 @@ impl IndexReader {
         self.flags.get(line_number).copied()
     }
 
+    /// Byte range of a line in the source file as `(start, end)`, with `end`
+    /// exclusive and the line terminator (`\n` or `\r\n`) left out, so
+    /// `file[start..end]` is exactly the line's content.
+    ///
+    /// Offsets are read from the index on each call rather than kept in
+    /// memory, so this suits lookups for individual lines (editor
+    /// integrations, jumping to an offset) rather than bulk scans. Returns
+    /// `None` for lines past the indexed range or when the index has no
+    /// offset columns.
+    ///
+    /// ```ignore
+    /// let index = IndexReader::open(Path::new("app.log")).unwrap();
+    /// if let Some((start, end)) = index.byte_range(41) {
+    ///     println!("line 42 is bytes {}..{}", start, end);
+    /// }
+    /// ```
+    pub fn byte_range(&self, line: usize) -> Option<(u64, u64)> {
+        if line >= self.flags.len() {
+            return None;
+        }
+        let dir = self.range_columns_dir.as_ref()?;
+        let start: u64 = read_entry(dir.join("offsets"), line)?;
+        let length: u32 = read_entry(dir.join("lengths"), line)?;
+        Some((start, start + length as u64))
+    }
+
     /// Number of indexed lines.
     pub fn len(&self) -> usize {
         self.flags.len()
@@ -333,6 +382,7 @@ impl IndexReader {
             checkpoints: Vec::new(),
             timestamps: timestamps.to_vec(),
             cached_severity_counts: SeverityCounts::default(),
+            range_columns_dir: None,
         }
     }
 }
@@ -351,6 +401,7 @@ mod tests {
             checkpoints: Vec::new(),
             timestamps: Vec::new(),
             cached_severity_counts,
+            range_columns_dir: None,
         }
     }
 
@@ -463,6 +514,82 @@ mod tests {
         assert_eq!(bitmap, vec![true, false, false, true]);
     }
 
+    // --- byte_range() ---
+
+    #[test]
+    fn test_byte_range_slices_line_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("app.log");
+        let content = "first\r\nsecond line\n\nlast";
+        std::fs::write(&log_path, content).unwrap();
+        crate::index::builder::IndexBuilder::new()
+            .build(&log_path, &index_dir_for_log(&log_path))
+            .unwrap();
+
+        let reader = IndexReader::open(&log_path).unwrap();
+        let lines: Vec<&str> = (0..reader.len())
+            .map(|i| {
+                let (start, end) = reader.byte_range(i).unwrap();
+                &content[start as usize..end as usize]
+            })
+            .collect();
+        assert_eq!(lines, ["first", "second line", "", "last"]);
+        assert_eq!(reader.byte_range(4), None);
+    }
+
+    #[test]
+    fn test_byte_range_needs_offset_columns() {
+        assert_eq!(reader_from(&[SEVERITY_INFO]).byte_range(0), None);
+    }
+
+    // --- source fingerprint ---
+
+    /// Helper: a 300-line log indexed by IndexBuilder (checkpoints every 100 lines).
+    fn build_fingerprinted_log(dir: &Path) -> (std::path::PathBuf, String) {
+        let log_path = dir.join("app.log");
+        let content: String = (0..300).map(|i| format!("line {i:04}\n")).collect();
+        std::fs::write(&log_path, &content).unwrap();
+        crate::index::builder::IndexBuilder::new()
+            .build(&log_path, &index_dir_for_log(&log_path))
+            .unwrap();
+        (log_path, content)
+    }
+
+    #[test]
+    fn test_open_rejects_swapped_content_between_checkpoints() {
+        let dir = tempfile::tempdir().unwrap();
+        let (log_path, content) = build_fingerprinted_log(dir.path());
+
+        // Same size and line boundaries; only a line no checkpoint covers differs
+        std::fs::write(&log_path, content.replace("line 0150", "LINE 0150")).unwrap();
+
+        assert!(IndexReader::open(&log_path).is_none());
+    }
+
+    #[test]
+    fn test_refresh_detects_swapped_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let (log_path, content) = build_fingerprinted_log(dir.path());
+        let mut reader = IndexReader::open(&log_path).unwrap();
+        assert!(reader.refresh(&log_path));
+
+        std::fs::write(&log_path, content.replace("line 0150", "LINE 0150")).unwrap();
+
+        assert!(!reader.refresh(&log_path));
+    }
+
+    #[test]
+    fn test_fingerprint_accepts_appended_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let (log_path, content) = build_fingerprinted_log(dir.path());
+        let mut reader = IndexReader::open(&log_path).unwrap();
+
+        std::fs::write(&log_path, content + "line 0300\n").unwrap();
+
+        assert!(reader.refresh(&log_path));
+        assert_eq!(IndexReader::open(&log_path).unwrap().len(), 300);
+    }
+
     // --- stats() stale index validation ---
 
     /// Helper: create a log file and its index directory with offsets + flags columns.
@@ -1,11 +1,15 @@
 use std::fs::File;
 use std::io::{Read, Seek, SeekFrom};
 use std::path::Path;
+use std::time::UNIX_EPOCH;
 
 use crate::index::checkpoint::CheckpointReader;
 use crate::index::column::ColumnReader;
 use crate::index::meta::{ColumnBit, IndexMeta};
 
+/// Size of each content sample hashed into the index fingerprint.
+const SAMPLE_LEN: u64 = 4096;
+
 /// Result of index validation with partial trust support.
 pub struct ValidatedIndex {
     /// Number of index entries that can be trusted.
@@ -24,13 +28,19 @@ pub struct ValidatedIndex {
 /// 2. **Checkpoint walk** — walks checkpoints from last to first, verifying content
 ///    hashes against the actual file. Returns partial trust at the first valid checkpoint.
 pub fn validate_index(idx_dir: &Path, log_path: &Path, meta: &IndexMeta) -> Option<ValidatedIndex> {
-    let file_size = std::fs::metadata(log_path).ok()?.len();
+    let file_meta = std::fs::metadata(log_path).ok()?;
+    let file_size = file_meta.len();
 
     // File was truncated below indexed range
     if file_size < meta.log_file_size {
         return None;
     }
 
+    // File was replaced by different content of at least the indexed size
+    if !fingerprint_matches(log_path, idx_dir, &file_meta, meta) {
+        return None;
+    }
+
     let entry_count = meta.entry_count as usize;
 
     // Empty index
@@ -174,6 +184,59 @@ pub fn validate_index(idx_dir: &Path, log_path: &Path, meta: &IndexMeta) -> Opti
     })
 }
 
+/// Check the log file against the fingerprint recorded in the index meta.
+///
+/// Returns `true` when the meta carries no fingerprint (indexes written by
+/// capture mode) or when the file is untouched since the build (same size
+/// and modification time). Otherwise the sampled content of the indexed
+/// range is hashed again and compared, so appends keep the index valid while
+/// a replaced file does not.
+///
+/// The size/mtime shortcut is only taken when the recorded mtime is strictly
+/// older than the meta file itself: a write landing in the same timestamp
+/// tick as the build would otherwise go unnoticed.
+pub fn fingerprint_matches(
+    log_path: &Path,
+    idx_dir: &Path,
+    file_meta: &std::fs::Metadata,
+    meta: &IndexMeta,
+) -> bool {
+    if meta.sample_hash == 0 {
+        return true;
+    }
+    let unchanged = file_meta.len() == meta.log_file_size
+        && mtime_millis(file_meta) == meta.source_mtime_ms
+        && std::fs::metadata(idx_dir.join("meta"))
+            .is_ok_and(|m| meta.source_mtime_ms < mtime_millis(&m));
+    unchanged || sample_hash(log_path, meta.log_file_size) == Some(meta.sample_hash)
+}
+
+/// Hash samples from the start, middle and end of the first `indexed_size`
+/// bytes of the log file, together with the size itself.
+pub fn sample_hash(log_path: &Path, indexed_size: u64) -> Option<u64> {
+    let mut file = File::open(log_path).ok()?;
+    let len = SAMPLE_LEN.min(indexed_size);
+    let mut buf = vec![0u8; len as usize];
+    let mut hasher = xxhash_rust::xxh3::Xxh3::new();
+    for start in [0, (indexed_size - len) / 2, indexed_size - len] {
+        file.seek(SeekFrom::Start(start)).ok()?;
+        file.read_exact(&mut buf).ok()?;
+        hasher.update(&buf);
+    }
+    hasher.update(&indexed_size.to_le_bytes());
+    Some(hasher.digest())
+}
+
+/// Modification time of a file in milliseconds since the Unix epoch, or 0 if
+/// the platform does not report one.
+pub fn mtime_millis(file_meta: &std::fs::Metadata) -> u64 {
+    file_meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_millis() as u64)
+}
+
 /// Verify a checkpoint's content hash against the actual log file.
 ///
 /// `max_bytes` caps how many bytes to read from the file (to avoid reading
@@ -0,0 +1,115 @@
+//! Line-level diff between successive versions of a rewritten file.
+//!
+//! Used by `--diff-filter`: status dumps and `/proc`-style snapshots are
+//! overwritten rather than appended, so the interesting part of a reload is
+//! which lines differ from the previous version.
+
+use std::collections::HashMap;
+use xxhash_rust::xxh3::xxh3_64;
+
+/// Hashes of the previous version plus the lines that changed in the latest.
+#[derive(Debug, Clone, Default)]
+pub struct LineDiff {
+    previous: Vec<u64>,
+    /// Line numbers in the current version that are new or modified
+    pub changed: Vec<usize>,
+}
+
+impl LineDiff {
+    /// Baseline from the initial content; every line counts as changed.
+    pub fn new(data: &[u8]) -> Self {
+        let previous = line_hashes(data);
+        let changed = (0..previous.len()).collect();
+        Self { previous, changed }
+    }
+
+    /// Compare `data` against the previous version and remember it.
+    ///
+    /// Returns `false` (leaving `changed` alone) when the content is
+    /// identical.
+    pub fn update(&mut self, data: &[u8]) -> bool {
+        let current = line_hashes(data);
+        if current == self.previous {
+            return false;
+        }
+        self.changed = changed_lines(&self.previous, &current);
+        self.previous = current;
+        true
+    }
+}
+
+/// Hash of every line in `data` (a trailing newline does not start a line).
+fn line_hashes(data: &[u8]) -> Vec<u64> {
+    let data = data.strip_suffix(b"\n").unwrap_or(data);
+    if data.is_empty() {
+        return Vec::new();
+    }
+    data.split(|&b| b == b'\n')
+        .map(|line| xxh3_64(line.strip_suffix(b"\r").unwrap_or(line)))
+        .collect()
+}
+
+/// Lines of `new` without a counterpart in `old`.
+///
+/// The common prefix and suffix are unchanged; in between, a line is
+/// unchanged if an equal line is still unclaimed in the old middle section.
+/// Cheaper than a full LCS and good enough for snapshots where a few values
+/// change in place.
+fn changed_lines(old: &[u64], new: &[u64]) -> Vec<usize> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut unclaimed: HashMap<u64, usize> = HashMap::new();
+    for &hash in &old[prefix..old.len() - suffix] {
+        *unclaimed.entry(hash).or_default() += 1;
+    }
+
+    (prefix..new.len() - suffix)
+        .filter(|&line| match unclaimed.get_mut(&new[line]) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_initial_content_is_all_changed() {
+        let diff = LineDiff::new(b"a\nb\n");
+        assert_eq!(diff.changed, vec![0, 1]);
+        assert!(LineDiff::new(b"").changed.is_empty());
+    }
+
+    #[test]
+    fn test_in_place_value_change() {
+        let mut diff = LineDiff::new(b"cpu: 10\nmem: 50\ndisk: 70\n");
+        assert!(diff.update(b"cpu: 12\nmem: 50\ndisk: 71\n"));
+        assert_eq!(diff.changed, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_insertion_does_not_mark_shifted_lines() {
+        let mut diff = LineDiff::new(b"a\nb\nc\n");
+        assert!(diff.update(b"a\nnew\nb\nc"));
+        assert_eq!(diff.changed, vec![1]);
+    }
+
+    #[test]
+    fn test_identical_content_keeps_previous_changes() {
+        let mut diff = LineDiff::new(b"a\n");
+        assert!(diff.update(b"b\n"));
+        assert!(!diff.update(b"b\r\n"));
+        assert_eq!(diff.changed, vec![0]);
+    }
+}
@@ -1,11 +1,17 @@
-use crate::app::{FilterState, ViewMode};
-use crate::filter::aggregation::AggregationResult;
+use crate::app::{FilterState, LineIndices, RawMode, RendererOverride, ViewMode};
+use crate::config::types::DefaultFilterConfig;
+use crate::filter::aggregation::{AggregationAccumulator, AggregationResult};
 use crate::filter::cancel::CancelToken;
 use crate::filter::engine::FilterProgress;
 use crate::filter::query::{Aggregation, Parser};
+use crate::filter::result_cache::{ResultCache, ResultKey};
 use crate::filter::FilterMode;
+use crate::index::flags::Severity;
 use crate::index::reader::IndexReader;
+use crate::line_diff::LineDiff;
+use crate::reader::encoding::Encoding;
 use crate::reader::LogReader;
+use crate::renderer::transform::DisplayTransform;
 use crate::source::SourceStatus;
 use std::collections::VecDeque;
 use std::path::{Path, PathBuf};
@@ -88,6 +94,31 @@ impl Default for LineRateTracker {
     }
 }
 
+/// When each streamed line arrived, for sources without embedded
+/// timestamps. Lines appended in one batch share a time, so this keeps one
+/// entry per batch rather than per line.
+#[derive(Debug, Default)]
+pub struct ArrivalTimes {
+    /// (first line of the batch, epoch milliseconds), in line order
+    batches: Vec<(usize, u64)>,
+}
+
+impl ArrivalTimes {
+    /// Record that lines from `first_line` onward arrived at `epoch_ms`.
+    pub fn record(&mut self, first_line: usize, epoch_ms: u64) {
+        match self.batches.last() {
+            Some(&(_, last_ms)) if last_ms == epoch_ms => {}
+            _ => self.batches.push((first_line, epoch_ms)),
+        }
+    }
+
+    /// Arrival time of `line` in epoch milliseconds, if it was recorded.
+    pub fn get(&self, line: usize) -> Option<u64> {
+        let pos = self.batches.partition_point(|&(first, _)| first <= line);
+        pos.checked_sub(1).map(|i| self.batches[i].1)
+    }
+}
+
 /// Filter-related state for a source
 #[derive(Default)]
 pub struct FilterConfig {
@@ -105,16 +136,66 @@ pub struct FilterConfig {
     pub is_incremental: bool,
     /// Last line number that was filtered (for incremental filtering)
     pub last_filtered_line: usize,
+    /// Line count the running filter covers; becomes `last_filtered_line`
+    /// when it completes, so lines added meanwhile are filtered next
+    pub filtering_to: Option<usize>,
     /// Original line when filter started (for restoring on Esc)
     pub origin_line: Option<usize>,
     /// Flag to clear results when first partial results arrive (prevents blink)
     pub needs_clear: bool,
     /// Pending aggregation to compute when filter completes
     pub pending_aggregation: Option<(Aggregation, Parser)>,
+    /// Running group counts for the pending aggregation, covering the
+    /// matches in `line_indices`
+    pub aggregation_progress: Option<AggregationAccumulator>,
     /// Saved aggregation result for drill-down return
     pub drill_down_aggregation: Option<AggregationResult>,
     /// Saved filter pattern during drill-down
     pub drill_down_pattern: Option<String>,
+    /// Severity floor ANDed with the text filter (lines below it are hidden)
+    pub min_severity: Option<Severity>,
+    /// Severities hidden from the view whatever the text filter, in
+    /// severity order
+    pub hidden_severities: Vec<Severity>,
+    /// Results of recent full runs, replayed instead of rescanning
+    pub result_cache: ResultCache,
+    /// Key of the completed full run `line_indices` currently holds
+    pub results_key: Option<ResultKey>,
+}
+
+impl FilterConfig {
+    /// Whether a severity floor or hidden severities filter the view.
+    pub fn filters_severity(&self) -> bool {
+        self.min_severity.is_some() || !self.hidden_severities.is_empty()
+    }
+
+    /// Whether lines of `severity` pass the severity floor and aren't hidden.
+    pub fn keeps_severity(&self, severity: Severity) -> bool {
+        self.min_severity
+            .is_none_or(|min| severity.to_bits() >= min.to_bits())
+            && !self.hidden_severities.contains(&severity)
+    }
+
+    /// Whether only severities filter the view, with no text filter.
+    pub fn is_severity_only(&self) -> bool {
+        self.pattern.is_none() && self.filters_severity()
+    }
+
+    /// Whether the view is filtered, by text or by severity.
+    pub fn is_filtering(&self) -> bool {
+        self.pattern.is_some() || self.filters_severity()
+    }
+
+    /// Pattern and mode a scan of this filter runs with: the text filter,
+    /// or for a severity-only view an empty plain pattern, which matches
+    /// every line.
+    pub fn scan(&self) -> Option<(String, FilterMode)> {
+        match &self.pattern {
+            Some(pattern) => Some((pattern.clone(), self.mode)),
+            None if self.filters_severity() => Some((String::new(), FilterMode::plain())),
+            None => None,
+        }
+    }
 }
 
 /// Domain-only state for a log source, shared across TUI and Web adapters.
@@ -133,37 +214,70 @@ pub struct LogSource {
     /// Total number of lines in the source
     pub total_lines: usize,
     /// Indices of lines to display (all lines or filtered results)
-    pub line_indices: Vec<usize>,
+    pub line_indices: LineIndices,
     /// Follow mode - auto-scroll to latest logs
     pub follow_mode: bool,
-    /// Raw mode - bypass preset rendering and ANSI parsing
-    pub raw_mode: bool,
+    /// Total lines when navigating away paused follow mode (None = not paused)
+    pub follow_paused_at: Option<usize>,
+    /// Raw mode - bypass presets, transforms, highlights and tab expansion
+    pub raw_mode: RawMode,
     /// Line wrap mode - wrap long lines instead of truncating
     pub line_wrap: bool,
     /// Show arrival timestamps next to line numbers
     pub show_timestamps: bool,
+    /// Since-marker: hide lines before this file line (None = show all)
+    pub since_line: Option<usize>,
     /// Per-source reader
     pub reader: Arc<Mutex<dyn LogReader + Send>>,
+    /// Text encoding the file is decoded with, by the reader and by filters
+    pub encoding: Encoding,
     /// Filter configuration and state
     pub filter: FilterConfig,
     /// Source status for discovered sources (Active/Ended)
     pub source_status: Option<SourceStatus>,
+    /// When the source was seen going from Active to Ended (None while
+    /// active, or if it had ended before the tab opened)
+    pub ended_at: Option<Instant>,
     /// Whether this source is disabled (file doesn't exist)
     pub disabled: bool,
     /// File size in bytes (None for stdin/pipes without a file path)
     pub file_size: Option<u64>,
+    /// (device, inode) of the file at source_path, used to detect rotation
+    pub file_id: Option<(u64, u64)>,
     /// Columnar index reader for severity coloring and stats (None if no index)
     pub index_reader: Option<IndexReader>,
     /// Index directory size in bytes (None if no index)
     pub index_size: Option<u64>,
     /// Warning when index exists but is broken/stale
     pub index_warning: Option<String>,
+    /// Whether building an index has already been suggested for this source
+    pub index_suggested: bool,
+    /// Why indexing is off for this source (its index location can't be
+    /// written); no builds are attempted and filters scan the file
+    pub index_disabled: Option<&'static str>,
+    /// Background index build in progress (receives the outcome)
+    pub index_build: Option<Receiver<anyhow::Result<()>>>,
     /// Tracks line ingestion rate
     pub rate_tracker: LineRateTracker,
+    /// Arrival times of streamed lines (empty for files)
+    pub arrival_times: ArrivalTimes,
     /// Aggregation result for grouped query views
     pub aggregation_result: Option<AggregationResult>,
+    /// Severity breakdown counting in the background (receives the result)
+    pub severity_breakdown: Option<Receiver<AggregationResult>>,
     /// Renderer preset names for this source (empty = auto-detect)
     pub renderer_names: Vec<String>,
+    /// Renderer picked at runtime (None = `renderer_names`)
+    pub renderer_override: Option<RendererOverride>,
+    /// Regex rewrite applied to displayed lines (from config)
+    pub display_transform: Option<DisplayTransform>,
+    /// Filter applied when the tab opens (from config)
+    pub default_filter: Option<DefaultFilterConfig>,
+    /// Lines are folded JSON objects, pretty-printed when expanded
+    pub multiline_json: bool,
+    /// Diff mode: the unfiltered view shows only lines changed by the last
+    /// reload (None = show everything)
+    pub diff: Option<LineDiff>,
 }
 
 impl LogSource {
@@ -177,27 +291,43 @@ impl LogSource {
             source_path: None,
             mode: ViewMode::Normal,
             total_lines: 0,
-            line_indices: Vec::new(),
+            line_indices: LineIndices::default(),
             follow_mode: true,
-            raw_mode: false,
+            follow_paused_at: None,
+            raw_mode: RawMode::Off,
             line_wrap: false,
             show_timestamps: false,
+            since_line: None,
             reader,
+            encoding: Encoding::default(),
             filter: FilterConfig::default(),
             source_status: None,
+            ended_at: None,
             disabled: false,
             file_size: None,
+            file_id: None,
             index_reader: None,
             index_size: None,
             index_warning: None,
+            index_suggested: false,
+            index_disabled: None,
+            index_build: None,
             rate_tracker: LineRateTracker::new(0),
+            arrival_times: ArrivalTimes::default(),
             aggregation_result: None,
+            severity_breakdown: None,
             renderer_names: Vec::new(),
+            renderer_override: None,
+            display_transform: None,
+            default_filter: None,
+            multiline_json: false,
+            diff: None,
         }
     }
 
-    /// Set the source file path.
+    /// Set the source file path (and record the identity of the file there).
     pub fn with_path(mut self, path: PathBuf) -> Self {
+        self.file_id = crate::reader::file_identity(&path);
         self.source_path = Some(path);
         self
     }
@@ -205,7 +335,7 @@ impl LogSource {
     /// Set total lines and populate line_indices with 0..total_lines.
     pub fn with_lines(mut self, total_lines: usize) -> Self {
         self.total_lines = total_lines;
-        self.line_indices = (0..total_lines).collect();
+        self.line_indices = LineIndices::all(total_lines);
         self.rate_tracker = LineRateTracker::new(total_lines);
         self
     }
@@ -241,6 +371,33 @@ impl LogSource {
         self
     }
 
+    /// Set the display transform.
+    pub fn with_display_transform(mut self, transform: Option<DisplayTransform>) -> Self {
+        self.display_transform = transform;
+        self
+    }
+
+    /// Set the filter applied when the tab opens.
+    pub fn with_default_filter(mut self, filter: Option<DefaultFilterConfig>) -> Self {
+        self.default_filter = filter;
+        self
+    }
+
+    /// Mark lines as folded JSON objects (pretty-printed on expand).
+    pub fn with_multiline_json(mut self) -> Self {
+        self.multiline_json = true;
+        self
+    }
+
+    /// Whether the active filter is the source's configured default filter.
+    pub fn is_default_filter_active(&self) -> bool {
+        self.mode == ViewMode::Filtered
+            && self.default_filter.as_ref().is_some_and(|d| {
+                self.filter.pattern.as_deref() == Some(d.pattern.as_str())
+                    && self.filter.mode == d.filter_mode()
+            })
+    }
+
     /// Mark this source as disabled (file doesn't exist).
     pub fn into_disabled(mut self) -> Self {
         self.disabled = true;
@@ -259,4 +416,99 @@ impl LogSource {
     pub fn file_path(&self) -> Option<&Path> {
         self.source_path.as_deref()
     }
+
+    /// Lines that arrived since follow mode was paused (0 when not paused).
+    pub fn new_lines_while_paused(&self) -> usize {
+        self.follow_paused_at
+            .map_or(0, |at| self.total_lines.saturating_sub(at))
+    }
+
+    /// First file line shown in the unfiltered view (the since-marker, or 0).
+    pub fn first_visible_line(&self) -> usize {
+        self.since_line.unwrap_or(0)
+    }
+
+    /// Line indices for the unfiltered view.
+    pub fn unfiltered_indices(&self) -> LineIndices {
+        match &self.diff {
+            Some(diff) => diff
+                .changed
+                .iter()
+                .copied()
+                .filter(|&line| line >= self.first_visible_line() && line < self.total_lines)
+                .collect(),
+            None => LineIndices::Range(self.first_visible_line()..self.total_lines),
+        }
+    }
+
+    /// Append newly arrived lines to the unfiltered view.
+    ///
+    /// No-op in diff mode, where a reload replaces the view instead.
+    pub fn extend_unfiltered_indices(&mut self, new_total: usize) {
+        if self.diff.is_some() {
+            return;
+        }
+        let next = self
+            .line_indices
+            .last()
+            .map_or(self.first_visible_line(), |last| last + 1);
+        if new_total > next {
+            self.line_indices.extend_range(next..new_total);
+        }
+    }
+
+    /// Step the runtime renderer through the configured renderers, each of
+    /// `presets`, then raw, and back to the configured ones.
+    pub fn cycle_renderer(&mut self, presets: &[&str]) {
+        let next = match &self.renderer_override {
+            None => 0,
+            Some(RendererOverride::Preset(name)) => presets
+                .iter()
+                .position(|preset| preset == name)
+                .map_or(0, |idx| idx + 1),
+            Some(RendererOverride::Raw) => {
+                self.renderer_override = None;
+                return;
+            }
+        };
+        self.renderer_override = Some(match presets.get(next) {
+            Some(name) => RendererOverride::Preset(name.to_string()),
+            None => RendererOverride::Raw,
+        });
+    }
+
+    /// Lines added since the filter results were computed that aren't in
+    /// them yet: an incremental run is (or is about to start) catching up.
+    pub fn unfiltered_new_lines(&self) -> usize {
+        let catching_up = match self.filter.state {
+            FilterState::Complete { .. } => true,
+            FilterState::Processing { .. } => self.filter.is_incremental,
+            FilterState::Inactive => false,
+        };
+        if !catching_up || !self.filter.is_filtering() {
+            return 0;
+        }
+        self.total_lines
+            .saturating_sub(self.filter.last_filtered_line)
+    }
+
+    /// Cache key for a full run of `pattern` in the source's current state.
+    pub fn result_key(&self, pattern: &str, mode: FilterMode) -> ResultKey {
+        ResultKey {
+            pattern: pattern.to_string(),
+            mode,
+            min_severity: self.filter.min_severity,
+            hidden_severities: self.filter.hidden_severities.clone(),
+            since_line: self.since_line,
+            total_lines: self.total_lines,
+        }
+    }
+
+    /// Drop indices before the since-marker from sorted line indices.
+    pub fn project_since_marker(&self, indices: &mut Vec<usize>) {
+        if let Some(since) = self.since_line {
+            let cut = indices.partition_point(|&idx| idx < since);
+            indices.drain(..cut);
+        }
+    }
 }
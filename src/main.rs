@@ -2,15 +2,18 @@ mod ansi;
 mod app;
 mod capture;
 mod cli;
+mod clipboard;
 mod config;
 mod filter;
 mod filter_orchestrator;
 mod handlers;
 mod history;
+mod line_diff;
 mod log_source;
 #[cfg(feature = "mcp")]
 mod mcp;
 mod parsing;
+mod power;
 mod reader;
 mod renderer;
 mod session;
@@ -28,6 +31,7 @@ mod web;
 mod test_utils;
 
 use anyhow::{Context, Result};
+use app::tab_manager::DEFAULT_COMBINED_MIN_SOURCES;
 use app::{App, AppEvent, FilterState, SourceType, StreamMessage, TabState, ViewMode};
 use clap::Parser;
 use crossterm::{
@@ -35,8 +39,10 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use handlers::mouse::DoubleClickDetector;
 use lazytail::index;
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{backend::CrosstermBackend, layout::Rect, Terminal, TerminalOptions};
+use reader::rotated_reader::RotatedReader;
 use std::io;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -44,8 +50,13 @@ use std::time::{Duration, Instant};
 
 // Constants
 const INPUT_POLL_DURATION_MS: u64 = 100;
+/// Input poll timeout in low-resource mode
+const LOW_RESOURCE_INPUT_POLL_MS: u64 = 250;
+/// Seconds between file size polls (normal, low-resource)
+const FILE_POLL_SECS: (u64, u64) = (1, 5);
+/// Seconds between discovered source status refreshes (normal, low-resource)
+const STATUS_REFRESH_SECS: (u64, u64) = (2, 10);
 const PAGE_SIZE_OFFSET: usize = 5;
-const MOUSE_SCROLL_LINES: usize = 3;
 
 #[derive(Parser, Debug)]
 #[command(name = "lazytail")]
@@ -60,6 +71,8 @@ EXAMPLES:
     lazytail app.log                    View a single log file
     lazytail app.log error.log          View multiple files in tabs
     kubectl logs pod | lazytail         Pipe logs from any command
+    cmd | lazytail --save out.log       Pipe logs and keep a copy in out.log
+    cmd | lazytail --merge-stdin a.log  Follow the pipe merged with a.log in one tab
     lazytail                            Discover sources from ~/.config/lazytail/data/
 
 CAPTURE MODE:
@@ -78,10 +91,70 @@ struct Cli {
     #[arg(value_name = "FILE")]
     files: Vec<PathBuf>,
 
+    /// View FILE together with its rotated siblings (FILE.1, FILE.2.gz, ...)
+    /// as one source, oldest first
+    #[arg(long = "rotated", value_name = "FILE")]
+    rotated: Vec<PathBuf>,
+
+    /// Show only lines that changed since the previous reload (for files
+    /// that are rewritten in place, like status dumps)
+    #[arg(long = "diff-filter")]
+    diff_filter: bool,
+
+    /// Run the command given after `--` and view its output, restarting it
+    /// with backoff whenever it exits (e.g. `kubectl logs -f`, `ssh host tail -F`)
+    #[arg(long = "respawn", requires = "respawn_command")]
+    respawn: bool,
+
+    /// Command run by --respawn
+    #[arg(last = true, value_name = "CMD", requires = "respawn")]
+    respawn_command: Vec<String>,
+
+    /// Accept line-oriented TCP connections on ADDR (e.g. 0.0.0.0:5140) and
+    /// show their lines as a live source. Plaintext and unauthenticated:
+    /// use on trusted networks only
+    #[arg(long = "listen", value_name = "ADDR")]
+    listen: Option<String>,
+
+    /// Receive syslog datagrams (RFC 3164/5424) on UDP ADDR (e.g.
+    /// 0.0.0.0:514) and show them with parsed severity as a live source.
+    /// Plaintext and unauthenticated: use on trusted networks only
+    #[arg(long = "syslog-udp", value_name = "ADDR")]
+    syslog_udp: Option<String>,
+
     /// Disable file watching (files won't auto-reload on changes)
     #[arg(long = "no-watch")]
     no_watch: bool,
 
+    /// Wait for FILEs that don't exist yet and follow them once they appear
+    /// (like `tail -F`)
+    #[arg(long = "wait")]
+    wait: bool,
+
+    /// Open files even if they look binary (contain NUL bytes)
+    #[arg(long = "force-text")]
+    force_text: bool,
+
+    /// Quit after SECONDS without a key press or mouse event (for demos,
+    /// CI previews and kiosk displays)
+    #[arg(long = "idle-exit", value_name = "SECONDS")]
+    idle_exit: Option<u64>,
+
+    /// Don't capture the mouse, so the terminal's native text selection
+    /// works (overrides `mouse_capture` in config; toggle at runtime with M)
+    #[arg(long = "no-mouse")]
+    no_mouse: bool,
+
+    /// Trade speed for battery: skip building missing indexes at startup and
+    /// poll files and input less often (overrides `low_resource` in config)
+    #[arg(long = "low-resource")]
+    low_resource: bool,
+
+    /// Show a pipe's unterminated last line while it is still being written,
+    /// instead of waiting for its newline (overrides `line_buffered` in config)
+    #[arg(long = "no-line-buffer")]
+    no_line_buffer: bool,
+
     /// Capture stdin to a named source file (tee-like behavior)
     ///
     /// Writes stdin to ~/.config/lazytail/data/<NAME>.log while echoing to stdout.
@@ -93,6 +166,42 @@ struct Cli {
     #[arg(long = "raw")]
     raw: bool,
 
+    /// Color theme for this run, overriding the config (built-in or imported;
+    /// see `lazytail theme list`)
+    #[arg(long = "theme", value_name = "NAME")]
+    theme: Option<String>,
+
+    /// Config profile to add on top of the base config (its sources,
+    /// renderers and highlights; see `profiles` in lazytail.yaml)
+    #[arg(long = "profile", value_name = "NAME", global = true)]
+    profile: Option<String>,
+
+    /// Text encoding of log files (utf-8, latin1, windows-1252, latin9)
+    #[arg(long = "encoding", value_name = "NAME")]
+    encoding: Option<reader::encoding::Encoding>,
+
+    /// Force the render width in columns (for deterministic rendering)
+    #[arg(long = "width", value_name = "COLS", hide = true)]
+    width: Option<u16>,
+
+    /// Force the render height in rows (for deterministic rendering)
+    #[arg(
+        long = "height",
+        alias = "lines-per-screen",
+        value_name = "ROWS",
+        hide = true
+    )]
+    height: Option<u16>,
+
+    /// Save piped stdin to FILE while viewing it
+    #[arg(long = "save", value_name = "FILE", conflicts_with = "name")]
+    save: Option<PathBuf>,
+
+    /// Show piped stdin and the FILE arguments merged in one tab: the files'
+    /// lines first, stdin's appended as they arrive
+    #[arg(long = "merge-stdin", conflicts_with_all = ["name", "wait"])]
+    merge_stdin: bool,
+
     /// Run as MCP (Model Context Protocol) server
     ///
     /// Starts an MCP server using stdio transport for AI assistant integration.
@@ -105,6 +214,10 @@ struct Cli {
     #[arg(short = 'v', long = "verbose")]
     verbose: bool,
 
+    /// Suppress non-error startup output (index building, update notices)
+    #[arg(short = 'q', long = "quiet", conflicts_with = "verbose")]
+    quiet: bool,
+
     /// Disable background update check on startup
     #[cfg(feature = "self-update")]
     #[arg(long = "no-update-check")]
@@ -131,18 +244,24 @@ fn main() -> Result<()> {
         return match command {
             cli::Commands::Init(args) => cli::init::run(args.force)
                 .map_err(|code| anyhow::anyhow!("init failed with exit code {}", code)),
-            cli::Commands::Web(args) => {
-                web::run(args).map_err(|code| anyhow::anyhow!("web failed with exit code {}", code))
-            }
+            cli::Commands::Web(args) => web::run(args, cli.profile.as_deref())
+                .map_err(|code| anyhow::anyhow!("web failed with exit code {}", code)),
             cli::Commands::Bench(args) => cli::bench::run(args)
                 .map_err(|code| anyhow::anyhow!("bench failed with exit code {}", code)),
             cli::Commands::Config { action } => match action {
-                cli::ConfigAction::Validate => cli::config::validate().map_err(|code| {
-                    anyhow::anyhow!("config validate failed with exit code {}", code)
-                }),
-                cli::ConfigAction::Show => cli::config::show()
+                cli::ConfigAction::Validate => cli::config::validate(cli.profile.as_deref())
+                    .map_err(|code| {
+                        anyhow::anyhow!("config validate failed with exit code {}", code)
+                    }),
+                cli::ConfigAction::Show => cli::config::show(cli.profile.as_deref())
                     .map_err(|code| anyhow::anyhow!("config show failed with exit code {}", code)),
             },
+            cli::Commands::Index { action } => match action {
+                cli::IndexAction::Gc(args) => cli::index::run_gc(args)
+                    .map_err(|code| anyhow::anyhow!("index gc failed with exit code {}", code)),
+            },
+            cli::Commands::Render(args) => cli::render::run(args, cli.profile.as_deref())
+                .map_err(|code| anyhow::anyhow!("render failed with exit code {}", code)),
             cli::Commands::Theme { action } => match action {
                 cli::ThemeAction::Import(args) => cli::theme::run_import(args)
                     .map_err(|code| anyhow::anyhow!("theme import failed with exit code {}", code)),
@@ -201,9 +320,14 @@ fn main() -> Result<()> {
 
     // Load config from discovered files
     phase = Instant::now();
-    let config_result = config::load(&discovery);
-    let (cfg, mut config_errors) = match config_result {
+    let config_result = config::load_with_profile(&discovery, cli.profile.as_deref());
+    let (mut cfg, mut config_errors) = match config_result {
         Ok(c) => (c, Vec::new()),
+        // An explicitly requested profile must exist
+        Err(err @ config::error::ConfigError::UnknownProfile { .. }) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
         Err(err) => {
             let err_msg = err.to_string();
             (config::Config::default(), vec![err_msg])
@@ -212,16 +336,50 @@ fn main() -> Result<()> {
     if verbose {
         eprintln!("[startup]   config load: {:.1?}", phase.elapsed());
     }
+    source::configure_indexes(&cfg);
 
     if verbose {
         if let Some(name) = &cfg.name {
             eprintln!("[config] Project name: {}", name);
         }
+        if let Some(profile) = &cfg.profile {
+            eprintln!("[config] Profile: {}", profile);
+        }
         eprintln!("[config] Project sources: {}", cfg.project_sources.len());
         eprintln!("[config] Global sources: {}", cfg.global_sources.len());
         for err in &config_errors {
             eprintln!("[config] Error: {}", err);
         }
+        let themes_dirs = theme::loader::collect_themes_dirs(discovery.project_root.as_deref());
+        for collision in theme::loader::theme_collisions(&themes_dirs) {
+            eprintln!("[config] Warning: {}", collision);
+        }
+    }
+
+    // CLI theme overrides the config key for this run
+    if let Some(name) = &cli.theme {
+        let themes_dirs = theme::loader::collect_themes_dirs(discovery.project_root.as_deref());
+        match theme::loader::resolve_theme_name(name, &themes_dirs) {
+            Ok(theme) => cfg.theme = theme,
+            Err(message) => {
+                eprintln!("Error: {}", message);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if cli.no_mouse {
+        cfg.mouse_capture = Some(false);
+    }
+    if cli.low_resource {
+        cfg.low_resource = Some(config::types::LowResource::On);
+    }
+    let low_resource = power::low_resource_active(cfg.low_resource.unwrap_or_default());
+    if low_resource {
+        source::defer_index_builds();
+    }
+    if cli.no_line_buffer {
+        cfg.line_buffered = Some(false);
     }
 
     // Spawn background update check (if self-update feature is enabled)
@@ -242,6 +400,12 @@ fn main() -> Result<()> {
     config_errors.extend(compile_errors);
     let preset_registry = Arc::new(registry);
 
+    // Compile always-on highlight rules from config
+    let (highlights, highlight_errors) =
+        renderer::highlight::HighlightSet::compile(&cfg.highlights);
+    config_errors.extend(highlight_errors);
+    let highlights = Arc::new(highlights);
+
     // Auto-detect stdin: if nothing is piped and no files given, check for other modes
     let stdin_is_tty = std::io::stdin().is_terminal();
     let has_piped_input = !stdin_is_tty;
@@ -270,8 +434,34 @@ fn main() -> Result<()> {
         );
     }
 
+    let fixed_area = fixed_viewport(cli.width, cli.height);
+    // CLI encoding overrides the config key
+    if cli.encoding.is_some() {
+        cfg.encoding = cli.encoding;
+    }
+
+    if cli.save.is_some() && !has_piped_input {
+        eprintln!("Error: --save requires piped stdin");
+        eprintln!("Usage: command | lazytail --save <FILE>");
+        std::process::exit(1);
+    }
+
+    let stdin_arg = cli.files.iter().any(|f| f.as_os_str() == "-");
+    let merge_files = cli.files.iter().filter(|f| f.as_os_str() != "-").count();
+    if cli.merge_stdin && (!(has_piped_input || stdin_arg) || merge_files == 0) {
+        eprintln!("Error: --merge-stdin requires piped stdin and at least one FILE");
+        eprintln!("Usage: command | lazytail --merge-stdin <FILE>...");
+        std::process::exit(1);
+    }
+
     // Mode 2: Discovery mode (no files, no stdin)
-    if cli.files.is_empty() && !has_piped_input {
+    if cli.files.is_empty()
+        && cli.rotated.is_empty()
+        && !cli.respawn
+        && cli.listen.is_none()
+        && cli.syslog_udp.is_none()
+        && !has_piped_input
+    {
         let result = run_discovery_mode(
             cli.no_watch,
             cfg,
@@ -279,7 +469,12 @@ fn main() -> Result<()> {
             &discovery,
             startup,
             verbose,
+            cli.quiet,
             preset_registry,
+            highlights,
+            fixed_area,
+            cli.idle_exit,
+            low_resource,
         );
         #[cfg(feature = "self-update")]
         print_update_notice(update_handle);
@@ -292,15 +487,34 @@ fn main() -> Result<()> {
 
     // Build tabs from config sources first
     phase = Instant::now();
-    let mut tabs = build_config_tabs(&cfg, watch, &mut config_errors);
+    let mut open_files = reader::OpenFiles::new(cfg.dedup_files.unwrap_or(true));
+    let mut duplicates = Vec::new();
+    let mut tabs = build_config_tabs(
+        &cfg,
+        watch,
+        &mut config_errors,
+        &mut open_files,
+        &mut duplicates,
+    );
     let first_cli_tab_idx = tabs.len(); // index where CLI tabs start
 
+    // Tab already showing the first CLI file, when that file was a duplicate
+    let mut first_cli_duplicate: Option<usize> = None;
+
     // Build tabs from CLI args, treating "-" as stdin
     let mut stdin_used = false;
-    let has_cli_files = has_piped_input || !cli.files.is_empty();
+    // Where the stdin tab went, for --merge-stdin
+    let mut stdin_tab_idx = None;
+    let has_cli_files = has_piped_input
+        || !cli.files.is_empty()
+        || !cli.rotated.is_empty()
+        || cli.respawn
+        || cli.listen.is_some()
+        || cli.syslog_udp.is_some();
 
     // If stdin has piped data, always include it as the first tab
     if has_piped_input {
+        stdin_tab_idx = Some(tabs.len());
         tabs.push(TabState::from_stdin().context("Failed to read from stdin")?);
         stdin_used = true;
     }
@@ -312,45 +526,119 @@ fn main() -> Result<()> {
                 continue;
             }
             stdin_used = true;
+            stdin_tab_idx = Some(tabs.len());
             tabs.push(TabState::from_stdin().context("Failed to read from stdin")?);
+        } else if let Some(existing) = find_duplicate(&open_files, &tabs, &file, &mut duplicates) {
+            if tabs.len() == first_cli_tab_idx && first_cli_duplicate.is_none() {
+                first_cli_duplicate = Some(existing);
+            }
         } else {
-            tabs.push(TabState::new(file, watch).context("Failed to open log file")?);
+            let tab = if cli.wait && !file.exists() {
+                TabState::waiting_for(file.clone(), watch, cli.force_text)?
+            } else {
+                TabState::open(file.clone(), watch, cli.force_text)
+                    .context("Failed to open log file")?
+            };
+            open_files.insert(&file, tabs.len());
+            tabs.push(tab);
         }
     }
+    // Stdin and plain FILE tabs, merged into one with --merge-stdin
+    let merge_range = first_cli_tab_idx..tabs.len();
+    for file in cli.rotated {
+        if find_duplicate(&open_files, &tabs, &file, &mut duplicates).is_some() {
+            continue;
+        }
+        let tab =
+            TabState::from_rotated(file.clone(), watch).context("Failed to open rotated log")?;
+        open_files.insert(&file, tabs.len());
+        tabs.push(tab);
+    }
+    if cli.respawn {
+        tabs.push(TabState::from_command(cli.respawn_command)?);
+    }
+    if let Some(addr) = &cli.listen {
+        tabs.push(TabState::from_listener(addr)?);
+    }
+    if let Some(addr) = &cli.syslog_udp {
+        tabs.push(TabState::from_syslog_udp(addr)?);
+    }
+    if cli.diff_filter {
+        for tab in &mut tabs[first_cli_tab_idx..] {
+            if tab.source.source_path.is_some() && !tab.is_waiting() {
+                tab.enable_diff()?;
+            }
+        }
+    }
+    // Stdin is always the first CLI tab when piped
+    if let Some(ref save_path) = cli.save {
+        tabs[first_cli_tab_idx].tee_to_file(save_path)?;
+    }
     if verbose {
         eprintln!("[startup]   tab creation: {:.1?}", phase.elapsed());
     }
 
     // Build columnar indexes for file tabs that don't have one yet
     phase = Instant::now();
-    build_tab_indexes(&tabs);
+    build_tab_indexes(&mut tabs, cli.quiet, cfg.index_checkpoint_interval());
     if verbose {
         eprintln!("[startup]   index build: {:.1?}", phase.elapsed());
     }
 
-    // Log config errors to stderr (debug source is a future enhancement)
-    for err in &config_errors {
-        eprintln!("[config error] {}", err);
+    // After indexing, so the files merge by their index timestamps
+    // (streams from FIFOs and process substitution stay separate tabs)
+    if cli.merge_stdin {
+        let mut stdin_tab = None;
+        let mut file_tabs = Vec::new();
+        let mut kept = Vec::new();
+        for (idx, tab) in tabs.drain(merge_range.clone()).enumerate() {
+            match tab.source_type() {
+                SourceType::File => file_tabs.push(tab),
+                SourceType::Pipe if stdin_tab_idx == Some(merge_range.start + idx) => {
+                    stdin_tab = Some(tab)
+                }
+                _ => kept.push(tab),
+            }
+        }
+        let mut merged = match stdin_tab {
+            Some(stdin_tab) => vec![TabState::merge_stdin(stdin_tab, file_tabs)],
+            None => file_tabs,
+        };
+        merged.extend(kept);
+        tabs.splice(merge_range.start..merge_range.start, merged);
     }
 
     phase = Instant::now();
     let mut app = App::with_tabs(tabs, preset_registry);
     app.startup_time = Some(startup);
     app.verbose = verbose;
-    app.theme = cfg.theme;
+    app.idle_exit = cli.idle_exit.map(Duration::from_secs);
+    app.highlights = highlights;
+    apply_config(&mut app, &cfg);
+    if low_resource {
+        app.enable_low_resource();
+    }
+    app.report_disabled_indexes();
+    app.report_startup_errors(config_errors);
+    app.apply_default_filters();
     app.tab_mgr.ensure_combined_tabs();
 
     // Restore last active source from session
     let project_root = discovery.project_root.as_deref();
     restore_last_source(&mut app, project_root);
+    restore_expanded_lines(&mut app, project_root);
+    restore_hidden_severities(&mut app, project_root);
 
     // When CLI files are passed, default to the first CLI tab instead of config tabs
     if has_cli_files && first_cli_tab_idx < app.tab_mgr.tabs.len() {
         app.select_tab(first_cli_tab_idx);
+    } else if let Some(idx) = first_cli_duplicate {
+        app.select_tab(idx);
     }
+    report_duplicates(&mut app, &duplicates);
 
     // Setup terminal
-    let mut terminal = setup_terminal()?;
+    let mut terminal = setup_terminal(fixed_area, app.mouse_capture)?;
     if verbose {
         eprintln!("[startup]   terminal setup: {:.1?}", phase.elapsed());
     }
@@ -358,11 +646,13 @@ fn main() -> Result<()> {
     // Main loop
     let res = run_app(&mut terminal, &mut app);
 
-    // Save active source to session
+    // Save active source and expanded lines to session
     save_active_source(&app, project_root);
+    save_expanded_lines(&app, project_root);
+    save_hidden_severities(&app, project_root);
 
     // Restore terminal
-    restore_terminal(&mut terminal)?;
+    restore_terminal(&mut terminal, app.mouse_capture)?;
 
     if app.verbose {
         if let Some(elapsed) = app.first_render_elapsed {
@@ -381,6 +671,7 @@ fn main() -> Result<()> {
 }
 
 /// Run in discovery mode: auto-discover sources from project and global data directories
+#[allow(clippy::too_many_arguments)]
 fn run_discovery_mode(
     no_watch: bool,
     cfg: config::Config,
@@ -388,7 +679,12 @@ fn run_discovery_mode(
     discovery: &config::DiscoveryResult,
     startup: Instant,
     verbose: bool,
+    quiet: bool,
     preset_registry: Arc<renderer::PresetRegistry>,
+    highlights: Arc<renderer::highlight::HighlightSet>,
+    fixed_area: Option<Rect>,
+    idle_exit: Option<u64>,
+    low_resource: bool,
 ) -> Result<()> {
     use source::{discover_sources_for_context, ensure_directories_for_context};
 
@@ -402,49 +698,71 @@ fn run_discovery_mode(
         eprintln!("[startup]   source discovery: {:.1?}", phase.elapsed());
     }
 
-    // Build columnar indexes for sources that don't have one yet
+    // Build columnar indexes for sources that don't have one yet (deferred
+    // to `I` in low-resource mode)
     phase = Instant::now();
-    source::build_missing_indexes(&sources);
+    let unwritable_indexes = if low_resource {
+        Vec::new()
+    } else {
+        source::build_missing_indexes(&sources, quiet, cfg.index_checkpoint_interval())
+    };
     if verbose {
         eprintln!("[startup]   index build: {:.1?}", phase.elapsed());
     }
 
     let watch = !no_watch;
 
-    // Build source name → renderer_names map from config sources
-    let source_renderer_map: std::collections::HashMap<String, Vec<String>> = cfg
-        .project_sources
-        .iter()
-        .chain(cfg.global_sources.iter())
-        .filter(|s| !s.renderer_names.is_empty())
-        .map(|s| (s.name.clone(), s.renderer_names.clone()))
-        .collect();
+    // Per-source settings from config, by source name
+    let source_renderer_map = config_source_map(&cfg, |s| {
+        (!s.renderer_names.is_empty()).then(|| s.renderer_names.clone())
+    });
+    let source_transform_map = config_source_map(&cfg, |s| s.display_transform.clone());
+    let source_default_filter_map = config_source_map(&cfg, |s| s.default_filter.clone());
 
     // Build tabs from config sources first, then add discovered sources
     phase = Instant::now();
-    let mut tabs = build_config_tabs(&cfg, watch, &mut config_errors);
+    let mut open_files = reader::OpenFiles::new(cfg.dedup_files.unwrap_or(true));
+    let mut duplicates = Vec::new();
+    let mut tabs = build_config_tabs(
+        &cfg,
+        watch,
+        &mut config_errors,
+        &mut open_files,
+        &mut duplicates,
+    );
 
     // Add discovered sources (with renderer_names from config if available)
-    let discovery_tabs: Vec<TabState> = sources
-        .into_iter()
-        .filter_map(|s| {
-            let renderers = source_renderer_map
-                .get(&s.name)
-                .cloned()
-                .unwrap_or_default();
-            TabState::from_discovered_source(s, watch, renderers).ok()
-        })
-        .collect();
-    tabs.extend(discovery_tabs);
+    for s in sources {
+        if find_duplicate(&open_files, &tabs, &s.log_path, &mut duplicates).is_some() {
+            continue;
+        }
+        let renderers = source_renderer_map
+            .get(&s.name)
+            .cloned()
+            .unwrap_or_default();
+        let transform = source_transform_map.get(&s.name).cloned();
+        let default_filter = source_default_filter_map.get(&s.name).cloned();
+        let log_path = s.log_path.clone();
+        if let Ok(tab) =
+            TabState::from_discovered_source(s, watch, renderers, transform, default_filter)
+        {
+            open_files.insert(&log_path, tabs.len());
+            tabs.push(tab);
+        }
+    }
+    for tab in &mut tabs {
+        if let Some(format_renderers) = &cfg.format_renderers {
+            tab.apply_format_renderer(format_renderers);
+        }
+        tab.source.index_disabled = unwritable_indexes
+            .iter()
+            .find(|(path, _)| tab.source.source_path.as_ref() == Some(path))
+            .map(|&(_, reason)| reason);
+    }
     if verbose {
         eprintln!("[startup]   tab creation: {:.1?}", phase.elapsed());
     }
 
-    // Log config errors to stderr (debug source is a future enhancement)
-    for err in &config_errors {
-        eprintln!("[config error] {}", err);
-    }
-
     if tabs.is_empty() {
         eprintln!("No log sources found.");
         eprintln!();
@@ -459,13 +777,23 @@ fn run_discovery_mode(
     let mut app = App::with_tabs(tabs, preset_registry);
     app.startup_time = Some(startup);
     app.verbose = verbose;
-    app.theme = cfg.theme;
-    app.source_renderer_map = source_renderer_map;
+    app.idle_exit = idle_exit.map(Duration::from_secs);
+    app.highlights = highlights;
+    apply_config(&mut app, &cfg);
+    if low_resource {
+        app.enable_low_resource();
+    }
+    app.report_disabled_indexes();
+    app.report_startup_errors(config_errors);
+    app.apply_default_filters();
     app.tab_mgr.ensure_combined_tabs();
 
     // Restore last active source from session
     let project_root = discovery.project_root.as_deref();
     restore_last_source(&mut app, project_root);
+    restore_expanded_lines(&mut app, project_root);
+    restore_hidden_severities(&mut app, project_root);
+    report_duplicates(&mut app, &duplicates);
 
     // Optionally set up directory watcher for new sources
     // Watch project data dir if in project, otherwise global
@@ -481,7 +809,7 @@ fn run_discovery_mode(
     };
 
     // Setup terminal
-    let mut terminal = setup_terminal()?;
+    let mut terminal = setup_terminal(fixed_area, app.mouse_capture)?;
     if verbose {
         eprintln!("[startup]   terminal setup: {:.1?}", phase.elapsed());
     }
@@ -496,11 +824,13 @@ fn run_discovery_mode(
     // Main loop with directory watcher
     let res = run_app_with_discovery(&mut terminal, &mut app, dir_watcher, watched_location);
 
-    // Save active source to session
+    // Save active source and expanded lines to session
     save_active_source(&app, project_root);
+    save_expanded_lines(&app, project_root);
+    save_hidden_severities(&app, project_root);
 
     // Restore terminal
-    restore_terminal(&mut terminal)?;
+    restore_terminal(&mut terminal, app.mouse_capture)?;
 
     if app.verbose {
         if let Some(elapsed) = app.first_render_elapsed {
@@ -530,6 +860,86 @@ fn restore_last_source(app: &mut App, project_root: Option<&std::path::Path>) {
     }
 }
 
+/// Re-expand lines remembered from the previous session for file-backed tabs.
+fn restore_expanded_lines(app: &mut App, project_root: Option<&std::path::Path>) {
+    let saved = session::load_expanded_lines(project_root);
+    if saved.is_empty() {
+        return;
+    }
+    for tab in &mut app.tab_mgr.tabs {
+        let key = tab.file_path().map(expansion_session_key);
+        if let Some(lines) = key.and_then(|k| saved.get(&k)) {
+            tab.restore_expanded_lines(lines);
+        }
+    }
+}
+
+/// Save expanded lines of file-backed tabs to session.
+fn save_expanded_lines(app: &App, project_root: Option<&std::path::Path>) {
+    let sources: Vec<(String, Vec<usize>)> = app
+        .tab_mgr
+        .tabs
+        .iter()
+        .filter(|tab| !tab.is_combined)
+        .filter_map(|tab| {
+            let path = tab.file_path()?;
+            let lines = tab.expansion.expanded_lines.iter().copied().collect();
+            Some((expansion_session_key(path), lines))
+        })
+        .collect();
+    session::save_expanded_lines(project_root, &sources);
+}
+
+/// Re-hide the severities remembered from the previous session for
+/// file-backed tabs.
+fn restore_hidden_severities(app: &mut App, project_root: Option<&std::path::Path>) {
+    let saved = session::load_hidden_severities(project_root);
+    if saved.is_empty() {
+        return;
+    }
+    for tab in &mut app.tab_mgr.tabs {
+        let key = tab.file_path().map(expansion_session_key);
+        if let Some(labels) = key.and_then(|k| saved.get(&k)) {
+            let levels = labels
+                .iter()
+                .filter_map(|l| index::flags::Severity::from_label(l));
+            let _ = tab.set_hidden_severities(levels.collect());
+        }
+    }
+}
+
+/// Save hidden severities of file-backed tabs to session.
+fn save_hidden_severities(app: &App, project_root: Option<&std::path::Path>) {
+    let sources: Vec<(String, Vec<String>)> = app
+        .tab_mgr
+        .tabs
+        .iter()
+        .filter(|tab| !tab.is_combined)
+        .filter_map(|tab| {
+            let path = tab.file_path()?;
+            let labels = tab
+                .source
+                .filter
+                .hidden_severities
+                .iter()
+                .filter_map(|s| s.label())
+                .map(str::to_string)
+                .collect();
+            Some((expansion_session_key(path), labels))
+        })
+        .collect();
+    session::save_hidden_severities(project_root, &sources);
+}
+
+/// Session key for a tab's per-source state: the absolute file path, so the
+/// same file opened via a relative path matches.
+fn expansion_session_key(path: &std::path::Path) -> String {
+    std::fs::canonicalize(path)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .display()
+        .to_string()
+}
+
 /// Save the active source name to session.
 fn save_active_source(app: &App, project_root: Option<&std::path::Path>) {
     if let Some(tab) = app.tab_mgr.tabs.get(app.tab_mgr.active) {
@@ -540,23 +950,103 @@ fn save_active_source(app: &App, project_root: Option<&std::path::Path>) {
     }
 }
 
+/// Apply the config's viewer settings to a freshly built app. Shared by
+/// both startup paths so they can't drift apart.
+fn apply_config(app: &mut App, cfg: &config::Config) {
+    app.theme = cfg.theme.clone();
+    if let Some(max) = cfg.max_filter_history {
+        app.filter.set_max_history(max);
+    }
+    if let Some(encoding) = cfg.encoding {
+        app.set_encoding(encoding);
+    }
+    app.filter.smart_case = cfg.smart_case.unwrap_or(false);
+    app.double_click_action = cfg.double_click.unwrap_or_default();
+    app.quick_filter_fields = cfg.quick_filter_fields.clone().unwrap_or_default();
+    if let Some(fields) = cfg.correlation_fields.clone() {
+        app.correlation_fields = fields;
+    }
+    app.copy_fields = cfg.copy_fields.clone().unwrap_or_default();
+    app.wrap_markers = cfg.wrap_markers.unwrap_or(true);
+    app.compact_numbers = cfg.compact_numbers.unwrap_or(true);
+    app.line_prefix = cfg.line_prefix.clone().unwrap_or_default();
+    app.follow_pause_banner = cfg.follow_pause_banner.unwrap_or(true);
+    app.follow_margin = cfg.follow_margin.unwrap_or(app::DEFAULT_FOLLOW_MARGIN);
+    app.max_content_width = cfg.max_content_width;
+    app.center_content = cfg.center_content.unwrap_or(true);
+    app.suggest_index = cfg.suggest_index.unwrap_or(true);
+    app.confirm_close = cfg.confirm_close.unwrap_or_default();
+    app.filter_esc = cfg.filter_esc.unwrap_or_default();
+    app.nav = cfg.nav.unwrap_or_default();
+    app.set_confirm_scan_mb(cfg.confirm_scan_mb);
+    if let Some(lines) = cfg.mouse_scroll_lines {
+        app.mouse_scroll_lines = lines;
+    }
+    app.mouse_capture = cfg.mouse_capture.unwrap_or(true);
+    app.timestamp_format = cfg.timestamp_format.clone();
+    app.background_alert = cfg.background_alert.unwrap_or_default();
+    app.tab_mgr.combined_min_sources = cfg.combined_views.unwrap_or(true).then(|| {
+        cfg.combined_min_sources
+            .unwrap_or(DEFAULT_COMBINED_MIN_SOURCES)
+    });
+    if !cfg.line_buffered.unwrap_or(true) {
+        for tab in &mut app.tab_mgr.tabs {
+            tab.show_partial_lines = true;
+        }
+    }
+    app.source_renderer_map = config_source_map(cfg, |s| {
+        (!s.renderer_names.is_empty()).then(|| s.renderer_names.clone())
+    });
+    app.source_transform_map = config_source_map(cfg, |s| s.display_transform.clone());
+    app.source_default_filter_map = config_source_map(cfg, |s| s.default_filter.clone());
+    app.source_color_map = config_source_map(cfg, |s| s.color);
+    app.format_renderers = cfg.format_renderers.clone();
+}
+
+/// A setting of each config source (project + global) that has it, by
+/// source name
+fn config_source_map<T>(
+    cfg: &config::Config,
+    setting: impl Fn(&config::Source) -> Option<T>,
+) -> std::collections::HashMap<String, T> {
+    cfg.project_sources
+        .iter()
+        .chain(cfg.global_sources.iter())
+        .filter_map(|s| Some((s.name.clone(), setting(s)?)))
+        .collect()
+}
+
 /// Create tabs from config sources (project + global).
 fn build_config_tabs(
     cfg: &config::Config,
     watch: bool,
     config_errors: &mut Vec<String>,
+    open_files: &mut reader::OpenFiles,
+    duplicates: &mut Vec<String>,
 ) -> Vec<TabState> {
     let mut tabs = Vec::new();
-    for source in &cfg.project_sources {
-        match TabState::from_config_source(source, SourceType::ProjectSource, watch) {
-            Ok(Some(t)) => tabs.push(t),
-            Ok(None) => {}
-            Err(e) => config_errors.push(format!("Failed to open {}: {}", source.name, e)),
+    let sources = cfg
+        .project_sources
+        .iter()
+        .map(|s| (s, SourceType::ProjectSource))
+        .chain(
+            cfg.global_sources
+                .iter()
+                .map(|s| (s, SourceType::GlobalSource)),
+        );
+    for (source, source_type) in sources {
+        if let Some(path) = &source.path {
+            if find_duplicate(open_files, &tabs, path, duplicates).is_some() {
+                continue;
+            }
         }
-    }
-    for source in &cfg.global_sources {
-        match TabState::from_config_source(source, SourceType::GlobalSource, watch) {
-            Ok(Some(t)) => tabs.push(t),
+        match TabState::from_config_source(source, source_type, watch) {
+            Ok(Some(t)) => {
+                if let Some(path) = &source.path {
+                    open_files.insert(path, tabs.len());
+                }
+                tabs.push(t);
+            }
             Ok(None) => {}
             Err(e) => config_errors.push(format!("Failed to open {}: {}", source.name, e)),
         }
@@ -564,17 +1054,65 @@ fn build_config_tabs(
     tabs
 }
 
+/// Index of the tab already showing the file at `path`, noting the skip in
+/// `duplicates`, if there is one.
+fn find_duplicate(
+    open_files: &reader::OpenFiles,
+    tabs: &[TabState],
+    path: &std::path::Path,
+    duplicates: &mut Vec<String>,
+) -> Option<usize> {
+    let existing = open_files.find(path)?;
+    duplicates.push(format!(
+        "{} is already open as '{}'",
+        path.display(),
+        tabs[existing].source.name
+    ));
+    Some(existing)
+}
+
+/// Tell the user which duplicate file tabs were skipped: in the status bar,
+/// and one line each with `--verbose`.
+fn report_duplicates(app: &mut App, duplicates: &[String]) {
+    if app.verbose {
+        for duplicate in duplicates {
+            eprintln!("[startup] Skipped duplicate tab: {}", duplicate);
+        }
+    }
+    let message = match duplicates {
+        [] => return,
+        [only] => format!("Skipped a duplicate tab: {}", only),
+        many => format!(
+            "Skipped {} duplicate tabs for files already open (dedup_files)",
+            many.len()
+        ),
+    };
+    app.status_message = Some((message, Instant::now()));
+}
+
 /// Build columnar indexes for file-backed tabs that don't have one yet.
-fn build_tab_indexes(tabs: &[TabState]) {
+///
+/// With `quiet`, only failures are reported. Tabs whose index location can't
+/// be written get indexing disabled instead of a warning.
+fn build_tab_indexes(tabs: &mut [TabState], quiet: bool, checkpoint_interval: u16) {
     for tab in tabs {
+        if tab.source.disabled {
+            continue;
+        }
         if let Some(path) = tab.file_path() {
             let idx_dir = source::index_dir_for_log(path);
             if !idx_dir.join("meta").exists() {
                 let file_size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
                 let name = path.file_name().unwrap_or_default().to_string_lossy();
-                eprintln!("Building index for {} ({} bytes)...", name, file_size);
+                if !quiet {
+                    eprintln!("Building index for {} ({} bytes)...", name, file_size);
+                }
                 let start = Instant::now();
-                match index::builder::IndexBuilder::new().build(path, &idx_dir) {
+                match index::builder::IndexBuilder::new()
+                    .with_checkpoint_interval(checkpoint_interval)
+                    .build(path, &idx_dir)
+                {
+                    Ok(_) if quiet => {}
                     Ok(meta) => {
                         eprintln!(
                             "  Done: {} lines indexed in {:.1?}",
@@ -582,36 +1120,83 @@ fn build_tab_indexes(tabs: &[TabState]) {
                             start.elapsed()
                         );
                     }
-                    Err(e) => {
-                        eprintln!("  Warning: failed to build index: {}", e);
-                    }
+                    Err(e) => match index::builder::unwritable_reason(&e) {
+                        Some(reason) => tab.source.index_disabled = Some(reason),
+                        None => eprintln!("  Warning: failed to build index: {}", e),
+                    },
                 }
             }
         }
     }
 }
 
+/// Fixed render area from `--width`/`--height`, if either is set.
+/// A missing dimension falls back to the real terminal size.
+fn fixed_viewport(width: Option<u16>, height: Option<u16>) -> Option<Rect> {
+    if width.is_none() && height.is_none() {
+        return None;
+    }
+    let (cols, rows) = crossterm::terminal::size().unwrap_or((80, 24));
+    Some(Rect::new(
+        0,
+        0,
+        width.unwrap_or(cols).max(1),
+        height.unwrap_or(rows).max(1),
+    ))
+}
+
 /// Set up the terminal for TUI rendering (raw mode, alternate screen, mouse).
-fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
+///
+/// With a fixed area the terminal never resizes, so renders are independent
+/// of the real TTY size. Without mouse capture the terminal keeps its native
+/// text selection.
+fn setup_terminal(
+    fixed_area: Option<Rect>,
+    mouse_capture: bool,
+) -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
     enable_raw_mode().context("Failed to enable raw mode")?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen)?;
+    if mouse_capture {
+        execute!(stdout, EnableMouseCapture)?;
+    }
     let backend = CrosstermBackend::new(stdout);
-    Terminal::new(backend).context("Failed to create terminal")
+    let terminal = match fixed_area {
+        Some(area) => Terminal::with_options(
+            backend,
+            TerminalOptions {
+                viewport: ratatui::Viewport::Fixed(area),
+            },
+        ),
+        None => Terminal::new(backend),
+    };
+    terminal.context("Failed to create terminal")
 }
 
 /// Restore terminal to normal state.
-fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+fn restore_terminal(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    mouse_capture: bool,
+) -> Result<()> {
     disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    if mouse_capture {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
     terminal.show_cursor()?;
     Ok(())
 }
 
+/// Turn mouse capture on or off while the TUI is running.
+fn set_mouse_capture(enabled: bool) -> Result<()> {
+    if enabled {
+        execute!(io::stdout(), EnableMouseCapture)?;
+    } else {
+        execute!(io::stdout(), DisableMouseCapture)?;
+    }
+    Ok(())
+}
+
 fn run_app<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
     run_app_with_discovery(terminal, app, None, None)
 }
@@ -625,6 +1210,9 @@ fn run_app_with_discovery<B: ratatui::backend::Backend>(
 ) -> Result<()> {
     let mut last_status_refresh = Instant::now();
     let mut last_file_poll = Instant::now();
+    let mut last_input = Instant::now();
+    let mut clicks = DoubleClickDetector::default();
+    let mut mouse_captured = app.mouse_capture;
     loop {
         // Phase 1: Render
         render(terminal, app)?;
@@ -641,8 +1229,10 @@ fn run_app_with_discovery<B: ratatui::backend::Backend>(
             }
         }
 
-        // Phase 2.5: Refresh source status for discovered sources (throttled to every 2s)
-        if last_status_refresh.elapsed() >= Duration::from_secs(2) {
+        // Phase 2.5: Refresh source status for discovered sources (throttled
+        // to every 2s, 10s in low-resource mode)
+        if last_status_refresh.elapsed() >= Duration::from_secs(poll_secs(app, STATUS_REFRESH_SECS))
+        {
             last_status_refresh = Instant::now();
             for tab in &mut app.tab_mgr.tabs {
                 tab.refresh_source_status();
@@ -684,9 +1274,19 @@ fn run_app_with_discovery<B: ratatui::backend::Backend>(
                                     .get(&source.name)
                                     .cloned()
                                     .unwrap_or_default();
-                                if let Ok(tab) =
-                                    TabState::from_discovered_source(source, true, renderers)
-                                {
+                                let transform = app.source_transform_map.get(&source.name).cloned();
+                                let default_filter =
+                                    app.source_default_filter_map.get(&source.name).cloned();
+                                if let Ok(mut tab) = TabState::from_discovered_source(
+                                    source,
+                                    true,
+                                    renderers,
+                                    transform,
+                                    default_filter,
+                                ) {
+                                    if let Some(format_renderers) = &app.format_renderers {
+                                        tab.apply_format_renderer(format_renderers);
+                                    }
                                     app.add_tab(tab);
                                     app.tab_mgr.ensure_combined_tabs();
                                 }
@@ -702,29 +1302,48 @@ fn run_app_with_discovery<B: ratatui::backend::Backend>(
 
         // Phase 2.7: Periodic file size poll — safety net for platforms where
         // the file watcher may not deliver events reliably (e.g. macOS FSEvents).
-        let force_poll = if last_file_poll.elapsed() >= Duration::from_secs(1) {
-            last_file_poll = Instant::now();
-            true
-        } else {
-            false
-        };
+        let force_poll =
+            if last_file_poll.elapsed() >= Duration::from_secs(poll_secs(app, FILE_POLL_SECS)) {
+                last_file_poll = Instant::now();
+                true
+            } else {
+                false
+            };
+
+        // Phase 2.8: Attach newly created --wait files, finished background
+        // index builds and severity breakdowns
+        app.attach_waiting_tabs(force_poll);
+        app.poll_index_builds();
+        app.poll_severity_breakdowns();
 
         // Phase 3: Collect events from all sources
         let mut events = Vec::new();
         events.extend(collect_file_events(app, force_poll));
+        catch_up_filters(app);
         events.extend(collect_filter_progress(app));
         let has_pending_stream = collect_stream_events(app);
-        events.extend(collect_input_events(terminal, app, has_pending_stream)?);
+        events.extend(collect_input_events(
+            terminal,
+            app,
+            has_pending_stream,
+            &mut clicks,
+            &mut last_input,
+        )?);
 
         // Phase 4: Process all events
-        app.has_start_filter_in_batch = events
-            .iter()
-            .any(|e| matches!(e, AppEvent::StartFilter { .. }));
-
         for event in events {
             process_event(app, event);
         }
 
+        if app.mouse_capture != mouse_captured {
+            set_mouse_capture(app.mouse_capture)?;
+            mouse_captured = app.mouse_capture;
+        }
+
+        if app.idle_exit_due(last_input.elapsed()) {
+            app.should_quit = true;
+        }
+
         if app.should_quit {
             break;
         }
@@ -733,15 +1352,25 @@ fn run_app_with_discovery<B: ratatui::backend::Backend>(
     Ok(())
 }
 
+/// Pick the normal or low-resource interval from a `(normal, low)` pair
+fn poll_secs(app: &App, (normal, low): (u64, u64)) -> u64 {
+    if app.low_resource {
+        low
+    } else {
+        normal
+    }
+}
+
 /// Render the UI and manage cursor visibility
 fn render<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
+    app.frame = app.frame.wrapping_add(1);
     terminal.draw(|f| {
         if let Err(e) = tui::render(f, app) {
             eprintln!("Render error: {}", e);
         }
     })?;
 
-    if app.is_entering_filter() || app.is_entering_line_jump() {
+    if app.is_entering_filter() || app.is_entering_line_jump() || app.is_entering_command() {
         terminal.show_cursor()?;
     } else {
         terminal.hide_cursor()?;
@@ -761,10 +1390,14 @@ struct ActiveTabFileModification {
 /// for platforms where the file watcher may miss events (e.g. macOS FSEvents).
 fn collect_file_events(app: &mut App, force_poll: bool) -> Vec<AppEvent> {
     let active_tab = app.tab_mgr.active;
+    let encoding = app.encoding();
 
     // First pass: reload files and handle inactive tabs
     let mut active_tab_modification: Option<ActiveTabFileModification> = None;
     let mut modified_categories = [false; 5];
+    let mut errors = Vec::new();
+    let alert_min = app.background_alert.min_severity();
+    let mut alert = None;
 
     for (tab_idx, tab) in app.tab_mgr.tabs.iter_mut().enumerate() {
         // Drain watcher events
@@ -782,6 +1415,13 @@ fn collect_file_events(app: &mut App, force_poll: bool) -> Vec<AppEvent> {
             }
         }
 
+        // Rotation check (same name, new inode): the watcher stays on the old
+        // inode, so it would never fire for the replacement file.
+        let rotated = force_poll && tab_idx == active_tab && tab.file_was_replaced();
+        if rotated {
+            has_modified = true;
+        }
+
         // Periodic file size poll: if the watcher didn't fire but the file grew,
         // treat it as a modification. This catches cases where the OS file watcher
         // fails to deliver events (common on macOS with FSEvents).
@@ -798,7 +1438,19 @@ fn collect_file_events(app: &mut App, force_poll: bool) -> Vec<AppEvent> {
         }
 
         if let Some(err) = last_error {
-            eprintln!("File watcher error for tab {}: {}", tab_idx, err);
+            errors.push(format!(
+                "File watcher error for {}: {}",
+                tab.source.name, err
+            ));
+        }
+
+        if has_modified && tab.source.diff.is_some() {
+            match tab.reload_diff(encoding) {
+                Ok(true) => modified_categories[tab.source_type() as usize] = true,
+                Ok(false) => {}
+                Err(e) => errors.push(format!("Failed to reload {}: {}", tab.source.name, e)),
+            }
+            continue;
         }
 
         if has_modified {
@@ -810,14 +1462,24 @@ fn collect_file_events(app: &mut App, force_poll: bool) -> Vec<AppEvent> {
             };
 
             if let Err(e) = reader_guard.reload() {
-                eprintln!("Failed to reload file for tab {}: {}", tab_idx, e);
+                errors.push(format!("Failed to reload {}: {}", tab.source.name, e));
                 continue;
             }
 
+            // A rotated reader renumbers its lines when archives rotate away
+            let reset = reader_guard
+                .as_any_mut()
+                .downcast_mut::<RotatedReader>()
+                .is_some_and(RotatedReader::take_reset);
             let new_total = reader_guard.total_lines();
             let old_total = tab.source.total_lines;
             drop(reader_guard);
 
+            if rotated || reset {
+                tab.reattach_after_rotation(new_total);
+                continue;
+            }
+
             // Update file size
             if let Some(ref path) = tab.source.source_path {
                 tab.source.file_size = std::fs::metadata(path).map(|m| m.len()).ok();
@@ -832,10 +1494,31 @@ fn collect_file_events(app: &mut App, force_poll: bool) -> Vec<AppEvent> {
             } else {
                 // Inactive tab: update state directly
                 tab.apply_file_modification(new_total);
+
+                // Alert on severe lines arriving in a followed background tab
+                if let Some(min) = alert_min.filter(|_| tab.source.follow_mode) {
+                    if let Some((line, severity)) =
+                        tab.newest_line_at_or_above(min, old_total..new_total)
+                    {
+                        alert = Some((
+                            tab.source.name.clone(),
+                            tab.source.reader.clone(),
+                            line,
+                            severity,
+                        ));
+                    }
+                }
             }
         }
     }
 
+    for error in errors {
+        app.error_log.push(error);
+    }
+    if let Some((source, reader, line, severity)) = alert {
+        app.raise_tab_alert(source, reader, line, severity);
+    }
+
     // Propagate file changes to combined tabs (only for categories that had modifications)
     for (cat_idx, cat_modified) in modified_categories.iter().enumerate() {
         if *cat_modified {
@@ -857,7 +1540,10 @@ fn collect_file_events(app: &mut App, force_poll: bool) -> Vec<AppEvent> {
                         if combined.source.mode == ViewMode::Normal {
                             let old_len = combined.source.line_indices.len();
                             if new_total > old_len {
-                                combined.source.line_indices.extend(old_len..new_total);
+                                combined
+                                    .source
+                                    .line_indices
+                                    .extend_range(old_len..new_total);
                             } else {
                                 combined.source.line_indices.truncate(new_total);
                             }
@@ -884,19 +1570,24 @@ fn collect_file_events(app: &mut App, force_poll: bool) -> Vec<AppEvent> {
 
     // Second pass: process active tab modification (needs immutable app access)
     if let Some(mod_data) = active_tab_modification {
-        handlers::file_events::process_file_modification(
-            mod_data.new_total,
-            mod_data.old_total,
-            app,
-        )
+        handlers::file_events::process_file_modification(mod_data.new_total, mod_data.old_total)
     } else {
         Vec::new()
     }
 }
 
+/// Start incremental runs for tabs whose completed filter results are
+/// behind their source (lines arrived while the last run was in flight).
+fn catch_up_filters(app: &mut App) {
+    for tab in app.tab_mgr.tabs.iter_mut() {
+        tab.catch_up_filter();
+    }
+}
+
 /// Collect filter progress from all tabs (regular + combined)
 fn collect_filter_progress(app: &mut App) -> Vec<AppEvent> {
     let mut events = Vec::new();
+    let mut errors = Vec::new();
     let active_tab = app.tab_mgr.active;
     let active_combined = app.tab_mgr.active_combined;
 
@@ -924,6 +1615,10 @@ fn collect_filter_progress(app: &mut App) -> Vec<AppEvent> {
                     } else {
                         // Inactive tab: apply filter events directly
                         for ev in &filter_events {
+                            if let AppEvent::FilterError(err) = ev {
+                                errors
+                                    .push(format!("Filter error in {}: {}", tab.source.name, err));
+                            }
                             if tab.apply_filter_event(ev) {
                                 tab.source.filter.receiver = None;
                             }
@@ -965,6 +1660,12 @@ fn collect_filter_progress(app: &mut App) -> Vec<AppEvent> {
                             }
                         } else {
                             for ev in &filter_events {
+                                if let AppEvent::FilterError(err) = ev {
+                                    errors.push(format!(
+                                        "Filter error in {}: {}",
+                                        combined.source.name, err
+                                    ));
+                                }
                                 if combined.apply_filter_event(ev) {
                                     combined.source.filter.receiver = None;
                                 }
@@ -983,6 +1684,9 @@ fn collect_filter_progress(app: &mut App) -> Vec<AppEvent> {
         }
     }
 
+    for error in errors {
+        app.error_log.push(error);
+    }
     events
 }
 
@@ -1023,6 +1727,7 @@ fn collect_stream_events(app: &mut App) -> bool {
                         break; // Yield to render loop
                     }
                 }
+                StreamMessage::Partial(partial) => tab.set_stream_partial(partial),
                 StreamMessage::Complete => {
                     tab.mark_stream_complete();
                     break;
@@ -1044,6 +1749,8 @@ fn collect_input_events<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &App,
     has_pending_stream: bool,
+    clicks: &mut DoubleClickDetector,
+    last_input: &mut Instant,
 ) -> Result<Vec<AppEvent>> {
     use crossterm_event::MouseEventKind;
 
@@ -1052,6 +1759,8 @@ fn collect_input_events<B: ratatui::backend::Backend>(
     // Use short poll when stream data is pending to cycle back quickly
     let poll_ms = if has_pending_stream {
         0
+    } else if app.low_resource {
+        LOW_RESOURCE_INPUT_POLL_MS
     } else {
         INPUT_POLL_DURATION_MS
     };
@@ -1061,18 +1770,21 @@ fn collect_input_events<B: ratatui::backend::Backend>(
 
     // Read the first event - this is guaranteed to be available after poll() returns true
     let first_event = crossterm_event::read()?;
+    *last_input = Instant::now();
 
     // Process the first event
     match first_event {
         Event::Key(key) => {
             events.extend(handlers::input::handle_input_event(key, app));
 
-            // Add page size for PageDown/PageUp
+            // Add page size for PageDown/PageUp (at least one line, as a
+            // fixed --height may be smaller than the offset)
+            let page_size = (terminal.get_frame().area().height as usize)
+                .saturating_sub(PAGE_SIZE_OFFSET)
+                .max(1);
             if matches!(key.code, KeyCode::PageDown) {
-                let page_size = terminal.size()?.height as usize - PAGE_SIZE_OFFSET;
                 events.push(AppEvent::PageDown(page_size));
             } else if matches!(key.code, KeyCode::PageUp) {
-                let page_size = terminal.size()?.height as usize - PAGE_SIZE_OFFSET;
                 events.push(AppEvent::PageUp(page_size));
             }
 
@@ -1095,7 +1807,7 @@ fn collect_input_events<B: ratatui::backend::Backend>(
                             Event::Key(key) => {
                                 // Got a key event - emit scroll first, then key
                                 events.push(AppEvent::MouseScrollDown(
-                                    scroll_count * MOUSE_SCROLL_LINES,
+                                    scroll_count * app.mouse_scroll_lines,
                                 ));
                                 events.push(AppEvent::DisableFollowMode);
                                 events.extend(handlers::input::handle_input_event(key, app));
@@ -1105,7 +1817,9 @@ fn collect_input_events<B: ratatui::backend::Backend>(
                         }
                     }
 
-                    events.push(AppEvent::MouseScrollDown(scroll_count * MOUSE_SCROLL_LINES));
+                    events.push(AppEvent::MouseScrollDown(
+                        scroll_count * app.mouse_scroll_lines,
+                    ));
                     events.push(AppEvent::DisableFollowMode);
                 }
                 MouseEventKind::ScrollUp => {
@@ -1120,7 +1834,7 @@ fn collect_input_events<B: ratatui::backend::Backend>(
                             Event::Key(key) => {
                                 // Got a key event - emit scroll first, then key
                                 events.push(AppEvent::MouseScrollUp(
-                                    scroll_count * MOUSE_SCROLL_LINES,
+                                    scroll_count * app.mouse_scroll_lines,
                                 ));
                                 events.push(AppEvent::DisableFollowMode);
                                 events.extend(handlers::input::handle_input_event(key, app));
@@ -1130,11 +1844,20 @@ fn collect_input_events<B: ratatui::backend::Backend>(
                         }
                     }
 
-                    events.push(AppEvent::MouseScrollUp(scroll_count * MOUSE_SCROLL_LINES));
+                    events.push(AppEvent::MouseScrollUp(
+                        scroll_count * app.mouse_scroll_lines,
+                    ));
                     events.push(AppEvent::DisableFollowMode);
                 }
                 MouseEventKind::Down(crossterm_event::MouseButton::Left) => {
-                    events.push(AppEvent::MouseClick {
+                    let (column, row) = (mouse_event.column, mouse_event.row);
+                    events.push(AppEvent::MouseClick { column, row });
+                    if clicks.register(row, Instant::now()) {
+                        events.push(AppEvent::MouseDoubleClick { column, row });
+                    }
+                }
+                MouseEventKind::Down(crossterm_event::MouseButton::Right) => {
+                    events.push(AppEvent::MouseRightClick {
                         column: mouse_event.column,
                         row: mouse_event.row,
                     });
@@ -1162,8 +1885,8 @@ fn spawn_update_check(
     cli: &Cli,
     cfg: &config::Config,
 ) -> Option<std::thread::JoinHandle<Result<update::UpdateInfo, String>>> {
-    // Respect --no-update-check flag
-    if cli.no_update_check {
+    // Respect --no-update-check flag (--quiet would hide the notice anyway)
+    if cli.no_update_check || cli.quiet {
         return None;
     }
     // Respect config: update_check: false
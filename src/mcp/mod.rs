@@ -22,9 +22,12 @@ pub fn run_mcp_server() -> Result<()> {
 
         let service = tools::LazyTailMcp::new();
         let running = service.serve(rmcp::transport::stdio()).await?;
+        let service = running.service().clone();
 
         // Wait for the service to complete
         running.waiting().await?;
+        // The peer is gone: stop the subscription notification tasks
+        service.close_subscriptions();
 
         Ok(())
     })
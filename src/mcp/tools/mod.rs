@@ -14,17 +14,24 @@ mod lines;
 pub(super) mod response;
 mod search;
 mod stats;
+mod subscribe;
 
 use super::types::*;
 use crate::config::{self, DiscoveryResult};
 use crate::renderer::PresetRegistry;
 use crate::source;
 use response::error_response;
-use rmcp::model::{Implementation, ServerCapabilities, ServerInfo};
-use rmcp::{tool, tool_box, ServerHandler};
+use rmcp::model::{
+    AnnotateAble, Implementation, ListResourceTemplatesResult, PaginatedRequestParam,
+    RawResourceTemplate, ReadResourceRequestParam, ReadResourceResult, ResourceContents,
+    ServerCapabilities, ServerInfo, SubscribeRequestParam, UnsubscribeRequestParam,
+};
+use rmcp::service::RequestContext;
+use rmcp::{tool, tool_box, Error as McpError, RoleServer, ServerHandler};
 use std::collections::HashMap;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use subscribe::Subscriptions;
 
 /// LazyTail MCP server providing log file analysis tools.
 #[derive(Clone)]
@@ -35,6 +42,8 @@ pub struct LazyTailMcp {
     preset_registry: Arc<PresetRegistry>,
     /// Source name → renderer preset names mapping.
     source_renderer_map: HashMap<String, Vec<String>>,
+    /// Live line subscriptions, shared with their notification tasks.
+    subscriptions: Subscriptions,
 }
 
 impl LazyTailMcp {
@@ -63,6 +72,7 @@ impl LazyTailMcp {
             discovery,
             preset_registry: Arc::new(registry),
             source_renderer_map,
+            subscriptions: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -126,7 +136,7 @@ impl LazyTailMcp {
 
     /// Search for patterns in a lazytail source using plain text, regex, or structured query.
     #[tool(
-        description = "Search for patterns in a lazytail-captured log source. Supports plain text (default), regex, or structured query modes. Pass a source name from list_sources. Returns up to max_results matches (default 100, max 1000) with optional context_lines. Structured queries use the `query` parameter (LogQL-style, ignores pattern/mode/case_sensitive when set). Query format: {\"parser\": \"json\"|\"logfmt\", \"filters\": [{\"field\": \"name\", \"op\": \"eq\"|\"ne\"|\"contains\"|\"regex\"|\"not_regex\"|\"gt\"|\"lt\"|\"gte\"|\"lte\", \"value\": \"...\"}]}. Supports dot notation for nested fields (\"user.id\"), exclusion patterns, and time-based filtering with relative values (\"now-5m\", \"now-1h30m\") or absolute timestamps on comparison operators. Use the virtual field \"@ts\" to filter by ingestion timestamp (when the line was captured, not the timestamp inside the log line) — e.g. {\"field\": \"@ts\", \"op\": \"gte\", \"value\": \"now-5m\"}. Set include_ts=true to see arrival timestamps in results. Aggregation: add {\"aggregate\": {\"type\": \"count_by\", \"fields\": [\"level\"], \"limit\": 10}} to group results."
+        description = "Search for patterns in a lazytail-captured log source. Supports plain text (default), regex, or structured query modes. Pass a source name from list_sources. Returns up to max_results matches (default 100, max 1000) with optional context_lines. Structured queries use the `query` parameter (LogQL-style, ignores pattern/mode/case_sensitive when set). Query format: {\"parser\": \"json\"|\"logfmt\", \"filters\": [{\"field\": \"name\", \"op\": \"eq\"|\"ne\"|\"contains\"|\"regex\"|\"not_regex\"|\"gt\"|\"lt\"|\"gte\"|\"lte\"|\"exists\"|\"not_exists\", \"value\": \"...\"}]} (exists/not_exists check field presence and take no value). Supports dot notation for nested fields (\"user.id\"), exclusion patterns, line length via the virtual field \"@len\" (e.g. {\"field\": \"@len\", \"op\": \"gt\", \"value\": \"2000\"}), and time-based filtering with relative values (\"now-5m\", \"now-1h30m\") or absolute timestamps on comparison operators. Use the virtual field \"@ts\" to filter by ingestion timestamp (when the line was captured, not the timestamp inside the log line) — e.g. {\"field\": \"@ts\", \"op\": \"gte\", \"value\": \"now-5m\"}. Set include_ts=true to see arrival timestamps in results. Aggregation: add {\"aggregate\": {\"type\": \"count_by\", \"fields\": [\"level\"], \"limit\": 10}} to group results."
     )]
     fn search(&self, #[tool(aggr)] req: SearchRequest) -> String {
         let path = match source::resolve_source_for_context(&req.source, &self.discovery) {
@@ -268,7 +278,11 @@ impl ServerHandler for LazyTailMcp {
                 name: "lazytail".into(),
                 version: env!("CARGO_PKG_VERSION").into(),
             },
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities: ServerCapabilities::builder()
+                .enable_tools()
+                .enable_resources()
+                .enable_resources_subscribe()
+                .build(),
             instructions: Some(
                 "LazyTail MCP server for log file analysis. \
                  Start with list_sources to discover available logs and their names. \
@@ -290,7 +304,10 @@ impl ServerHandler for LazyTailMcp {
                  Set include_ts=true on search, get_lines, get_tail, or get_context to see arrival timestamps in results. \
                  Use get_stats to check the time range of a source before writing @ts queries. \
                  The get_tail tool supports incremental polling via since_line — pass the last line_number \
-                 you received to get only new lines added after that point."
+                 you received to get only new lines added after that point. \
+                 To be pushed new lines instead of polling, subscribe to the resource \
+                 lazytail://source/{name}/new (optionally ?pattern=...&mode=regex&case_sensitive=true); \
+                 on each resources/updated notification, read it to get the matching lines since the last read."
                     .into(),
             ),
             ..Default::default()
@@ -299,6 +316,69 @@ impl ServerHandler for LazyTailMcp {
 
     // Derive list_tools and call_tool from the tool_box
     tool_box!(@derive);
+
+    async fn list_resource_templates(
+        &self,
+        _request: PaginatedRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, McpError> {
+        let template = RawResourceTemplate {
+            uri_template: subscribe::URI_TEMPLATE.into(),
+            name: "New lines".into(),
+            description: Some(
+                "Lines appended to a source since the last read, optionally filtered by pattern. \
+                 Subscribe to be notified when new matching lines arrive."
+                    .into(),
+            ),
+            mime_type: Some("text/plain".into()),
+        };
+        Ok(ListResourceTemplatesResult {
+            resource_templates: vec![template.no_annotation()],
+            next_cursor: None,
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        let text = self
+            .read_subscription_impl(&request.uri)
+            .map_err(|e| McpError::resource_not_found(e, None))?;
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::text(text, request.uri)],
+        })
+    }
+
+    async fn subscribe(
+        &self,
+        request: SubscribeRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        let path = self
+            .subscribe_impl(&request.uri)
+            .map_err(|e| McpError::invalid_params(e, None))?;
+        if let Some(path) = path {
+            tokio::spawn(subscribe::watch_subscription(
+                self.subscriptions.clone(),
+                context.peer,
+                request.uri,
+                path,
+            ));
+        }
+        Ok(())
+    }
+
+    async fn unsubscribe(
+        &self,
+        request: UnsubscribeRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        // The notification task exits on its next tick
+        self.subscriptions.lock().unwrap().remove(&request.uri);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -315,6 +395,7 @@ mod tests {
             discovery: config::discover(),
             preset_registry: Arc::new(PresetRegistry::new(Vec::new())),
             source_renderer_map: HashMap::new(),
+            subscriptions: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -1458,6 +1539,7 @@ plain line with no escapes\n\
             discovery: config::discover(),
             preset_registry: Arc::new(registry),
             source_renderer_map,
+            subscriptions: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -1558,6 +1640,7 @@ plain line with no escapes\n\
             discovery: config::discover(),
             preset_registry: Arc::new(registry),
             source_renderer_map,
+            subscriptions: Arc::new(Mutex::new(HashMap::new())),
         };
 
         // The renderer_names_for_path function should resolve names
@@ -7,6 +7,7 @@ use crate::filter::search_engine::SearchEngine;
 use crate::filter::{cancel::CancelToken, regex_filter::RegexFilter, Filter};
 use crate::index::reader::IndexReader;
 use crate::mcp::types::*;
+use crate::reader::encoding::Encoding;
 use crate::reader::file_reader::FileReader;
 use memchr::memchr_iter;
 use memmap2::Mmap;
@@ -44,7 +45,16 @@ impl LazyTailMcp {
                     Ok(f) => Arc::new(f),
                     Err(e) => return error_response(format!("Invalid regex pattern: {}", e)),
                 };
-                SearchEngine::search_file(path, filter, None, None, None, CancelToken::new())
+                SearchEngine::search_file(
+                    path,
+                    filter,
+                    Encoding::Utf8,
+                    None,
+                    None,
+                    None,
+                    None,
+                    CancelToken::new(),
+                )
             }
         };
         let rx = match rx {
@@ -204,9 +214,11 @@ impl LazyTailMcp {
         let rx = match SearchEngine::search_file(
             path,
             filter,
+            Encoding::Utf8,
             Some(&filter_query),
             index.as_ref(),
             None,
+            None,
             CancelToken::new(),
         ) {
             Ok(rx) => rx,
@@ -0,0 +1,635 @@
+//! Live line subscriptions exposed as MCP resources.
+//!
+//! A client subscribes to `lazytail://source/{name}/new`, optionally narrowed with
+//! `?pattern=...&mode=regex&case_sensitive=true`. While the source grows, the server
+//! sends `notifications/resources/updated` for that URI (at most once per
+//! [`NOTIFY_INTERVAL`], and not again until the client reads), and reading the
+//! resource returns the matching lines appended since the previous read.
+
+use super::response::*;
+use super::LazyTailMcp;
+use crate::ansi::strip_ansi;
+use crate::filter::regex_filter::RegexFilter;
+use crate::filter::string_filter::StringFilter;
+use crate::filter::Filter;
+use crate::index::reader::IndexReader;
+use crate::mcp::types::*;
+use crate::source;
+use crate::watcher::FileWatcher;
+use rmcp::model::ResourceUpdatedNotificationParam;
+use rmcp::{Peer, RoleServer};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// URI prefix shared by all subscription resources.
+pub(super) const URI_PREFIX: &str = "lazytail://source/";
+
+/// URI template advertised via `resources/templates/list`.
+pub(super) const URI_TEMPLATE: &str = "lazytail://source/{name}/new{?pattern,mode,case_sensitive}";
+
+/// Minimum time between two update notifications for one subscription.
+const NOTIFY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Matching lines kept between reads; older ones are dropped and counted.
+const MAX_PENDING: usize = 1000;
+
+/// Read size for finding the end of the file when a subscription starts.
+const COUNT_CHUNK: usize = 64 * 1024;
+
+/// Active subscriptions keyed by resource URI.
+pub(super) type Subscriptions = Arc<Mutex<HashMap<String, Subscription>>>;
+
+/// A parsed subscription URI.
+#[derive(Debug, PartialEq)]
+pub(super) struct SubscriptionSpec {
+    pub source: String,
+    pub pattern: Option<String>,
+    pub mode: SearchMode,
+    pub case_sensitive: bool,
+}
+
+impl SubscriptionSpec {
+    /// Parse `lazytail://source/{name}/new[?query]`.
+    pub fn parse(uri: &str) -> Result<Self, String> {
+        let rest = uri
+            .strip_prefix(URI_PREFIX)
+            .ok_or_else(|| format!("Unknown resource '{}'", uri))?;
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let source = path
+            .strip_suffix("/new")
+            .filter(|name| !name.is_empty())
+            .ok_or_else(|| format!("Expected {} in resource '{}'", URI_TEMPLATE, uri))?;
+
+        let mut spec = Self {
+            source: percent_decode(source),
+            pattern: None,
+            mode: SearchMode::Plain,
+            case_sensitive: false,
+        };
+        for pair in query.split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let value = percent_decode(value);
+            match key {
+                "pattern" if !value.is_empty() => spec.pattern = Some(value),
+                "pattern" => {}
+                "mode" => {
+                    spec.mode = match value.as_str() {
+                        "plain" => SearchMode::Plain,
+                        "regex" => SearchMode::Regex,
+                        other => return Err(format!("Unknown mode '{}'", other)),
+                    }
+                }
+                "case_sensitive" => spec.case_sensitive = value != "false",
+                other => return Err(format!("Unknown parameter '{}'", other)),
+            }
+        }
+        Ok(spec)
+    }
+
+    fn filter(&self) -> Result<Option<Arc<dyn Filter>>, String> {
+        let Some(pattern) = &self.pattern else {
+            return Ok(None);
+        };
+        Ok(Some(match self.mode {
+            SearchMode::Plain => Arc::new(StringFilter::new(pattern, self.case_sensitive)),
+            SearchMode::Regex => Arc::new(
+                RegexFilter::new(pattern, self.case_sensitive)
+                    .map_err(|e| format!("Invalid regex pattern: {}", e))?,
+            ),
+        }))
+    }
+}
+
+/// Decode `%XX` escapes and `+` in a URI component; invalid escapes are kept as-is.
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (escaped, bytes[i]) {
+            (Some(b), _) => {
+                out.push(b);
+                i += 3;
+            }
+            (None, b'+') => {
+                out.push(b' ');
+                i += 1;
+            }
+            (None, b) => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Tail state for one subscription: where the next scan starts and the
+/// matching lines not yet read by the client.
+pub(super) struct Subscription {
+    source: String,
+    tail: Tail,
+    pending: VecDeque<(usize, String)>,
+    dropped: usize,
+    /// An update notification was sent and the client has not read since
+    notified: bool,
+}
+
+/// Where a subscription's next scan starts. Scans run on a copy, outside
+/// the subscriptions lock, and are applied with [`Subscription::apply`].
+#[derive(Clone)]
+pub(super) struct Tail {
+    path: PathBuf,
+    filter: Option<Arc<dyn Filter>>,
+    /// Byte offset of the first unscanned line
+    offset: u64,
+    /// Line number at `offset`
+    next_line: usize,
+}
+
+/// Lines a [`Tail::scan`] found, and where the next scan starts.
+pub(super) struct Scan {
+    /// Offset the scan started from, to detect a scan applied meanwhile
+    from: u64,
+    offset: u64,
+    next_line: usize,
+    /// Matching lines, at most [`MAX_PENDING`] of them
+    lines: VecDeque<(usize, String)>,
+    /// Matching lines left out of `lines`
+    dropped: usize,
+}
+
+impl Subscription {
+    /// Start tailing `path` from its current end.
+    ///
+    /// The end is found by streaming the file once to count its lines, so
+    /// memory use doesn't grow with its size.
+    pub fn new(path: PathBuf, spec: &SubscriptionSpec) -> Result<Self, String> {
+        let filter = spec.filter()?;
+        let (offset, next_line) = complete_lines(&path)
+            .map_err(|e| format!("Failed to read file '{}': {}", path.display(), e))?;
+        Ok(Self {
+            source: spec.source.clone(),
+            tail: Tail {
+                path,
+                filter,
+                offset,
+                next_line,
+            },
+            pending: VecDeque::new(),
+            dropped: 0,
+            notified: false,
+        })
+    }
+
+    /// A copy of the scan position, to scan without holding the lock.
+    pub fn tail(&self) -> Tail {
+        self.tail.clone()
+    }
+
+    /// Queue the lines of a scan of [`tail`](Self::tail). A scan that
+    /// started elsewhere than the current position is stale (another one
+    /// was applied meanwhile) and is ignored.
+    pub fn apply(&mut self, scan: Scan) {
+        if scan.from != self.tail.offset {
+            return;
+        }
+        self.dropped += scan.dropped;
+        for line in scan.lines {
+            if self.pending.len() == MAX_PENDING {
+                self.pending.pop_front();
+                self.dropped += 1;
+            }
+            self.pending.push_back(line);
+        }
+        self.tail.offset = scan.offset;
+        self.tail.next_line = scan.next_line;
+    }
+
+    /// Whether an update notification is due; marks it as sent.
+    fn take_notification(&mut self) -> bool {
+        if self.notified || self.pending.is_empty() {
+            return false;
+        }
+        self.notified = true;
+        true
+    }
+
+    /// Hand out the queued lines and the number dropped since the last read.
+    pub fn drain(&mut self) -> (Vec<(usize, String)>, usize) {
+        self.notified = false;
+        let dropped = std::mem::take(&mut self.dropped);
+        (self.pending.drain(..).collect(), dropped)
+    }
+}
+
+impl Tail {
+    /// Read the complete lines appended since this position, keeping the
+    /// matching ones. `None` if there is nothing new or the file can't be
+    /// read.
+    pub fn scan(mut self) -> Option<Scan> {
+        let from = self.offset;
+        let mut file = File::open(&self.path).ok()?;
+        let len = file.metadata().map_or(0, |m| m.len());
+        if len < self.offset {
+            // Truncated or rotated: everything in the new content is new
+            self.offset = 0;
+            self.next_line = 0;
+        }
+        if len == self.offset {
+            return None;
+        }
+        file.seek(SeekFrom::Start(self.offset)).ok()?;
+        let mut data = Vec::new();
+        file.take(len - self.offset).read_to_end(&mut data).ok()?;
+        let end = data.iter().rposition(|&b| b == b'\n')?;
+
+        let mut lines = VecDeque::new();
+        let mut dropped = 0;
+        for raw in data[..end].split(|&b| b == b'\n') {
+            let raw = raw.strip_suffix(b"\r").unwrap_or(raw);
+            let line = String::from_utf8_lossy(raw).into_owned();
+            let matches = self
+                .filter
+                .as_ref()
+                .is_none_or(|f| f.matches(&strip_ansi(&line)));
+            if matches {
+                if lines.len() == MAX_PENDING {
+                    lines.pop_front();
+                    dropped += 1;
+                }
+                lines.push_back((self.next_line, line));
+            }
+            self.next_line += 1;
+        }
+        Some(Scan {
+            from,
+            offset: self.offset + end as u64 + 1,
+            next_line: self.next_line,
+            lines,
+            dropped,
+        })
+    }
+}
+
+/// Byte length and line count of the complete lines of `path`, read in
+/// chunks. A trailing partial line is left out; it is picked up once it is
+/// completed.
+fn complete_lines(path: &Path) -> std::io::Result<(u64, usize)> {
+    let file = File::open(path)?;
+    let len = file.metadata()?.len();
+    let mut reader = file.take(len);
+    let mut buf = vec![0; COUNT_CHUNK];
+    let (mut read, mut complete, mut lines) = (0u64, 0u64, 0usize);
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        let chunk = &buf[..n];
+        lines += memchr::memchr_iter(b'\n', chunk).count();
+        if let Some(last) = memchr::memrchr(b'\n', chunk) {
+            complete = read + last as u64 + 1;
+        }
+        read += n as u64;
+    }
+    Ok((complete, lines))
+}
+
+/// Push update notifications for `uri` until it is unsubscribed or the
+/// peer disconnects (see [`LazyTailMcp::close_subscriptions`]).
+pub(super) async fn watch_subscription(
+    subscriptions: Subscriptions,
+    peer: Peer<RoleServer>,
+    uri: String,
+    path: PathBuf,
+) {
+    // Without a file watcher, fall back to scanning on every tick
+    let watcher = FileWatcher::new(&path).ok();
+    let mut ticker = tokio::time::interval(NOTIFY_INTERVAL);
+    loop {
+        ticker.tick().await;
+        let changed = watcher.as_ref().is_none_or(|w| {
+            let mut any = false;
+            while w.try_recv().is_some() {
+                any = true;
+            }
+            any
+        });
+
+        // The file is read on a blocking thread, without holding the lock
+        let Some(tail) = subscriptions
+            .lock()
+            .unwrap()
+            .get(&uri)
+            .map(Subscription::tail)
+        else {
+            return;
+        };
+        let scan = if changed {
+            tokio::task::spawn_blocking(move || tail.scan())
+                .await
+                .ok()
+                .flatten()
+        } else {
+            None
+        };
+        let notify = {
+            let mut subs = subscriptions.lock().unwrap();
+            let Some(sub) = subs.get_mut(&uri) else {
+                return;
+            };
+            if let Some(scan) = scan {
+                sub.apply(scan);
+            }
+            sub.take_notification()
+        };
+
+        if notify {
+            let param = ResourceUpdatedNotificationParam { uri: uri.clone() };
+            // rmcp reports an error even for delivered notifications, so the result
+            // says nothing about the client; the task ends once the subscription
+            // is removed instead.
+            let _ = peer.notify_resource_updated(param).await;
+        }
+    }
+}
+
+impl LazyTailMcp {
+    /// Register a subscription; returns the file to watch, or `None` if it already exists.
+    pub(super) fn subscribe_impl(&self, uri: &str) -> Result<Option<PathBuf>, String> {
+        let spec = SubscriptionSpec::parse(uri)?;
+        let path = source::resolve_source_for_context(&spec.source, &self.discovery)
+            .map_err(|e| e.to_string())?;
+
+        let mut subs = self.subscriptions.lock().unwrap();
+        if subs.contains_key(uri) {
+            return Ok(None);
+        }
+        subs.insert(uri.to_string(), Subscription::new(path.clone(), &spec)?);
+        Ok(Some(path))
+    }
+
+    /// Drop every subscription, ending their notification tasks on their
+    /// next tick. Called when the peer disconnects.
+    pub(crate) fn close_subscriptions(&self) {
+        self.subscriptions.lock().unwrap().clear();
+    }
+
+    /// New matching lines for a subscribed URI, formatted like `get_lines`.
+    pub(super) fn read_subscription_impl(&self, uri: &str) -> Result<String, String> {
+        let not_subscribed = || format!("Not subscribed to '{}'", uri);
+        // Scan without holding the lock; the notification task may poll too
+        let tail = self
+            .subscriptions
+            .lock()
+            .unwrap()
+            .get(uri)
+            .map(Subscription::tail)
+            .ok_or_else(not_subscribed)?;
+        let scan = tail.scan();
+        let (path, source, lines, dropped, next_line) = {
+            let mut subs = self.subscriptions.lock().unwrap();
+            let sub = subs.get_mut(uri).ok_or_else(not_subscribed)?;
+            if let Some(scan) = scan {
+                sub.apply(scan);
+            }
+            let (lines, dropped) = sub.drain();
+            (
+                sub.tail.path.clone(),
+                sub.source.clone(),
+                lines,
+                dropped,
+                sub.tail.next_line,
+            )
+        };
+        Ok(self.format_new_lines(&path, &source, lines, dropped, next_line))
+    }
+
+    fn format_new_lines(
+        &self,
+        path: &Path,
+        source: &str,
+        lines: Vec<(usize, String)>,
+        dropped: usize,
+        total_lines: usize,
+    ) -> String {
+        let index_reader = IndexReader::open(path);
+        let renderer_names = self.renderer_names_for_path(path);
+        let ctx = RenderContext {
+            registry: &self.preset_registry,
+            renderer_names: &renderer_names,
+        };
+
+        let lines = lines
+            .into_iter()
+            .map(|(line_number, content)| {
+                let flags = index_reader.as_ref().and_then(|ir| ir.flags(line_number));
+                let mut info = LineInfo {
+                    line_number,
+                    content,
+                    severity: index_reader
+                        .as_ref()
+                        .map(|ir| ir.severity(line_number))
+                        .and_then(|s| s.label().map(String::from)),
+                    rendered: None,
+                    timestamp: None,
+                };
+                let raw_content = info.content.clone();
+                render_line_info(&mut info, &raw_content, flags, &ctx);
+                info
+            })
+            .collect();
+
+        let mut response = GetLinesResponse {
+            lines,
+            total_lines,
+            has_more: false,
+        };
+        strip_lines_response(&mut response);
+        truncate_lines_response(&mut response);
+
+        let mut out = String::new();
+        writeln!(out, "--- source: {}", source).unwrap();
+        if dropped > 0 {
+            writeln!(
+                out,
+                "--- dropped: {} (read more often or narrow the pattern)",
+                dropped
+            )
+            .unwrap();
+        }
+        out.push_str(&format_lines(&response, OutputFormat::Text));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn spec(uri: &str) -> SubscriptionSpec {
+        SubscriptionSpec::parse(uri).unwrap()
+    }
+
+    /// Scan lines appended since the last poll, queueing the matching ones.
+    fn poll(sub: &mut Subscription) {
+        if let Some(scan) = sub.tail().scan() {
+            sub.apply(scan);
+        }
+    }
+
+    fn append(file: &mut NamedTempFile, text: &str) {
+        file.write_all(text.as_bytes()).unwrap();
+        file.flush().unwrap();
+    }
+
+    #[test]
+    fn parse_plain_uri() {
+        assert_eq!(
+            spec("lazytail://source/api/new"),
+            SubscriptionSpec {
+                source: "api".into(),
+                pattern: None,
+                mode: SearchMode::Plain,
+                case_sensitive: false,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_uri_with_filter() {
+        let s = spec(
+            "lazytail://source/api/new?pattern=status%3D5%5Cd%5Cd&mode=regex&case_sensitive=true",
+        );
+        assert_eq!(s.pattern.as_deref(), Some("status=5\\d\\d"));
+        assert_eq!(s.mode, SearchMode::Regex);
+        assert!(s.case_sensitive);
+        assert_eq!(
+            spec("lazytail://source/api/new?pattern=connection+refused")
+                .pattern
+                .as_deref(),
+            Some("connection refused")
+        );
+    }
+
+    #[test]
+    fn parse_rejects_bad_uris() {
+        assert!(SubscriptionSpec::parse("file:///tmp/api.log").is_err());
+        assert!(SubscriptionSpec::parse("lazytail://source/api").is_err());
+        assert!(SubscriptionSpec::parse("lazytail://source//new").is_err());
+        assert!(SubscriptionSpec::parse("lazytail://source/api/new?mode=fuzzy").is_err());
+        assert!(SubscriptionSpec::parse("lazytail://source/api/new?limit=5").is_err());
+    }
+
+    #[test]
+    fn percent_decode_keeps_invalid_escapes() {
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+        assert_eq!(percent_decode("%41b"), "Ab");
+    }
+
+    #[test]
+    fn subscription_starts_at_end_and_queues_new_lines() {
+        let mut file = NamedTempFile::new().unwrap();
+        append(&mut file, "old 1\nold 2\n");
+        let mut sub =
+            Subscription::new(file.path().to_path_buf(), &spec("lazytail://source/x/new")).unwrap();
+
+        poll(&mut sub);
+        assert!(!sub.take_notification());
+
+        append(&mut file, "new 1\nnew 2\npartial");
+        poll(&mut sub);
+        assert!(sub.take_notification());
+        assert!(!sub.take_notification(), "one notification per read");
+
+        let (lines, dropped) = sub.drain();
+        assert_eq!(
+            lines,
+            vec![(2, "new 1".to_string()), (3, "new 2".to_string())]
+        );
+        assert_eq!(dropped, 0);
+
+        append(&mut file, " done\n");
+        poll(&mut sub);
+        assert!(sub.take_notification());
+        assert_eq!(sub.drain().0, vec![(4, "partial done".to_string())]);
+    }
+
+    #[test]
+    fn subscription_applies_filter() {
+        let mut file = NamedTempFile::new().unwrap();
+        let mut sub = Subscription::new(
+            file.path().to_path_buf(),
+            &spec("lazytail://source/x/new?pattern=error"),
+        )
+        .unwrap();
+
+        append(
+            &mut file,
+            "info ok\nERROR boom\n\x1b[31merror\x1b[0m colored\n",
+        );
+        poll(&mut sub);
+        let (lines, _) = sub.drain();
+        let numbers: Vec<usize> = lines.iter().map(|(n, _)| *n).collect();
+        assert_eq!(numbers, vec![1, 2]);
+    }
+
+    #[test]
+    fn subscription_bounds_pending_lines() {
+        let mut file = NamedTempFile::new().unwrap();
+        let mut sub =
+            Subscription::new(file.path().to_path_buf(), &spec("lazytail://source/x/new")).unwrap();
+
+        let text: String = (0..MAX_PENDING + 5).map(|i| format!("{}\n", i)).collect();
+        append(&mut file, &text);
+        poll(&mut sub);
+        let (lines, dropped) = sub.drain();
+        assert_eq!(lines.len(), MAX_PENDING);
+        assert_eq!(dropped, 5);
+        assert_eq!(lines[0], (5, "5".to_string()));
+    }
+
+    #[test]
+    fn stale_scan_is_ignored() {
+        let mut file = NamedTempFile::new().unwrap();
+        let mut sub =
+            Subscription::new(file.path().to_path_buf(), &spec("lazytail://source/x/new")).unwrap();
+
+        append(&mut file, "one\n");
+        let first = sub.tail().scan().unwrap();
+        let second = sub.tail().scan().unwrap();
+        sub.apply(first);
+        sub.apply(second);
+        assert_eq!(sub.drain().0, vec![(0, "one".to_string())]);
+    }
+
+    #[test]
+    fn subscription_restarts_after_truncation() {
+        let mut file = NamedTempFile::new().unwrap();
+        append(&mut file, "a long first line\nanother long line\n");
+        let mut sub =
+            Subscription::new(file.path().to_path_buf(), &spec("lazytail://source/x/new")).unwrap();
+
+        file.as_file().set_len(0).unwrap();
+        file.as_file_mut().seek(SeekFrom::Start(0)).unwrap();
+        append(&mut file, "fresh\n");
+        poll(&mut sub);
+        assert_eq!(sub.drain().0, vec![(0, "fresh".to_string())]);
+    }
+}
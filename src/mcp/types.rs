@@ -289,7 +289,7 @@ pub struct LineInfo {
 }
 
 /// Search mode for pattern matching.
-#[derive(Debug, Default, Clone, Copy, Deserialize, JsonSchema)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum SearchMode {
     /// Plain text search (fast, literal matching)
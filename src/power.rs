@@ -0,0 +1,71 @@
+//! Low-resource mode: whether it is on, and the battery check behind
+//! `low_resource: auto`.
+
+use crate::config::types::LowResource;
+use std::fs;
+use std::path::Path;
+
+/// Whether low-resource mode is on for this run. `auto` is checked once, at
+/// startup.
+pub fn low_resource_active(mode: LowResource) -> bool {
+    match mode {
+        LowResource::Off => false,
+        LowResource::On => true,
+        LowResource::Auto => on_battery(Path::new("/sys/class/power_supply")),
+    }
+}
+
+/// Whether the machine runs on battery, as reported under `power_supply`
+/// (Linux sysfs): a battery discharging, or a mains adapter offline. False
+/// where the OS exposes neither.
+fn on_battery(power_supply: &Path) -> bool {
+    let Ok(entries) = fs::read_dir(power_supply) else {
+        return false;
+    };
+    let read = |dir: &Path, name: &str| {
+        fs::read_to_string(dir.join(name))
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default()
+    };
+    let mut discharging = false;
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        match read(&dir, "type").as_str() {
+            "Mains" if read(&dir, "online") == "1" => return false,
+            "Battery" if read(&dir, "status") == "Discharging" => discharging = true,
+            _ => {}
+        }
+    }
+    discharging
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn supply(root: &Path, name: &str, files: &[(&str, &str)]) {
+        let dir = root.join(name);
+        fs::create_dir(&dir).unwrap();
+        for (file, value) in files {
+            fs::write(dir.join(file), format!("{}\n", value)).unwrap();
+        }
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_on_battery_reads_power_supply_state() {
+        let temp = TempDir::new().unwrap();
+        supply(
+            temp.path(),
+            "BAT0",
+            &[("type", "Battery"), ("status", "Discharging")],
+        );
+        assert!(on_battery(temp.path()));
+
+        supply(temp.path(), "AC", &[("type", "Mains"), ("online", "1")]);
+        assert!(!on_battery(temp.path()));
+
+        assert!(!on_battery(&temp.path().join("missing")));
+    }
+}
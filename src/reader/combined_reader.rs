@@ -4,6 +4,7 @@ use crate::reader::LogReader;
 use anyhow::Result;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// A source contributing lines to the combined view.
 pub struct SourceEntry {
@@ -13,6 +14,11 @@ pub struct SourceEntry {
     pub source_path: Option<PathBuf>,
     pub total_lines: usize,
     pub renderer_names: Vec<String>,
+    /// A live stream (which has no index) merged with files: its lines are
+    /// ordered by when they arrive and always follow the other sources'.
+    /// Those are then read once, as context, and reloads only pick up the
+    /// live source's new lines, so the merge just appends.
+    pub live: bool,
 }
 
 /// A merged line reference: which source, which line in that source.
@@ -42,40 +48,71 @@ pub struct CombinedReader {
     merged: Vec<MergedLine>,
     /// Previous total_lines per source, for incremental append on reload.
     prev_totals: Vec<usize>,
+    /// Per source, when each line was first seen (epoch ms); empty unless
+    /// the source is [`SourceEntry::live`].
+    arrivals: Vec<Vec<u64>>,
 }
 
 impl CombinedReader {
     pub fn new(sources: Vec<SourceEntry>) -> Self {
         let prev_totals = sources.iter().map(|s| s.total_lines).collect();
+        let arrivals = vec![Vec::new(); sources.len()];
         let mut reader = Self {
             sources,
             merged: Vec::new(),
             prev_totals,
+            arrivals,
         };
+        reader.stamp_arrivals();
         reader.build_merged();
         reader
     }
 
-    /// Get the timestamp for a line, carrying forward the last known timestamp
-    /// from the same source when the index hasn't caught up yet.
-    fn get_timestamp(source: &SourceEntry, line: usize, last_ts: &mut u64) -> u64 {
-        let ts = source
-            .index_reader
-            .as_ref()
-            .and_then(|ir| ir.get_timestamp(line))
+    /// Stamp arrival-ordered sources' not yet seen lines with the current time.
+    fn stamp_arrivals(&mut self) {
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+        for (source, arrivals) in self.sources.iter().zip(&mut self.arrivals) {
+            if source.live {
+                arrivals.resize(source.total_lines, now_ms);
+            }
+        }
+    }
+
+    /// Get the timestamp for a line: its arrival time if stamped, else from
+    /// the index, carrying forward the last known timestamp from
+    /// the same source when the index hasn't caught up yet.
+    fn get_timestamp(
+        source: &SourceEntry,
+        arrivals: &[u64],
+        line: usize,
+        last_ts: &mut u64,
+    ) -> u64 {
+        let ts = arrivals
+            .get(line)
+            .copied()
+            .or_else(|| source.index_reader.as_ref()?.get_timestamp(line))
             .unwrap_or(*last_ts);
         *last_ts = ts;
         ts
     }
 
+    /// Whether each source is live, for [`sort_key`](Self::sort_key).
+    fn live_sources(&self) -> Vec<bool> {
+        self.sources.iter().map(|s| s.live).collect()
+    }
+
     /// Rebuild the merged line list from all sources, sorted by timestamp.
     fn build_merged(&mut self) {
         self.merged.clear();
+        let live = self.live_sources();
 
         for (source_id, source) in self.sources.iter().enumerate() {
+            let arrivals = &self.arrivals[source_id];
             let mut last_ts = 0u64;
             for line in 0..source.total_lines {
-                let timestamp = Self::get_timestamp(source, line, &mut last_ts);
+                let timestamp = Self::get_timestamp(source, arrivals, line, &mut last_ts);
                 self.merged.push(MergedLine {
                     source_id,
                     file_line: line,
@@ -84,15 +121,14 @@ impl CombinedReader {
             }
         }
 
-        // Deterministic sort: timestamp first, then source order, then line order
-        self.merged
-            .sort_by_key(|m| (m.timestamp, m.source_id, m.file_line));
+        self.merged.sort_by_key(|m| Self::sort_key(&live, m));
     }
 
-    /// Sort key for deterministic ordering.
+    /// Sort key for deterministic ordering: live sources last, then
+    /// timestamp, then source order, then line order.
     #[inline]
-    fn sort_key(m: &MergedLine) -> (u64, usize, usize) {
-        (m.timestamp, m.source_id, m.file_line)
+    fn sort_key(live: &[bool], m: &MergedLine) -> (bool, u64, usize, usize) {
+        (live[m.source_id], m.timestamp, m.source_id, m.file_line)
     }
 
     /// Append only new lines from sources that grew since the last reload.
@@ -112,17 +148,14 @@ impl CombinedReader {
             if source.total_lines > prev {
                 // Carry forward the last known timestamp from this source
                 // so new lines without index data sort near their true position.
+                let arrivals = &self.arrivals[source_id];
                 let mut last_ts = if prev > 0 {
-                    source
-                        .index_reader
-                        .as_ref()
-                        .and_then(|ir| ir.get_timestamp(prev - 1))
-                        .unwrap_or(0)
+                    Self::get_timestamp(source, arrivals, prev - 1, &mut 0)
                 } else {
                     0
                 };
                 for line in prev..source.total_lines {
-                    let timestamp = Self::get_timestamp(source, line, &mut last_ts);
+                    let timestamp = Self::get_timestamp(source, arrivals, line, &mut last_ts);
                     new_lines.push(MergedLine {
                         source_id,
                         file_line: line,
@@ -136,13 +169,14 @@ impl CombinedReader {
             return;
         }
 
-        new_lines.sort_by_key(Self::sort_key);
+        let live = self.live_sources();
+        new_lines.sort_by_key(|m| Self::sort_key(&live, m));
 
         // Fast path: if all new lines sort after all existing lines, just append.
         // This is the common case for append-only logs with monotonic timestamps.
         let can_append = self.merged.is_empty()
-            || Self::sort_key(new_lines.first().unwrap())
-                >= Self::sort_key(self.merged.last().unwrap());
+            || Self::sort_key(&live, new_lines.first().unwrap())
+                >= Self::sort_key(&live, self.merged.last().unwrap());
 
         if can_append {
             self.merged.extend(new_lines);
@@ -153,8 +187,10 @@ impl CombinedReader {
             // allocation that would thrash CPU caches for large merged vecs.
             // Process in reverse so earlier insertions don't shift later positions.
             for line in new_lines.into_iter().rev() {
-                let key = Self::sort_key(&line);
-                let pos = self.merged.partition_point(|m| Self::sort_key(m) <= key);
+                let key = Self::sort_key(&live, &line);
+                let pos = self
+                    .merged
+                    .partition_point(|m| Self::sort_key(&live, m) <= key);
                 self.merged.insert(pos, line);
             }
         } else {
@@ -165,7 +201,7 @@ impl CombinedReader {
             let mut i = 0;
             let mut j = 0;
             while i < old.len() && j < new_lines.len() {
-                if Self::sort_key(&old[i]) <= Self::sort_key(&new_lines[j]) {
+                if Self::sort_key(&live, &old[i]) <= Self::sort_key(&live, &new_lines[j]) {
                     self.merged.push(old[i]);
                     i += 1;
                 } else {
@@ -190,6 +226,13 @@ impl CombinedReader {
         Some((name, color))
     }
 
+    /// Map a virtual line back to its origin: the source entry and the line
+    /// index within that source.
+    pub fn source_line(&self, virtual_idx: usize) -> Option<(&SourceEntry, usize)> {
+        let m = self.merged.get(virtual_idx)?;
+        Some((&self.sources[m.source_id], m.file_line))
+    }
+
     /// Get the renderer_names for the source that owns a given virtual line.
     pub fn renderer_names(&self, virtual_idx: usize) -> &[String] {
         let Some(m) = self.merged.get(virtual_idx) else {
@@ -242,7 +285,12 @@ impl LogReader for CombinedReader {
         // Individual source failures (e.g. deleted file) are skipped gracefully.
         let mut any_truncated = false;
         let mut index_gained = false;
+        let has_live = self.sources.iter().any(|s| s.live);
         for (i, source) in self.sources.iter_mut().enumerate() {
+            // Next to a live source the others are context, read once
+            if has_live && !source.live {
+                continue;
+            }
             let mut reader = match source.reader.lock() {
                 Ok(guard) => guard,
                 Err(poisoned) => poisoned.into_inner(),
@@ -260,7 +308,12 @@ impl LogReader for CombinedReader {
 
             if let Some(ref mut ir) = source.index_reader {
                 if let Some(ref path) = source.source_path {
-                    ir.refresh(path);
+                    if !ir.refresh(path) {
+                        // File replaced under the index — drop it until a
+                        // rebuilt index can be opened on a later reload.
+                        source.index_reader = None;
+                        any_truncated = true;
+                    }
                 }
             } else if let Some(ref path) = source.source_path {
                 // Index didn't exist when combined tab was created — retry.
@@ -271,6 +324,11 @@ impl LogReader for CombinedReader {
             }
         }
 
+        for (source, arrivals) in self.sources.iter().zip(&mut self.arrivals) {
+            arrivals.truncate(source.total_lines);
+        }
+        self.stamp_arrivals();
+
         // If any source was truncated, or a new index appeared (meaning lines
         // that previously had no timestamp can now be positioned correctly),
         // do a full rebuild.
@@ -285,6 +343,10 @@ impl LogReader for CombinedReader {
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
 }
 
 #[cfg(test)]
@@ -305,6 +367,7 @@ mod tests {
             source_path: None,
             total_lines,
             renderer_names: Vec::new(),
+            live: false,
         }
     }
 
@@ -351,6 +414,24 @@ mod tests {
         assert!(reader.source_info(2, &colors).is_none());
     }
 
+    #[test]
+    fn test_combined_reader_source_line() {
+        let sources = vec![
+            make_source("api", vec!["a1", "a2"]),
+            make_source("web", vec!["b1"]),
+        ];
+        let reader = CombinedReader::new(sources);
+
+        let origin = |idx| {
+            reader
+                .source_line(idx)
+                .map(|(source, line)| (source.name.as_str(), line))
+        };
+        assert_eq!(origin(1), Some(("api", 1)));
+        assert_eq!(origin(2), Some(("web", 0)));
+        assert_eq!(origin(3), None);
+    }
+
     #[test]
     fn test_combined_reader_empty_sources() {
         let sources = vec![make_source("empty", vec![])];
@@ -401,10 +482,11 @@ mod tests {
             file_line: 1,
             timestamp: 20,
         };
-        let key = CombinedReader::sort_key(&new_line);
+        let live = reader.live_sources();
+        let key = CombinedReader::sort_key(&live, &new_line);
         let pos = reader
             .merged
-            .partition_point(|m| CombinedReader::sort_key(m) <= key);
+            .partition_point(|m| CombinedReader::sort_key(&live, m) <= key);
         reader.merged.insert(pos, new_line);
 
         assert_eq!(reader.merged.len(), 4);
@@ -471,6 +553,44 @@ mod tests {
         assert_eq!(reader.get_line(5).unwrap(), Some("b3".to_string())); // ts=60
     }
 
+    #[test]
+    fn test_live_source_only_appends_after_context() {
+        let mut file_stream = StreamReader::new_incremental();
+        file_stream.append_lines(vec!["ctx1".into(), "ctx2".into()]);
+        let file_stream = Arc::new(Mutex::new(file_stream));
+        let file = SourceEntry {
+            reader: file_stream.clone(),
+            total_lines: 2,
+            ..make_source("file", vec![])
+        };
+        let stream = Arc::new(Mutex::new(StreamReader::new_incremental()));
+        let live = SourceEntry {
+            reader: stream.clone(),
+            live: true,
+            ..make_source("stdin", vec![])
+        };
+        let mut reader = CombinedReader::new(vec![live, file]);
+        assert_eq!(reader.total_lines(), 2);
+
+        // The file grows too, but only the live source is re-read
+        stream
+            .lock()
+            .unwrap()
+            .append_lines(vec!["s1".into(), "s2".into()]);
+        reader.reload().unwrap();
+        stream.lock().unwrap().append_lines(vec!["s3".into()]);
+        file_stream
+            .lock()
+            .unwrap()
+            .append_lines(vec!["ctx3".into()]);
+        reader.reload().unwrap();
+
+        let lines: Vec<_> = (0..reader.total_lines())
+            .map(|i| reader.get_line(i).unwrap().unwrap())
+            .collect();
+        assert_eq!(lines, vec!["ctx1", "ctx2", "s1", "s2", "s3"]);
+    }
+
     #[test]
     fn test_reload_picks_up_new_index() {
         use crate::index::column::ColumnWriter;
@@ -524,6 +644,7 @@ mod tests {
             source_path: Some(log_a.clone()),
             total_lines: 2,
             renderer_names: Vec::new(),
+            live: false,
         };
         let source_b = SourceEntry {
             name: "b".into(),
@@ -534,6 +655,7 @@ mod tests {
             source_path: Some(log_b.clone()),
             total_lines: 2,
             renderer_names: Vec::new(),
+            live: false,
         };
 
         let mut reader = CombinedReader::new(vec![source_a, source_b]);
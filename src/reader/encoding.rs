@@ -0,0 +1,141 @@
+//! Text encodings for reading non-UTF-8 log files.
+//!
+//! Only single-byte encodings are supported: every line boundary is still a
+//! `\n` byte, so byte offsets from the sparse and columnar indexes stay valid
+//! and decoding happens after a line has been sliced out of the file.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+/// Supported input encodings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    /// UTF-8, invalid sequences replaced with U+FFFD.
+    #[default]
+    Utf8,
+    /// ISO-8859-1: bytes map directly to U+0000..U+00FF.
+    Latin1,
+    /// Windows-1252: Latin-1 with printable characters in 0x80..0x9F.
+    Windows1252,
+    /// ISO-8859-15: Latin-1 with the euro sign and a few letter changes.
+    Latin9,
+}
+
+/// Windows-1252 mapping for 0x80..=0x9F (undefined bytes map to their C1 code point).
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž', '\u{8F}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}', 'ž', 'Ÿ',
+];
+
+impl Encoding {
+    /// Names accepted by `FromStr`, for error messages.
+    pub const NAMES: &'static [&'static str] = &["utf-8", "latin1", "windows-1252", "latin9"];
+
+    /// Decode one line of bytes to UTF-8.
+    pub fn decode(self, bytes: &[u8]) -> Cow<'_, str> {
+        match self {
+            Encoding::Utf8 => String::from_utf8_lossy(bytes),
+            Encoding::Latin1 => Self::decode_single_byte(bytes, |b| b as char),
+            Encoding::Windows1252 => Self::decode_single_byte(bytes, |b| match b {
+                0x80..=0x9F => WINDOWS_1252_HIGH[(b - 0x80) as usize],
+                _ => b as char,
+            }),
+            Encoding::Latin9 => Self::decode_single_byte(bytes, |b| match b {
+                0xA4 => '€',
+                0xA6 => 'Š',
+                0xA8 => 'š',
+                0xB4 => 'Ž',
+                0xB8 => 'ž',
+                0xBC => 'Œ',
+                0xBD => 'œ',
+                0xBE => 'Ÿ',
+                _ => b as char,
+            }),
+        }
+    }
+
+    /// ASCII is identical in every supported encoding, so pure-ASCII lines
+    /// are borrowed without copying.
+    fn decode_single_byte(bytes: &[u8], map: impl Fn(u8) -> char) -> Cow<'_, str> {
+        if bytes.is_ascii() {
+            // ASCII is valid UTF-8, so this borrows
+            return String::from_utf8_lossy(bytes);
+        }
+        Cow::Owned(bytes.iter().map(|&b| map(b)).collect())
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('_', "-").as_str() {
+            "utf-8" | "utf8" => Ok(Encoding::Utf8),
+            "latin1" | "latin-1" | "iso-8859-1" | "iso8859-1" => Ok(Encoding::Latin1),
+            "windows-1252" | "cp1252" | "win1252" => Ok(Encoding::Windows1252),
+            "latin9" | "latin-9" | "iso-8859-15" | "iso8859-15" => Ok(Encoding::Latin9),
+            _ => Err(format!(
+                "unknown encoding '{}'. Supported: {}",
+                s,
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Encoding::Utf8 => "utf-8",
+            Encoding::Latin1 => "latin1",
+            Encoding::Windows1252 => "windows-1252",
+            Encoding::Latin9 => "latin9",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_names_and_aliases() {
+        assert_eq!("UTF-8".parse(), Ok(Encoding::Utf8));
+        assert_eq!("iso-8859-1".parse(), Ok(Encoding::Latin1));
+        assert_eq!("cp1252".parse(), Ok(Encoding::Windows1252));
+        assert_eq!("ISO_8859_15".parse(), Ok(Encoding::Latin9));
+        assert!("ebcdic".parse::<Encoding>().is_err());
+    }
+
+    #[test]
+    fn test_decode_windows_1252_accents_and_punctuation() {
+        // "café – 5€"
+        let bytes = b"caf\xe9 \x96 5\x80";
+        assert_eq!(Encoding::Windows1252.decode(bytes), "café – 5€");
+    }
+
+    #[test]
+    fn test_decode_latin1_maps_bytes_directly() {
+        assert_eq!(Encoding::Latin1.decode(b"na\xefve \x80"), "naïve \u{80}");
+    }
+
+    #[test]
+    fn test_decode_latin9_euro() {
+        assert_eq!(Encoding::Latin9.decode(b"\xa4 10"), "€ 10");
+    }
+
+    #[test]
+    fn test_decode_utf8_is_lossy() {
+        assert_eq!(Encoding::Utf8.decode(b"caf\xe9"), "caf\u{FFFD}");
+    }
+
+    #[test]
+    fn test_ascii_is_borrowed() {
+        assert!(matches!(
+            Encoding::Windows1252.decode(b"plain"),
+            Cow::Borrowed(_)
+        ));
+    }
+}
@@ -1,5 +1,6 @@
+use super::encoding::Encoding;
 use super::sparse_index::SparseIndex;
-use super::LogReader;
+use super::{file_identity, LogReader};
 use crate::index::column::ColumnReader;
 use crate::index::meta::IndexMeta;
 use crate::index::validate::validate_index;
@@ -43,6 +44,12 @@ pub struct FileReader {
     /// When the next get_line(N+1) follows get_line(N), the reader is already
     /// positioned at the right byte offset so no seek is needed.
     last_read_line: Option<usize>,
+
+    /// (device, inode) of the opened file — detects replacement on reload
+    file_id: Option<(u64, u64)>,
+
+    /// Encoding used to decode line bytes (offsets are encoding-agnostic)
+    encoding: Encoding,
 }
 
 impl FileReader {
@@ -58,6 +65,7 @@ impl FileReader {
     pub fn with_interval<P: AsRef<Path>>(path: P, interval: usize) -> Result<Self> {
         let path = path.as_ref().to_path_buf();
         let file = File::open(&path).context(format!("Failed to open file: {}", path.display()))?;
+        let file_id = file_identity(&path);
 
         let mut reader = Self {
             path,
@@ -67,6 +75,8 @@ impl FileReader {
             columnar_offsets: None,
             indexed_lines: 0,
             last_read_line: None,
+            file_id,
+            encoding: Encoding::default(),
         };
 
         if !reader.try_seed_from_index() {
@@ -75,6 +85,17 @@ impl FileReader {
         Ok(reader)
     }
 
+    /// Decode lines with the given encoding.
+    pub fn set_encoding(&mut self, encoding: Encoding) {
+        self.encoding = encoding;
+        self.last_read_line = None;
+    }
+
+    /// The file being read.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
     /// Try to load the columnar index's offsets column for O(1) line access.
     /// Uses `validate_index()` for structural and checkpoint-based validation
     /// with partial trust support. Returns true if successful.
@@ -328,7 +349,7 @@ impl FileReader {
             };
 
             if position_ok {
-                match read_line_lossy(&mut self.reader)? {
+                match read_line_lossy(&mut self.reader, self.encoding)? {
                     Some(line) => {
                         self.last_read_line = Some(line_num);
                         return Ok(Some(line));
@@ -345,7 +366,7 @@ impl FileReader {
         if line_num < self.indexed_lines {
             if let Some(offset) = self.columnar_offsets.as_ref().and_then(|c| c.get(line_num)) {
                 self.reader.seek(SeekFrom::Start(offset))?;
-                match read_line_lossy(&mut self.reader)? {
+                match read_line_lossy(&mut self.reader, self.encoding)? {
                     Some(line) => {
                         self.last_read_line = Some(line_num);
                         return Ok(Some(line));
@@ -368,12 +389,12 @@ impl FileReader {
                     self.reader.seek(SeekFrom::Start(last_offset))?;
                     let skip = line_num - (self.indexed_lines - 1);
                     for _ in 0..skip {
-                        if read_line_lossy(&mut self.reader)?.is_none() {
+                        if read_line_lossy(&mut self.reader, self.encoding)?.is_none() {
                             self.last_read_line = None;
                             return Ok(None);
                         }
                     }
-                    match read_line_lossy(&mut self.reader)? {
+                    match read_line_lossy(&mut self.reader, self.encoding)? {
                         Some(line) => {
                             self.last_read_line = Some(line_num);
                             return Ok(Some(line));
@@ -392,13 +413,13 @@ impl FileReader {
         self.reader.seek(SeekFrom::Start(offset))?;
 
         for _ in 0..skip {
-            if read_line_lossy(&mut self.reader)?.is_none() {
+            if read_line_lossy(&mut self.reader, self.encoding)?.is_none() {
                 self.last_read_line = None;
                 return Ok(None);
             }
         }
 
-        match read_line_lossy(&mut self.reader)? {
+        match read_line_lossy(&mut self.reader, self.encoding)? {
             Some(line) => {
                 self.last_read_line = Some(line_num);
                 Ok(Some(line))
@@ -443,18 +464,23 @@ fn trim_newline(buf: &mut String) {
 
 /// Read a line from a buffered reader, tolerating invalid UTF-8 and binary content.
 ///
-/// Uses `read_until(b'\n')` into a byte buffer and converts via
-/// `String::from_utf8_lossy`, replacing invalid bytes with U+FFFD.
+/// Uses `read_until(b'\n')` into a byte buffer and decodes it with `encoding`
+/// (UTF-8 replaces invalid bytes with U+FFFD).
 /// Control characters (0x00-0x1F) other than tab are stripped to prevent
 /// them from corrupting TUI layout calculations.
 /// Returns `Ok(None)` on EOF (0 bytes read).
-fn read_line_lossy(reader: &mut BufReader<File>) -> Result<Option<String>> {
+fn read_line_lossy(reader: &mut BufReader<File>, encoding: Encoding) -> Result<Option<String>> {
     let mut buf = Vec::new();
     let n = reader.read_until(b'\n', &mut buf)?;
     if n == 0 {
         return Ok(None);
     }
-    let mut line = String::from_utf8_lossy(&buf).into_owned();
+    Ok(Some(decode_line(&buf, encoding)))
+}
+
+/// Decode one raw line (with or without its newline) for display.
+pub(super) fn decode_line(buf: &[u8], encoding: Encoding) -> String {
+    let mut line = encoding.decode(buf).into_owned();
     trim_newline(&mut line);
     // Strip control characters that break TUI rendering.
     // Keep: \t (expand_tabs), \x1b (ANSI ESC), \x07 (BEL, used as OSC terminator)
@@ -467,7 +493,7 @@ fn read_line_lossy(reader: &mut BufReader<File>) -> Result<Option<String>> {
             .filter(|&c| c >= ' ' || c == '\t' || c == '\x1b' || c == '\x07')
             .collect();
     }
-    Ok(Some(line))
+    line
 }
 
 impl LogReader for FileReader {
@@ -483,19 +509,36 @@ impl LogReader for FileReader {
         self
     }
 
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
     fn reload(&mut self) -> Result<()> {
         let new_size = std::fs::metadata(&self.path).map(|m| m.len()).unwrap_or(0);
+        let new_id = file_identity(&self.path);
+        let replaced = new_id != self.file_id;
 
         // Nothing changed — skip the reload entirely
-        if new_size == self.scanned_up_to {
+        if !replaced && new_size == self.scanned_up_to {
             return Ok(());
         }
 
         let file = File::open(&self.path)?;
         self.reader = BufReader::new(file);
         self.last_read_line = None;
+        self.file_id = new_id;
 
-        if new_size >= self.scanned_up_to {
+        if replaced {
+            // File was replaced (rotation) — nothing from the old file applies
+            self.columnar_offsets = None;
+            self.indexed_lines = 0;
+            self.scanned_up_to = 0;
+            self.sparse_index.clear();
+            if self.try_seed_from_index() {
+                return Ok(());
+            }
+            self.build_index()
+        } else if new_size >= self.scanned_up_to {
             // File grew — refresh columnar offsets from the index that
             // capture is building in real-time, then scan only the unindexed tail.
             // SAFETY: columnar_offsets mmap is protected from concurrent truncation
@@ -1118,7 +1161,6 @@ mod tests {
         Ok(())
     }
 
-    #[test]
     /// Regression test: capture appends to existing file but builds index
     /// from offset 0. The TUI's refresh should reject these broken offsets.
     #[test]
@@ -1233,6 +1275,45 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_windows_1252_file_decodes_accents() -> Result<()> {
+        let mut temp_file = NamedTempFile::new()?;
+        // "Café crème" / "Zürich – 5€" in Windows-1252
+        temp_file.write_all(b"Caf\xe9 cr\xe8me\nZ\xfcrich \x96 5\x80\n")?;
+        temp_file.flush()?;
+
+        let mut reader = FileReader::new(temp_file.path())?;
+        assert_eq!(reader.get_line(0)?.unwrap(), "Caf\u{FFFD} cr\u{FFFD}me");
+
+        reader.set_encoding(Encoding::Windows1252);
+        assert_eq!(reader.total_lines(), 2);
+        assert_eq!(reader.get_line(0)?.unwrap(), "Café crème");
+        assert_eq!(reader.get_line(1)?.unwrap(), "Zürich – 5€");
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_reload_detects_replaced_file_of_same_size() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let log_path = dir.path().join("test.log");
+        std::fs::write(&log_path, "aaa\nbbb\n")?;
+
+        let mut reader = FileReader::new(&log_path)?;
+        assert_eq!(reader.get_line(0)?.unwrap(), "aaa");
+
+        let tmp_path = dir.path().join("test.log.new");
+        std::fs::write(&tmp_path, "ccc\nddd\n")?;
+        std::fs::rename(&tmp_path, &log_path)?;
+
+        reader.reload()?;
+        assert_eq!(reader.total_lines(), 2);
+        assert_eq!(reader.get_line(0)?.unwrap(), "ccc");
+        assert_eq!(reader.get_line(1)?.unwrap(), "ddd");
+        Ok(())
+    }
+
+    #[test]
     fn test_stale_index_rejected_on_file_replacement() -> Result<()> {
         use crate::index::builder::IndexBuilder;
         use crate::source::index_dir_for_log;
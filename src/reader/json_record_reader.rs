@@ -0,0 +1,325 @@
+//! Reader that folds pretty-printed JSON objects into one line each.
+//!
+//! Some services log each event as a multi-line, indented JSON object. This
+//! reader groups the physical lines of every top-level object (found by
+//! brace matching) into a single record, served as compact JSON, so
+//! filtering and navigation work per event. Lines outside an object are
+//! records of their own.
+
+use super::encoding::Encoding;
+use super::file_reader::FileReader;
+use super::LogReader;
+use anyhow::Result;
+use std::any::Any;
+use std::ops::Range;
+use std::path::Path;
+
+/// An object still open after this many lines is treated as malformed
+const MAX_RECORD_LINES: usize = 10_000;
+
+/// Brace-matching state for the record being scanned.
+#[derive(Debug, Default)]
+struct Scanner {
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+}
+
+impl Scanner {
+    /// Feed one line; returns `false` if a closing bracket has no opener.
+    fn feed(&mut self, line: &str) -> bool {
+        line.chars().all(|c| self.feed_char(c))
+    }
+
+    /// Feed one character; returns `false` if it closes an unopened bracket.
+    fn feed_char(&mut self, c: char) -> bool {
+        if self.in_string {
+            match c {
+                _ if self.escaped => self.escaped = false,
+                '\\' => self.escaped = true,
+                '"' => self.in_string = false,
+                _ => {}
+            }
+            return true;
+        }
+        match c {
+            '"' => self.in_string = true,
+            '{' | '[' => self.depth += 1,
+            '}' | ']' => match self.depth.checked_sub(1) {
+                Some(depth) => self.depth = depth,
+                None => return false,
+            },
+            _ => {}
+        }
+        true
+    }
+}
+
+/// Serves each top-level JSON object of a file as one line.
+pub struct JsonRecordReader {
+    inner: FileReader,
+    /// Physical line range of every complete record
+    records: Vec<Range<usize>>,
+    /// First physical line not yet part of a complete record
+    scanned: usize,
+}
+
+impl JsonRecordReader {
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut reader = Self {
+            inner: FileReader::new(path)?,
+            records: Vec::new(),
+            scanned: 0,
+        };
+        reader.scan()?;
+        Ok(reader)
+    }
+
+    /// Decode lines with the given encoding.
+    pub fn set_encoding(&mut self, encoding: Encoding) {
+        self.inner.set_encoding(encoding);
+    }
+
+    /// The file being read.
+    pub fn path(&self) -> &Path {
+        self.inner.path()
+    }
+
+    /// Group physical lines from `scanned` on into records. An object that
+    /// is still open at the end of the file is left for the next scan.
+    fn scan(&mut self) -> Result<()> {
+        let total = self.inner.total_lines();
+        let mut line = self.scanned;
+        while line < total {
+            let text = self.inner.get_line(line)?.unwrap_or_default();
+            if !starts_object(&text) {
+                self.records.push(line..line + 1);
+                line += 1;
+                continue;
+            }
+
+            match self.object_end(line, total)? {
+                ObjectEnd::Closed(end) => {
+                    self.records.push(line..end);
+                    line = end;
+                }
+                // Broken object: show its first line as-is and rescan after it
+                ObjectEnd::Malformed => {
+                    self.records.push(line..line + 1);
+                    line += 1;
+                }
+                ObjectEnd::Open => break,
+            }
+        }
+        self.scanned = line;
+        Ok(())
+    }
+
+    /// Find the end (exclusive) of the object starting at `start`.
+    fn object_end(&mut self, start: usize, total: usize) -> Result<ObjectEnd> {
+        let mut scanner = Scanner::default();
+        for line in start..total {
+            let text = self.inner.get_line(line)?.unwrap_or_default();
+            // An unindented opener inside an object means the previous one
+            // was never closed
+            if line > start && text.starts_with(['{', '[']) {
+                return Ok(ObjectEnd::Malformed);
+            }
+            if !scanner.feed(&text) {
+                return Ok(ObjectEnd::Malformed);
+            }
+            if scanner.depth == 0 {
+                return Ok(ObjectEnd::Closed(line + 1));
+            }
+            if line + 1 - start >= MAX_RECORD_LINES {
+                return Ok(ObjectEnd::Malformed);
+            }
+        }
+        Ok(ObjectEnd::Open)
+    }
+}
+
+enum ObjectEnd {
+    Closed(usize),
+    Malformed,
+    Open,
+}
+
+fn starts_object(line: &str) -> bool {
+    line.trim_start().starts_with(['{', '['])
+}
+
+/// Fold a record's lines into one: JSON loses its insignificant
+/// whitespace (key order is kept), other text is joined with spaces.
+fn compact(lines: &[String]) -> String {
+    if let [line] = lines {
+        return line.clone();
+    }
+    let joined = lines.join("\n");
+    if serde_json::from_str::<serde::de::IgnoredAny>(&joined).is_err() {
+        return lines.iter().map(|l| l.trim()).collect::<Vec<_>>().join(" ");
+    }
+
+    let mut out = String::with_capacity(joined.len());
+    let mut scanner = Scanner::default();
+    for c in joined.chars() {
+        let in_string = scanner.in_string;
+        scanner.feed_char(c);
+        if in_string || scanner.in_string || !c.is_whitespace() {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Indent a compact JSON document two spaces per level, keeping key order.
+/// `None` if `text` is not JSON.
+pub fn pretty_json(text: &str) -> Option<String> {
+    let text = text.trim();
+    if !starts_object(text) || serde_json::from_str::<serde::de::IgnoredAny>(text).is_err() {
+        return None;
+    }
+
+    let mut out = String::with_capacity(text.len() * 2);
+    let mut scanner = Scanner::default();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let in_string = scanner.in_string;
+        scanner.feed_char(c);
+        if in_string || scanner.in_string {
+            out.push(c);
+            continue;
+        }
+        match c {
+            '{' | '[' if matches!(chars.peek(), Some('}' | ']')) => {
+                // Empty container stays on one line
+                out.push(c);
+                if let Some(close) = chars.next() {
+                    scanner.feed_char(close);
+                    out.push(close);
+                }
+            }
+            '{' | '[' => {
+                out.push(c);
+                newline(&mut out, scanner.depth);
+            }
+            '}' | ']' => {
+                newline(&mut out, scanner.depth);
+                out.push(c);
+            }
+            ',' => {
+                out.push(c);
+                newline(&mut out, scanner.depth);
+            }
+            ':' => out.push_str(": "),
+            c if c.is_whitespace() => {}
+            c => out.push(c),
+        }
+    }
+    Some(out)
+}
+
+fn newline(out: &mut String, depth: usize) {
+    out.push('\n');
+    out.push_str(&" ".repeat(depth * 2));
+}
+
+impl LogReader for JsonRecordReader {
+    fn total_lines(&self) -> usize {
+        self.records.len()
+    }
+
+    fn get_line(&mut self, index: usize) -> Result<Option<String>> {
+        let Some(range) = self.records.get(index).cloned() else {
+            return Ok(None);
+        };
+        let mut lines = Vec::with_capacity(range.len());
+        for line in range {
+            lines.push(self.inner.get_line(line)?.unwrap_or_default());
+        }
+        Ok(Some(compact(&lines)))
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        self.inner.reload()?;
+        if self.inner.total_lines() < self.scanned {
+            // Truncated: regroup from scratch
+            self.records.clear();
+            self.scanned = 0;
+        }
+        self.scan()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn reader_for(content: &str) -> (JsonRecordReader, NamedTempFile) {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file.flush().unwrap();
+        (JsonRecordReader::new(file.path()).unwrap(), file)
+    }
+
+    fn all_lines(reader: &mut JsonRecordReader) -> Vec<String> {
+        (0..reader.total_lines())
+            .map(|i| reader.get_line(i).unwrap().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_pretty_objects_become_one_line_each() {
+        let (mut reader, _file) = reader_for(
+            "starting\n{\n  \"msg\": \"a {brace}\",\n  \"level\": \"info\"\n}\n{\n  \"n\": [1,\n    2]\n}\n",
+        );
+        assert_eq!(
+            all_lines(&mut reader),
+            vec![
+                "starting",
+                r#"{"msg":"a {brace}","level":"info"}"#,
+                r#"{"n":[1,2]}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pretty_json_keeps_key_order() {
+        assert_eq!(
+            pretty_json(r#"{"z":1,"a":{"s":"x, {y}"},"e":[]}"#).unwrap(),
+            "{\n  \"z\": 1,\n  \"a\": {\n    \"s\": \"x, {y}\"\n  },\n  \"e\": []\n}"
+        );
+        assert!(pretty_json("plain text").is_none());
+        assert!(pretty_json("{broken").is_none());
+    }
+
+    #[test]
+    fn test_partial_object_waits_for_the_rest() {
+        let (mut reader, mut file) = reader_for("{\"a\": 1}\n{\n  \"b\": 2,\n");
+        assert_eq!(reader.total_lines(), 1);
+
+        file.write_all(b"  \"c\": 3\n}\n").unwrap();
+        file.flush().unwrap();
+        reader.reload().unwrap();
+        assert_eq!(all_lines(&mut reader)[1], r#"{"b":2,"c":3}"#);
+    }
+
+    #[test]
+    fn test_unclosed_object_does_not_swallow_the_next() {
+        let (mut reader, _file) = reader_for("{\n  \"broken\": 1\n{\n  \"ok\": 2\n}\n");
+        assert_eq!(
+            all_lines(&mut reader),
+            vec!["{", r#"  "broken": 1"#, r#"{"ok":2}"#]
+        );
+    }
+}
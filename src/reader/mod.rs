@@ -1,10 +1,102 @@
 pub mod combined_reader;
+pub mod encoding;
 pub mod file_reader;
+pub mod json_record_reader;
+pub mod rotated_reader;
 pub mod sparse_index;
 pub mod stream_reader;
 
 use anyhow::Result;
 use std::any::Any;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Bytes sampled from the start of a file when checking for binary content
+const BINARY_SAMPLE_BYTES: u64 = 8192;
+
+/// Whether the file at `path` looks binary rather than text: a NUL byte in
+/// its first 8 KiB. Rendering such files would mostly show control bytes.
+pub fn looks_binary(path: &Path) -> std::io::Result<bool> {
+    let mut sample = Vec::new();
+    File::open(path)?
+        .take(BINARY_SAMPLE_BYTES)
+        .read_to_end(&mut sample)?;
+    Ok(sample.contains(&0))
+}
+
+/// Identity of the file currently at `path` as (device, inode).
+///
+/// Changes when the file is replaced under the same name (log rotation,
+/// atomic rename). Always `None` on platforms without inode numbers.
+pub fn file_identity(path: &Path) -> Option<(u64, u64)> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        std::fs::metadata(path).ok().map(|m| (m.dev(), m.ino()))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+/// What makes two paths the same file: its identity where the platform has
+/// one, its canonical path otherwise (or the path as given, if it doesn't
+/// exist yet).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum FileKey {
+    Identity(u64, u64),
+    Path(PathBuf),
+}
+
+impl FileKey {
+    fn of(path: &Path) -> Self {
+        match file_identity(path) {
+            Some((dev, ino)) => FileKey::Identity(dev, ino),
+            None => FileKey::Path(path.canonicalize().unwrap_or_else(|_| path.to_path_buf())),
+        }
+    }
+}
+
+/// Files opened in tabs so far, to skip opening one again under another
+/// path: repeated arguments, `./app.log` and `app.log`, a symlink, or a
+/// config source that is also given on the command line.
+#[derive(Debug)]
+pub struct OpenFiles {
+    /// Index of the tab showing each file
+    seen: HashMap<FileKey, usize>,
+    /// Off (`dedup_files: false`) lets the same file open in several tabs
+    enabled: bool,
+}
+
+impl OpenFiles {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            seen: HashMap::new(),
+            enabled,
+        }
+    }
+
+    /// Index of the tab that already shows the file at `path`, if any
+    pub fn find(&self, path: &Path) -> Option<usize> {
+        if !self.enabled {
+            return None;
+        }
+        self.seen.get(&FileKey::of(path)).copied()
+    }
+
+    /// Record that the tab at `tab_idx` shows the file at `path`. The first
+    /// tab recorded for a file keeps it.
+    pub fn insert(&mut self, path: &Path, tab_idx: usize) {
+        if self.enabled {
+            self.seen.entry(FileKey::of(path)).or_insert(tab_idx);
+        }
+    }
+}
 
 /// Trait for reading log lines
 pub trait LogReader {
@@ -19,6 +111,9 @@ pub trait LogReader {
 
     /// Downcast support for accessing concrete reader types (e.g. CombinedReader).
     fn as_any(&self) -> &dyn Any;
+
+    /// Mutable downcast support, for settings only some readers have.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
 }
 
 /// Extension trait for stream-based readers that support incremental loading.
@@ -26,9 +121,13 @@ pub trait LogReader {
 /// Only implemented by `StreamReader` — `FileReader` does not implement this.
 /// Tab stores an optional `Box<dyn StreamableReader>` for stream-specific operations.
 pub trait StreamableReader: LogReader + Send {
-    /// Append lines for incremental loading
+    /// Append lines for incremental loading. Clears any partial line, which
+    /// the appended lines complete.
     fn append_lines(&mut self, lines: Vec<String>);
 
+    /// Show (or with `None`, drop) an unterminated line after the last one
+    fn set_partial_line(&mut self, line: Option<String>);
+
     /// Mark the stream as complete (no more data will arrive)
     fn mark_complete(&mut self);
 
@@ -36,3 +135,44 @@ pub trait StreamableReader: LogReader + Send {
     #[allow(dead_code)]
     fn is_loading(&self) -> bool;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_looks_binary() {
+        let mut text = NamedTempFile::new().unwrap();
+        writeln!(text, "INFO caf\u{e9} \t tab").unwrap();
+        assert!(!looks_binary(text.path()).unwrap());
+
+        let mut binary = NamedTempFile::new().unwrap();
+        binary.write_all(b"\x7fELF\x02\x01\x00\x00").unwrap();
+        assert!(looks_binary(binary.path()).unwrap());
+    }
+
+    #[test]
+    fn test_open_files_spots_the_same_file_under_another_path() {
+        let file = NamedTempFile::new().unwrap();
+        let path = file.path();
+        let dotted = path
+            .parent()
+            .unwrap()
+            .join(".")
+            .join(path.file_name().unwrap());
+
+        let mut open = OpenFiles::new(true);
+        assert_eq!(open.find(path), None);
+        open.insert(path, 2);
+        assert_eq!(open.find(&dotted), Some(2));
+        open.insert(&dotted, 5);
+        assert_eq!(open.find(path), Some(2));
+        assert_eq!(open.find(Path::new("/nonexistent/other.log")), None);
+
+        let mut allowed = OpenFiles::new(false);
+        allowed.insert(path, 0);
+        assert_eq!(allowed.find(path), None);
+    }
+}
@@ -0,0 +1,403 @@
+//! One logical source stitched from a log file and its rotated siblings.
+//!
+//! `app.log.2.gz`, `app.log.1` and `app.log` are read back to back, oldest
+//! first, so the live file's lines come last and follow mode tails it.
+//! Handles logrotate's numbered (`app.log.1`, `app.log.2.gz`) and dated
+//! (`app.log-20240101`, `app.log-20240101.gz`) names.
+
+use super::encoding::Encoding;
+use super::file_reader::{decode_line, FileReader};
+use super::{file_identity, LogReader};
+use anyhow::{Context, Result};
+use flate2::read::MultiGzDecoder;
+use std::cmp::Ordering;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Position of a rotated file in the rotation, parsed from its name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RotationKey {
+    /// dateext suffix, digits only (later sorts newer)
+    Dated(String),
+    /// Numbered suffix (higher is older)
+    Numbered(u32),
+}
+
+impl RotationKey {
+    /// Ordering from oldest to newest.
+    fn age_order(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (RotationKey::Dated(a), RotationKey::Dated(b)) => a.cmp(b),
+            (RotationKey::Numbered(a), RotationKey::Numbered(b)) => b.cmp(a),
+            (RotationKey::Dated(_), RotationKey::Numbered(_)) => Ordering::Less,
+            (RotationKey::Numbered(_), RotationKey::Dated(_)) => Ordering::Greater,
+        }
+    }
+}
+
+/// Parse `candidate` as a rotated copy of the file named `base`.
+fn rotation_key(base: &str, candidate: &str) -> Option<RotationKey> {
+    let rest = candidate.strip_prefix(base)?;
+    let rest = rest.strip_suffix(".gz").unwrap_or(rest);
+    let suffix = rest.strip_prefix(['.', '-', '_'])?;
+    if suffix.is_empty() || !suffix.chars().all(|c| c.is_ascii_digit() || c == '-') {
+        return None;
+    }
+    let digits: String = suffix.chars().filter(char::is_ascii_digit).collect();
+    // Dates (YYYYMMDD and longer) vs. rotation counters
+    if digits.len() >= 8 {
+        Some(RotationKey::Dated(digits))
+    } else if digits.len() == suffix.len() {
+        suffix.parse().ok().map(RotationKey::Numbered)
+    } else {
+        None
+    }
+}
+
+/// Rotated siblings of `live` in its directory, oldest first (`live` itself
+/// excluded).
+pub fn rotation_siblings(live: &Path) -> Vec<PathBuf> {
+    let Some(base) = live.file_name().map(|n| n.to_string_lossy().to_string()) else {
+        return Vec::new();
+    };
+    let dir = match live.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut found: Vec<(RotationKey, PathBuf)> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            rotation_key(&base, &name).map(|key| (key, entry.path()))
+        })
+        .collect();
+    found.sort_by(|(a, _), (b, _)| a.age_order(b));
+    found.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Decompressed rotated file, kept as raw bytes plus line starts so the
+/// encoding can still change after loading.
+struct MemoryLines {
+    data: Vec<u8>,
+    starts: Vec<usize>,
+}
+
+impl MemoryLines {
+    fn read_gz(path: &Path) -> Result<Self> {
+        let file =
+            File::open(path).with_context(|| format!("Failed to open: {}", path.display()))?;
+        let mut data = Vec::new();
+        MultiGzDecoder::new(file)
+            .read_to_end(&mut data)
+            .with_context(|| format!("Failed to decompress: {}", path.display()))?;
+        Ok(Self::from_bytes(data))
+    }
+
+    fn from_bytes(data: Vec<u8>) -> Self {
+        let mut starts = Vec::new();
+        if !data.is_empty() {
+            starts.push(0);
+        }
+        starts.extend(
+            memchr::memchr_iter(b'\n', &data)
+                .map(|i| i + 1)
+                .filter(|&start| start < data.len()),
+        );
+        Self { data, starts }
+    }
+
+    fn get(&self, index: usize, encoding: Encoding) -> Option<String> {
+        let start = *self.starts.get(index)?;
+        let end = self
+            .starts
+            .get(index + 1)
+            .copied()
+            .unwrap_or(self.data.len());
+        Some(decode_line(&self.data[start..end], encoding))
+    }
+}
+
+/// One rotated file: plain files are read lazily, compressed ones from memory.
+enum Segment {
+    File(FileReader),
+    Memory(MemoryLines),
+}
+
+impl Segment {
+    fn open(path: &Path) -> Result<Self> {
+        if path.extension().is_some_and(|ext| ext == "gz") {
+            MemoryLines::read_gz(path).map(Segment::Memory)
+        } else {
+            FileReader::new(path).map(Segment::File)
+        }
+    }
+
+    fn total_lines(&self) -> usize {
+        match self {
+            Segment::File(reader) => reader.total_lines(),
+            Segment::Memory(lines) => lines.starts.len(),
+        }
+    }
+
+    fn get_line(&mut self, index: usize, encoding: Encoding) -> Result<Option<String>> {
+        match self {
+            Segment::File(reader) => reader.get_line(index),
+            Segment::Memory(lines) => Ok(lines.get(index, encoding)),
+        }
+    }
+}
+
+/// Reader presenting a log file and its rotated siblings as one source.
+pub struct RotatedReader {
+    live_path: PathBuf,
+    /// Rotated files, oldest first
+    archives: Vec<Segment>,
+    /// First line of each archive, plus the archived line total at the end
+    starts: Vec<usize>,
+    live: FileReader,
+    /// (device, inode) of the live file — a change means it was rotated
+    live_id: Option<(u64, u64)>,
+    encoding: Encoding,
+    /// Segments were reopened on reload, so line numbers shifted
+    reset: bool,
+}
+
+impl RotatedReader {
+    /// Open `live` together with every rotated sibling found next to it.
+    ///
+    /// Siblings that cannot be read are left out.
+    pub fn open(live: &Path) -> Result<Self> {
+        let live_path = live.to_path_buf();
+        let live_id = file_identity(&live_path);
+        let reader = FileReader::new(&live_path)?;
+        let archives: Vec<Segment> = rotation_siblings(&live_path)
+            .iter()
+            .filter_map(|path| Segment::open(path).ok())
+            .collect();
+
+        let mut starts = Vec::with_capacity(archives.len() + 1);
+        let mut total = 0;
+        for archive in &archives {
+            starts.push(total);
+            total += archive.total_lines();
+        }
+        starts.push(total);
+
+        Ok(Self {
+            live_path,
+            archives,
+            starts,
+            live: reader,
+            live_id,
+            encoding: Encoding::default(),
+            reset: false,
+        })
+    }
+
+    /// Decode every segment's lines with the given encoding.
+    pub fn set_encoding(&mut self, encoding: Encoding) {
+        self.encoding = encoding;
+        self.live.set_encoding(encoding);
+        for archive in &mut self.archives {
+            if let Segment::File(reader) = archive {
+                reader.set_encoding(encoding);
+            }
+        }
+    }
+
+    /// The live file the rotated ones belong to.
+    pub fn live_path(&self) -> &Path {
+        &self.live_path
+    }
+
+    /// Number of rotated files read before the live one.
+    pub fn archive_count(&self) -> usize {
+        self.archives.len()
+    }
+
+    /// Whether the last reload reopened the segments, clearing the flag.
+    ///
+    /// Archives may have rotated away, so every line number may have moved:
+    /// callers must treat this like a truncation, not growth.
+    pub fn take_reset(&mut self) -> bool {
+        std::mem::take(&mut self.reset)
+    }
+
+    fn archived_lines(&self) -> usize {
+        self.starts.last().copied().unwrap_or(0)
+    }
+}
+
+impl LogReader for RotatedReader {
+    fn total_lines(&self) -> usize {
+        self.archived_lines() + self.live.total_lines()
+    }
+
+    fn get_line(&mut self, index: usize) -> Result<Option<String>> {
+        let archived = self.archived_lines();
+        if index >= archived {
+            return self.live.get_line(index - archived);
+        }
+        let segment = self.starts.partition_point(|&start| start <= index) - 1;
+        let offset = index - self.starts[segment];
+        self.archives[segment].get_line(offset, self.encoding)
+    }
+
+    fn reload(&mut self) -> Result<()> {
+        if file_identity(&self.live_path) != self.live_id {
+            // Rotated since opening: the old live file is now an archive
+            let encoding = self.encoding;
+            *self = Self::open(&self.live_path)?;
+            self.set_encoding(encoding);
+            self.reset = true;
+            return Ok(());
+        }
+        self.live.reload()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn write_gz(path: &Path, content: &str) {
+        let mut encoder = GzEncoder::new(File::create(path).unwrap(), Compression::default());
+        encoder.write_all(content.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+    }
+
+    fn all_lines(reader: &mut RotatedReader) -> Vec<String> {
+        (0..reader.total_lines())
+            .map(|i| reader.get_line(i).unwrap().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_rotation_key_conventions() {
+        assert_eq!(
+            rotation_key("app.log", "app.log.1"),
+            Some(RotationKey::Numbered(1))
+        );
+        assert_eq!(
+            rotation_key("app.log", "app.log.12.gz"),
+            Some(RotationKey::Numbered(12))
+        );
+        assert_eq!(
+            rotation_key("app.log", "app.log-20240131.gz"),
+            Some(RotationKey::Dated("20240131".to_string()))
+        );
+        assert_eq!(
+            rotation_key("app.log", "app.log.2024-01-31"),
+            Some(RotationKey::Dated("20240131".to_string()))
+        );
+        assert_eq!(rotation_key("app.log", "app.log"), None);
+        assert_eq!(rotation_key("app.log", "app.log.bak"), None);
+        assert_eq!(rotation_key("app.log", "app.logger.1"), None);
+        assert_eq!(rotation_key("app.log", "app.log.1-2"), None);
+    }
+
+    #[test]
+    fn test_age_order_oldest_first() {
+        let mut keys = vec![
+            RotationKey::Numbered(1),
+            RotationKey::Numbered(3),
+            RotationKey::Dated("20240102".to_string()),
+            RotationKey::Dated("20240101".to_string()),
+        ];
+        keys.sort_by(|a, b| a.age_order(b));
+        assert_eq!(
+            keys,
+            vec![
+                RotationKey::Dated("20240101".to_string()),
+                RotationKey::Dated("20240102".to_string()),
+                RotationKey::Numbered(3),
+                RotationKey::Numbered(1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_memory_lines_handle_missing_trailing_newline() {
+        let lines = MemoryLines::from_bytes(b"a\nb\r\nc".to_vec());
+        assert_eq!(lines.starts.len(), 3);
+        assert_eq!(lines.get(1, Encoding::default()).as_deref(), Some("b"));
+        assert_eq!(lines.get(2, Encoding::default()).as_deref(), Some("c"));
+        assert_eq!(lines.get(3, Encoding::default()), None);
+        assert!(MemoryLines::from_bytes(Vec::new()).starts.is_empty());
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_reads_rotation_oldest_first() {
+        let dir = TempDir::new().unwrap();
+        let live = dir.path().join("app.log");
+        write_gz(&dir.path().join("app.log.2.gz"), "one\ntwo\n");
+        std::fs::write(dir.path().join("app.log.1"), "three\n").unwrap();
+        std::fs::write(&live, "four\n").unwrap();
+        std::fs::write(dir.path().join("other.log.1"), "noise\n").unwrap();
+
+        let mut reader = RotatedReader::open(&live).unwrap();
+        assert_eq!(reader.archive_count(), 2);
+        assert_eq!(all_lines(&mut reader), vec!["one", "two", "three", "four"]);
+
+        // Appends to the live file show up at the end
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&live)
+            .unwrap();
+        writeln!(file, "five").unwrap();
+        reader.reload().unwrap();
+        assert_eq!(reader.get_line(4).unwrap().as_deref(), Some("five"));
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_reload_picks_up_rotation() {
+        let dir = TempDir::new().unwrap();
+        let live = dir.path().join("app.log");
+        std::fs::write(&live, "old\n").unwrap();
+        let mut reader = RotatedReader::open(&live).unwrap();
+        assert_eq!(reader.archive_count(), 0);
+
+        std::fs::rename(&live, dir.path().join("app.log.1")).unwrap();
+        std::fs::write(&live, "new\n").unwrap();
+        reader.reload().unwrap();
+
+        assert_eq!(reader.archive_count(), 1);
+        assert_eq!(all_lines(&mut reader), vec!["old", "new"]);
+        assert!(reader.take_reset());
+        assert!(!reader.take_reset());
+    }
+
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_unreadable_sibling_is_skipped() {
+        let dir = TempDir::new().unwrap();
+        let live = dir.path().join("app.log");
+        std::fs::write(dir.path().join("app.log.2.gz"), "not gzip").unwrap();
+        std::fs::write(dir.path().join("app.log.1"), "one\n").unwrap();
+        std::fs::write(&live, "two\n").unwrap();
+
+        let mut reader = RotatedReader::open(&live).unwrap();
+        assert_eq!(reader.archive_count(), 1);
+        assert_eq!(all_lines(&mut reader), vec!["one", "two"]);
+    }
+}
@@ -6,6 +6,8 @@ use anyhow::Result;
 pub struct StreamReader {
     /// All lines stored in memory
     lines: Vec<String>,
+    /// Unterminated last line shown after `lines`, if any
+    partial: Option<String>,
     /// Whether the stream has finished loading
     complete: bool,
 }
@@ -21,6 +23,7 @@ impl StreamReader {
 
         Ok(Self {
             lines,
+            partial: None,
             complete: true,
         })
     }
@@ -29,6 +32,7 @@ impl StreamReader {
     pub fn new_incremental() -> Self {
         Self {
             lines: Vec::new(),
+            partial: None,
             complete: false,
         }
     }
@@ -36,10 +40,13 @@ impl StreamReader {
 
 impl LogReader for StreamReader {
     fn total_lines(&self) -> usize {
-        self.lines.len()
+        self.lines.len() + usize::from(self.partial.is_some())
     }
 
     fn get_line(&mut self, index: usize) -> Result<Option<String>> {
+        if index == self.lines.len() {
+            return Ok(self.partial.clone());
+        }
         Ok(self.lines.get(index).cloned())
     }
 
@@ -52,13 +59,22 @@ impl LogReader for StreamReader {
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
 }
 
 impl StreamableReader for StreamReader {
     fn append_lines(&mut self, lines: Vec<String>) {
+        self.partial = None;
         self.lines.extend(lines);
     }
 
+    fn set_partial_line(&mut self, line: Option<String>) {
+        self.partial = line;
+    }
+
     fn mark_complete(&mut self) {
         self.complete = true;
     }
@@ -127,4 +143,18 @@ mod tests {
         // Content should still be there
         assert_eq!(reader.total_lines(), 2);
     }
+
+    #[test]
+    fn test_stream_reader_partial_line_is_replaced_by_appended_lines() {
+        let mut reader = StreamReader::new_incremental();
+        reader.append_lines(vec!["Line 1".to_string()]);
+        reader.set_partial_line(Some("Line".to_string()));
+        assert_eq!(reader.total_lines(), 2);
+        assert_eq!(reader.get_line(1).unwrap(), Some("Line".to_string()));
+
+        reader.append_lines(vec!["Line 2".to_string(), "Line 3".to_string()]);
+        assert_eq!(reader.total_lines(), 3);
+        assert_eq!(reader.get_line(1).unwrap(), Some("Line 2".to_string()));
+        assert_eq!(reader.get_line(3).unwrap(), None);
+    }
 }
@@ -1,4 +1,9 @@
 use super::preset::{CompiledPreset, PresetParser};
+use crate::index::flags::{detect_flags, FLAG_FORMAT_JSON, FLAG_FORMAT_LOGFMT, FLAG_IS_EMPTY};
+use crate::reader::LogReader;
+
+/// Newest lines sampled to find a source's dominant format
+const FORMAT_SAMPLE_LINES: usize = 200;
 
 /// Compiled detection rules for auto-matching presets to sources.
 pub struct CompiledDetect {
@@ -53,8 +58,6 @@ pub fn detect_presets<'a>(
     filename: Option<&str>,
     flags: Option<u32>,
 ) -> Vec<&'a CompiledPreset> {
-    use crate::index::flags::{FLAG_FORMAT_JSON, FLAG_FORMAT_LOGFMT};
-
     let mut results = Vec::new();
 
     // First pass: filename-based matching (higher priority)
@@ -95,6 +98,37 @@ pub fn detect_presets<'a>(
     results
 }
 
+/// The line format (JSON or logfmt) of at least four in five non-empty lines
+/// among the newest in `reader`, as the index flags would record it. `None`
+/// for mixed or plain text sources.
+pub fn dominant_format(reader: &mut dyn LogReader) -> Option<PresetParser> {
+    let total = reader.total_lines();
+    let (mut lines, mut json, mut logfmt) = (0usize, 0usize, 0usize);
+    for line in total.saturating_sub(FORMAT_SAMPLE_LINES)..total {
+        let Some(text) = reader.get_line(line).ok().flatten() else {
+            continue;
+        };
+        let flags = detect_flags(&text);
+        if flags & FLAG_IS_EMPTY != 0 {
+            continue;
+        }
+        lines += 1;
+        if flags & FLAG_FORMAT_JSON != 0 {
+            json += 1;
+        } else if flags & FLAG_FORMAT_LOGFMT != 0 {
+            logfmt += 1;
+        }
+    }
+    let dominant = |count: usize| lines > 0 && count * 5 >= lines * 4;
+    if dominant(json) {
+        Some(PresetParser::Json)
+    } else if dominant(logfmt) {
+        Some(PresetParser::Logfmt)
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -168,4 +202,34 @@ mod tests {
         assert_eq!(detected.len(), 1);
         assert_eq!(detected[0].name, "custom");
     }
+
+    #[test]
+    fn test_dominant_format_needs_most_lines_to_agree() {
+        use crate::reader::stream_reader::StreamReader;
+        use crate::reader::StreamableReader;
+
+        let reader_with = |lines: &[&str]| {
+            let mut reader = StreamReader::new_incremental();
+            reader.append_lines(lines.iter().map(|l| l.to_string()).collect());
+            reader
+        };
+
+        let mut json = reader_with(&[
+            r#"{"level":"info"}"#,
+            "",
+            r#"{"level":"warn"}"#,
+            r#"{"level":"info"}"#,
+            r#"{"level":"error"}"#,
+            "starting up",
+        ]);
+        assert_eq!(dominant_format(&mut json), Some(PresetParser::Json));
+
+        let mut logfmt = reader_with(&["level=info msg=up", "level=warn msg=slow"]);
+        assert_eq!(dominant_format(&mut logfmt), Some(PresetParser::Logfmt));
+
+        let mut mixed = reader_with(&[r#"{"level":"info"}"#, "level=warn msg=slow", "plain"]);
+        assert_eq!(dominant_format(&mut mixed), None);
+
+        assert_eq!(dominant_format(&mut reader_with(&[])), None);
+    }
 }
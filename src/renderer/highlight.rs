@@ -0,0 +1,227 @@
+//! Always-on highlight rules for "important" patterns.
+//!
+//! Highlights are independent of the active filter: every rendered line is
+//! checked against the configured patterns and matching substrings get the
+//! rule's style patched on top of whatever the renderer produced.
+
+use super::preset::resolve_style_value;
+use super::segment::SegmentStyle;
+use crate::config::types::RawHighlightDef;
+use regex::{Regex, RegexSet};
+use std::ops::Range;
+
+/// A single compiled highlight rule.
+pub struct HighlightRule {
+    pub regex: Regex,
+    pub style: SegmentStyle,
+}
+
+/// All compiled highlight rules, in config order.
+///
+/// A `RegexSet` over every pattern is used as a single-pass pre-check so lines
+/// that match nothing (the common case) never run the individual regexes.
+#[derive(Default)]
+pub struct HighlightSet {
+    set: Option<RegexSet>,
+    rules: Vec<HighlightRule>,
+}
+
+impl HighlightSet {
+    /// Compile highlight definitions from config.
+    /// Returns the set and a list of compilation error messages; invalid
+    /// rules are skipped so the remaining ones still apply.
+    pub fn compile(defs: &[RawHighlightDef]) -> (Self, Vec<String>) {
+        let mut rules = Vec::new();
+        let mut errors = Vec::new();
+
+        for def in defs {
+            let regex = match Regex::new(&def.pattern) {
+                Ok(r) => r,
+                Err(e) => {
+                    errors.push(format!(
+                        "Highlight '{}': invalid pattern: {}",
+                        def.pattern, e
+                    ));
+                    continue;
+                }
+            };
+            let style = match resolve_style_value(Some(&def.style)) {
+                Ok(s) => s,
+                Err(e) => {
+                    errors.push(format!("Highlight '{}': {}", def.pattern, e));
+                    continue;
+                }
+            };
+            rules.push(HighlightRule { regex, style });
+        }
+
+        let set = if rules.is_empty() {
+            None
+        } else {
+            RegexSet::new(rules.iter().map(|r| r.regex.as_str())).ok()
+        };
+
+        (Self { set, rules }, errors)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Find all highlighted byte ranges in `text`.
+    ///
+    /// Ranges are returned in rule order so later rules compose on top of
+    /// earlier ones when applied sequentially.
+    pub fn find(&self, text: &str) -> Vec<(Range<usize>, &SegmentStyle)> {
+        let Some(ref set) = self.set else {
+            return Vec::new();
+        };
+        let matched = set.matches(text);
+        if !matched.matched_any() {
+            return Vec::new();
+        }
+
+        let mut ranges = Vec::new();
+        for idx in matched.iter() {
+            let rule = &self.rules[idx];
+            for m in rule.regex.find_iter(text) {
+                if !m.is_empty() {
+                    ranges.push((m.range(), &rule.style));
+                }
+            }
+        }
+        ranges
+    }
+}
+
+/// Whether `c` can be part of a word for word highlighting: identifiers,
+/// numbers, and dotted or dashed ids like `req-42` or `10.0.0.1`.
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.')
+}
+
+/// Byte ranges of the words in `text`, in order. Leading and trailing dots
+/// and dashes are not part of a word.
+pub fn word_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        match (start, is_word_char(c)) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                start = None;
+                let word = text[s..i].trim_matches(['.', '-']);
+                if !word.is_empty() {
+                    let offset = s + text[s..i].find(word).unwrap_or(0);
+                    words.push(offset..offset + word.len());
+                }
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+/// Byte ranges of whole-word occurrences of `word` in `text`.
+pub fn find_word(text: &str, word: &str) -> Vec<Range<usize>> {
+    if word.is_empty() {
+        return Vec::new();
+    }
+    let continues = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    text.match_indices(word)
+        .map(|(start, _)| start..start + word.len())
+        .filter(|range| {
+            !continues(text[..range.start].chars().next_back())
+                && !continues(text[range.end..].chars().next())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::types::StyleValue;
+    use crate::renderer::segment::SegmentColor;
+
+    fn def(pattern: &str, style: &str) -> RawHighlightDef {
+        RawHighlightDef {
+            pattern: pattern.to_string(),
+            style: StyleValue::Single(style.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_compile_valid_rules() {
+        let (set, errors) = HighlightSet::compile(&[def("panic", "red"), def("OOM", "bold")]);
+        assert!(errors.is_empty());
+        assert!(!set.is_empty());
+    }
+
+    #[test]
+    fn test_compile_invalid_pattern_is_skipped() {
+        let (set, errors) = HighlightSet::compile(&[def("[unclosed", "red"), def("ok", "red")]);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("[unclosed"));
+        assert_eq!(set.find("ok").len(), 1);
+    }
+
+    #[test]
+    fn test_compile_invalid_style_is_reported() {
+        let (set, errors) = HighlightSet::compile(&[RawHighlightDef {
+            pattern: "x".to_string(),
+            style: StyleValue::List(vec!["red".to_string(), "blue".to_string()]),
+        }]);
+        assert_eq!(errors.len(), 1);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_find_no_match_is_empty() {
+        let (set, _) = HighlightSet::compile(&[def("panic", "red")]);
+        assert!(set.find("all good here").is_empty());
+    }
+
+    #[test]
+    fn test_find_all_occurrences() {
+        let (set, _) = HighlightSet::compile(&[def("panic", "red")]);
+        let ranges = set.find("panic then panic");
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(ranges[0].0, 0..5);
+        assert_eq!(ranges[1].0, 11..16);
+        assert_eq!(*ranges[0].1, SegmentStyle::Fg(SegmentColor::Red));
+    }
+
+    #[test]
+    fn test_find_multiple_rules_in_config_order() {
+        let (set, _) = HighlightSet::compile(&[def("OOM", "bold"), def("killed", "red")]);
+        let ranges = set.find("worker killed: OOM");
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(*ranges[0].1, SegmentStyle::Bold);
+        assert_eq!(*ranges[1].1, SegmentStyle::Fg(SegmentColor::Red));
+    }
+
+    #[test]
+    fn test_empty_set_finds_nothing() {
+        let set = HighlightSet::default();
+        assert!(set.is_empty());
+        assert!(set.find("anything").is_empty());
+    }
+
+    #[test]
+    fn test_word_ranges_trim_punctuation() {
+        let text = "ERROR [req-42] from 10.0.0.1: failed.";
+        let words: Vec<&str> = word_ranges(text).into_iter().map(|r| &text[r]).collect();
+        assert_eq!(words, vec!["ERROR", "req-42", "from", "10.0.0.1", "failed"]);
+        assert!(word_ranges(" -- ").is_empty());
+    }
+
+    #[test]
+    fn test_find_word_matches_whole_words_only() {
+        let text = "req-42 req-420 xreq-42 (req-42)";
+        assert_eq!(find_word(text, "req-42"), vec![0..6, 24..30]);
+        assert!(find_word(text, "").is_empty());
+    }
+}
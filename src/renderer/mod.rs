@@ -2,8 +2,10 @@ pub mod builtin;
 pub mod detect;
 pub mod field;
 pub mod format;
+pub mod highlight;
 pub mod preset;
 pub mod segment;
+pub mod transform;
 
 use preset::CompiledPreset;
 use segment::StyledSegment;
@@ -100,12 +102,18 @@ impl PresetRegistry {
         Self { presets }
     }
 
-    /// Returns names of all registered presets (test-only).
-    #[cfg(test)]
+    /// Returns names of all registered presets, user presets first.
     pub fn all_preset_names(&self) -> Vec<&str> {
         self.presets.iter().map(|p| p.name.as_str()).collect()
     }
 
+    /// Put `preset` first, shadowing any registered preset of the same name.
+    pub fn with_preset(mut self, preset: CompiledPreset) -> Self {
+        self.presets.retain(|p| p.name != preset.name);
+        self.presets.insert(0, preset);
+        self
+    }
+
     /// Lookup preset by name.
     pub fn get_by_name(&self, name: &str) -> Option<&CompiledPreset> {
         self.presets.iter().find(|p| p.name == name)
@@ -194,26 +202,9 @@ fn load_external_presets(dirs: &[PathBuf]) -> (Vec<preset::RawPreset>, Vec<Strin
             if path.extension().and_then(|e| e.to_str()) != Some("yaml") {
                 continue;
             }
-            let content = match std::fs::read_to_string(&path) {
-                Ok(c) => c,
-                Err(e) => {
-                    errors.push(format!(
-                        "Failed to read renderer file {}: {}",
-                        path.display(),
-                        e
-                    ));
-                    continue;
-                }
-            };
-            match serde_saphyr::from_str::<preset::RawPreset>(&content) {
+            match read_preset_file(&path) {
                 Ok(raw) => presets.push(raw),
-                Err(e) => {
-                    errors.push(format!(
-                        "Failed to parse renderer file {}: {}",
-                        path.display(),
-                        e
-                    ));
-                }
+                Err(e) => errors.push(e),
             }
         }
     }
@@ -221,6 +212,21 @@ fn load_external_presets(dirs: &[PathBuf]) -> (Vec<preset::RawPreset>, Vec<Strin
     (presets, errors)
 }
 
+/// Read and parse one preset file (same format as the renderers directories).
+fn read_preset_file(path: &Path) -> Result<preset::RawPreset, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read renderer file {}: {}", path.display(), e))?;
+    serde_saphyr::from_str::<preset::RawPreset>(&content)
+        .map_err(|e| format!("Failed to parse renderer file {}: {}", path.display(), e))
+}
+
+/// Read, parse and compile one preset file.
+pub fn compile_preset_file(path: &Path) -> Result<CompiledPreset, String> {
+    let raw = read_preset_file(path)?;
+    let name = raw.name.clone();
+    preset::compile(raw).map_err(|e| format!("Renderer '{}' ({}): {}", name, path.display(), e))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -233,6 +239,36 @@ mod tests {
         assert_eq!(json.unwrap().name, "json");
     }
 
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_compile_preset_file_reports_errors_and_shadows_builtins() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("json.yaml");
+        std::fs::write(
+            &path,
+            "name: json\nregex: '(unclosed'\nlayout:\n  - field: a\n",
+        )
+        .unwrap();
+        let Err(err) = compile_preset_file(&path) else {
+            panic!("invalid regex should not compile");
+        };
+        assert!(err.starts_with("Renderer 'json'"), "{}", err);
+        assert!(err.contains("json.yaml"), "{}", err);
+
+        std::fs::write(&path, "name: json\nlayout:\n  - literal: custom\n").unwrap();
+        let preset = compile_preset_file(&path).unwrap();
+        let registry = PresetRegistry::new(Vec::new()).with_preset(preset);
+        assert_eq!(registry.all_preset_names()[0], "json");
+        assert_eq!(
+            registry
+                .all_preset_names()
+                .iter()
+                .filter(|n| **n == "json")
+                .count(),
+            1
+        );
+    }
+
     #[test]
     fn test_registry_render_line_json() {
         let registry = PresetRegistry::new(Vec::new());
@@ -344,7 +344,7 @@ fn resolve_style_string(style: Option<&str>) -> SegmentStyle {
     }
 }
 
-fn resolve_style_value(style: Option<&StyleValue>) -> Result<SegmentStyle, String> {
+pub(crate) fn resolve_style_value(style: Option<&StyleValue>) -> Result<SegmentStyle, String> {
     match style {
         Some(StyleValue::Single(s)) => Ok(resolve_style_string(Some(s))),
         Some(StyleValue::List(names)) => resolve_compound_style(names),
@@ -0,0 +1,122 @@
+//! Per-source display transform: rewrite lines with a regex and template.
+//!
+//! The transform only changes what is drawn. Filtering, copying and the MCP
+//! tools keep working on the original line.
+
+use crate::config::types::RawDisplayTransform;
+use regex::Regex;
+
+/// A compiled `display_transform` rule.
+#[derive(Debug, Clone)]
+pub struct DisplayTransform {
+    regex: Regex,
+    template: String,
+}
+
+impl DisplayTransform {
+    /// Compile and validate a transform from config.
+    ///
+    /// Fails if the pattern is not a valid regex or the template references
+    /// a capture group the pattern does not define.
+    pub fn compile(raw: &RawDisplayTransform) -> Result<Self, String> {
+        let regex = Regex::new(&raw.pattern).map_err(|e| format!("invalid pattern: {}", e))?;
+        validate_template(&regex, &raw.template)?;
+        Ok(Self {
+            regex,
+            template: raw.template.clone(),
+        })
+    }
+
+    /// Rewrite a line (already stripped of ANSI codes).
+    ///
+    /// Returns `None` when the pattern does not match, so the line is shown
+    /// unchanged.
+    pub fn apply(&self, line: &str) -> Option<String> {
+        let caps = self.regex.captures(line)?;
+        let mut out = String::new();
+        caps.expand(&self.template, &mut out);
+        Some(out)
+    }
+}
+
+/// Check every `$N`, `${N}`, `$name` and `${name}` reference in the template
+/// against the pattern's capture groups (`$$` is a literal dollar).
+fn validate_template(regex: &Regex, template: &str) -> Result<(), String> {
+    let mut rest = template;
+    while let Some(pos) = rest.find('$') {
+        rest = &rest[pos + 1..];
+        let (name, len) = if let Some(braced) = rest.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], end + 2),
+                None => return Err("unclosed '${' in template".to_string()),
+            }
+        } else if rest.starts_with('$') {
+            rest = &rest[1..];
+            continue;
+        } else {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            (&rest[..end], end)
+        };
+        rest = &rest[len..];
+
+        if name.is_empty() {
+            continue;
+        }
+        let known = match name.parse::<usize>() {
+            Ok(index) => index < regex.captures_len(),
+            Err(_) => regex.capture_names().flatten().any(|n| n == name),
+        };
+        if !known {
+            return Err(format!("template references unknown group '{}'", name));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transform(pattern: &str, template: &str) -> Result<DisplayTransform, String> {
+        DisplayTransform::compile(&RawDisplayTransform {
+            pattern: pattern.to_string(),
+            template: template.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_apply_numbered_and_named_groups() {
+        let t = transform(r"level=(\w+) .*msg=(?P<msg>.*)", "[$1] ${msg}").unwrap();
+        assert_eq!(
+            t.apply("ts=1 level=warn user=7 msg=disk almost full")
+                .as_deref(),
+            Some("[warn] disk almost full")
+        );
+    }
+
+    #[test]
+    fn test_apply_no_match_returns_none() {
+        let t = transform(r"msg=(.*)", "$1").unwrap();
+        assert_eq!(t.apply("plain line"), None);
+    }
+
+    #[test]
+    fn test_dollar_escape_is_literal() {
+        let t = transform(r"cost=(\d+)", "$$$1").unwrap();
+        assert_eq!(t.apply("cost=5").as_deref(), Some("$5"));
+    }
+
+    #[test]
+    fn test_compile_rejects_invalid_pattern_and_unknown_groups() {
+        assert!(transform("(unclosed", "$1")
+            .unwrap_err()
+            .contains("invalid pattern"));
+        assert!(transform(r"(\d+)", "$2").unwrap_err().contains("'2'"));
+        assert!(transform(r"(?P<a>\d+)", "${b}")
+            .unwrap_err()
+            .contains("'b'"));
+        assert!(transform(r"(\d+)", "${1").is_err());
+    }
+}
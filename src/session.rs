@@ -8,6 +8,12 @@ use std::path::PathBuf;
 /// Maximum number of context entries to keep in the session file.
 const MAX_CONTEXTS: usize = 100;
 
+/// Maximum number of expanded lines remembered per source.
+const MAX_EXPANDED_PER_SOURCE: usize = 1000;
+
+/// Maximum number of sources with expanded lines remembered per context.
+const MAX_EXPANDED_SOURCES: usize = 50;
+
 /// Key used for non-project (global) context.
 const GLOBAL_KEY: &str = "__global__";
 
@@ -16,9 +22,16 @@ struct SessionFile {
     contexts: HashMap<String, ContextEntry>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default)]
 struct ContextEntry {
+    #[serde(default)]
     last_source: String,
+    /// Expanded file line numbers, keyed by source file path.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    expanded_lines: HashMap<String, Vec<usize>>,
+    /// Hidden severity labels, keyed by source file path.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    hidden_severities: HashMap<String, Vec<String>>,
 }
 
 #[cfg(not(test))]
@@ -70,18 +83,156 @@ pub fn save_last_source(project_root: Option<&Path>, name: &str) {
     }
 }
 
-fn load_from(path: &Path, project_root: Option<&Path>) -> Option<String> {
+/// Load remembered expanded lines for the given project context, keyed by
+/// source file path.
+///
+/// In test builds, returns an empty map to avoid reading the user's real session file.
+pub fn load_expanded_lines(project_root: Option<&Path>) -> HashMap<String, Vec<usize>> {
+    #[cfg(test)]
+    {
+        let _ = project_root;
+        HashMap::new()
+    }
+
+    #[cfg(not(test))]
+    {
+        session_file_path()
+            .and_then(|path| load_entry(&path, project_root))
+            .map(|entry| entry.expanded_lines)
+            .unwrap_or_default()
+    }
+}
+
+/// Save expanded lines per source file path for the given project context.
+///
+/// Sources with no expanded lines are forgotten; sources not listed keep
+/// their previously saved state. In test builds, this is a no-op.
+pub fn save_expanded_lines(project_root: Option<&Path>, sources: &[(String, Vec<usize>)]) {
+    #[cfg(test)]
+    {
+        let _ = (project_root, sources);
+    }
+
+    #[cfg(not(test))]
+    {
+        let Some(path) = session_file_path() else {
+            return;
+        };
+        save_expanded_to(&path, project_root, sources);
+    }
+}
+
+/// Load remembered hidden severity labels for the given project context,
+/// keyed by source file path.
+///
+/// In test builds, returns an empty map to avoid reading the user's real session file.
+pub fn load_hidden_severities(project_root: Option<&Path>) -> HashMap<String, Vec<String>> {
+    #[cfg(test)]
+    {
+        let _ = project_root;
+        HashMap::new()
+    }
+
+    #[cfg(not(test))]
+    {
+        session_file_path()
+            .and_then(|path| load_entry(&path, project_root))
+            .map(|entry| entry.hidden_severities)
+            .unwrap_or_default()
+    }
+}
+
+/// Save hidden severity labels per source file path for the given project
+/// context.
+///
+/// Sources hiding nothing are forgotten; sources not listed keep their
+/// previously saved state. In test builds, this is a no-op.
+pub fn save_hidden_severities(project_root: Option<&Path>, sources: &[(String, Vec<String>)]) {
+    #[cfg(test)]
+    {
+        let _ = (project_root, sources);
+    }
+
+    #[cfg(not(test))]
+    {
+        let Some(path) = session_file_path() else {
+            return;
+        };
+        save_hidden_to(&path, project_root, sources);
+    }
+}
+
+fn load_entry(path: &Path, project_root: Option<&Path>) -> Option<ContextEntry> {
     if !path.exists() {
         return None;
     }
 
     let content = fs::read_to_string(path).ok()?;
-    let session: SessionFile = serde_json::from_str(&content).ok()?;
+    let mut session: SessionFile = serde_json::from_str(&content).ok()?;
     let key = context_key(project_root);
-    session.contexts.get(&key).map(|e| e.last_source.clone())
+    session.contexts.remove(&key)
+}
+
+fn load_from(path: &Path, project_root: Option<&Path>) -> Option<String> {
+    load_entry(path, project_root)
+        .map(|e| e.last_source)
+        .filter(|name| !name.is_empty())
 }
 
 fn save_to(path: &Path, project_root: Option<&Path>, name: &str) {
+    update_entry(path, project_root, |entry| {
+        entry.last_source = name.to_string();
+    });
+}
+
+fn save_expanded_to(path: &Path, project_root: Option<&Path>, sources: &[(String, Vec<usize>)]) {
+    update_entry(path, project_root, |entry| {
+        for (source, lines) in sources {
+            if lines.is_empty() {
+                entry.expanded_lines.remove(source);
+            } else {
+                let mut lines = lines.clone();
+                lines.sort_unstable();
+                lines.truncate(MAX_EXPANDED_PER_SOURCE);
+                entry.expanded_lines.insert(source.clone(), lines);
+            }
+        }
+
+        // Forget sources not saved this time once there are too many
+        // (arbitrary since HashMap has no order, but bounds the file)
+        let excess = entry
+            .expanded_lines
+            .len()
+            .saturating_sub(MAX_EXPANDED_SOURCES);
+        let stale: Vec<String> = entry
+            .expanded_lines
+            .keys()
+            .filter(|key| !sources.iter().any(|(source, _)| source == *key))
+            .take(excess)
+            .cloned()
+            .collect();
+        for key in stale {
+            entry.expanded_lines.remove(&key);
+        }
+    });
+}
+
+fn save_hidden_to(path: &Path, project_root: Option<&Path>, sources: &[(String, Vec<String>)]) {
+    update_entry(path, project_root, |entry| {
+        for (source, levels) in sources {
+            if levels.is_empty() {
+                entry.hidden_severities.remove(source);
+            } else {
+                entry
+                    .hidden_severities
+                    .insert(source.clone(), levels.clone());
+            }
+        }
+    });
+}
+
+/// Read-modify-write the context entry for `project_root`.
+fn update_entry(path: &Path, project_root: Option<&Path>, update: impl FnOnce(&mut ContextEntry)) {
     // Create parent directory if it doesn't exist
     if let Some(parent) = path.parent() {
         if fs::create_dir_all(parent).is_err() {
@@ -101,12 +252,7 @@ fn save_to(path: &Path, project_root: Option<&Path>, name: &str) {
         .unwrap_or_default();
 
     let key = context_key(project_root);
-    session.contexts.insert(
-        key,
-        ContextEntry {
-            last_source: name.to_string(),
-        },
-    );
+    update(session.contexts.entry(key).or_default());
 
     // Cap entries to prevent unbounded growth
     if session.contexts.len() > MAX_CONTEXTS {
@@ -136,12 +282,14 @@ mod tests {
             "/home/user/project".to_string(),
             ContextEntry {
                 last_source: "api-logs".to_string(),
+                ..Default::default()
             },
         );
         session.contexts.insert(
             GLOBAL_KEY.to_string(),
             ContextEntry {
                 last_source: "system".to_string(),
+                ..Default::default()
             },
         );
 
@@ -172,6 +320,7 @@ mod tests {
                 format!("/project/{}", i),
                 ContextEntry {
                     last_source: format!("source-{}", i),
+                    ..Default::default()
                 },
             );
         }
@@ -225,4 +374,91 @@ mod tests {
         );
         assert_eq!(load_from(&path, None).as_deref(), Some("global-source"));
     }
+
+    #[test]
+    fn test_expanded_lines_roundtrip_keeps_last_source() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("session.json");
+        let project = Path::new("/test/project");
+
+        save_to(&path, Some(project), "api");
+        save_expanded_to(
+            &path,
+            Some(project),
+            &[
+                ("/logs/api.log".to_string(), vec![42, 7]),
+                ("/logs/worker.log".to_string(), vec![3]),
+            ],
+        );
+
+        let entry = load_entry(&path, Some(project)).unwrap();
+        assert_eq!(entry.expanded_lines["/logs/api.log"], vec![7, 42]);
+        assert_eq!(load_from(&path, Some(project)).as_deref(), Some("api"));
+
+        // Collapsing everything forgets the source; others are untouched
+        save_expanded_to(
+            &path,
+            Some(project),
+            &[("/logs/api.log".to_string(), vec![])],
+        );
+        let entry = load_entry(&path, Some(project)).unwrap();
+        assert!(!entry.expanded_lines.contains_key("/logs/api.log"));
+        assert_eq!(entry.expanded_lines["/logs/worker.log"], vec![3]);
+    }
+
+    #[test]
+    fn test_expanded_lines_caps_remembered_sources() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("session.json");
+
+        for i in 0..MAX_EXPANDED_SOURCES + 5 {
+            save_expanded_to(&path, None, &[(format!("/logs/{}.log", i), vec![i])]);
+        }
+
+        let entry = load_entry(&path, None).unwrap();
+        assert_eq!(entry.expanded_lines.len(), MAX_EXPANDED_SOURCES);
+        let last = format!("/logs/{}.log", MAX_EXPANDED_SOURCES + 4);
+        assert_eq!(entry.expanded_lines[&last], vec![MAX_EXPANDED_SOURCES + 4]);
+    }
+
+    #[test]
+    fn test_hidden_severities_roundtrip_keeps_expanded_lines() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("session.json");
+
+        save_expanded_to(&path, None, &[("/logs/api.log".to_string(), vec![5])]);
+        save_hidden_to(
+            &path,
+            None,
+            &[
+                ("/logs/api.log".to_string(), vec!["debug".to_string()]),
+                ("/logs/worker.log".to_string(), vec!["trace".to_string()]),
+            ],
+        );
+        let entry = load_entry(&path, None).unwrap();
+        assert_eq!(entry.hidden_severities["/logs/api.log"], vec!["debug"]);
+        assert_eq!(entry.expanded_lines["/logs/api.log"], vec![5]);
+
+        // Showing every level forgets the source; others are untouched
+        save_hidden_to(&path, None, &[("/logs/api.log".to_string(), vec![])]);
+        let entry = load_entry(&path, None).unwrap();
+        assert!(!entry.hidden_severities.contains_key("/logs/api.log"));
+        assert_eq!(entry.hidden_severities["/logs/worker.log"], vec!["trace"]);
+    }
+
+    #[test]
+    fn test_load_session_without_expanded_lines() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("session.json");
+        fs::write(
+            &path,
+            r#"{"contexts":{"__global__":{"last_source":"old"}}}"#,
+        )
+        .unwrap();
+
+        let entry = load_entry(&path, None).unwrap();
+        assert_eq!(entry.last_source, "old");
+        assert!(entry.expanded_lines.is_empty());
+        assert!(entry.hidden_severities.is_empty());
+    }
 }
@@ -5,17 +5,204 @@
 //! - Source discovery from the data directory
 //! - PID-based marker files for active source tracking
 //! - Collision detection for capture mode
+//! - Index directory placement (beside the log or in a central cache)
 
 use crate::config::DiscoveryResult;
 use anyhow::{Context, Result};
 use std::fs::{self, DirBuilder, File, OpenOptions};
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Central index cache directory, once enabled for this process.
+static INDEX_CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Configured checkpoint interval for indexes built while viewing.
+static INDEX_CHECKPOINT_INTERVAL: OnceLock<u16> = OnceLock::new();
+
+/// Set in low-resource mode: indexes are only built on request.
+static INDEX_BUILDS_DEFERRED: OnceLock<bool> = OnceLock::new();
+
+/// File inside a cached index directory recording which log it indexes.
+const INDEX_SOURCE_FILE: &str = "source";
 
 /// Derive the index directory path for a given log file.
 /// e.g., `/path/to/myapp.log` → `/path/to/myapp.idx/`
+///
+/// With the central cache enabled ([`use_index_cache_dir`]), the directory
+/// lives under the cache instead (see [`cached_index_dir`]).
 pub fn index_dir_for_log(log_path: &Path) -> PathBuf {
-    log_path.with_extension("idx")
+    match INDEX_CACHE_DIR.get() {
+        Some(cache_dir) => cached_index_dir(cache_dir, log_path),
+        None => log_path.with_extension("idx"),
+    }
+}
+
+/// Whether the log at `log_path` has no usable index to open and should get
+/// one built: none exists yet, or it was written in an older format.
+pub fn index_needs_build(log_path: &Path) -> bool {
+    let meta = index_dir_for_log(log_path).join("meta");
+    !meta.exists() || crate::index::meta::IndexMeta::is_outdated(&meta)
+}
+
+/// Default central index cache: ~/.cache/lazytail/index/
+pub fn index_cache_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|p| p.join(".cache").join("lazytail").join("index"))
+}
+
+/// Store indexes under `cache_dir` instead of beside each log for the rest
+/// of the process. Only the first call takes effect.
+pub fn use_index_cache_dir(cache_dir: PathBuf) {
+    let _ = INDEX_CACHE_DIR.set(cache_dir);
+}
+
+/// Apply the config's index settings for the rest of the process: switch to
+/// the central index cache when enabled, and keep the checkpoint interval
+/// for indexes built in the background. Must run before any index path is
+/// derived.
+pub fn configure_indexes(cfg: &crate::config::Config) {
+    if cfg.index.as_ref().is_some_and(|index| index.central_cache) {
+        if let Some(cache_dir) = index_cache_dir() {
+            use_index_cache_dir(cache_dir);
+        }
+    }
+    let _ = INDEX_CHECKPOINT_INTERVAL.set(cfg.index_checkpoint_interval());
+}
+
+/// Checkpoint interval for indexes built in the background: the configured
+/// `index.checkpoint_interval`, or the builder's default.
+pub fn index_checkpoint_interval() -> u16 {
+    INDEX_CHECKPOINT_INTERVAL
+        .get()
+        .copied()
+        .unwrap_or(crate::index::builder::DEFAULT_CHECKPOINT_INTERVAL)
+}
+
+/// Stop building indexes automatically for the rest of the process
+/// (low-resource mode). Explicit builds still run.
+pub fn defer_index_builds() {
+    let _ = INDEX_BUILDS_DEFERRED.set(true);
+}
+
+/// Whether indexes are only built on request.
+pub fn index_builds_deferred() -> bool {
+    INDEX_BUILDS_DEFERRED.get().copied().unwrap_or(false)
+}
+
+/// Index directory for a log inside a central cache:
+/// `<cache_dir>/<file stem>-<hash>/`, where the hash covers the canonical log
+/// path so same-named logs in different directories never share an index.
+pub fn cached_index_dir(cache_dir: &Path, log_path: &Path) -> PathBuf {
+    let canonical = canonical_log_path(log_path);
+    let hash = xxhash_rust::xxh3::xxh3_64(canonical.as_os_str().as_encoded_bytes());
+    let stem = canonical
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    cache_dir.join(format!("{}-{:016x}", stem, hash))
+}
+
+/// Record which log a cached index directory belongs to, so `lazytail index
+/// gc` can tell orphans apart. No-op for indexes stored beside their log.
+pub fn record_index_source(log_path: &Path, index_dir: &Path) -> std::io::Result<()> {
+    match INDEX_CACHE_DIR.get() {
+        Some(cache_dir) if index_dir.starts_with(cache_dir) => fs::write(
+            index_dir.join(INDEX_SOURCE_FILE),
+            canonical_log_path(log_path).as_os_str().as_encoded_bytes(),
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Absolute, symlink-resolved path of a log file. A log that doesn't exist
+/// yet (capture about to create it) resolves through its parent directory.
+fn canonical_log_path(log_path: &Path) -> PathBuf {
+    if let Ok(path) = fs::canonicalize(log_path) {
+        return path;
+    }
+    let parent = match log_path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    match (fs::canonicalize(parent), log_path.file_name()) {
+        (Ok(dir), Some(name)) => dir.join(name),
+        _ => std::path::absolute(log_path).unwrap_or_else(|_| log_path.to_path_buf()),
+    }
+}
+
+/// Outcome of pruning the central index cache.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct IndexGcReport {
+    /// Index directories kept because their log still exists, or because
+    /// a build or capture is writing them.
+    pub kept: usize,
+    /// Orphaned index directories (removed unless this was a dry run).
+    pub orphaned: Vec<PathBuf>,
+    /// Bytes held by the orphaned directories.
+    pub orphaned_bytes: u64,
+}
+
+/// Find index directories in `cache_dir` whose log file no longer exists (or
+/// that don't record a log at all) and remove them unless `dry_run`.
+pub fn gc_index_cache(cache_dir: &Path, dry_run: bool) -> Result<IndexGcReport> {
+    let mut report = IndexGcReport::default();
+    let entries = match fs::read_dir(cache_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(report),
+        Err(e) => {
+            return Err(e).with_context(|| format!("reading {}", cache_dir.display()));
+        }
+    };
+    for entry in entries {
+        let dir = entry?.path();
+        if !dir.is_dir() {
+            continue;
+        }
+        let source = fs::read(dir.join(INDEX_SOURCE_FILE))
+            .ok()
+            .and_then(recorded_log_path);
+        if source.is_some_and(|path| path.is_file()) {
+            report.kept += 1;
+            continue;
+        }
+        // Held until the directory is gone, so no writer starts meanwhile
+        let Ok(Some(_lock)) = crate::index::lock::IndexWriteLock::try_acquire(&dir) else {
+            report.kept += 1;
+            continue;
+        };
+        report.orphaned_bytes += dir_size(&dir);
+        if !dry_run {
+            fs::remove_dir_all(&dir).with_context(|| format!("removing {}", dir.display()))?;
+        }
+        report.orphaned.push(dir);
+    }
+    report.orphaned.sort();
+    Ok(report)
+}
+
+/// Decode the log path [`record_index_source`] wrote (its raw OS bytes).
+fn recorded_log_path(bytes: Vec<u8>) -> Option<PathBuf> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        Some(PathBuf::from(std::ffi::OsStr::from_bytes(&bytes)))
+    }
+    #[cfg(not(unix))]
+    {
+        String::from_utf8(bytes).ok().map(PathBuf::from)
+    }
+}
+
+/// Total size of the files directly inside `dir`.
+fn dir_size(dir: &Path) -> u64 {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok()?.metadata().ok())
+                .map(|m| m.len())
+                .sum()
+        })
+        .unwrap_or(0)
 }
 
 #[cfg(unix)]
@@ -502,39 +689,57 @@ pub fn resolve_source_for_context(name: &str, discovery: &DiscoveryResult) -> Re
     resolve_source_in(name, &data)
 }
 
-/// Build columnar indexes for discovered sources that don't have one.
-pub fn build_missing_indexes(sources: &[DiscoveredSource]) {
-    use crate::index::builder::IndexBuilder;
+/// Build columnar indexes for discovered sources that don't have one, or
+/// whose index was written in an older format.
+///
+/// With `quiet`, only failures are reported. Returns the logs whose index
+/// location can't be written, with the reason, so the viewer can disable
+/// indexing for them.
+pub fn build_missing_indexes(
+    sources: &[DiscoveredSource],
+    quiet: bool,
+    checkpoint_interval: u16,
+) -> Vec<(PathBuf, &'static str)> {
+    use crate::index::builder::{unwritable_reason, IndexBuilder};
 
     let missing: Vec<_> = sources
         .iter()
-        .filter(|s| !index_dir_for_log(&s.log_path).join("meta").exists())
+        .filter(|s| index_needs_build(&s.log_path))
         .collect();
 
+    let mut unwritable = Vec::new();
     if missing.is_empty() {
-        return;
+        return unwritable;
     }
 
-    eprintln!(
-        "Building indexes for {} source{}...",
-        missing.len(),
-        if missing.len() == 1 { "" } else { "s" }
-    );
+    if !quiet {
+        eprintln!(
+            "Building indexes for {} source{}...",
+            missing.len(),
+            if missing.len() == 1 { "" } else { "s" }
+        );
+    }
 
     for (i, source) in missing.iter().enumerate() {
         let idx_dir = index_dir_for_log(&source.log_path);
         let file_size = std::fs::metadata(&source.log_path)
             .map(|m| m.len())
             .unwrap_or(0);
-        eprintln!(
-            "  [{}/{}] Indexing {} ({})...",
-            i + 1,
-            missing.len(),
-            source.name,
-            format_bytes(file_size),
-        );
+        if !quiet {
+            eprintln!(
+                "  [{}/{}] Indexing {} ({})...",
+                i + 1,
+                missing.len(),
+                source.name,
+                format_bytes(file_size),
+            );
+        }
         let start = std::time::Instant::now();
-        match IndexBuilder::new().build(&source.log_path, &idx_dir) {
+        match IndexBuilder::new()
+            .with_checkpoint_interval(checkpoint_interval)
+            .build(&source.log_path, &idx_dir)
+        {
+            Ok(_) if quiet => {}
             Ok(meta) => {
                 eprintln!(
                     "  [{}/{}] Done: {} lines indexed in {:.1?}",
@@ -544,20 +749,35 @@ pub fn build_missing_indexes(sources: &[DiscoveredSource]) {
                     start.elapsed(),
                 );
             }
-            Err(e) => {
-                eprintln!(
-                    "  [{}/{}] Warning: failed to build index for {}: {}",
-                    i + 1,
-                    missing.len(),
-                    source.name,
-                    e,
-                );
-            }
+            Err(e) => match unwritable_reason(&e) {
+                Some(reason) => {
+                    if !quiet {
+                        eprintln!(
+                            "  [{}/{}] Skipped {}: {}",
+                            i + 1,
+                            missing.len(),
+                            source.name,
+                            reason,
+                        );
+                    }
+                    unwritable.push((source.log_path.clone(), reason));
+                }
+                None => {
+                    eprintln!(
+                        "  [{}/{}] Warning: failed to build index for {}: {}",
+                        i + 1,
+                        missing.len(),
+                        source.name,
+                        e,
+                    );
+                }
+            },
         }
     }
+    unwritable
 }
 
-fn format_bytes(bytes: u64) -> String {
+pub(crate) fn format_bytes(bytes: u64) -> String {
     if bytes < 1024 {
         format!("{} B", bytes)
     } else if bytes < 1024 * 1024 {
@@ -569,6 +789,38 @@ fn format_bytes(bytes: u64) -> String {
     }
 }
 
+/// Format a line or match count for display: in compact human-readable form
+/// with `compact` (`0`, `999`, `1.2K`, `60M`, `1.3Bn`), exactly otherwise.
+pub fn format_count(count: usize, compact: bool) -> String {
+    if !compact {
+        return count.to_string();
+    }
+    if count >= 1_000_000_000 {
+        let val = count as f64 / 1_000_000_000.0;
+        if val >= 10.0 {
+            format!("{}Bn", val as u64)
+        } else {
+            format!("{:.1}Bn", val)
+        }
+    } else if count >= 1_000_000 {
+        let val = count as f64 / 1_000_000.0;
+        if val >= 10.0 {
+            format!("{}M", val as u64)
+        } else {
+            format!("{:.1}M", val)
+        }
+    } else if count >= 1_000 {
+        let val = count as f64 / 1_000.0;
+        if val >= 10.0 {
+            format!("{}K", val as u64)
+        } else {
+            format!("{:.1}K", val)
+        }
+    } else {
+        format!("{}", count)
+    }
+}
+
 /// Validate a source name for use in capture mode.
 pub fn validate_source_name(name: &str) -> Result<()> {
     if name.is_empty() {
@@ -620,6 +872,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_format_count_compact_or_exact() {
+        assert_eq!(format_count(999, true), "999");
+        assert_eq!(format_count(1_234, true), "1.2K");
+        assert_eq!(format_count(340_000, true), "340K");
+        assert_eq!(format_count(1_234_567, true), "1.2M");
+        assert_eq!(format_count(60_000_000, true), "60M");
+        assert_eq!(format_count(1_300_000_000, true), "1.3Bn");
+        assert_eq!(format_count(1_234_567, false), "1234567");
+    }
+
     #[test]
     fn test_is_pid_running_self() {
         // Current process should be running
@@ -1071,4 +1334,144 @@ mod tests {
             env::remove_var("HOME");
         }
     }
+
+    #[test]
+    #[ignore] // Slow test - requires temp dir setup
+    fn test_cached_index_dir_keyed_by_canonical_path() {
+        let temp = TempDir::new().unwrap();
+        let cache = temp.path().join("cache");
+        let a = temp.path().join("a");
+        let b = temp.path().join("b");
+        fs::create_dir_all(&a).unwrap();
+        fs::create_dir_all(&b).unwrap();
+        fs::write(a.join("app.log"), "x\n").unwrap();
+        fs::write(b.join("app.log"), "x\n").unwrap();
+
+        let dir_a = cached_index_dir(&cache, &a.join("app.log"));
+        let dir_b = cached_index_dir(&cache, &b.join("app.log"));
+        assert_ne!(dir_a, dir_b, "same-named logs must not share an index");
+        assert_eq!(dir_a.parent(), Some(cache.as_path()));
+        assert!(dir_a
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("app-"));
+
+        // Non-canonical spelling and a not-yet-created log resolve consistently
+        assert_eq!(cached_index_dir(&cache, &b.join("../a/app.log")), dir_a);
+        let pending = a.join("later.log");
+        let before = cached_index_dir(&cache, &pending);
+        fs::write(&pending, "").unwrap();
+        assert_eq!(cached_index_dir(&cache, &pending), before);
+    }
+
+    #[test]
+    #[ignore] // Slow test - requires temp dir setup
+    fn test_gc_index_cache_removes_orphans() {
+        let temp = TempDir::new().unwrap();
+        let cache = temp.path().join("cache");
+        let live_log = temp.path().join("live.log");
+        fs::write(&live_log, "x\n").unwrap();
+
+        let live = cache.join("live-1");
+        let gone = cache.join("gone-2");
+        let unknown = cache.join("unknown-3");
+        for dir in [&live, &gone, &unknown] {
+            fs::create_dir_all(dir).unwrap();
+            fs::write(dir.join("meta"), [0u8; 64]).unwrap();
+        }
+        fs::write(live.join(INDEX_SOURCE_FILE), live_log.to_str().unwrap()).unwrap();
+        let gone_log = temp.path().join("gone.log");
+        fs::write(gone.join(INDEX_SOURCE_FILE), gone_log.to_str().unwrap()).unwrap();
+
+        let report = gc_index_cache(&cache, true).unwrap();
+        assert_eq!(report.kept, 1);
+        assert_eq!(report.orphaned, vec![gone.clone(), unknown.clone()]);
+        assert!(report.orphaned_bytes >= 128);
+        assert!(gone.exists(), "dry run must not remove anything");
+
+        gc_index_cache(&cache, false).unwrap();
+        assert!(live.exists());
+        assert!(!gone.exists());
+        assert!(!unknown.exists());
+
+        let missing = gc_index_cache(&temp.path().join("nope"), false).unwrap();
+        assert_eq!(missing, IndexGcReport::default());
+    }
+
+    #[test]
+    #[ignore] // Slow test - requires temp dir setup
+    fn test_gc_index_cache_keeps_indexes_being_written() {
+        use crate::index::lock::IndexWriteLock;
+
+        let temp = TempDir::new().unwrap();
+        let cache = temp.path().join("cache");
+        let building = cache.join("building-1");
+        fs::create_dir_all(&building).unwrap();
+
+        let lock = IndexWriteLock::try_acquire(&building).unwrap().unwrap();
+        let report = gc_index_cache(&cache, false).unwrap();
+        assert_eq!(report.kept, 1);
+        assert!(report.orphaned.is_empty());
+        assert!(building.exists());
+
+        drop(lock);
+        gc_index_cache(&cache, false).unwrap();
+        assert!(!building.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    #[ignore] // Slow test - requires temp dir setup
+    fn test_gc_index_cache_keeps_non_utf8_log_paths() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let temp = TempDir::new().unwrap();
+        let cache = temp.path().join("cache");
+        let log = temp
+            .path()
+            .join(std::ffi::OsStr::from_bytes(b"caf\xe9.log"));
+        fs::write(&log, "x\n").unwrap();
+        let index = cache.join("cafe-1");
+        fs::create_dir_all(&index).unwrap();
+        fs::write(index.join(INDEX_SOURCE_FILE), log.as_os_str().as_bytes()).unwrap();
+
+        let report = gc_index_cache(&cache, false).unwrap();
+        assert_eq!(report.kept, 1);
+        assert!(index.exists());
+    }
+
+    #[test]
+    #[ignore] // Slow test - requires temp dir setup
+    fn test_build_missing_indexes_rebuilds_outdated_index() {
+        use crate::index::meta::IndexMeta;
+        use crate::index::reader::IndexReader;
+
+        let temp = TempDir::new().unwrap();
+        let log_path = temp.path().join("app.log");
+        fs::write(&log_path, "one\ntwo\nthree\n").unwrap();
+
+        // An index left by an older lazytail
+        let idx_dir = index_dir_for_log(&log_path);
+        fs::create_dir_all(&idx_dir).unwrap();
+        let mut old = IndexMeta::new().to_bytes();
+        old[4..6].copy_from_slice(&0u16.to_le_bytes());
+        fs::write(idx_dir.join("meta"), old).unwrap();
+        assert!(index_needs_build(&log_path));
+        assert!(IndexReader::open(&log_path).is_none());
+
+        let source = DiscoveredSource {
+            name: "app".to_string(),
+            log_path: log_path.clone(),
+            status: SourceStatus::Ended,
+            location: SourceLocation::Project,
+        };
+        assert!(build_missing_indexes(&[source], true, 100).is_empty());
+
+        assert!(!index_needs_build(&log_path));
+        let meta = IndexMeta::read_from(idx_dir.join("meta")).unwrap();
+        assert_eq!(meta.version, IndexMeta::new().version);
+        assert_eq!(meta.entry_count, 3);
+        assert!(IndexReader::open(&log_path).is_some());
+    }
 }
@@ -60,6 +60,15 @@ pub fn resolve_theme(
     }
 }
 
+/// Resolve a theme by name (built-in or from `themes_dirs`), as for
+/// `--theme`. The error lists the available themes.
+pub fn resolve_theme_name(name: &str, themes_dirs: &[PathBuf]) -> Result<Theme, String> {
+    resolve_named(name, themes_dirs, &mut Vec::new()).map_err(|e| match e {
+        ConfigError::Validation { message, .. } => message,
+        other => other.to_string(),
+    })
+}
+
 fn resolve_named(
     name: &str,
     themes_dirs: &[PathBuf],
@@ -170,47 +179,83 @@ fn load_theme_file(
 ///
 /// Scans each directory for `.yaml` files and returns the filename stem as the theme name.
 pub fn discover_themes(themes_dirs: &[PathBuf]) -> Vec<String> {
-    let mut names = Vec::new();
-    for dir in themes_dirs {
-        if let Ok(entries) = std::fs::read_dir(dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.extension().and_then(|e| e.to_str()) == Some("yaml") {
-                    if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
-                        names.push(stem.to_string());
-                    }
-                }
-            }
-        }
-    }
+    let mut names: Vec<String> = themes_dirs
+        .iter()
+        .flat_map(|dir| theme_files(dir))
+        .map(|(name, _)| name)
+        .collect();
     names.sort();
     names.dedup();
     names
 }
 
-/// Build the list of theme directories from project root and global config dir.
-///
-/// Returns only directories that exist on disk.
-pub fn collect_themes_dirs(project_root: Option<&std::path::Path>) -> Vec<PathBuf> {
-    let mut dirs = Vec::new();
-
-    // Project themes dir: {project_root}/.lazytail/themes/
-    if let Some(root) = project_root {
-        let project_themes = root.join(".lazytail").join("themes");
-        if project_themes.is_dir() {
-            dirs.push(project_themes);
+/// Theme files (`{name}.yaml`) in `dir` as `(name, path)`, sorted by name.
+pub fn theme_files(dir: &std::path::Path) -> Vec<(String, PathBuf)> {
+    let mut files = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("yaml") {
+                if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                    files.push((stem.to_string(), path));
+                }
+            }
         }
     }
+    files.sort();
+    files
+}
 
-    // Global themes dir: ~/.config/lazytail/themes/
-    if let Some(lazytail_dir) = crate::source::lazytail_dir() {
-        let global_themes = lazytail_dir.join("themes");
-        if global_themes.is_dir() {
-            dirs.push(global_themes);
+/// Theme files that can never be used because a theme of the same name is
+/// found first: a built-in theme, or one in an earlier directory (project
+/// themes come before global ones). One message per shadowed file.
+pub fn theme_collisions(themes_dirs: &[PathBuf]) -> Vec<String> {
+    let mut found: Vec<(String, PathBuf)> = Vec::new();
+    let mut collisions = Vec::new();
+    for dir in themes_dirs {
+        for (name, path) in theme_files(dir) {
+            if BUILTIN_THEMES.contains(&name.as_str()) {
+                collisions.push(format!(
+                    "theme '{}' in {} is shadowed by the built-in theme",
+                    name,
+                    path.display()
+                ));
+            } else if let Some((_, first)) = found.iter().find(|(known, _)| *known == name) {
+                collisions.push(format!(
+                    "theme '{}' in {} is shadowed by {}",
+                    name,
+                    path.display(),
+                    first.display()
+                ));
+            } else {
+                found.push((name, path));
+            }
         }
     }
+    collisions
+}
+
+/// Project themes dir: `{project_root}/.lazytail/themes/`
+pub fn project_themes_dir(project_root: &std::path::Path) -> PathBuf {
+    project_root.join(".lazytail").join("themes")
+}
+
+/// Global themes dir: `~/.config/lazytail/themes/`
+pub fn global_themes_dir() -> Option<PathBuf> {
+    crate::source::lazytail_dir().map(|dir| dir.join("themes"))
+}
 
-    dirs
+/// Build the list of theme directories from project root and global config dir.
+///
+/// Returns only directories that exist on disk. The project directory comes
+/// first, so its themes take precedence over global ones of the same name.
+pub fn collect_themes_dirs(project_root: Option<&std::path::Path>) -> Vec<PathBuf> {
+    project_root
+        .map(project_themes_dir)
+        .into_iter()
+        .chain(global_themes_dir())
+        .filter(|dir| dir.is_dir())
+        .collect()
 }
 
 fn apply_palette_overrides(palette: &mut Palette, raw: &crate::theme::RawPalette) {
@@ -260,6 +305,7 @@ fn apply_ui_overrides(ui: &mut UiColors, raw: &RawUiColors) {
     override_field!(selection_bg);
     override_field!(selection_fg);
     override_field!(expanded_bg);
+    override_field!(wrap_marker);
     override_field!(severity_warn_bg);
     override_field!(severity_error_bg);
     override_field!(severity_fatal_bg);
@@ -323,6 +369,17 @@ mod tests {
         assert!(err.contains("light"));
     }
 
+    #[test]
+    fn test_resolve_theme_name() {
+        assert_eq!(resolve_theme_name("light", &[]).unwrap(), Theme::light());
+
+        let err = resolve_theme_name("ligth", &[]).unwrap_err();
+        assert_eq!(
+            err,
+            "unknown theme 'ligth'. Available themes: dark, light. Did you mean 'light'?"
+        );
+    }
+
     #[test]
     fn test_resolve_custom_palette_override() {
         let raw = Some(RawThemeConfig::Custom {
@@ -511,6 +568,30 @@ mod tests {
         assert!(names.is_empty());
     }
 
+    #[test]
+    #[ignore] // Slow: creates temp directory and files
+    fn test_project_theme_wins_and_collisions_are_reported() {
+        let temp = TempDir::new().unwrap();
+        let project = temp.path().join("project");
+        let global = temp.path().join("global");
+        fs::create_dir_all(&project).unwrap();
+        fs::create_dir_all(&global).unwrap();
+        fs::write(project.join("team.yaml"), "palette:\n  red: \"#110000\"\n").unwrap();
+        fs::write(global.join("team.yaml"), "palette:\n  red: \"#220000\"\n").unwrap();
+        fs::write(global.join("light.yaml"), "base: dark\n").unwrap();
+        fs::write(global.join("solo.yaml"), "base: dark\n").unwrap();
+
+        let dirs = vec![project.clone(), global.clone()];
+        let theme = resolve_theme(&Some(RawThemeConfig::Named("team".into())), &dirs).unwrap();
+        assert_eq!(theme.palette.red, Color::Rgb(0x11, 0, 0));
+
+        let collisions = theme_collisions(&dirs);
+        assert_eq!(collisions.len(), 2, "{:?}", collisions);
+        assert!(collisions[0].contains("'light'") && collisions[0].contains("built-in"));
+        assert!(collisions[1].contains("'team'"));
+        assert!(collisions[1].ends_with(&project.join("team.yaml").display().to_string()));
+    }
+
     #[test]
     #[ignore] // Slow: creates temp directory
     fn test_collect_themes_dirs() {
@@ -4,7 +4,8 @@ use ratatui::style::{Color, Style};
 use serde::Deserialize;
 
 /// A named color suitable for YAML config deserialization.
-/// Wraps `ratatui::style::Color` with support for named colors, hex, and "default".
+/// Wraps `ratatui::style::Color` with support for named colors, hex, `rgb()`,
+/// 256-palette indices and "default".
 #[derive(Debug, Clone, Copy)]
 pub struct ThemeColor(pub Color);
 
@@ -13,10 +14,44 @@ impl<'de> Deserialize<'de> for ThemeColor {
     where
         D: serde::Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
-        parse_color(&s)
-            .map(ThemeColor)
-            .map_err(serde::de::Error::custom)
+        struct ColorVisitor;
+
+        impl serde::de::Visitor<'_> for ColorVisitor {
+            type Value = ThemeColor;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a color name, #rrggbb, rgb(r,g,b) or a 0-255 palette index")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<ThemeColor, E> {
+                parse_color(v).map(ThemeColor).map_err(E::custom)
+            }
+
+            // Unquoted palette indices (`color: 208`) arrive as numbers
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<ThemeColor, E> {
+                u8::try_from(v)
+                    .map(|i| ThemeColor(Color::Indexed(i)))
+                    .map_err(|_| {
+                        E::custom(format!(
+                            "invalid color '{}': palette index must be 0-255",
+                            v
+                        ))
+                    })
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<ThemeColor, E> {
+                u64::try_from(v)
+                    .map_err(|_| {
+                        E::custom(format!(
+                            "invalid color '{}': palette index must be 0-255",
+                            v
+                        ))
+                    })
+                    .and_then(|v| self.visit_u64(v))
+            }
+        }
+
+        deserializer.deserialize_any(ColorVisitor)
     }
 }
 
@@ -25,6 +60,8 @@ impl<'de> Deserialize<'de> for ThemeColor {
 /// Supports:
 /// - Named colors: `red`, `dark_gray`, `light_cyan`, etc.
 /// - Hex: `#rrggbb` or `#rgb`
+/// - Truecolor: `rgb(r, g, b)` with components 0-255
+/// - 256-color palette index: `0`-`255`
 /// - `"default"` → `Color::Reset`
 pub fn parse_color(s: &str) -> Result<Color, String> {
     let s = s.trim();
@@ -34,31 +71,58 @@ pub fn parse_color(s: &str) -> Result<Color, String> {
     if let Some(hex) = s.strip_prefix('#') {
         return parse_hex_color(hex);
     }
+    if let Some(args) = strip_rgb_call(s) {
+        return parse_rgb_call(s, args);
+    }
+    if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+        return s
+            .parse::<u8>()
+            .map(Color::Indexed)
+            .map_err(|_| format!("invalid color '{}': palette index must be 0-255", s));
+    }
     parse_named_color(s)
 }
 
 fn parse_hex_color(hex: &str) -> Result<Color, String> {
+    let invalid = || format!("invalid hex color: #{}", hex);
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let channel =
+        |range: std::ops::Range<usize>| u8::from_str_radix(&hex[range], 16).map_err(|_| invalid());
     match hex.len() {
-        6 => {
-            let r = u8::from_str_radix(&hex[0..2], 16)
-                .map_err(|_| format!("invalid hex color: #{}", hex))?;
-            let g = u8::from_str_radix(&hex[2..4], 16)
-                .map_err(|_| format!("invalid hex color: #{}", hex))?;
-            let b = u8::from_str_radix(&hex[4..6], 16)
-                .map_err(|_| format!("invalid hex color: #{}", hex))?;
-            Ok(Color::Rgb(r, g, b))
-        }
-        3 => {
-            let r = u8::from_str_radix(&hex[0..1], 16)
-                .map_err(|_| format!("invalid hex color: #{}", hex))?;
-            let g = u8::from_str_radix(&hex[1..2], 16)
-                .map_err(|_| format!("invalid hex color: #{}", hex))?;
-            let b = u8::from_str_radix(&hex[2..3], 16)
-                .map_err(|_| format!("invalid hex color: #{}", hex))?;
-            Ok(Color::Rgb(r * 17, g * 17, b * 17))
-        }
-        _ => Err(format!("invalid hex color: #{}", hex)),
+        6 => Ok(Color::Rgb(channel(0..2)?, channel(2..4)?, channel(4..6)?)),
+        3 => Ok(Color::Rgb(
+            channel(0..1)? * 17,
+            channel(1..2)? * 17,
+            channel(2..3)? * 17,
+        )),
+        _ => Err(invalid()),
+    }
+}
+
+/// The argument list of `rgb(...)`, case-insensitive
+fn strip_rgb_call(s: &str) -> Option<&str> {
+    let prefix = s.get(..4)?;
+    if !prefix.eq_ignore_ascii_case("rgb(") {
+        return None;
     }
+    s[4..].strip_suffix(')')
+}
+
+fn parse_rgb_call(s: &str, args: &str) -> Result<Color, String> {
+    let components: Vec<&str> = args.split(',').map(str::trim).collect();
+    let [r, g, b] = components.as_slice() else {
+        return Err(format!(
+            "invalid color '{}': rgb() takes three components",
+            s
+        ));
+    };
+    let component = |c: &str| {
+        c.parse::<u8>()
+            .map_err(|_| format!("invalid color '{}': '{}' is not in 0-255", s, c))
+    };
+    Ok(Color::Rgb(component(r)?, component(g)?, component(b)?))
 }
 
 fn parse_named_color(s: &str) -> Result<Color, String> {
@@ -80,7 +144,7 @@ fn parse_named_color(s: &str) -> Result<Color, String> {
         "light_cyan" | "lightcyan" => Ok(Color::LightCyan),
         "white" => Ok(Color::White),
         "reset" => Ok(Color::Reset),
-        _ => Err(format!("unknown color: '{}'. Valid names: black, red, green, yellow, blue, magenta, cyan, gray, dark_gray, light_red, light_green, light_yellow, light_blue, light_magenta, light_cyan, white, reset, default, or hex (#rrggbb / #rgb)", s)),
+        _ => Err(format!("unknown color: '{}'. Valid names: black, red, green, yellow, blue, magenta, cyan, gray, dark_gray, light_red, light_green, light_yellow, light_blue, light_magenta, light_cyan, white, reset, default, or hex (#rrggbb / #rgb), rgb(r,g,b), or a 0-255 palette index", s)),
     }
 }
 
@@ -217,6 +281,7 @@ impl Palette {
             selection_bg: self.selection,
             selection_fg: self.foreground,
             expanded_bg,
+            wrap_marker: self.bright_black,
             severity_warn_bg,
             severity_error_bg,
             severity_fatal_bg,
@@ -274,6 +339,7 @@ pub struct UiColors {
     pub selection_bg: Color,
     pub selection_fg: Color,
     pub expanded_bg: Color,
+    pub wrap_marker: Color,
     pub severity_warn_bg: Color,
     pub severity_error_bg: Color,
     pub severity_fatal_bg: Color,
@@ -296,6 +362,19 @@ impl UiColors {
     pub fn bg_style(&self) -> Style {
         Style::default().bg(self.bg)
     }
+
+    /// Stable accent color for a source name, picked from `source_colors`.
+    ///
+    /// Uses FNV-1a so the same name gets the same color on every run.
+    pub fn source_color(&self, name: &str) -> Color {
+        let hash = name.bytes().fold(0xcbf29ce484222325u64, |h, b| {
+            (h ^ b as u64).wrapping_mul(0x100000001b3)
+        });
+        match self.source_colors.len() {
+            0 => self.primary,
+            n => self.source_colors[(hash % n as u64) as usize],
+        }
+    }
 }
 
 /// A complete theme: palette + derived/overridden UI colors.
@@ -378,6 +457,7 @@ pub struct RawUiColors {
     pub selection_bg: Option<ThemeColor>,
     pub selection_fg: Option<ThemeColor>,
     pub expanded_bg: Option<ThemeColor>,
+    pub wrap_marker: Option<ThemeColor>,
     pub severity_warn_bg: Option<ThemeColor>,
     pub severity_error_bg: Option<ThemeColor>,
     pub severity_fatal_bg: Option<ThemeColor>,
@@ -434,6 +514,60 @@ mod tests {
         let result = parse_color("#xyz");
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("invalid hex color"));
+        // Multibyte input must not be sliced mid-character
+        assert!(parse_color("#é1234").is_err());
+        assert!(parse_color("#12345").is_err());
+    }
+
+    #[test]
+    fn test_parse_color_rgb_call() {
+        assert_eq!(
+            parse_color("rgb(255,85,0)").unwrap(),
+            Color::Rgb(255, 85, 0)
+        );
+        assert_eq!(
+            parse_color(" RGB( 1 , 2 , 3 ) ").unwrap(),
+            Color::Rgb(1, 2, 3)
+        );
+    }
+
+    #[test]
+    fn test_parse_color_rgb_call_invalid() {
+        let err = parse_color("rgb(300,0,0)").unwrap_err();
+        assert!(
+            err.contains("rgb(300,0,0)") && err.contains("'300'"),
+            "{}",
+            err
+        );
+        assert!(parse_color("rgb(1,2)")
+            .unwrap_err()
+            .contains("three components"));
+        assert!(parse_color("rgb(1,2,3").is_err());
+        assert!(parse_color("rgb(a,b,c)").is_err());
+    }
+
+    #[test]
+    fn test_parse_color_palette_index() {
+        assert_eq!(parse_color("0").unwrap(), Color::Indexed(0));
+        assert_eq!(parse_color("208").unwrap(), Color::Indexed(208));
+        let err = parse_color("256").unwrap_err();
+        assert!(err.contains("'256'") && err.contains("0-255"), "{}", err);
+    }
+
+    #[test]
+    fn test_theme_color_deserializes_every_form() {
+        let parse = |yaml: &str| serde_saphyr::from_str::<ThemeColor>(yaml).map(|c| c.0);
+        assert_eq!(parse("208").unwrap(), Color::Indexed(208));
+        assert_eq!(parse("\"208\"").unwrap(), Color::Indexed(208));
+        assert_eq!(parse("\"#ff5500\"").unwrap(), Color::Rgb(255, 85, 0));
+        assert_eq!(parse("rgb(1, 2, 3)").unwrap(), Color::Rgb(1, 2, 3));
+        assert_eq!(parse("light_cyan").unwrap(), Color::LightCyan);
+        assert!(parse("300").is_err());
+        assert!(parse("-1").is_err());
+        assert!(parse("chartreuse")
+            .unwrap_err()
+            .to_string()
+            .contains("chartreuse"));
     }
 
     #[test]
@@ -499,6 +633,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_source_color_is_stable_per_name() {
+        let ui = Theme::dark().ui;
+        assert_eq!(ui.source_color("api"), ui.source_color("api"));
+        assert!(ui.source_colors.contains(&ui.source_color("worker")));
+
+        let empty = UiColors {
+            source_colors: Vec::new(),
+            ..ui.clone()
+        };
+        assert_eq!(empty.source_color("api"), ui.primary);
+    }
+
     #[test]
     fn test_palette_get_color_standard() {
         let palette = Palette::dark();
@@ -1,4 +1,5 @@
 use crate::app::tab::TabState;
+use crate::filter::aggregation::AggregationRow;
 use crate::theme::UiColors;
 use ratatui::{
     layout::Rect,
@@ -43,30 +44,25 @@ pub(super) fn render_aggregation_view(
     let scroll = tab.aggregation_view.scroll_offset;
     let selected = tab.aggregation_view.selected_row;
 
-    // Find max count for bar scaling
-    let max_count = result
-        .groups
-        .iter()
-        .map(|g| g.count)
-        .max()
-        .unwrap_or(1)
-        .max(1);
+    // Multi-field keys nest: subtotal rows introduce each value of the
+    // outer fields, with bars scaled within each nesting level
+    let rows = result.rows();
+    let field_count = result.aggregation.fields.len();
+    let mut max_counts = vec![1; field_count.max(1)];
+    for row in rows {
+        max_counts[row.depth] = max_counts[row.depth].max(row.count);
+    }
 
     // Build header
     let header_spans = build_header(&result.aggregation.fields, inner_width, ui);
     let mut items: Vec<ListItem> = vec![ListItem::new(Line::from(header_spans))];
 
     // Build data rows
-    let visible_groups = result
-        .groups
-        .iter()
-        .enumerate()
-        .skip(scroll)
-        .take(data_rows);
+    let visible_rows = rows.iter().enumerate().skip(scroll).take(data_rows);
 
-    for (idx, group) in visible_groups {
+    for (idx, row) in visible_rows {
         let is_selected = idx == selected;
-        let spans = build_row(group, max_count, inner_width, ui);
+        let spans = build_row(row, field_count, max_counts[row.depth], inner_width, ui);
         let mut item = ListItem::new(Line::from(spans));
         if is_selected {
             item = item.style(
@@ -110,34 +106,44 @@ fn build_header(fields: &[String], width: usize, ui: &UiColors) -> Vec<Span<'sta
 }
 
 fn build_row(
-    group: &crate::filter::aggregation::AggregationGroup,
+    row: &AggregationRow,
+    field_count: usize,
     max_count: usize,
     width: usize,
     ui: &UiColors,
 ) -> Vec<Span<'static>> {
     let mut spans = Vec::new();
-    let field_cols = group.key.len() * 16;
+    let field_cols = field_count * 16;
+    let style = if row.is_subtotal(field_count) {
+        Style::default().add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
 
-    // Field values
-    for (_name, value) in &group.key {
+    // Field values: only the one the row introduces, in its own column
+    for col in 0..field_count {
+        let value = match row.key.get(col) {
+            Some((_name, value)) if col == row.depth => value.as_str(),
+            _ => "",
+        };
         let display = if value.len() > 14 {
             format!(" {:.14}", value)
         } else {
             format!(" {:<14}", value)
         };
-        spans.push(Span::styled(display, Style::default().fg(ui.fg)));
+        spans.push(Span::styled(display, style.fg(ui.fg)));
         spans.push(Span::raw(" "));
     }
 
     // Count
-    let count_str = format!("{:>7}", group.count);
-    spans.push(Span::styled(count_str, Style::default().fg(ui.primary)));
+    let count_str = format!("{:>7}", row.count);
+    spans.push(Span::styled(count_str, style.fg(ui.primary)));
 
     // Bar chart
     let bar_space = width.saturating_sub(field_cols + 8 + 1);
     if bar_space > 2 {
         let bar_max = bar_space.min(20);
-        let filled = ((group.count as u64 * bar_max as u64) / max_count as u64) as usize;
+        let filled = ((row.count as u64 * bar_max as u64) / max_count as u64) as usize;
         let filled = filled.max(1);
         let empty = bar_max.saturating_sub(filled);
         let bar_filled: String = "\u{2588}".repeat(filled);
@@ -1,5 +1,10 @@
-use crate::app::App;
-use crate::source::SourceStatus;
+use crate::app::context_menu::{ContextMenu, ContextMenuAction};
+use crate::app::error_log::ErrorLog;
+use crate::app::quick_filter::FieldPicker;
+use crate::app::{App, HelpSearch, LayoutRect};
+use crate::handlers::command::{COMMANDS, COMMANDS_HEADER};
+use crate::handlers::keymap::{Category, Keymap};
+use crate::text_wrap::{wrap_plain, wrap_spans};
 use crate::theme::UiColors;
 use ratatui::{
     layout::Rect,
@@ -13,7 +18,14 @@ use ratatui::{
 const HELP_POPUP_WIDTH_PERCENT: f32 = 0.6;
 const HELP_POPUP_HEIGHT_PERCENT: f32 = 0.8;
 
-pub(super) fn render_help_overlay(f: &mut Frame, area: Rect, scroll_offset: usize, ui: &UiColors) {
+pub(super) fn render_help_overlay(
+    f: &mut Frame,
+    area: Rect,
+    scroll_offset: usize,
+    search: Option<&HelpSearch>,
+    keymap: &Keymap,
+    ui: &UiColors,
+) {
     // Calculate centered popup area
     let popup_width = (area.width as f32 * HELP_POPUP_WIDTH_PERCENT) as u16;
     let popup_height = (area.height as f32 * HELP_POPUP_HEIGHT_PERCENT) as u16;
@@ -27,120 +39,28 @@ pub(super) fn render_help_overlay(f: &mut Frame, area: Rect, scroll_offset: usiz
         height: popup_height,
     };
 
-    // Help content
-    let help_lines = vec![
-        Line::from(vec![Span::styled(
-            "LazyTail - Quick Reference",
-            Style::default().fg(ui.primary).add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Navigation",
-            Style::default().fg(ui.accent).add_modifier(Modifier::BOLD),
-        )]),
-        Line::from("  j/k, ↑/↓      Move selection up/down"),
-        Line::from("  g / G         Jump to start / end"),
-        Line::from("  PageUp/Down   Scroll by page"),
-        Line::from("  Ctrl+E/Y      Scroll viewport (vim-style)"),
-        Line::from("  :123          Jump to line number"),
-        Line::from("  zz/zt/zb      Center/top/bottom view"),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Filtering",
-            Style::default().fg(ui.accent).add_modifier(Modifier::BOLD),
-        )]),
-        Line::from("  /             Start filter (live preview)"),
-        Line::from("  Tab           Cycle Plain → Regex → Query"),
-        Line::from("  Alt+C         Toggle case sensitivity"),
-        Line::from("  ↑/↓           Browse filter history"),
-        Line::from("  Enter         Apply filter"),
-        Line::from("  Esc           Clear filter"),
-        Line::from("  Query mode    json | ... / logfmt | ..."),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Tabs",
-            Style::default().fg(ui.accent).add_modifier(Modifier::BOLD),
-        )]),
-        Line::from("  1-9           Jump to tab"),
-        Line::from("  x, Ctrl+W     Close tab"),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Source Panel",
-            Style::default().fg(ui.accent).add_modifier(Modifier::BOLD),
-        )]),
-        Line::from("  Tab           Toggle panel focus"),
-        Line::from("  j/k, ↑/↓      Navigate tree"),
-        Line::from("  Space         Expand/collapse category"),
-        Line::from("  Enter         Select source"),
-        Line::from("  x, Ctrl+W     Close selected source"),
-        Line::from("  y             Copy source path"),
-        Line::from("  Esc           Return to log view"),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "View",
-            Style::default().fg(ui.accent).add_modifier(Modifier::BOLD),
-        )]),
-        Line::from("  Space         Expand/collapse line"),
-        Line::from("  c             Collapse all"),
-        Line::from("  f             Toggle follow mode"),
-        Line::from("  r             Toggle raw mode"),
-        Line::from("  w             Toggle line wrap"),
-        Line::from("  t             Toggle timestamps"),
-        Line::from("  y             Copy line to clipboard"),
-        Line::from("  R             Refresh combined view"),
-        Line::from("  Esc           Clear active filter"),
-        Line::from("  ?             Show this help"),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Aggregation View",
-            Style::default().fg(ui.accent).add_modifier(Modifier::BOLD),
-        )]),
-        Line::from("  j/k, ↑/↓      Navigate groups"),
-        Line::from("  Enter         Drill down into group"),
-        Line::from("  Esc           Back to log view"),
-        Line::from("  g / G         Jump to first / last"),
-        Line::from("  /             Start filter"),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Mouse",
-            Style::default().fg(ui.accent).add_modifier(Modifier::BOLD),
-        )]),
-        Line::from("  Click source     Switch to tab"),
-        Line::from("  Click log line   Select line"),
-        Line::from("  Scroll wheel     Scroll log view"),
-        Line::from("  Click category   Expand/collapse"),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Side Panel Indicators",
-            Style::default().fg(ui.accent).add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(vec![
-            Span::raw("  "),
-            Span::styled("F", Style::default().fg(ui.positive)),
-            Span::raw("  Follow mode    "),
-            Span::styled("*", Style::default().fg(ui.accent)),
-            Span::raw("  Filter active"),
-        ]),
-        Line::from(vec![
-            Span::raw("  "),
-            Span::styled("●", Style::default().fg(ui.positive)),
-            Span::raw("  Source active  "),
-            Span::styled("○", Style::default().fg(ui.muted)),
-            Span::raw("  Source ended"),
-        ]),
-        Line::from(vec![
-            Span::raw("  "),
-            Span::styled("⟳", Style::default().fg(ui.highlight)),
-            Span::raw("  Loading"),
-        ]),
-        Line::from(""),
-        Line::from("  q / Ctrl+C    Quit"),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "j/k to scroll, any other key to close",
-            Style::default().fg(ui.muted).add_modifier(Modifier::ITALIC),
-        )]),
-    ];
+    let inner_width = popup_width.saturating_sub(2) as usize;
+    let query = search.map(|s| s.query.as_str()).unwrap_or_default();
+    let mut help_lines: Vec<Line<'static>> = filter_help_lines(help_content(ui, keymap), query)
+        .into_iter()
+        .flat_map(|line| wrap_spans(line.spans, inner_width))
+        .collect();
+    if help_lines.is_empty() {
+        help_lines.push(Line::from(Span::styled(
+            format!("  No shortcuts match \"{}\"", query),
+            Style::default().fg(ui.muted),
+        )));
+    }
+    help_lines.push(Line::from(""));
+    let hint = match search {
+        Some(s) if s.editing => "Type to search, Enter to keep, Esc to clear",
+        Some(_) => "j/k to scroll, / to edit search, Esc to clear, any other key to close",
+        None => "j/k to scroll, / to search, any other key to close",
+    };
+    help_lines.push(Line::from(vec![Span::styled(
+        hint,
+        Style::default().fg(ui.muted).add_modifier(Modifier::ITALIC),
+    )]));
 
     let total_lines = help_lines.len();
     // Inner height = popup height - 2 (top/bottom border)
@@ -153,11 +73,20 @@ pub(super) fn render_help_overlay(f: &mut Frame, area: Rect, scroll_offset: usiz
     let has_more_below = scroll < max_scroll;
 
     // Build title with scroll indicators
-    let title = match (has_more_above, has_more_below) {
-        (true, true) => " Help ↑↓ ",
-        (true, false) => " Help ↑ ",
-        (false, true) => " Help ↓ ",
-        (false, false) => " Help ",
+    let arrows = match (has_more_above, has_more_below) {
+        (true, true) => " ↑↓",
+        (true, false) => " ↑",
+        (false, true) => " ↓",
+        (false, false) => "",
+    };
+    let title = match search {
+        Some(s) => format!(
+            " Help /{}{}{} ",
+            s.query,
+            if s.editing { "_" } else { "" },
+            arrows
+        ),
+        None => format!(" Help{} ", arrows),
     };
 
     let help_paragraph = Paragraph::new(help_lines)
@@ -175,6 +104,214 @@ pub(super) fn render_help_overlay(f: &mut Frame, area: Rect, scroll_offset: usiz
     f.render_widget(help_paragraph, popup_area);
 }
 
+/// Help text: the keymap's bindings by category, plus the filter prompt,
+/// mouse and indicator notes that aren't keybindings. Section headers are
+/// the lines not indented by two spaces; shortcut lines are.
+fn help_content(ui: &UiColors, keymap: &Keymap) -> Vec<Line<'static>> {
+    let header = |title: &'static str| {
+        Line::from(vec![Span::styled(
+            title,
+            Style::default().fg(ui.accent).add_modifier(Modifier::BOLD),
+        )])
+    };
+    let mut lines = vec![
+        Line::from(vec![Span::styled(
+            "LazyTail - Quick Reference",
+            Style::default().fg(ui.primary).add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+    ];
+
+    for category in [
+        Category::Navigation,
+        Category::Filtering,
+        Category::Tabs,
+        Category::SourcePanel,
+        Category::View,
+        Category::Aggregation,
+        Category::Mouse,
+        Category::General,
+    ] {
+        lines.push(header(category.title()));
+        for binding in keymap.in_category(category) {
+            lines.push(Line::from(format!(
+                "  {:<13} {}",
+                binding.keys_label(),
+                binding.description
+            )));
+        }
+        match category {
+            Category::Filtering => {
+                lines.push(Line::from(""));
+                lines.push(header("Filter Prompt"));
+                lines.push(Line::from("  Tab           Cycle Plain → Regex → Query"));
+                lines.push(Line::from(
+                    "  Tab (query)   Complete a partly typed field name",
+                ));
+                lines.push(Line::from("  Alt+C         Toggle case sensitivity"));
+                lines.push(Line::from(
+                    "  Alt+N         Invert filter (show non-matching lines)",
+                ));
+                lines.push(Line::from("  ↑/↓           Browse filter history"));
+                lines.push(Line::from("  Enter         Apply filter"));
+                lines.push(Line::from(
+                    "  Ctrl+Enter    Apply and keep typing (or Alt+Enter)",
+                ));
+                lines.push(Line::from(
+                    "  Esc           Stop running scan / close (see filter_esc)",
+                ));
+                lines.push(Line::from("  Query mode    json | ... / logfmt | ..."));
+            }
+            Category::Mouse => {
+                lines.push(Line::from("  Click source  Switch to tab"));
+                lines.push(Line::from("  Click line    Select line"));
+                lines.push(Line::from("  Double-click  Expand/collapse line"));
+                lines.push(Line::from(
+                    "  Right-click   Line menu (copy, expand, filter...)",
+                ));
+                lines.push(Line::from("  Scroll wheel  Scroll log view"));
+                lines.push(Line::from("  Click group   Expand/collapse category"));
+                lines.push(Line::from(""));
+                lines.push(header("Side Panel Indicators"));
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled("F", Style::default().fg(ui.positive)),
+                    Span::raw("  Follow mode    "),
+                    Span::styled("*", Style::default().fg(ui.accent)),
+                    Span::raw("  Filter active"),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled("●", Style::default().fg(ui.positive)),
+                    Span::raw("  Source active  "),
+                    Span::styled("○", Style::default().fg(ui.muted)),
+                    Span::raw("  Source ended"),
+                ]));
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled("⟳", Style::default().fg(ui.highlight)),
+                    Span::raw("  Loading"),
+                ]));
+            }
+            Category::General => {
+                lines.push(Line::from(""));
+                lines.push(header(COMMANDS_HEADER));
+                for (usage, description) in COMMANDS {
+                    lines.push(Line::from(format!("  {:<16} {}", usage, description)));
+                }
+            }
+            _ => {}
+        }
+        lines.push(Line::from(""));
+    }
+    lines.pop();
+    lines
+}
+
+/// Shortcut lines containing `query` (case-insensitive), each group under
+/// its section header; a section whose header contains `query` is kept
+/// whole. An empty query keeps everything.
+fn filter_help_lines(lines: Vec<Line<'static>>, query: &str) -> Vec<Line<'static>> {
+    if query.is_empty() {
+        return lines;
+    }
+    let query = query.to_lowercase();
+    let mut out = Vec::new();
+    let mut header: Option<Line<'static>> = None;
+    let mut whole_section = false;
+    for line in lines {
+        let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        if !text.starts_with("  ") {
+            whole_section = !text.is_empty() && text.to_lowercase().contains(&query);
+            header = (!text.is_empty()).then_some(line);
+            continue;
+        }
+        if whole_section || text.to_lowercase().contains(&query) {
+            if let Some(header) = header.take() {
+                if !out.is_empty() {
+                    out.push(Line::from(""));
+                }
+                out.push(header);
+            }
+            out.push(line);
+        }
+    }
+    out
+}
+
+/// Session errors, newest first, each stamped with its local time and
+/// wrapped to the popup width.
+pub(super) fn render_errors_overlay(
+    f: &mut Frame,
+    area: Rect,
+    errors: &ErrorLog,
+    scroll_offset: usize,
+    ui: &UiColors,
+) {
+    let popup_width = (area.width as f32 * HELP_POPUP_WIDTH_PERCENT) as u16;
+    let popup_height = (area.height as f32 * HELP_POPUP_HEIGHT_PERCENT) as u16;
+    let popup_area = Rect {
+        x: area.x + (area.width.saturating_sub(popup_width)) / 2,
+        y: area.y + (area.height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    // "HH:MM:SS " before the first row, matching indent after it
+    const STAMP_WIDTH: usize = 9;
+    let text_width = (popup_width as usize).saturating_sub(2 + STAMP_WIDTH);
+    let mut lines = Vec::new();
+    for error in errors.entries().rev() {
+        let message = if error.count > 1 {
+            format!("{} (×{})", error.message, error.count)
+        } else {
+            error.message.clone()
+        };
+        let stamp = super::log_view::format_epoch_ms_pattern(error.at_ms, "%H:%M:%S ");
+        for (row, mut line) in wrap_plain(&message, text_width).into_iter().enumerate() {
+            let prefix = if row == 0 {
+                Span::styled(stamp.clone(), Style::default().fg(ui.muted))
+            } else {
+                Span::raw(" ".repeat(STAMP_WIDTH))
+            };
+            line.spans.insert(0, prefix);
+            lines.push(line.style(Style::default().fg(ui.severity_error)));
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled(
+        "j/k to scroll, any other key to close",
+        Style::default().fg(ui.muted).add_modifier(Modifier::ITALIC),
+    )]));
+
+    let inner_height = popup_height.saturating_sub(2) as usize;
+    let max_scroll = lines.len().saturating_sub(inner_height);
+    let scroll = scroll_offset.min(max_scroll);
+    let title = format!(
+        " Errors ({}){} ",
+        errors.len(),
+        match (scroll > 0, scroll < max_scroll) {
+            (true, true) => " ↑↓",
+            (true, false) => " ↑",
+            (false, true) => " ↓",
+            (false, false) => "",
+        }
+    );
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .style(Style::default().bg(ui.popup_bg)),
+        )
+        .style(Style::default().bg(ui.popup_bg).fg(ui.fg))
+        .scroll((scroll as u16, 0));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(paragraph, popup_area);
+}
+
 pub(super) fn render_confirm_close_dialog(f: &mut Frame, area: Rect, app: &App) {
     let ui = &app.theme.ui;
     let tab_index = match &app.pending_close_tab {
@@ -189,8 +326,7 @@ pub(super) fn render_confirm_close_dialog(f: &mut Frame, area: Rect, app: &App)
     let tab = &app.tab_mgr.tabs[tab_index];
     let tab_name = &tab.source.name;
     let is_last = app.tab_mgr.tabs.len() <= 1;
-    let will_delete =
-        tab.source.source_status == Some(SourceStatus::Ended) && tab.source.source_path.is_some();
+    let will_delete = tab.close_deletes_source();
 
     // Truncate name to fit in popup
     let max_name_len: usize = 30;
@@ -262,6 +398,98 @@ pub(super) fn render_confirm_close_dialog(f: &mut Frame, area: Rect, app: &App)
     f.render_widget(paragraph, popup_area);
 }
 
+/// Render the right-click line menu, anchored near the click inside `bounds`.
+pub(super) fn render_context_menu(
+    f: &mut Frame,
+    menu: &ContextMenu,
+    bounds: LayoutRect,
+    ui: &UiColors,
+) {
+    let area = menu.area(bounds);
+    let popup_area = Rect {
+        x: area.x,
+        y: area.y,
+        width: area.width,
+        height: area.height,
+    };
+
+    let lines: Vec<Line> = ContextMenuAction::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, action)| {
+            let style = if i == menu.selected {
+                Style::default()
+                    .fg(ui.selection_fg)
+                    .bg(ui.selection_bg)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            Line::from(Span::styled(format!(" {} ", action.label()), style))
+        })
+        .collect();
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(Style::default().bg(ui.popup_bg)),
+        )
+        .style(Style::default().bg(ui.popup_bg).fg(ui.fg));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(paragraph, popup_area);
+}
+
+/// Render the quick filter (or copy) picker, centered in `bounds`.
+pub(super) fn render_field_picker(
+    f: &mut Frame,
+    picker: &FieldPicker,
+    bounds: LayoutRect,
+    ui: &UiColors,
+) {
+    let area = picker.area(bounds);
+    let popup_area = Rect {
+        x: area.x,
+        y: area.y,
+        width: area.width,
+        height: area.height,
+    };
+
+    let rows = area.height.saturating_sub(2) as usize;
+    let offset = picker.scroll_offset(rows);
+    let lines: Vec<Line> = picker
+        .items
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(rows)
+        .map(|(i, item)| {
+            let style = if i == picker.selected {
+                Style::default()
+                    .fg(ui.selection_fg)
+                    .bg(ui.selection_bg)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            Line::from(Span::styled(format!(" {} ", item.label()), style))
+        })
+        .collect();
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(picker.action.title())
+                .style(Style::default().bg(ui.popup_bg)),
+        )
+        .style(Style::default().bg(ui.popup_bg).fg(ui.fg));
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(paragraph, popup_area);
+}
+
 pub(super) fn render_warning_popup(f: &mut Frame, area: Rect, message: &str, ui: &UiColors) {
     let lines = vec![
         Line::from(""),
@@ -303,3 +531,80 @@ pub(super) fn render_warning_popup(f: &mut Frame, area: Rect, message: &str, ui:
     f.render_widget(Clear, popup_area);
     f.render_widget(paragraph, popup_area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::Theme;
+
+    fn texts(lines: &[Line<'_>]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn search_keeps_matching_shortcuts_under_their_section() {
+        let ui = Theme::default().ui;
+        let keymap = Keymap::default();
+        let lines = texts(&filter_help_lines(help_content(&ui, &keymap), "WRAP"));
+        assert_eq!(lines[0], "View");
+        assert!(lines[1].contains("Toggle line wrap"));
+        assert!(lines
+            .iter()
+            .all(|l| l.is_empty() || !l.starts_with("  ") || l.to_lowercase().contains("wrap")));
+    }
+
+    #[test]
+    fn search_matching_a_header_keeps_its_whole_section() {
+        let ui = Theme::default().ui;
+        let keymap = Keymap::default();
+        let lines = texts(&filter_help_lines(
+            help_content(&ui, &keymap),
+            COMMANDS_HEADER,
+        ));
+        assert_eq!(lines[0], COMMANDS_HEADER);
+        assert_eq!(lines.len(), COMMANDS.len() + 1);
+    }
+
+    #[test]
+    fn empty_search_shows_everything() {
+        let ui = Theme::default().ui;
+        let keymap = Keymap::default();
+        let all = help_content(&ui, &keymap);
+        assert_eq!(
+            filter_help_lines(help_content(&ui, &keymap), "").len(),
+            all.len()
+        );
+        assert!(filter_help_lines(all, "no such shortcut").is_empty());
+    }
+
+    #[test]
+    fn every_binding_is_listed_under_its_category() {
+        let ui = Theme::default().ui;
+        let keymap = Keymap::default();
+        let lines = texts(&help_content(&ui, &keymap));
+        let mut section = "";
+        let mut listed = Vec::new();
+        for line in &lines {
+            if !line.starts_with("  ") {
+                section = line;
+            } else {
+                listed.push((section, line.as_str()));
+            }
+        }
+        for category in [Category::Navigation, Category::Tabs, Category::General] {
+            for binding in keymap.in_category(category) {
+                let expected = format!("{:<13} {}", binding.keys_label(), binding.description);
+                assert!(
+                    listed
+                        .iter()
+                        .any(|(s, l)| *s == category.title() && l.trim_start() == expected),
+                    "missing {:?}",
+                    expected
+                );
+            }
+        }
+    }
+}
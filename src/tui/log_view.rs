@@ -1,9 +1,15 @@
-use crate::app::{App, InputMode, TabState, ViewMode};
+use crate::ansi::strip_ansi;
+use crate::app::{App, InputMode, RawMode, RendererOverride, TabState, ViewMode};
+use crate::config::types::LinePrefixConfig;
 use crate::index::flags::Severity;
 use crate::index::reader::IndexReader;
+use crate::log_source::ArrivalTimes;
 use crate::reader::combined_reader::CombinedReader;
+use crate::reader::json_record_reader::pretty_json;
 use crate::reader::LogReader;
+use crate::renderer::highlight::{find_word, word_ranges, HighlightSet};
 use crate::renderer::segment::{to_ratatui_style, StyledSegment};
+use crate::renderer::transform::DisplayTransform;
 use crate::renderer::PresetRegistry;
 use crate::text_wrap::{expand_tabs, wrap_content, wrap_plain, wrap_spans};
 use crate::theme::UiColors;
@@ -15,32 +21,47 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem},
     Frame,
 };
-
-#[cfg(test)]
+use std::collections::HashMap;
+use std::ops::Range;
 use unicode_width::UnicodeWidthStr;
 
 // Line rendering constants
-const LINE_PREFIX_WIDTH: usize = 9; // "{:6} | " = 9 characters
 /// Extra prefix width for combined view: "[tag] " before the line number
 const MAX_SOURCE_TAG_WIDTH: usize = 8; // e.g. "[api-sv] "
 /// Width of the timestamp column: "YYYY-MM-DD HH:MM:SS.mmm " = 24 characters (max, with date).
 /// Today's lines use "HH:MM:SS.mmm" (13 chars) left-padded to this width.
 const TIMESTAMP_COL_WIDTH: usize = 24;
 
+/// Drawn in the prefix of wrapped continuation rows (one column wide)
+const WRAP_MARKER: &str = "↳";
+
 /// Shared rendering state for all lines in a frame.
 struct RenderContext<'a> {
     ui: &'a UiColors,
     palette: &'a crate::theme::Palette,
     preset_registry: &'a PresetRegistry,
+    highlights: &'a HighlightSet,
+    display_transform: Option<&'a DisplayTransform>,
     tab_renderer_names: Vec<String>,
+    /// Runtime renderer choice is raw: skip presets entirely
+    skip_presets: bool,
     tab_filename: Option<String>,
     index_reader: Option<&'a IndexReader>,
+    arrival_times: &'a ArrivalTimes,
     is_combined: bool,
-    raw_mode: bool,
+    raw_mode: RawMode,
+    pretty_json: bool,
     line_wrap: bool,
     show_timestamps: bool,
+    /// Configured timestamp format (None = adaptive default)
+    timestamp_format: Option<String>,
+    timestamp_width: usize,
+    wrap_markers: bool,
+    line_prefix: LinePrefix,
     prefix_width: usize,
     content_width: usize,
+    /// Word under the cursor whose occurrences are highlighted (`*`)
+    word: Option<String>,
 }
 
 /// Per-line metadata resolved before rendering.
@@ -75,6 +96,12 @@ fn apply_selection_style(style: Style, ui: &UiColors) -> Style {
 
 pub(super) fn render_log_view(f: &mut Frame, area: Rect, app: &mut App) -> Result<()> {
     let preset_registry = app.preset_registry.clone();
+    let highlights = app.highlights.clone();
+    let wrap_markers = app.wrap_markers;
+    let follow_pause_banner = app.follow_pause_banner;
+    let follow_margin = app.follow_margin;
+    let line_prefix_config = app.line_prefix.clone();
+    let timestamp_format = app.timestamp_format.clone();
 
     let ui = &app.theme.ui;
     let palette = &app.theme.palette;
@@ -91,32 +118,43 @@ pub(super) fn render_log_view(f: &mut Frame, area: Rect, app: &mut App) -> Resul
     let available_width = area.width.saturating_sub(2) as usize;
     let is_combined = tab.is_combined;
     let show_timestamps = tab.source.show_timestamps;
-    let prefix_width = LINE_PREFIX_WIDTH
+    let timestamp_width = timestamp_column_width(timestamp_format.as_deref());
+    let line_prefix = LinePrefix::new(&line_prefix_config, tab.source.total_lines);
+    let prefix_width = line_prefix.width()
         + if is_combined { MAX_SOURCE_TAG_WIDTH } else { 0 }
-        + if show_timestamps {
-            TIMESTAMP_COL_WIDTH
-        } else {
-            0
-        };
+        + if show_timestamps { timestamp_width } else { 0 };
     let content_width = available_width.saturating_sub(prefix_width);
 
-    let ctx = RenderContext {
+    let mut ctx = RenderContext {
         ui,
         palette,
         preset_registry: &preset_registry,
-        tab_renderer_names: tab.source.renderer_names.clone(),
+        highlights: &highlights,
+        display_transform: tab.source.display_transform.as_ref(),
+        tab_renderer_names: match &tab.source.renderer_override {
+            Some(RendererOverride::Preset(name)) => vec![name.clone()],
+            _ => tab.source.renderer_names.clone(),
+        },
+        skip_presets: tab.source.renderer_override == Some(RendererOverride::Raw),
         tab_filename: tab
             .source
             .source_path
             .as_ref()
             .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string())),
         index_reader: tab.source.index_reader.as_ref(),
+        arrival_times: &tab.source.arrival_times,
         is_combined,
         raw_mode: tab.source.raw_mode,
+        pretty_json: tab.source.multiline_json && !tab.source.raw_mode.is_raw(),
         line_wrap: tab.source.line_wrap,
         show_timestamps,
+        timestamp_format,
+        timestamp_width,
+        wrap_markers,
+        line_prefix,
         prefix_width,
         content_width,
+        word: None,
     };
 
     let mut reader_guard = match tab.source.reader.lock() {
@@ -125,19 +163,33 @@ pub(super) fn render_log_view(f: &mut Frame, area: Rect, app: &mut App) -> Resul
     };
     let expanded_lines = tab.expansion.expanded_lines.clone();
     let total_lines = tab.source.line_indices.len();
+    // Viewport indices are in display order; map them back to line_indices
+    let reversed = tab.viewport.is_reversed();
+    let data_idx = move |idx: usize| {
+        if reversed {
+            total_lines.checked_sub(idx + 1)
+        } else {
+            Some(idx)
+        }
+    };
 
     // Resolve viewport with visual line heights.
     // For non-wrap mode every line is 1 row; for wrap mode lines may span
     // multiple rows. Viewport::ensure_visible uses these heights so
     // scrolling works correctly in both modes — single code path.
     let mut line_height = |idx: usize| -> usize {
-        if let Some(&ln) = tab.source.line_indices.get(idx) {
+        if let Some(ln) = data_idx(idx).and_then(|d| tab.source.line_indices.get(d)) {
             let needs_wrap =
                 (ctx.line_wrap || expanded_lines.contains(&ln)) && ctx.content_width > 0;
             if needs_wrap {
                 let raw = reader_guard.get_line(ln).ok().flatten().unwrap_or_default();
-                let text = expand_tabs(&raw);
-                if ctx.raw_mode {
+                let text = display_text(raw, &ctx);
+                let pretty = (ctx.pretty_json && expanded_lines.contains(&ln))
+                    .then(|| wrap_pretty_json(&text, ctx.content_width))
+                    .flatten();
+                if let Some(lines) = pretty {
+                    lines.len()
+                } else if ctx.raw_mode == RawMode::Plain {
                     wrap_plain(&text, ctx.content_width).len()
                 } else {
                     wrap_content(&text, ctx.content_width).len()
@@ -150,6 +202,11 @@ pub(super) fn render_log_view(f: &mut Frame, area: Rect, app: &mut App) -> Resul
         }
     };
 
+    tab.viewport.set_tail_gap(if tab.source.follow_mode {
+        follow_margin
+    } else {
+        0
+    });
     let view = tab.viewport.resolve_with_heights(
         &tab.source.line_indices,
         visible_height,
@@ -157,11 +214,24 @@ pub(super) fn render_log_view(f: &mut Frame, area: Rect, app: &mut App) -> Resul
     );
 
     tab.scroll_position = view.scroll_position;
-    tab.selected_line = view.selected_index;
+    if total_lines > 0 {
+        tab.selected_line = tab.viewport.data_index(view.selected_index, total_lines);
+    } else {
+        tab.selected_line = view.selected_index;
+    }
 
     let start_idx = view.scroll_position;
     let selected_idx = view.selected_index;
 
+    ctx.word = tab.word_cursor.and_then(|cursor| {
+        let line_number = data_idx(selected_idx).and_then(|d| tab.source.line_indices.get(d))?;
+        let raw = reader_guard.get_line(line_number).ok().flatten()?;
+        let text = strip_ansi(&display_line(raw, &ctx));
+        let words = word_ranges(&text);
+        let word = words.get(cursor.min(words.len().checked_sub(1)?))?;
+        Some(text[word.clone()].to_string())
+    });
+
     // Build visible items
     let mut items = Vec::new();
     let mut visual_rows_used = 0usize;
@@ -171,9 +241,16 @@ pub(super) fn render_log_view(f: &mut Frame, area: Rect, app: &mut App) -> Resul
         (0, 0, 0)
     };
     for i in start_idx..total_lines {
-        if let Some(&line_number) = tab.source.line_indices.get(i) {
+        if let Some(line_number) = data_idx(i).and_then(|d| tab.source.line_indices.get(d)) {
             let raw_line = reader_guard.get_line(line_number)?.unwrap_or_default();
-            let line_text = expand_tabs(&raw_line);
+            let (raw_line, line_text) = if ctx.raw_mode.is_raw() {
+                let text = raw_text(&raw_line, ctx.raw_mode);
+                (text.clone(), text)
+            } else {
+                let raw_line = display_line(raw_line, &ctx);
+                let line_text = expand_tabs(&raw_line);
+                (raw_line, line_text)
+            };
             let is_expanded = expanded_lines.contains(&line_number);
 
             let timestamp_display = if ctx.show_timestamps {
@@ -183,8 +260,12 @@ pub(super) fn render_log_view(f: &mut Frame, area: Rect, app: &mut App) -> Resul
                     ctx.index_reader,
                     &*reader_guard,
                 )
+                .or_else(|| ctx.arrival_times.get(line_number))
                 .filter(|&ms| ms > 0)
-                .map(|ms| format_epoch_ms_local(ms, today))
+                .map(|ms| match &ctx.timestamp_format {
+                    Some(format) => format_epoch_ms_pattern(ms, format),
+                    None => format_epoch_ms_local(ms, today),
+                })
             } else {
                 None
             };
@@ -214,8 +295,13 @@ pub(super) fn render_log_view(f: &mut Frame, area: Rect, app: &mut App) -> Resul
 
             // Wrap if needed (expanded, line_wrap, or neither → single line)
             let should_wrap = (is_expanded || ctx.line_wrap) && ctx.content_width > 0;
-            let wrapped = if should_wrap {
-                if is_expanded && ctx.raw_mode {
+            let pretty = (is_expanded && ctx.pretty_json && ctx.content_width > 0)
+                .then(|| wrap_pretty_json(&line_text, ctx.content_width))
+                .flatten();
+            let wrapped = if pretty.is_some() {
+                pretty
+            } else if should_wrap {
+                if is_expanded && ctx.raw_mode == RawMode::Plain {
                     // Expanded + raw: wrap the raw text directly
                     Some(wrap_plain(&line_text, ctx.content_width))
                 } else if is_expanded {
@@ -248,25 +334,46 @@ pub(super) fn render_log_view(f: &mut Frame, area: Rect, app: &mut App) -> Resul
 
     // Render widget
     let title = build_title(tab);
+    let accent = source_accent(tab, &app.source_color_map, ui);
     let is_log_focused = app.input.mode != InputMode::SourcePanel;
     let border_style = if is_log_focused {
-        Style::default().fg(ui.primary)
+        Style::default().fg(accent.unwrap_or(ui.primary))
     } else {
         Style::default()
     };
+    let title_style = accent.map_or_else(Style::default, |c| Style::default().fg(c));
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(border_style)
+        .title(title)
+        .title_style(title_style)
+        .style(ui.bg_style());
+    let paused_lines = tab.source.new_lines_while_paused();
+    if follow_pause_banner && paused_lines > 0 {
+        block = block.title_bottom(
+            Line::from(Span::styled(
+                follow_paused_banner(paused_lines),
+                Style::default()
+                    .fg(ui.severity_warn)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .right_aligned(),
+        );
+    }
 
-    let list = List::new(items).style(ui.bg_style()).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .border_style(border_style)
-            .title(title)
-            .style(ui.bg_style()),
-    );
+    let list = List::new(items).style(ui.bg_style()).block(block);
 
     f.render_widget(list, area);
     Ok(())
 }
 
+/// Bottom-border notice shown while follow mode is paused and lines arrive.
+fn follow_paused_banner(new_lines: usize) -> String {
+    let noun = if new_lines == 1 { "line" } else { "lines" };
+    format!(" follow paused — {new_lines} new {noun} · f resume · G end ")
+}
+
 // ---------------------------------------------------------------------------
 // Per-line metadata resolution
 // ---------------------------------------------------------------------------
@@ -344,6 +451,52 @@ fn format_epoch_ms_local(epoch_ms: u64, today: (i32, i32, i32)) -> String {
     }
 }
 
+/// Format epoch milliseconds in local time with a strftime-style pattern.
+/// Supports `%Y %m %d %H %M %S`, `%f` (milliseconds) and `%%`; anything
+/// else is copied as-is.
+pub(super) fn format_epoch_ms_pattern(epoch_ms: u64, pattern: &str) -> String {
+    let epoch_secs = (epoch_ms / 1000) as libc::time_t;
+    let mut tm = std::mem::MaybeUninit::<libc::tm>::uninit();
+    // Safety: localtime_r is thread-safe and writes into our stack buffer.
+    let tm = unsafe {
+        libc::localtime_r(&epoch_secs, tm.as_mut_ptr());
+        tm.assume_init()
+    };
+    let mut out = String::with_capacity(pattern.len() + 8);
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('Y') => out.push_str(&format!("{:04}", tm.tm_year + 1900)),
+            Some('m') => out.push_str(&format!("{:02}", tm.tm_mon + 1)),
+            Some('d') => out.push_str(&format!("{:02}", tm.tm_mday)),
+            Some('H') => out.push_str(&format!("{:02}", tm.tm_hour)),
+            Some('M') => out.push_str(&format!("{:02}", tm.tm_min)),
+            Some('S') => out.push_str(&format!("{:02}", tm.tm_sec)),
+            Some('f') => out.push_str(&format!("{:03}", epoch_ms % 1000)),
+            Some('%') => out.push('%'),
+            Some(other) => {
+                out.push('%');
+                out.push(other);
+            }
+            None => out.push('%'),
+        }
+    }
+    out
+}
+
+/// Timestamp column width including the trailing gap. Custom formats are
+/// fixed-width, so one formatted sample gives the width.
+fn timestamp_column_width(format: Option<&str>) -> usize {
+    match format {
+        Some(format) => format_epoch_ms_pattern(0, format).width() + 1,
+        None => TIMESTAMP_COL_WIDTH,
+    }
+}
+
 fn resolve_severity(
     line_number: usize,
     is_combined: bool,
@@ -377,8 +530,68 @@ fn format_line_spans(
     ctx: &RenderContext<'_>,
     reader: &dyn LogReader,
 ) -> Vec<Span<'static>> {
-    if ctx.raw_mode {
-        return vec![Span::raw(line_text.to_string())];
+    let spans = format_base_spans(raw_line, line_text, info, ctx, reader);
+    let spans = if ctx.highlights.is_empty() || ctx.raw_mode.is_raw() {
+        spans
+    } else {
+        apply_highlights(spans, ctx.highlights, ctx.palette)
+    };
+    match &ctx.word {
+        // Patched last, over configured highlights: an underline in the
+        // highlight color, leaving their background visible
+        Some(word) => {
+            let style = Style::default()
+                .fg(ctx.ui.highlight)
+                .add_modifier(Modifier::UNDERLINED);
+            apply_word_highlight(spans, word, style)
+        }
+        None => spans,
+    }
+}
+
+/// Apply the source's display transform, if any. Lines the pattern does not
+/// match are shown unchanged.
+fn display_line(raw_line: String, ctx: &RenderContext<'_>) -> String {
+    match ctx.display_transform {
+        Some(transform) => transform.apply(&strip_ansi(&raw_line)).unwrap_or(raw_line),
+        None => raw_line,
+    }
+}
+
+/// Line text as shown in the view: transformed and tab-expanded, or the
+/// raw bytes when raw mode is on.
+fn display_text(raw_line: String, ctx: &RenderContext<'_>) -> String {
+    if ctx.raw_mode.is_raw() {
+        raw_text(&raw_line, ctx.raw_mode)
+    } else {
+        expand_tabs(&display_line(raw_line, ctx))
+    }
+}
+
+/// Make the control characters the terminal would swallow visible: tabs
+/// always, and ESC too when ANSI parsing is off.
+fn raw_text(line: &str, mode: RawMode) -> String {
+    line.chars()
+        .map(|c| match c {
+            '\t' => '⇥',
+            '\x1b' if mode == RawMode::Plain => '␛',
+            c => c,
+        })
+        .collect()
+}
+
+/// Render content spans via raw text, a preset, or ANSI parsing.
+fn format_base_spans(
+    raw_line: &str,
+    line_text: &str,
+    info: &LineInfo,
+    ctx: &RenderContext<'_>,
+    reader: &dyn LogReader,
+) -> Vec<Span<'static>> {
+    match ctx.raw_mode {
+        RawMode::Plain => return vec![Span::raw(line_text.to_string())],
+        RawMode::Ansi => return ansi_spans(line_text),
+        RawMode::Off => {}
     }
 
     let line_flags: Option<u32> = if ctx.is_combined {
@@ -396,7 +609,9 @@ fn format_line_spans(
         &ctx.tab_renderer_names
     };
 
-    let preset_segments: Option<Vec<StyledSegment>> = if !renderer_names.is_empty() {
+    let preset_segments: Option<Vec<StyledSegment>> = if ctx.skip_presets {
+        None
+    } else if !renderer_names.is_empty() {
         ctx.preset_registry
             .render_line(raw_line, renderer_names, line_flags)
     } else {
@@ -415,18 +630,104 @@ fn format_line_spans(
             })
             .collect()
     } else {
-        let parsed_text = ansi_to_tui::IntoText::into_text(&line_text)
-            .unwrap_or_else(|_| ratatui::text::Text::raw(line_text.to_string()));
-        if let Some(first_line) = parsed_text.lines.first() {
-            first_line
-                .spans
-                .iter()
-                .map(|s| Span::styled(s.content.to_string(), s.style))
-                .collect()
-        } else {
-            vec![Span::raw(line_text.to_string())]
+        ansi_spans(line_text)
+    }
+}
+
+/// An expanded folded-JSON line, pretty-printed and wrapped to `width`.
+/// `None` if the line is not a JSON document.
+fn wrap_pretty_json(text: &str, width: usize) -> Option<Vec<Line<'static>>> {
+    let pretty = pretty_json(text)?;
+    Some(
+        pretty
+            .lines()
+            .flat_map(|line| wrap_content(line, width))
+            .collect(),
+    )
+}
+
+/// Parse ANSI escape codes in `line_text` into styled spans.
+fn ansi_spans(line_text: &str) -> Vec<Span<'static>> {
+    let parsed_text = ansi_to_tui::IntoText::into_text(&line_text)
+        .unwrap_or_else(|_| ratatui::text::Text::raw(line_text.to_string()));
+    if let Some(first_line) = parsed_text.lines.first() {
+        first_line
+            .spans
+            .iter()
+            .map(|s| Span::styled(s.content.to_string(), s.style))
+            .collect()
+    } else {
+        vec![Span::raw(line_text.to_string())]
+    }
+}
+
+/// Patch highlight styles onto the substrings of `spans` matched by any rule.
+/// Spans are split at match boundaries; overlapping rules compose in order.
+fn apply_highlights(
+    spans: Vec<Span<'static>>,
+    highlights: &HighlightSet,
+    palette: &crate::theme::Palette,
+) -> Vec<Span<'static>> {
+    let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+    let ranges: Vec<_> = highlights
+        .find(&text)
+        .into_iter()
+        .map(|(range, style)| (range, to_ratatui_style(style, Some(palette))))
+        .collect();
+    patch_ranges(spans, &ranges)
+}
+
+/// Patch `style` onto whole-word occurrences of `word` in `spans`.
+fn apply_word_highlight(spans: Vec<Span<'static>>, word: &str, style: Style) -> Vec<Span<'static>> {
+    let text: String = spans.iter().map(|s| s.content.as_ref()).collect();
+    let ranges: Vec<_> = find_word(&text, word)
+        .into_iter()
+        .map(|range| (range, style))
+        .collect();
+    patch_ranges(spans, &ranges)
+}
+
+/// Patch each range's style onto the text it covers, splitting spans at
+/// range boundaries. Ranges are byte offsets into the spans' joined text.
+fn patch_ranges(spans: Vec<Span<'static>>, ranges: &[(Range<usize>, Style)]) -> Vec<Span<'static>> {
+    if ranges.is_empty() {
+        return spans;
+    }
+
+    // Collect every boundary so each output span lies entirely inside or
+    // outside each highlight range.
+    let mut cuts: Vec<usize> = Vec::with_capacity(ranges.len() * 2 + spans.len());
+    let mut offset = 0;
+    for span in &spans {
+        offset += span.content.len();
+        cuts.push(offset);
+    }
+    for (range, _) in ranges {
+        cuts.push(range.start);
+        cuts.push(range.end);
+    }
+    cuts.sort_unstable();
+    cuts.dedup();
+
+    let mut out = Vec::with_capacity(cuts.len());
+    let mut span_start = 0;
+    for span in spans {
+        let span_end = span_start + span.content.len();
+        let mut piece_start = span_start;
+        for &cut in cuts.iter().filter(|&&c| c > span_start && c <= span_end) {
+            let mut style = span.style;
+            for (range, range_style) in ranges {
+                if range.start <= piece_start && cut <= range.end {
+                    style = style.patch(*range_style);
+                }
+            }
+            let piece = &span.content[piece_start - span_start..cut - span_start];
+            out.push(Span::styled(piece.to_string(), style));
+            piece_start = cut;
         }
+        span_start = span_end;
     }
+    out
 }
 
 // ---------------------------------------------------------------------------
@@ -484,8 +785,8 @@ fn build_item(
     ctx: &RenderContext<'_>,
 ) -> ListItem<'static> {
     let severity_color = severity_bg(info.severity, ctx.ui);
-    let line_num_part = format!("{:6} |", info.line_number + 1);
-    let line_sep_part = " ";
+    let line_num_part = ctx.line_prefix.number_part(info.line_number);
+    let line_sep_part = ctx.line_prefix.separator_tail().to_string();
     let layout = PrefixLayout::new(info.source_tag.is_some(), ctx.show_timestamps);
 
     let mut item_lines: Vec<Line<'static>> = Vec::new();
@@ -498,13 +799,13 @@ fn build_item(
                 .map(|bg| Style::default().bg(bg))
                 .unwrap_or_default();
             line.spans
-                .insert(0, Span::styled(line_sep_part, Style::default()));
+                .insert(0, Span::styled(line_sep_part.clone(), Style::default()));
             line.spans
                 .insert(0, Span::styled(line_num_part.clone(), num_style));
             if ctx.show_timestamps {
                 let ts_text = match &info.timestamp_display {
-                    Some(ts) => format!("{:<width$}", ts, width = TIMESTAMP_COL_WIDTH),
-                    None => " ".repeat(TIMESTAMP_COL_WIDTH),
+                    Some(ts) => format!("{:<width$}", ts, width = ctx.timestamp_width),
+                    None => " ".repeat(ctx.timestamp_width),
                 };
                 line.spans
                     .insert(0, Span::styled(ts_text, Style::default().fg(ctx.ui.muted)));
@@ -515,8 +816,16 @@ fn build_item(
                     .insert(0, Span::styled(tag, Style::default().fg(color)));
             }
         } else {
+            let marker = ctx
+                .line_prefix
+                .marker_offset()
+                .filter(|_| ctx.wrap_markers)
+                .map(|offset| {
+                    let column = ctx.prefix_width - ctx.line_prefix.width() + offset;
+                    (column, ctx.ui.wrap_marker)
+                });
             line.spans
-                .insert(0, Span::raw(" ".repeat(ctx.prefix_width)));
+                .splice(0..0, continuation_prefix(ctx.prefix_width, marker));
         }
 
         // Apply styling
@@ -541,6 +850,76 @@ fn build_item(
     ListItem::new(item_lines)
 }
 
+/// Prefix for a wrapped continuation row: blank, or with a marker at the
+/// given column (under the separator) so the row reads as part of the line
+/// above.
+fn continuation_prefix(width: usize, marker: Option<(usize, Color)>) -> Vec<Span<'static>> {
+    match marker {
+        Some((column, color)) if column < width => vec![
+            Span::raw(" ".repeat(column)),
+            Span::styled(WRAP_MARKER, Style::default().fg(color)),
+            Span::raw(" ".repeat(width - column - 1)),
+        ],
+        _ => vec![Span::raw(" ".repeat(width))],
+    }
+}
+
+/// Resolved line number prefix: number column plus separator.
+struct LinePrefix {
+    /// Number column width, 0 when numbers are hidden
+    number_width: usize,
+    separator: String,
+}
+
+impl LinePrefix {
+    /// Widen the configured number column so `total_lines` still fits.
+    fn new(config: &LinePrefixConfig, total_lines: usize) -> Self {
+        let number_width = if config.show_numbers {
+            config.width.max(total_lines.max(1).to_string().len())
+        } else {
+            0
+        };
+        Self {
+            number_width,
+            separator: config.separator.clone(),
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.number_width + self.separator.width()
+    }
+
+    /// Number (right-aligned) plus the visible part of the separator.
+    fn number_part(&self, line_number: usize) -> String {
+        let head = self.separator.trim_end();
+        if self.number_width == 0 {
+            return head.to_string();
+        }
+        format!(
+            "{:>width$}{}",
+            line_number + 1,
+            head,
+            width = self.number_width
+        )
+    }
+
+    /// Trailing whitespace of the separator, kept as its own span.
+    fn separator_tail(&self) -> &str {
+        &self.separator[self.separator.trim_end().len()..]
+    }
+
+    /// Column (within the prefix) of the separator's first visible
+    /// character, where continuation rows draw their marker.
+    fn marker_offset(&self) -> Option<usize> {
+        let trimmed = self.separator.trim_start();
+        if trimmed.trim_end().is_empty() {
+            return None;
+        }
+        let leading = &self.separator[..self.separator.len() - trimmed.len()];
+        Some(self.number_width + leading.width())
+    }
+}
+
 /// Apply expanded background color to non-prefix spans.
 fn apply_expanded_bg(
     line: &mut Line<'static>,
@@ -571,7 +950,7 @@ fn apply_expanded_bg(
             }
         }
     } else {
-        // Continuation row: single indent span
+        // Continuation row: indent (and marker) spans
         for span in line.spans.iter_mut() {
             span.style = span.style.bg(ui.expanded_bg);
         }
@@ -582,6 +961,24 @@ fn apply_expanded_bg(
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Accent color for a tab: its configured color, else one derived from the
+/// name for discovered (captured) sources. `None` means use the theme.
+fn source_accent(
+    tab: &TabState,
+    configured: &HashMap<String, Color>,
+    ui: &UiColors,
+) -> Option<Color> {
+    if tab.is_combined {
+        return None;
+    }
+    configured.get(&tab.source.name).copied().or_else(|| {
+        tab.source
+            .source_status
+            .is_some()
+            .then(|| ui.source_color(&tab.source.name))
+    })
+}
+
 fn build_title(tab: &TabState) -> String {
     let path_suffix = tab
         .source
@@ -589,15 +986,53 @@ fn build_title(tab: &TabState) -> String {
         .as_ref()
         .map(|p| format!(" — {}", p.display()))
         .unwrap_or_default();
+    let renderer = tab
+        .source
+        .renderer_override
+        .as_ref()
+        .map(|r| format!(" · renderer: {}", r.label()))
+        .unwrap_or_default();
+    let path_suffix = path_suffix + &renderer;
+
+    if tab.is_waiting() {
+        return format!(
+            "{}{} (waiting for file to appear)",
+            tab.source.name, path_suffix
+        );
+    }
+
+    let severity = severity_summary(tab);
 
     match (&tab.source.mode, &tab.source.filter.pattern) {
         (ViewMode::Normal, None) => format!("{}{}", tab.source.name, path_suffix),
-        (ViewMode::Filtered, Some(pattern)) => {
+        (ViewMode::Filtered, None) if !severity.is_empty() => {
+            format!(
+                "{}{} (Severity: {})",
+                tab.source.name, path_suffix, severity
+            )
+        }
+        (ViewMode::Filtered, Some(pattern)) if tab.source.is_default_filter_active() => {
             format!(
-                "{}{} (Filter: \"{}\")",
+                "{}{} (Default filter: \"{}\" · Esc to clear)",
                 tab.source.name, path_suffix, pattern
             )
         }
+        (ViewMode::Filtered, Some(pattern)) => {
+            let negated = if tab.source.filter.mode.is_negated() {
+                "!"
+            } else {
+                ""
+            };
+            let severity = if severity.is_empty() {
+                severity
+            } else {
+                format!(" · {}", severity)
+            };
+            format!(
+                "{}{} (Filter: {}\"{}\"{})",
+                tab.source.name, path_suffix, negated, pattern, severity
+            )
+        }
         (ViewMode::Filtered, None) => format!("{}{} (Filtered)", tab.source.name, path_suffix),
         (ViewMode::Aggregation, Some(pattern)) => {
             format!(
@@ -612,6 +1047,27 @@ fn build_title(tab: &TabState) -> String {
     }
 }
 
+/// The tab's severity floor and hidden levels for the title, e.g.
+/// "info+ · hiding trace, debug"; empty when neither is set.
+fn severity_summary(tab: &TabState) -> String {
+    let filter = &tab.source.filter;
+    let floor = filter
+        .min_severity
+        .and_then(|s| s.label())
+        .map(|level| format!("{}+", level));
+    let hidden: Vec<&str> = filter
+        .hidden_severities
+        .iter()
+        .filter_map(|s| s.label())
+        .collect();
+    let hidden = (!hidden.is_empty()).then(|| format!("hiding {}", hidden.join(", ")));
+    floor
+        .into_iter()
+        .chain(hidden)
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
 /// Format a source name into a fixed-width tag like "[api] " or "[web-s..] ".
 fn format_source_tag(name: &str, max_width: usize) -> String {
     let inner_max = max_width.saturating_sub(3);
@@ -814,3 +1270,213 @@ mod wrap_spans_tests {
         assert_eq!(lines.len(), 1);
     }
 }
+
+#[cfg(test)]
+mod highlight_tests {
+    use super::*;
+    use crate::config::types::{RawHighlightDef, StyleValue};
+    use crate::theme::Palette;
+
+    fn set(rules: &[(&str, &str)]) -> HighlightSet {
+        let defs: Vec<RawHighlightDef> = rules
+            .iter()
+            .map(|(p, s)| RawHighlightDef {
+                pattern: p.to_string(),
+                style: StyleValue::Single(s.to_string()),
+            })
+            .collect();
+        HighlightSet::compile(&defs).0
+    }
+
+    fn text(spans: &[Span<'_>]) -> String {
+        spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn splits_span_at_match_boundaries() {
+        let spans = vec![Span::raw("thread panic here")];
+        let out = apply_highlights(spans, &set(&[("panic", "bold")]), &Palette::dark());
+        assert_eq!(text(&out), "thread panic here");
+        assert_eq!(out.len(), 3);
+        assert_eq!(out[1].content, "panic");
+        assert!(out[1].style.add_modifier.contains(Modifier::BOLD));
+        assert!(!out[0].style.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn match_across_span_boundary_keeps_base_styles() {
+        let spans = vec![
+            Span::styled("pa", Style::default().fg(Color::Green)),
+            Span::raw("nic"),
+        ];
+        let out = apply_highlights(spans, &set(&[("panic", "bold")]), &Palette::dark());
+        assert_eq!(text(&out), "panic");
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0].style.fg, Some(Color::Green));
+        assert!(out[0].style.add_modifier.contains(Modifier::BOLD));
+        assert!(out[1].style.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn overlapping_rules_compose() {
+        let spans = vec![Span::raw("OOM killer")];
+        let out = apply_highlights(
+            spans,
+            &set(&[("OOM killer", "bold"), ("killer", "red")]),
+            &Palette::dark(),
+        );
+        assert_eq!(text(&out), "OOM killer");
+        let killer = out.iter().find(|s| s.content == "killer").unwrap();
+        assert!(killer.style.add_modifier.contains(Modifier::BOLD));
+        assert!(killer.style.fg.is_some());
+    }
+
+    #[test]
+    fn no_match_returns_spans_unchanged() {
+        let spans = vec![Span::raw("all good")];
+        let out = apply_highlights(spans.clone(), &set(&[("panic", "red")]), &Palette::dark());
+        assert_eq!(out, spans);
+    }
+
+    #[test]
+    fn word_highlight_keeps_rule_styles() {
+        let spans = vec![Span::raw("retry req-7 after req-7x")];
+        let spans = apply_highlights(spans, &set(&[("req", "bold")]), &Palette::dark());
+        let underline = Style::default().add_modifier(Modifier::UNDERLINED);
+        let out = apply_word_highlight(spans, "req-7", underline);
+        assert_eq!(text(&out), "retry req-7 after req-7x");
+        let marked: Vec<&str> = out
+            .iter()
+            .filter(|s| s.style.add_modifier.contains(Modifier::UNDERLINED))
+            .map(|s| s.content.as_ref())
+            .collect();
+        assert_eq!(marked, vec!["req", "-7"]);
+        assert!(out[1].style.add_modifier.contains(Modifier::BOLD));
+    }
+}
+
+#[cfg(test)]
+mod source_accent_tests {
+    use super::*;
+    use crate::source::SourceStatus;
+    use crate::theme::Theme;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn tab(name: &str) -> (TabState, NamedTempFile) {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "line").unwrap();
+        let mut tab = TabState::new(file.path().to_path_buf(), false).unwrap();
+        tab.source.name = name.to_string();
+        (tab, file)
+    }
+
+    #[test]
+    fn configured_color_wins_then_derived_for_discovered() {
+        let ui = Theme::dark().ui;
+        let configured = HashMap::from([("api".to_string(), Color::Rgb(1, 2, 3))]);
+
+        let (api, _f1) = tab("api");
+        assert_eq!(
+            source_accent(&api, &configured, &ui),
+            Some(Color::Rgb(1, 2, 3))
+        );
+
+        let (mut worker, _f2) = tab("worker");
+        assert_eq!(source_accent(&worker, &configured, &ui), None);
+        worker.source.source_status = Some(SourceStatus::Active);
+        assert_eq!(
+            source_accent(&worker, &configured, &ui),
+            Some(ui.source_color("worker"))
+        );
+    }
+}
+
+#[cfg(test)]
+mod line_prefix_tests {
+    use super::*;
+
+    fn text(spans: &[Span<'_>]) -> String {
+        spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn default_prefix_matches_classic_layout() {
+        let prefix = LinePrefix::new(&LinePrefixConfig::default(), 100);
+        assert_eq!(prefix.width(), 9);
+        assert_eq!(prefix.number_part(0), "     1 |");
+        assert_eq!(prefix.separator_tail(), " ");
+    }
+
+    #[test]
+    fn number_column_widens_for_large_files() {
+        let prefix = LinePrefix::new(&LinePrefixConfig::default(), 12_345_678);
+        assert_eq!(prefix.number_part(41), "      42 |");
+        assert_eq!(prefix.width(), 11);
+    }
+
+    #[test]
+    fn hidden_numbers_keep_separator() {
+        let config = LinePrefixConfig {
+            show_numbers: false,
+            width: 6,
+            separator: "│ ".to_string(),
+        };
+        let prefix = LinePrefix::new(&config, 100);
+        assert_eq!(prefix.width(), 2);
+        assert_eq!(prefix.number_part(7), "│");
+        assert_eq!(prefix.marker_offset(), Some(0));
+
+        let bare = LinePrefix::new(
+            &LinePrefixConfig {
+                separator: String::new(),
+                ..config
+            },
+            100,
+        );
+        assert_eq!(bare.width(), 0);
+        assert_eq!(bare.marker_offset(), None);
+    }
+
+    #[test]
+    fn marker_sits_under_the_separator() {
+        let prefix = LinePrefix::new(&LinePrefixConfig::default(), 100);
+        let column = prefix.marker_offset().unwrap();
+        let spans = continuation_prefix(prefix.width(), Some((column, Color::DarkGray)));
+        assert_eq!(text(&spans), format!("       {} ", WRAP_MARKER));
+        assert_eq!(spans[1].style.fg, Some(Color::DarkGray));
+        assert_eq!(
+            prefix.number_part(0).find('|'),
+            text(&spans).find(WRAP_MARKER)
+        );
+    }
+
+    #[test]
+    fn disabled_marker_is_blank_indent() {
+        let spans = continuation_prefix(9, None);
+        assert_eq!(text(&spans), " ".repeat(9));
+    }
+}
+
+#[cfg(test)]
+mod timestamp_format_tests {
+    use super::*;
+
+    #[test]
+    fn pattern_fields_are_zero_padded() {
+        let text = format_epoch_ms_pattern(7, "%Y-%m-%d %H:%M:%S.%f");
+        assert_eq!(text.len(), "YYYY-MM-DD HH:MM:SS.mmm".len());
+        assert!(text.ends_with(".007"));
+    }
+
+    #[test]
+    fn unknown_specifiers_are_kept() {
+        assert_eq!(format_epoch_ms_pattern(0, "100%% %q %"), "100% %q %");
+    }
+
+    #[test]
+    fn column_width_follows_format() {
+        assert_eq!(timestamp_column_width(None), TIMESTAMP_COL_WIDTH);
+        assert_eq!(timestamp_column_width(Some("%H:%M")), 6);
+    }
+}
@@ -3,8 +3,10 @@ mod help;
 mod log_view;
 mod side_panel;
 mod status_bar;
+mod tab_bar;
 
 use crate::app::{App, InputMode, LayoutRect, ViewMode};
+use crate::config::types::NavLayout;
 use anyhow::Result;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -16,14 +18,36 @@ pub fn render(f: &mut Frame, app: &mut App) -> Result<()> {
     let bg_block = Block::default().style(app.theme.ui.bg_style());
     f.render_widget(bg_block, f.area());
 
+    // With `nav: tabbar` a one-row tab strip replaces the side panel, which
+    // only shows while focused (Tab) for tree navigation
+    let tab_bar = app.nav == NavLayout::Tabbar;
+    let show_panel = !tab_bar || app.input.mode == InputMode::SourcePanel;
+    let (tab_bar_area, body_area) = if tab_bar {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(1)])
+            .split(f.area());
+        (chunks[0], chunks[1])
+    } else {
+        (Rect::default(), f.area())
+    };
+
     // Main horizontal layout: side panel + content area
+    let panel_width = if show_panel { app.panel.width } else { 0 };
     let main_chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Length(app.panel.width), Constraint::Min(1)])
-        .split(f.area());
+        .constraints([Constraint::Length(panel_width), Constraint::Min(1)])
+        .split(body_area);
 
     // Render side panel with tabs
-    let (sources_area, source_overflow) = side_panel::render_side_panel(f, main_chunks[0], app);
+    let (sources_area, source_overflow) = if show_panel {
+        side_panel::render_side_panel(f, main_chunks[0], app)
+    } else {
+        (Rect::default(), None)
+    };
+    if tab_bar {
+        tab_bar::render_tab_bar(f, tab_bar_area, app);
+    }
 
     // Content area layout
     let content_chunks = Layout::default()
@@ -31,17 +55,25 @@ pub fn render(f: &mut Frame, app: &mut App) -> Result<()> {
         .constraints([
             Constraint::Min(1),    // Main content
             Constraint::Length(4), // Status bar (2 lines + borders)
-            Constraint::Length(if app.is_entering_filter() || app.is_entering_line_jump() {
-                3
-            } else {
-                0
-            }), // Input prompt
+            Constraint::Length(
+                if app.is_entering_filter()
+                    || app.is_entering_line_jump()
+                    || app.is_entering_command()
+                {
+                    3
+                } else {
+                    0
+                },
+            ), // Input prompt
         ])
         .split(main_chunks[1]);
 
+    let log_area = cap_width(content_chunks[0], app.max_content_width, app.center_content);
+
     // Store layout areas for mouse click hit testing
     app.layout.side_panel_sources = rect_to_layout(sources_area);
-    app.layout.log_view = rect_to_layout(content_chunks[0]);
+    app.layout.log_view = rect_to_layout(log_area);
+    app.layout.tab_bar = rect_to_layout(tab_bar_area);
 
     if app.active_tab().source.mode == ViewMode::Aggregation {
         let ui = &app.theme.ui;
@@ -52,9 +84,9 @@ pub fn render(f: &mut Frame, app: &mut App) -> Result<()> {
         } else {
             &mut app.tab_mgr.tabs[app.tab_mgr.active]
         };
-        aggregation_view::render_aggregation_view(f, content_chunks[0], tab, ui);
+        aggregation_view::render_aggregation_view(f, log_area, tab, ui);
     } else {
-        log_view::render_log_view(f, content_chunks[0], app)?;
+        log_view::render_log_view(f, log_area, app)?;
     }
 
     status_bar::render_status_bar(f, content_chunks[1], app);
@@ -63,6 +95,8 @@ pub fn render(f: &mut Frame, app: &mut App) -> Result<()> {
         status_bar::render_filter_input_prompt(f, content_chunks[2], app);
     } else if app.is_entering_line_jump() {
         status_bar::render_line_jump_prompt(f, content_chunks[2], app);
+    } else if app.is_entering_command() {
+        status_bar::render_command_prompt(f, content_chunks[2], app);
     }
 
     // Render source overflow overlay on top of log view
@@ -73,7 +107,19 @@ pub fn render(f: &mut Frame, app: &mut App) -> Result<()> {
 
     // Render help overlay on top of everything if active
     if let Some(scroll_offset) = app.help_scroll_offset {
-        help::render_help_overlay(f, f.area(), scroll_offset, &app.theme.ui);
+        help::render_help_overlay(
+            f,
+            f.area(),
+            scroll_offset,
+            app.help_search.as_ref(),
+            &app.keymap,
+            &app.theme.ui,
+        );
+    }
+
+    // Render errors overlay
+    if let Some(scroll_offset) = app.errors_scroll_offset {
+        help::render_errors_overlay(f, f.area(), &app.error_log, scroll_offset, &app.theme.ui);
     }
 
     // Render close confirmation dialog on top of everything if active
@@ -81,6 +127,16 @@ pub fn render(f: &mut Frame, app: &mut App) -> Result<()> {
         help::render_confirm_close_dialog(f, f.area(), app);
     }
 
+    // Render line context menu
+    if let Some(ref menu) = app.context_menu {
+        help::render_context_menu(f, menu, app.layout.log_view, &app.theme.ui);
+    }
+
+    // Render quick filter picker
+    if let Some(ref picker) = app.field_picker {
+        help::render_field_picker(f, picker, app.layout.log_view, &app.theme.ui);
+    }
+
     // Render warning popup
     if let Some(ref msg) = app.warning_popup {
         help::render_warning_popup(f, f.area(), msg, &app.theme.ui);
@@ -89,6 +145,21 @@ pub fn render(f: &mut Frame, app: &mut App) -> Result<()> {
     Ok(())
 }
 
+/// Narrow `area` to at most `max_width` columns, centered or left-aligned;
+/// the margins keep the background.
+fn cap_width(area: Rect, max_width: Option<usize>, center: bool) -> Rect {
+    let Some(max_width) = max_width else {
+        return area;
+    };
+    let width = area.width.min(u16::try_from(max_width).unwrap_or(u16::MAX));
+    let margin = if center { (area.width - width) / 2 } else { 0 };
+    Rect {
+        x: area.x + margin,
+        width,
+        ..area
+    }
+}
+
 fn rect_to_layout(r: Rect) -> LayoutRect {
     LayoutRect {
         x: r.x,
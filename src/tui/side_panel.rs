@@ -1,5 +1,5 @@
 use crate::app::{App, InputMode, SourceType, TabState, TreeSelection};
-use crate::source::SourceStatus;
+use crate::source::{format_count, SourceStatus};
 use crate::theme::UiColors;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -8,8 +8,12 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, Paragraph},
     Frame,
 };
+use std::time::Duration;
 use unicode_width::UnicodeWidthStr;
 
+/// How long a source that just ended stays emphasized in the list
+const JUST_ENDED_SECS: u64 = 300;
+
 pub(super) fn render_side_panel(
     f: &mut Frame,
     area: Rect,
@@ -18,7 +22,7 @@ pub(super) fn render_side_panel(
     let ui = &app.theme.ui;
     // Stats panel height: 2 (borders) + 1 (line count) + 1 if filtered + 1 if index + severity rows
     let tab = app.active_tab();
-    let is_filtered = tab.source.filter.pattern.is_some();
+    let is_filtered = tab.source.filter.is_filtering();
     let has_index = tab.source.index_size.is_some();
     let severity_rows = tab
         .source
@@ -33,8 +37,12 @@ pub(super) fn render_side_panel(
                 .count() as u16
         })
         .unwrap_or(0);
-    let stats_height =
-        3 + if is_filtered { 1 } else { 0 } + if has_index { 1 } else { 0 } + severity_rows;
+    let has_ended = tab.source.ended_at.is_some();
+    let stats_height = 3
+        + if is_filtered { 1 } else { 0 }
+        + if has_index { 1 } else { 0 }
+        + if has_ended { 1 } else { 0 }
+        + severity_rows;
 
     // Split side panel into sources list and stats
     let chunks = Layout::default()
@@ -73,7 +81,7 @@ fn build_source_line(
         line.spans
             .push(Span::styled(" ⟳", Style::default().fg(ui.highlight)));
     }
-    if tab.source.filter.pattern.is_some() {
+    if tab.source.filter.is_filtering() {
         line.spans
             .push(Span::styled(" *", Style::default().fg(ui.accent)));
     }
@@ -82,9 +90,18 @@ fn build_source_line(
             .push(Span::styled(" F", Style::default().fg(ui.positive)));
     }
     if let Some(status) = tab.source.source_status {
-        let (status_ind, color) = match status {
-            SourceStatus::Active => ("●", ui.positive),
-            SourceStatus::Ended => ("○", ui.muted),
+        // A source that just ended stands out, with how long ago
+        let just_ended = tab
+            .source
+            .ended_at
+            .map(|at| at.elapsed())
+            .filter(|elapsed| elapsed.as_secs() < JUST_ENDED_SECS);
+        let (status_ind, color) = match (status, just_ended) {
+            (SourceStatus::Active, _) => ("●".to_string(), ui.positive),
+            (SourceStatus::Ended, Some(elapsed)) => {
+                (format!("○ {}", format_ago(elapsed)), ui.negative)
+            }
+            (SourceStatus::Ended, None) => ("○".to_string(), ui.muted),
         };
         line.spans.push(Span::styled(
             format!(" {}", status_ind),
@@ -96,15 +113,12 @@ fn build_source_line(
 }
 
 /// Format metadata string for a source (line count and optional file size)
-fn format_source_meta(tab: &TabState) -> String {
+fn format_source_meta(tab: &TabState, compact: bool) -> String {
+    let lines = format_count(tab.source.total_lines, compact);
     if let Some(size) = tab.source.file_size {
-        format!(
-            " {} \u{00b7} {}",
-            format_count(tab.source.total_lines),
-            format_file_size(size)
-        )
+        format!(" {} \u{00b7} {}", lines, format_file_size(size))
     } else {
-        format!(" {}", format_count(tab.source.total_lines))
+        format!(" {}", lines)
     }
 }
 
@@ -193,7 +207,7 @@ fn render_sources_list(
                     .count();
                 let meta = format!(
                     " {} \u{00b7} {}src",
-                    format_count(combined.source.total_lines),
+                    format_count(combined.source.total_lines, app.compact_numbers),
                     source_count
                 );
                 let used_width: usize = line.spans.iter().map(|s| s.content.width()).sum();
@@ -277,7 +291,7 @@ fn render_sources_list(
                 );
 
                 // Inline metadata (line count · file size) - show whatever fits
-                let meta = format_source_meta(tab);
+                let meta = format_source_meta(tab, app.compact_numbers);
                 let used_width: usize = line.spans.iter().map(|s| s.content.width()).sum();
                 let panel_inner = (area.width as usize).saturating_sub(2); // borders
                 let remaining = panel_inner.saturating_sub(used_width);
@@ -375,7 +389,7 @@ fn render_stats_panel(f: &mut Frame, area: Rect, app: &App, ui: &UiColors) {
 
     let total_lines = tab.source.total_lines;
     let filtered_lines = tab.source.line_indices.len();
-    let is_filtered = tab.source.filter.pattern.is_some();
+    let is_filtered = tab.source.filter.is_filtering();
     let is_loading = tab.stream_receiver.is_some();
 
     let mut stats_text = Vec::new();
@@ -388,27 +402,33 @@ fn render_stats_panel(f: &mut Frame, area: Rect, app: &App, ui: &UiColors) {
         )]));
     }
 
-    // Show line counts
+    // Show line counts (compact ones followed by the exact number)
+    let count_spans = |count: usize, color| {
+        let shown = format_count(count, app.compact_numbers);
+        let exact = count.to_string();
+        let mut spans = vec![Span::styled(shown.clone(), Style::default().fg(color))];
+        if shown != exact {
+            spans.push(Span::styled(
+                format!(" ({})", exact),
+                Style::default().fg(ui.muted),
+            ));
+        }
+        spans
+    };
     if is_filtered {
-        stats_text.push(Line::from(vec![
-            Span::raw(" Lines:    "),
-            Span::styled(format!("{}", total_lines), Style::default().fg(ui.fg)),
-        ]));
-        stats_text.push(Line::from(vec![
-            Span::raw(" Filtered: "),
-            Span::styled(
-                format!("{}", filtered_lines),
-                Style::default().fg(ui.accent),
-            ),
-        ]));
+        let mut lines = vec![Span::raw(" Lines:    ")];
+        lines.extend(count_spans(total_lines, ui.fg));
+        stats_text.push(Line::from(lines));
+        let mut filtered = vec![Span::raw(" Filtered: ")];
+        filtered.extend(count_spans(filtered_lines, ui.accent));
+        stats_text.push(Line::from(filtered));
     } else {
-        stats_text.push(Line::from(vec![
-            Span::raw(" Lines: "),
-            Span::styled(format!("{}", total_lines), Style::default().fg(ui.fg)),
-        ]));
+        let mut lines = vec![Span::raw(" Lines: ")];
+        lines.extend(count_spans(total_lines, ui.fg));
+        stats_text.push(Line::from(lines));
     }
 
-    // Show index size if available, or warning if broken
+    // Show index size if available, warning if broken, or why indexing is off
     if let Some(ref warning) = tab.source.index_warning {
         stats_text.push(Line::from(vec![Span::styled(
             format!(" {}", warning),
@@ -419,6 +439,22 @@ fn render_stats_panel(f: &mut Frame, area: Rect, app: &App, ui: &UiColors) {
             Span::raw(" Index: "),
             Span::styled(format_file_size(index_size), Style::default().fg(ui.muted)),
         ]));
+    } else if tab.source.index_disabled.is_some() {
+        stats_text.push(Line::from(vec![
+            Span::raw(" Index: "),
+            Span::styled("disabled (not writable)", Style::default().fg(ui.muted)),
+        ]));
+    }
+
+    // Show when a captured source stopped
+    if let Some(ended_at) = tab.source.ended_at {
+        stats_text.push(Line::from(vec![
+            Span::raw(" Ended: "),
+            Span::styled(
+                format!("{} ago", format_ago(ended_at.elapsed())),
+                Style::default().fg(ui.negative),
+            ),
+        ]));
     }
 
     // Show line ingestion rate
@@ -463,7 +499,7 @@ fn render_stats_panel(f: &mut Frame, area: Rect, app: &App, ui: &UiColors) {
                 let empty = bar_max as usize - filled;
                 let bar_filled: String = "\u{2588}".repeat(filled);
                 let bar_empty: String = "\u{2591}".repeat(empty);
-                let count_str = format_count(count as usize);
+                let count_str = format_count(count as usize, app.compact_numbers);
 
                 stats_text.push(Line::from(vec![
                     Span::raw(format!(" {:<5} ", label)),
@@ -531,31 +567,12 @@ fn format_rate(lines_per_sec: f64) -> (f64, &'static str) {
     }
 }
 
-/// Format a line count in compact human-readable form.
-/// Examples: `0`, `999`, `1.2K`, `60M`, `1.3B`
-pub(super) fn format_count(count: usize) -> String {
-    if count >= 1_000_000_000 {
-        let val = count as f64 / 1_000_000_000.0;
-        if val >= 10.0 {
-            format!("{}Bn", val as u64)
-        } else {
-            format!("{:.1}Bn", val)
-        }
-    } else if count >= 1_000_000 {
-        let val = count as f64 / 1_000_000.0;
-        if val >= 10.0 {
-            format!("{}M", val as u64)
-        } else {
-            format!("{:.1}M", val)
-        }
-    } else if count >= 1_000 {
-        let val = count as f64 / 1_000.0;
-        if val >= 10.0 {
-            format!("{}K", val as u64)
-        } else {
-            format!("{:.1}K", val)
-        }
-    } else {
-        format!("{}", count)
+/// Format an elapsed time in its largest whole unit: `45s`, `2m`, `1h`, `3d`.
+fn format_ago(elapsed: Duration) -> String {
+    match elapsed.as_secs() {
+        secs @ 0..60 => format!("{}s", secs),
+        secs @ 60..3600 => format!("{}m", secs / 60),
+        secs @ 3600..86400 => format!("{}h", secs / 3600),
+        secs => format!("{}d", secs / 86400),
     }
 }
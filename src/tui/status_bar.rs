@@ -1,4 +1,6 @@
-use crate::app::{App, FilterState, ViewMode};
+use crate::app::{App, FilterState, RawMode, ViewMode};
+use crate::index::flags::Severity;
+use crate::source::format_count;
 use ratatui::{
     layout::Rect,
     style::{Modifier, Style},
@@ -7,23 +9,64 @@ use ratatui::{
     Frame,
 };
 
+/// How long a background tab alert stays in the status bar
+const ALERT_TOAST_SECS: u64 = 10;
+
+/// The view must hold this many screens of lines before the status bar
+/// names the rows on screen
+const WINDOW_HINT_SCREENS: usize = 2;
+
+/// Status bar segment for the rows on screen when they are a small window
+/// of the view, e.g. " (showing 1201-1240)".
+fn window_extent(scroll: usize, height: usize, count: usize) -> String {
+    if height == 0 || count <= height * WINDOW_HINT_SCREENS {
+        return String::new();
+    }
+    format!(" (showing {}-{})", scroll + 1, (scroll + height).min(count))
+}
+
+/// Status bar segment for hidden severities, e.g. " | HIDE TRACE,DEBUG".
+fn hidden_levels(hidden: &[Severity]) -> String {
+    let labels: Vec<String> = hidden
+        .iter()
+        .filter_map(|s| s.label())
+        .map(str::to_uppercase)
+        .collect();
+    if labels.is_empty() {
+        String::new()
+    } else {
+        format!(" | HIDE {}", labels.join(","))
+    }
+}
+
 pub(super) fn render_status_bar(f: &mut Frame, area: Rect, app: &App) {
     let ui = &app.theme.ui;
     let tab = app.active_tab();
 
     let status_text = format!(
-        " Line {}/{} | Total: {} | Mode: {} {}{}{}{}{}",
+        " Line {}/{}{} | Total: {} | Mode: {} {}{}{}{}{}{}{}{}{}{}{}{}",
         tab.selected_line + 1,
         tab.visible_line_count(),
-        tab.source.total_lines,
+        window_extent(
+            tab.viewport.scroll_position(),
+            tab.viewport.height(),
+            tab.visible_line_count()
+        ),
+        format_count(tab.source.total_lines, app.compact_numbers),
         match tab.source.mode {
             ViewMode::Normal => "Normal",
             ViewMode::Filtered => "Filtered",
             ViewMode::Aggregation => "Aggregation",
         },
         match &tab.source.filter.state {
+            _ if tab.source.unfiltered_new_lines() > 0 => format!(
+                "| Matches: {} (+{} new, re-filtering…)",
+                format_count(tab.source.line_indices.len(), app.compact_numbers),
+                tab.source.unfiltered_new_lines()
+            ),
             FilterState::Inactive => String::new(),
             FilterState::Processing { lines_processed } => {
+                #[allow(clippy::manual_checked_ops)]
                 let percent = if tab.source.total_lines > 0 {
                     (lines_processed * 100) / tab.source.total_lines
                 } else {
@@ -31,20 +74,51 @@ pub(super) fn render_status_bar(f: &mut Frame, area: Rect, app: &App) {
                 };
                 format!("| Filtering: {}%", percent)
             }
-            FilterState::Complete { matches } => format!("| Matches: {}", matches),
+            FilterState::Complete { matches } => {
+                format!("| Matches: {}", format_count(*matches, app.compact_numbers))
+            }
+        },
+        match tab.source.filter.min_severity.and_then(|s| s.label()) {
+            Some(level) => format!(" | {}+", level.to_uppercase()),
+            None => String::new(),
         },
+        hidden_levels(&tab.source.filter.hidden_severities),
         if tab.source.follow_mode {
             " | FOLLOW"
         } else {
             ""
         },
-        if tab.source.raw_mode { " | RAW" } else { "" },
+        match tab.source.raw_mode {
+            RawMode::Off => "",
+            RawMode::Ansi => " | RAW",
+            RawMode::Plain => " | RAW (no ANSI)",
+        },
         if tab.source.line_wrap { " | WRAP" } else { "" },
         if tab.source.show_timestamps {
             " | TS"
         } else {
             ""
-        }
+        },
+        if tab.viewport.is_reversed() {
+            " | NEWEST FIRST"
+        } else {
+            ""
+        },
+        match tab.source.since_line {
+            Some(line) => format!(" | SINCE L{}", line + 1),
+            None => String::new(),
+        },
+        match &tab.source.diff {
+            Some(diff) => format!(" | DIFF {} changed", diff.changed.len()),
+            None => String::new(),
+        },
+        match &tab.tee {
+            Some(tee) if tee.error().is_some() => " | SAVE FAILED",
+            Some(tee) if tee.is_active() => " | SAVING",
+            Some(_) => " | SAVED",
+            None => "",
+        },
+        if app.low_resource { " | LOW POWER" } else { "" }
     );
 
     let show_status_msg = app
@@ -52,13 +126,19 @@ pub(super) fn render_status_bar(f: &mut Frame, area: Rect, app: &App) {
         .as_ref()
         .is_some_and(|(_, t)| t.elapsed().as_secs() < 3);
 
+    // Toast for a fresh alert from a tab other than the one on screen
+    let alert = app
+        .tab_alert
+        .as_ref()
+        .filter(|alert| alert.at.elapsed().as_secs() < ALERT_TOAST_SECS && !alert.is_from(tab));
+
     let bottom_line = if tab.source.mode == ViewMode::Aggregation {
         if let Some(ref result) = tab.source.aggregation_result {
             Line::from(vec![Span::styled(
                 format!(
                     " Row {}/{} | Enter: drill down | Esc: back | / - re-filter",
                     tab.aggregation_view.selected_row + 1,
-                    result.groups.len()
+                    result.rows().len()
                 ),
                 Style::default().fg(ui.highlight),
             )])
@@ -81,6 +161,20 @@ pub(super) fn render_status_bar(f: &mut Frame, area: Rect, app: &App) {
             format!(" {}", msg),
             Style::default().fg(ui.positive),
         )])
+    } else if let Some(alert) = alert {
+        let color = match alert.severity {
+            Severity::Fatal => ui.severity_fatal,
+            Severity::Error => ui.severity_error,
+            _ => ui.severity_warn,
+        };
+        Line::from(vec![Span::styled(
+            format!(
+                " {}: new {} (N to jump)",
+                alert.source,
+                alert.severity.label().unwrap_or("line")
+            ),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )])
     } else {
         let help_text = if app.tab_count() > 1 {
             " Tab/Shift+Tab - Switch | 1-9 - Select | ? - Help"
@@ -108,11 +202,17 @@ pub(super) fn render_status_bar(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(paragraph, area);
 }
 
+/// Filter prompt spinner, one frame per render
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
 pub(super) fn render_filter_input_prompt(f: &mut Frame, area: Rect, app: &App) {
     let ui = &app.theme.ui;
     let input = app.get_input();
 
-    let label = app.filter.current_mode.prompt_label();
+    let label = app
+        .filter
+        .current_mode
+        .prompt_label_with_smart_case(app.filter.smart_case);
     let input_text = format!("{}: {}", label, input);
 
     // Determine border color based on mode and validation state
@@ -134,10 +234,18 @@ pub(super) fn render_filter_input_prompt(f: &mut Frame, area: Rect, app: &App) {
     } else {
         ("Plain", "Regex")
     };
-    let title = format!(
-        "[{}] Tab\u{2192}{} | Enter: submit | Esc: clear",
-        mode_label, next_mode
-    );
+    let title = if app.is_filter_processing() {
+        let spinner = SPINNER_FRAMES[app.frame % SPINNER_FRAMES.len()];
+        format!(
+            "[{}] {} Scanning… | Enter: submit | Esc: stop scan",
+            mode_label, spinner
+        )
+    } else {
+        format!(
+            "[{}] Tab\u{2192}{} | Enter: submit | Esc: clear",
+            mode_label, next_mode
+        )
+    };
 
     let input = Paragraph::new(input_text)
         .style(ui.bg_style().fg(ui.primary))
@@ -177,3 +285,22 @@ pub(super) fn render_line_jump_prompt(f: &mut Frame, area: Rect, app: &App) {
     let chars_before_cursor = app.get_input()[..app.get_cursor_position()].chars().count() as u16;
     f.set_cursor_position((area.x + 2 + chars_before_cursor, area.y + 1));
 }
+
+pub(super) fn render_command_prompt(f: &mut Frame, area: Rect, app: &App) {
+    let ui = &app.theme.ui;
+    let input_text = format!(";{}", app.get_input());
+
+    let input = Paragraph::new(input_text)
+        .style(ui.bg_style().fg(ui.accent))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Command (Enter to run, Esc to cancel, ? lists commands)")
+                .style(ui.bg_style()),
+        );
+
+    f.render_widget(input, area);
+
+    let chars_before_cursor = app.get_input()[..app.get_cursor_position()].chars().count() as u16;
+    f.set_cursor_position((area.x + 2 + chars_before_cursor, area.y + 1));
+}
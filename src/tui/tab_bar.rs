@@ -0,0 +1,49 @@
+use crate::app::tab_bar::{self, TabBarTarget};
+use crate::app::App;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+/// Render the one-row strip of source tabs (`nav: tabbar`).
+pub(super) fn render_tab_bar(f: &mut Frame, area: Rect, app: &App) {
+    let ui = &app.theme.ui;
+    let mut spans = Vec::new();
+    let mut x = 0u16;
+
+    for item in tab_bar::layout(&app.tab_mgr, area.width) {
+        if item.x > x {
+            let gap = (item.x - x) as usize;
+            let fill = if item.group_start {
+                format!("{}│", " ".repeat(gap - 1))
+            } else {
+                " ".repeat(gap)
+            };
+            spans.push(Span::styled(fill, Style::default().fg(ui.muted)));
+        }
+
+        let disabled = match item.target {
+            TabBarTarget::Tab(idx) => app.tab_mgr.tabs[idx].source.disabled,
+            TabBarTarget::Combined(_) => false,
+        };
+        let style = if item.active {
+            Style::default()
+                .bg(ui.selection_bg)
+                .fg(ui.primary)
+                .add_modifier(Modifier::BOLD)
+        } else if disabled {
+            Style::default().fg(ui.muted)
+        } else if matches!(item.target, TabBarTarget::Combined(_)) {
+            Style::default().fg(ui.highlight)
+        } else {
+            Style::default().fg(ui.fg)
+        };
+        spans.push(Span::styled(item.label, style));
+        x = item.x + item.width;
+    }
+
+    f.render_widget(Paragraph::new(Line::from(spans)).style(ui.bg_style()), area);
+}
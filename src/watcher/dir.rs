@@ -1,6 +1,7 @@
 //! Directory watcher for detecting new log files in the data directory.
 //!
-//! Uses the notify crate to watch ~/.config/lazytail/data/ for new .log files.
+//! Uses the notify crate to watch ~/.config/lazytail/data/ for new .log files,
+//! or a file's parent directory for that file to appear (`--wait`).
 
 use anyhow::{Context, Result};
 use notify::{
@@ -13,9 +14,9 @@ use std::sync::mpsc::{channel, Receiver, TryRecvError};
 /// Events from the directory watcher
 #[derive(Debug, Clone)]
 pub enum DirEvent {
-    /// A new .log file was created
+    /// A watched file was created
     NewFile(PathBuf),
-    /// A .log file was removed
+    /// A watched file was removed
     FileRemoved(PathBuf),
 }
 
@@ -30,8 +31,33 @@ impl DirectoryWatcher {
     ///
     /// Only notifies about .log files being created or removed.
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::with_filter(path.as_ref(), |path| {
+            path.extension().is_some_and(|ext| ext == "log")
+        })
+    }
+
+    /// Watch the parent directory of `file` for that one file being created
+    /// (or renamed into place) or removed. The file need not exist yet.
+    pub fn for_file<P: AsRef<Path>>(file: P) -> Result<Self> {
+        let file = file.as_ref();
+        let name = file
+            .file_name()
+            .context("Path has no file name")?
+            .to_os_string();
+        let dir = match file.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        Self::with_filter(dir, move |path| path.file_name() == Some(name.as_os_str()))
+    }
+
+    /// Watch `dir`, reporting created/removed files accepted by `filter`.
+    fn with_filter<F>(dir: &Path, filter: F) -> Result<Self>
+    where
+        F: Fn(&Path) -> bool + Send + 'static,
+    {
         let (tx, rx) = channel();
-        let path_buf = path.as_ref().to_path_buf();
+        let path_buf = dir.to_path_buf();
 
         let mut watcher = notify::recommended_watcher(move |res: Result<Event, _>| {
             let event = match res {
@@ -44,14 +70,14 @@ impl DirectoryWatcher {
             match event.kind {
                 EventKind::Create(CreateKind::File) | EventKind::Modify(ModifyKind::Name(_)) => {
                     for path in event.paths {
-                        if path.extension().is_some_and(|ext| ext == "log") {
+                        if filter(&path) {
                             let _ = tx.send(DirEvent::NewFile(path));
                         }
                     }
                 }
                 EventKind::Remove(RemoveKind::File) => {
                     for path in event.paths {
-                        if path.extension().is_some_and(|ext| ext == "log") {
+                        if filter(&path) {
                             let _ = tx.send(DirEvent::FileRemoved(path));
                         }
                     }
@@ -61,7 +87,7 @@ impl DirectoryWatcher {
         })
         .context("Failed to create directory watcher")?;
 
-        // Watch the directory (non-recursive since we only care about data/)
+        // Watch the directory (non-recursive since we only care about its files)
         watcher
             .watch(&path_buf, RecursiveMode::NonRecursive)
             .context("Failed to watch directory")?;
@@ -129,4 +155,22 @@ mod tests {
         let event = watcher.try_recv();
         assert!(event.is_none());
     }
+
+    #[test]
+    #[ignore] // Slow test - involves file system operations
+    fn test_file_watcher_reports_only_its_file() {
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("app.out");
+        let watcher = DirectoryWatcher::for_file(&target).unwrap();
+
+        thread::sleep(Duration::from_millis(100));
+        fs::write(temp.path().join("other.log"), "x").unwrap();
+        fs::write(&target, "x").unwrap();
+        thread::sleep(Duration::from_millis(200));
+
+        match watcher.try_recv() {
+            Some(DirEvent::NewFile(path)) => assert_eq!(path.file_name(), target.file_name()),
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
 }
@@ -1,302 +1,3 @@
-<!doctype html>
-<html lang="en">
-<head>
-  <meta charset="utf-8">
-  <meta name="viewport" content="width=device-width, initial-scale=1">
-  <title>LazyTail Web UI</title>
-  <style>
-    :root {
-      --bg: #0f1115;
-      --panel: #171a21;
-      --panel-2: #1c2029;
-      --border: #2a3140;
-      --text: #d8e0ef;
-      --muted: #96a1b8;
-      --accent: #67b3ff;
-      --ok: #5ac66f;
-      --warn: #f0bf5a;
-      --err: #ec6a5e;
-      --row-sel: #222a3a;
-      --mono: ui-monospace, SFMono-Regular, Menlo, Consolas, "Liberation Mono", monospace;
-      --sans: "Iosevka Aile", "IBM Plex Sans", "Segoe UI", system-ui, sans-serif;
-      --source-row-h: 34px;
-      --log-row-h: 22px;
-    }
-
-    * { box-sizing: border-box; }
-    html, body { margin: 0; padding: 0; height: 100%; background: var(--bg); color: var(--text); font-family: var(--sans); }
-
-    .app {
-      display: grid;
-      grid-template-columns: 280px 1fr;
-      height: 100vh;
-      min-height: 0;
-      gap: 0;
-    }
-
-    .panel {
-      border-right: 1px solid var(--border);
-      background: linear-gradient(180deg, var(--panel), #12151c);
-      display: grid;
-      grid-template-rows: auto 1fr auto;
-      min-height: 0;
-    }
-
-    .panel h1 {
-      margin: 0;
-      font-size: 14px;
-      font-weight: 700;
-      letter-spacing: 0.02em;
-      padding: 12px;
-      border-bottom: 1px solid var(--border);
-    }
-
-    .source-viewport {
-      position: relative;
-      overflow: auto;
-      min-height: 0;
-    }
-
-    .source-spacer {
-      position: relative;
-      width: 100%;
-      height: 0;
-    }
-
-    .source-rows {
-      position: absolute;
-      top: 0;
-      left: 0;
-      right: 0;
-    }
-
-    .source-row {
-      position: absolute;
-      left: 0;
-      right: 0;
-      height: var(--source-row-h);
-      display: grid;
-      grid-template-columns: 1fr auto;
-      align-items: center;
-      gap: 8px;
-      padding: 0 10px;
-      border-bottom: 1px solid rgba(255,255,255,0.03);
-      cursor: pointer;
-      user-select: none;
-    }
-
-    .source-row:hover { background: rgba(255,255,255,0.04); }
-    .source-row.selected { background: var(--row-sel); outline: 1px solid #31405f; }
-    .source-row.disabled { opacity: 0.55; }
-
-    .source-main {
-      display: flex;
-      gap: 8px;
-      align-items: baseline;
-      min-width: 0;
-    }
-
-    .source-name {
-      font-size: 13px;
-      font-weight: 650;
-      white-space: nowrap;
-      overflow: hidden;
-      text-overflow: ellipsis;
-    }
-
-    .source-meta {
-      font-size: 11px;
-      color: var(--muted);
-      text-transform: uppercase;
-      letter-spacing: 0.04em;
-      white-space: nowrap;
-    }
-
-    .source-count {
-      font-size: 12px;
-      color: var(--muted);
-      font-family: var(--mono);
-    }
-
-    .status-dot {
-      display: inline-block;
-      width: 7px;
-      height: 7px;
-      border-radius: 50%;
-      margin-right: 5px;
-    }
-
-    .dot-active { background: var(--ok); }
-    .dot-ended { background: #6e7480; }
-
-    .panel-footer {
-      border-top: 1px solid var(--border);
-      padding: 10px 12px;
-      font-size: 11px;
-      color: var(--muted);
-      line-height: 1.45;
-    }
-
-    .main {
-      display: grid;
-      grid-template-rows: auto auto 1fr;
-      min-height: 0;
-      background:
-        radial-gradient(circle at 95% 5%, rgba(103,179,255,0.10), transparent 28%),
-        linear-gradient(180deg, #11151d, #0d1016);
-    }
-
-    .toolbar {
-      display: flex;
-      align-items: center;
-      gap: 8px;
-      padding: 10px 12px;
-      border-bottom: 1px solid var(--border);
-      background: rgba(23, 27, 36, 0.88);
-      backdrop-filter: blur(4px);
-      flex-wrap: wrap;
-    }
-
-    .title {
-      min-width: 180px;
-      font-size: 13px;
-      font-weight: 650;
-      color: var(--text);
-      margin-right: 8px;
-    }
-
-    .toolbar input[type="text"],
-    .toolbar select {
-      background: #11151d;
-      color: var(--text);
-      border: 1px solid var(--border);
-      border-radius: 6px;
-      padding: 7px 9px;
-      font-size: 13px;
-    }
-
-    #filterInput {
-      min-width: 260px;
-      flex: 1 1 320px;
-      font-family: var(--mono);
-    }
-
-    .toolbar button {
-      border: 1px solid var(--border);
-      background: #1a2230;
-      color: var(--text);
-      border-radius: 6px;
-      padding: 7px 10px;
-      cursor: pointer;
-      font-size: 12px;
-      font-weight: 600;
-    }
-
-    .toolbar button:hover { background: #223047; }
-    .toolbar label { font-size: 12px; color: var(--muted); display: inline-flex; gap: 6px; align-items: center; }
-
-    .status {
-      display: grid;
-      grid-template-columns: 1fr auto;
-      gap: 8px;
-      align-items: center;
-      padding: 8px 12px;
-      border-bottom: 1px solid var(--border);
-      background: rgba(12, 15, 22, 0.9);
-      font-size: 12px;
-      color: var(--muted);
-      min-height: 36px;
-    }
-
-    #statusText { font-family: var(--mono); white-space: nowrap; overflow: hidden; text-overflow: ellipsis; }
-    #errorText { color: var(--err); font-weight: 600; }
-
-    .log-viewport {
-      position: relative;
-      overflow: auto;
-      min-height: 0;
-      font-family: var(--mono);
-      font-size: 12px;
-      line-height: var(--log-row-h);
-      background: rgba(8, 10, 14, 0.75);
-    }
-
-    .log-spacer { position: relative; width: 100%; height: 0; }
-    .log-rows { position: absolute; top: 0; left: 0; right: 0; }
-
-    .log-row {
-      position: absolute;
-      left: 0;
-      right: 0;
-      height: var(--log-row-h);
-      display: grid;
-      grid-template-columns: 92px 1fr;
-      gap: 10px;
-      align-items: center;
-      padding: 0 12px;
-      white-space: nowrap;
-      border-bottom: 1px solid rgba(255,255,255,0.02);
-    }
-
-    .ln {
-      color: #8092b5;
-      text-align: right;
-      user-select: none;
-    }
-
-    .content {
-      color: #dae4ff;
-      overflow: hidden;
-      text-overflow: ellipsis;
-      white-space: pre;
-    }
-
-    @media (max-width: 880px) {
-      .app { grid-template-columns: 220px 1fr; }
-      #filterInput { min-width: 160px; }
-      .log-row { grid-template-columns: 74px 1fr; }
-    }
-  </style>
-</head>
-<body>
-<div class="app">
-  <aside class="panel">
-    <h1>Sources</h1>
-    <div class="source-viewport" id="sourceViewport">
-      <div class="source-spacer" id="sourceSpacer"></div>
-      <div class="source-rows" id="sourceRows"></div>
-    </div>
-    <div class="panel-footer" id="sourceFooter">Loading sources...</div>
-  </aside>
-
-  <section class="main">
-    <div class="toolbar">
-      <div class="title" id="selectedTitle">No source selected</div>
-      <input id="filterInput" type="text" placeholder="Type to filter (plain, regex, or query syntax: json | level == \"error\")">
-      <select id="modeSelect">
-        <option value="plain">Plain</option>
-        <option value="regex">Regex</option>
-      </select>
-      <label><input id="caseCheckbox" type="checkbox">Case</label>
-      <label><input id="followCheckbox" type="checkbox" checked>Follow</label>
-      <button id="clearBtn" type="button">Clear</button>
-      <button id="closeBtn" type="button">Close Tab</button>
-      <button id="deleteBtn" type="button">Delete Ended</button>
-    </div>
-
-    <div class="status">
-      <div id="statusText">Ready</div>
-      <div id="errorText"></div>
-    </div>
-
-    <div class="log-viewport" id="logViewport">
-      <div class="log-spacer" id="logSpacer"></div>
-      <div class="log-rows" id="logRows"></div>
-    </div>
-  </section>
-</div>
-
-<script>
 (() => {
   const SOURCE_ROW_HEIGHT = 34;
   const LOG_ROW_HEIGHT = 22;
@@ -316,6 +17,7 @@
   const selectedTitle = document.getElementById('selectedTitle');
   const statusText = document.getElementById('statusText');
   const errorText = document.getElementById('errorText');
+  const extentText = document.getElementById('extentText');
 
   const filterInput = document.getElementById('filterInput');
   const modeSelect = document.getElementById('modeSelect');
@@ -324,6 +26,9 @@
   const clearBtn = document.getElementById('clearBtn');
   const closeBtn = document.getElementById('closeBtn');
   const deleteBtn = document.getElementById('deleteBtn');
+  const searchAllBtn = document.getElementById('searchAllBtn');
+  const searchResults = document.getElementById('searchResults');
+  const copyLinkBtn = document.getElementById('copyLinkBtn');
 
   const state = {
     revision: 0,
@@ -333,8 +38,11 @@
     visibleRows: [],
     totalVisible: 0,
     totalLines: 0,
+    windowed: false,
+    filtered: false,
     sourceReq: 0,
     linesReq: 0,
+    searchReq: 0,
     filterDebounce: null,
     eventLoopRunning: false,
     eventAbort: null,
@@ -348,8 +56,79 @@
     syncInFlight: false,
     syncQueued: false,
     syncForce: false,
+    // View restored from the URL: its filter rides on the first lines fetch
+    urlView: parseViewHash(),
+    urlWriteTimer: null,
   };
 
+  // Shareable view state: #source=<id>&name=<name>&pattern=...&mode=...&case=1&offset=<row>
+  function parseViewHash() {
+    const params = new URLSearchParams(location.hash.slice(1));
+    if (!params.has('source') && !params.has('name')) return null;
+    return {
+      source: params.has('source') ? Number(params.get('source')) : null,
+      name: params.get('name'),
+      pattern: params.get('pattern') || '',
+      mode: params.get('mode') || 'plain',
+      caseSensitive: params.get('case') === '1',
+      offset: Number(params.get('offset')) || 0,
+    };
+  }
+
+  function writeViewToUrl() {
+    const source = selectedSourceObj();
+    if (!source) return;
+    const params = new URLSearchParams();
+    params.set('source', String(source.id));
+    params.set('name', source.name);
+    if (filterInput.value) {
+      params.set('pattern', filterInput.value);
+      params.set('mode', looksLikeQuery(filterInput.value) ? 'query' : (modeSelect.value || 'plain'));
+      if (caseCheckbox.checked) params.set('case', '1');
+    }
+    const offset = Math.floor(logViewport.scrollTop / LOG_ROW_HEIGHT);
+    if (offset > 0) params.set('offset', String(offset));
+    history.replaceState(null, '', `#${params}`);
+  }
+
+  function scheduleUrlWrite() {
+    if (state.urlView) return;
+    clearTimeout(state.urlWriteTimer);
+    state.urlWriteTimer = setTimeout(writeViewToUrl, 300);
+  }
+
+  // Pick the linked source: same id and name, else same name, else same id
+  function restoreUrlView() {
+    const view = state.urlView;
+    if (!view) return;
+    const source = state.sources.find(s => s.id === view.source && (!view.name || s.name === view.name))
+      || state.sources.find(s => view.name && s.name === view.name)
+      || state.sources.find(s => s.id === view.source);
+    if (!source) {
+      state.urlView = null;
+      setError('Linked source is no longer open');
+      return;
+    }
+    selectSource(source.id);
+    if (view.pattern) {
+      filterInput.value = view.pattern;
+      modeSelect.value = view.mode === 'regex' ? 'regex' : 'plain';
+      caseCheckbox.checked = view.caseSensitive;
+    }
+  }
+
+  async function copyViewLink() {
+    writeViewToUrl();
+    const url = location.href;
+    try {
+      await navigator.clipboard.writeText(url);
+      statusText.textContent = 'Link copied';
+    } catch (_) {
+      // Clipboard needs a secure context; let the user copy it by hand
+      window.prompt('Copy this link', url);
+    }
+  }
+
   function setError(message) {
     errorText.textContent = message || '';
   }
@@ -372,11 +151,13 @@
   function sourceStatusLabel(source) {
     const mode = source.filter_mode === 'regex' ? 'regex' : 'plain';
     const caseLabel = source.case_sensitive ? 'Aa' : 'aa';
-    const statePart = source.filter_state.kind === 'processing'
-      ? `filtering ${source.filter_state.lines_processed}...`
-      : source.filter_state.kind === 'complete'
-        ? `${source.filter_state.matches} matches`
-        : 'idle';
+    const statePart = source.filter_pending_lines
+      ? `${source.visible_lines} matches (+${source.filter_pending_lines} new, re-filtering…)`
+      : source.filter_state.kind === 'processing'
+        ? `filtering ${source.filter_state.lines_processed}...`
+        : source.filter_state.kind === 'complete'
+          ? `${source.filter_state.matches} matches`
+          : 'idle';
     return `${source.visible_lines}/${source.total_lines} | ${mode} ${caseLabel} | ${statePart}`;
   }
 
@@ -408,7 +189,7 @@
           <div class="source-name">${statusDot}${escapeHtml(source.name)}</div>
           <div class="source-meta">${escapeHtml(source.category)}</div>
         </div>
-        <div class="source-count">${source.visible_lines}/${source.total_lines}</div>
+        <div class="source-count" title="${source.visible_lines}/${source.total_lines} lines">${source.visible_lines_label}/${source.total_lines_label}</div>
       `;
 
       row.addEventListener('click', () => selectSource(source.id));
@@ -454,6 +235,9 @@
     state.visibleRows = [];
     state.totalVisible = 0;
     state.totalLines = 0;
+    state.windowed = false;
+    state.filtered = false;
+    extentText.textContent = '';
     logViewport.scrollTop = 0;
 
     const src = selectedSourceObj();
@@ -466,6 +250,7 @@
     updateHeaderFromSelection();
     scheduleLinesRefresh(true);
     renderSourceVirtualList();
+    scheduleUrlWrite();
   }
 
   async function refreshSources() {
@@ -530,7 +315,7 @@
     const end = Math.max(start, Math.ceil((scrollTop + viewportHeight) / LOG_ROW_HEIGHT) + OVERSCAN);
 
     const cached = state.loadedWindow;
-    if (!force && cached.source === source.id && cached.revision === state.revision && start >= cached.start && end <= cached.end) {
+    if (!force && cached.source === source.id && cached.revision === source.revision && start >= cached.start && end <= cached.end) {
       renderLogVirtualRows(start, end);
       return;
     }
@@ -545,9 +330,17 @@
     state.linesController?.abort();
     state.linesController = controller;
 
+    const view = state.urlView;
+    let filterParams = '';
+    if (view && view.pattern) {
+      const params = new URLSearchParams({ pattern: view.pattern, mode: view.mode });
+      if (view.caseSensitive) params.set('case', '1');
+      filterParams = `&${params}`;
+    }
+
     try {
       const payload = await api(
-        `/api/lines?source=${source.id}&offset=${reqStart}&limit=${limit}`,
+        `/api/lines?source=${source.id}&offset=${reqStart}&limit=${limit}${filterParams}`,
         { signal: controller.signal }
       );
       if (reqId !== state.linesReq) return;
@@ -556,11 +349,13 @@
       state.visibleRows = payload.rows || [];
       state.totalVisible = payload.total_visible || 0;
       state.totalLines = payload.total_lines || 0;
+      state.windowed = !!payload.windowed;
+      state.filtered = !!payload.filtered;
       state.loadedWindow = {
         source: source.id,
         start: reqStart,
         end: reqStart + state.visibleRows.length,
-        revision: payload.revision,
+        revision: source.revision,
       };
 
       if (source.follow_mode) {
@@ -570,10 +365,21 @@
         }
       }
 
+      if (view && state.urlView === view) {
+        state.urlView = null;
+        if (view.offset > 0) {
+          logViewport.scrollTop = view.offset * LOG_ROW_HEIGHT;
+          scheduleLinesRefresh();
+        }
+        void refreshSources().catch(err => setError(err.message || String(err)));
+      }
+
       renderLogVirtualRows(start, end);
       updateHeaderFromSelection();
     } catch (err) {
       if (err?.name !== 'AbortError') {
+        // A bad linked filter shouldn't ride on every later fetch
+        if (view && state.urlView === view) state.urlView = null;
         throw err;
       }
     } finally {
@@ -607,10 +413,29 @@
       el.style.transform = `translateY(${i * LOG_ROW_HEIGHT}px)`;
       el.innerHTML = `
         <div class="ln">${row.line_number}</div>
-        <div class="content" title="${escapeHtml(row.content)}">${escapeHtml(row.content)}</div>
+        <div class="content" title="${escapeHtml(row.content)}">${rowContentHtml(row)}</div>
       `;
       logRows.appendChild(el);
     }
+    updateExtent();
+  }
+
+  // Say whether every line of the view is on screen or only a window of
+  // it, so lines scrolled out of the virtual list don't look missing
+  function updateExtent() {
+    const total = state.totalVisible;
+    const what = state.filtered ? 'matching lines' : 'lines';
+    const ofAll = state.filtered ? ` (of ${state.totalLines.toLocaleString()})` : '';
+    const first = Math.floor(logViewport.scrollTop / LOG_ROW_HEIGHT);
+    const last = Math.min(total, Math.ceil((logViewport.scrollTop + logViewport.clientHeight) / LOG_ROW_HEIGHT));
+
+    if (total === 0) {
+      extentText.textContent = state.filtered ? `No matching lines${ofAll}` : '';
+    } else if (!state.windowed && first === 0 && last >= total) {
+      extentText.textContent = `All ${total.toLocaleString()} ${what}${ofAll}`;
+    } else {
+      extentText.textContent = `Showing ${(first + 1).toLocaleString()}–${last.toLocaleString()} of ${total.toLocaleString()} ${what}${ofAll}`;
+    }
   }
 
   async function postFilter() {
@@ -636,6 +461,7 @@
 
     setError('');
     state.loadedWindow = { source: null, start: -1, end: -1, revision: -1 };
+    scheduleUrlWrite();
   }
 
   function scheduleFilter(delay = 500) {
@@ -650,6 +476,76 @@
     }, delay);
   }
 
+  async function searchAll(offset = 0) {
+    const pattern = filterInput.value;
+    if (!pattern) {
+      searchResults.hidden = true;
+      return;
+    }
+
+    // A newer search cancels this one on the server; drop its answer
+    const reqId = ++state.searchReq;
+    let res;
+    try {
+      res = await api('/api/search', {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify({
+          pattern,
+          mode: looksLikeQuery(pattern) ? 'query' : modeSelect.value,
+          case_sensitive: !!caseCheckbox.checked,
+          offset,
+        }),
+      });
+    } catch (err) {
+      if (reqId !== state.searchReq) return;
+      throw err;
+    }
+    if (reqId !== state.searchReq) return;
+
+    setError('');
+    renderSearchResults(res);
+  }
+
+  function renderSearchResults(res) {
+    const shown = res.sources.reduce((n, s) => n + s.rows.length, 0);
+    const range = shown ? `${res.offset + 1}-${res.offset + shown}` : '0';
+    let html = `
+      <div class="search-head">
+        <span>${res.total_matches} matches in ${res.sources.filter(s => s.match_count).length} sources (showing ${range})</span>
+        <span>
+          ${res.offset > 0 ? '<button type="button" data-offset="prev">Prev</button>' : ''}
+          ${res.next_offset != null ? '<button type="button" data-offset="next">Next</button>' : ''}
+          <button type="button" data-close>Close</button>
+        </span>
+      </div>`;
+    for (const group of res.sources) {
+      if (!group.match_count && !group.error) continue;
+      const detail = group.error ? escapeHtml(group.error) : `${group.match_count} matches`;
+      html += `<div class="search-group-title" data-source="${group.source}">${escapeHtml(group.name)} <span class="muted">${detail}</span></div>`;
+      for (const row of group.rows) {
+        html += `<div class="search-row"><span class="ln">${row.line_number}</span><span>${escapeHtml(row.content)}</span></div>`;
+      }
+    }
+    searchResults.innerHTML = html;
+    searchResults.hidden = false;
+
+    searchResults.querySelector('[data-close]').addEventListener('click', () => {
+      searchResults.hidden = true;
+    });
+    for (const btn of searchResults.querySelectorAll('[data-offset]')) {
+      const offset = btn.dataset.offset === 'next'
+        ? res.next_offset
+        : Math.max(0, res.offset - res.limit);
+      btn.addEventListener('click', () => {
+        void searchAll(offset).catch(err => setError(err.message || String(err)));
+      });
+    }
+    for (const title of searchResults.querySelectorAll('[data-source]')) {
+      title.addEventListener('click', () => selectSource(Number(title.dataset.source)));
+    }
+  }
+
   async function clearFilter() {
     const source = selectedSourceObj();
     if (!source) return;
@@ -663,6 +559,7 @@
     filterInput.value = '';
     setError('');
     state.loadedWindow = { source: null, start: -1, end: -1, revision: -1 };
+    scheduleUrlWrite();
     await refreshSources();
     scheduleLinesRefresh(true);
   }
@@ -695,7 +592,7 @@
       body: JSON.stringify({ source: source.id, delete_ended: !!deleteEnded }),
     });
 
-    const removedIndex = source.id;
+    const removedIndex = state.sources.findIndex(s => s.id === source.id);
     await refreshSources();
     if (state.sources.length === 0) {
       state.selectedSource = null;
@@ -703,7 +600,7 @@
       return;
     }
 
-    const nextIndex = Math.min(removedIndex, state.sources.length - 1);
+    const nextIndex = Math.min(Math.max(removedIndex, 0), state.sources.length - 1);
     selectSource(state.sources[nextIndex].id);
   }
 
@@ -863,12 +760,14 @@
           }
 
           const text = await response.text();
-          const match = text.match(/\bdata:\s*(\d+)/);
+          const match = text.match(/\bdata:\s*(\{.*\})/);
           if (match) {
-            const nextRevision = Number(match[1]);
-            if (Number.isFinite(nextRevision) && nextRevision > state.revision) {
-              state.revision = nextRevision;
-              scheduleSync(true);
+            let event = null;
+            try { event = JSON.parse(match[1]); } catch (_) {}
+            if (event && event.revision > state.revision) {
+              state.revision = event.revision;
+              // Only refetch lines when the source on screen changed
+              scheduleSync((event.sources || []).includes(state.selectedSource));
             }
           }
         } catch (err) {
@@ -883,6 +782,22 @@
     void run();
   }
 
+  // Source ANSI colors arrive as styled spans; uncolored rows as plain text
+  function rowContentHtml(row) {
+    if (!row.spans) return escapeHtml(row.content);
+    return row.spans.map((span) => {
+      const css = [];
+      if (span.fg) css.push(`color:${span.fg}`);
+      if (span.bg) css.push(`background:${span.bg}`);
+      if (span.bold) css.push('font-weight:bold');
+      if (span.dim) css.push('opacity:0.6');
+      if (span.italic) css.push('font-style:italic');
+      if (span.underline) css.push('text-decoration:underline');
+      const text = escapeHtml(span.text);
+      return css.length ? `<span style="${escapeHtml(css.join(';'))}">${text}</span>` : text;
+    }).join('');
+  }
+
   function escapeHtml(input) {
     return String(input)
       .replaceAll('&', '&amp;')
@@ -895,6 +810,7 @@
   sourceViewport.addEventListener('scroll', () => renderSourceVirtualList());
   logViewport.addEventListener('scroll', () => {
     scheduleLinesRefresh();
+    scheduleUrlWrite();
   });
 
   filterInput.addEventListener('input', () => scheduleFilter(500));
@@ -903,6 +819,12 @@
   clearBtn.addEventListener('click', () => {
     void clearFilter().catch(err => setError(err.message || String(err)));
   });
+  copyLinkBtn.addEventListener('click', () => {
+    void copyViewLink();
+  });
+  searchAllBtn.addEventListener('click', () => {
+    void searchAll().catch(err => setError(err.message || String(err)));
+  });
   closeBtn.addEventListener('click', () => {
     void closeSource(false).catch(err => setError(err.message || String(err)));
   });
@@ -945,9 +867,12 @@
 
     state.syncInFlight = true;
     try {
-      const prevRevision = state.revision;
+      const prevSource = selectedSourceObj();
       await refreshSources();
-      const forceLines = state.syncForce || state.revision !== prevRevision;
+      const source = selectedSourceObj();
+      const forceLines = state.syncForce
+        || source?.id !== prevSource?.id
+        || source?.revision !== prevSource?.revision;
       state.syncForce = false;
       scheduleLinesRefresh(forceLines);
     } catch (err) {
@@ -964,6 +889,7 @@
   async function boot() {
     try {
       await refreshSources();
+      restoreUrlView();
       scheduleLinesRefresh(true);
       setError('');
     } catch (err) {
@@ -982,6 +908,3 @@
 
   void boot();
 })();
-</script>
-</body>
-</html>
@@ -1,24 +1,27 @@
 //! HTTP request routing and response helpers for the web server.
 
 use crate::ansi::strip_ansi;
-use crate::app::TabState;
+use crate::app::{FilterState, TabState, ViewMode};
 use crate::filter::query;
 use crate::filter::regex_filter::RegexFilter;
+use crate::filter::FilterMode;
 use crate::filter_orchestrator::FilterOrchestrator;
 use crate::source::SourceStatus;
 
 use std::collections::HashMap;
 use std::fs;
 use std::io::Read;
-use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 use tiny_http::{Header, Method, Response, StatusCode};
 
+use super::search::{self, SearchRequest};
 use super::state::{lock_state, PendingEventRequest, WebState};
 use super::{
-    BasicResponse, BodyReadError, CloseSourceRequest, FilterRequest, FollowRequest, LineRow,
-    LinesResponse, SourceRequest, INDEX_HTML, MAX_LINES_PER_REQUEST, MAX_PENDING_EVENT_REQUESTS,
-    MAX_REQUEST_BODY_SIZE,
+    ansi_styled_spans, embedded_asset, BasicResponse, BodyReadError, CloseSourceRequest,
+    FilterRequest, FollowRequest, LineRow, LinesResponse, RevisionEvent, SourceRequest,
+    WebFilterMode, INDEX_HTML, INITIAL_FILTER_WAIT, MAX_LINES_PER_REQUEST,
+    MAX_PENDING_EVENT_REQUESTS, MAX_REQUEST_BODY_SIZE,
 };
 
 pub(super) fn handle_request(request: tiny_http::Request, shared: &Arc<Mutex<WebState>>) {
@@ -32,7 +35,21 @@ pub(super) fn handle_request(request: tiny_http::Request, shared: &Arc<Mutex<Web
             return;
         }
         (&Method::Get, "/favicon.ico") => {
-            respond_plain(request, 204, "");
+            let mut response = make_response(301, "text/plain; charset=utf-8", String::new());
+            if let Ok(header) = Header::from_bytes("Location", "/assets/favicon.svg") {
+                response = response.with_header(header);
+            }
+            let _ = request.respond(response);
+            return;
+        }
+        (&Method::Get, asset) if asset.starts_with("/assets/") => {
+            match embedded_asset(&asset["/assets/".len()..]) {
+                Some((content_type, body)) => {
+                    let response = make_response(200, content_type, body.to_string());
+                    let _ = request.respond(response);
+                }
+                None => respond_plain(request, 404, "Not found"),
+            }
             return;
         }
         (&Method::Get, "/api/sources") => {
@@ -51,8 +68,9 @@ pub(super) fn handle_request(request: tiny_http::Request, shared: &Arc<Mutex<Web
             let revision = state.revision;
 
             if revision > since {
+                let changed = state.changed_since(since);
                 drop(state);
-                respond_events(request, Some(revision));
+                respond_events(request, Some((revision, changed)));
             } else if state.pending_event_requests.len() >= MAX_PENDING_EVENT_REQUESTS {
                 drop(state);
                 respond_events_busy(request);
@@ -71,6 +89,7 @@ pub(super) fn handle_request(request: tiny_http::Request, shared: &Arc<Mutex<Web
             let limit = parse_usize_query(&query, "limit")
                 .unwrap_or(200)
                 .min(MAX_LINES_PER_REQUEST);
+            let plain = parse_usize_query(&query, "plain").is_some_and(|v| v != 0);
 
             let Some(source) = source else {
                 respond_json_error(request, 400, "Missing 'source' query parameter");
@@ -79,54 +98,41 @@ pub(super) fn handle_request(request: tiny_http::Request, shared: &Arc<Mutex<Web
 
             let mut state = lock_state(shared);
             state.tick();
-            let revision = state.revision;
-
-            let Some(tab) = state.tabs.get_mut(source) else {
-                respond_json_error(request, 404, "Source not found");
-                return;
-            };
-
-            let total_visible = tab.source.line_indices.len();
-            let start = offset.min(total_visible);
-            let end = (start + limit).min(total_visible);
 
-            let mut reader = match tab.source.reader.lock() {
-                Ok(guard) => guard,
-                Err(poisoned) => poisoned.into_inner(),
-            };
-
-            let index_reader = tab.source.index_reader.as_ref();
-
-            let mut rows = Vec::with_capacity(end.saturating_sub(start));
-            for visible_index in start..end {
-                if let Some(&file_line) = tab.source.line_indices.get(visible_index) {
-                    let content = reader
-                        .get_line(file_line)
-                        .ok()
-                        .flatten()
-                        .map(|line| strip_ansi(&line))
-                        .unwrap_or_default();
-
-                    rows.push(LineRow {
-                        visible_index,
-                        line_number: file_line + 1,
-                        content,
-                        severity: index_reader
-                            .map(|ir| ir.severity(file_line))
-                            .and_then(|s| s.label()),
+            if let Some(filter) = requested_filter(&query) {
+                let (pattern, mode) = match filter {
+                    Ok(filter) => filter,
+                    Err(err) => {
+                        respond_json_error(request, 400, err);
+                        return;
+                    }
+                };
+                let Some(tab) = state.tab_mut(source) else {
+                    respond_json_error(request, 404, "Source not found");
+                    return;
+                };
+                let filter = &tab.source.filter;
+                if filter.pattern.as_deref() != Some(pattern.as_str()) || filter.mode != mode {
+                    if let Err(err) = start_filter(tab, pattern, mode) {
+                        respond_json_error(request, 400, err);
+                        return;
+                    }
+                    state.bump_revision(source);
+                    drop(state);
+
+                    // Give the filter a moment to finish so the lines already
+                    // show its results, waiting on a worker so the server
+                    // keeps serving other requests meanwhile
+                    let shared = Arc::clone(shared);
+                    std::thread::spawn(move || {
+                        let mut state = wait_for_filter(&shared, source);
+                        respond_lines(request, &mut state, source, offset, limit, plain);
                     });
+                    return;
                 }
             }
 
-            let body = to_json_string(&LinesResponse {
-                revision,
-                total_visible,
-                total_lines: tab.source.total_lines,
-                offset: start,
-                limit,
-                rows,
-            });
-            respond_json(request, 200, body);
+            respond_lines(request, &mut state, source, offset, limit, plain);
             return;
         }
         (&Method::Post, "/api/filter") => {
@@ -153,7 +159,7 @@ pub(super) fn handle_request(request: tiny_http::Request, shared: &Arc<Mutex<Web
             let mut state = lock_state(shared);
             state.tick();
 
-            let Some(tab) = state.tabs.get_mut(payload.source) else {
+            let Some(tab) = state.tab_mut(payload.source) else {
                 respond_json_error(request, 404, "Source not found");
                 return;
             };
@@ -167,7 +173,7 @@ pub(super) fn handle_request(request: tiny_http::Request, shared: &Arc<Mutex<Web
                 }
                 tab.source.filter.receiver = None;
                 tab.clear_filter();
-                state.bump_revision();
+                state.bump_revision(payload.source);
                 respond_json(
                     request,
                     200,
@@ -179,28 +185,11 @@ pub(super) fn handle_request(request: tiny_http::Request, shared: &Arc<Mutex<Web
                 return;
             }
 
-            // Pre-validate pattern before passing to orchestrator
-            if mode.is_query() {
-                if let Err(err) = query::parse_query(&trimmed_pattern) {
-                    respond_json_error(request, 400, format!("Invalid query: {}", err));
-                    return;
-                }
-            } else if mode.is_regex() {
-                if let Err(err) = RegexFilter::new(&trimmed_pattern, mode.is_case_sensitive()) {
-                    respond_json_error(request, 400, format!("Invalid regex pattern: {}", err));
-                    return;
-                }
-            }
-
-            tab.source.filter.pattern = Some(trimmed_pattern.clone());
-            tab.source.filter.mode = mode;
-            if let Err(e) =
-                FilterOrchestrator::trigger(&mut tab.source, trimmed_pattern, mode, None)
-            {
+            if let Err(e) = start_filter(tab, trimmed_pattern, mode) {
                 respond_json_error(request, 400, e);
                 return;
             }
-            state.bump_revision();
+            state.bump_revision(payload.source);
             respond_json(
                 request,
                 200,
@@ -212,6 +201,47 @@ pub(super) fn handle_request(request: tiny_http::Request, shared: &Arc<Mutex<Web
 
             return;
         }
+        (&Method::Post, "/api/search") => {
+            let body = match read_body(&mut request) {
+                Ok(body) => body,
+                Err(BodyReadError::TooLarge) => {
+                    respond_json_error(request, 413, "Request body too large");
+                    return;
+                }
+                Err(BodyReadError::Invalid(err)) => {
+                    respond_json_error(request, 400, format!("Invalid request body: {}", err));
+                    return;
+                }
+            };
+
+            let payload: SearchRequest = match serde_json::from_str(&body) {
+                Ok(payload) => payload,
+                Err(err) => {
+                    respond_json_error(request, 400, format!("Invalid JSON payload: {}", err));
+                    return;
+                }
+            };
+
+            let mut state = lock_state(shared);
+            state.tick();
+            let pending = match search::start(&mut state, payload) {
+                Ok(pending) => pending,
+                Err((status, message)) => {
+                    drop(state);
+                    respond_json_error(request, status, message);
+                    return;
+                }
+            };
+            drop(state);
+
+            // Scans can take a while; answer from a worker so the server
+            // keeps serving other requests meanwhile
+            std::thread::spawn(move || match pending.collect() {
+                Ok(response) => respond_json(request, 200, to_json_string(&response)),
+                Err((status, message)) => respond_json_error(request, status, message),
+            });
+            return;
+        }
         (&Method::Post, "/api/filter/clear") => {
             let body = match read_body(&mut request) {
                 Ok(body) => body,
@@ -236,7 +266,7 @@ pub(super) fn handle_request(request: tiny_http::Request, shared: &Arc<Mutex<Web
             let mut state = lock_state(shared);
             state.tick();
 
-            let Some(tab) = state.tabs.get_mut(payload.source) else {
+            let Some(tab) = state.tab_mut(payload.source) else {
                 respond_json_error(request, 404, "Source not found");
                 return;
             };
@@ -246,7 +276,7 @@ pub(super) fn handle_request(request: tiny_http::Request, shared: &Arc<Mutex<Web
             }
             tab.source.filter.receiver = None;
             tab.clear_filter();
-            state.bump_revision();
+            state.bump_revision(payload.source);
 
             respond_json(
                 request,
@@ -282,7 +312,7 @@ pub(super) fn handle_request(request: tiny_http::Request, shared: &Arc<Mutex<Web
             let mut state = lock_state(shared);
             state.tick();
 
-            let Some(tab) = state.tabs.get_mut(payload.source) else {
+            let Some(tab) = state.tab_mut(payload.source) else {
                 respond_json_error(request, 404, "Source not found");
                 return;
             };
@@ -291,7 +321,7 @@ pub(super) fn handle_request(request: tiny_http::Request, shared: &Arc<Mutex<Web
             if tab.source.follow_mode {
                 tab.jump_to_end();
             }
-            state.bump_revision();
+            state.bump_revision(payload.source);
 
             respond_json(
                 request,
@@ -327,13 +357,13 @@ pub(super) fn handle_request(request: tiny_http::Request, shared: &Arc<Mutex<Web
             let mut state = lock_state(shared);
             state.tick();
 
-            if payload.source >= state.tabs.len() {
+            let Some(pos) = state.tab_position(payload.source) else {
                 respond_json_error(request, 404, "Source not found");
                 return;
-            }
+            };
 
             if payload.delete_ended {
-                let tab_ref = &state.tabs[payload.source];
+                let tab_ref = &state.tabs[pos];
                 if let Err(err) = delete_ended_source(tab_ref, &state) {
                     respond_json(
                         request,
@@ -347,13 +377,16 @@ pub(super) fn handle_request(request: tiny_http::Request, shared: &Arc<Mutex<Web
                 }
             }
 
-            let mut tab = state.tabs.remove(payload.source);
+            let Some(mut tab) = state.remove_tab(payload.source) else {
+                respond_json_error(request, 404, "Source not found");
+                return;
+            };
             if let Some(ref cancel) = tab.source.filter.cancel_token {
                 cancel.cancel();
             }
             tab.source.filter.receiver = None;
 
-            state.bump_revision();
+            state.bump_revision(payload.source);
 
             respond_json(
                 request,
@@ -396,11 +429,125 @@ fn respond_plain(request: tiny_http::Request, status: u16, body: &str) {
     let _ = request.respond(response);
 }
 
-pub(super) fn respond_events(request: tiny_http::Request, next_revision: Option<u64>) {
-    let body = match next_revision {
-        Some(next) => format!(
+/// Answer `/api/lines` with `limit` visible lines of `source` from `offset`.
+fn respond_lines(
+    request: tiny_http::Request,
+    state: &mut WebState,
+    source: usize,
+    offset: usize,
+    limit: usize,
+    plain: bool,
+) {
+    let revision = state.revision;
+
+    let Some(tab) = state.tab_mut(source) else {
+        respond_json_error(request, 404, "Source not found");
+        return;
+    };
+
+    let total_visible = tab.source.line_indices.len();
+    let start = offset.min(total_visible);
+    let end = (start + limit).min(total_visible);
+
+    let mut reader = match tab.source.reader.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    let index_reader = tab.source.index_reader.as_ref();
+
+    let mut rows = Vec::with_capacity(end.saturating_sub(start));
+    for visible_index in start..end {
+        if let Some(file_line) = tab.source.line_indices.get(visible_index) {
+            let line = reader.get_line(file_line).ok().flatten();
+            let spans = line
+                .as_deref()
+                .filter(|_| !plain)
+                .and_then(ansi_styled_spans);
+            let content = line.map(|line| strip_ansi(&line)).unwrap_or_default();
+
+            rows.push(LineRow {
+                visible_index,
+                line_number: file_line + 1,
+                content,
+                severity: index_reader
+                    .map(|ir| ir.severity(file_line))
+                    .and_then(|s| s.label()),
+                spans,
+            });
+        }
+    }
+
+    let body = to_json_string(&LinesResponse {
+        revision,
+        total_visible,
+        total_lines: tab.source.total_lines,
+        offset: start,
+        limit,
+        windowed: start > 0 || end < total_visible,
+        filtered: tab.source.mode == ViewMode::Filtered,
+        rows,
+    });
+    respond_json(request, 200, body);
+}
+
+/// Wait up to [`INITIAL_FILTER_WAIT`] for the filter just started on
+/// `source` to finish, and return the lock. A slower filter keeps streaming
+/// and reports through events. Runs on a worker thread; the lock is only
+/// held to check.
+fn wait_for_filter(shared: &Arc<Mutex<WebState>>, source: usize) -> MutexGuard<'_, WebState> {
+    let deadline = Instant::now() + INITIAL_FILTER_WAIT;
+    loop {
+        let mut state = lock_state(shared);
+        state.tick();
+        let processing = state
+            .tab_mut(source)
+            .is_some_and(|tab| matches!(tab.source.filter.state, FilterState::Processing { .. }));
+        if !processing || Instant::now() >= deadline {
+            return state;
+        }
+        drop(state);
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// Validate `pattern` and start filtering `tab` with it.
+fn start_filter(tab: &mut TabState, pattern: String, mode: FilterMode) -> Result<(), String> {
+    // Pre-validate pattern before passing to orchestrator
+    if mode.is_query() {
+        query::parse_query(&pattern).map_err(|err| format!("Invalid query: {}", err))?;
+    } else if mode.is_regex() {
+        RegexFilter::new(&pattern, mode.is_case_sensitive())
+            .map_err(|err| format!("Invalid regex pattern: {}", err))?;
+    }
+
+    tab.source.filter.pattern = Some(pattern.clone());
+    tab.source.filter.mode = mode;
+    FilterOrchestrator::trigger(&mut tab.source, pattern, mode, None)
+}
+
+/// Filter requested alongside `/api/lines` (`pattern`, `mode`, `case`), as
+/// a shared link does on first load. `None` when no pattern is given.
+fn requested_filter(
+    query: &HashMap<String, String>,
+) -> Option<Result<(String, FilterMode), String>> {
+    let pattern = query.get("pattern").filter(|p| !p.is_empty())?;
+    let mode_name = query.get("mode").map_or("plain", String::as_str);
+    let Some(mode) = WebFilterMode::from_name(mode_name) else {
+        return Some(Err(format!("Unknown filter mode '{}'", mode_name)));
+    };
+    let case_sensitive = parse_usize_query(query, "case").is_some_and(|v| v != 0);
+    Some(Ok((pattern.clone(), mode.into_filter_mode(case_sensitive))))
+}
+
+/// Answer a long-poll with the next revision and the sources it changed,
+/// or a keepalive when `next` is `None`.
+pub(super) fn respond_events(request: tiny_http::Request, next: Option<(u64, Vec<usize>)>) {
+    let body = match next {
+        Some((revision, sources)) => format!(
             "retry: 250\nid: {}\nevent: revision\ndata: {}\n\n",
-            next, next
+            revision,
+            to_json_string(&RevisionEvent { revision, sources })
         ),
         None => "retry: 250\n: keepalive\n\n".to_string(),
     };
@@ -640,4 +787,28 @@ mod tests {
         let params = parse_query_params("q=hello+world");
         assert_eq!(params.get("q"), Some(&"hello world".to_string()));
     }
+
+    #[test]
+    fn lines_query_can_request_a_filter() {
+        let parse = |query: &str| requested_filter(&parse_query_params(query));
+
+        assert!(parse("source=0&offset=10").is_none());
+        assert!(parse("source=0&pattern=").is_none());
+
+        let (pattern, mode) = parse("pattern=disk%20full").unwrap().unwrap();
+        assert_eq!(pattern, "disk full");
+        assert_eq!(
+            mode,
+            FilterMode::Plain {
+                case_sensitive: false,
+                negate: false
+            }
+        );
+
+        let (_, mode) = parse("pattern=err.*&mode=regex&case=1").unwrap().unwrap();
+        assert!(mode.is_regex() && mode.is_case_sensitive());
+
+        let err = parse("pattern=x&mode=fuzzy").unwrap().unwrap_err();
+        assert!(err.contains("fuzzy"), "{}", err);
+    }
 }
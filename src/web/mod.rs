@@ -1,4 +1,5 @@
 mod handlers;
+mod search;
 mod state;
 
 use crate::app::TabState;
@@ -6,11 +7,14 @@ use crate::app::{FilterState, SourceType};
 use crate::cli::WebArgs;
 use crate::config;
 use crate::filter::FilterMode;
+use crate::power;
 use crate::signal::setup_shutdown_handlers;
 use crate::source::{self, SourceLocation, SourceStatus};
 use crate::watcher::DirectoryWatcher;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::io::ErrorKind;
+use std::net::TcpListener;
 use std::path::PathBuf;
 use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
@@ -18,12 +22,38 @@ use std::time::Duration;
 
 use state::{lock_state, WebState};
 
-const INDEX_HTML: &str = include_str!("index.html");
+const INDEX_HTML: &str = include_str!("assets/index.html");
+/// Embedded files served under `/assets/`: (name, content type, body)
+const ASSETS: &[(&str, &str, &str)] = &[
+    (
+        "app.css",
+        "text/css; charset=utf-8",
+        include_str!("assets/app.css"),
+    ),
+    (
+        "app.js",
+        "text/javascript; charset=utf-8",
+        include_str!("assets/app.js"),
+    ),
+    (
+        "favicon.svg",
+        "image/svg+xml",
+        include_str!("assets/favicon.svg"),
+    ),
+];
 const MAX_LINES_PER_REQUEST: usize = 5_000;
 const MAX_REQUEST_BODY_SIZE: usize = 1024 * 1024;
 const MAX_PENDING_EVENT_REQUESTS: usize = 256;
+/// How long `/api/lines` waits for a filter it started to finish
+const INITIAL_FILTER_WAIT: Duration = Duration::from_millis(1_000);
+/// Styled spans sent per line; the rest of a line arrives as one plain span
+const MAX_SPANS_PER_ROW: usize = 64;
 const TICK_INTERVAL_MS: u64 = 150;
+/// Minimum time between revisions published for background changes
+const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_millis(300);
 const EVENTS_WAIT_TIMEOUT: Duration = Duration::from_secs(25);
+/// Ports tried after the requested one, for `--port-auto` and suggestions
+const PORT_SEARCH_RANGE: u16 = 20;
 
 type InitialTabsBuild = (
     Vec<TabState>,
@@ -31,8 +61,20 @@ type InitialTabsBuild = (
     Option<SourceLocation>,
     Option<PathBuf>,
     Option<PathBuf>,
+    // Configured `web_refresh_ms`
+    Option<u64>,
+    // Configured `compact_numbers`
+    Option<bool>,
 );
 
+/// Content type and body of an embedded asset.
+fn embedded_asset(name: &str) -> Option<(&'static str, &'static str)> {
+    ASSETS
+        .iter()
+        .find(|(asset, _, _)| *asset == name)
+        .map(|(_, content_type, body)| (*content_type, *body))
+}
+
 // --- Serde types for API responses ---
 
 #[derive(Serialize)]
@@ -44,6 +86,8 @@ struct SourcesResponse {
 #[derive(Serialize)]
 struct SourceView {
     id: usize,
+    /// Revision at which the source last changed (0 if not since startup)
+    revision: u64,
     name: String,
     category: &'static str,
     disabled: bool,
@@ -51,10 +95,16 @@ struct SourceView {
     source_status: Option<&'static str>,
     total_lines: usize,
     visible_lines: usize,
+    /// `total_lines` and `visible_lines` for display (compact unless
+    /// `compact_numbers` is off)
+    total_lines_label: String,
+    visible_lines_label: String,
     filter_pattern: Option<String>,
     filter_mode: &'static str,
     case_sensitive: bool,
     filter_state: FilterStateView,
+    /// Lines added since the filter results were computed, being re-filtered
+    filter_pending_lines: usize,
     can_delete_ended: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     severity_counts: Option<SeverityCountsView>,
@@ -81,13 +131,26 @@ struct SeverityCountsView {
     fatal: u32,
 }
 
+/// Payload of an `/api/events` revision event.
+#[derive(Serialize)]
+struct RevisionEvent {
+    revision: u64,
+    /// Sources changed since the client's `since` revision
+    sources: Vec<usize>,
+}
+
 #[derive(Serialize)]
 struct LinesResponse {
     revision: u64,
+    /// Lines in the view (matches, when filtered)
     total_visible: usize,
     total_lines: usize,
     offset: usize,
     limit: usize,
+    /// `rows` is a window of `total_visible`, not all of it
+    windowed: bool,
+    /// The view shows a filter's matches (even if every line matches)
+    filtered: bool,
     rows: Vec<LineRow>,
 }
 
@@ -98,6 +161,144 @@ struct LineRow {
     content: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     severity: Option<&'static str>,
+    /// `content` split into styled spans, for lines the source colored
+    /// with ANSI escapes (omitted for uncolored lines and `?plain=1`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    spans: Option<Vec<StyledSpan>>,
+}
+
+/// A run of text with the style its ANSI escapes gave it.
+#[derive(Serialize, Debug, PartialEq)]
+struct StyledSpan {
+    text: String,
+    /// CSS color (`#rrggbb`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fg: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bg: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    bold: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    dim: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    italic: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    underline: bool,
+}
+
+impl StyledSpan {
+    fn plain(text: String) -> Self {
+        Self {
+            text,
+            fg: None,
+            bg: None,
+            bold: false,
+            dim: false,
+            italic: false,
+            underline: false,
+        }
+    }
+}
+
+/// Parse a line's ANSI escapes into styled spans (at most
+/// `MAX_SPANS_PER_ROW`). `None` if the line has no escapes.
+fn ansi_styled_spans(line: &str) -> Option<Vec<StyledSpan>> {
+    use ratatui::style::Modifier;
+
+    if !line.contains('\x1b') {
+        return None;
+    }
+    let text = ansi_to_tui::IntoText::into_text(&line).ok()?;
+    let parsed = text.lines.into_iter().next()?.spans;
+
+    let mut spans: Vec<StyledSpan> = Vec::new();
+    for span in parsed {
+        if span.content.is_empty() {
+            continue;
+        }
+        if spans.len() + 1 >= MAX_SPANS_PER_ROW {
+            // Out of budget: the rest of the line goes in one unstyled span
+            if spans.len() < MAX_SPANS_PER_ROW {
+                spans.push(StyledSpan::plain(span.content.into_owned()));
+            } else if let Some(tail) = spans.last_mut() {
+                tail.text.push_str(&span.content);
+            }
+            continue;
+        }
+        let style = span.style;
+        spans.push(StyledSpan {
+            text: span.content.into_owned(),
+            fg: style.fg.and_then(css_color),
+            bg: style.bg.and_then(css_color),
+            bold: style.add_modifier.contains(Modifier::BOLD),
+            dim: style.add_modifier.contains(Modifier::DIM),
+            italic: style.add_modifier.contains(Modifier::ITALIC),
+            underline: style.add_modifier.contains(Modifier::UNDERLINED),
+        });
+    }
+    Some(spans)
+}
+
+/// CSS form of a terminal color (xterm defaults for the 16 named colors).
+fn css_color(color: ratatui::style::Color) -> Option<String> {
+    use ratatui::style::Color;
+
+    const BASE16: [(u8, u8, u8); 16] = [
+        (0x00, 0x00, 0x00),
+        (0xcd, 0x00, 0x00),
+        (0x00, 0xcd, 0x00),
+        (0xcd, 0xcd, 0x00),
+        (0x00, 0x00, 0xee),
+        (0xcd, 0x00, 0xcd),
+        (0x00, 0xcd, 0xcd),
+        (0xe5, 0xe5, 0xe5),
+        (0x7f, 0x7f, 0x7f),
+        (0xff, 0x00, 0x00),
+        (0x00, 0xff, 0x00),
+        (0xff, 0xff, 0x00),
+        (0x5c, 0x5c, 0xff),
+        (0xff, 0x00, 0xff),
+        (0x00, 0xff, 0xff),
+        (0xff, 0xff, 0xff),
+    ];
+
+    let (r, g, b) = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(idx @ 0..=15) => BASE16[idx as usize],
+        Color::Indexed(idx @ 16..=231) => {
+            // 6x6x6 color cube
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let idx = idx - 16;
+            (level(idx / 36), level((idx / 6) % 6), level(idx % 6))
+        }
+        Color::Indexed(idx) => {
+            let gray = 8 + (idx - 232) * 10;
+            (gray, gray, gray)
+        }
+        named => {
+            let idx = match named {
+                Color::Black => 0,
+                Color::Red => 1,
+                Color::Green => 2,
+                Color::Yellow => 3,
+                Color::Blue => 4,
+                Color::Magenta => 5,
+                Color::Cyan => 6,
+                Color::Gray => 7,
+                Color::DarkGray => 8,
+                Color::LightRed => 9,
+                Color::LightGreen => 10,
+                Color::LightYellow => 11,
+                Color::LightBlue => 12,
+                Color::LightMagenta => 13,
+                Color::LightCyan => 14,
+                _ => 15,
+            };
+            BASE16[idx]
+        }
+    };
+    Some(format!("#{:02x}{:02x}{:02x}", r, g, b))
 }
 
 #[derive(Serialize)]
@@ -117,10 +318,25 @@ enum WebFilterMode {
 }
 
 impl WebFilterMode {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "plain" => Some(WebFilterMode::Plain),
+            "regex" => Some(WebFilterMode::Regex),
+            "query" => Some(WebFilterMode::Query),
+            _ => None,
+        }
+    }
+
     fn into_filter_mode(self, case_sensitive: bool) -> FilterMode {
         match self {
-            WebFilterMode::Plain => FilterMode::Plain { case_sensitive },
-            WebFilterMode::Regex => FilterMode::Regex { case_sensitive },
+            WebFilterMode::Plain => FilterMode::Plain {
+                case_sensitive,
+                negate: false,
+            },
+            WebFilterMode::Regex => FilterMode::Regex {
+                case_sensitive,
+                negate: false,
+            },
             WebFilterMode::Query => FilterMode::Query {},
         }
     }
@@ -197,18 +413,30 @@ fn filter_state_view(state: FilterState) -> FilterStateView {
 
 // --- Public entry point ---
 
-pub fn run(args: WebArgs) -> Result<(), i32> {
+pub fn run(args: WebArgs, profile: Option<&str>) -> Result<(), i32> {
     source::cleanup_stale_markers();
 
     let watch = !args.no_watch;
-    let (tabs, dir_watcher, watched_location, project_data_dir, global_data_dir) =
-        match build_initial_tabs(&args.files, watch, args.verbose) {
-            Ok(result) => result,
-            Err(err) => {
-                eprintln!("error: {}", err);
-                return Err(1);
-            }
-        };
+    let (
+        tabs,
+        dir_watcher,
+        watched_location,
+        project_data_dir,
+        global_data_dir,
+        refresh_ms,
+        compact_numbers,
+    ) = match build_initial_tabs(&args.files, watch, args.force_text, args.verbose, profile) {
+        Ok(result) => result,
+        // Already formatted with its own `error:` prefix
+        Err(err) if err.is::<config::error::ConfigError>() => {
+            eprintln!("{}", err);
+            return Err(1);
+        }
+        Err(err) => {
+            eprintln!("error: {:#}", err);
+            return Err(1);
+        }
+    };
 
     if tabs.is_empty() {
         eprintln!("No log sources found.");
@@ -219,20 +447,34 @@ pub fn run(args: WebArgs) -> Result<(), i32> {
         return Err(1);
     }
 
-    let shared = Arc::new(Mutex::new(WebState::new(
+    let mut state = WebState::new(
         tabs,
         dir_watcher,
         watched_location,
         project_data_dir,
         global_data_dir,
         watch,
-    )));
+    );
+    if let Some(ms) = args.refresh_ms.or(refresh_ms) {
+        state.refresh_interval = Duration::from_millis(ms);
+    }
+    state.compact_numbers = compact_numbers.unwrap_or(true);
+    let shared = Arc::new(Mutex::new(state));
 
-    let bind_addr = format!("{}:{}", args.host, args.port);
-    let server = match tiny_http::Server::http(&bind_addr) {
+    let (listener, port) = match bind_listener(&args.host, args.port, args.port_auto) {
+        Ok(bound) => bound,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return Err(1);
+        }
+    };
+    if port != args.port {
+        println!("Port {} is in use, using {} instead.", args.port, port);
+    }
+    let server = match tiny_http::Server::from_listener(listener, None) {
         Ok(server) => server,
         Err(err) => {
-            eprintln!("error: Failed to bind web server on {}: {}", bind_addr, err);
+            eprintln!("error: Failed to start web server: {}", err);
             return Err(1);
         }
     };
@@ -242,7 +484,7 @@ pub fn run(args: WebArgs) -> Result<(), i32> {
     } else {
         &args.host
     };
-    let open_url = format!("http://{}:{}/", open_host, args.port);
+    let open_url = format!("http://{}:{}/", open_host, port);
 
     println!("LazyTail Web UI started at {}", open_url);
     println!("Press Ctrl+C to stop.");
@@ -272,7 +514,65 @@ pub fn run(args: WebArgs) -> Result<(), i32> {
     Ok(())
 }
 
-fn build_initial_tabs(files: &[PathBuf], watch: bool, verbose: bool) -> Result<InitialTabsBuild> {
+/// Bind the web server's socket, returning it with the port actually used.
+///
+/// With `port_auto`, a port in use moves on to the next free one. Errors are
+/// full messages with what to try next.
+fn bind_listener(host: &str, port: u16, port_auto: bool) -> Result<(TcpListener, u16), String> {
+    let err = match TcpListener::bind((host, port)) {
+        Ok(listener) => return Ok((listener, port)),
+        Err(err) => err,
+    };
+    let addr = format!("{}:{}", host, port);
+
+    match err.kind() {
+        ErrorKind::AddrInUse => {
+            let next_free = (1..=PORT_SEARCH_RANGE)
+                .filter_map(|offset| port.checked_add(offset))
+                .find_map(|candidate| {
+                    TcpListener::bind((host, candidate))
+                        .ok()
+                        .map(|listener| (listener, candidate))
+                });
+            match next_free {
+                Some(bound) if port_auto => Ok(bound),
+                Some((_, free)) => Err(format!(
+                    "{} is already in use (another lazytail web or server is running there).\n  \
+                     Use a free port: lazytail web --port {}\n  \
+                     Or pick one automatically: lazytail web --port-auto",
+                    addr, free
+                )),
+                None => Err(format!(
+                    "{} and the next {} ports are in use. Choose another with --port",
+                    addr, PORT_SEARCH_RANGE
+                )),
+            }
+        }
+        ErrorKind::PermissionDenied => Err(format!(
+            "Permission denied binding {}.{}",
+            addr,
+            if port < 1024 {
+                " Ports below 1024 need elevated privileges; use a higher one, e.g. --port 8421"
+            } else {
+                " Check firewall or sandbox rules, or try another --port"
+            }
+        )),
+        ErrorKind::AddrNotAvailable => Err(format!(
+            "{} is not an address of this machine. Use --host 127.0.0.1 (local only) \
+             or --host 0.0.0.0 (all interfaces)",
+            host
+        )),
+        _ => Err(format!("Failed to bind web server on {}: {}", addr, err)),
+    }
+}
+
+fn build_initial_tabs(
+    files: &[PathBuf],
+    watch: bool,
+    force_text: bool,
+    verbose: bool,
+    profile: Option<&str>,
+) -> Result<InitialTabsBuild> {
     let (discovery, searched_paths) = config::discovery::discover_verbose();
 
     if verbose {
@@ -306,13 +606,19 @@ fn build_initial_tabs(files: &[PathBuf], watch: bool, verbose: bool) -> Result<I
     }
 
     let mut config_errors = Vec::new();
-    let cfg = match config::load(&discovery) {
+    let cfg = match config::load_with_profile(&discovery, profile) {
         Ok(c) => c,
+        // An explicitly requested profile must exist
+        Err(err @ config::error::ConfigError::UnknownProfile { .. }) => return Err(err.into()),
         Err(err) => {
             config_errors.push(err.to_string());
             config::Config::default()
         }
     };
+    source::configure_indexes(&cfg);
+    if power::low_resource_active(cfg.low_resource.unwrap_or_default()) {
+        source::defer_index_builds();
+    }
 
     let mut tabs = Vec::new();
 
@@ -348,7 +654,7 @@ fn build_initial_tabs(files: &[PathBuf], watch: bool, verbose: bool) -> Result<I
         let discovered = source::discover_sources_for_context(&discovery)
             .context("Failed to discover sources")?;
         for src in discovered {
-            if let Ok(tab) = TabState::from_discovered_source(src, watch, Vec::new()) {
+            if let Ok(tab) = TabState::from_discovered_source(src, watch, Vec::new(), None, None) {
                 tabs.push(tab);
             }
         }
@@ -369,7 +675,7 @@ fn build_initial_tabs(files: &[PathBuf], watch: bool, verbose: bool) -> Result<I
     } else {
         for path in files {
             tabs.push(
-                TabState::new(path.clone(), watch)
+                TabState::open(path.clone(), watch, force_text)
                     .with_context(|| format!("Failed to open {}", path.display()))?,
             );
         }
@@ -385,5 +691,70 @@ fn build_initial_tabs(files: &[PathBuf], watch: bool, verbose: bool) -> Result<I
         watched_location,
         project_data_dir,
         global_data_dir,
+        cfg.web_refresh_ms,
+        cfg.compact_numbers,
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_assets_are_embedded_with_content_types() {
+        for reference in INDEX_HTML.split("\"/assets/").skip(1) {
+            let name = &reference[..reference.find('"').unwrap()];
+            assert!(embedded_asset(name).is_some(), "missing asset {}", name);
+        }
+        assert_eq!(
+            embedded_asset("app.js").unwrap().0,
+            "text/javascript; charset=utf-8"
+        );
+        assert_eq!(embedded_asset("favicon.svg").unwrap().0, "image/svg+xml");
+        assert!(embedded_asset("../Cargo.toml").is_none());
+    }
+
+    #[test]
+    fn ansi_lines_become_styled_spans() {
+        assert_eq!(ansi_styled_spans("no escapes here"), None);
+
+        let spans =
+            ansi_styled_spans("\x1b[1;31mERROR\x1b[0m disk \x1b[38;5;46mfull\x1b[0m").unwrap();
+        let text: String = spans.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(text, "ERROR disk full");
+        assert_eq!(spans[0].text, "ERROR");
+        assert_eq!(spans[0].fg.as_deref(), Some("#cd0000"));
+        assert!(spans[0].bold);
+        assert_eq!(spans[1], StyledSpan::plain(" disk ".to_string()));
+        assert_eq!(spans[2].fg.as_deref(), Some("#00ff00"));
+
+        let json = serde_json::to_string(&spans[1]).unwrap();
+        assert_eq!(json, r#"{"text":" disk "}"#);
+    }
+
+    #[test]
+    fn styled_spans_are_capped_per_row() {
+        let line: String = (0..200)
+            .map(|i| format!("\x1b[3{}m{}\x1b[0m", i % 8, i % 10))
+            .collect();
+        let spans = ansi_styled_spans(&line).unwrap();
+        assert_eq!(spans.len(), MAX_SPANS_PER_ROW);
+        let text: String = spans.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(text, crate::ansi::strip_ansi(&line));
+        assert!(spans.last().unwrap().fg.is_none());
+    }
+
+    #[test]
+    fn port_in_use_suggests_or_picks_next_free() {
+        let taken = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = taken.local_addr().unwrap().port();
+
+        let message = bind_listener("127.0.0.1", port, false).unwrap_err();
+        assert!(message.contains("already in use"), "{}", message);
+        assert!(message.contains("--port-auto"), "{}", message);
+
+        let (listener, bound) = bind_listener("127.0.0.1", port, true).unwrap();
+        assert_ne!(bound, port);
+        assert_eq!(listener.local_addr().unwrap().port(), bound);
+    }
+}
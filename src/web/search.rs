@@ -0,0 +1,508 @@
+//! Cross-source search for `POST /api/search`.
+//!
+//! A search runs one filter over every (or every selected) source without
+//! touching their tabs' own filters. Searches start under the state lock —
+//! index bitmaps are read up front — and are drained afterwards, so a slow
+//! scan never holds up other requests. File scans keep only the requested
+//! page of matches and count the rest; reader scans arrive newest-first, so
+//! their matches are sorted before paging. A newer search cancels the running one,
+//! and at most [`MAX_RUNNING_SEARCHES`] are answered at once.
+
+use crate::ansi::strip_ansi;
+use crate::filter::cancel::CancelToken;
+use crate::filter::engine::FilterProgress;
+use crate::filter::query::{self, FilterQuery, QueryFilter};
+use crate::filter::regex_filter::RegexFilter;
+use crate::filter::search_engine::SearchEngine;
+use crate::filter::string_filter::StringFilter;
+use crate::filter::{Filter, FilterMode};
+use crate::reader::encoding::Encoding;
+use crate::reader::LogReader;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::Receiver;
+use std::sync::{Arc, Mutex};
+
+use super::state::WebState;
+use super::{WebFilterMode, MAX_LINES_PER_REQUEST};
+
+/// Rows returned when the request sets no `limit`.
+const DEFAULT_SEARCH_LIMIT: usize = 500;
+
+/// Searches answered at once, counting cancelled ones still winding down.
+const MAX_RUNNING_SEARCHES: usize = 4;
+
+#[derive(Deserialize)]
+pub(super) struct SearchRequest {
+    pattern: String,
+    mode: WebFilterMode,
+    #[serde(default)]
+    case_sensitive: bool,
+    /// Source ids to search (all sources when omitted)
+    #[serde(default)]
+    sources: Option<Vec<usize>>,
+    /// Matches to skip, counted across sources in order
+    #[serde(default)]
+    offset: usize,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub(super) struct SearchResponse {
+    total_matches: usize,
+    offset: usize,
+    limit: usize,
+    /// `offset` of the next page, if there are more matches
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_offset: Option<usize>,
+    sources: Vec<SearchSourceResult>,
+}
+
+#[derive(Serialize)]
+struct SearchSourceResult {
+    source: usize,
+    name: String,
+    match_count: usize,
+    /// This page's matches from the source
+    rows: Vec<SearchRow>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SearchRow {
+    line_number: usize,
+    content: String,
+}
+
+/// A search started on one source.
+struct SourceSearch {
+    id: usize,
+    name: String,
+    reader: Arc<Mutex<dyn LogReader + Send>>,
+    receiver: Result<Receiver<FilterProgress>, String>,
+    /// Matches arrive in line order (file scans)
+    in_order: bool,
+}
+
+/// Searches started by [`start`], to be drained with [`PendingSearch::collect`].
+pub(super) struct PendingSearch {
+    searches: Vec<SourceSearch>,
+    offset: usize,
+    limit: usize,
+    /// Cancelled when a newer search starts
+    cancel: CancelToken,
+    _running: RunningSearch,
+}
+
+/// Counts a search in [`WebState::running_searches`] until dropped.
+struct RunningSearch(Arc<AtomicUsize>);
+
+impl Drop for RunningSearch {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Validate `request` and start its filter on each requested source.
+///
+/// Errors are client errors: `(status, message)`.
+pub(super) fn start(
+    state: &mut WebState,
+    request: SearchRequest,
+) -> Result<PendingSearch, (u16, String)> {
+    if request.pattern.is_empty() {
+        return Err((400, "Search pattern is empty".to_string()));
+    }
+    let mode = request.mode.into_filter_mode(request.case_sensitive);
+    let (filter, filter_query) = build_filter(&request.pattern, mode).map_err(|e| (400, e))?;
+
+    let ids = match request.sources {
+        Some(ids) => {
+            if let Some(&missing) = ids.iter().find(|&&id| state.tab_position(id).is_none()) {
+                return Err((404, format!("Source not found: {}", missing)));
+            }
+            ids
+        }
+        None => state.tab_ids().to_vec(),
+    };
+
+    let running = state.running_searches.clone();
+    if running.fetch_add(1, Ordering::SeqCst) >= MAX_RUNNING_SEARCHES {
+        running.fetch_sub(1, Ordering::SeqCst);
+        return Err((503, "Too many searches running".to_string()));
+    }
+    let running = RunningSearch(running);
+    let cancel = CancelToken::new();
+    if let Some(previous) = state.search_cancel.replace(cancel.clone()) {
+        previous.cancel();
+    }
+
+    let searches = ids
+        .into_iter()
+        .filter_map(|id| {
+            let source = &state.tab_mut(id)?.source;
+            let in_order = source.source_path.is_some();
+            let receiver = if let Some(path) = &source.source_path {
+                // Plain text takes the byte-level fast path, as full
+                // tab filters do
+                let raw_bytes_match =
+                    source.encoding == Encoding::Utf8 || request.pattern.is_ascii();
+                if matches!(mode, FilterMode::Plain { .. }) && raw_bytes_match {
+                    SearchEngine::search_file_fast(
+                        path,
+                        request.pattern.as_bytes(),
+                        mode.is_case_sensitive(),
+                        cancel.clone(),
+                    )
+                } else {
+                    SearchEngine::search_file(
+                        path,
+                        filter.clone(),
+                        source.encoding,
+                        filter_query.as_ref(),
+                        source.index_reader.as_ref(),
+                        None,
+                        None,
+                        cancel.clone(),
+                    )
+                }
+                .map_err(|e| format!("filter I/O error: {}", e))
+            } else {
+                Ok(SearchEngine::search_reader(
+                    source.reader.clone(),
+                    filter.clone(),
+                    None,
+                    cancel.clone(),
+                ))
+            };
+            Some(SourceSearch {
+                id,
+                name: source.name.clone(),
+                reader: source.reader.clone(),
+                receiver,
+                in_order,
+            })
+        })
+        .collect();
+
+    Ok(PendingSearch {
+        searches,
+        offset: request.offset,
+        limit: request
+            .limit
+            .unwrap_or(DEFAULT_SEARCH_LIMIT)
+            .min(MAX_LINES_PER_REQUEST),
+        cancel,
+        _running: running,
+    })
+}
+
+impl PendingSearch {
+    /// Wait for every source's search and read this page's lines.
+    ///
+    /// Pages run over the sources' matches concatenated in request order.
+    /// Errors with `(status, message)` if a newer search cancelled this one.
+    pub(super) fn collect(self) -> Result<SearchResponse, (u16, String)> {
+        let mut skip = self.offset;
+        let mut remaining = self.limit;
+        let mut total_matches = 0;
+
+        let sources: Vec<SearchSourceResult> = self
+            .searches
+            .into_iter()
+            .map(|search| {
+                let (page, match_count, error) = match search.receiver {
+                    Ok(receiver) => drain(receiver, skip, remaining, search.in_order),
+                    Err(err) => (Vec::new(), 0, Some(err)),
+                };
+                total_matches += match_count;
+                skip -= skip.min(match_count);
+                remaining -= page.len();
+
+                let mut reader = match search.reader.lock() {
+                    Ok(guard) => guard,
+                    Err(poisoned) => poisoned.into_inner(),
+                };
+                let rows = page
+                    .iter()
+                    .map(|&line| SearchRow {
+                        line_number: line + 1,
+                        content: reader
+                            .get_line(line)
+                            .ok()
+                            .flatten()
+                            .map(|line| strip_ansi(&line))
+                            .unwrap_or_default(),
+                    })
+                    .collect();
+
+                SearchSourceResult {
+                    source: search.id,
+                    name: search.name,
+                    match_count,
+                    rows,
+                    error,
+                }
+            })
+            .collect();
+        if self.cancel.is_cancelled() {
+            return Err((409, "Search replaced by a newer one".to_string()));
+        }
+
+        let end = self.offset + self.limit;
+        Ok(SearchResponse {
+            total_matches,
+            offset: self.offset,
+            limit: self.limit,
+            next_offset: (end < total_matches).then_some(end),
+            sources,
+        })
+    }
+}
+
+/// Build the filter for a search pattern; queries also return their parse
+/// for index acceleration. Aggregation clauses are ignored.
+fn build_filter(
+    pattern: &str,
+    mode: FilterMode,
+) -> Result<(Arc<dyn Filter>, Option<FilterQuery>), String> {
+    if mode.is_query() {
+        let mut filter_query =
+            query::parse_query(pattern).map_err(|e| format!("Invalid query: {}", e))?;
+        filter_query.aggregate = None;
+        if filter_query.has_ts_filters() {
+            return Err("@ts filters are not supported in cross-source search".to_string());
+        }
+        let filter =
+            QueryFilter::new(filter_query.clone()).map_err(|e| format!("Invalid query: {}", e))?;
+        Ok((Arc::new(filter), Some(filter_query)))
+    } else if mode.is_regex() {
+        let filter = RegexFilter::new(pattern, mode.is_case_sensitive())
+            .map_err(|e| format!("Invalid regex pattern: {}", e))?;
+        Ok((Arc::new(filter), None))
+    } else {
+        Ok((
+            Arc::new(StringFilter::new(pattern, mode.is_case_sensitive())),
+            None,
+        ))
+    }
+}
+
+/// Block until a search finishes. Returns the matches after the first
+/// `skip`, at most `take` of them, and how many matches there are in all.
+///
+/// When matches arrive in line order only the page is kept. Otherwise
+/// (reader scans, which batch from the end) all are collected and sorted.
+fn drain(
+    receiver: Receiver<FilterProgress>,
+    skip: usize,
+    take: usize,
+    in_order: bool,
+) -> (Vec<usize>, usize, Option<String>) {
+    let mut page = Vec::new();
+    let mut count = 0;
+    let mut error = None;
+    let mut keep = |batch: Vec<usize>| {
+        if in_order {
+            let start = skip.saturating_sub(count).min(batch.len());
+            let end = (skip + take).saturating_sub(count).min(batch.len());
+            page.extend_from_slice(&batch[start..end.max(start)]);
+        } else {
+            page.extend_from_slice(&batch);
+        }
+        count += batch.len();
+    };
+    while let Ok(progress) = receiver.recv() {
+        match progress {
+            FilterProgress::Processing(_) => {}
+            FilterProgress::PartialResults { matches, .. } => keep(matches),
+            FilterProgress::Complete { matches, .. } => {
+                keep(matches);
+                break;
+            }
+            FilterProgress::Error(err) => {
+                error = Some(err);
+                break;
+            }
+        }
+    }
+    if !in_order {
+        page.sort_unstable();
+        page.truncate(skip.saturating_add(take).min(page.len()));
+        page.drain(..skip.min(page.len()));
+    }
+    (page, count, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::TabState;
+    use std::io::Write;
+
+    fn log_file(lines: &[&str]) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        for line in lines {
+            writeln!(file, "{}", line).unwrap();
+        }
+        file
+    }
+
+    fn request(json: &str) -> SearchRequest {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn search_groups_matches_by_source_and_pages_across_them() {
+        let a = log_file(&["ERROR one", "ok", "error two"]);
+        let b = log_file(&["fine", "\x1b[31mERROR\x1b[0m three"]);
+        let open = |f: &tempfile::NamedTempFile| TabState::new(f.path().to_path_buf(), false);
+        let mut state = WebState::new(
+            vec![open(&a).unwrap(), open(&b).unwrap()],
+            None,
+            None,
+            None,
+            None,
+            false,
+        );
+
+        let pending = start(
+            &mut state,
+            request(r#"{"pattern":"error","mode":"plain","limit":2}"#),
+        )
+        .ok()
+        .unwrap();
+        let response = pending.collect().ok().unwrap();
+        assert_eq!(response.total_matches, 3);
+        assert_eq!(response.next_offset, Some(2));
+        assert_eq!(response.sources[0].match_count, 2);
+        let lines: Vec<_> = response.sources[0]
+            .rows
+            .iter()
+            .map(|r| (r.line_number, r.content.as_str()))
+            .collect();
+        assert_eq!(lines, vec![(1, "ERROR one"), (3, "error two")]);
+        assert_eq!(response.sources[1].match_count, 1);
+        assert!(response.sources[1].rows.is_empty());
+
+        let response = start(
+            &mut state,
+            request(r#"{"pattern":"ERROR","mode":"regex","case_sensitive":true,"offset":1,"sources":[1]}"#),
+        )
+        .ok()
+        .unwrap()
+        .collect()
+        .ok()
+        .unwrap();
+        assert_eq!(response.sources.len(), 1);
+        assert_eq!(response.sources[0].match_count, 1);
+        assert!(response.sources[0].rows.is_empty());
+        assert_eq!(response.next_offset, None);
+
+        let response = start(
+            &mut state,
+            request(r#"{"pattern":"json | level == \"x\"","mode":"query"}"#),
+        )
+        .ok()
+        .unwrap()
+        .collect()
+        .ok()
+        .unwrap();
+        assert_eq!(response.total_matches, 0);
+    }
+
+    #[test]
+    fn drain_keeps_only_the_page() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        for batch in [vec![1, 2, 3], vec![5, 8], vec![13, 21]] {
+            tx.send(FilterProgress::PartialResults {
+                matches: batch,
+                lines_processed: 0,
+            })
+            .unwrap();
+        }
+        tx.send(FilterProgress::Complete {
+            matches: vec![34],
+            lines_processed: 0,
+        })
+        .unwrap();
+
+        let (page, count, error) = drain(rx, 2, 3, true);
+        assert_eq!(page, vec![3, 5, 8]);
+        assert_eq!(count, 8);
+        assert!(error.is_none());
+    }
+
+    #[test]
+    fn reader_search_pages_in_line_order() {
+        // More lines than one reader batch, so matches arrive newest-first
+        let lines: Vec<String> = (0..2500).map(|i| format!("line {}", i)).collect();
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let a = log_file(&lines);
+        let mut tab = TabState::new(a.path().to_path_buf(), false).unwrap();
+        tab.source.source_path = None;
+        let mut state = WebState::new(vec![tab], None, None, None, None, false);
+
+        let response = start(
+            &mut state,
+            request(r#"{"pattern":"line","mode":"plain","offset":998,"limit":4}"#),
+        )
+        .ok()
+        .unwrap()
+        .collect()
+        .ok()
+        .unwrap();
+        assert_eq!(response.total_matches, 2500);
+        let numbers: Vec<_> = response.sources[0]
+            .rows
+            .iter()
+            .map(|r| r.line_number)
+            .collect();
+        assert_eq!(numbers, vec![999, 1000, 1001, 1002]);
+    }
+
+    #[test]
+    fn newer_search_cancels_the_running_one() {
+        let a = log_file(&["error"]);
+        let tab = TabState::new(a.path().to_path_buf(), false).unwrap();
+        let mut state = WebState::new(vec![tab], None, None, None, None, false);
+
+        let search = r#"{"pattern":"error","mode":"regex"}"#;
+        let first = start(&mut state, request(search)).ok().unwrap();
+        let second = start(&mut state, request(search)).ok().unwrap();
+        assert_eq!(first.collect().err().unwrap().0, 409);
+        assert_eq!(second.collect().ok().unwrap().total_matches, 1);
+
+        let running: Vec<_> = (0..MAX_RUNNING_SEARCHES)
+            .map(|_| start(&mut state, request(search)).ok().unwrap())
+            .collect();
+        assert_eq!(start(&mut state, request(search)).err().unwrap().0, 503);
+        drop(running);
+        assert!(start(&mut state, request(search)).is_ok());
+    }
+
+    #[test]
+    fn invalid_searches_are_rejected() {
+        let a = log_file(&["line"]);
+        let tab = TabState::new(a.path().to_path_buf(), false).unwrap();
+        let mut state = WebState::new(vec![tab], None, None, None, None, false);
+
+        let status = |state: &mut WebState, json: &str| start(state, request(json)).err().unwrap();
+        assert_eq!(
+            status(&mut state, r#"{"pattern":"","mode":"plain"}"#).0,
+            400
+        );
+        assert_eq!(
+            status(&mut state, r#"{"pattern":"(","mode":"regex"}"#).0,
+            400
+        );
+        let (code, message) = status(
+            &mut state,
+            r#"{"pattern":"x","mode":"plain","sources":[7]}"#,
+        );
+        assert_eq!(code, 404);
+        assert!(message.contains('7'), "{}", message);
+    }
+}
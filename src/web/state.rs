@@ -2,21 +2,24 @@
 
 use crate::app::TabState;
 use crate::app::{FilterState, ViewMode};
+use crate::filter::cancel::CancelToken;
 use crate::filter::engine::FilterProgress;
 use crate::filter::FilterMode;
-use crate::filter_orchestrator::FilterOrchestrator;
-use crate::source::{self, SourceLocation, SourceStatus};
+use crate::reader::rotated_reader::RotatedReader;
+use crate::source::{self, format_count, SourceLocation, SourceStatus};
 use crate::watcher::{DirEvent, DirectoryWatcher, FileEvent};
 
+use std::collections::{BTreeSet, HashMap};
 use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
 use std::sync::mpsc::TryRecvError;
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use super::handlers::respond_events;
 use super::{
     filter_state_view, source_status_label, source_type_label, SeverityCountsView, SourceView,
-    SourcesResponse, EVENTS_WAIT_TIMEOUT,
+    SourcesResponse, DEFAULT_REFRESH_INTERVAL, EVENTS_WAIT_TIMEOUT,
 };
 
 pub(super) struct PendingEventRequest {
@@ -26,7 +29,14 @@ pub(super) struct PendingEventRequest {
 }
 
 pub(super) struct WebState {
+    /// Open sources in creation order. Add and remove them through
+    /// [`push_tab`](Self::push_tab) and [`remove_tab`](Self::remove_tab) so
+    /// `tab_ids` stays aligned.
     pub(super) tabs: Vec<TabState>,
+    /// Stable API id of each tab; unlike the position it survives other
+    /// sources being added or closed
+    tab_ids: Vec<usize>,
+    next_tab_id: usize,
     pub(super) dir_watcher: Option<DirectoryWatcher>,
     pub(super) watched_location: Option<SourceLocation>,
     pub(super) project_data_dir: Option<PathBuf>,
@@ -34,6 +44,19 @@ pub(super) struct WebState {
     pub(super) watch_enabled: bool,
     pub(super) revision: u64,
     pub(super) pending_event_requests: Vec<PendingEventRequest>,
+    /// Minimum time between revisions published by [`tick`](Self::tick)
+    pub(super) refresh_interval: Duration,
+    /// Show line counts compactly in source labels (from config)
+    pub(super) compact_numbers: bool,
+    last_published: Option<Instant>,
+    /// Sources changed since the last published revision
+    dirty: BTreeSet<usize>,
+    /// Revision at which each source last changed
+    source_revisions: HashMap<usize, u64>,
+    /// Cancels the running cross-source search; a newer one replaces it
+    pub(super) search_cancel: Option<CancelToken>,
+    /// Cross-source searches being answered
+    pub(super) running_searches: Arc<AtomicUsize>,
 }
 
 impl WebState {
@@ -45,8 +68,11 @@ impl WebState {
         global_data_dir: Option<PathBuf>,
         watch_enabled: bool,
     ) -> Self {
+        let tab_ids = (0..tabs.len()).collect();
         Self {
+            next_tab_id: tabs.len(),
             tabs,
+            tab_ids,
             dir_watcher,
             watched_location,
             project_data_dir,
@@ -54,26 +80,93 @@ impl WebState {
             watch_enabled,
             revision: 1,
             pending_event_requests: Vec::new(),
+            refresh_interval: DEFAULT_REFRESH_INTERVAL,
+            compact_numbers: true,
+            last_published: None,
+            dirty: BTreeSet::new(),
+            source_revisions: HashMap::new(),
+            search_cancel: None,
+            running_searches: Arc::new(AtomicUsize::new(0)),
         }
     }
 
-    pub(super) fn bump_revision(&mut self) {
-        self.revision = self.revision.saturating_add(1);
+    /// Add a tab under a fresh id.
+    pub(super) fn push_tab(&mut self, tab: TabState) {
+        self.tabs.push(tab);
+        self.tab_ids.push(self.next_tab_id);
+        self.dirty.insert(self.next_tab_id);
+        self.next_tab_id += 1;
     }
 
-    pub(super) fn tick(&mut self) {
-        let mut changed = false;
+    /// API ids of the sources, in tab order.
+    pub(super) fn tab_ids(&self) -> &[usize] {
+        &self.tab_ids
+    }
 
-        changed |= self.process_directory_events();
-        changed |= self.process_file_events();
-        changed |= self.process_filter_progress();
-        changed |= self.refresh_source_statuses();
-        self.process_pending_event_requests();
+    /// Position in `tabs` of the source with API id `id`.
+    pub(super) fn tab_position(&self, id: usize) -> Option<usize> {
+        self.tab_ids.iter().position(|&tab_id| tab_id == id)
+    }
+
+    pub(super) fn tab_mut(&mut self, id: usize) -> Option<&mut TabState> {
+        let pos = self.tab_position(id)?;
+        self.tabs.get_mut(pos)
+    }
+
+    pub(super) fn remove_tab(&mut self, id: usize) -> Option<TabState> {
+        let pos = self.tab_position(id)?;
+        self.tab_ids.remove(pos);
+        self.dirty.remove(&id);
+        self.source_revisions.remove(&id);
+        Some(self.tabs.remove(pos))
+    }
+
+    /// Publish a change to source `id` right away, bypassing the refresh
+    /// interval, so user actions show up without delay.
+    pub(super) fn bump_revision(&mut self, id: usize) {
+        if self.tab_position(id).is_some() {
+            self.dirty.insert(id);
+        }
+        self.publish();
+    }
 
-        if changed {
-            self.bump_revision();
-            self.process_pending_event_requests();
+    /// Publish a new revision covering every dirty source.
+    fn publish(&mut self) {
+        self.revision = self.revision.saturating_add(1);
+        for id in std::mem::take(&mut self.dirty) {
+            self.source_revisions.insert(id, self.revision);
         }
+        self.last_published = Some(Instant::now());
+    }
+
+    /// Sources whose content changed after revision `since`, in tab order.
+    pub(super) fn changed_since(&self, since: u64) -> Vec<usize> {
+        self.tab_ids
+            .iter()
+            .copied()
+            .filter(|id| {
+                self.source_revisions
+                    .get(id)
+                    .is_some_and(|&rev| rev > since)
+            })
+            .collect()
+    }
+
+    /// Process pending events, publishing at most one revision per refresh
+    /// interval however many sources changed in between.
+    pub(super) fn tick(&mut self) {
+        self.process_directory_events();
+        self.process_file_events();
+        self.process_filter_progress();
+        self.refresh_source_statuses();
+
+        let due = self
+            .last_published
+            .is_none_or(|at| at.elapsed() >= self.refresh_interval);
+        if !self.dirty.is_empty() && due {
+            self.publish();
+        }
+        self.process_pending_event_requests();
     }
 
     fn process_pending_event_requests(&mut self) {
@@ -84,9 +177,10 @@ impl WebState {
         let now = Instant::now();
         let mut remaining = Vec::with_capacity(self.pending_event_requests.len());
 
-        for pending in self.pending_event_requests.drain(..) {
+        for pending in std::mem::take(&mut self.pending_event_requests) {
             if self.revision > pending.since {
-                respond_events(pending.request, Some(self.revision));
+                let changed = self.changed_since(pending.since);
+                respond_events(pending.request, Some((self.revision, changed)));
                 continue;
             }
 
@@ -100,14 +194,14 @@ impl WebState {
         self.pending_event_requests = remaining;
     }
 
-    fn process_directory_events(&mut self) -> bool {
+    fn process_directory_events(&mut self) {
         let Some(ref watcher) = self.dir_watcher else {
-            return false;
+            return;
         };
 
-        let mut changed = false;
+        let events: Vec<_> = std::iter::from_fn(|| watcher.try_recv()).collect();
 
-        while let Some(event) = watcher.try_recv() {
+        for event in events {
             match event {
                 DirEvent::NewFile(path) => {
                     let already_open = self
@@ -139,9 +233,10 @@ impl WebState {
                             discovered,
                             self.watch_enabled,
                             Vec::new(),
+                            None,
+                            None,
                         ) {
-                            self.tabs.push(tab);
-                            changed = true;
+                            self.push_tab(tab);
                         }
                     }
                 }
@@ -150,14 +245,10 @@ impl WebState {
                 }
             }
         }
-
-        changed
     }
 
-    fn process_file_events(&mut self) -> bool {
-        let mut changed = false;
-
-        for tab in &mut self.tabs {
+    fn process_file_events(&mut self) {
+        for (tab, &id) in self.tabs.iter_mut().zip(&self.tab_ids) {
             // Drain all pending events — only reload once per cycle.
             let mut has_modified = false;
             if let Some(ref watcher) = tab.watcher {
@@ -171,6 +262,13 @@ impl WebState {
                 }
             }
 
+            // A rotated file keeps its name but not its inode; the watcher
+            // stays on the old inode, so detect replacement explicitly.
+            let rotated = tab.file_was_replaced();
+            if rotated {
+                has_modified = true;
+            }
+
             // Fallback: check file size directly if watcher didn't fire.
             // Catches cases where the OS file watcher misses events (macOS FSEvents).
             if !has_modified {
@@ -196,6 +294,11 @@ impl WebState {
                     continue;
                 }
 
+                // A rotated reader renumbers its lines when archives rotate away
+                let reset = reader
+                    .as_any_mut()
+                    .downcast_mut::<RotatedReader>()
+                    .is_some_and(RotatedReader::take_reset);
                 let new_total = reader.total_lines();
                 drop(reader);
 
@@ -204,9 +307,15 @@ impl WebState {
                     tab.source.file_size = std::fs::metadata(path).map(|m| m.len()).ok();
                 }
 
+                if rotated || reset {
+                    tab.reattach_after_rotation(new_total);
+                    self.dirty.insert(id);
+                    continue;
+                }
+
                 if new_total < old_total {
                     tab.reset_after_truncation(new_total);
-                    changed = true;
+                    self.dirty.insert(id);
                     continue;
                 }
 
@@ -214,37 +323,25 @@ impl WebState {
                 if tab.source.mode == ViewMode::Normal {
                     let old = tab.source.line_indices.len();
                     if new_total > old {
-                        tab.source.line_indices.extend(old..new_total);
+                        tab.source.line_indices.extend_range(old..new_total);
                     }
                 }
 
-                if let Some(pattern) = tab.source.filter.pattern.clone() {
-                    if new_total > tab.source.filter.last_filtered_line {
-                        let mode = tab.source.filter.mode;
-                        let range = Some((tab.source.filter.last_filtered_line, new_total));
-                        if let Err(e) =
-                            FilterOrchestrator::trigger(&mut tab.source, pattern, mode, range)
-                        {
-                            eprintln!("Web incremental filter error: {}", e);
-                        }
-                    }
-                }
+                // A run in flight is left alone; the lines it doesn't
+                // cover are caught up on once it completes
+                tab.catch_up_filter();
 
                 if tab.source.follow_mode {
                     tab.jump_to_end();
                 }
 
-                changed = true;
+                self.dirty.insert(id);
             }
         }
-
-        changed
     }
 
-    fn process_filter_progress(&mut self) -> bool {
-        let mut changed = false;
-
-        for tab in &mut self.tabs {
+    fn process_filter_progress(&mut self) {
+        for (tab, &id) in self.tabs.iter_mut().zip(&self.tab_ids) {
             loop {
                 let recv_result = {
                     let Some(rx) = tab.source.filter.receiver.as_ref() else {
@@ -256,14 +353,14 @@ impl WebState {
                 match recv_result {
                     Ok(FilterProgress::Processing(lines_processed)) => {
                         tab.source.filter.state = FilterState::Processing { lines_processed };
-                        changed = true;
+                        self.dirty.insert(id);
                     }
                     Ok(FilterProgress::PartialResults {
                         matches,
                         lines_processed,
                     }) => {
                         tab.merge_partial_filter_results(matches, lines_processed);
-                        changed = true;
+                        self.dirty.insert(id);
                     }
                     Ok(FilterProgress::Complete {
                         matches,
@@ -281,49 +378,49 @@ impl WebState {
                         }
 
                         tab.source.filter.receiver = None;
-                        changed = true;
+                        // Lines added while it ran
+                        tab.catch_up_filter();
+                        self.dirty.insert(id);
+                        break;
                     }
                     Ok(FilterProgress::Error(err)) => {
                         eprintln!("[web] Filter error for '{}': {}", tab.source.name, err);
                         tab.source.filter.state = FilterState::Inactive;
                         tab.source.filter.receiver = None;
-                        changed = true;
+                        self.dirty.insert(id);
                     }
                     Err(TryRecvError::Empty) => break,
                     Err(TryRecvError::Disconnected) => {
                         tab.source.filter.receiver = None;
                         if matches!(tab.source.filter.state, FilterState::Processing { .. }) {
                             tab.source.filter.state = FilterState::Inactive;
-                            changed = true;
+                            self.dirty.insert(id);
                         }
                         break;
                     }
                 }
             }
         }
-
-        changed
     }
 
-    fn refresh_source_statuses(&mut self) -> bool {
-        let mut changed = false;
-        for tab in &mut self.tabs {
+    fn refresh_source_statuses(&mut self) {
+        for (tab, &id) in self.tabs.iter_mut().zip(&self.tab_ids) {
             let before = tab.source.source_status;
             tab.refresh_source_status();
             if tab.source.source_status != before {
-                changed = true;
+                self.dirty.insert(id);
             }
         }
-        changed
     }
 
     pub(super) fn as_sources_response(&self) -> SourcesResponse {
         let sources =
             self.tabs
                 .iter()
-                .enumerate()
-                .map(|(id, tab)| SourceView {
+                .zip(&self.tab_ids)
+                .map(|(tab, &id)| SourceView {
                     id,
+                    revision: self.source_revisions.get(&id).copied().unwrap_or(0),
                     name: tab.source.name.clone(),
                     category: source_type_label(tab.source_type()),
                     disabled: tab.source.disabled,
@@ -331,6 +428,11 @@ impl WebState {
                     source_status: tab.source.source_status.map(source_status_label),
                     total_lines: tab.source.total_lines,
                     visible_lines: tab.source.line_indices.len(),
+                    total_lines_label: format_count(tab.source.total_lines, self.compact_numbers),
+                    visible_lines_label: format_count(
+                        tab.source.line_indices.len(),
+                        self.compact_numbers,
+                    ),
                     filter_pattern: tab.source.filter.pattern.clone(),
                     filter_mode: match tab.source.filter.mode {
                         FilterMode::Plain { .. } => "plain",
@@ -339,6 +441,7 @@ impl WebState {
                     },
                     case_sensitive: tab.source.filter.mode.is_case_sensitive(),
                     filter_state: filter_state_view(tab.source.filter.state),
+                    filter_pending_lines: tab.source.unfiltered_new_lines(),
                     can_delete_ended: tab.source.source_status == Some(SourceStatus::Ended)
                         && tab.source.source_path.as_ref().is_some_and(|path| {
                             self.is_under_data_roots(path) && !tab.source.disabled
@@ -379,3 +482,113 @@ pub(super) fn lock_state(shared: &Arc<Mutex<WebState>>) -> std::sync::MutexGuard
         Err(poisoned) => poisoned.into_inner(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[cfg(unix)]
+    #[test]
+    fn rotated_capture_file_is_reattached() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("api.log");
+        fs::write(&path, "old 1\nold 2\nold 3\n").unwrap();
+
+        let tab = TabState::new(path.clone(), false).unwrap();
+        let mut state = WebState::new(vec![tab], None, None, None, None, false);
+        let revision = state.revision;
+
+        // Rotate: new file with the same size renamed over the old one
+        let rotated = dir.path().join("api.log.new");
+        fs::write(&rotated, "new 1\nnew 2\nnew 3\n").unwrap();
+        fs::rename(&rotated, &path).unwrap();
+
+        state.tick();
+
+        assert!(state.revision > revision);
+        let tab = &mut state.tabs[0];
+        assert!(!tab.file_was_replaced());
+        assert_eq!(tab.source.total_lines, 3);
+        let mut reader = tab.source.reader.lock().unwrap();
+        assert_eq!(reader.get_line(0).unwrap().unwrap(), "new 1");
+    }
+
+    #[test]
+    fn source_ids_survive_removal_and_addition() {
+        let files: Vec<_> = (0..3)
+            .map(|_| tempfile::NamedTempFile::new().unwrap())
+            .collect();
+        let open = |i: usize| TabState::new(files[i].path().to_path_buf(), false).unwrap();
+        let mut state = WebState::new(vec![open(0), open(1)], None, None, None, None, false);
+
+        assert!(state.remove_tab(0).is_some());
+        state.push_tab(open(2));
+
+        let ids: Vec<_> = state
+            .as_sources_response()
+            .sources
+            .iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(
+            state.tab_mut(2).unwrap().source.source_path.as_deref(),
+            Some(files[2].path())
+        );
+        assert!(state.tab_mut(0).is_none());
+    }
+
+    #[test]
+    fn background_changes_are_coalesced_per_refresh_interval() {
+        let files: Vec<_> = (0..3)
+            .map(|_| tempfile::NamedTempFile::new().unwrap())
+            .collect();
+        let open = |i: usize| TabState::new(files[i].path().to_path_buf(), false).unwrap();
+        let mut state = WebState::new(
+            vec![open(0), open(1), open(2)],
+            None,
+            None,
+            None,
+            None,
+            false,
+        );
+        state.refresh_interval = Duration::from_secs(3600);
+        let append = |i: usize| {
+            let mut file = fs::OpenOptions::new()
+                .append(true)
+                .open(files[i].path())
+                .unwrap();
+            std::io::Write::write_all(&mut file, b"line\n").unwrap();
+        };
+
+        // Nothing published yet: the first change goes out right away
+        append(0);
+        state.tick();
+        assert_eq!(state.revision, 2);
+        assert_eq!(state.changed_since(1), vec![0]);
+
+        // Later changes wait for the interval
+        append(2);
+        state.tick();
+        append(0);
+        state.tick();
+        assert_eq!(state.revision, 2);
+
+        // A user action publishes at once, along with the pending changes
+        state.bump_revision(1);
+        assert_eq!(state.revision, 3);
+        assert_eq!(state.changed_since(2), vec![0, 1, 2]);
+        assert_eq!(state.changed_since(3), Vec::<usize>::new());
+        let revisions: Vec<_> = state
+            .as_sources_response()
+            .sources
+            .iter()
+            .map(|s| s.revision)
+            .collect();
+        assert_eq!(revisions, vec![3, 3, 3]);
+
+        state.tick();
+        assert_eq!(state.revision, 3);
+    }
+}